@@ -25,6 +25,7 @@
 //! // Construct: let x = 42 in x + 1
 //! let expr = Expr::Let {
 //!     name: "x".to_string(),
+//!     ty: None,
 //!     value: Box::new(Expr::Lit(Literal::Int(42))),
 //!     body: Box::new(Expr::BinOp {
 //!         op: BinOp::Add,
@@ -34,6 +35,8 @@
 //! };
 //! ```
 
+use crate::span::Span;
+use std::collections::HashMap;
 use std::fmt;
 
 /// Literal values in the AST.
@@ -49,6 +52,10 @@ pub enum Literal {
     Bool(bool),
     /// String literal (e.g., "hello")
     Str(String),
+    /// Character literal (e.g., 'a')
+    Char(char),
+    /// Byte string literal (e.g., "GET"B)
+    Bytes(Vec<u8>),
     /// Unit value (equivalent to () in F#)
     Unit,
 }
@@ -60,6 +67,8 @@ impl fmt::Display for Literal {
             Literal::Float(n) => write!(f, "{}", n),
             Literal::Bool(b) => write!(f, "{}", b),
             Literal::Str(s) => write!(f, "\"{}\"", s),
+            Literal::Char(c) => write!(f, "{:?}", c),
+            Literal::Bytes(b) => write!(f, "\"{}\"B", b.escape_ascii()),
             Literal::Unit => write!(f, "()"),
         }
     }
@@ -79,6 +88,8 @@ pub enum BinOp {
     Mul,
     /// Division (/)
     Div,
+    /// Remainder (%)
+    Mod,
     /// String concatenation (++)
     Concat,
 
@@ -101,6 +112,24 @@ pub enum BinOp {
     And,
     /// Logical OR (||)
     Or,
+
+    // Bitwise operators (int only)
+    /// Bitwise AND (&&&)
+    BitAnd,
+    /// Bitwise OR (|||)
+    BitOr,
+    /// Bitwise exclusive OR (^^^)
+    BitXor,
+    /// Left shift (<<<)
+    Shl,
+    /// Arithmetic right shift (>>>)
+    Shr,
+
+    // Function composition
+    /// Forward composition (>>): `f >> g` is `fun x -> g (f x)`
+    ComposeRight,
+    /// Backward composition (<<): `f << g` is `fun x -> f (g x)`
+    ComposeLeft,
 }
 
 impl fmt::Display for BinOp {
@@ -110,6 +139,7 @@ impl fmt::Display for BinOp {
             BinOp::Sub => "-",
             BinOp::Mul => "*",
             BinOp::Div => "/",
+            BinOp::Mod => "%",
             BinOp::Concat => "++",
             BinOp::Eq => "=",
             BinOp::Neq => "<>",
@@ -119,6 +149,13 @@ impl fmt::Display for BinOp {
             BinOp::Gte => ">=",
             BinOp::And => "&&",
             BinOp::Or => "||",
+            BinOp::BitAnd => "&&&",
+            BinOp::BitOr => "|||",
+            BinOp::BitXor => "^^^",
+            BinOp::Shl => "<<<",
+            BinOp::Shr => ">>>",
+            BinOp::ComposeRight => ">>",
+            BinOp::ComposeLeft => "<<",
         };
         write!(f, "{}", s)
     }
@@ -127,7 +164,10 @@ impl fmt::Display for BinOp {
 impl BinOp {
     /// Returns true if this is an arithmetic operator.
     pub fn is_arithmetic(&self) -> bool {
-        matches!(self, BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div)
+        matches!(
+            self,
+            BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Mod
+        )
     }
 
     /// Returns true if this is a comparison operator.
@@ -142,6 +182,35 @@ impl BinOp {
     pub fn is_logical(&self) -> bool {
         matches!(self, BinOp::And | BinOp::Or)
     }
+
+    /// Returns true if this is a bitwise or shift operator.
+    pub fn is_bitwise(&self) -> bool {
+        matches!(
+            self,
+            BinOp::BitAnd | BinOp::BitOr | BinOp::BitXor | BinOp::Shl | BinOp::Shr
+        )
+    }
+
+    /// Returns true if this is a function composition operator.
+    pub fn is_composition(&self) -> bool {
+        matches!(self, BinOp::ComposeRight | BinOp::ComposeLeft)
+    }
+
+    /// The name of the static member a type defines to overload this
+    /// operator, such as `op_Addition` for `+`.
+    ///
+    /// Only the arithmetic operators can be overloaded; the ordering
+    /// operators use a type's `Compare` member instead.
+    pub fn member_name(&self) -> Option<&'static str> {
+        match self {
+            BinOp::Add => Some("op_Addition"),
+            BinOp::Sub => Some("op_Subtraction"),
+            BinOp::Mul => Some("op_Multiply"),
+            BinOp::Div => Some("op_Division"),
+            BinOp::Mod => Some("op_Modulus"),
+            _ => None,
+        }
+    }
 }
 
 /// Type expressions for record field type annotations.
@@ -155,12 +224,91 @@ pub enum TypeExpr {
     Tuple(Vec<TypeExpr>),
     /// Function type (e.g., int -> string)
     Function(Box<TypeExpr>, Box<TypeExpr>),
+    /// Type variable, stored without its quote (e.g., 'a is Var("a"))
+    Var(String),
+    /// Applied generic type (e.g., Tree<int>, or `int list` as App("list", [int]))
+    App(String, Vec<TypeExpr>),
+    /// Unit of measure, the argument of `float<m/s>` or `int<beat>`: named
+    /// units and unit variables (`Named` and `Var`) with their exponents.
+    /// The empty product is the dimensionless unit `1`.
+    Measure(Vec<(TypeExpr, i32)>),
+}
+
+impl TypeExpr {
+    /// Collect the names of the type variables used in this type, in order of appearance.
+    pub fn type_vars(&self) -> Vec<&str> {
+        let mut vars = Vec::new();
+        self.collect_type_vars(&mut vars);
+        vars
+    }
+
+    fn collect_type_vars<'a>(&'a self, vars: &mut Vec<&'a str>) {
+        match self {
+            TypeExpr::Named(_) => {}
+            TypeExpr::Var(name) => {
+                if !vars.contains(&name.as_str()) {
+                    vars.push(name);
+                }
+            }
+            TypeExpr::Tuple(types) | TypeExpr::App(_, types) => {
+                for ty in types {
+                    ty.collect_type_vars(vars);
+                }
+            }
+            TypeExpr::Function(arg, ret) => {
+                arg.collect_type_vars(vars);
+                ret.collect_type_vars(vars);
+            }
+            TypeExpr::Measure(factors) => {
+                for (factor, _) in factors {
+                    factor.collect_type_vars(vars);
+                }
+            }
+        }
+    }
+}
+
+/// Write a `<'a, 'b>` type parameter list (nothing when there are no parameters).
+fn fmt_type_params(f: &mut fmt::Formatter<'_>, params: &[String]) -> fmt::Result {
+    if params.is_empty() {
+        return Ok(());
+    }
+    write!(f, "<")?;
+    for (i, param) in params.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "'{}", param)?;
+    }
+    write!(f, ">")
 }
 
 impl fmt::Display for TypeExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TypeExpr::Named(name) => write!(f, "{}", name),
+            TypeExpr::Var(name) => write!(f, "'{}", name),
+            // Built-in generics use the postfix form: int list, 'a option
+            TypeExpr::App(name, args)
+                if args.len() == 1 && matches!(name.as_str(), "list" | "option" | "array") =>
+            {
+                match &args[0] {
+                    arg @ (TypeExpr::Tuple(_) | TypeExpr::Function(_, _)) => {
+                        write!(f, "({}) {}", arg, name)
+                    }
+                    arg => write!(f, "{} {}", arg, name),
+                }
+            }
+            TypeExpr::App(name, args) => {
+                write!(f, "{}<", name)?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ">")
+            }
             TypeExpr::Tuple(types) => {
                 for (i, ty) in types.iter().enumerate() {
                     if i > 0 {
@@ -171,10 +319,73 @@ impl fmt::Display for TypeExpr {
                 Ok(())
             }
             TypeExpr::Function(arg, ret) => write!(f, "{} -> {}", arg, ret),
+            TypeExpr::Measure(factors) => fmt_measure(f, factors),
         }
     }
 }
 
+/// Write a unit of measure F#-style: `m/s^2`, `1/s`, or `1` when dimensionless.
+pub(crate) fn fmt_measure<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    factors: &[(T, i32)],
+) -> fmt::Result {
+    let numerator: Vec<_> = factors.iter().filter(|(_, exp)| *exp > 0).collect();
+    let denominator: Vec<_> = factors.iter().filter(|(_, exp)| *exp < 0).collect();
+    let write_factors = |f: &mut fmt::Formatter<'_>, factors: &[&(T, i32)]| {
+        for (i, (unit, exp)) in factors.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            match exp.abs() {
+                1 => write!(f, "{}", unit)?,
+                n => write!(f, "{}^{}", unit, n)?,
+            }
+        }
+        Ok(())
+    };
+
+    if numerator.is_empty() {
+        write!(f, "1")?;
+    } else {
+        write_factors(f, &numerator)?;
+    }
+    if !denominator.is_empty() {
+        write!(f, "/")?;
+        if denominator.len() > 1 {
+            write!(f, "(")?;
+            write_factors(f, &denominator)?;
+            write!(f, ")")?;
+        } else {
+            write_factors(f, &denominator)?;
+        }
+    }
+    Ok(())
+}
+
+/// A type annotation written in the source, such as the `int` in `(x: int)`.
+///
+/// The span covers the type expression so type errors can point at it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAnnotation {
+    /// The annotated type
+    pub ty: TypeExpr,
+    /// Location of the type expression
+    pub span: Span,
+}
+
+impl TypeAnnotation {
+    /// Create a new annotation
+    pub fn new(ty: TypeExpr, span: Span) -> Self {
+        TypeAnnotation { ty, span }
+    }
+}
+
+impl fmt::Display for TypeAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ty)
+    }
+}
+
 /// Record type definition.
 ///
 /// Represents a user-defined record type with named fields.
@@ -183,23 +394,77 @@ impl fmt::Display for TypeExpr {
 pub struct RecordTypeDef {
     /// Name of the record type
     pub name: String,
+    /// Type parameters, without their quotes (e.g., ["t"] for Page<'t>)
+    pub type_params: Vec<String>,
     /// Field definitions: (field_name, field_type)
     pub fields: Vec<(String, TypeExpr)>,
+    /// Members declared in the `with` block
+    pub members: Vec<MemberDef>,
 }
 
 impl fmt::Display for RecordTypeDef {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "type {} = {{ ", self.name)?;
+        write!(f, "type {}", self.name)?;
+        fmt_type_params(f, &self.type_params)?;
+        write!(f, " = {{ ")?;
         for (i, (field_name, field_type)) in self.fields.iter().enumerate() {
             if i > 0 {
                 write!(f, "; ")?;
             }
             write!(f, "{}: {}", field_name, field_type)?;
         }
-        write!(f, " }}")
+        write!(f, " }}")?;
+        fmt_members(f, &self.members)
+    }
+}
+
+/// A member declared in the `with` block of a type definition.
+///
+/// Examples:
+/// - `static member (+) (a: Vector2, b: Vector2) = ...`
+/// - `static member Default = { Port = 80 }`
+/// - `member this.Area() = ...`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberDef {
+    /// Name of the member; operators use their .NET names, such as
+    /// `op_Addition` for `(+)`
+    pub name: String,
+    /// Name an instance member gives its receiver, such as `this`;
+    /// `None` for a static member
+    pub self_name: Option<String>,
+    /// The member's value, with its parameters desugared into nested lambdas.
+    /// An instance member takes the receiver as its first parameter.
+    pub value: Expr,
+}
+
+impl MemberDef {
+    /// Whether the member belongs to the type rather than to its values
+    pub fn is_static(&self) -> bool {
+        self.self_name.is_none()
+    }
+}
+
+impl fmt::Display for MemberDef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.self_name {
+            Some(self_name) => write!(f, "member {}.{} = {}", self_name, self.name, self.value),
+            None => write!(f, "static member {} = {}", self.name, self.value),
+        }
     }
 }
 
+/// Format the `with` block of a type definition, if it declares members
+fn fmt_members(f: &mut fmt::Formatter<'_>, members: &[MemberDef]) -> fmt::Result {
+    if members.is_empty() {
+        return Ok(());
+    }
+    write!(f, " with")?;
+    for member in members {
+        write!(f, " {}", member)?;
+    }
+    Ok(())
+}
+
 /// Variant definition in a discriminated union.
 ///
 /// Represents a single case/variant in a DU.
@@ -261,20 +526,26 @@ impl VariantDef {
 pub struct DuTypeDef {
     /// Name of the DU type
     pub name: String,
+    /// Type parameters, without their quotes (e.g., ["a"] for Tree<'a>)
+    pub type_params: Vec<String>,
     /// Variants/cases of this DU
     pub variants: Vec<VariantDef>,
+    /// Members declared in the `with` block
+    pub members: Vec<MemberDef>,
 }
 
 impl fmt::Display for DuTypeDef {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "type {} = ", self.name)?;
+        write!(f, "type {}", self.name)?;
+        fmt_type_params(f, &self.type_params)?;
+        write!(f, " = ")?;
         for (i, variant) in self.variants.iter().enumerate() {
             if i > 0 {
                 write!(f, " | ")?;
             }
             write!(f, "{}", variant)?;
         }
-        Ok(())
+        fmt_members(f, &self.members)
     }
 }
 
@@ -353,6 +624,19 @@ pub enum TypeDefinition {
     Du(DuTypeDef),
     /// Type provider declaration (generates types from external schemas)
     Provider(TypeProviderDecl),
+    /// Unit of measure declaration
+    Measure(MeasureDef),
+}
+
+impl TypeDefinition {
+    /// The members declared in the definition's `with` block
+    pub fn members(&self) -> &[MemberDef] {
+        match self {
+            TypeDefinition::Record(r) => &r.members,
+            TypeDefinition::Du(du) => &du.members,
+            TypeDefinition::Provider(_) | TypeDefinition::Measure(_) => &[],
+        }
+    }
 }
 
 impl fmt::Display for TypeDefinition {
@@ -361,10 +645,34 @@ impl fmt::Display for TypeDefinition {
             TypeDefinition::Record(r) => write!(f, "{}", r),
             TypeDefinition::Du(du) => write!(f, "{}", du),
             TypeDefinition::Provider(p) => write!(f, "{}", p),
+            TypeDefinition::Measure(m) => write!(f, "{}", m),
         }
     }
 }
 
+/// Unit of measure declaration.
+///
+/// Examples:
+/// - `[<Measure>] type ms` declares a base unit
+/// - `[<Measure>] type Hz = 1/s` abbreviates another unit
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeasureDef {
+    /// Name of the unit
+    pub name: String,
+    /// The unit it abbreviates (a `TypeExpr::Measure`), if any
+    pub definition: Option<TypeExpr>,
+}
+
+impl fmt::Display for MeasureDef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[<Measure>] type {}", self.name)?;
+        if let Some(definition) = &self.definition {
+            write!(f, " = {}", definition)?;
+        }
+        Ok(())
+    }
+}
+
 /// Top-level declaration in a module.
 ///
 /// Represents declarations that can appear at the top level of a module.
@@ -375,7 +683,10 @@ pub enum Declaration {
     /// Let-binding declaration
     LetBinding {
         name: String,
-        params: Vec<String>,
+        /// Parameters with their optional annotations: `x` or `(x: int)`
+        params: Vec<(String, Option<TypeAnnotation>)>,
+        /// Annotated result type (or value type when there are no parameters)
+        return_ty: Option<TypeAnnotation>,
         body: Box<Expr>,
     },
 }
@@ -384,10 +695,21 @@ impl fmt::Display for Declaration {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Declaration::TypeDef(typedef) => write!(f, "{}", typedef),
-            Declaration::LetBinding { name, params, body } => {
+            Declaration::LetBinding {
+                name,
+                params,
+                return_ty,
+                body,
+            } => {
                 write!(f, "let {}", name)?;
-                for param in params {
-                    write!(f, " {}", param)?;
+                for (param, ty) in params {
+                    match ty {
+                        Some(ty) => write!(f, " ({}: {})", param, ty)?,
+                        None => write!(f, " {}", param)?,
+                    }
+                }
+                if let Some(ty) = return_ty {
+                    write!(f, " : {}", ty)?;
                 }
                 write!(f, " = {}", body)
             }
@@ -418,8 +740,8 @@ impl fmt::Display for Module {
 
 /// Pattern in a match expression.
 ///
-/// Patterns can match literals, variables, wildcards, tuples, and DU variants.
-/// Issue #27 supports basic patterns; Issue #28 will add lists/arrays.
+/// Patterns can match literals, variables, wildcards, tuples, DU variants,
+/// lists, arrays and records, and can be combined with `|` and `as`.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Pattern {
     /// Wildcard pattern (_) - matches anything
@@ -437,6 +759,65 @@ pub enum Pattern {
         /// Nested patterns for variant fields (empty for simple variants)
         patterns: Vec<Pattern>,
     },
+    /// List pattern ([p1; p2], []) - matches lists of exactly this length
+    List(Vec<Pattern>),
+    /// Cons pattern (head :: tail) - matches non-empty lists
+    Cons {
+        /// Pattern for the first element
+        head: Box<Pattern>,
+        /// Pattern for the rest of the list
+        tail: Box<Pattern>,
+    },
+    /// Array pattern ([| p1; p2 |]) - matches arrays of exactly this length
+    Array(Vec<Pattern>),
+    /// Record pattern ({ Name = n; Age = a }) - matches the listed fields
+    Record(Vec<(String, Pattern)>),
+    /// Or-pattern (p1 | p2) - matches if any alternative matches
+    Or(Vec<Pattern>),
+    /// As-pattern (p as name) - matches p and also binds the whole value
+    As {
+        /// The pattern the value must match
+        pattern: Box<Pattern>,
+        /// Variable bound to the whole value
+        name: String,
+    },
+    /// Active pattern case (Even, Int n) - applies an active pattern
+    /// function to the value and matches its result
+    Active {
+        /// The active pattern function (e.g., "|Even|Odd|", "|Int|_|")
+        function: String,
+        /// The case being matched (e.g., "Even", "Int")
+        case: String,
+        /// Nested patterns for the case's result (empty to ignore it)
+        patterns: Vec<Pattern>,
+    },
+}
+
+/// Split an active pattern name such as `|Even|Odd|` or `|Int|_|` into its
+/// case names, and whether the pattern is partial (ends in `_`).
+///
+/// Returns `None` if `name` does not name an active pattern.
+pub fn active_pattern_cases(name: &str) -> Option<(Vec<&str>, bool)> {
+    let inner = name.strip_prefix('|')?.strip_suffix('|')?;
+    let mut cases: Vec<&str> = inner.split('|').collect();
+    let partial = cases.last() == Some(&"_");
+    if partial {
+        cases.pop();
+    }
+    if cases.is_empty() || cases.iter().any(|case| case.is_empty()) {
+        return None;
+    }
+    Some((cases, partial))
+}
+
+/// The patterns written after an active pattern case, as a single pattern
+/// for the value the case carries: none ignore it, several form a tuple.
+pub fn active_result_pattern(patterns: &[Pattern]) -> Pattern {
+    match patterns {
+        [] => Pattern::Wildcard,
+        [single] => single.clone(),
+        _ => Pattern::Tuple(patterns.to_vec()),
+    }
 }
 
 impl fmt::Display for Pattern {
@@ -469,6 +850,66 @@ impl fmt::Display for Pattern {
                 }
                 Ok(())
             }
+            Pattern::List(patterns) => {
+                write!(f, "[")?;
+                for (i, pat) in patterns.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}", pat)?;
+                }
+                write!(f, "]")
+            }
+            Pattern::Cons { head, tail } => match head.as_ref() {
+                Pattern::Cons { .. } | Pattern::Or(_) | Pattern::As { .. } => {
+                    write!(f, "({}) :: {}", head, tail)
+                }
+                _ => write!(f, "{} :: {}", head, tail),
+            },
+            Pattern::Array(patterns) => {
+                write!(f, "[|")?;
+                for (i, pat) in patterns.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}", pat)?;
+                }
+                write!(f, "|]")
+            }
+            Pattern::Record(fields) => {
+                write!(f, "{{ ")?;
+                for (i, (name, pat)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{} = {}", name, pat)?;
+                }
+                write!(f, " }}")
+            }
+            Pattern::Or(alternatives) => {
+                for (i, pat) in alternatives.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " | ")?;
+                    }
+                    write!(f, "{}", pat)?;
+                }
+                Ok(())
+            }
+            Pattern::As { pattern, name } => write!(f, "{} as {}", pattern, name),
+            Pattern::Active { case, patterns, .. } => {
+                write!(f, "{}", case)?;
+                if !patterns.is_empty() {
+                    write!(f, "(")?;
+                    for (i, pat) in patterns.iter().enumerate() {
+                        if i > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{}", pat)?;
+                    }
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -530,22 +971,72 @@ impl Pattern {
             _ => None,
         }
     }
+
+    /// Returns the variables bound by this pattern, in source order.
+    ///
+    /// For an or-pattern only the first alternative is consulted, since every
+    /// alternative must bind the same set of variables.
+    pub fn bound_vars(&self) -> Vec<&str> {
+        let mut vars = Vec::new();
+        self.collect_vars(&mut vars);
+        vars
+    }
+
+    fn collect_vars<'a>(&'a self, vars: &mut Vec<&'a str>) {
+        match self {
+            Pattern::Wildcard | Pattern::Literal(_) => {}
+            Pattern::Var(name) => vars.push(name),
+            Pattern::Tuple(patterns)
+            | Pattern::Variant { patterns, .. }
+            | Pattern::Active { patterns, .. }
+            | Pattern::List(patterns)
+            | Pattern::Array(patterns) => {
+                for pat in patterns {
+                    pat.collect_vars(vars);
+                }
+            }
+            Pattern::Cons { head, tail } => {
+                head.collect_vars(vars);
+                tail.collect_vars(vars);
+            }
+            Pattern::Record(fields) => {
+                for (_, pat) in fields {
+                    pat.collect_vars(vars);
+                }
+            }
+            Pattern::Or(alternatives) => {
+                if let Some(first) = alternatives.first() {
+                    first.collect_vars(vars);
+                }
+            }
+            Pattern::As { pattern, name } => {
+                pattern.collect_vars(vars);
+                vars.push(name);
+            }
+        }
+    }
 }
 
 /// Match arm in a match expression.
 ///
-/// Each arm consists of a pattern and the body expression to evaluate if the pattern matches.
+/// Each arm consists of a pattern, an optional `when` guard, and the body
+/// expression to evaluate if the pattern matches and the guard holds.
 #[derive(Debug, Clone, PartialEq)]
 pub struct MatchArm {
     /// The pattern to match against
     pub pattern: Pattern,
     /// The expression to evaluate if the pattern matches
     pub body: Box<Expr>,
+    /// Optional guard (`when cond`); the arm is skipped if it evaluates to false
+    pub guard: Option<Box<Expr>>,
 }
 
 impl fmt::Display for MatchArm {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} -> {}", self.pattern, self.body)
+        match &self.guard {
+            Some(guard) => write!(f, "{} when {} -> {}", self.pattern, guard, self.body),
+            None => write!(f, "{} -> {}", self.pattern, self.body),
+        }
     }
 }
 
@@ -555,9 +1046,24 @@ impl MatchArm {
         MatchArm {
             pattern,
             body: Box::new(body),
+            guard: None,
+        }
+    }
+
+    /// Create a new match arm with a `when` guard.
+    pub fn with_guard(pattern: Pattern, guard: Expr, body: Expr) -> Self {
+        MatchArm {
+            pattern,
+            body: Box::new(body),
+            guard: Some(Box::new(guard)),
         }
     }
 
+    /// Returns true if this arm has a `when` guard.
+    pub fn has_guard(&self) -> bool {
+        self.guard.is_some()
+    }
+
     /// Returns true if this arm's pattern is a wildcard.
     pub fn is_wildcard(&self) -> bool {
         self.pattern.is_wildcard()
@@ -616,6 +1122,15 @@ pub enum Expr {
     /// Literal value
     Lit(Literal),
 
+    /// Numeric literal with a unit of measure (e.g., 500.0<ms>, 120<beat/min>).
+    /// The unit only exists for type checking; it compiles to the plain literal.
+    MeasuredLit {
+        /// The number
+        value: Literal,
+        /// Its unit (a `TypeExpr::Measure`)
+        measure: TypeExpr,
+    },
+
     /// Binary operation (e.g., x + 1, a && b)
     BinOp {
         op: BinOp,
@@ -626,6 +1141,8 @@ pub enum Expr {
     /// Let-binding (e.g., let x = 5 in x + 1)
     Let {
         name: String,
+        /// Annotated type of the value (e.g., let x: int = 42 in x)
+        ty: Option<TypeAnnotation>,
         value: Box<Expr>,
         body: Box<Expr>,
     },
@@ -637,6 +1154,16 @@ pub enum Expr {
         body: Box<Expr>,
     },
 
+    /// Mutable let-binding (e.g., let mutable count = 0 in count <- count + 1)
+    LetMutable {
+        name: String,
+        value: Box<Expr>,
+        body: Box<Expr>,
+    },
+
+    /// Assignment to a mutable variable (e.g., count <- count + 1)
+    Assign { name: String, value: Box<Expr> },
+
     /// Mutually recursive let-bindings (e.g., let rec even n = ... and odd n = ...)
     LetRecMutual {
         bindings: Vec<(String, Expr)>,
@@ -644,7 +1171,14 @@ pub enum Expr {
     },
 
     /// Lambda function (e.g., fun x -> x + 1)
-    Lambda { param: String, body: Box<Expr> },
+    Lambda {
+        param: String,
+        /// Annotated parameter type (e.g., fun (x: int) -> x)
+        param_ty: Option<TypeAnnotation>,
+        /// Annotated result type, from `let f (x: int) : bool = ...`
+        return_ty: Option<TypeAnnotation>,
+        body: Box<Expr>,
+    },
 
     /// Function application (e.g., f x, add 1 2)
     App { func: Box<Expr>, arg: Box<Expr> },
@@ -662,6 +1196,20 @@ pub enum Expr {
         arms: Vec<MatchArm>,
     },
 
+    /// Exception handler (e.g., try risky () with | Failure msg -> msg)
+    /// The arms match the raised value; if none matches it is raised again
+    TryWith {
+        body: Box<Expr>,
+        arms: Vec<MatchArm>,
+    },
+
+    /// Cleanup block run on both normal and exceptional exit
+    /// (e.g., try work () finally cleanup ())
+    TryFinally {
+        body: Box<Expr>,
+        finalizer: Box<Expr>,
+    },
+
     /// Tuple expression (e.g., (1, 2), (x, y, z))
     /// Empty tuple () is represented as Lit(Literal::Unit)
     Tuple(Vec<Expr>),
@@ -690,10 +1238,13 @@ pub enum Expr {
 
     /// Record literal (e.g., { name = "John"; age = 30 })
     RecordLiteral {
-        type_name: String,    // Filled by typechecker, empty during parsing
+        type_name: String,    // Set by a qualified field ({ Person.name = .. }), else empty
         fields: RecordFields, // (field_name, value_expr)
     },
 
+    /// Anonymous record literal (e.g., {| name = "John"; age = 30 |})
+    AnonRecordLiteral { fields: RecordFields },
+
     /// Record field access (e.g., person.name, record.field)
     RecordAccess { record: Box<Expr>, field: String },
 
@@ -731,6 +1282,54 @@ pub enum Expr {
         body: Box<Expr>,
     },
 
+    /// Counted for loop (e.g., for i = 1 to 10 do body, for i = 10 downto 1 do body)
+    For {
+        /// Loop variable, bound to each counter value in turn
+        var: String,
+        /// First counter value
+        start: Box<Expr>,
+        /// Last counter value (inclusive)
+        finish: Box<Expr>,
+        /// Count down (`downto`) instead of up (`to`)
+        descending: bool,
+        /// Loop body
+        body: Box<Expr>,
+    },
+
+    /// Collection for loop (e.g., for (k, v) in pairs do body)
+    ForIn {
+        /// Pattern bound to each element; elements it rejects are skipped
+        pattern: Pattern,
+        /// List, array or map to iterate
+        source: Box<Expr>,
+        /// Loop body
+        body: Box<Expr>,
+    },
+
+    /// Range (e.g., 1 .. 10 or 0 .. 2 .. 100): the list of numbers from start
+    /// to finish inclusive, every step apart
+    Range {
+        start: Box<Expr>,
+        /// Distance between elements; 1 when omitted
+        step: Option<Box<Expr>>,
+        finish: Box<Expr>,
+    },
+
+    /// List or array comprehension (e.g., [ for x in xs -> x * 2 ]): the
+    /// values yielded while evaluating the body, in order
+    Comprehension {
+        /// Body made of loops, conditionals, bindings and yields
+        body: Box<Expr>,
+        /// Build an array (`[| ... |]`) instead of a list
+        array: bool,
+    },
+
+    /// Yield one value from a comprehension body (e.g., yield x * 2)
+    Yield(Box<Expr>),
+
+    /// Yield every element of a list or array from a comprehension body (e.g., yield! xs)
+    YieldBang(Box<Expr>),
+
     /// Break statement (exits current loop)
     Break,
 
@@ -810,6 +1409,11 @@ impl Expr {
         matches!(self, Expr::List(_))
     }
 
+    /// Returns true if this expression is a range.
+    pub fn is_range(&self) -> bool {
+        matches!(self, Expr::Range { .. })
+    }
+
     /// Returns true if this expression is a cons.
     pub fn is_cons(&self) -> bool {
         matches!(self, Expr::Cons { .. })
@@ -865,6 +1469,11 @@ impl Expr {
         matches!(self, Expr::While { .. })
     }
 
+    /// Returns true if this expression is a for loop of either form.
+    pub fn is_for(&self) -> bool {
+        matches!(self, Expr::For { .. } | Expr::ForIn { .. })
+    }
+
     /// Returns true if this expression is a break statement.
     pub fn is_break(&self) -> bool {
         matches!(self, Expr::Break)
@@ -958,15 +1567,30 @@ impl fmt::Display for Expr {
         match self {
             Expr::Var(name) => write!(f, "{}", name),
             Expr::Lit(lit) => write!(f, "{}", lit),
+            Expr::MeasuredLit { value, measure } => write!(f, "{}<{}>", value, measure),
             Expr::BinOp { op, left, right } => {
                 write!(f, "({} {} {})", left, op, right)
             }
-            Expr::Let { name, value, body } => {
+            Expr::Let {
+                name,
+                ty: Some(ty),
+                value,
+                body,
+            } => {
+                write!(f, "(let {}: {} = {} in {})", name, ty, value, body)
+            }
+            Expr::Let {
+                name, value, body, ..
+            } => {
                 write!(f, "(let {} = {} in {})", name, value, body)
             }
             Expr::LetRec { name, value, body } => {
                 write!(f, "(let rec {} = {} in {})", name, value, body)
             }
+            Expr::LetMutable { name, value, body } => {
+                write!(f, "(let mutable {} = {} in {})", name, value, body)
+            }
+            Expr::Assign { name, value } => write!(f, "({} <- {})", name, value),
             Expr::LetRecMutual { bindings, body } => {
                 write!(f, "(let rec ")?;
                 for (i, (name, value)) in bindings.iter().enumerate() {
@@ -977,8 +1601,20 @@ impl fmt::Display for Expr {
                 }
                 write!(f, " in {})", body)
             }
-            Expr::Lambda { param, body } => {
-                write!(f, "(fun {} -> {})", param, body)
+            Expr::Lambda {
+                param,
+                param_ty,
+                return_ty,
+                body,
+            } => {
+                match param_ty {
+                    Some(ty) => write!(f, "(fun ({}: {})", param, ty)?,
+                    None => write!(f, "(fun {}", param)?,
+                }
+                if let Some(ty) = return_ty {
+                    write!(f, " : {}", ty)?;
+                }
+                write!(f, " -> {})", body)
             }
             Expr::App { func, arg } => {
                 write!(f, "({} {})", func, arg)
@@ -997,6 +1633,16 @@ impl fmt::Display for Expr {
                 }
                 write!(f, ")")
             }
+            Expr::TryWith { body, arms } => {
+                write!(f, "(try {} with", body)?;
+                for arm in arms {
+                    write!(f, " | {}", arm)?;
+                }
+                write!(f, ")")
+            }
+            Expr::TryFinally { body, finalizer } => {
+                write!(f, "(try {} finally {})", body, finalizer)
+            }
             Expr::Tuple(elements) => {
                 write!(f, "(")?;
                 for (i, element) in elements.iter().enumerate() {
@@ -1060,6 +1706,16 @@ impl fmt::Display for Expr {
                 }
                 Ok(())
             }
+            Expr::AnonRecordLiteral { fields } => {
+                write!(f, "{{| ")?;
+                for (i, (field_name, field_expr)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{} = {}", field_name, field_expr)?;
+                }
+                write!(f, " |}}")
+            }
             Expr::RecordAccess { record, field } => {
                 write!(f, "({}.{})", record, field)
             }
@@ -1108,6 +1764,39 @@ impl fmt::Display for Expr {
             Expr::While { cond, body } => {
                 write!(f, "(while {} do {})", cond, body)
             }
+            Expr::For {
+                var,
+                start,
+                finish,
+                descending,
+                body,
+            } => {
+                let direction = if *descending { "downto" } else { "to" };
+                write!(
+                    f,
+                    "(for {} = {} {} {} do {})",
+                    var, start, direction, finish, body
+                )
+            }
+            Expr::ForIn {
+                pattern,
+                source,
+                body,
+            } => write!(f, "(for {} in {} do {})", pattern, source, body),
+            Expr::Range {
+                start,
+                step: Some(step),
+                finish,
+            } => write!(f, "({} .. {} .. {})", start, step, finish),
+            Expr::Range {
+                start,
+                step: None,
+                finish,
+            } => write!(f, "({} .. {})", start, finish),
+            Expr::Comprehension { body, array: false } => write!(f, "[ {} ]", body),
+            Expr::Comprehension { body, array: true } => write!(f, "[| {} |]", body),
+            Expr::Yield(value) => write!(f, "(yield {})", value),
+            Expr::YieldBang(value) => write!(f, "(yield! {})", value),
             Expr::Break => write!(f, "break"),
             Expr::Continue => write!(f, "continue"),
             Expr::ComputationExpr { builder, body } => {
@@ -1130,6 +1819,37 @@ pub struct ModuleDef {
     pub name: String,
     /// Items contained in this module
     pub items: Vec<ModuleItem>,
+    /// Bindings declared `private` or `internal`; the others are public
+    pub access: HashMap<String, Access>,
+}
+
+impl ModuleDef {
+    /// The accessibility of a binding declared in this module.
+    pub fn access_of(&self, name: &str) -> Access {
+        self.access.get(name).copied().unwrap_or_default()
+    }
+}
+
+/// Accessibility of a module binding, written after `let`: `let private helper = ...`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Access {
+    /// Visible wherever the module is (the default)
+    #[default]
+    Public,
+    /// Visible only in the file that declares the module, not in files that `#load` it
+    Internal,
+    /// Visible only inside the module and its nested modules
+    Private,
+}
+
+impl fmt::Display for Access {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Access::Public => write!(f, "public"),
+            Access::Internal => write!(f, "internal"),
+            Access::Private => write!(f, "private"),
+        }
+    }
 }
 
 impl fmt::Display for ModuleDef {
@@ -1152,6 +1872,8 @@ pub enum ModuleItem {
     Let(Option<String>, Expr),
     /// Recursive let binding: let rec f = expr
     LetRec(Vec<(String, Expr)>),
+    /// Mutable let binding: let mutable x = expr
+    LetMutable(String, Expr),
     /// Type definition (record or DU)
     TypeDef(TypeDefinition),
     /// Nested module
@@ -1164,6 +1886,7 @@ impl fmt::Display for ModuleItem {
             ModuleItem::Let(name, expr) => {
                 write!(f, "let {} = {}", name.as_deref().unwrap_or("_"), expr)
             }
+            ModuleItem::LetMutable(name, expr) => write!(f, "let mutable {} = {}", name, expr),
             ModuleItem::LetRec(bindings) => {
                 write!(f, "let rec ")?;
                 for (i, (name, expr)) in bindings.iter().enumerate() {
@@ -1317,6 +2040,16 @@ mod tests {
         assert_eq!(format!("{}", lit), "\"hello\"");
     }
 
+    #[test]
+    fn test_literal_char_and_bytes() {
+        assert_eq!(format!("{}", Literal::Char('a')), "'a'");
+        assert_eq!(format!("{}", Literal::Char('\n')), "'\\n'");
+        assert_eq!(
+            format!("{}", Literal::Bytes(b"GET\n".to_vec())),
+            "\"GET\\n\"B"
+        );
+    }
+
     #[test]
     fn test_literal_unit() {
         let lit = Literal::Unit;
@@ -1341,6 +2074,7 @@ mod tests {
         assert!(BinOp::Sub.is_arithmetic());
         assert!(BinOp::Mul.is_arithmetic());
         assert!(BinOp::Div.is_arithmetic());
+        assert!(BinOp::Mod.is_arithmetic());
         assert!(!BinOp::Eq.is_arithmetic());
         assert!(!BinOp::And.is_arithmetic());
     }
@@ -1365,6 +2099,18 @@ mod tests {
         assert!(!BinOp::Eq.is_logical());
     }
 
+    #[test]
+    fn test_binop_bitwise_and_composition() {
+        assert!(BinOp::BitAnd.is_bitwise());
+        assert!(BinOp::Shr.is_bitwise());
+        assert!(!BinOp::And.is_bitwise());
+        assert!(BinOp::ComposeRight.is_composition());
+        assert!(BinOp::ComposeLeft.is_composition());
+        assert!(!BinOp::Shl.is_composition());
+        assert_eq!(format!("{}", BinOp::BitXor), "^^^");
+        assert_eq!(format!("{}", BinOp::ComposeRight), ">>");
+    }
+
     #[test]
     fn test_binop_display() {
         assert_eq!(format!("{}", BinOp::Add), "+");
@@ -1423,6 +2169,33 @@ mod tests {
         assert_eq!(format!("{}", ty), "int * string -> bool");
     }
 
+    #[test]
+    fn test_type_expr_generic() {
+        let a = TypeExpr::Var("a".to_string());
+        assert_eq!(format!("{}", a), "'a");
+
+        let list = TypeExpr::App("list".to_string(), vec![a.clone()]);
+        assert_eq!(format!("{}", list), "'a list");
+
+        let pairs = TypeExpr::App(
+            "option".to_string(),
+            vec![TypeExpr::Tuple(vec![
+                TypeExpr::Named("int".to_string()),
+                a.clone(),
+            ])],
+        );
+        assert_eq!(format!("{}", pairs), "(int * 'a) option");
+
+        let map = TypeExpr::App(
+            "Map".to_string(),
+            vec![TypeExpr::Named("string".to_string()), list.clone()],
+        );
+        assert_eq!(format!("{}", map), "Map<string, 'a list>");
+
+        let f = TypeExpr::Function(Box::new(map), Box::new(TypeExpr::Var("b".to_string())));
+        assert_eq!(f.type_vars(), vec!["a", "b"]);
+    }
+
     #[test]
     fn test_type_expr_clone() {
         let ty1 = TypeExpr::Named("int".to_string());
@@ -1438,7 +2211,9 @@ mod tests {
     fn test_record_typedef_empty() {
         let typedef = RecordTypeDef {
             name: "Empty".to_string(),
+            type_params: vec![],
             fields: vec![],
+            members: vec![],
         };
         assert_eq!(typedef.name, "Empty");
         assert_eq!(typedef.fields.len(), 0);
@@ -1449,7 +2224,9 @@ mod tests {
     fn test_record_typedef_single_field() {
         let typedef = RecordTypeDef {
             name: "Age".to_string(),
+            type_params: vec![],
             fields: vec![("age".to_string(), TypeExpr::Named("int".to_string()))],
+            members: vec![],
         };
         assert_eq!(typedef.name, "Age");
         assert_eq!(typedef.fields.len(), 1);
@@ -1460,11 +2237,13 @@ mod tests {
     fn test_record_typedef_multiple_fields() {
         let typedef = RecordTypeDef {
             name: "Person".to_string(),
+            type_params: vec![],
             fields: vec![
                 ("name".to_string(), TypeExpr::Named("string".to_string())),
                 ("age".to_string(), TypeExpr::Named("int".to_string())),
                 ("active".to_string(), TypeExpr::Named("bool".to_string())),
             ],
+            members: vec![],
         };
         assert_eq!(typedef.name, "Person");
         assert_eq!(typedef.fields.len(), 3);
@@ -1478,6 +2257,7 @@ mod tests {
     fn test_record_typedef_with_tuple_type() {
         let typedef = RecordTypeDef {
             name: "Point".to_string(),
+            type_params: vec![],
             fields: vec![(
                 "coords".to_string(),
                 TypeExpr::Tuple(vec![
@@ -1485,6 +2265,7 @@ mod tests {
                     TypeExpr::Named("int".to_string()),
                 ]),
             )],
+            members: vec![],
         };
         assert_eq!(format!("{}", typedef), "type Point = { coords: int * int }");
     }
@@ -1493,6 +2274,7 @@ mod tests {
     fn test_record_typedef_with_function_type() {
         let typedef = RecordTypeDef {
             name: "Processor".to_string(),
+            type_params: vec![],
             fields: vec![(
                 "process".to_string(),
                 TypeExpr::Function(
@@ -1500,6 +2282,7 @@ mod tests {
                     Box::new(TypeExpr::Named("string".to_string())),
                 ),
             )],
+            members: vec![],
         };
         assert_eq!(
             format!("{}", typedef),
@@ -1511,7 +2294,9 @@ mod tests {
     fn test_record_typedef_clone() {
         let typedef1 = RecordTypeDef {
             name: "Person".to_string(),
+            type_params: vec![],
             fields: vec![("name".to_string(), TypeExpr::Named("string".to_string()))],
+            members: vec![],
         };
         let typedef2 = typedef1.clone();
         assert_eq!(typedef1, typedef2);
@@ -1525,7 +2310,9 @@ mod tests {
     fn test_declaration_typedef() {
         let decl = Declaration::TypeDef(TypeDefinition::Record(RecordTypeDef {
             name: "Person".to_string(),
+            type_params: vec![],
             fields: vec![("name".to_string(), TypeExpr::Named("string".to_string()))],
+            members: vec![],
         }));
         assert!(matches!(decl, Declaration::TypeDef(_)));
         assert_eq!(format!("{}", decl), "type Person = { name: string }");
@@ -1536,6 +2323,7 @@ mod tests {
         let decl = Declaration::LetBinding {
             name: "x".to_string(),
             params: vec![],
+            return_ty: None,
             body: Box::new(Expr::Lit(Literal::Int(42))),
         };
         assert!(matches!(decl, Declaration::LetBinding { .. }));
@@ -1546,7 +2334,8 @@ mod tests {
     fn test_declaration_let_binding_with_params() {
         let decl = Declaration::LetBinding {
             name: "add".to_string(),
-            params: vec!["x".to_string(), "y".to_string()],
+            params: vec![("x".to_string(), None), ("y".to_string(), None)],
+            return_ty: None,
             body: Box::new(Expr::BinOp {
                 op: BinOp::Add,
                 left: Box::new(Expr::Var("x".to_string())),
@@ -1556,11 +2345,36 @@ mod tests {
         assert_eq!(format!("{}", decl), "let add x y = (x + y)");
     }
 
+    #[test]
+    fn test_declaration_let_binding_with_annotations() {
+        let annotation = |name: &str| {
+            TypeAnnotation::new(
+                TypeExpr::Named(name.to_string()),
+                Span::point(crate::span::Position::start()),
+            )
+        };
+        let decl = Declaration::LetBinding {
+            name: "greet".to_string(),
+            params: vec![
+                ("name".to_string(), Some(annotation("string"))),
+                ("times".to_string(), None),
+            ],
+            return_ty: Some(annotation("bool")),
+            body: Box::new(Expr::Lit(Literal::Bool(true))),
+        };
+        assert_eq!(
+            format!("{}", decl),
+            "let greet (name: string) times : bool = true"
+        );
+    }
+
     #[test]
     fn test_declaration_clone() {
         let decl1 = Declaration::TypeDef(TypeDefinition::Record(RecordTypeDef {
             name: "Person".to_string(),
+            type_params: vec![],
             fields: vec![],
+            members: vec![],
         }));
         let decl2 = decl1.clone();
         assert_eq!(decl1, decl2);
@@ -1585,7 +2399,9 @@ mod tests {
             declarations: vec![Declaration::TypeDef(TypeDefinition::Record(
                 RecordTypeDef {
                     name: "Person".to_string(),
+                    type_params: vec![],
                     fields: vec![("name".to_string(), TypeExpr::Named("string".to_string()))],
+                    members: vec![],
                 },
             ))],
         };
@@ -1599,11 +2415,14 @@ mod tests {
             declarations: vec![
                 Declaration::TypeDef(TypeDefinition::Record(RecordTypeDef {
                     name: "Person".to_string(),
+                    type_params: vec![],
                     fields: vec![("name".to_string(), TypeExpr::Named("string".to_string()))],
+                    members: vec![],
                 })),
                 Declaration::LetBinding {
                     name: "john".to_string(),
                     params: vec![],
+                    return_ty: None,
                     body: Box::new(Expr::RecordLiteral {
                         type_name: String::new(),
                         fields: vec![(
@@ -2012,6 +2831,120 @@ fn test_pattern_tuple_nested() {
     assert_eq!(format!("{}", pat), "(x, (y, z))");
 }
 
+#[test]
+fn test_pattern_list_and_cons_display() {
+    let pat = Pattern::Cons {
+        head: Box::new(Pattern::Var("x".to_string())),
+        tail: Box::new(Pattern::List(vec![
+            Pattern::Var("y".to_string()),
+            Pattern::Wildcard,
+        ])),
+    };
+    assert_eq!(format!("{}", pat), "x :: [y; _]");
+    assert_eq!(format!("{}", Pattern::List(vec![])), "[]");
+    assert_eq!(
+        format!(
+            "{}",
+            Pattern::Array(vec![Pattern::Literal(Literal::Int(1))])
+        ),
+        "[|1|]"
+    );
+}
+
+#[test]
+fn test_pattern_record_or_as_display() {
+    let pat = Pattern::As {
+        pattern: Box::new(Pattern::Record(vec![
+            ("Name".to_string(), Pattern::Var("n".to_string())),
+            ("Age".to_string(), Pattern::Wildcard),
+        ])),
+        name: "p".to_string(),
+    };
+    assert_eq!(format!("{}", pat), "{ Name = n; Age = _ } as p");
+
+    let pat = Pattern::Or(vec![
+        Pattern::Literal(Literal::Int(1)),
+        Pattern::Literal(Literal::Int(2)),
+    ]);
+    assert_eq!(format!("{}", pat), "1 | 2");
+}
+
+#[test]
+fn test_active_pattern_display_and_cases() {
+    let pat = Pattern::Active {
+        function: "|Int|_|".to_string(),
+        case: "Int".to_string(),
+        patterns: vec![Pattern::Var("n".to_string())],
+    };
+    assert_eq!(format!("{}", pat), "Int(n)");
+    assert_eq!(pat.bound_vars(), vec!["n"]);
+
+    assert_eq!(
+        active_pattern_cases("|Even|Odd|"),
+        Some((vec!["Even", "Odd"], false))
+    );
+    assert_eq!(active_pattern_cases("|Int|_|"), Some((vec!["Int"], true)));
+    assert_eq!(active_pattern_cases("|_|"), None);
+    assert_eq!(active_pattern_cases("even"), None);
+}
+
+#[test]
+fn test_mutable_binding_and_assignment_display() {
+    let expr = Expr::LetMutable {
+        name: "x".to_string(),
+        value: Box::new(Expr::Lit(Literal::Int(0))),
+        body: Box::new(Expr::Assign {
+            name: "x".to_string(),
+            value: Box::new(Expr::Lit(Literal::Int(1))),
+        }),
+    };
+    assert_eq!(format!("{}", expr), "(let mutable x = 0 in (x <- 1))");
+
+    let item = ModuleItem::LetMutable("count".to_string(), Expr::Lit(Literal::Int(0)));
+    assert_eq!(format!("{}", item), "let mutable count = 0");
+}
+
+#[test]
+fn test_for_loop_display() {
+    let expr = Expr::For {
+        var: "i".to_string(),
+        start: Box::new(Expr::Lit(Literal::Int(10))),
+        finish: Box::new(Expr::Lit(Literal::Int(1))),
+        descending: true,
+        body: Box::new(Expr::Lit(Literal::Unit)),
+    };
+    assert_eq!(format!("{}", expr), "(for i = 10 downto 1 do ())");
+    assert!(expr.is_for());
+
+    let expr = Expr::ForIn {
+        pattern: Pattern::Tuple(vec![
+            Pattern::Var("k".to_string()),
+            Pattern::Var("v".to_string()),
+        ]),
+        source: Box::new(Expr::Var("pairs".to_string())),
+        body: Box::new(Expr::Lit(Literal::Unit)),
+    };
+    assert_eq!(format!("{}", expr), "(for (k, v) in pairs do ())");
+}
+
+#[test]
+fn test_pattern_bound_vars() {
+    let pat = Pattern::As {
+        pattern: Box::new(Pattern::Cons {
+            head: Box::new(Pattern::Var("h".to_string())),
+            tail: Box::new(Pattern::Or(vec![
+                Pattern::List(vec![Pattern::Var("x".to_string())]),
+                Pattern::Cons {
+                    head: Box::new(Pattern::Var("x".to_string())),
+                    tail: Box::new(Pattern::Wildcard),
+                },
+            ])),
+        }),
+        name: "all".to_string(),
+    };
+    assert_eq!(pat.bound_vars(), vec!["h", "x", "all"]);
+}
+
 #[test]
 fn test_pattern_clone() {
     let pat1 = Pattern::Var("x".to_string());
@@ -2222,6 +3155,7 @@ fn test_expr_match_with_tuple_pattern() {
 fn test_expr_match_nested_in_let() {
     let expr = Expr::Let {
         name: "result".to_string(),
+        ty: None,
         value: Box::new(Expr::Match {
             scrutinee: Box::new(Expr::Var("x".to_string())),
             arms: vec![
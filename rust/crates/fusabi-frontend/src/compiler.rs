@@ -38,15 +38,21 @@
 //! let chunk = Compiler::compile_with_options(&expr, options).unwrap();
 //! ```
 
-use crate::ast::{BinOp, Expr, Import, Literal, MatchArm, ModuleDef, ModuleItem, Pattern, Program};
+use crate::ast::{
+    active_pattern_cases, active_result_pattern, Access, BinOp, Expr, Import, Literal, MatchArm,
+    ModuleDef, ModuleItem, Pattern, Program, TypeDefinition,
+};
+use crate::exhaustiveness::{check_match, ConstructorTable};
+use crate::inference::TypeInference;
+use crate::loader::LoadedFile;
 use crate::modules::ModuleRegistry;
 use crate::provider_resolver::ProviderResolver;
 use crate::types::{Type, TypeEnv};
-use fusabi_vm::chunk::Chunk;
+use fusabi_vm::chunk::{Chunk, UpvalueSource};
 use fusabi_vm::closure::Closure;
 use fusabi_vm::instruction::Instruction;
 use fusabi_vm::value::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::sync::Arc;
 
@@ -77,8 +83,16 @@ pub enum CompileError {
     BreakOutsideLoop,
     /// Continue statement used outside of loop
     ContinueOutsideLoop,
+    /// `yield` or `yield!` used outside of a list or array comprehension
+    YieldOutsideComprehension,
+    /// Assignment to a variable not declared with `let mutable`
+    NotMutable(String),
+    /// Qualified reference to a `private` or `internal` module binding
+    InaccessibleBinding(String, Access),
     /// Type provider resolution error
     ProviderError(String),
+    /// A warning promoted to an error by `strict_mode` or `allow_warnings: false`
+    WarningAsError(CompileWarning),
 }
 
 impl fmt::Display for CompileError {
@@ -120,15 +134,78 @@ impl fmt::Display for CompileError {
             CompileError::ContinueOutsideLoop => {
                 write!(f, "Continue statement used outside of loop")
             }
+            CompileError::YieldOutsideComprehension => {
+                write!(f, "Yield used outside of a list or array comprehension")
+            }
+            CompileError::NotMutable(name) => {
+                write!(
+                    f,
+                    "Cannot assign to '{}': value is not mutable (use 'let mutable')",
+                    name
+                )
+            }
+            CompileError::InaccessibleBinding(name, access) => {
+                write!(
+                    f,
+                    "'{}' is {} and cannot be used outside its module",
+                    name, access
+                )
+            }
             CompileError::ProviderError(msg) => {
                 write!(f, "Type provider error: {}", msg)
             }
+            CompileError::WarningAsError(warning) => {
+                write!(f, "{} (warnings are treated as errors)", warning)
+            }
         }
     }
 }
 
 impl std::error::Error for CompileError {}
 
+/// Compilation warnings
+///
+/// Warnings do not stop compilation unless `CompileOptions::strict_mode` is set
+/// or `CompileOptions::allow_warnings` is false, in which case the first warning
+/// is reported as `CompileError::WarningAsError`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompileWarning {
+    /// A match expression does not cover every possible value
+    IncompleteMatch {
+        /// Example patterns that are not covered (e.g. `Circle(_)`)
+        missing: Vec<String>,
+    },
+    /// A match arm can never be selected because earlier arms cover it
+    UnreachableArm {
+        /// Zero-based index of the arm within its match expression
+        arm_index: usize,
+        /// The arm's pattern, rendered as source text
+        pattern: String,
+    },
+}
+
+impl fmt::Display for CompileWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompileWarning::IncompleteMatch { missing } => {
+                write!(
+                    f,
+                    "Incomplete pattern match: {} not covered",
+                    missing.join(", ")
+                )
+            }
+            CompileWarning::UnreachableArm { arm_index, pattern } => {
+                write!(
+                    f,
+                    "Unreachable match arm {}: pattern '{}' is already covered by earlier arms",
+                    arm_index + 1,
+                    pattern
+                )
+            }
+        }
+    }
+}
+
 /// Compilation result type
 pub type CompileResult<T> = Result<T, CompileError>;
 
@@ -141,7 +218,7 @@ pub struct CompileOptions {
     pub enable_type_checking: bool,
     /// Strict mode - treat warnings as errors
     pub strict_mode: bool,
-    /// Allow type warnings (only relevant if enable_type_checking is true)
+    /// Allow warnings such as incomplete or redundant matches; when false they become errors
     pub allow_warnings: bool,
     /// Optional provider resolver for type provider declarations
     pub provider_resolver: Option<ProviderResolver>,
@@ -174,6 +251,10 @@ impl Default for CompileOptions {
 struct Local {
     name: String,
     depth: usize,
+    /// Declared with `let mutable`
+    mutable: bool,
+    /// Captured by a closure; its upvalue is closed when the scope ends
+    captured: bool,
 }
 
 /// Loop state for tracking break/continue targets
@@ -185,6 +266,10 @@ struct LoopState {
     break_jumps: Vec<usize>,
     /// Offsets of continue jumps to be patched to loop start
     continue_jumps: Vec<usize>,
+    /// Number of enclosing try blocks when the loop was entered
+    handler_depth: usize,
+    /// Number of locals in scope when the loop was entered
+    local_depth: usize,
 }
 
 /// Bytecode compiler state
@@ -199,23 +284,54 @@ pub struct Compiler {
     module_registry: Option<ModuleRegistry>,
     imported_bindings: HashMap<String, Expr>,
 
+    // Closure capture: variables of enclosing functions as (name, mutable),
+    // innermost last, and the names this function captures from them
+    enclosing: Vec<(String, bool)>,
+    upvalues: Vec<String>,
+
     // Loop support
     loop_stack: Vec<LoopState>,
+
+    // Accumulator slots of the enclosing comprehensions, innermost last
+    comprehension_stack: Vec<u8>,
+
+    // Enclosing try blocks, innermost last; `finally` blocks keep their cleanup
+    handler_stack: Vec<Option<Expr>>,
+
+    // Whether the next expression compiled is the result of the function,
+    // so a call there can reuse the function's frame
+    tail_position: bool,
+
+    // Pattern match analysis
+    constructors: ConstructorTable,
+    warnings: Vec<CompileWarning>,
+
+    // Script types declaring static members, reached as `Type.Member`
+    types_with_statics: HashSet<String>,
 }
 
 impl Compiler {
-    /// Create a new compiler with default options
-    fn new() -> Self {
-        Compiler {
-            chunk: Chunk::new(),
-            locals: Vec::new(),
-            scope_depth: 0,
-            options: CompileOptions::default(),
-            type_env: None,
-            module_registry: None,
-            imported_bindings: HashMap::new(),
-            loop_stack: Vec::new(),
-        }
+    /// Create a compiler for a nested function body
+    ///
+    /// The nested compiler inherits the warning policy and known union cases
+    /// of its parent so that matches inside lambdas are checked the same way,
+    /// and sees the parent's variables so it can capture them as upvalues.
+    fn new_nested(&self) -> Self {
+        let mut compiler = Compiler::new_with_options(CompileOptions {
+            enable_type_checking: false,
+            strict_mode: self.options.strict_mode,
+            allow_warnings: self.options.allow_warnings,
+            provider_resolver: None,
+        });
+        compiler.constructors = self.constructors.clone();
+        compiler.types_with_statics = self.types_with_statics.clone();
+        compiler.enclosing = self.enclosing.clone();
+        compiler.enclosing.extend(
+            self.locals
+                .iter()
+                .map(|local| (local.name.clone(), local.mutable)),
+        );
+        compiler
     }
 
     /// Create a new compiler with custom options
@@ -228,7 +344,15 @@ impl Compiler {
             type_env: None,
             module_registry: None,
             imported_bindings: HashMap::new(),
+            enclosing: Vec::new(),
+            upvalues: Vec::new(),
             loop_stack: Vec::new(),
+            comprehension_stack: Vec::new(),
+            handler_stack: Vec::new(),
+            tail_position: false,
+            constructors: ConstructorTable::new(),
+            types_with_statics: HashSet::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -242,7 +366,7 @@ impl Compiler {
     fn expr_references_var(expr: &Expr, name: &str) -> bool {
         match expr {
             Expr::Var(var_name) => var_name == name,
-            Expr::Lambda { param, body } => {
+            Expr::Lambda { param, body, .. } => {
                 // If the lambda parameter shadows the name, don't look inside
                 if param == name {
                     false
@@ -257,6 +381,7 @@ impl Compiler {
                 name: let_name,
                 value,
                 body,
+                ..
             } => {
                 // Check value, but if let shadows the name, don't check body
                 Self::expr_references_var(value, name)
@@ -271,6 +396,18 @@ impl Compiler {
                 Self::expr_references_var(value, name)
                     || (rec_name != name && Self::expr_references_var(body, name))
             }
+            Expr::LetMutable {
+                name: let_name,
+                value,
+                body,
+            } => {
+                Self::expr_references_var(value, name)
+                    || (let_name != name && Self::expr_references_var(body, name))
+            }
+            Expr::Assign {
+                name: target,
+                value,
+            } => target == name || Self::expr_references_var(value, name),
             Expr::LetRecMutual { bindings, body } => {
                 // Check all binding values
                 bindings
@@ -311,7 +448,7 @@ impl Compiler {
                     || Self::expr_references_var(value, name)
             }
             Expr::ArrayLength(array) => Self::expr_references_var(array, name),
-            Expr::RecordLiteral { fields, .. } => fields
+            Expr::RecordLiteral { fields, .. } | Expr::AnonRecordLiteral { fields } => fields
                 .iter()
                 .any(|(_, expr)| Self::expr_references_var(expr, name)),
             Expr::RecordAccess { record, .. } => Self::expr_references_var(record, name),
@@ -330,9 +467,24 @@ impl Compiler {
                         // For simplicity, we don't check if pattern shadows the name
                         // This is a conservative approach - may detect false recursion
                         // but won't miss actual recursion
-                        Self::expr_references_var(&arm.body, name)
+                        arm.guard
+                            .as_ref()
+                            .is_some_and(|g| Self::expr_references_var(g, name))
+                            || Self::expr_references_var(&arm.body, name)
+                    })
+            }
+            Expr::TryWith { body, arms } => {
+                Self::expr_references_var(body, name)
+                    || arms.iter().any(|arm| {
+                        arm.guard
+                            .as_ref()
+                            .is_some_and(|g| Self::expr_references_var(g, name))
+                            || Self::expr_references_var(&arm.body, name)
                     })
             }
+            Expr::TryFinally { body, finalizer } => {
+                Self::expr_references_var(body, name) || Self::expr_references_var(finalizer, name)
+            }
             Expr::MethodCall { receiver, args, .. } => {
                 Self::expr_references_var(receiver, name)
                     || args.iter().any(|e| Self::expr_references_var(e, name))
@@ -340,6 +492,35 @@ impl Compiler {
             Expr::While { cond, body } => {
                 Self::expr_references_var(cond, name) || Self::expr_references_var(body, name)
             }
+            Expr::For {
+                var,
+                start,
+                finish,
+                body,
+                ..
+            } => {
+                Self::expr_references_var(start, name)
+                    || Self::expr_references_var(finish, name)
+                    || (var != name && Self::expr_references_var(body, name))
+            }
+            Expr::ForIn { source, body, .. } => {
+                // For simplicity, we don't check if the pattern shadows the name
+                Self::expr_references_var(source, name) || Self::expr_references_var(body, name)
+            }
+            Expr::Range {
+                start,
+                step,
+                finish,
+            } => {
+                Self::expr_references_var(start, name)
+                    || step
+                        .as_ref()
+                        .is_some_and(|step| Self::expr_references_var(step, name))
+                    || Self::expr_references_var(finish, name)
+            }
+            Expr::Comprehension { body, .. } | Expr::Yield(body) | Expr::YieldBang(body) => {
+                Self::expr_references_var(body, name)
+            }
             Expr::ComputationExpr { body, .. } => {
                 // Check if any statement in the CE body references the variable
                 body.iter().any(|stmt| {
@@ -357,7 +538,7 @@ impl Compiler {
                 })
             }
             // Literals and control flow don't reference variables
-            Expr::Lit(_) | Expr::Break | Expr::Continue => false,
+            Expr::Lit(_) | Expr::MeasuredLit { .. } | Expr::Break | Expr::Continue => false,
         }
     }
 
@@ -377,6 +558,17 @@ impl Compiler {
 
     /// Compile an expression with custom options
     pub fn compile_with_options(expr: &Expr, options: CompileOptions) -> CompileResult<Chunk> {
+        Self::compile_with_warnings(expr, options).map(|(chunk, _)| chunk)
+    }
+
+    /// Compile an expression and return any warnings alongside the chunk
+    ///
+    /// Warnings are only returned when they are allowed by `options`;
+    /// otherwise the first one is reported as `CompileError::WarningAsError`.
+    pub fn compile_with_warnings(
+        expr: &Expr,
+        options: CompileOptions,
+    ) -> CompileResult<(Chunk, Vec<CompileWarning>)> {
         let mut compiler = Compiler::new_with_options(options);
 
         // Optional type checking phase
@@ -387,7 +579,7 @@ impl Compiler {
         // Compilation phase
         compiler.compile_expr(expr)?;
         compiler.emit(Instruction::Return);
-        Ok(compiler.chunk)
+        Ok((compiler.chunk, compiler.warnings))
     }
 
     /// Compile a complete program with modules
@@ -418,6 +610,27 @@ impl Compiler {
         program: &Program,
         options: CompileOptions,
     ) -> CompileResult<Chunk> {
+        Self::compile_program_with_warnings(program, options).map(|(chunk, _)| chunk)
+    }
+
+    /// Compile a complete program and return any warnings alongside the chunk
+    pub fn compile_program_with_warnings(
+        program: &Program,
+        options: CompileOptions,
+    ) -> CompileResult<(Chunk, Vec<CompileWarning>)> {
+        Self::compile_program_with_loads(program, &[], options)
+    }
+
+    /// Compile a program together with the files its `#load` directives
+    /// brought in
+    ///
+    /// The modules of `loaded` are registered before those of `program` and
+    /// marked as loaded, so their `internal` bindings stay hidden.
+    pub fn compile_program_with_loads(
+        program: &Program,
+        loaded: &[LoadedFile],
+        options: CompileOptions,
+    ) -> CompileResult<(Chunk, Vec<CompileWarning>)> {
         let mut compiler = Compiler::new_with_options(options);
         let mut registry = ModuleRegistry::with_stdlib();
 
@@ -449,7 +662,13 @@ impl Compiler {
             }
         }
 
-        // Phase 2: Register all modules
+        // Phase 2: Register all modules, starting with those of loaded files
+        for file in loaded {
+            for module in &file.program.modules {
+                compiler.register_module(&mut registry, module)?;
+                mark_loaded(&mut registry, module);
+            }
+        }
         for module in &program.modules {
             compiler.register_module(&mut registry, module)?;
         }
@@ -462,18 +681,29 @@ impl Compiler {
             compiler.apply_import(import)?;
         }
 
-        // Phase 4: Compile top-level items and main expression
+        // Make top-level union cases known to match analysis
+        for item in &program.items {
+            if let ModuleItem::TypeDef(crate::ast::TypeDefinition::Du(du)) = item {
+                compiler.constructors.register_du(du);
+            }
+        }
+
+        // Phase 4: Optional type checking
+        if compiler.options.enable_type_checking {
+            compiler.type_check_program(program)?;
+        }
+
+        // Phase 5: Compile top-level items and main expression
         compiler.compile_top_level_items(&program.items, &program.main_expr)?;
 
         compiler.emit(Instruction::Return);
-        Ok(compiler.chunk)
+        Ok((compiler.chunk, compiler.warnings))
     }
 
     /// Register a module and compile its bindings
     ///
     /// This processes all items in a module definition and registers them
     /// in the module registry for later lookup.
-    #[allow(clippy::only_used_in_recursion)]
     fn register_module(
         &mut self,
         registry: &mut ModuleRegistry,
@@ -481,22 +711,69 @@ impl Compiler {
     ) -> CompileResult<()> {
         let mut bindings = HashMap::new();
         let mut types = HashMap::new();
+        // Bindings declared so far; later bindings may refer to them
+        let mut siblings: Vec<String> = Vec::new();
 
         for item in &module.items {
             match item {
                 ModuleItem::Let(name, expr) => {
                     // Store binding for later compilation (skip discard bindings)
                     if let Some(name) = name {
-                        bindings.insert(name.clone(), expr.clone());
+                        let expr = if Self::expr_references_var(expr, name) {
+                            Expr::LetRec {
+                                name: name.clone(),
+                                value: Box::new(expr.clone()),
+                                body: Box::new(Expr::Var(name.clone())),
+                            }
+                        } else {
+                            expr.clone()
+                        };
+                        let expr = Self::with_siblings(expr, &siblings, &bindings);
+                        bindings.insert(name.clone(), expr);
+                        siblings.retain(|sibling| sibling != name);
+                        siblings.push(name.clone());
                     }
                 }
+                ModuleItem::LetMutable(name, _) => {
+                    // Module bindings are inlined at each use, so there is
+                    // no single storage location to assign to
+                    return Err(CompileError::CodeGenError(format!(
+                        "mutable binding '{}' is not supported inside a module",
+                        name
+                    )));
+                }
                 ModuleItem::LetRec(rec_bindings) => {
-                    // Handle recursive bindings
-                    for (name, expr) in rec_bindings {
-                        bindings.insert(name.clone(), expr.clone());
+                    // Each binding is inlined on its own, so it carries the
+                    // whole recursive group with it
+                    let mut group = Vec::new();
+                    for (name, _) in rec_bindings {
+                        let expr = match rec_bindings.as_slice() {
+                            [(_, value)] => Expr::LetRec {
+                                name: name.clone(),
+                                value: Box::new(value.clone()),
+                                body: Box::new(Expr::Var(name.clone())),
+                            },
+                            _ => Expr::LetRecMutual {
+                                bindings: rec_bindings.clone(),
+                                body: Box::new(Expr::Var(name.clone())),
+                            },
+                        };
+                        group.push((
+                            name.clone(),
+                            Self::with_siblings(expr, &siblings, &bindings),
+                        ));
+                    }
+                    for (name, expr) in group {
+                        bindings.insert(name.clone(), expr);
+                        siblings.retain(|sibling| *sibling != name);
+                        siblings.push(name);
                     }
                 }
                 ModuleItem::TypeDef(type_def) => {
+                    if let crate::ast::TypeDefinition::Du(du) = type_def {
+                        self.constructors.register_du(du);
+                    }
+
                     // Convert AST TypeDefinition to modules TypeDefinition
                     let module_type_def = match type_def {
                         crate::ast::TypeDefinition::Record(r) => {
@@ -508,6 +785,9 @@ impl Compiler {
                         crate::ast::TypeDefinition::Provider(p) => {
                             crate::modules::TypeDefinition::Provider(p.clone())
                         }
+                        crate::ast::TypeDefinition::Measure(m) => {
+                            crate::modules::TypeDefinition::Measure(m.clone())
+                        }
                     };
 
                     // Extract type name based on definition
@@ -515,7 +795,15 @@ impl Compiler {
                         crate::ast::TypeDefinition::Record(r) => r.name.clone(),
                         crate::ast::TypeDefinition::Du(du) => du.name.clone(),
                         crate::ast::TypeDefinition::Provider(p) => p.name.clone(),
+                        crate::ast::TypeDefinition::Measure(m) => m.name.clone(),
                     };
+                    if !type_def.members().is_empty() {
+                        // Module items have no initialization code to register members in
+                        return Err(CompileError::CodeGenError(format!(
+                            "members of type '{}' are only supported at the top level",
+                            type_name
+                        )));
+                    }
 
                     types.insert(type_name, module_type_def);
                 }
@@ -527,11 +815,39 @@ impl Compiler {
         }
 
         // Register module in registry
-        registry.register_module(module.name.clone(), bindings, types);
+        registry.register_module_with_access(
+            module.name.clone(),
+            bindings,
+            types,
+            module.access.clone(),
+        );
 
         Ok(())
     }
 
+    /// Bind the earlier module bindings an expression refers to
+    ///
+    /// Module bindings are inlined where they are used, so each one carries
+    /// the siblings it needs, including `private` ones that cannot be
+    /// resolved from outside the module.
+    fn with_siblings(expr: Expr, siblings: &[String], bindings: &HashMap<String, Expr>) -> Expr {
+        siblings
+            .iter()
+            .filter(|sibling| Self::expr_references_var(&expr, sibling))
+            .cloned()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .fold(expr, |body, sibling| {
+                let value = bindings[&sibling].clone();
+                Expr::Let {
+                    name: sibling,
+                    ty: None,
+                    value: Box::new(value),
+                    body: Box::new(body),
+                }
+            })
+    }
+
     /// Apply an import to the current environment
     ///
     /// This brings all bindings from the imported module into the current scope,
@@ -549,58 +865,133 @@ impl Compiler {
             .ok_or(CompileError::NoModuleContext)?;
 
         let module_bindings = registry
-            .get_module_bindings(module_name)
+            .get_exported_bindings(module_name)
             .ok_or_else(|| CompileError::ModuleNotFound(module_name.clone()))?;
 
-        // Add all bindings from imported module to current environment
+        // Add the visible bindings from imported module to current environment
         for (name, expr) in module_bindings {
-            self.imported_bindings.insert(name.clone(), expr.clone());
+            self.imported_bindings
+                .insert(name.to_string(), expr.clone());
         }
 
         Ok(())
     }
 
-    /// Type check expression
+    /// Type check an expression
     ///
-    /// This is a placeholder for the actual type inference implementation.
-    /// Once the type inference module is complete, this will perform full
-    /// Hindley-Milner type inference and constraint solving.
-    fn type_check(&mut self, _expr: &Expr) -> CompileResult<Type> {
-        // Placeholder implementation
-        // TODO: Replace with actual type inference when available
-        //
-        // Expected implementation:
-        // let mut inference = TypeInference::new();
-        // let env = TypeEnv::new();
-        // let ty = inference.infer(expr, &env)
-        //     .map_err(|e| CompileError::TypeError(format!("{}", e)))?;
-        // let subst = inference.solve_constraints()
-        //     .map_err(|e| CompileError::TypeError(format!("{}", e)))?;
-        // let final_ty = subst.apply_type(&ty);
-        // self.type_env = Some(env);
-        // Ok(final_ty)
-
-        // For now, just initialize empty type environment
-        self.type_env = Some(TypeEnv::new());
-        Ok(Type::Unit)
+    /// Runs Hindley-Milner inference over `expr` and solves the resulting
+    /// constraints. Host and standard library functions have no declared
+    /// types, so names the script does not bind itself are left unchecked.
+    fn type_check(&mut self, expr: &Expr) -> CompileResult<Type> {
+        let inference = self.new_inference();
+        self.infer_types(inference, expr)
+    }
+
+    /// Type check a program's top-level items and main expression
+    ///
+    /// Top-level bindings are checked as nested `let`s around the main
+    /// expression, the way they are compiled.
+    fn type_check_program(&mut self, program: &Program) -> CompileResult<Type> {
+        let mut inference = self.new_inference();
+        for item in &program.items {
+            if let ModuleItem::TypeDef(type_def) = item {
+                match type_def {
+                    TypeDefinition::Du(du) => inference.register_du(du),
+                    TypeDefinition::Record(record) => inference.register_record(record),
+                    TypeDefinition::Measure(measure) => inference.register_measure(measure),
+                    TypeDefinition::Provider(_) => {}
+                }
+            }
+        }
+
+        let main = program
+            .main_expr
+            .clone()
+            .unwrap_or(Expr::Lit(Literal::Unit));
+        let expr = program
+            .items
+            .iter()
+            .rev()
+            .fold(main, |body, item| match item {
+                ModuleItem::Let(name, value) => Expr::Let {
+                    name: name.clone().unwrap_or_else(|| "_".to_string()),
+                    ty: None,
+                    value: Box::new(value.clone()),
+                    body: Box::new(body),
+                },
+                ModuleItem::LetMutable(name, value) => Expr::LetMutable {
+                    name: name.clone(),
+                    value: Box::new(value.clone()),
+                    body: Box::new(body),
+                },
+                ModuleItem::LetRec(bindings) => match bindings.as_slice() {
+                    [(name, value)] => Expr::LetRec {
+                        name: name.clone(),
+                        value: Box::new(value.clone()),
+                        body: Box::new(body),
+                    },
+                    _ => Expr::LetRecMutual {
+                        bindings: bindings.clone(),
+                        body: Box::new(body),
+                    },
+                },
+                ModuleItem::TypeDef(_) | ModuleItem::Module(_) => body,
+            });
+        self.infer_types(inference, &expr)
+    }
+
+    /// Inference that knows the types declared by registered modules
+    fn new_inference(&self) -> TypeInference {
+        let mut inference = TypeInference::new();
+        inference.set_untyped_globals(true);
+        if let Some(registry) = &self.module_registry {
+            inference.set_module_registry(registry.clone());
+        }
+        inference
+    }
+
+    /// Infer the type of `expr`, reporting failures as `CompileError::TypeError`
+    fn infer_types(&mut self, mut inference: TypeInference, expr: &Expr) -> CompileResult<Type> {
+        let env = self.type_env.take().unwrap_or_default();
+        let result = inference.infer_and_solve(expr, &env);
+        self.type_env = Some(env);
+        result.map_err(|error| {
+            let message = match &error.span {
+                Some(span) => format!("{} (at {})", error, span.format_location()),
+                None => error.to_string(),
+            };
+            CompileError::TypeError(message)
+        })
     }
 
     /// Compile an expression and emit instructions
     fn compile_expr(&mut self, expr: &Expr) -> CompileResult<()> {
+        // Only the forms below that return a subexpression's value keep it in tail position
+        let tail = std::mem::take(&mut self.tail_position);
         match expr {
             Expr::Lit(lit) => self.compile_literal(lit),
+            // Units of measure are erased
+            Expr::MeasuredLit { value, .. } => self.compile_literal(value),
             Expr::Var(name) => self.compile_var(name),
             Expr::BinOp { op, left, right } => self.compile_binop(*op, left, right),
-            Expr::Let { name, value, body } => self.compile_let(name, value, body),
-            Expr::LetRec { name, value, body } => self.compile_let_rec(name, value, body),
-            Expr::LetRecMutual { bindings, body } => self.compile_let_rec_mutual(bindings, body),
-            Expr::Lambda { param, body } => self.compile_lambda(param, body),
-            Expr::App { func, arg } => self.compile_app(func, arg),
+            Expr::Let {
+                name, value, body, ..
+            } => self.compile_let(name, value, body, tail),
+            Expr::LetRec { name, value, body } => self.compile_let_rec(name, value, body, tail),
+            Expr::LetMutable { name, value, body } => {
+                self.compile_let_mutable(name, value, body, tail)
+            }
+            Expr::Assign { name, value } => self.compile_assign(name, value),
+            Expr::LetRecMutual { bindings, body } => {
+                self.compile_let_rec_mutual(bindings, body, tail)
+            }
+            Expr::Lambda { param, body, .. } => self.compile_lambda(param, body),
+            Expr::App { func, arg } => self.compile_app(func, arg, tail),
             Expr::If {
                 cond,
                 then_branch,
                 else_branch,
-            } => self.compile_if(cond, then_branch, else_branch),
+            } => self.compile_if(cond, then_branch, else_branch, tail),
             Expr::Tuple(elements) => self.compile_tuple(elements),
             Expr::List(elements) => self.compile_list(elements),
             Expr::Cons { head, tail } => self.compile_cons(head, tail),
@@ -612,10 +1003,14 @@ impl Compiler {
                 value,
             } => self.compile_array_update(array, index, value),
             Expr::ArrayLength(array) => self.compile_array_length(array),
-            Expr::RecordLiteral { fields, .. } => self.compile_record_literal(fields),
+            Expr::RecordLiteral { fields, .. } | Expr::AnonRecordLiteral { fields } => {
+                self.compile_record_literal(fields)
+            }
             Expr::RecordAccess { record, field } => self.compile_record_access(record, field),
             Expr::RecordUpdate { record, fields } => self.compile_record_update(record, fields),
-            Expr::Match { scrutinee, arms } => self.compile_match(scrutinee, arms),
+            Expr::Match { scrutinee, arms } => self.compile_match(scrutinee, arms, tail),
+            Expr::TryWith { body, arms } => self.compile_try_with(body, arms),
+            Expr::TryFinally { body, finalizer } => self.compile_try_finally(body, finalizer),
             Expr::VariantConstruct {
                 type_name,
                 variant,
@@ -627,6 +1022,26 @@ impl Compiler {
                 args,
             } => self.compile_method_call(receiver, method_name, args),
             Expr::While { cond, body } => self.compile_while(cond, body),
+            Expr::For {
+                var,
+                start,
+                finish,
+                descending,
+                body,
+            } => self.compile_for(var, start, finish, *descending, body),
+            Expr::ForIn {
+                pattern,
+                source,
+                body,
+            } => self.compile_for_in(pattern, source, body),
+            Expr::Range {
+                start,
+                step,
+                finish,
+            } => self.compile_range(start, step.as_deref(), finish),
+            Expr::Comprehension { body, array } => self.compile_comprehension(body, *array),
+            Expr::Yield(value) => self.compile_yield(value),
+            Expr::YieldBang(source) => self.compile_yield_bang(source),
             Expr::Break => self.compile_break(),
             Expr::Continue => self.compile_continue(),
             Expr::ComputationExpr { builder, body } => self.compile_computation_expr(builder, body),
@@ -641,6 +1056,8 @@ impl Compiler {
             Literal::Str(s) => Value::Str(s.clone()),
             Literal::Unit => Value::Unit,
             Literal::Float(f) => Value::Float(*f),
+            Literal::Char(c) => Value::Char(*c),
+            Literal::Bytes(b) => Value::Bytes(b.clone()),
         };
 
         let idx = self.add_constant(value)?;
@@ -653,6 +1070,7 @@ impl Compiler {
     /// - Qualified names (e.g., Math.add)
     /// - Imported bindings (from open statements)
     /// - Local variables
+    /// - Variables captured from enclosing functions
     fn compile_var(&mut self, name: &str) -> CompileResult<()> {
         // Check if it's a qualified name (e.g., "Math.add")
         if let Some((module_path, binding_name)) = parse_qualified_name(name) {
@@ -660,12 +1078,15 @@ impl Compiler {
         }
 
         // Check local scope first
-        for (i, local) in self.locals.iter().enumerate().rev() {
-            if local.name == name {
-                let idx = i as u8;
-                self.emit(Instruction::LoadLocal(idx));
-                return Ok(());
-            }
+        if let Some(slot) = self.resolve_local(name) {
+            self.emit(Instruction::LoadLocal(slot));
+            return Ok(());
+        }
+
+        // Then the variables of enclosing functions
+        if let Some(idx) = self.resolve_upvalue(name)? {
+            self.emit(Instruction::LoadUpvalue(idx));
+            return Ok(());
         }
 
         // Check imported bindings
@@ -715,6 +1136,7 @@ impl Compiler {
                 return self.compile_expr(&expr.clone());
             }
         }
+        self.check_binding_access(module_name, name)?;
 
         // Fall back to treating qualified name as a runtime-resolved global
         // This allows host functions registered via register_module() to work in eval()
@@ -723,8 +1145,32 @@ impl Compiler {
         self.emit(Instruction::LoadGlobal(idx));
         Ok(())
     }
+    /// Reject a qualified reference to a hidden module binding
+    ///
+    /// Without this, `Lib.helper` would fall back to a runtime global lookup
+    /// and fail with a less helpful error.
+    fn check_binding_access(&self, module_name: &str, name: &str) -> CompileResult<()> {
+        let module = self
+            .module_registry
+            .as_ref()
+            .and_then(|registry| registry.get_module(module_name));
+        match module {
+            Some(module) if module.bindings.contains_key(name) && !module.is_exported(name) => {
+                Err(CompileError::InaccessibleBinding(
+                    format!("{}.{}", module_name, name),
+                    module.access_of(name),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Compile a binary operation
     fn compile_binop(&mut self, op: BinOp, left: &Expr, right: &Expr) -> CompileResult<()> {
+        if op.is_composition() {
+            return self.compile_expr(&Self::desugar_composition(op, left, right));
+        }
+
         // Compile operands
         self.compile_expr(left)?;
         self.compile_expr(right)?;
@@ -735,6 +1181,7 @@ impl Compiler {
             BinOp::Sub => Instruction::Sub,
             BinOp::Mul => Instruction::Mul,
             BinOp::Div => Instruction::Div,
+            BinOp::Mod => Instruction::Mod,
             BinOp::Concat => Instruction::Concat,
             BinOp::Eq => Instruction::Eq,
             BinOp::Neq => Instruction::Neq,
@@ -744,11 +1191,50 @@ impl Compiler {
             BinOp::Gte => Instruction::Gte,
             BinOp::And => Instruction::And,
             BinOp::Or => Instruction::Or,
+            BinOp::BitAnd => Instruction::BitAnd,
+            BinOp::BitOr => Instruction::BitOr,
+            BinOp::BitXor => Instruction::BitXor,
+            BinOp::Shl => Instruction::Shl,
+            BinOp::Shr => Instruction::Shr,
+            BinOp::ComposeRight | BinOp::ComposeLeft => unreachable!("desugared above"),
         };
         self.emit(instr);
         Ok(())
     }
 
+    /// Desugar `f >> g` into `let $f = f in let $g = g in fun $x -> $g ($f $x)`,
+    /// and `f << g` likewise with the calls swapped.
+    ///
+    /// Both operands are evaluated once, left to right, when the composition is built.
+    fn desugar_composition(op: BinOp, left: &Expr, right: &Expr) -> Expr {
+        // '$' cannot start an identifier, so user code can never see these names
+        let call = |func: &str, arg: Expr| Expr::App {
+            func: Box::new(Expr::Var(func.to_string())),
+            arg: Box::new(arg),
+        };
+        let (first, second) = match op {
+            BinOp::ComposeRight => ("$f", "$g"),
+            _ => ("$g", "$f"),
+        };
+        let body = call(second, call(first, Expr::Var("$x".to_string())));
+        Expr::Let {
+            name: "$f".to_string(),
+            ty: None,
+            value: Box::new(left.clone()),
+            body: Box::new(Expr::Let {
+                name: "$g".to_string(),
+                ty: None,
+                value: Box::new(right.clone()),
+                body: Box::new(Expr::Lambda {
+                    param: "$x".to_string(),
+                    param_ty: None,
+                    return_ty: None,
+                    body: Box::new(body),
+                }),
+            }),
+        }
+    }
+
     /// Compile a tuple expression
     fn compile_tuple(&mut self, elements: &[Expr]) -> CompileResult<()> {
         // Check if tuple size fits in u16
@@ -889,17 +1375,21 @@ impl Compiler {
                         self.compile_top_level_items(rest, main_expr)?;
 
                         // Clean up scope
-                        let locals_to_remove = self.end_scope_count();
-                        for _ in 0..locals_to_remove {
-                            self.locals.pop();
-                        }
-                        self.scope_depth -= 1;
+                        self.end_scope();
                     } else {
                         // Discard pattern: just pop the value and continue
                         self.emit(Instruction::Pop);
                         self.compile_top_level_items(rest, main_expr)?;
                     }
                 }
+                ModuleItem::LetMutable(name, value) => {
+                    self.compile_expr(value)?;
+                    self.begin_scope();
+                    let local_idx = self.add_mutable_local(name.to_string())?;
+                    self.emit(Instruction::StoreLocal(local_idx));
+                    self.compile_top_level_items(rest, main_expr)?;
+                    self.end_scope();
+                }
                 ModuleItem::LetRec(bindings) => {
                     if bindings.len() == 1 {
                         // Single recursive binding
@@ -917,11 +1407,7 @@ impl Compiler {
 
                         self.compile_top_level_items(rest, main_expr)?;
 
-                        let locals_to_remove = self.end_scope_count();
-                        for _ in 0..locals_to_remove {
-                            self.locals.pop();
-                        }
-                        self.scope_depth -= 1;
+                        self.end_scope();
                     } else {
                         // Mutual recursion
                         let placeholder_idx = self.add_constant(Value::Unit)?;
@@ -943,14 +1429,14 @@ impl Compiler {
 
                         self.compile_top_level_items(rest, main_expr)?;
 
-                        let locals_to_remove = self.end_scope_count();
-                        for _ in 0..locals_to_remove {
-                            self.locals.pop();
-                        }
-                        self.scope_depth -= 1;
+                        self.end_scope();
                     }
                 }
-                ModuleItem::TypeDef(_) | ModuleItem::Module(_) => {
+                ModuleItem::TypeDef(type_def) => {
+                    self.compile_type_members(type_def)?;
+                    self.compile_top_level_items(rest, main_expr)?;
+                }
+                ModuleItem::Module(_) => {
                     // Skip non-executable items and recurse
                     self.compile_top_level_items(rest, main_expr)?;
                 }
@@ -967,13 +1453,19 @@ impl Compiler {
         Ok(())
     }
 
-    fn compile_let(&mut self, name: &str, value: &Expr, body: &Expr) -> CompileResult<()> {
+    fn compile_let(
+        &mut self,
+        name: &str,
+        value: &Expr,
+        body: &Expr,
+        tail: bool,
+    ) -> CompileResult<()> {
         // Auto-detect recursion: check if value references name
         let auto_recursive = Self::expr_references_var(value, name);
 
         if auto_recursive {
             // Use recursive compilation strategy
-            self.compile_let_rec(name, value, body)
+            self.compile_let_rec(name, value, body, tail)
         } else {
             // Use standard let compilation
             // Compile the value expression
@@ -990,19 +1482,11 @@ impl Compiler {
             self.emit(Instruction::StoreLocal(local_idx));
 
             // Compile the body expression
-            self.compile_expr(body)?;
+            self.compile_tail_expr(body, tail)?;
 
             // Exit scope - note: we don't emit POP for the body result
             // The result stays on top of the stack for the caller
-            let locals_to_remove = self.end_scope_count();
-
-            // Only pop if we have multiple locals and need to clean up intermediates
-            // For Phase 1 MVP, we simplify by not emitting POPs
-            // The locals stay in their stack slots until function return
-            for _ in 0..locals_to_remove {
-                self.locals.pop();
-            }
-            self.scope_depth -= 1;
+            self.end_scope();
 
             Ok(())
         }
@@ -1011,36 +1495,97 @@ impl Compiler {
     /// Compile a lambda function
     fn compile_lambda(&mut self, param: &str, body: &Expr) -> CompileResult<()> {
         // Create a nested chunk for the lambda body
-        let mut lambda_compiler = Compiler::new();
+        let mut lambda_compiler = self.new_nested();
 
         // Lambda parameter becomes local 0
         lambda_compiler.begin_scope();
         lambda_compiler.add_local(param.to_string())?;
 
-        // Compile the lambda body
-        lambda_compiler.compile_expr(body)?;
+        // Compile the lambda body; a call returning its value reuses the frame
+        lambda_compiler.compile_tail_expr(body, true)?;
         lambda_compiler.emit(Instruction::Return);
 
-        // Clean up scope (not strictly needed as we discard compiler, but good practice)
-        let _locals_to_remove = lambda_compiler.end_scope_count();
+        // No CloseUpvalue needed here: Return closes the frame's upvalues
         lambda_compiler.scope_depth -= 1;
+        self.warnings.append(&mut lambda_compiler.warnings);
+
+        // Tell MakeClosure where each captured variable lives in this function
+        let upvalue_sources = lambda_compiler
+            .upvalues
+            .iter()
+            .map(|name| self.capture_variable(name))
+            .collect::<CompileResult<Vec<_>>>()?;
+        let upvalue_count = upvalue_sources.len() as u8;
+        lambda_compiler.chunk.upvalue_sources = upvalue_sources;
 
         // Create a closure prototype (chunk + arity)
-        // For now, we don't support upvalue capture in the compiler (Phase 2 extension)
-        // We assume no upvalues.
         let closure = Closure::with_arity(lambda_compiler.chunk, 1);
         let closure_val = Value::Closure(Arc::new(closure));
 
         // Store prototype in constants
         let const_idx = self.add_constant(closure_val)?;
 
-        // Emit MakeClosure instruction (0 upvalues for now)
-        self.emit(Instruction::MakeClosure(const_idx, 0));
+        self.emit(Instruction::MakeClosure(const_idx, upvalue_count));
+        Ok(())
+    }
+
+    /// Compile a mutable let-binding
+    fn compile_let_mutable(
+        &mut self,
+        name: &str,
+        value: &Expr,
+        body: &Expr,
+        tail: bool,
+    ) -> CompileResult<()> {
+        self.compile_expr(value)?;
+        self.begin_scope();
+        let local_idx = self.add_mutable_local(name.to_string())?;
+        self.emit(Instruction::StoreLocal(local_idx));
+        self.compile_tail_expr(body, tail)?;
+        self.end_scope();
+        Ok(())
+    }
+
+    /// Compile an assignment to a mutable variable
+    ///
+    /// Captured mutables are assigned through their upvalue, so the closure
+    /// and the declaring scope always see the same value.
+    fn compile_assign(&mut self, name: &str, value: &Expr) -> CompileResult<()> {
+        if let Some(slot) = self.resolve_local(name) {
+            if !self.locals[slot as usize].mutable {
+                return Err(CompileError::NotMutable(name.to_string()));
+            }
+            self.compile_expr(value)?;
+            self.emit(Instruction::StoreLocal(slot));
+        } else if let Some(mutable) = self.enclosing_var(name) {
+            if !mutable {
+                return Err(CompileError::NotMutable(name.to_string()));
+            }
+            self.compile_expr(value)?;
+            let idx = self
+                .resolve_upvalue(name)?
+                .ok_or_else(|| CompileError::UndefinedVariable(name.to_string()))?;
+            self.emit(Instruction::StoreUpvalue(idx));
+        } else if self.imported_bindings.contains_key(name) {
+            return Err(CompileError::NotMutable(name.to_string()));
+        } else {
+            return Err(CompileError::UndefinedVariable(name.to_string()));
+        }
+
+        // Assignment evaluates to unit
+        let unit_idx = self.add_constant(Value::Unit)?;
+        self.emit(Instruction::LoadConst(unit_idx));
         Ok(())
     }
 
     /// Compile a recursive let-binding using placeholder strategy
-    fn compile_let_rec(&mut self, name: &str, value: &Expr, body: &Expr) -> CompileResult<()> {
+    fn compile_let_rec(
+        &mut self,
+        name: &str,
+        value: &Expr,
+        body: &Expr,
+        tail: bool,
+    ) -> CompileResult<()> {
         // Strategy: Create a placeholder, compile the function with
         // the name in scope, then update the binding
 
@@ -1064,14 +1609,10 @@ impl Compiler {
         self.emit(Instruction::StoreLocal(local_idx));
 
         // 6. Compile body (the local is still in scope)
-        self.compile_expr(body)?;
+        self.compile_tail_expr(body, tail)?;
 
         // 7. Clean up scope
-        let locals_to_remove = self.end_scope_count();
-        for _ in 0..locals_to_remove {
-            self.locals.pop();
-        }
-        self.scope_depth -= 1;
+        self.end_scope();
 
         Ok(())
     }
@@ -1081,6 +1622,7 @@ impl Compiler {
         &mut self,
         bindings: &[(String, Expr)],
         body: &Expr,
+        tail: bool,
     ) -> CompileResult<()> {
         // Strategy: Create placeholders for all bindings, then fill them in
 
@@ -1111,20 +1653,19 @@ impl Compiler {
         }
 
         // 4. Compile body
-        self.compile_expr(body)?;
+        self.compile_tail_expr(body, tail)?;
 
         // 5. Clean up scope
-        let locals_to_remove = self.end_scope_count();
-        for _ in 0..locals_to_remove {
-            self.locals.pop();
-        }
-        self.scope_depth -= 1;
+        self.end_scope();
 
         Ok(())
     }
 
     /// Compile a function application
-    fn compile_app(&mut self, func: &Expr, arg: &Expr) -> CompileResult<()> {
+    ///
+    /// In tail position the call replaces the current frame, so recursive
+    /// loops run in constant stack space.
+    fn compile_app(&mut self, func: &Expr, arg: &Expr, tail: bool) -> CompileResult<()> {
         // Compile the function expression
         self.compile_expr(func)?;
 
@@ -1132,17 +1673,31 @@ impl Compiler {
         self.compile_expr(arg)?;
 
         // Emit call instruction with 1 argument
-        self.emit(Instruction::Call(1));
+        if tail {
+            self.emit(Instruction::TailCall(1));
+        } else {
+            self.emit(Instruction::Call(1));
+        }
 
         Ok(())
     }
 
+    /// Compile an expression whose value the enclosing expression returns
+    ///
+    /// `tail` is passed on only when the enclosing expression is itself in
+    /// tail position.
+    fn compile_tail_expr(&mut self, expr: &Expr, tail: bool) -> CompileResult<()> {
+        self.tail_position = tail;
+        self.compile_expr(expr)
+    }
+
     /// Compile an if-then-else expression
     fn compile_if(
         &mut self,
         cond: &Expr,
         then_branch: &Expr,
         else_branch: &Expr,
+        tail: bool,
     ) -> CompileResult<()> {
         // Compile condition
         self.compile_expr(cond)?;
@@ -1153,7 +1708,7 @@ impl Compiler {
         // Note: JumpIfFalse pops the condition value, so no manual POP needed
 
         // Compile then branch
-        self.compile_expr(then_branch)?;
+        self.compile_tail_expr(then_branch, tail)?;
 
         // Emit Jump to skip else branch with placeholder offset
         let jump_to_end = self.emit_jump(Instruction::Jump(0));
@@ -1162,7 +1717,7 @@ impl Compiler {
         self.patch_jump(jump_to_else)?;
 
         // Compile else branch
-        self.compile_expr(else_branch)?;
+        self.compile_tail_expr(else_branch, tail)?;
 
         // Patch the Jump to point here
         self.patch_jump(jump_to_end)?;
@@ -1186,6 +1741,8 @@ impl Compiler {
             start_offset,
             break_jumps: Vec::new(),
             continue_jumps: Vec::new(),
+            handler_depth: self.handler_stack.len(),
+            local_depth: self.locals.len(),
         });
 
         // Compile body
@@ -1195,11 +1752,7 @@ impl Compiler {
         self.emit(Instruction::Pop);
 
         // Jump back to loop start
-        let offset_to_start = self.chunk.current_offset() as i32 - start_offset as i32 + 1;
-        if offset_to_start > i16::MAX as i32 || -offset_to_start > i16::MAX as i32 {
-            return Err(CompileError::InvalidJumpOffset);
-        }
-        self.emit(Instruction::Jump(-offset_to_start as i16));
+        self.emit_loop(start_offset)?;
 
         // Patch jump to end
         self.patch_jump(jump_to_end)?;
@@ -1213,19 +1766,7 @@ impl Compiler {
         }
 
         // Patch all continue jumps to point to loop start
-        for continue_jump in loop_state.continue_jumps {
-            let target = start_offset;
-            let offset = target as i32 - continue_jump as i32 - 1;
-            if offset > i16::MAX as i32 || offset < i16::MIN as i32 {
-                return Err(CompileError::InvalidJumpOffset);
-            }
-            match self.chunk.instructions[continue_jump] {
-                Instruction::Jump(_) => {
-                    self.chunk.instructions[continue_jump] = Instruction::Jump(offset as i16);
-                }
-                _ => unreachable!("Expected Jump instruction for continue"),
-            }
-        }
+        self.patch_continue_jumps(loop_state.continue_jumps, start_offset)?;
 
         // Push unit (result of while loop)
         let unit_idx = self.add_constant(Value::Unit)?;
@@ -1234,52 +1775,370 @@ impl Compiler {
         Ok(())
     }
 
-    /// Compile a break statement
-    fn compile_break(&mut self) -> CompileResult<()> {
-        if self.loop_stack.is_empty() {
-            return Err(CompileError::BreakOutsideLoop);
-        }
+    /// Compile a counted loop: for <var> = <start> to|downto <finish> do <body>
+    ///
+    /// Both bounds are evaluated once, and the loop variable is a fresh
+    /// binding in every iteration, so closures capture the value they saw.
+    ///
+    /// ```text
+    ///     <start>; STORE counter
+    ///     <finish>; STORE limit
+    /// loop:
+    ///     LOAD counter; LOAD limit; LTE (GTE for downto); JUMP_IF_FALSE end
+    ///     LOAD counter; STORE var
+    ///     <body>; POP
+    /// step:                         ; continue lands here
+    ///     LOAD counter; CONST 1; ADD (SUB for downto); STORE counter
+    ///     JUMP loop
+    /// end:                          ; break lands here
+    ///     CONST ()
+    /// ```
+    fn compile_for(
+        &mut self,
+        var: &str,
+        start: &Expr,
+        finish: &Expr,
+        descending: bool,
+        body: &Expr,
+    ) -> CompileResult<()> {
+        self.compile_expr(start)?;
+        self.begin_scope();
+        let counter = self.add_hidden_local()?;
+        self.emit(Instruction::StoreLocal(counter));
+        self.compile_expr(finish)?;
+        let limit = self.add_hidden_local()?;
+        self.emit(Instruction::StoreLocal(limit));
 
-        // Emit a jump to loop end (placeholder offset)
-        let jump_idx = self.emit_jump(Instruction::Jump(0));
+        let start_offset = self.chunk.current_offset();
+        self.emit(Instruction::LoadLocal(counter));
+        self.emit(Instruction::LoadLocal(limit));
+        self.emit(if descending {
+            Instruction::Gte
+        } else {
+            Instruction::Lte
+        });
+        let jump_to_end = self.emit_jump(Instruction::JumpIfFalse(0));
 
-        // Record this jump to be patched later
-        self.loop_stack
-            .last_mut()
-            .unwrap()
-            .break_jumps
-            .push(jump_idx);
+        self.loop_stack.push(LoopState {
+            start_offset,
+            break_jumps: Vec::new(),
+            continue_jumps: Vec::new(),
+            handler_depth: self.handler_stack.len(),
+            local_depth: self.locals.len(),
+        });
 
-        Ok(())
-    }
+        self.begin_scope();
+        self.add_local(var.to_string())?;
+        self.emit(Instruction::LoadLocal(counter));
+        self.emit(Instruction::StoreLocal((self.locals.len() - 1) as u8));
+        self.compile_expr(body)?;
+        self.emit(Instruction::Pop);
+        self.end_scope();
 
-    /// Compile a continue statement
-    fn compile_continue(&mut self) -> CompileResult<()> {
-        if self.loop_stack.is_empty() {
-            return Err(CompileError::ContinueOutsideLoop);
-        }
+        let loop_state = self.loop_stack.pop().unwrap();
+        let step_offset = self.chunk.current_offset();
+        self.patch_continue_jumps(loop_state.continue_jumps, step_offset)?;
+
+        let one_idx = self.add_constant(Value::Int(1))?;
+        self.emit(Instruction::LoadLocal(counter));
+        self.emit(Instruction::LoadConst(one_idx));
+        self.emit(if descending {
+            Instruction::Sub
+        } else {
+            Instruction::Add
+        });
+        self.emit(Instruction::StoreLocal(counter));
+        self.emit_loop(start_offset)?;
 
-        // Emit a jump to loop start (placeholder offset)
-        let jump_idx = self.emit_jump(Instruction::Jump(0));
+        self.patch_jump(jump_to_end)?;
+        for break_jump in loop_state.break_jumps {
+            self.patch_jump(break_jump)?;
+        }
+        self.end_scope();
 
-        // Record this jump to be patched later
-        self.loop_stack
-            .last_mut()
-            .unwrap()
-            .continue_jumps
-            .push(jump_idx);
+        let unit_idx = self.add_constant(Value::Unit)?;
+        self.emit(Instruction::LoadConst(unit_idx));
 
         Ok(())
     }
 
-    /// Compile a computation expression
-    fn compile_computation_expr(
+    /// Compile a collection loop: for <pattern> in <source> do <body>
+    ///
+    /// The source is walked with a cursor that is advanced before the body
+    /// runs. A sequence is enumerated one element at a time, so breaking out
+    /// of an infinite one terminates; other collections are turned into a
+    /// list once. Elements the pattern rejects are skipped, as in F#.
+    ///
+    /// ```text
+    ///     <source>; GET_ENUMERATOR; STORE cursor
+    /// loop:                         ; continue lands here
+    ///     MOVE_NEXT cursor; JUMP_IF_FALSE end
+    ///     <bind pattern>            ; JUMP loop if it fails
+    ///     <body>; POP
+    ///     JUMP loop
+    /// end:                          ; break lands here
+    ///     CONST ()
+    /// ```
+    fn compile_for_in(
+        &mut self,
+        pattern: &Pattern,
+        source: &Expr,
+        body: &Expr,
+    ) -> CompileResult<()> {
+        self.compile_expr(source)?;
+        self.emit(Instruction::GetEnumerator);
+        self.begin_scope();
+        let cursor = self.add_hidden_local()?;
+        self.emit(Instruction::StoreLocal(cursor));
+
+        let start_offset = self.chunk.current_offset();
+        self.emit(Instruction::MoveNext(cursor));
+        let jump_to_end = self.emit_jump(Instruction::JumpIfFalse(0));
+
+        self.loop_stack.push(LoopState {
+            start_offset,
+            break_jumps: Vec::new(),
+            continue_jumps: Vec::new(),
+            handler_depth: self.handler_stack.len(),
+            local_depth: self.locals.len(),
+        });
+
+        self.begin_scope();
+        for name in pattern.bound_vars() {
+            self.add_local(name.to_string())?;
+        }
+        let mut fails = Vec::new();
+        self.compile_subpattern(pattern, &mut fails)?;
+        self.compile_expr(body)?;
+        self.emit(Instruction::Pop);
+        self.end_scope();
+        self.emit_loop(start_offset)?;
+
+        if !fails.is_empty() {
+            self.patch_pattern_fails(fails)?;
+            self.emit_loop(start_offset)?;
+        }
+
+        let loop_state = self.loop_stack.pop().unwrap();
+        self.patch_jump(jump_to_end)?;
+        for break_jump in loop_state.break_jumps {
+            self.patch_jump(break_jump)?;
+        }
+        self.patch_continue_jumps(loop_state.continue_jumps, start_offset)?;
+        self.end_scope();
+
+        let unit_idx = self.add_constant(Value::Unit)?;
+        self.emit(Instruction::LoadConst(unit_idx));
+
+        Ok(())
+    }
+
+    /// Compile a range `start .. finish` or `start .. step .. finish`
+    /// Stack effect: pushes the list of values in the range
+    fn compile_range(
+        &mut self,
+        start: &Expr,
+        step: Option<&Expr>,
+        finish: &Expr,
+    ) -> CompileResult<()> {
+        self.compile_expr(start)?;
+        match step {
+            Some(step) => self.compile_expr(step)?,
+            None => {
+                let one_idx = self.add_constant(Value::Int(1))?;
+                self.emit(Instruction::LoadConst(one_idx));
+            }
+        }
+        self.compile_expr(finish)?;
+        self.emit(Instruction::MakeRange);
+        Ok(())
+    }
+
+    /// Compile a list or array comprehension
+    ///
+    /// The body runs once for its effect while `yield` conses values onto a
+    /// hidden accumulator, which is reversed into yield order at the end.
+    /// Stack effect: pushes the list or array of yielded values
+    fn compile_comprehension(&mut self, body: &Expr, array: bool) -> CompileResult<()> {
+        self.begin_scope();
+        let acc = self.add_hidden_local()?;
+        self.emit(Instruction::MakeList(0));
+        self.emit(Instruction::StoreLocal(acc));
+
+        self.comprehension_stack.push(acc);
+        let result = self.compile_expr(body);
+        self.comprehension_stack.pop();
+        result?;
+
+        self.emit(Instruction::Pop);
+        self.emit(Instruction::LoadLocal(acc));
+        self.emit(Instruction::ListReverse);
+        if array {
+            self.emit(Instruction::ToArray);
+        }
+        self.end_scope();
+        Ok(())
+    }
+
+    /// Accumulator slot of the innermost comprehension
+    fn comprehension_acc(&self) -> CompileResult<u8> {
+        self.comprehension_stack
+            .last()
+            .copied()
+            .ok_or(CompileError::YieldOutsideComprehension)
+    }
+
+    /// Compile `yield value`
+    /// Stack effect: pushes unit
+    fn compile_yield(&mut self, value: &Expr) -> CompileResult<()> {
+        let acc = self.comprehension_acc()?;
+        self.compile_expr(value)?;
+        self.emit(Instruction::LoadLocal(acc));
+        self.emit(Instruction::Cons);
+        self.emit(Instruction::StoreLocal(acc));
+
+        let unit_idx = self.add_constant(Value::Unit)?;
+        self.emit(Instruction::LoadConst(unit_idx));
+        Ok(())
+    }
+
+    /// Compile `yield! source`, yielding every element of a list, array, range or sequence
+    /// Stack effect: pushes unit
+    fn compile_yield_bang(&mut self, source: &Expr) -> CompileResult<()> {
+        let acc = self.comprehension_acc()?;
+        self.compile_expr(source)?;
+        self.emit(Instruction::GetEnumerator);
+        self.begin_scope();
+        let cursor = self.add_hidden_local()?;
+        self.emit(Instruction::StoreLocal(cursor));
+
+        let start_offset = self.chunk.current_offset();
+        self.emit(Instruction::MoveNext(cursor));
+        let jump_to_end = self.emit_jump(Instruction::JumpIfFalse(0));
+        self.emit(Instruction::LoadLocal(acc));
+        self.emit(Instruction::Cons);
+        self.emit(Instruction::StoreLocal(acc));
+        self.emit_loop(start_offset)?;
+        self.patch_jump(jump_to_end)?;
+        self.end_scope();
+
+        let unit_idx = self.add_constant(Value::Unit)?;
+        self.emit(Instruction::LoadConst(unit_idx));
+        Ok(())
+    }
+
+    /// Emit a backward jump to the loop start at `target`
+    fn emit_loop(&mut self, target: usize) -> CompileResult<()> {
+        let offset_to_start = self.chunk.current_offset() as i32 - target as i32 + 1;
+        if offset_to_start > i16::MAX as i32 || -offset_to_start > i16::MAX as i32 {
+            return Err(CompileError::InvalidJumpOffset);
+        }
+        self.emit(Instruction::Jump(-offset_to_start as i16));
+        Ok(())
+    }
+
+    /// Point the continue jumps of a finished loop at `target`
+    fn patch_continue_jumps(&mut self, jumps: Vec<usize>, target: usize) -> CompileResult<()> {
+        for continue_jump in jumps {
+            let offset = target as i32 - continue_jump as i32 - 1;
+            if offset > i16::MAX as i32 || offset < i16::MIN as i32 {
+                return Err(CompileError::InvalidJumpOffset);
+            }
+            match self.chunk.instructions[continue_jump] {
+                Instruction::Jump(_) => {
+                    self.chunk.instructions[continue_jump] = Instruction::Jump(offset as i16);
+                }
+                _ => unreachable!("Expected Jump instruction for continue"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Compile a break statement
+    fn compile_break(&mut self) -> CompileResult<()> {
+        if self.loop_stack.is_empty() {
+            return Err(CompileError::BreakOutsideLoop);
+        }
+        self.exit_handlers_for_loop_jump()?;
+
+        // Emit a jump to loop end (placeholder offset)
+        let jump_idx = self.emit_jump(Instruction::Jump(0));
+
+        // Record this jump to be patched later
+        self.loop_stack
+            .last_mut()
+            .unwrap()
+            .break_jumps
+            .push(jump_idx);
+
+        Ok(())
+    }
+
+    /// Compile a continue statement
+    fn compile_continue(&mut self) -> CompileResult<()> {
+        if self.loop_stack.is_empty() {
+            return Err(CompileError::ContinueOutsideLoop);
+        }
+        self.exit_handlers_for_loop_jump()?;
+
+        // Emit a jump to loop start (placeholder offset)
+        let jump_idx = self.emit_jump(Instruction::Jump(0));
+
+        // Record this jump to be patched later
+        self.loop_stack
+            .last_mut()
+            .unwrap()
+            .continue_jumps
+            .push(jump_idx);
+
+        Ok(())
+    }
+
+    /// Leave the scopes and try blocks between a break/continue and its loop
+    ///
+    /// Handlers are removed innermost first, running any `finally` cleanup on
+    /// the way out.
+    fn exit_handlers_for_loop_jump(&mut self) -> CompileResult<()> {
+        // Locals declared inside the loop are left behind by the jump
+        let local_depth = self.loop_stack.last().map_or(0, |l| l.local_depth);
+        if self.locals.len() > local_depth {
+            self.emit(Instruction::CloseUpvalue(local_depth as u8));
+        }
+
+        let loop_depth = self.loop_stack.last().map_or(0, |l| l.handler_depth);
+        let exited: Vec<Option<Expr>> = self.handler_stack[loop_depth..].to_vec();
+        for finalizer in exited.iter().rev() {
+            self.emit(Instruction::PopHandler);
+            if let Some(finalizer) = finalizer {
+                self.compile_expr(finalizer)?;
+                self.emit(Instruction::Pop);
+            }
+        }
+        Ok(())
+    }
+
+    /// Compile a computation expression
+    fn compile_computation_expr(
         &mut self,
         builder: &str,
         body: &[crate::ast::CEStatement],
     ) -> CompileResult<()> {
         // Desugar the computation expression
-        let desugared = self.desugar_ce_statements(builder, body)?;
+        let mut desugared = self.desugar_ce_statements(builder, body)?;
+
+        // A sequence runs its body only when enumerated
+        if builder == "seq" {
+            desugared = Self::builder_call(
+                builder,
+                "Delay",
+                vec![Expr::Lambda {
+                    param: "_".to_string(),
+                    param_ty: None,
+                    return_ty: None,
+                    body: Box::new(desugared),
+                }],
+            );
+        }
 
         // Compile the desugared expression
         self.compile_expr(&desugared)
@@ -1316,6 +2175,7 @@ impl Compiler {
                 let rest_expr = self.desugar_ce_statements(builder, rest)?;
                 Ok(Expr::Let {
                     name: name.clone(),
+                    ty: None,
                     value: value.clone(),
                     body: Box::new(rest_expr),
                 })
@@ -1329,6 +2189,8 @@ impl Compiler {
 
                 let continuation = Expr::Lambda {
                     param: name.clone(),
+                    param_ty: None,
+                    return_ty: None,
                     body: Box::new(rest_expr),
                 };
 
@@ -1347,6 +2209,8 @@ impl Compiler {
                 // Use wildcard/dummy param for unit
                 let continuation = Expr::Lambda {
                     param: "_".to_string(),
+                    param_ty: None,
+                    return_ty: None,
                     body: Box::new(rest_expr),
                 };
 
@@ -1378,67 +2242,21 @@ impl Compiler {
             crate::ast::CEStatement::Yield { value } => {
                 // yield expr
                 // -> builder.Yield(expr)
-                let yield_expr = Expr::MethodCall {
-                    receiver: builder_expr.clone(),
-                    method_name: "Yield".to_string(),
-                    args: vec![*value.clone()],
-                };
-
-                if rest.is_empty() {
-                    Ok(yield_expr)
-                } else {
-                    // builder.Combine(yield_expr, builder.Delay(fun () -> desugar(rest)))
-                    let rest_expr = self.desugar_ce_statements(builder, rest)?;
-
-                    let delay_lambda = Expr::Lambda {
-                        param: "_".to_string(),
-                        body: Box::new(rest_expr),
-                    };
-
-                    let delay_expr = Expr::MethodCall {
-                        receiver: builder_expr.clone(),
-                        method_name: "Delay".to_string(),
-                        args: vec![delay_lambda],
-                    };
-
-                    Ok(Expr::MethodCall {
-                        receiver: builder_expr,
-                        method_name: "Combine".to_string(),
-                        args: vec![yield_expr, delay_expr],
-                    })
-                }
+                let yield_expr = Self::builder_call(builder, "Yield", vec![*value.clone()]);
+                self.combine_with_rest(builder, yield_expr, rest)
             }
             crate::ast::CEStatement::YieldBang { value } => {
                 // yield! expr
                 // -> builder.YieldFrom(expr)
-                let yield_from_expr = Expr::MethodCall {
-                    receiver: builder_expr.clone(),
-                    method_name: "YieldFrom".to_string(),
-                    args: vec![*value.clone()],
-                };
-
-                if rest.is_empty() {
-                    Ok(yield_from_expr)
-                } else {
-                    let rest_expr = self.desugar_ce_statements(builder, rest)?;
-
-                    let delay_lambda = Expr::Lambda {
-                        param: "_".to_string(),
-                        body: Box::new(rest_expr),
-                    };
-
-                    let delay_expr = Expr::MethodCall {
-                        receiver: builder_expr.clone(),
-                        method_name: "Delay".to_string(),
-                        args: vec![delay_lambda],
-                    };
-
-                    Ok(Expr::MethodCall {
-                        receiver: builder_expr,
-                        method_name: "Combine".to_string(),
-                        args: vec![yield_from_expr, delay_expr],
-                    })
-                }
+                let source = Self::ce_source(builder, *value.clone());
+                let yield_from_expr = Self::builder_call(builder, "YieldFrom", vec![source]);
+                self.combine_with_rest(builder, yield_from_expr, rest)
+            }
+            crate::ast::CEStatement::Expr { value } if Self::ce_expr_yields(value) => {
+                // for x in xs do yield ...; if c then yield ...
+                // -> builder.For(xs, fun x -> ...), if c then ... else builder.Zero()
+                let computation = self.desugar_ce_expr(builder, value);
+                self.combine_with_rest(builder, computation, rest)
             }
             crate::ast::CEStatement::Expr { value } => {
                 // expr; rest...
@@ -1453,6 +2271,7 @@ impl Compiler {
 
                     Ok(Expr::Let {
                         name: "_".to_string(),
+                        ty: None,
                         value: value.clone(),
                         body: Box::new(rest_expr),
                     })
@@ -1461,75 +2280,319 @@ impl Compiler {
         }
     }
 
-    /// Compile a match expression with full pattern matching support
-    fn compile_match(&mut self, scrutinee: &Expr, arms: &[MatchArm]) -> CompileResult<()> {
-        // Compile scrutinee once and keep it on the stack
-        self.compile_expr(scrutinee)?;
+    /// `builder.method(args)`
+    fn builder_call(builder: &str, method: &str, args: Vec<Expr>) -> Expr {
+        Expr::MethodCall {
+            receiver: Box::new(Expr::Var(builder.to_string())),
+            method_name: method.to_string(),
+            args,
+        }
+    }
 
-        let _end_label = self.chunk.current_offset();
-        let mut end_jumps = Vec::new();
+    /// The source a computation expression loop or `yield!` walks
+    ///
+    /// In a sequence, a range becomes `seq.Range(start, step, finish)`,
+    /// which counts lazily rather than building the whole list first.
+    fn ce_source(builder: &str, source: Expr) -> Expr {
+        match source {
+            Expr::Range {
+                start,
+                step,
+                finish,
+            } if builder == "seq" => {
+                let step = step.map_or(Expr::Lit(Literal::Int(1)), |step| *step);
+                Self::builder_call(builder, "Range", vec![*start, step, *finish])
+            }
+            source => source,
+        }
+    }
 
-        for (i, arm) in arms.iter().enumerate() {
-            let is_last_arm = i == arms.len() - 1;
+    /// Follow a computation with the rest of the statements
+    ///
+    /// -> builder.Combine(first, builder.Delay(fun () -> desugar(rest)))
+    fn combine_with_rest(
+        &self,
+        builder: &str,
+        first: Expr,
+        rest: &[crate::ast::CEStatement],
+    ) -> CompileResult<Expr> {
+        if rest.is_empty() {
+            return Ok(first);
+        }
+        let rest_expr = self.desugar_ce_statements(builder, rest)?;
+        let delay_lambda = Expr::Lambda {
+            param: "_".to_string(),
+            param_ty: None,
+            return_ty: None,
+            body: Box::new(rest_expr),
+        };
+        let delay_expr = Self::builder_call(builder, "Delay", vec![delay_lambda]);
+        Ok(Self::builder_call(
+            builder,
+            "Combine",
+            vec![first, delay_expr],
+        ))
+    }
+
+    /// Whether a computation expression statement yields through loops,
+    /// conditionals, bindings or matches (but not inside nested functions)
+    fn ce_expr_yields(expr: &Expr) -> bool {
+        match expr {
+            Expr::Yield(_) | Expr::YieldBang(_) => true,
+            Expr::For { body, .. } | Expr::ForIn { body, .. } => Self::ce_expr_yields(body),
+            Expr::If {
+                then_branch,
+                else_branch,
+                ..
+            } => Self::ce_expr_yields(then_branch) || Self::ce_expr_yields(else_branch),
+            Expr::Let { body, .. } | Expr::LetRec { body, .. } => Self::ce_expr_yields(body),
+            Expr::Match { arms, .. } => arms.iter().any(|arm| Self::ce_expr_yields(&arm.body)),
+            _ => false,
+        }
+    }
 
-            // Duplicate scrutinee for pattern test (except we'll clean it up)
-            if !is_last_arm {
-                self.emit(Instruction::Dup);
+    /// Desugar a yielding statement into builder calls
+    ///
+    /// Loops become `builder.For(source, fun x -> body)` and branches that
+    /// do not yield become `builder.Zero()`.
+    fn desugar_ce_expr(&self, builder: &str, expr: &Expr) -> Expr {
+        let body_of = |body: &Expr| Box::new(self.desugar_ce_expr(builder, body));
+        match expr {
+            Expr::Yield(value) => Self::builder_call(builder, "Yield", vec![*value.clone()]),
+            Expr::YieldBang(source) => Self::builder_call(
+                builder,
+                "YieldFrom",
+                vec![Self::ce_source(builder, *source.clone())],
+            ),
+            Expr::For {
+                var,
+                start,
+                finish,
+                descending,
+                body,
+            } => {
+                let range = Self::ce_source(
+                    builder,
+                    Expr::Range {
+                        start: start.clone(),
+                        step: descending.then(|| Box::new(Expr::Lit(Literal::Int(-1)))),
+                        finish: finish.clone(),
+                    },
+                );
+                let loop_body = Expr::Lambda {
+                    param: var.clone(),
+                    param_ty: None,
+                    return_ty: None,
+                    body: body_of(body),
+                };
+                Self::builder_call(builder, "For", vec![range, loop_body])
+            }
+            Expr::ForIn {
+                pattern,
+                source,
+                body,
+            } => {
+                let loop_body = match pattern {
+                    Pattern::Var(name) => Expr::Lambda {
+                        param: name.clone(),
+                        param_ty: None,
+                        return_ty: None,
+                        body: body_of(body),
+                    },
+                    Pattern::Wildcard => Expr::Lambda {
+                        param: "_".to_string(),
+                        param_ty: None,
+                        return_ty: None,
+                        body: body_of(body),
+                    },
+                    _ => {
+                        // As in for loops, elements the pattern does not match are skipped
+                        let mut arms = vec![MatchArm {
+                            pattern: pattern.clone(),
+                            body: body_of(body),
+                            guard: None,
+                        }];
+                        if !check_match(&arms, &self.constructors).is_exhaustive() {
+                            arms.push(MatchArm {
+                                pattern: Pattern::Wildcard,
+                                body: Box::new(Self::builder_call(builder, "Zero", vec![])),
+                                guard: None,
+                            });
+                        }
+                        // '$' cannot start an identifier, so user code can never see it
+                        Expr::Lambda {
+                            param: "$item".to_string(),
+                            param_ty: None,
+                            return_ty: None,
+                            body: Box::new(Expr::Match {
+                                scrutinee: Box::new(Expr::Var("$item".to_string())),
+                                arms,
+                            }),
+                        }
+                    }
+                };
+                let source = Self::ce_source(builder, *source.clone());
+                Self::builder_call(builder, "For", vec![source, loop_body])
             }
+            Expr::If {
+                cond,
+                then_branch,
+                else_branch,
+            } => Expr::If {
+                cond: cond.clone(),
+                then_branch: body_of(then_branch),
+                else_branch: body_of(else_branch),
+            },
+            Expr::Let {
+                name,
+                ty,
+                value,
+                body,
+            } => Expr::Let {
+                name: name.clone(),
+                ty: ty.clone(),
+                value: value.clone(),
+                body: body_of(body),
+            },
+            Expr::LetRec { name, value, body } => Expr::LetRec {
+                name: name.clone(),
+                value: value.clone(),
+                body: body_of(body),
+            },
+            Expr::Match { scrutinee, arms } => Expr::Match {
+                scrutinee: scrutinee.clone(),
+                arms: arms
+                    .iter()
+                    .map(|arm| MatchArm {
+                        pattern: arm.pattern.clone(),
+                        body: body_of(&arm.body),
+                        guard: arm.guard.clone(),
+                    })
+                    .collect(),
+            },
+            Expr::Lit(Literal::Unit) => Self::builder_call(builder, "Zero", vec![]),
+            // Runs for its effect and yields nothing
+            _ => Expr::Let {
+                name: "_".to_string(),
+                ty: None,
+                value: Box::new(expr.clone()),
+                body: Box::new(Self::builder_call(builder, "Zero", vec![])),
+            },
+        }
+    }
 
-            // Compile pattern test - this will push a boolean result
-            let _next_arm_offset = if !is_last_arm {
-                self.chunk.current_offset()
-            } else {
-                0 // Last arm doesn't need a jump
-            };
+    /// Check a match for missing and unreachable patterns
+    ///
+    /// Findings are recorded as warnings, or returned as an error when the
+    /// compile options do not allow warnings.
+    fn check_match_arms(&mut self, arms: &[MatchArm]) -> CompileResult<()> {
+        let report = check_match(arms, &self.constructors);
+
+        let mut found = Vec::new();
+        if !report.is_exhaustive() {
+            found.push(CompileWarning::IncompleteMatch {
+                missing: report.missing_patterns(),
+            });
+        }
+        for arm_index in report.unreachable_arms {
+            found.push(CompileWarning::UnreachableArm {
+                arm_index,
+                pattern: arms[arm_index].pattern.to_string(),
+            });
+        }
 
-            // Test the pattern and get a boolean result
-            self.compile_pattern_test(&arm.pattern)?;
+        for warning in found {
+            self.warn(warning)?;
+        }
+        Ok(())
+    }
 
-            // Jump to next arm if pattern didn't match
-            let jump_to_next = if !is_last_arm {
-                self.emit_jump(Instruction::JumpIfFalse(0))
-            } else {
-                // Pop the boolean since it's not consumed by jump
-                self.emit(Instruction::Pop);
-                0
-            };
+    /// Record a warning, or fail if warnings are not allowed
+    fn warn(&mut self, warning: CompileWarning) -> CompileResult<()> {
+        if self.options.strict_mode || !self.options.allow_warnings {
+            return Err(CompileError::WarningAsError(warning));
+        }
+        self.warnings.push(warning);
+        Ok(())
+    }
 
-            // Pattern matched - now bind variables from the pattern
-            // We still have the scrutinee on the stack
-            if !is_last_arm {
-                self.emit(Instruction::Dup); // Keep scrutinee for binding
-            }
+    /// Compile a match expression with full pattern matching support
+    fn compile_match(
+        &mut self,
+        scrutinee: &Expr,
+        arms: &[MatchArm],
+        tail: bool,
+    ) -> CompileResult<()> {
+        self.check_match_arms(arms)?;
+
+        // Compile scrutinee once and park it in a hidden local, so every arm
+        // starts from the same stack height whichever way the previous arm failed
+        self.compile_expr(scrutinee)?;
+        self.begin_scope();
+        let scrutinee_slot = self.add_hidden_local()?;
+        self.emit(Instruction::StoreLocal(scrutinee_slot));
+
+        self.compile_match_arms(scrutinee_slot, arms, Instruction::MatchFailure, tail)?;
+
+        self.end_scope();
+
+        Ok(())
+    }
+
+    /// Compile match arms against the value held in local `slot`
+    ///
+    /// If every arm rejects the value it is pushed and `unmatched` is emitted
+    /// (`MatchFailure` for match expressions, `Raise` for exception handlers).
+    fn compile_match_arms(
+        &mut self,
+        slot: u8,
+        arms: &[MatchArm],
+        unmatched: Instruction,
+        tail: bool,
+    ) -> CompileResult<()> {
+        let mut end_jumps = Vec::new();
+        let mut last_arm_can_fail = false;
 
+        for arm in arms {
             // Enter a new scope for pattern bindings
             self.begin_scope();
 
-            // Bind variables from the pattern
-            self.compile_pattern_bindings(&arm.pattern)?;
+            // Declare every variable up front so all alternatives of an
+            // or-pattern store into the same slots
+            for name in arm.pattern.bound_vars() {
+                self.add_local(name.to_string())?;
+            }
 
-            // Compile arm body
-            self.compile_expr(&arm.body)?;
+            // Test the pattern and bind its variables
+            let mut fails = Vec::new();
+            self.compile_pattern(&arm.pattern, slot, &mut fails)?;
 
-            // Exit scope for pattern bindings
-            let locals_to_remove = self.end_scope_count();
-            for _ in 0..locals_to_remove {
-                self.locals.pop();
+            // Guard sees the pattern bindings; on failure move to the next arm
+            if let Some(guard) = &arm.guard {
+                self.compile_expr(guard)?;
+                fails.push((self.emit_jump(Instruction::JumpIfFalse(0)), false));
             }
-            self.scope_depth -= 1;
 
-            // Jump to end of match expression
-            let jump_to_end = self.emit_jump(Instruction::Jump(0));
-            end_jumps.push(jump_to_end);
+            // Compile arm body
+            self.compile_tail_expr(&arm.body, tail)?;
 
-            // Patch jump to next arm (if not last)
-            if !is_last_arm {
-                self.patch_jump(jump_to_next)?;
-                // Pop the scrutinee since this arm didn't match
-                self.emit(Instruction::Pop);
+            // Exit scope for pattern bindings
+            self.end_scope();
+
+            // Jump to end of match expression, or fall into the next arm
+            last_arm_can_fail = !fails.is_empty();
+            if last_arm_can_fail {
+                end_jumps.push(self.emit_jump(Instruction::Jump(0)));
+                self.patch_pattern_fails(fails)?;
             }
         }
 
+        // Every arm was rejected
+        if last_arm_can_fail {
+            self.emit(Instruction::LoadLocal(slot));
+            self.emit(unmatched);
+        }
+
         // Patch all end jumps to point here
         for jump_idx in end_jumps {
             self.patch_jump(jump_idx)?;
@@ -1538,111 +2601,324 @@ impl Compiler {
         Ok(())
     }
 
-    /// Compile a pattern test - checks if scrutinee matches pattern
-    /// Expects scrutinee on top of stack, pushes boolean result
-    fn compile_pattern_test(&mut self, pattern: &Pattern) -> CompileResult<()> {
+    /// Compile try ... with
+    ///
+    /// ```text
+    ///     PUSH_HANDLER catch
+    ///     <body>
+    ///     POP_HANDLER
+    ///     JUMP end
+    /// catch:                ; raised value on the stack
+    ///     <arms>            ; re-raised if no arm matches
+    /// end:
+    /// ```
+    fn compile_try_with(&mut self, body: &Expr, arms: &[MatchArm]) -> CompileResult<()> {
+        let handler = self.emit_jump(Instruction::PushHandler(0));
+        self.handler_stack.push(None);
+        self.compile_expr(body)?;
+        self.handler_stack.pop();
+        self.emit(Instruction::PopHandler);
+        let end_jump = self.emit_jump(Instruction::Jump(0));
+
+        self.patch_jump(handler)?;
+        self.begin_scope();
+        let exn_slot = self.add_hidden_local()?;
+        // The body's locals were abandoned mid-scope
+        self.emit(Instruction::CloseUpvalue(exn_slot));
+        self.emit(Instruction::StoreLocal(exn_slot));
+        self.compile_match_arms(exn_slot, arms, Instruction::Raise, false)?;
+
+        self.end_scope();
+
+        self.patch_jump(end_jump)
+    }
+
+    /// Compile try ... finally
+    ///
+    /// The finalizer is emitted twice: once after the body completes, and
+    /// once in the handler, which raises the exception again afterwards.
+    fn compile_try_finally(&mut self, body: &Expr, finalizer: &Expr) -> CompileResult<()> {
+        let handler = self.emit_jump(Instruction::PushHandler(0));
+        self.handler_stack.push(Some(finalizer.clone()));
+        self.compile_expr(body)?;
+        self.handler_stack.pop();
+        self.emit(Instruction::PopHandler);
+        self.compile_expr(finalizer)?;
+        self.emit(Instruction::Pop);
+        let end_jump = self.emit_jump(Instruction::Jump(0));
+
+        self.patch_jump(handler)?;
+        self.begin_scope();
+        let exn_slot = self.add_hidden_local()?;
+        self.emit(Instruction::CloseUpvalue(exn_slot));
+        self.emit(Instruction::StoreLocal(exn_slot));
+        self.compile_expr(finalizer)?;
+        self.emit(Instruction::Pop);
+        self.emit(Instruction::LoadLocal(exn_slot));
+        self.emit(Instruction::Raise);
+
+        self.end_scope();
+
+        self.patch_jump(end_jump)
+    }
+
+    /// Compile a pattern against the value held in local `slot`
+    ///
+    /// Binds the pattern's variables (which must already be declared) and
+    /// records a jump for every point where the match can fail. Each jump is
+    /// paired with a flag that is true when the tested value is still on the
+    /// stack at that point; the stack is otherwise left unchanged.
+    fn compile_pattern(
+        &mut self,
+        pattern: &Pattern,
+        slot: u8,
+        fails: &mut Vec<(usize, bool)>,
+    ) -> CompileResult<()> {
         match pattern {
-            Pattern::Wildcard | Pattern::Var(_) => {
-                // Always matches - push true
-                let true_idx = self.add_constant(Value::Bool(true))?;
-                self.emit(Instruction::LoadConst(true_idx));
+            Pattern::Wildcard => Ok(()),
+            Pattern::Var(name) => {
+                self.emit(Instruction::LoadLocal(slot));
+                self.store_pattern_var(name)
+            }
+            Pattern::Literal(lit) => {
+                self.emit(Instruction::LoadLocal(slot));
+                match lit {
+                    Literal::Int(n) => self.emit(Instruction::CheckInt(*n)),
+                    Literal::Bool(b) => self.emit(Instruction::CheckBool(*b)),
+                    Literal::Str(s) => self.emit(Instruction::CheckString(s.clone())),
+                    Literal::Float(_) | Literal::Char(_) | Literal::Bytes(_) | Literal::Unit => {
+                        // No dedicated check: compare for equality instead
+                        self.compile_literal(lit)?;
+                        self.emit(Instruction::Eq);
+                        fails.push((self.emit_jump(Instruction::JumpIfFalse(0)), false));
+                        return Ok(());
+                    }
+                }
+                // CheckInt/CheckBool/CheckString leave the value below the result
+                fails.push((self.emit_jump(Instruction::JumpIfFalse(0)), true));
+                self.emit(Instruction::Pop);
                 Ok(())
             }
-            Pattern::Literal(Literal::Int(n)) => {
-                self.emit(Instruction::CheckInt(*n));
+            Pattern::Tuple(patterns) => {
+                if patterns.len() > u8::MAX as usize {
+                    return Err(CompileError::TupleTooLarge);
+                }
+                self.emit(Instruction::LoadLocal(slot));
+                self.emit(Instruction::CheckTupleLen(patterns.len() as u8));
+                fails.push((self.emit_jump(Instruction::JumpIfFalse(0)), true));
+                self.emit(Instruction::Pop);
+
+                for (i, pat) in patterns.iter().enumerate() {
+                    self.emit(Instruction::LoadLocal(slot));
+                    self.emit(Instruction::GetTupleField(i as u8));
+                    self.compile_subpattern(pat, fails)?;
+                }
                 Ok(())
             }
-            Pattern::Literal(Literal::Bool(b)) => {
-                self.emit(Instruction::CheckBool(*b));
+            Pattern::Variant { variant, patterns } => {
+                self.emit(Instruction::LoadLocal(slot));
+                self.emit(Instruction::CheckVariantTag(variant.clone()));
+                fails.push((self.emit_jump(Instruction::JumpIfFalse(0)), false));
+
+                for (i, pat) in patterns.iter().enumerate() {
+                    self.emit(Instruction::LoadLocal(slot));
+                    self.emit(Instruction::GetVariantField(i as u8));
+                    self.compile_subpattern(pat, fails)?;
+                }
                 Ok(())
             }
-            Pattern::Literal(Literal::Str(s)) => {
-                self.emit(Instruction::CheckString(s.clone()));
+            Pattern::List(patterns) => {
+                // Walk the spine one cell at a time, then require the end
+                let mut cell = slot;
+                for pat in patterns {
+                    self.emit_cons_check(cell, fails);
+                    self.emit(Instruction::LoadLocal(cell));
+                    self.emit(Instruction::ListHead);
+                    self.compile_subpattern(pat, fails)?;
+
+                    self.emit(Instruction::LoadLocal(cell));
+                    self.emit(Instruction::ListTail);
+                    cell = self.add_hidden_local()?;
+                    self.emit(Instruction::StoreLocal(cell));
+                }
+                self.emit(Instruction::LoadLocal(cell));
+                self.emit(Instruction::IsNil);
+                fails.push((self.emit_jump(Instruction::JumpIfFalse(0)), false));
                 Ok(())
             }
-            Pattern::Literal(Literal::Unit) => {
-                // Check if value equals Unit
-                let unit_idx = self.add_constant(Value::Unit)?;
-                self.emit(Instruction::LoadConst(unit_idx));
+            Pattern::Cons { head, tail } => {
+                self.emit_cons_check(slot, fails);
+                self.emit(Instruction::LoadLocal(slot));
+                self.emit(Instruction::ListHead);
+                self.compile_subpattern(head, fails)?;
+                self.emit(Instruction::LoadLocal(slot));
+                self.emit(Instruction::ListTail);
+                self.compile_subpattern(tail, fails)
+            }
+            Pattern::Array(patterns) => {
+                let len_idx = self.add_constant(Value::Int(patterns.len() as i64))?;
+                self.emit(Instruction::LoadLocal(slot));
+                self.emit(Instruction::ArrayLength);
+                self.emit(Instruction::LoadConst(len_idx));
                 self.emit(Instruction::Eq);
+                fails.push((self.emit_jump(Instruction::JumpIfFalse(0)), false));
+
+                for (i, pat) in patterns.iter().enumerate() {
+                    let idx = self.add_constant(Value::Int(i as i64))?;
+                    self.emit(Instruction::LoadLocal(slot));
+                    self.emit(Instruction::LoadConst(idx));
+                    self.emit(Instruction::ArrayGet);
+                    self.compile_subpattern(pat, fails)?;
+                }
                 Ok(())
             }
-            Pattern::Literal(Literal::Float(f)) => {
-                // Check if value equals the float
-                let float_idx = self.add_constant(Value::Float(*f))?;
-                self.emit(Instruction::LoadConst(float_idx));
-                self.emit(Instruction::Eq);
+            Pattern::Record(fields) => {
+                for (name, pat) in fields {
+                    let idx = self.add_constant(Value::Str(name.clone()))?;
+                    self.emit(Instruction::LoadLocal(slot));
+                    self.emit(Instruction::LoadConst(idx));
+                    self.emit(Instruction::GetRecordField);
+                    self.compile_subpattern(pat, fails)?;
+                }
                 Ok(())
             }
-            Pattern::Tuple(patterns) => {
-                // Check tuple length first
-                self.emit(Instruction::Dup);
-                self.emit(Instruction::CheckTupleLen(patterns.len() as u8));
+            Pattern::Or(alternatives) => {
+                let Some((last, rest)) = alternatives.split_last() else {
+                    return Ok(());
+                };
 
-                // If not a tuple of right length, we're done (false on stack)
-                // If it is, we need to check each element
-                if !patterns.is_empty() {
-                    // We have length check result on stack
-                    // For now, simplified: just check length
-                    // Full nested pattern checking would require more complex control flow
+                let expected = Self::sorted_vars(last);
+                let mut matched_jumps = Vec::new();
+                for alternative in rest {
+                    if Self::sorted_vars(alternative) != expected {
+                        return Err(CompileError::CodeGenError(format!(
+                            "The alternatives of or-pattern '{}' must bind the same variables",
+                            pattern
+                        )));
+                    }
+
+                    // A failing alternative falls through to the next one
+                    let mut alt_fails = Vec::new();
+                    self.compile_pattern(alternative, slot, &mut alt_fails)?;
+                    matched_jumps.push(self.emit_jump(Instruction::Jump(0)));
+                    self.patch_pattern_fails(alt_fails)?;
                 }
 
+                self.compile_pattern(last, slot, fails)?;
+                for jump in matched_jumps {
+                    self.patch_jump(jump)?;
+                }
                 Ok(())
             }
-            Pattern::Variant {
-                variant,
-                patterns: _,
+            Pattern::As { pattern, name } => {
+                self.emit(Instruction::LoadLocal(slot));
+                self.store_pattern_var(name)?;
+                self.compile_pattern(pattern, slot, fails)
+            }
+            Pattern::Active {
+                function,
+                case,
+                patterns,
             } => {
-                // Check variant tag
-                self.emit(Instruction::Dup);
-                self.emit(Instruction::CheckVariantTag(variant.clone()));
-                Ok(())
+                // Apply the active pattern function to the value
+                self.compile_expr(&Expr::Var(function.clone()))?;
+                self.emit(Instruction::LoadLocal(slot));
+                self.emit(Instruction::Call(1));
+                let result = self.add_hidden_local()?;
+                self.emit(Instruction::StoreLocal(result));
+
+                let variant = match active_pattern_cases(function) {
+                    // Single case: the result itself
+                    Some((cases, false)) if cases.len() == 1 => {
+                        return self.compile_pattern(
+                            &active_result_pattern(patterns),
+                            result,
+                            fails,
+                        );
+                    }
+                    // Partial: Some carries the result, None fails
+                    Some((_, true)) => "Some".to_string(),
+                    // Multi-case: the result is a variant named after the case
+                    _ => case.clone(),
+                };
+                self.compile_pattern(
+                    &Pattern::Variant {
+                        variant,
+                        patterns: patterns.clone(),
+                    },
+                    result,
+                    fails,
+                )
             }
         }
     }
 
-    /// Compile pattern bindings - extracts values from scrutinee and stores in locals
-    /// Expects scrutinee on top of stack, consumes it
-    fn compile_pattern_bindings(&mut self, pattern: &Pattern) -> CompileResult<()> {
+    /// Compile a pattern against the value on top of the stack, consuming it
+    fn compile_subpattern(
+        &mut self,
+        pattern: &Pattern,
+        fails: &mut Vec<(usize, bool)>,
+    ) -> CompileResult<()> {
         match pattern {
-            Pattern::Wildcard | Pattern::Literal(_) => {
-                // No bindings, just pop the scrutinee
+            Pattern::Wildcard => {
                 self.emit(Instruction::Pop);
                 Ok(())
             }
-            Pattern::Var(name) => {
-                // Bind the scrutinee to this variable
-                self.add_local(name.clone())?;
-                let local_idx = (self.locals.len() - 1) as u8;
-                self.emit(Instruction::StoreLocal(local_idx));
-                Ok(())
-            }
-            Pattern::Tuple(patterns) => {
-                // Extract each element and bind recursively
-                for (i, pat) in patterns.iter().enumerate() {
-                    self.emit(Instruction::Dup); // Dup the tuple
-                    self.emit(Instruction::GetTupleElem(i as u8)); // Get element
-                    self.compile_pattern_bindings(pat)?; // Bind it
-                }
-                // Pop the original tuple
-                self.emit(Instruction::Pop);
-                Ok(())
+            Pattern::Var(name) => self.store_pattern_var(name),
+            _ => {
+                let slot = self.add_hidden_local()?;
+                self.emit(Instruction::StoreLocal(slot));
+                self.compile_pattern(pattern, slot, fails)
             }
-            Pattern::Variant {
-                variant: _,
-                patterns,
-            } => {
-                // Extract each field from the variant and bind recursively
-                for (i, pat) in patterns.iter().enumerate() {
-                    self.emit(Instruction::Dup); // Dup the variant
-                    self.emit(Instruction::GetVariantField(i as u8)); // Get field
-                    self.compile_pattern_bindings(pat)?; // Bind it
-                }
-                // Pop the original variant
-                self.emit(Instruction::Pop);
-                Ok(())
+        }
+    }
+
+    /// Fail unless the list in local `slot` is non-empty
+    fn emit_cons_check(&mut self, slot: u8, fails: &mut Vec<(usize, bool)>) {
+        self.emit(Instruction::LoadLocal(slot));
+        self.emit(Instruction::IsNil);
+        self.emit(Instruction::Not);
+        fails.push((self.emit_jump(Instruction::JumpIfFalse(0)), false));
+    }
+
+    /// Land every pattern failure jump here with the tested values popped
+    fn patch_pattern_fails(&mut self, fails: Vec<(usize, bool)>) -> CompileResult<()> {
+        let (with_value, clean): (Vec<_>, Vec<_>) = fails.into_iter().partition(|(_, pop)| *pop);
+
+        if !with_value.is_empty() {
+            for (jump, _) in with_value {
+                self.patch_jump(jump)?;
             }
+            self.emit(Instruction::Pop);
+        }
+        for (jump, _) in clean {
+            self.patch_jump(jump)?;
         }
+        Ok(())
+    }
+
+    /// Pop the top of the stack into a variable declared for the current pattern
+    fn store_pattern_var(&mut self, name: &str) -> CompileResult<()> {
+        let idx = self
+            .locals
+            .iter()
+            .rposition(|local| local.name == name)
+            .ok_or_else(|| CompileError::UndefinedVariable(name.to_string()))?;
+        self.emit(Instruction::StoreLocal(idx as u8));
+        Ok(())
+    }
+
+    /// Reserve an unnamed local slot for compiler temporaries
+    fn add_hidden_local(&mut self) -> CompileResult<u8> {
+        // '$' cannot start an identifier, so user code can never see it
+        self.add_local("$pattern".to_string())?;
+        Ok((self.locals.len() - 1) as u8)
+    }
+
+    fn sorted_vars(pattern: &Pattern) -> Vec<&str> {
+        let mut vars = pattern.bound_vars();
+        vars.sort_unstable();
+        vars
     }
 
     /// Emit an instruction
@@ -1707,6 +2983,9 @@ impl Compiler {
             Instruction::JumpIfFalse(_) => {
                 self.chunk.instructions[jump_index] = Instruction::JumpIfFalse(jump_offset as i16);
             }
+            Instruction::PushHandler(_) => {
+                self.chunk.instructions[jump_index] = Instruction::PushHandler(jump_offset as i16);
+            }
             _ => unreachable!("patch_jump called on non-jump instruction"),
         }
 
@@ -1736,6 +3015,63 @@ impl Compiler {
             .count()
     }
 
+    /// End the current scope and drop its locals
+    ///
+    /// If a closure captured any of them, their upvalues are closed so the
+    /// slots can be reused without the closure seeing the new values.
+    fn end_scope(&mut self) {
+        let first = self.locals.len() - self.end_scope_count();
+        if self.locals[first..].iter().any(|local| local.captured) {
+            self.emit(Instruction::CloseUpvalue(first as u8));
+        }
+        self.locals.truncate(first);
+        self.scope_depth -= 1;
+    }
+
+    /// Slot of the innermost local named `name`
+    fn resolve_local(&self, name: &str) -> Option<u8> {
+        self.locals
+            .iter()
+            .rposition(|local| local.name == name)
+            .map(|slot| slot as u8)
+    }
+
+    /// Mutability of the innermost variable `name` of an enclosing function
+    fn enclosing_var(&self, name: &str) -> Option<bool> {
+        self.enclosing
+            .iter()
+            .rev()
+            .find(|(var, _)| var == name)
+            .map(|(_, mutable)| *mutable)
+    }
+
+    /// Upvalue index for a variable of an enclosing function, adding it to
+    /// this function's captures on first use
+    fn resolve_upvalue(&mut self, name: &str) -> CompileResult<Option<u8>> {
+        if self.enclosing_var(name).is_none() {
+            return Ok(None);
+        }
+        if let Some(idx) = self.upvalues.iter().position(|var| var == name) {
+            return Ok(Some(idx as u8));
+        }
+        if self.upvalues.len() >= u8::MAX as usize {
+            return Err(CompileError::TooManyLocals);
+        }
+        self.upvalues.push(name.to_string());
+        Ok(Some((self.upvalues.len() - 1) as u8))
+    }
+
+    /// Where a nested function finds the variable `name` it captures
+    fn capture_variable(&mut self, name: &str) -> CompileResult<UpvalueSource> {
+        if let Some(slot) = self.resolve_local(name) {
+            self.locals[slot as usize].captured = true;
+            return Ok(UpvalueSource::Local(slot));
+        }
+        self.resolve_upvalue(name)?
+            .map(UpvalueSource::Upvalue)
+            .ok_or_else(|| CompileError::UndefinedVariable(name.to_string()))
+    }
+
     /// Add a local variable
     fn add_local(&mut self, name: String) -> CompileResult<()> {
         if self.locals.len() >= u8::MAX as usize {
@@ -1745,10 +3081,26 @@ impl Compiler {
         self.locals.push(Local {
             name,
             depth: self.scope_depth,
+            mutable: false,
+            captured: false,
         });
 
+        // Track the high-water mark so the VM can reserve every slot up front
+        let slots = self.locals.len() as u8;
+        if slots > self.chunk.local_count {
+            self.chunk.local_count = slots;
+        }
+
         Ok(())
     }
+
+    /// Add a local declared with `let mutable`, returning its slot
+    fn add_mutable_local(&mut self, name: String) -> CompileResult<u8> {
+        self.add_local(name)?;
+        let slot = self.locals.len() - 1;
+        self.locals[slot].mutable = true;
+        Ok(slot as u8)
+    }
     /// Compile a record literal expression
     /// Stack effect: pushes a record value
     fn compile_record_literal(&mut self, fields: &[(String, Box<Expr>)]) -> CompileResult<()> {
@@ -1775,9 +3127,73 @@ impl Compiler {
         Ok(())
     }
 
+    /// Register the members declared by a type definition with the VM
+    ///
+    /// Static members and instance members are passed as two records; an
+    /// instance member takes its receiver as the first argument.
+    /// Stack effect: none. Types without members compile to nothing.
+    fn compile_type_members(&mut self, type_def: &TypeDefinition) -> CompileResult<()> {
+        // The VM recognizes values of the type by their field or case names
+        let (type_name, names, is_union): (_, Vec<&String>, _) = match type_def {
+            TypeDefinition::Record(r) => {
+                (&r.name, r.fields.iter().map(|(n, _)| n).collect(), false)
+            }
+            TypeDefinition::Du(du) => (
+                &du.name,
+                du.variants.iter().map(|v| &v.name).collect(),
+                true,
+            ),
+            TypeDefinition::Provider(_) | TypeDefinition::Measure(_) => return Ok(()),
+        };
+        let members = type_def.members();
+        if members.is_empty() {
+            return Ok(());
+        }
+
+        let names: Vec<Expr> = names
+            .into_iter()
+            .map(|name| Expr::Lit(Literal::Str(name.clone())))
+            .collect();
+        self.compile_list(&names)?;
+
+        let (statics, instance): (Vec<_>, Vec<_>) =
+            members.iter().partition(|member| member.is_static());
+        if !statics.is_empty() {
+            self.types_with_statics.insert(type_name.clone());
+        }
+        for group in [statics, instance] {
+            let fields: Vec<(String, Box<Expr>)> = group
+                .into_iter()
+                .map(|member| (member.name.clone(), Box::new(member.value.clone())))
+                .collect();
+            self.compile_record_literal(&fields)?;
+        }
+
+        let type_name_idx = self.add_constant(Value::Str(type_name.clone()))?;
+        self.emit(Instruction::DefineType(type_name_idx, is_union));
+        Ok(())
+    }
+
     /// Compile a record field access expression
     /// Stack effect: pushes the field value
     fn compile_record_access(&mut self, record: &Expr, field: &str) -> CompileResult<()> {
+        if let Expr::Var(name) = record {
+            if self.resolve_local(name).is_none() && self.enclosing_var(name).is_none() {
+                self.check_binding_access(name, field)?;
+                // `Lib.x` on a script module is a qualified name, not a field;
+                // stdlib placeholders keep reading the field of the runtime record
+                let qualified = format!("{}.{}", name, field);
+                if self
+                    .module_registry
+                    .as_ref()
+                    .and_then(|registry| registry.resolve_in_module(name, field))
+                    .is_some_and(|expr| !matches!(expr, Expr::Var(global) if *global == qualified))
+                {
+                    return self.compile_qualified_var(std::slice::from_ref(name), field);
+                }
+            }
+        }
+
         // Compile the record expression
         self.compile_expr(record)?;
 
@@ -1852,13 +3268,32 @@ impl Compiler {
                 .and_then(|r| r.resolve_qualified(module_name, method_name))
                 .is_some();
 
+            // A variable in scope, such as a user-defined builder record, is a real receiver
+            let in_scope = self.resolve_local(module_name).is_some()
+                || self.enclosing_var(module_name).is_some();
+
             // If not in module registry, assume it could be a runtime-registered host function
             // and compile as a function call instead of a method call
-            if !has_module_binding {
+            if !has_module_binding && !in_scope {
+                self.check_binding_access(module_name, method_name)?;
                 // Load the function by qualified name
                 let name_idx = self.add_constant(Value::Str(qualified_name))?;
                 self.emit(Instruction::LoadGlobal(name_idx));
 
+                // Static members of a script type are curried: `Config.Create()`
+                // passes unit, and `Config.WithPort(host, port)` one argument at a time
+                if self.types_with_statics.contains(module_name) {
+                    if args.is_empty() {
+                        self.compile_literal(&Literal::Unit)?;
+                        self.emit(Instruction::Call(1));
+                    }
+                    for arg in args {
+                        self.compile_expr(arg)?;
+                        self.emit(Instruction::Call(1));
+                    }
+                    return Ok(());
+                }
+
                 // Compile argument expressions
                 for arg in args {
                     self.compile_expr(arg)?;
@@ -1912,6 +3347,17 @@ fn parse_qualified_name(name: &str) -> Option<(Vec<String>, String)> {
     None
 }
 
+/// Mark a module from a `#load`ed file, and the modules nested in it, as
+/// loaded in the registry
+fn mark_loaded(registry: &mut ModuleRegistry, module: &ModuleDef) {
+    registry.mark_loaded(&module.name);
+    for item in &module.items {
+        if let ModuleItem::Module(nested) = item {
+            mark_loaded(registry, nested);
+        }
+    }
+}
+
 // Note: Tests remain unchanged from original file
 // They validate the compiler works with default options (no type checking)
 #[cfg(test)]
@@ -1934,6 +3380,38 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_compile_checked_rejects_ill_typed_expression() {
+        // 1 + "a"
+        let expr = Expr::BinOp {
+            op: BinOp::Add,
+            left: Box::new(Expr::Lit(Literal::Int(1))),
+            right: Box::new(Expr::Lit(Literal::Str("a".to_string()))),
+        };
+        assert!(Compiler::compile(&expr).is_ok());
+        assert!(matches!(
+            Compiler::compile_checked(&expr),
+            Err(CompileError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_compile_checked_leaves_host_functions_unchecked() {
+        // print (List.length [1])
+        let length = Expr::RecordAccess {
+            record: Box::new(Expr::Var("List".to_string())),
+            field: "length".to_string(),
+        };
+        let expr = Expr::App {
+            func: Box::new(Expr::Var("print".to_string())),
+            arg: Box::new(Expr::App {
+                func: Box::new(length),
+                arg: Box::new(Expr::List(vec![Expr::Lit(Literal::Int(1))])),
+            }),
+        };
+        assert!(Compiler::compile_checked(&expr).is_ok());
+    }
+
     #[test]
     fn test_compile_backwards_compatible() {
         let expr = Expr::Lit(Literal::Int(42));
@@ -70,6 +70,14 @@ pub enum TypeErrorKind {
         field: String,
     },
 
+    /// Member not declared by the type it is used on
+    MemberNotFound {
+        /// The name of the declared type
+        type_name: String,
+        /// The member name that wasn't found
+        member: String,
+    },
+
     /// Function arity mismatch
     ArityMismatch {
         /// Expected number of arguments
@@ -306,7 +314,13 @@ impl TypeError {
                 }
             }
             TypeErrorKind::NotAFunction { got } => match got {
-                Type::Int | Type::Bool | Type::String | Type::Unit | Type::Float => {
+                Type::Int
+                | Type::Bool
+                | Type::String
+                | Type::Char
+                | Type::Bytes
+                | Type::Unit
+                | Type::Float => {
                     Some("This is a value, not a function - did you mean to call a function instead?".to_string())
                 }
                 _ => Some("This expression is not a function and cannot be called".to_string()),
@@ -322,6 +336,10 @@ impl TypeError {
                     None
                 }
             }
+            TypeErrorKind::MemberNotFound { type_name, .. } => Some(format!(
+                "Declare the member in the `with` block of type '{}'",
+                type_name
+            )),
             TypeErrorKind::ArityMismatch { expected, got } => {
                 if got < expected {
                     Some(format!("You provided {} arguments but {} are required", got, expected))
@@ -426,6 +444,9 @@ impl fmt::Display for TypeError {
                     field, record_type
                 )
             }
+            TypeErrorKind::MemberNotFound { type_name, member } => {
+                write!(f, "Type '{}' has no member '{}'", type_name, member)
+            }
             TypeErrorKind::ArityMismatch { expected, got } => {
                 write!(
                     f,
@@ -637,6 +658,16 @@ mod tests {
         assert!(display.contains("Field 'y' not found"));
     }
 
+    #[test]
+    fn test_display_member_not_found() {
+        let err = TypeError::new(TypeErrorKind::MemberNotFound {
+            type_name: "Person".to_string(),
+            member: "Missing".to_string(),
+        });
+        let display = format!("{}", err);
+        assert!(display.contains("Type 'Person' has no member 'Missing'"));
+    }
+
     #[test]
     fn test_display_arity_mismatch() {
         let err = TypeError::new(TypeErrorKind::ArityMismatch {
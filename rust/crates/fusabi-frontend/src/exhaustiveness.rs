@@ -0,0 +1,849 @@
+//! Pattern Match Exhaustiveness and Redundancy Analysis
+//!
+//! This module checks `match` expressions for two classes of mistakes:
+//! - **Non-exhaustive matches**: some value of the scrutinee type is not covered
+//!   by any arm. The analysis reports example patterns that are missing, such as
+//!   `Circle(_)`.
+//! - **Unreachable arms**: an arm can never be selected because earlier arms
+//!   already cover every value it would match.
+//!
+//! Arms with a `when` guard are checked for reachability but do not count
+//! towards coverage, since the guard may reject the value.
+//!
+//! # Algorithm
+//!
+//! The analysis follows Maranget's "Warnings for pattern matching" usefulness
+//! algorithm. Patterns are lowered to a small constructor/wildcard form and
+//! checked column by column against a pattern matrix. Constructor sets are
+//! complete for tuples, records, unit, booleans, lists (`[]` and `::`) and
+//! discriminated unions whose cases are known to the [`ConstructorTable`].
+//! Literal patterns over ints, floats and strings, and fixed-length array
+//! patterns, can never be complete on their own. Or-patterns are expanded into
+//! one row per alternative. The cases of a total active pattern such as
+//! `(|Even|Odd|)` form a complete set; a partial one such as `(|Int|_|)` never
+//! covers every value.
+//!
+//! # Example
+//!
+//! ```rust
+//! use fusabi_frontend::ast::{Expr, Literal, MatchArm, Pattern};
+//! use fusabi_frontend::exhaustiveness::{check_match, ConstructorTable};
+//!
+//! let arms = vec![MatchArm::new(
+//!     Pattern::Variant { variant: "Some".to_string(), patterns: vec![Pattern::Var("x".to_string())] },
+//!     Expr::Var("x".to_string()),
+//! )];
+//!
+//! let report = check_match(&arms, &ConstructorTable::new());
+//! assert_eq!(report.missing_patterns(), vec!["None".to_string()]);
+//! ```
+
+use crate::ast::{
+    active_pattern_cases, active_result_pattern, DuTypeDef, Literal, MatchArm, Pattern,
+};
+use std::collections::HashMap;
+
+/// Maximum number of missing patterns reported for a single match
+const MAX_WITNESSES: usize = 3;
+
+/// Known discriminated union cases, used to decide when a set of variant
+/// patterns is complete.
+///
+/// The table is pre-populated with the built-in `Option` and `Result` types.
+/// User-defined unions are added with [`ConstructorTable::register_du`].
+#[derive(Debug, Clone)]
+pub struct ConstructorTable {
+    /// Map from union name to its cases (case name, field count)
+    unions: HashMap<String, Vec<(String, usize)>>,
+    /// Map from case name to the union that defines it
+    owners: HashMap<String, String>,
+}
+
+impl ConstructorTable {
+    /// Create a table containing the built-in `Option` and `Result` unions
+    pub fn new() -> Self {
+        let mut table = ConstructorTable {
+            unions: HashMap::new(),
+            owners: HashMap::new(),
+        };
+        table.register_cases(
+            "Option",
+            vec![("Some".to_string(), 1), ("None".to_string(), 0)],
+        );
+        table.register_cases(
+            "Result",
+            vec![("Ok".to_string(), 1), ("Error".to_string(), 1)],
+        );
+        table
+    }
+
+    /// Register a user-defined discriminated union
+    ///
+    /// Later definitions shadow earlier ones that reuse the same case names,
+    /// matching the usual F# scoping rules.
+    pub fn register_du(&mut self, du: &DuTypeDef) {
+        let cases = du
+            .variants
+            .iter()
+            .map(|v| (v.name.clone(), v.fields.len()))
+            .collect();
+        self.register_cases(&du.name, cases);
+    }
+
+    fn register_cases(&mut self, type_name: &str, cases: Vec<(String, usize)>) {
+        for (case, _) in &cases {
+            self.owners.insert(case.clone(), type_name.to_string());
+        }
+        self.unions.insert(type_name.to_string(), cases);
+    }
+
+    /// Get all cases of the union that defines `variant`, if it is known
+    pub fn siblings(&self, variant: &str) -> Option<&[(String, usize)]> {
+        self.owners
+            .get(variant)
+            .and_then(|owner| self.unions.get(owner))
+            .map(|cases| cases.as_slice())
+    }
+
+    /// Get the declared field count of a variant, if it is known
+    pub fn arity(&self, variant: &str) -> Option<usize> {
+        self.siblings(variant)?
+            .iter()
+            .find(|(name, _)| name == variant)
+            .map(|(_, arity)| *arity)
+    }
+}
+
+impl Default for ConstructorTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Result of analysing a single match expression
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MatchReport {
+    /// Example patterns that no arm covers (empty if the match is exhaustive)
+    pub missing: Vec<Pattern>,
+    /// Indices of arms that can never be reached
+    pub unreachable_arms: Vec<usize>,
+}
+
+impl MatchReport {
+    /// Returns true if every value is covered by some arm
+    pub fn is_exhaustive(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Returns the missing patterns rendered as source text
+    pub fn missing_patterns(&self) -> Vec<String> {
+        self.missing.iter().map(|p| p.to_string()).collect()
+    }
+}
+
+/// Analyse the arms of a match expression for exhaustiveness and redundancy
+pub fn check_match(arms: &[MatchArm], table: &ConstructorTable) -> MatchReport {
+    let mut record_fields = Vec::new();
+    for arm in arms {
+        collect_record_fields(&arm.pattern, &mut record_fields);
+    }
+    record_fields.sort();
+    record_fields.dedup();
+
+    let checker = Checker {
+        table,
+        record_fields,
+    };
+    let mut report = MatchReport::default();
+    let mut rows: Vec<Vec<Pat>> = Vec::new();
+
+    for (i, arm) in arms.iter().enumerate() {
+        let row = vec![checker.lower(&arm.pattern)];
+        if !checker.is_useful(&rows, &row) {
+            report.unreachable_arms.push(i);
+        }
+        // A guarded arm may fall through, so it never covers values on its own
+        if arm.guard.is_none() {
+            rows.push(row);
+        }
+    }
+
+    report.missing = checker
+        .missing(&rows, 1)
+        .into_iter()
+        .take(MAX_WITNESSES)
+        .filter_map(|mut witness| witness.pop())
+        .map(|pat| pat.raise(&checker.record_fields))
+        .collect();
+
+    report
+}
+
+/// Head constructor of a lowered pattern
+#[derive(Debug, Clone, PartialEq)]
+enum Ctor {
+    Variant(String),
+    Tuple(usize),
+    Bool(bool),
+    Unit,
+    /// Empty list
+    Nil,
+    /// Non-empty list (head, tail)
+    Cons,
+    /// Record with every field named in the match, in sorted order
+    Record,
+    /// Array of a fixed length - these never form a complete set
+    Array(usize),
+    /// Int, float or string literal - these never form a complete set
+    Lit(Literal),
+    /// Case of an active pattern (function, case), carrying its result
+    Active(String, String),
+}
+
+/// Pattern lowered to constructor applications and wildcards
+#[derive(Debug, Clone, PartialEq)]
+enum Pat {
+    Wild,
+    Ctor(Ctor, Vec<Pat>),
+    Or(Vec<Pat>),
+}
+
+impl Pat {
+    /// Convert a witness back into a surface pattern for reporting
+    fn raise(self, record_fields: &[String]) -> Pattern {
+        match self {
+            Pat::Wild => Pattern::Wildcard,
+            Pat::Ctor(Ctor::Variant(variant), args) => Pattern::Variant {
+                variant,
+                patterns: raise_all(args, record_fields),
+            },
+            Pat::Ctor(Ctor::Tuple(_), args) => Pattern::Tuple(raise_all(args, record_fields)),
+            Pat::Ctor(Ctor::Bool(b), _) => Pattern::Literal(Literal::Bool(b)),
+            Pat::Ctor(Ctor::Unit, _) => Pattern::Literal(Literal::Unit),
+            Pat::Ctor(Ctor::Nil, _) => Pattern::List(vec![]),
+            Pat::Ctor(Ctor::Cons, args) => {
+                let mut args = raise_all(args, record_fields).into_iter();
+                Pattern::Cons {
+                    head: Box::new(args.next().unwrap_or(Pattern::Wildcard)),
+                    tail: Box::new(args.next().unwrap_or(Pattern::Wildcard)),
+                }
+            }
+            Pat::Ctor(Ctor::Record, args) => {
+                // Only mention the fields that make the example interesting
+                let fields: Vec<_> = record_fields
+                    .iter()
+                    .cloned()
+                    .zip(raise_all(args, record_fields))
+                    .filter(|(_, pat)| !pat.is_wildcard())
+                    .collect();
+                if fields.is_empty() {
+                    Pattern::Wildcard
+                } else {
+                    Pattern::Record(fields)
+                }
+            }
+            Pat::Ctor(Ctor::Array(_), args) => Pattern::Array(raise_all(args, record_fields)),
+            Pat::Ctor(Ctor::Lit(lit), _) => Pattern::Literal(lit),
+            Pat::Ctor(Ctor::Active(function, case), args) => Pattern::Active {
+                function,
+                case,
+                patterns: raise_all(args, record_fields)
+                    .into_iter()
+                    .filter(|pat| !pat.is_wildcard())
+                    .collect(),
+            },
+            Pat::Or(alternatives) => Pattern::Or(raise_all(alternatives, record_fields)),
+        }
+    }
+}
+
+fn raise_all(pats: Vec<Pat>, record_fields: &[String]) -> Vec<Pattern> {
+    pats.into_iter().map(|p| p.raise(record_fields)).collect()
+}
+
+/// Collect the field names used by record patterns anywhere in `pattern`
+fn collect_record_fields(pattern: &Pattern, fields: &mut Vec<String>) {
+    match pattern {
+        Pattern::Wildcard | Pattern::Var(_) | Pattern::Literal(_) => {}
+        Pattern::Tuple(patterns)
+        | Pattern::Variant { patterns, .. }
+        | Pattern::Active { patterns, .. }
+        | Pattern::List(patterns)
+        | Pattern::Array(patterns)
+        | Pattern::Or(patterns) => {
+            for pat in patterns {
+                collect_record_fields(pat, fields);
+            }
+        }
+        Pattern::Cons { head, tail } => {
+            collect_record_fields(head, fields);
+            collect_record_fields(tail, fields);
+        }
+        Pattern::Record(record) => {
+            for (name, pat) in record {
+                fields.push(name.clone());
+                collect_record_fields(pat, fields);
+            }
+        }
+        Pattern::As { pattern, .. } => collect_record_fields(pattern, fields),
+    }
+}
+
+struct Checker<'a> {
+    table: &'a ConstructorTable,
+    /// Union of all record field names in the match, sorted. Record patterns
+    /// are widened to this set so that rows naming different fields line up.
+    record_fields: Vec<String>,
+}
+
+impl Checker<'_> {
+    /// Lower a surface pattern into the checker's representation
+    fn lower(&self, pattern: &Pattern) -> Pat {
+        match pattern {
+            Pattern::Wildcard | Pattern::Var(_) => Pat::Wild,
+            Pattern::Literal(Literal::Bool(b)) => Pat::Ctor(Ctor::Bool(*b), vec![]),
+            Pattern::Literal(Literal::Unit) => Pat::Ctor(Ctor::Unit, vec![]),
+            Pattern::Literal(lit) => Pat::Ctor(Ctor::Lit(lit.clone()), vec![]),
+            Pattern::Tuple(patterns) => Pat::Ctor(
+                Ctor::Tuple(patterns.len()),
+                patterns.iter().map(|p| self.lower(p)).collect(),
+            ),
+            Pattern::Variant { variant, patterns } => {
+                let args = patterns.iter().map(|p| self.lower(p)).collect();
+                let arity = self.table.arity(variant).unwrap_or(patterns.len());
+                Pat::Ctor(Ctor::Variant(variant.clone()), fit_arity(args, arity))
+            }
+            Pattern::List(patterns) => patterns
+                .iter()
+                .rev()
+                .fold(Pat::Ctor(Ctor::Nil, vec![]), |tail, head| {
+                    Pat::Ctor(Ctor::Cons, vec![self.lower(head), tail])
+                }),
+            Pattern::Cons { head, tail } => {
+                Pat::Ctor(Ctor::Cons, vec![self.lower(head), self.lower(tail)])
+            }
+            Pattern::Array(patterns) => Pat::Ctor(
+                Ctor::Array(patterns.len()),
+                patterns.iter().map(|p| self.lower(p)).collect(),
+            ),
+            Pattern::Record(fields) => {
+                let args = self
+                    .record_fields
+                    .iter()
+                    .map(|name| {
+                        fields
+                            .iter()
+                            .find(|(field, _)| field == name)
+                            .map_or(Pat::Wild, |(_, pat)| self.lower(pat))
+                    })
+                    .collect();
+                Pat::Ctor(Ctor::Record, args)
+            }
+            Pattern::Or(alternatives) => {
+                Pat::Or(alternatives.iter().map(|p| self.lower(p)).collect())
+            }
+            Pattern::As { pattern, .. } => self.lower(pattern),
+            Pattern::Active {
+                function,
+                case,
+                patterns,
+            } => Pat::Ctor(
+                Ctor::Active(function.clone(), case.clone()),
+                vec![self.lower(&active_result_pattern(patterns))],
+            ),
+        }
+    }
+
+    /// Number of sub-patterns a constructor takes
+    fn ctor_arity(&self, ctor: &Ctor) -> usize {
+        match ctor {
+            Ctor::Tuple(n) | Ctor::Array(n) => *n,
+            Ctor::Variant(name) => self.table.arity(name).unwrap_or(0),
+            Ctor::Cons => 2,
+            Ctor::Active(_, _) => 1,
+            Ctor::Record => self.record_fields.len(),
+            Ctor::Bool(_) | Ctor::Unit | Ctor::Nil | Ctor::Lit(_) => 0,
+        }
+    }
+
+    /// Replace rows whose head is an or-pattern with one row per alternative
+    fn expand_or(rows: &[Vec<Pat>]) -> Vec<Vec<Pat>> {
+        let mut expanded = Vec::with_capacity(rows.len());
+        for row in rows {
+            match row.split_first() {
+                Some((Pat::Or(alternatives), rest)) => {
+                    let alt_rows: Vec<Vec<Pat>> = alternatives
+                        .iter()
+                        .map(|alt| {
+                            let mut alt_row = vec![alt.clone()];
+                            alt_row.extend_from_slice(rest);
+                            alt_row
+                        })
+                        .collect();
+                    expanded.extend(Self::expand_or(&alt_rows));
+                }
+                _ => expanded.push(row.clone()),
+            }
+        }
+        expanded
+    }
+
+    /// Distinct head constructors appearing in the first column
+    fn head_ctors(rows: &[Vec<Pat>]) -> Vec<Ctor> {
+        let mut ctors = Vec::new();
+        for row in rows {
+            if let Some(Pat::Ctor(ctor, _)) = row.first() {
+                if !ctors.contains(ctor) {
+                    ctors.push(ctor.clone());
+                }
+            }
+        }
+        ctors
+    }
+
+    /// The full constructor signature of the type, if `ctors` covers all of it
+    fn complete_signature(&self, ctors: &[Ctor]) -> Option<Vec<Ctor>> {
+        let first = ctors.first()?;
+        let signature = match first {
+            Ctor::Tuple(n) => vec![Ctor::Tuple(*n)],
+            Ctor::Unit => vec![Ctor::Unit],
+            Ctor::Record => vec![Ctor::Record],
+            Ctor::Bool(_) => vec![Ctor::Bool(true), Ctor::Bool(false)],
+            Ctor::Nil | Ctor::Cons => vec![Ctor::Nil, Ctor::Cons],
+            Ctor::Variant(name) => self
+                .table
+                .siblings(name)?
+                .iter()
+                .map(|(case, _)| Ctor::Variant(case.clone()))
+                .collect(),
+            Ctor::Active(function, _) => Self::active_signature(function)?,
+            Ctor::Array(_) | Ctor::Lit(_) => return None,
+        };
+        if signature.iter().all(|c| ctors.contains(c)) {
+            Some(signature)
+        } else {
+            None
+        }
+    }
+
+    /// Constructors of the type that do not appear in `ctors`
+    ///
+    /// Returns `None` when the type has infinitely many values or is unknown,
+    /// in which case a wildcard is the best available example.
+    fn missing_ctors(&self, ctors: &[Ctor]) -> Option<Vec<Ctor>> {
+        let signature = match ctors.first()? {
+            Ctor::Bool(_) => vec![Ctor::Bool(true), Ctor::Bool(false)],
+            Ctor::Nil | Ctor::Cons => vec![Ctor::Nil, Ctor::Cons],
+            Ctor::Variant(name) => self
+                .table
+                .siblings(name)?
+                .iter()
+                .map(|(case, _)| Ctor::Variant(case.clone()))
+                .collect(),
+            Ctor::Active(function, _) => Self::active_signature(function)?,
+            _ => return None,
+        };
+        Some(
+            signature
+                .into_iter()
+                .filter(|c| !ctors.contains(c))
+                .collect(),
+        )
+    }
+
+    /// All cases of a total active pattern; partial ones have no complete set
+    fn active_signature(function: &str) -> Option<Vec<Ctor>> {
+        match active_pattern_cases(function)? {
+            (cases, false) => Some(
+                cases
+                    .into_iter()
+                    .map(|case| Ctor::Active(function.to_string(), case.to_string()))
+                    .collect(),
+            ),
+            (_, true) => None,
+        }
+    }
+
+    /// Rows whose head matches `ctor`, with the head replaced by its arguments
+    fn specialize(&self, rows: &[Vec<Pat>], ctor: &Ctor) -> Vec<Vec<Pat>> {
+        let arity = self.ctor_arity(ctor);
+        rows.iter()
+            .filter_map(|row| {
+                let (head, rest) = row.split_first()?;
+                let mut new_row = match head {
+                    Pat::Wild => vec![Pat::Wild; arity],
+                    Pat::Ctor(c, args) if c == ctor => fit_arity(args.clone(), arity),
+                    Pat::Ctor(_, _) => return None,
+                    Pat::Or(_) => unreachable!("or-patterns are expanded before specializing"),
+                };
+                new_row.extend_from_slice(rest);
+                Some(new_row)
+            })
+            .collect()
+    }
+
+    /// Rows whose head is a wildcard, with the head removed
+    fn default_rows(rows: &[Vec<Pat>]) -> Vec<Vec<Pat>> {
+        rows.iter()
+            .filter(|row| matches!(row.first(), Some(Pat::Wild)))
+            .map(|row| row[1..].to_vec())
+            .collect()
+    }
+
+    /// Is there a value matched by `row` but by none of `rows`?
+    fn is_useful(&self, rows: &[Vec<Pat>], row: &[Pat]) -> bool {
+        let Some((head, rest)) = row.split_first() else {
+            return rows.is_empty();
+        };
+        let rows = &Self::expand_or(rows);
+
+        match head {
+            Pat::Or(alternatives) => alternatives.iter().any(|alt| {
+                let mut alt_row = vec![alt.clone()];
+                alt_row.extend_from_slice(rest);
+                self.is_useful(rows, &alt_row)
+            }),
+            Pat::Ctor(ctor, args) => {
+                let mut specialized_row = fit_arity(args.clone(), self.ctor_arity(ctor));
+                specialized_row.extend_from_slice(rest);
+                self.is_useful(&self.specialize(rows, ctor), &specialized_row)
+            }
+            Pat::Wild => {
+                let ctors = Self::head_ctors(rows);
+                match self.complete_signature(&ctors) {
+                    Some(signature) => signature.iter().any(|ctor| {
+                        let mut specialized_row = vec![Pat::Wild; self.ctor_arity(ctor)];
+                        specialized_row.extend_from_slice(rest);
+                        self.is_useful(&self.specialize(rows, ctor), &specialized_row)
+                    }),
+                    None => self.is_useful(&Self::default_rows(rows), rest),
+                }
+            }
+        }
+    }
+
+    /// Example value vectors of length `width` not matched by any row
+    fn missing(&self, rows: &[Vec<Pat>], width: usize) -> Vec<Vec<Pat>> {
+        if width == 0 {
+            return if rows.is_empty() {
+                vec![vec![]]
+            } else {
+                vec![]
+            };
+        }
+
+        let rows = &Self::expand_or(rows);
+        let ctors = Self::head_ctors(rows);
+        if let Some(signature) = self.complete_signature(&ctors) {
+            let mut witnesses = Vec::new();
+            for ctor in signature {
+                let arity = self.ctor_arity(&ctor);
+                for mut witness in self.missing(&self.specialize(rows, &ctor), arity + width - 1) {
+                    let rest = witness.split_off(arity);
+                    let mut full = vec![Pat::Ctor(ctor.clone(), witness)];
+                    full.extend(rest);
+                    witnesses.push(full);
+                    if witnesses.len() >= MAX_WITNESSES {
+                        return witnesses;
+                    }
+                }
+            }
+            return witnesses;
+        }
+
+        let rest_witnesses = self.missing(&Self::default_rows(rows), width - 1);
+        if rest_witnesses.is_empty() {
+            return vec![];
+        }
+
+        let heads: Vec<Pat> = match self.missing_ctors(&ctors) {
+            Some(missing) if !missing.is_empty() => missing
+                .into_iter()
+                .map(|ctor| {
+                    let arity = self.ctor_arity(&ctor);
+                    Pat::Ctor(ctor, vec![Pat::Wild; arity])
+                })
+                .collect(),
+            _ => vec![Pat::Wild],
+        };
+
+        let mut witnesses = Vec::new();
+        for head in heads {
+            for rest in &rest_witnesses {
+                let mut full = vec![head.clone()];
+                full.extend(rest.iter().cloned());
+                witnesses.push(full);
+                if witnesses.len() >= MAX_WITNESSES {
+                    return witnesses;
+                }
+            }
+        }
+        witnesses
+    }
+}
+
+/// Adjust sub-patterns to a constructor's declared field count
+///
+/// `Some x` against a multi-field case, or a short pattern list, is padded
+/// with wildcards so that rows in the matrix always have consistent widths.
+fn fit_arity(mut args: Vec<Pat>, arity: usize) -> Vec<Pat> {
+    if args.len() != arity {
+        args.truncate(arity);
+        args.resize(arity, Pat::Wild);
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Expr, TypeExpr, VariantDef};
+
+    fn arm(pattern: Pattern) -> MatchArm {
+        MatchArm::new(pattern, Expr::Lit(Literal::Int(0)))
+    }
+
+    fn variant(name: &str, patterns: Vec<Pattern>) -> Pattern {
+        Pattern::Variant {
+            variant: name.to_string(),
+            patterns,
+        }
+    }
+
+    fn shape_table() -> ConstructorTable {
+        let mut table = ConstructorTable::new();
+        table.register_du(&DuTypeDef {
+            name: "Shape".to_string(),
+            type_params: vec![],
+            variants: vec![
+                VariantDef::new(
+                    "Circle".to_string(),
+                    vec![TypeExpr::Named("float".to_string())],
+                ),
+                VariantDef::new(
+                    "Rect".to_string(),
+                    vec![
+                        TypeExpr::Named("float".to_string()),
+                        TypeExpr::Named("float".to_string()),
+                    ],
+                ),
+                VariantDef::new_simple("Point".to_string()),
+            ],
+            members: vec![],
+        });
+        table
+    }
+
+    #[test]
+    fn test_option_exhaustive() {
+        let arms = vec![
+            arm(variant("Some", vec![Pattern::Var("x".to_string())])),
+            arm(variant("None", vec![])),
+        ];
+        let report = check_match(&arms, &ConstructorTable::new());
+        assert!(report.is_exhaustive());
+        assert!(report.unreachable_arms.is_empty());
+    }
+
+    #[test]
+    fn test_option_missing_none() {
+        let arms = vec![arm(variant("Some", vec![Pattern::Wildcard]))];
+        let report = check_match(&arms, &ConstructorTable::new());
+        assert_eq!(report.missing_patterns(), vec!["None"]);
+    }
+
+    #[test]
+    fn test_du_missing_case_reports_fields() {
+        let arms = vec![
+            arm(variant(
+                "Rect",
+                vec![Pattern::Var("w".to_string()), Pattern::Wildcard],
+            )),
+            arm(variant("Point", vec![])),
+        ];
+        let report = check_match(&arms, &shape_table());
+        assert_eq!(report.missing_patterns(), vec!["Circle(_)"]);
+    }
+
+    #[test]
+    fn test_nested_option_missing() {
+        let arms = vec![
+            arm(variant(
+                "Some",
+                vec![variant("Some", vec![Pattern::Wildcard])],
+            )),
+            arm(variant("None", vec![])),
+        ];
+        let report = check_match(&arms, &ConstructorTable::new());
+        assert_eq!(report.missing_patterns(), vec!["Some(None)"]);
+    }
+
+    #[test]
+    fn test_bool_tuple_missing() {
+        let t = |a: bool, b: bool| {
+            Pattern::Tuple(vec![
+                Pattern::Literal(Literal::Bool(a)),
+                Pattern::Literal(Literal::Bool(b)),
+            ])
+        };
+        let arms = vec![
+            arm(t(true, true)),
+            arm(t(false, false)),
+            arm(t(true, false)),
+        ];
+        let report = check_match(&arms, &ConstructorTable::new());
+        assert_eq!(report.missing_patterns(), vec!["(false, true)"]);
+    }
+
+    #[test]
+    fn test_int_literals_need_wildcard() {
+        let arms = vec![
+            arm(Pattern::Literal(Literal::Int(0))),
+            arm(Pattern::Literal(Literal::Int(1))),
+        ];
+        let report = check_match(&arms, &ConstructorTable::new());
+        assert_eq!(report.missing_patterns(), vec!["_"]);
+
+        let mut arms = arms;
+        arms.push(arm(Pattern::Var("n".to_string())));
+        assert!(check_match(&arms, &ConstructorTable::new()).is_exhaustive());
+    }
+
+    #[test]
+    fn test_unreachable_after_wildcard() {
+        let arms = vec![
+            arm(Pattern::Wildcard),
+            arm(Pattern::Literal(Literal::Int(1))),
+        ];
+        let report = check_match(&arms, &ConstructorTable::new());
+        assert_eq!(report.unreachable_arms, vec![1]);
+    }
+
+    #[test]
+    fn test_unreachable_duplicate_variant() {
+        let arms = vec![
+            arm(variant("Circle", vec![Pattern::Var("r".to_string())])),
+            arm(variant(
+                "Circle",
+                vec![Pattern::Literal(Literal::Float(1.0))],
+            )),
+            arm(Pattern::Wildcard),
+        ];
+        let report = check_match(&arms, &shape_table());
+        assert!(report.is_exhaustive());
+        assert_eq!(report.unreachable_arms, vec![1]);
+    }
+
+    #[test]
+    fn test_wildcard_after_complete_cases_is_unreachable() {
+        let arms = vec![
+            arm(variant("Some", vec![Pattern::Wildcard])),
+            arm(variant("None", vec![])),
+            arm(Pattern::Wildcard),
+        ];
+        let report = check_match(&arms, &ConstructorTable::new());
+        assert_eq!(report.unreachable_arms, vec![2]);
+    }
+
+    #[test]
+    fn test_guarded_arm_does_not_cover() {
+        let guard = Expr::Lit(Literal::Bool(true));
+        let arms = vec![
+            MatchArm::with_guard(
+                variant("None", vec![]),
+                guard.clone(),
+                Expr::Lit(Literal::Int(0)),
+            ),
+            arm(variant("Some", vec![Pattern::Wildcard])),
+        ];
+        let report = check_match(&arms, &ConstructorTable::new());
+        assert_eq!(report.missing_patterns(), vec!["None"]);
+
+        let mut arms = arms;
+        arms.push(arm(variant("None", vec![])));
+        let report = check_match(&arms, &ConstructorTable::new());
+        assert!(report.is_exhaustive());
+        assert!(report.unreachable_arms.is_empty());
+    }
+
+    #[test]
+    fn test_list_patterns() {
+        let cons = |head: Pattern, tail: Pattern| Pattern::Cons {
+            head: Box::new(head),
+            tail: Box::new(tail),
+        };
+        let arms = vec![
+            arm(Pattern::List(vec![])),
+            arm(Pattern::List(vec![Pattern::Var("x".to_string())])),
+        ];
+        let report = check_match(&arms, &ConstructorTable::new());
+        assert_eq!(report.missing_patterns(), vec!["_ :: _ :: _"]);
+
+        let mut arms = arms;
+        arms.push(arm(cons(Pattern::Wildcard, Pattern::Wildcard)));
+        let report = check_match(&arms, &ConstructorTable::new());
+        assert!(report.is_exhaustive());
+        assert!(report.unreachable_arms.is_empty());
+    }
+
+    #[test]
+    fn test_or_pattern_covers_each_alternative() {
+        let arms = vec![
+            arm(Pattern::Or(vec![
+                variant("Circle", vec![Pattern::Wildcard]),
+                variant("Point", vec![]),
+            ])),
+            arm(variant("Point", vec![])),
+        ];
+        let report = check_match(&arms, &shape_table());
+        assert_eq!(report.missing_patterns(), vec!["Rect(_, _)"]);
+        assert_eq!(report.unreachable_arms, vec![1]);
+    }
+
+    #[test]
+    fn test_record_patterns_with_different_fields() {
+        let record = |fields: Vec<(&str, Pattern)>| {
+            Pattern::Record(
+                fields
+                    .into_iter()
+                    .map(|(name, pat)| (name.to_string(), pat))
+                    .collect(),
+            )
+        };
+        let arms = vec![
+            arm(record(vec![(
+                "Active",
+                Pattern::Literal(Literal::Bool(true)),
+            )])),
+            arm(record(vec![("Name", Pattern::Var("n".to_string()))])),
+        ];
+        assert!(check_match(&arms, &ConstructorTable::new()).is_exhaustive());
+
+        let arms = vec![arm(record(vec![(
+            "Active",
+            Pattern::Literal(Literal::Bool(true)),
+        )]))];
+        let report = check_match(&arms, &ConstructorTable::new());
+        assert_eq!(report.missing_patterns(), vec!["{ Active = false }"]);
+    }
+
+    #[test]
+    fn test_array_patterns_need_catch_all() {
+        let arms = vec![
+            arm(Pattern::Array(vec![])),
+            arm(Pattern::As {
+                pattern: Box::new(Pattern::Array(vec![Pattern::Wildcard])),
+                name: "single".to_string(),
+            }),
+        ];
+        let report = check_match(&arms, &ConstructorTable::new());
+        assert_eq!(report.missing_patterns(), vec!["_"]);
+    }
+
+    #[test]
+    fn test_unknown_variant_requires_catch_all() {
+        let arms = vec![arm(variant("Mystery", vec![]))];
+        let report = check_match(&arms, &ConstructorTable::new());
+        assert_eq!(report.missing_patterns(), vec!["_"]);
+    }
+}
@@ -0,0 +1,100 @@
+//! printf-style format strings.
+//!
+//! Parses the format strings accepted by `sprintf` and `String.format` so the
+//! parser can validate interpolated string holes and type inference can check
+//! the arguments of a format call before it runs. The accepted specifiers match
+//! the runtime formatter in `fusabi_vm::stdlib::string`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use fusabi_frontend::format::{parse_format, FormatSpec};
+//!
+//! let specs = parse_format("%s is %d years old").unwrap();
+//! assert_eq!(specs, vec![FormatSpec::Any, FormatSpec::Int]);
+//! ```
+
+use crate::types::Type;
+
+/// A conversion specifier in a format string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatSpec {
+    /// `%s` - any value, printed with its display form
+    Any,
+    /// `%d` - an integer
+    Int,
+    /// `%f` or `%.Nf` - a float
+    Float,
+}
+
+impl FormatSpec {
+    /// The type an argument must have, if the specifier constrains it.
+    pub fn expected_type(&self) -> Option<Type> {
+        match self {
+            FormatSpec::Any => None,
+            FormatSpec::Int => Some(Type::Int),
+            FormatSpec::Float => Some(Type::Float),
+        }
+    }
+}
+
+/// Parse a format string into the specifiers it expects, in order.
+///
+/// `%%` is a literal percent sign and takes no argument.
+pub fn parse_format(fmt: &str) -> Result<Vec<FormatSpec>, String> {
+    let mut specs = Vec::new();
+    let mut chars = fmt.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            continue;
+        }
+        match chars.next() {
+            Some('%') => {}
+            Some('s') => specs.push(FormatSpec::Any),
+            Some('d') => specs.push(FormatSpec::Int),
+            Some('f') => specs.push(FormatSpec::Float),
+            Some('.') => {
+                let mut precision = String::new();
+                while let Some(digit) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                    precision.push(*digit);
+                    chars.next();
+                }
+                if precision.is_empty() || chars.next() != Some('f') {
+                    return Err(format!("Invalid format specifier: %.{}", precision));
+                }
+                specs.push(FormatSpec::Float);
+            }
+            Some(other) => return Err(format!("Unknown format specifier: %{}", other)),
+            None => return Err("Incomplete format specifier at end of string".to_string()),
+        }
+    }
+
+    Ok(specs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_format_specifiers() {
+        assert_eq!(
+            parse_format("%s: %d (%.2f%%) %f").unwrap(),
+            vec![
+                FormatSpec::Any,
+                FormatSpec::Int,
+                FormatSpec::Float,
+                FormatSpec::Float
+            ]
+        );
+        assert_eq!(parse_format("no holes").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_parse_format_rejects_bad_specifiers() {
+        assert!(parse_format("%x").unwrap_err().contains("%x"));
+        assert!(parse_format("%.f").is_err());
+        assert!(parse_format("50%").is_err());
+    }
+}
@@ -36,10 +36,16 @@
 //! - **Helpful errors**: Detailed error messages with suggestions
 //! - **Auto-recursive detection**: Automatically detects recursive lambdas (issue #126)
 
-use crate::ast::{BinOp, Expr, Literal, MatchArm, Pattern};
+use crate::ast::{
+    active_pattern_cases, active_result_pattern, BinOp, DuTypeDef, Expr, Literal, MatchArm,
+    MeasureDef, MemberDef, Pattern, RecordTypeDef, TypeAnnotation, TypeExpr, VariantDef,
+};
 use crate::error::{TypeError, TypeErrorKind};
+use crate::format::parse_format;
 use crate::modules::ModuleRegistry;
-use crate::types::{Substitution, Type, TypeEnv, TypeScheme, TypeVar};
+use crate::span::Span;
+use crate::types::{Measure, MeasureUnit, Substitution, Type, TypeEnv, TypeScheme, TypeVar};
+use fusabi_vm::exception::EXN_TYPE_NAME;
 use std::collections::HashMap;
 
 /// Constraint representing equality between two types.
@@ -50,6 +56,66 @@ use std::collections::HashMap;
 pub enum Constraint {
     /// Two types must be equal
     Equal(Type, Type),
+    /// A written annotation must equal the inferred type; errors carry the
+    /// annotation's span
+    Annotated(Type, Type, Span),
+    /// An overloadable operator applied to operands of the first two types
+    /// gives the third. Numbers use the built-in operator and other types the
+    /// operator member they declare; while both operands are unknown the
+    /// constraint waits, and it is left on the variables a let generalizes.
+    Operator(BinOp, Type, Type, Type),
+}
+
+impl Constraint {
+    /// Apply a substitution to the types of the constraint
+    fn apply(&self, subst: &Substitution) -> Constraint {
+        match self {
+            Constraint::Equal(t1, t2) => Constraint::Equal(t1.apply(subst), t2.apply(subst)),
+            Constraint::Annotated(expected, actual, span) => {
+                Constraint::Annotated(expected.apply(subst), actual.apply(subst), *span)
+            }
+            Constraint::Operator(op, left, right, result) => Constraint::Operator(
+                *op,
+                left.apply(subst),
+                right.apply(subst),
+                result.apply(subst),
+            ),
+        }
+    }
+
+    /// Whether any of the given type variables occurs in the constraint
+    fn mentions(&self, vars: &[TypeVar]) -> bool {
+        let types = match self {
+            Constraint::Equal(t1, t2) | Constraint::Annotated(t1, t2, _) => vec![t1, t2],
+            Constraint::Operator(_, left, right, result) => vec![left, right, result],
+        };
+        types
+            .into_iter()
+            .any(|ty| ty.free_vars().iter().any(|v| vars.contains(v)))
+    }
+}
+
+/// The signature of a union case, quantified over its union's type parameters.
+///
+/// For `type Tree<'a> = Leaf | Node of Tree<'a> * 'a * Tree<'a>`, the case
+/// `Node` has fields `[Tree<'a>; 'a; Tree<'a>]` and result `Tree<'a>`.
+#[derive(Debug, Clone)]
+struct CaseSignature {
+    /// The union's type parameters
+    vars: Vec<TypeVar>,
+    /// Field types of the case
+    fields: Vec<Type>,
+    /// The union type the case constructs
+    result: Type,
+}
+
+/// The fields of a declared record type, quantified over its type parameters.
+#[derive(Debug, Clone)]
+struct RecordSignature {
+    /// The record's type parameters
+    vars: Vec<TypeVar>,
+    /// Field types by name
+    fields: HashMap<String, Type>,
 }
 
 /// Type inference engine implementing Algorithm W.
@@ -62,33 +128,504 @@ pub struct TypeInference {
     constraints: Vec<Constraint>,
     /// Optional module registry for type definition lookups
     module_registry: Option<ModuleRegistry>,
+    /// Declared union cases by case name
+    cases: HashMap<String, CaseSignature>,
+    /// Declared record types, most recently declared last
+    records: Vec<(String, RecordSignature)>,
+    /// Type variables written in annotations of the binding being inferred
+    annotation_vars: HashMap<String, TypeVar>,
+    /// Element types of the enclosing comprehensions, innermost last
+    comprehensions: Vec<Type>,
+    /// Types of the static members declared by record and union types, by
+    /// type name and then member name
+    members: HashMap<String, HashMap<String, TypeScheme>>,
+    /// Types of the instance members declared by record and union types,
+    /// each taking the receiver as its first argument
+    instance_members: HashMap<String, HashMap<String, TypeScheme>>,
+    /// Members registered but not yet inferred, with the type declaring them
+    pending_members: Vec<(String, MemberDef)>,
+    /// Declared units of measure, with the measure an abbreviation stands for
+    measures: HashMap<String, Option<Measure>>,
+    /// Operator constraints the last solve left waiting for their operands
+    deferred: Vec<Constraint>,
+    /// Operator constraints on the quantified variables of generalized
+    /// bindings, copied to every instantiation
+    qualified: Vec<Constraint>,
+    /// Give names that nothing binds a fresh type instead of failing, for
+    /// host and standard library functions that have no declared types
+    untyped_globals: bool,
 }
 
 #[allow(clippy::result_large_err)]
 impl TypeInference {
     /// Create a new type inference instance.
+    ///
+    /// The built-in `Option`, `Result` and `exn` unions are known from the start.
     pub fn new() -> Self {
-        TypeInference {
+        let mut inference = TypeInference {
             next_var_id: 0,
             constraints: Vec::new(),
             module_registry: None,
-        }
+            cases: HashMap::new(),
+            records: Vec::new(),
+            annotation_vars: HashMap::new(),
+            comprehensions: Vec::new(),
+            members: HashMap::new(),
+            instance_members: HashMap::new(),
+            pending_members: Vec::new(),
+            measures: HashMap::new(),
+            deferred: Vec::new(),
+            qualified: Vec::new(),
+            untyped_globals: false,
+        };
+        let var = |name: &str| TypeExpr::Var(name.to_string());
+        inference.register_du(&DuTypeDef {
+            name: "Option".to_string(),
+            type_params: vec!["a".to_string()],
+            variants: vec![
+                VariantDef::new("Some".to_string(), vec![var("a")]),
+                VariantDef::new_simple("None".to_string()),
+            ],
+            members: vec![],
+        });
+        inference.register_du(&DuTypeDef {
+            name: "Result".to_string(),
+            type_params: vec!["t".to_string(), "e".to_string()],
+            variants: vec![
+                VariantDef::new("Ok".to_string(), vec![var("t")]),
+                VariantDef::new("Error".to_string(), vec![var("e")]),
+            ],
+            members: vec![],
+        });
+        let named = |name: &str| TypeExpr::Named(name.to_string());
+        inference.register_du(&DuTypeDef {
+            name: EXN_TYPE_NAME.to_string(),
+            type_params: vec![],
+            variants: vec![
+                VariantDef::new("Failure".to_string(), vec![named("string")]),
+                VariantDef::new(
+                    "HostError".to_string(),
+                    vec![named("string"), named("int"), named("int")],
+                ),
+            ],
+            members: vec![],
+        });
+        inference
     }
 
     /// Create a new type inference instance with a module registry for type validation.
     pub fn with_module_registry(module_registry: ModuleRegistry) -> Self {
-        TypeInference {
-            next_var_id: 0,
-            constraints: Vec::new(),
-            module_registry: Some(module_registry),
-        }
+        let mut inference = Self::new();
+        inference.set_module_registry(module_registry);
+        inference
     }
 
     /// Set the module registry for type definition lookups.
+    ///
+    /// Union and record types declared in the registry's modules become known
+    /// to inference.
     pub fn set_module_registry(&mut self, registry: ModuleRegistry) {
+        for module_name in registry.module_names() {
+            if let Some(types) = registry.get_module_types(module_name) {
+                for type_def in types.values() {
+                    match type_def {
+                        crate::modules::TypeDefinition::Du(du) => self.register_du(du),
+                        crate::modules::TypeDefinition::Record(record) => {
+                            self.register_record(record)
+                        }
+                        crate::modules::TypeDefinition::Measure(measure) => {
+                            self.register_measure(measure)
+                        }
+                        crate::modules::TypeDefinition::Provider(_) => {}
+                    }
+                }
+            }
+        }
         self.module_registry = Some(registry);
     }
 
+    /// Leave names that nothing in scope binds unchecked.
+    ///
+    /// Such a name, bare or qualified as in `List.map`, is assumed to be a
+    /// global the host provides and takes a fresh type at each use instead of
+    /// being reported as unbound.
+    pub fn set_untyped_globals(&mut self, untyped: bool) {
+        self.untyped_globals = untyped;
+    }
+
+    /// Make the cases of a union type known to inference.
+    ///
+    /// Constructing or matching a case then produces the union type, with the
+    /// union's type parameters instantiated freshly at each use, so
+    /// `Node(Leaf, 1, Leaf)` has type `Tree<int>`.
+    pub fn register_du(&mut self, du: &DuTypeDef) {
+        let mut vars: HashMap<String, TypeVar> = HashMap::new();
+        for param in &du.type_params {
+            let var = self.fresh_var();
+            vars.insert(param.clone(), TypeVar::new(var.id, param.clone()));
+        }
+        let params: Vec<TypeVar> = du.type_params.iter().map(|p| vars[p].clone()).collect();
+        let result = Type::Variant(
+            du.name.clone(),
+            params.iter().cloned().map(Type::Var).collect(),
+        );
+
+        for variant in &du.variants {
+            let mut case_vars = vars.clone();
+            let fields = variant
+                .fields
+                .iter()
+                .map(|field| {
+                    let mut next = || self.fresh_var();
+                    Type::from_type_expr(field, &mut case_vars, &mut next)
+                })
+                .collect();
+            // Variables the definition did not declare are quantified too
+            let mut quantified = params.clone();
+            quantified.extend(case_vars.into_values().filter(|v| !params.contains(v)));
+            self.cases.insert(
+                variant.name.clone(),
+                CaseSignature {
+                    vars: quantified,
+                    fields,
+                    result: result.clone(),
+                },
+            );
+        }
+        self.register_members(&du.name, &du.members);
+    }
+
+    /// Make a record type known to inference, so that annotations naming it
+    /// stand for its fields.
+    ///
+    /// With `type Point = { X: int; Y: int }`, the annotation `p: Point` gives
+    /// `p` the type `{ X: int; Y: int }`.
+    pub fn register_record(&mut self, record: &RecordTypeDef) {
+        let mut vars: HashMap<String, TypeVar> = HashMap::new();
+        for param in &record.type_params {
+            let var = self.fresh_var();
+            vars.insert(param.clone(), TypeVar::new(var.id, param.clone()));
+        }
+        let params = record.type_params.iter().map(|p| vars[p].clone()).collect();
+        let fields = record
+            .fields
+            .iter()
+            .map(|(name, ty)| {
+                let mut next = || self.fresh_var();
+                (name.clone(), Type::from_type_expr(ty, &mut vars, &mut next))
+            })
+            .collect();
+        // A redeclared record shadows the earlier one
+        self.records.retain(|(name, _)| name != &record.name);
+        self.records.push((
+            record.name.clone(),
+            RecordSignature {
+                vars: params,
+                fields,
+            },
+        ));
+        self.register_members(&record.name, &record.members);
+    }
+
+    /// Declare the members of a type.
+    ///
+    /// Their types are inferred by the next [`infer_and_solve`], in the
+    /// program's environment; until then a member accepts any operands.
+    ///
+    /// [`infer_and_solve`]: TypeInference::infer_and_solve
+    fn register_members(&mut self, type_name: &str, members: &[MemberDef]) {
+        self.members.remove(type_name);
+        self.instance_members.remove(type_name);
+        self.pending_members
+            .retain(|(declaring, _)| declaring != type_name);
+
+        for member in members {
+            let placeholder = TypeEnv::new().generalize(&Type::Var(self.fresh_var()));
+            self.declare_member(type_name, member, placeholder);
+            self.pending_members
+                .push((type_name.to_string(), member.clone()));
+        }
+    }
+
+    /// Record the type of a member declared by `type_name`.
+    fn declare_member(&mut self, type_name: &str, member: &MemberDef, scheme: TypeScheme) {
+        let declared = if member.is_static() {
+            &mut self.members
+        } else {
+            &mut self.instance_members
+        };
+        declared
+            .entry(type_name.to_string())
+            .or_default()
+            .insert(member.name.clone(), scheme);
+    }
+
+    /// Infer the types of the members registered since the last call.
+    ///
+    /// Each member sees the bindings of `env` and the members inferred before
+    /// it; an error in a member's body is reported like any other.
+    fn infer_members(&mut self, env: &TypeEnv) -> Result<(), TypeError> {
+        for (type_name, member) in std::mem::take(&mut self.pending_members) {
+            let outer_constraints = std::mem::take(&mut self.constraints);
+            self.annotation_vars.clear();
+            let inferred = self
+                .infer(&member.value, env)
+                .and_then(|ty| Ok(ty.apply(&self.solve_deferring()?)));
+            self.constraints = outer_constraints;
+            self.deferred.clear();
+
+            let ty = inferred.map_err(|error| {
+                error.with_context(format!("member '{}' of type '{}'", member.name, type_name))
+            })?;
+            self.declare_member(&type_name, &member, env.generalize(&ty));
+        }
+        Ok(())
+    }
+
+    /// Make a unit of measure known to inference.
+    ///
+    /// An abbreviation such as `[<Measure>] type Hz = 1/s` is replaced by the
+    /// measure it stands for wherever it is written.
+    pub fn register_measure(&mut self, measure: &MeasureDef) {
+        let definition = measure.definition.as_ref().map(|definition| {
+            let written =
+                Measure::from_type_expr(definition, &mut HashMap::new(), &mut || self.fresh_var());
+            self.expand_measure(&written)
+        });
+        self.measures.insert(measure.name.clone(), definition);
+    }
+
+    /// Replace unit abbreviations in a measure with what they stand for.
+    fn expand_measure(&self, measure: &Measure) -> Measure {
+        measure
+            .units()
+            .fold(Measure::one(), |acc, (unit, exp)| match unit {
+                MeasureUnit::Named(name) => match self.measures.get(name) {
+                    Some(Some(definition)) => acc.product(&definition.pow(exp)),
+                    _ => acc.product(&Measure::factor(unit.clone(), exp)),
+                },
+                MeasureUnit::Var(_) => acc.product(&Measure::factor(unit.clone(), exp)),
+            })
+    }
+
+    /// The first unit in a written type that was not declared with
+    /// `[<Measure>] type`.
+    fn undeclared_unit<'a>(&self, expr: &'a TypeExpr) -> Option<&'a str> {
+        match expr {
+            TypeExpr::Named(_) | TypeExpr::Var(_) => None,
+            TypeExpr::Tuple(types) => types.iter().find_map(|t| self.undeclared_unit(t)),
+            TypeExpr::Function(param, ret) => self
+                .undeclared_unit(param)
+                .or_else(|| self.undeclared_unit(ret)),
+            TypeExpr::App(_, args) => args.iter().find_map(|t| self.undeclared_unit(t)),
+            TypeExpr::Measure(factors) => factors.iter().find_map(|(factor, _)| match factor {
+                TypeExpr::Named(name) if !self.measures.contains_key(name) => Some(name.as_str()),
+                other => self.undeclared_unit(other),
+            }),
+        }
+    }
+
+    /// The name of the declared type that `ty` stands for.
+    ///
+    /// Records are structural, so a record type belongs to the most recently
+    /// declared record with exactly its fields.
+    fn declared_type_name<'a>(&'a self, ty: &'a Type) -> Option<&'a str> {
+        match ty {
+            Type::Variant(name, _) => Some(name.as_str()),
+            Type::Record(fields) => self
+                .records
+                .iter()
+                .rev()
+                .find(|(_, record)| {
+                    record.fields.len() == fields.len()
+                        && fields.keys().all(|field| record.fields.contains_key(field))
+                })
+                .map(|(name, _)| name.as_str()),
+            _ => None,
+        }
+    }
+
+    /// The static members declared by the type that `ty` stands for.
+    fn type_members(&self, ty: &Type) -> Option<&HashMap<String, TypeScheme>> {
+        self.members.get(self.declared_type_name(ty)?)
+    }
+
+    /// The instance member `name` declared by the type that `ty` stands for;
+    /// a record's own fields take precedence.
+    fn instance_member(&self, ty: &Type, name: &str) -> Option<TypeScheme> {
+        if matches!(ty, Type::Record(fields) if fields.contains_key(name)) {
+            return None;
+        }
+        self.instance_members
+            .get(self.declared_type_name(ty)?)?
+            .get(name)
+            .cloned()
+    }
+
+    /// Instantiate a member and apply it to `arg`, giving the type of the
+    /// result.
+    ///
+    /// Applied to its receiver, an instance member gives the type of a
+    /// property, or of a method still waiting for its arguments.
+    fn apply_member(&mut self, member_type: Type, arg: Type) -> Type {
+        let result = Type::Var(self.fresh_var());
+        self.add_constraint(Constraint::Equal(
+            member_type,
+            Type::Function(Box::new(arg), Box::new(result.clone())),
+        ));
+        result
+    }
+
+    /// Look up a declared record type by name; `Module.Person` finds `Person`.
+    fn record_signature(&self, name: &str) -> Option<&RecordSignature> {
+        let local_name = name.rsplit('.').next().unwrap_or(name);
+        self.records
+            .iter()
+            .rev()
+            .find(|(declared, _)| declared == local_name)
+            .map(|(_, signature)| signature)
+    }
+
+    /// Instantiate a declared record type with fresh type variables.
+    fn instantiate_record(&mut self, name: &str) -> Type {
+        self.expand_records(Type::Variant(name.to_string(), vec![]), &mut Vec::new())
+    }
+
+    /// The most recently declared record type with the given field, which is
+    /// the one an otherwise unknown `r.Name` refers to.
+    fn latest_record_with_field(&self, field: &str) -> Option<String> {
+        self.records
+            .iter()
+            .rev()
+            .find(|(_, signature)| signature.fields.contains_key(field))
+            .map(|(name, _)| name.clone())
+    }
+
+    /// Convert a written annotation to a type.
+    ///
+    /// Type variables such as `'a` are shared across the annotations of one
+    /// binding, names of registered record types expand to their fields and
+    /// units of measure must have been declared.
+    fn annotation_type(&mut self, annotation: &TypeAnnotation) -> Result<Type, TypeError> {
+        if let Some(unit) = self.undeclared_unit(&annotation.ty) {
+            return Err(TypeError::with_span(
+                TypeErrorKind::UnknownType {
+                    type_name: unit.to_string(),
+                },
+                annotation.span,
+            ));
+        }
+        let mut vars = std::mem::take(&mut self.annotation_vars);
+        let ty = Type::from_type_expr(&annotation.ty, &mut vars, &mut || self.fresh_var());
+        self.annotation_vars = vars;
+        Ok(self.expand_records(ty, &mut Vec::new()))
+    }
+
+    /// Replace named record types with their structural field types.
+    ///
+    /// `expanding` guards against records that refer to themselves, which stay
+    /// named below the first level.
+    fn expand_records(&mut self, ty: Type, expanding: &mut Vec<String>) -> Type {
+        match ty {
+            Type::Variant(name, args) => {
+                let record = match self.record_signature(&name) {
+                    Some(record) if !expanding.contains(&name) => record.clone(),
+                    _ => {
+                        let args = args
+                            .into_iter()
+                            .map(|arg| self.expand_records(arg, expanding))
+                            .collect();
+                        return Type::Variant(name, args);
+                    }
+                };
+                // Parameters left off, as in `p: Page` for `Page<'t>`, are inferred
+                let mut args = args.into_iter();
+                let mut subst = Substitution::empty();
+                for var in &record.vars {
+                    let arg = args.next().unwrap_or_else(|| Type::Var(self.fresh_var()));
+                    subst.insert(var.clone(), arg);
+                }
+                expanding.push(name);
+                let fields = record
+                    .fields
+                    .into_iter()
+                    .map(|(field, ty)| (field, self.expand_records(ty.apply(&subst), expanding)))
+                    .collect();
+                expanding.pop();
+                Type::Record(fields)
+            }
+            Type::List(elem) => Type::List(Box::new(self.expand_records(*elem, expanding))),
+            Type::Array(elem) => Type::Array(Box::new(self.expand_records(*elem, expanding))),
+            Type::Tuple(elements) => Type::Tuple(
+                elements
+                    .into_iter()
+                    .map(|t| self.expand_records(t, expanding))
+                    .collect(),
+            ),
+            Type::Function(param, result) => Type::Function(
+                Box::new(self.expand_records(*param, expanding)),
+                Box::new(self.expand_records(*result, expanding)),
+            ),
+            Type::Record(fields) => Type::Record(
+                fields
+                    .into_iter()
+                    .map(|(field, ty)| (field, self.expand_records(ty, expanding)))
+                    .collect(),
+            ),
+            Type::AnonRecord(fields, rest) => Type::AnonRecord(
+                fields
+                    .into_iter()
+                    .map(|(field, ty)| (field, self.expand_records(ty, expanding)))
+                    .collect(),
+                rest,
+            ),
+            Type::Measured(base, measure) => Type::measured(*base, self.expand_measure(&measure)),
+            Type::Measure(measure) => Type::Measure(self.expand_measure(&measure)),
+            other => other,
+        }
+    }
+
+    /// Instantiate a registered case with fresh type variables.
+    ///
+    /// Returns the case's field types and the union type it belongs to.
+    fn instantiate_case(&mut self, variant: &str) -> Option<(Vec<Type>, Type)> {
+        let signature = self.cases.get(variant)?.clone();
+        let mut subst = Substitution::empty();
+        for var in &signature.vars {
+            subst.insert(var.clone(), Type::Var(self.fresh_var()));
+        }
+        let fields = signature.fields.iter().map(|f| f.apply(&subst)).collect();
+        Some((fields, signature.result.apply(&subst)))
+    }
+
+    /// Match the written arity of a case against its declared fields.
+    ///
+    /// A single-field case written with several arguments, as in `Some(1, 2)`,
+    /// takes them as one tuple.
+    fn case_field_types(
+        &mut self,
+        variant: &str,
+        declared: Vec<Type>,
+        count: usize,
+    ) -> Result<Vec<Type>, TypeError> {
+        if declared.len() == count {
+            return Ok(declared);
+        }
+        if declared.len() == 1 && count > 1 {
+            let elements: Vec<Type> = (0..count).map(|_| Type::Var(self.fresh_var())).collect();
+            self.add_constraint(Constraint::Equal(
+                declared[0].clone(),
+                Type::Tuple(elements.clone()),
+            ));
+            return Ok(elements);
+        }
+        Err(TypeError::new(TypeErrorKind::ArityMismatch {
+            expected: declared.len(),
+            got: count,
+        })
+        .with_context(format!("union case '{}'", variant)))
+    }
+
     /// Generate a fresh type variable.
     ///
     /// Each call produces a unique type variable that hasn't been used before.
@@ -111,7 +648,7 @@ impl TypeInference {
     fn expr_references_var(expr: &Expr, name: &str) -> bool {
         match expr {
             Expr::Var(var_name) => var_name == name,
-            Expr::Lambda { param, body } => {
+            Expr::Lambda { param, body, .. } => {
                 // If the lambda parameter shadows the name, don't look inside
                 if param == name {
                     false
@@ -126,6 +663,7 @@ impl TypeInference {
                 name: let_name,
                 value,
                 body,
+                ..
             } => {
                 // Check value, but if let shadows the name, don't check body
                 Self::expr_references_var(value, name)
@@ -140,6 +678,18 @@ impl TypeInference {
                 Self::expr_references_var(value, name)
                     || (rec_name != name && Self::expr_references_var(body, name))
             }
+            Expr::LetMutable {
+                name: let_name,
+                value,
+                body,
+            } => {
+                Self::expr_references_var(value, name)
+                    || (let_name != name && Self::expr_references_var(body, name))
+            }
+            Expr::Assign {
+                name: target,
+                value,
+            } => target == name || Self::expr_references_var(value, name),
             Expr::LetRecMutual { bindings, body } => {
                 // Check all binding values
                 bindings
@@ -180,7 +730,7 @@ impl TypeInference {
                     || Self::expr_references_var(value, name)
             }
             Expr::ArrayLength(array) => Self::expr_references_var(array, name),
-            Expr::RecordLiteral { fields, .. } => fields
+            Expr::RecordLiteral { fields, .. } | Expr::AnonRecordLiteral { fields } => fields
                 .iter()
                 .any(|(_, expr)| Self::expr_references_var(expr, name)),
             Expr::RecordAccess { record, .. } => Self::expr_references_var(record, name),
@@ -198,10 +748,29 @@ impl TypeInference {
                     || arms.iter().any(|arm| {
                         // Check if pattern binds the name (shadows it)
                         let pattern_binds = Self::pattern_binds(&arm.pattern, name);
-                        // Only check body if pattern doesn't shadow the name
-                        !pattern_binds && Self::expr_references_var(&arm.body, name)
+                        // Only check guard and body if pattern doesn't shadow the name
+                        !pattern_binds
+                            && (arm
+                                .guard
+                                .as_ref()
+                                .is_some_and(|g| Self::expr_references_var(g, name))
+                                || Self::expr_references_var(&arm.body, name))
+                    })
+            }
+            Expr::TryWith { body, arms } => {
+                Self::expr_references_var(body, name)
+                    || arms.iter().any(|arm| {
+                        !Self::pattern_binds(&arm.pattern, name)
+                            && (arm
+                                .guard
+                                .as_ref()
+                                .is_some_and(|g| Self::expr_references_var(g, name))
+                                || Self::expr_references_var(&arm.body, name))
                     })
             }
+            Expr::TryFinally { body, finalizer } => {
+                Self::expr_references_var(body, name) || Self::expr_references_var(finalizer, name)
+            }
             Expr::MethodCall { receiver, args, .. } => {
                 Self::expr_references_var(receiver, name)
                     || args.iter().any(|e| Self::expr_references_var(e, name))
@@ -209,6 +778,40 @@ impl TypeInference {
             Expr::While { cond, body } => {
                 Self::expr_references_var(cond, name) || Self::expr_references_var(body, name)
             }
+            Expr::For {
+                var,
+                start,
+                finish,
+                body,
+                ..
+            } => {
+                Self::expr_references_var(start, name)
+                    || Self::expr_references_var(finish, name)
+                    || (var != name && Self::expr_references_var(body, name))
+            }
+            Expr::ForIn {
+                pattern,
+                source,
+                body,
+            } => {
+                Self::expr_references_var(source, name)
+                    || (!Self::pattern_binds(pattern, name)
+                        && Self::expr_references_var(body, name))
+            }
+            Expr::Range {
+                start,
+                step,
+                finish,
+            } => {
+                Self::expr_references_var(start, name)
+                    || step
+                        .as_ref()
+                        .is_some_and(|step| Self::expr_references_var(step, name))
+                    || Self::expr_references_var(finish, name)
+            }
+            Expr::Comprehension { body, .. } | Expr::Yield(body) | Expr::YieldBang(body) => {
+                Self::expr_references_var(body, name)
+            }
             Expr::ComputationExpr { body, .. } => {
                 // Check if any statement in the CE body references the variable
                 body.iter().any(|stmt| {
@@ -226,19 +829,13 @@ impl TypeInference {
                 })
             }
             // Literals and control flow don't reference variables
-            Expr::Lit(_) | Expr::Break | Expr::Continue => false,
+            Expr::Lit(_) | Expr::MeasuredLit { .. } | Expr::Break | Expr::Continue => false,
         }
     }
 
     /// Check if a pattern binds a variable name.
     fn pattern_binds(pattern: &Pattern, name: &str) -> bool {
-        match pattern {
-            Pattern::Var(var_name) => var_name == name,
-            Pattern::Tuple(patterns) | Pattern::Variant { patterns, .. } => {
-                patterns.iter().any(|p| Self::pattern_binds(p, name))
-            }
-            Pattern::Wildcard | Pattern::Literal(_) => false,
-        }
+        pattern.bound_vars().contains(&name)
     }
 
     /// Infer the type of an expression in the given environment.
@@ -259,20 +856,43 @@ impl TypeInference {
             // Literals have concrete types
             Expr::Lit(lit) => Ok(self.infer_literal(lit)),
 
+            // Numbers with a unit of measure: 9.81<m/s^2>
+            Expr::MeasuredLit { value, measure } => self.infer_measured_literal(value, measure),
+
             // Variables: lookup in environment and instantiate
             Expr::Var(name) => self.infer_var(name, env),
 
             // Lambda: fun x -> body
-            Expr::Lambda { param, body } => self.infer_lambda(param, body, env),
+            Expr::Lambda {
+                param,
+                param_ty,
+                return_ty,
+                body,
+            } => self.infer_lambda(param, param_ty.as_ref(), return_ty.as_ref(), body, env),
 
             // Function application: f arg
             Expr::App { func, arg } => self.infer_app(func, arg, env),
 
             // Let-binding: let x = value in body
-            Expr::Let { name, value, body } => self.infer_let(name, value, body, env, false),
+            Expr::Let {
+                name,
+                ty,
+                value,
+                body,
+            } => self.infer_let(name, ty.as_ref(), value, body, env, false),
 
             // Recursive let-binding: let rec f = value in body
-            Expr::LetRec { name, value, body } => self.infer_let(name, value, body, env, true),
+            Expr::LetRec { name, value, body } => {
+                self.infer_let(name, None, value, body, env, true)
+            }
+
+            // Mutable binding: let mutable x = value in body
+            Expr::LetMutable { name, value, body } => {
+                self.infer_let_mutable(name, value, body, env)
+            }
+
+            // Assignment: x <- value
+            Expr::Assign { name, value } => self.infer_assign(name, value, env),
 
             // Mutually recursive bindings: let rec f = ... and g = ... in body
             Expr::LetRecMutual { bindings, body } => self.infer_let_rec_mutual(bindings, body, env),
@@ -317,6 +937,9 @@ impl TypeInference {
                 self.infer_record_literal(type_name, fields, env)
             }
 
+            // Anonymous record literal: {| field1 = e1; field2 = e2 |}
+            Expr::AnonRecordLiteral { fields } => self.infer_anon_record_literal(fields, env),
+
             // Record access: record.field
             Expr::RecordAccess { record, field } => self.infer_record_access(record, field, env),
 
@@ -333,18 +956,20 @@ impl TypeInference {
             // Pattern matching: match scrutinee with | pat1 -> e1 | pat2 -> e2
             Expr::Match { scrutinee, arms } => self.infer_match(scrutinee, arms, env),
 
+            // Exception handling: try body with | pat -> e ... / try body finally e
+            Expr::TryWith { body, arms } => self.infer_try_with(body, arms, env),
+            Expr::TryFinally { body, finalizer } => {
+                let body_type = self.infer(body, env)?;
+                self.infer(finalizer, env)?;
+                Ok(body_type)
+            }
+
             // Method call: obj.method(args...)
             Expr::MethodCall {
                 receiver,
-                method_name: _,
-                args: _,
-            } => {
-                // For now, we infer method calls conservatively
-                // Type check the receiver
-                self.infer(receiver, env)?;
-                // Return a fresh type variable since we don't know the method's return type
-                Ok(Type::Var(self.fresh_var()))
-            }
+                method_name,
+                args,
+            } => self.infer_method_call(receiver, method_name, args, env),
 
             // While loop: while cond do body
             Expr::While { cond, body } => {
@@ -357,6 +982,90 @@ impl TypeInference {
                 Ok(Type::Unit)
             }
 
+            // Counted loop: for var = start to finish do body
+            Expr::For {
+                var,
+                start,
+                finish,
+                body,
+                ..
+            } => {
+                let start_ty = self.infer(start, env)?;
+                self.unify(&start_ty, &Type::Int)?;
+                let finish_ty = self.infer(finish, env)?;
+                self.unify(&finish_ty, &Type::Int)?;
+                let body_env = env.extend(var.clone(), TypeScheme::mono(Type::Int));
+                self.infer(body, &body_env)?;
+                Ok(Type::Unit)
+            }
+
+            // Collection loop: for pattern in source do body
+            Expr::ForIn {
+                pattern,
+                source,
+                body,
+            } => {
+                // The source may be a list, array or map, so the element
+                // type is left for the pattern and body to determine
+                self.infer(source, env)?;
+                let elem_ty = Type::Var(self.fresh_var());
+                let body_env = self.infer_pattern(pattern, &elem_ty, env)?;
+                self.infer(body, &body_env)?;
+                Ok(Type::Unit)
+            }
+
+            // Range: a .. b or a .. step .. b
+            Expr::Range {
+                start,
+                step,
+                finish,
+            } => {
+                let elem_ty = self.infer(start, env)?;
+                if let Some(step) = step {
+                    let step_ty = self.infer(step, env)?;
+                    self.add_constraint(Constraint::Equal(step_ty, elem_ty.clone()));
+                }
+                let finish_ty = self.infer(finish, env)?;
+                self.add_constraint(Constraint::Equal(finish_ty, elem_ty.clone()));
+                Ok(Type::List(Box::new(elem_ty)))
+            }
+
+            // Comprehension: [ for x in xs -> e ] or [| ... |]
+            Expr::Comprehension { body, array } => {
+                let elem_ty = Type::Var(self.fresh_var());
+                self.comprehensions.push(elem_ty.clone());
+                let result = self.infer(body, env);
+                self.comprehensions.pop();
+                result?;
+                if *array {
+                    Ok(Type::Array(Box::new(elem_ty)))
+                } else {
+                    Ok(Type::List(Box::new(elem_ty)))
+                }
+            }
+
+            // Yield a single element of the enclosing comprehension
+            Expr::Yield(value) => {
+                let elem_ty = self.comprehension_element()?;
+                let value_ty = self.infer(value, env)?;
+                self.add_constraint(Constraint::Equal(value_ty, elem_ty));
+                Ok(Type::Unit)
+            }
+
+            // Yield every element of a collection
+            Expr::YieldBang(source) => {
+                let elem_ty = self.comprehension_element()?;
+                // As with for-in, a source of unknown shape leaves the
+                // element type to the rest of the comprehension
+                match self.infer(source, env)? {
+                    Type::List(source_elem) | Type::Array(source_elem) => {
+                        self.add_constraint(Constraint::Equal(*source_elem, elem_ty));
+                    }
+                    _ => {}
+                }
+                Ok(Type::Unit)
+            }
+
             // Break statement
             Expr::Break => {
                 // Break has unit type but can only appear in loops
@@ -390,17 +1099,54 @@ impl TypeInference {
             Literal::Float(_) => Type::Float,
             Literal::Bool(_) => Type::Bool,
             Literal::Str(_) => Type::String,
+            Literal::Char(_) => Type::Char,
+            Literal::Bytes(_) => Type::Bytes,
             Literal::Unit => Type::Unit,
         }
     }
 
+    /// Infer the type of a number literal with a unit of measure.
+    fn infer_measured_literal(
+        &mut self,
+        value: &Literal,
+        measure: &TypeExpr,
+    ) -> Result<Type, TypeError> {
+        if let Some(unit) = self.undeclared_unit(measure) {
+            return Err(TypeError::new(TypeErrorKind::UnknownType {
+                type_name: unit.to_string(),
+            }));
+        }
+        let mut vars = std::mem::take(&mut self.annotation_vars);
+        let written = Measure::from_type_expr(measure, &mut vars, &mut || self.fresh_var());
+        self.annotation_vars = vars;
+        Ok(Type::measured(
+            self.infer_literal(value),
+            self.expand_measure(&written),
+        ))
+    }
+
     /// Infer the type of a variable by looking it up in the environment.
     fn infer_var(&mut self, name: &str, env: &TypeEnv) -> Result<Type, TypeError> {
         match env.lookup(name) {
             Some(scheme) => {
-                // Instantiate the type scheme with fresh type variables
-                Ok(env.instantiate(scheme, &mut || self.fresh_var()))
+                // Instantiate the type scheme with fresh type variables, along
+                // with the operator constraints on them
+                let mut subst = Substitution::empty();
+                for var in &scheme.vars {
+                    subst.insert(var.clone(), Type::Var(self.fresh_var()));
+                }
+                let qualified: Vec<Constraint> = self
+                    .qualified
+                    .iter()
+                    .filter(|constraint| constraint.mentions(&scheme.vars))
+                    .map(|constraint| constraint.apply(&subst))
+                    .collect();
+                for constraint in qualified {
+                    self.add_constraint(constraint);
+                }
+                Ok(scheme.ty.apply(&subst))
             }
+            None if self.untyped_globals => Ok(Type::Var(self.fresh_var())),
             None => Err(TypeError::new(TypeErrorKind::UnboundVariable {
                 name: name.to_string(),
             })),
@@ -414,12 +1160,34 @@ impl TypeInference {
     /// 2. Extend the environment with x: α
     /// 3. Infer the type β of the body
     /// 4. Return α -> β
-    fn infer_lambda(&mut self, param: &str, body: &Expr, env: &TypeEnv) -> Result<Type, TypeError> {
-        let param_type = Type::Var(self.fresh_var());
+    ///
+    /// An annotated parameter `fun (x: T) -> ...` takes the written type in
+    /// place of α, and a return annotation is unified with β.
+    fn infer_lambda(
+        &mut self,
+        param: &str,
+        param_ty: Option<&TypeAnnotation>,
+        return_ty: Option<&TypeAnnotation>,
+        body: &Expr,
+        env: &TypeEnv,
+    ) -> Result<Type, TypeError> {
+        let param_type = match param_ty {
+            Some(annotation) => self.annotation_type(annotation)?,
+            None => Type::Var(self.fresh_var()),
+        };
         let param_scheme = TypeScheme::mono(param_type.clone());
         let extended_env = env.extend(param.to_string(), param_scheme);
 
-        let body_type = self.infer(body, &extended_env)?;
+        let mut body_type = self.infer(body, &extended_env)?;
+        if let Some(annotation) = return_ty {
+            let expected = self.annotation_type(annotation)?;
+            self.add_constraint(Constraint::Annotated(
+                expected.clone(),
+                body_type,
+                annotation.span,
+            ));
+            body_type = expected;
+        }
 
         Ok(Type::Function(Box::new(param_type), Box::new(body_type)))
     }
@@ -433,6 +1201,10 @@ impl TypeInference {
     /// 4. Add constraint: tf = targ -> α
     /// 5. Return α
     fn infer_app(&mut self, func: &Expr, arg: &Expr, env: &TypeEnv) -> Result<Type, TypeError> {
+        if let (Some(format), Expr::List(args)) = (Self::format_string(func), arg) {
+            return self.infer_format_call(format, args, env);
+        }
+
         let func_type = self.infer(func, env)?;
         let arg_type = self.infer(arg, env)?;
         let result_type = Type::Var(self.fresh_var());
@@ -444,6 +1216,67 @@ impl TypeInference {
         Ok(result_type)
     }
 
+    /// The literal format string of a partial `sprintf`/`String.format` call.
+    fn format_string(func: &Expr) -> Option<&str> {
+        let Expr::App { func, arg } = func else {
+            return None;
+        };
+        let is_format_fn = match func.as_ref() {
+            Expr::Var(name) => name == "sprintf",
+            Expr::RecordAccess { record, field } => {
+                field == "format" && matches!(record.as_ref(), Expr::Var(m) if m == "String")
+            }
+            _ => false,
+        };
+        match arg.as_ref() {
+            Expr::Lit(Literal::Str(format)) if is_format_fn => Some(format),
+            _ => None,
+        }
+    }
+
+    /// Element type of the innermost comprehension, for `yield`
+    fn comprehension_element(&self) -> Result<Type, TypeError> {
+        self.comprehensions.last().cloned().ok_or_else(|| {
+            TypeError::new(TypeErrorKind::Custom {
+                message: "yield used outside of a list or array comprehension".to_string(),
+            })
+        })
+    }
+
+    /// Infer a format call with a literal format string and argument list.
+    ///
+    /// The arguments are checked one by one against their specifiers (`%d`
+    /// takes an int, `%f` a float, `%s` anything), so unlike an ordinary list
+    /// they may have different types. Interpolated strings desugar to this.
+    fn infer_format_call(
+        &mut self,
+        format: &str,
+        args: &[Expr],
+        env: &TypeEnv,
+    ) -> Result<Type, TypeError> {
+        let specs = parse_format(format).map_err(|message| {
+            TypeError::new(TypeErrorKind::Custom {
+                message: format!("{} in format string \"{}\"", message, format),
+            })
+        })?;
+        if specs.len() != args.len() {
+            return Err(TypeError::new(TypeErrorKind::ArityMismatch {
+                expected: specs.len(),
+                got: args.len(),
+            })
+            .with_context(format!("in format string \"{}\"", format)));
+        }
+
+        for (spec, arg) in specs.iter().zip(args) {
+            let arg_type = self.infer(arg, env)?;
+            if let Some(expected) = spec.expected_type() {
+                self.add_constraint(Constraint::Equal(arg_type, expected));
+            }
+        }
+
+        Ok(Type::String)
+    }
+
     /// Infer the type of a let-binding with automatic recursion detection.
     ///
     /// For `let x = value in body`:
@@ -454,9 +1287,13 @@ impl TypeInference {
     ///
     /// This implements issue #126: automatic recursive function detection
     /// for lambda expressions like `let factorial = fun n -> ... factorial ...`
+    ///
+    /// An annotation `let x: T = value` is unified with the value's type, and
+    /// type variables written in it are shared by the annotations inside value.
     fn infer_let(
         &mut self,
         name: &str,
+        ty: Option<&TypeAnnotation>,
         value: &Expr,
         body: &Expr,
         env: &TypeEnv,
@@ -466,9 +1303,19 @@ impl TypeInference {
         let auto_recursive = !is_recursive && Self::expr_references_var(value, name);
         let treat_as_recursive = is_recursive || auto_recursive;
 
+        let outer_annotation_vars = self.annotation_vars.clone();
+        let annotated = match ty {
+            Some(annotation) => Some((self.annotation_type(annotation)?, annotation.span)),
+            None => None,
+        };
+
         let value_type = if treat_as_recursive {
             // For recursive bindings, assume a fresh type variable for the name
-            let rec_var = Type::Var(self.fresh_var());
+            // (or the annotated type, when there is one)
+            let rec_var = match &annotated {
+                Some((expected, _)) => expected.clone(),
+                None => Type::Var(self.fresh_var()),
+            };
             let rec_scheme = TypeScheme::mono(rec_var.clone());
             let rec_env = env.extend(name.to_string(), rec_scheme);
 
@@ -482,17 +1329,58 @@ impl TypeInference {
             // Non-recursive: infer value in current environment
             self.infer(value, env)?
         };
-
-        // Generalize the type (let-polymorphism)
-        let value_scheme = env.generalize(&value_type);
+        let value_type = match annotated {
+            Some((expected, span)) => {
+                self.add_constraint(Constraint::Annotated(expected.clone(), value_type, span));
+                expected
+            }
+            None => value_type,
+        };
+        self.annotation_vars = outer_annotation_vars;
+
+        // Generalize the type (let-polymorphism), solving first so that
+        // variables already fixed by constraints are not quantified
+        let subst = self.solve_deferring()?;
+        let value_type = value_type.apply(&subst);
+        let value_scheme = env.apply(&subst).generalize(&value_type);
+        let deferred = std::mem::take(&mut self.deferred);
+        self.qualified.extend(
+            deferred
+                .into_iter()
+                .filter(|constraint| constraint.mentions(&value_scheme.vars)),
+        );
 
         // Extend environment and infer body
         let extended_env = env.extend(name.to_string(), value_scheme);
         self.infer(body, &extended_env)
     }
 
-    /// Infer the type of mutually recursive let-bindings.
-    fn infer_let_rec_mutual(
+    /// Infer the type of a mutable let-binding.
+    ///
+    /// The variable is not generalized: every assignment must store a value
+    /// of the one type it was declared with.
+    fn infer_let_mutable(
+        &mut self,
+        name: &str,
+        value: &Expr,
+        body: &Expr,
+        env: &TypeEnv,
+    ) -> Result<Type, TypeError> {
+        let value_type = self.infer(value, env)?;
+        let extended_env = env.extend(name.to_string(), TypeScheme::mono(value_type));
+        self.infer(body, &extended_env)
+    }
+
+    /// Infer the type of an assignment `x <- value`, which is always unit.
+    fn infer_assign(&mut self, name: &str, value: &Expr, env: &TypeEnv) -> Result<Type, TypeError> {
+        let var_type = self.infer_var(name, env)?;
+        let value_type = self.infer(value, env)?;
+        self.add_constraint(Constraint::Equal(var_type, value_type));
+        Ok(Type::Unit)
+    }
+
+    /// Infer the type of mutually recursive let-bindings.
+    fn infer_let_rec_mutual(
         &mut self,
         bindings: &[(String, Expr)],
         body: &Expr,
@@ -556,11 +1444,16 @@ impl TypeInference {
         let right_type = self.infer(right, env)?;
 
         if op.is_arithmetic() {
-            // Arithmetic: both operands must be int or float, result is same type
-            // For simplicity, we constrain to int (full implementation would support float)
-            self.add_constraint(Constraint::Equal(left_type.clone(), Type::Int));
-            self.add_constraint(Constraint::Equal(right_type, Type::Int));
-            Ok(Type::Int)
+            // Arithmetic: int or float operands of the same type, or operands
+            // whose type declares the operator as a member
+            let result = Type::Var(self.fresh_var());
+            self.add_constraint(Constraint::Operator(
+                op,
+                left_type,
+                right_type,
+                result.clone(),
+            ));
+            Ok(result)
         } else if op.is_comparison() {
             // Comparison: operands must have the same type, result is bool
             self.add_constraint(Constraint::Equal(left_type, right_type));
@@ -570,8 +1463,30 @@ impl TypeInference {
             self.add_constraint(Constraint::Equal(left_type, Type::Bool));
             self.add_constraint(Constraint::Equal(right_type, Type::Bool));
             Ok(Type::Bool)
+        } else if op.is_bitwise() {
+            // Bitwise and shift operators are defined on int only
+            self.add_constraint(Constraint::Equal(left_type, Type::Int));
+            self.add_constraint(Constraint::Equal(right_type, Type::Int));
+            Ok(Type::Int)
+        } else if op.is_composition() {
+            // f >> g : ('a -> 'b) -> ('b -> 'c) -> ('a -> 'c), and << flips the operands
+            let (first, second) = match op {
+                BinOp::ComposeRight => (left_type, right_type),
+                _ => (right_type, left_type),
+            };
+            let a = Type::Var(self.fresh_var());
+            let b = Type::Var(self.fresh_var());
+            let c = Type::Var(self.fresh_var());
+            let first_fn = Type::Function(Box::new(a.clone()), Box::new(b.clone()));
+            let second_fn = Type::Function(Box::new(b), Box::new(c.clone()));
+            self.add_constraint(Constraint::Equal(first, first_fn));
+            self.add_constraint(Constraint::Equal(second, second_fn));
+            Ok(Type::Function(Box::new(a), Box::new(c)))
         } else {
-            unreachable!("Unknown binary operator")
+            // String concatenation
+            self.add_constraint(Constraint::Equal(left_type, Type::String));
+            self.add_constraint(Constraint::Equal(right_type, Type::String));
+            Ok(Type::String)
         }
     }
 
@@ -688,8 +1603,8 @@ impl TypeInference {
         fields: &[(String, Box<Expr>)],
         env: &TypeEnv,
     ) -> Result<Type, TypeError> {
-        // If type_name is provided and we have a module registry, validate fields
-        if !type_name.is_empty() {
+        // A named type that this program does not declare must come from a module
+        if !type_name.is_empty() && self.record_signature(type_name).is_none() {
             if let Some(ref registry) = self.module_registry {
                 self.validate_record_fields(type_name, fields, registry)?;
             }
@@ -702,7 +1617,77 @@ impl TypeInference {
             field_types.insert(field_name.clone(), field_type);
         }
 
-        Ok(Type::Record(field_types))
+        // The literal has the record type it names, as in `{ Person.Name = .. }`,
+        // or else the most recently declared record with exactly its fields
+        let declared = if type_name.is_empty() {
+            self.records
+                .iter()
+                .rev()
+                .find(|(_, signature)| {
+                    signature.fields.len() == field_types.len()
+                        && field_types.keys().all(|f| signature.fields.contains_key(f))
+                })
+                .map(|(name, _)| name.clone())
+        } else if self.record_signature(type_name).is_some() {
+            Some(type_name.to_string())
+        } else if self.module_registry.is_none() {
+            return Err(TypeError::new(TypeErrorKind::UnknownType {
+                type_name: type_name.to_string(),
+            }));
+        } else {
+            None
+        };
+        let Some(declared) = declared else {
+            return Ok(Type::Record(field_types));
+        };
+
+        let expected = self.instantiate_record(&declared);
+        if let Type::Record(expected_fields) = &expected {
+            if let Some(extra) = field_types
+                .keys()
+                .find(|f| !expected_fields.contains_key(*f))
+            {
+                let names: Vec<String> = expected_fields.keys().cloned().collect();
+                return Err(TypeError::new(TypeErrorKind::ExtraField {
+                    type_name: declared,
+                    field: extra.clone(),
+                    suggestions: Self::compute_field_suggestions(extra, &names),
+                }));
+            }
+            if let Some(missing) = expected_fields
+                .keys()
+                .find(|f| !field_types.contains_key(*f))
+            {
+                return Err(TypeError::new(TypeErrorKind::MissingField {
+                    record_type: expected.clone(),
+                    field: missing.clone(),
+                }));
+            }
+        }
+        self.add_constraint(Constraint::Equal(
+            expected.clone(),
+            Type::Record(field_types),
+        ));
+        Ok(expected)
+    }
+
+    /// Infer the type of an anonymous record literal `{| Name = ..; Age = .. |}`.
+    fn infer_anon_record_literal(
+        &mut self,
+        fields: &[(String, Box<Expr>)],
+        env: &TypeEnv,
+    ) -> Result<Type, TypeError> {
+        let mut field_types = HashMap::new();
+        for (field_name, field_expr) in fields {
+            if field_types.contains_key(field_name) {
+                return Err(TypeError::new(TypeErrorKind::DuplicateField {
+                    field: field_name.clone(),
+                }));
+            }
+            let field_type = self.infer(field_expr, env)?;
+            field_types.insert(field_name.clone(), field_type);
+        }
+        Ok(Type::AnonRecord(field_types, None))
     }
 
     /// Validate that record literal fields match the type definition.
@@ -769,6 +1754,14 @@ impl TypeInference {
                         ),
                     }));
                 }
+                crate::modules::TypeDefinition::Measure(_) => {
+                    return Err(TypeError::new(TypeErrorKind::Custom {
+                        message: format!(
+                            "Type '{}' is a unit of measure, not a record.",
+                            type_name
+                        ),
+                    }));
+                }
                 crate::modules::TypeDefinition::Provider(_) => {
                     // Type provider declarations should already be resolved
                     return Err(TypeError::new(TypeErrorKind::Custom {
@@ -840,6 +1833,22 @@ impl TypeInference {
                     Box::new(self.ast_type_expr_to_provider_type_expr(ret)),
                 )
             }
+            crate::ast::TypeExpr::Var(name) => fusabi_type_providers::TypeExpr::Var(name.clone()),
+            // Providers know nothing of units of measure: `float<ms>` is a float
+            crate::ast::TypeExpr::App(name, args)
+                if matches!(args.as_slice(), [crate::ast::TypeExpr::Measure(_)]) =>
+            {
+                fusabi_type_providers::TypeExpr::Named(name.clone())
+            }
+            crate::ast::TypeExpr::Measure(_) => {
+                fusabi_type_providers::TypeExpr::Named(ty.to_string())
+            }
+            crate::ast::TypeExpr::App(name, args) => fusabi_type_providers::TypeExpr::App(
+                name.clone(),
+                args.iter()
+                    .map(|t| self.ast_type_expr_to_provider_type_expr(t))
+                    .collect(),
+            ),
         }
     }
 
@@ -907,20 +1916,160 @@ impl TypeInference {
         field: &str,
         env: &TypeEnv,
     ) -> Result<Type, TypeError> {
+        // `Config.Default`: a static member of a declared type
+        if let Some(scheme) = self.static_member(record, field, env)? {
+            return Ok(TypeEnv::new().instantiate(&scheme, &mut || self.fresh_var()));
+        }
+        // `List.map`: a member of a host module
+        if let Expr::Var(module) = record {
+            if self.untyped_globals && env.lookup(module).is_none() {
+                return Ok(Type::Var(self.fresh_var()));
+            }
+        }
         let record_type = self.infer(record, env)?;
+        self.infer_field(record_type, field)
+    }
 
-        // Create a fresh type variable for the field
-        let field_type = Type::Var(self.fresh_var());
+    /// The static member `name` when `receiver` names a declared type that
+    /// no variable shadows, or an error when that type has static members but
+    /// not this one.
+    fn static_member(
+        &self,
+        receiver: &Expr,
+        name: &str,
+        env: &TypeEnv,
+    ) -> Result<Option<TypeScheme>, TypeError> {
+        match receiver {
+            Expr::Var(type_name) if env.lookup(type_name).is_none() => {
+                match self.members.get(type_name) {
+                    Some(members) => match members.get(name) {
+                        Some(scheme) => Ok(Some(scheme.clone())),
+                        None => Err(TypeError::new(TypeErrorKind::MemberNotFound {
+                            type_name: type_name.clone(),
+                            member: name.to_string(),
+                        })),
+                    },
+                    None => Ok(None),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
 
-        // Create a record type with at least this field
-        let mut expected_fields = HashMap::new();
-        expected_fields.insert(field.to_string(), field_type.clone());
-        let expected_record = Type::Record(expected_fields);
+    /// The error for using `name` on a value of a type the program declares,
+    /// when neither the type nor its record fields provide it.
+    fn undeclared_member(&self, ty: &Type, name: &str) -> Option<TypeError> {
+        if matches!(ty, Type::Record(fields) if fields.contains_key(name)) {
+            return None;
+        }
+        let type_name = self.declared_type_name(ty)?;
+        let declared = self.records.iter().any(|(record, _)| record == type_name)
+            || self
+                .cases
+                .values()
+                .any(|case| matches!(&case.result, Type::Variant(union, _) if union == type_name));
+        declared.then(|| {
+            TypeError::new(TypeErrorKind::MemberNotFound {
+                type_name: type_name.to_string(),
+                member: name.to_string(),
+            })
+        })
+    }
 
-        // Note: This is a simplified version. A full implementation would use
-        // row polymorphism or structural typing for better record handling.
-        self.add_constraint(Constraint::Equal(record_type, expected_record));
+    /// Infer the type of a method call.
+    ///
+    /// Members declared by the receiver's type, or by the type a static call
+    /// names, are applied to the arguments one at a time; a call without
+    /// arguments passes unit. Other methods, such as those of host types,
+    /// are not checked.
+    fn infer_method_call(
+        &mut self,
+        receiver: &Expr,
+        method_name: &str,
+        args: &[Expr],
+        env: &TypeEnv,
+    ) -> Result<Type, TypeError> {
+        let mut method_type = match self.static_member(receiver, method_name, env)? {
+            Some(scheme) => TypeEnv::new().instantiate(&scheme, &mut || self.fresh_var()),
+            None => {
+                let receiver_type = self.infer(receiver, env)?;
+                let resolved = receiver_type.apply(&self.solve_deferring()?);
+                match self.instance_member(&resolved, method_name) {
+                    Some(scheme) => {
+                        let member_type =
+                            TypeEnv::new().instantiate(&scheme, &mut || self.fresh_var());
+                        self.apply_member(member_type, receiver_type)
+                    }
+                    None => {
+                        if let Some(error) = self.undeclared_member(&resolved, method_name) {
+                            return Err(error);
+                        }
+                        // We don't know the method's return type
+                        return Ok(Type::Var(self.fresh_var()));
+                    }
+                }
+            }
+        };
+
+        if args.is_empty() {
+            return Ok(self.apply_member(method_type, Type::Unit));
+        }
+        for arg in args {
+            let arg_type = self.infer(arg, env)?;
+            method_type = self.apply_member(method_type, arg_type);
+        }
+        Ok(method_type)
+    }
+
+    /// The type of `field` on a value of type `record_type`.
+    ///
+    /// A record type known at this point, such as an annotated parameter,
+    /// gives the field type directly. An unknown type resolves F#-style to the
+    /// most recently declared record with the field. Failing that the value
+    /// only needs to have the field: it becomes an open anonymous record, so
+    /// `let name r = r.Name` generalizes over every record with a `Name`.
+    fn infer_field(&mut self, record_type: Type, field: &str) -> Result<Type, TypeError> {
+        let resolved = record_type.apply(&self.solve_deferring()?);
+        // Properties declared by the type: `shape.Area`
+        if let Some(scheme) = self.instance_member(&resolved, field) {
+            let member_type = TypeEnv::new().instantiate(&scheme, &mut || self.fresh_var());
+            return Ok(self.apply_member(member_type, record_type));
+        }
+        // Declared records report their missing fields below
+        if matches!(resolved, Type::Variant(..)) {
+            if let Some(error) = self.undeclared_member(&resolved, field) {
+                return Err(error);
+            }
+        }
+        match &resolved {
+            Type::Record(fields) | Type::AnonRecord(fields, None) => {
+                return match fields.get(field) {
+                    Some(field_type) => Ok(field_type.clone()),
+                    None => Err(TypeError::new(TypeErrorKind::FieldNotFound {
+                        record_type: resolved.clone(),
+                        field: field.to_string(),
+                    })),
+                };
+            }
+            Type::AnonRecord(fields, Some(_)) if fields.contains_key(field) => {
+                return Ok(fields[field].clone());
+            }
+            Type::Var(_) => {
+                if let Some(name) = self.latest_record_with_field(field) {
+                    let declared = self.instantiate_record(&name);
+                    self.add_constraint(Constraint::Equal(record_type, declared.clone()));
+                    return self.infer_field(declared, field);
+                }
+            }
+            _ => {}
+        }
 
+        let field_type = Type::Var(self.fresh_var());
+        let row = Type::AnonRecord(
+            HashMap::from([(field.to_string(), field_type.clone())]),
+            Some(self.fresh_var()),
+        );
+        self.add_constraint(Constraint::Equal(record_type, row));
         Ok(field_type)
     }
 
@@ -933,26 +2082,50 @@ impl TypeInference {
     ) -> Result<Type, TypeError> {
         let record_type = self.infer(record, env)?;
 
-        // Infer types of updated fields
-        let mut update_field_types = HashMap::new();
+        // Updated fields must exist and keep their types, so the result has
+        // the same type as the input record
         for (field_name, field_expr) in fields {
+            let expected = self.infer_field(record_type.clone(), field_name)?;
             let field_type = self.infer(field_expr, env)?;
-            update_field_types.insert(field_name.clone(), field_type);
+            self.add_constraint(Constraint::Equal(expected, field_type));
         }
 
-        // The result has the same type as the input record
-        // (with potentially different field types for updated fields)
         Ok(record_type)
     }
 
     /// Infer the type of a variant constructor.
     fn infer_variant_construct(
         &mut self,
-        _type_name: &str,
+        type_name: &str,
         variant: &str,
         fields: &[Box<Expr>],
         env: &TypeEnv,
     ) -> Result<Type, TypeError> {
+        // Results of a multi-case active pattern carry their fields as one value
+        if let Some((union_type, carried)) = self.active_case_type(type_name, variant) {
+            let mut field_types = Vec::new();
+            for field in fields {
+                field_types.push(self.infer(field, env)?);
+            }
+            let fields_type = match field_types.len() {
+                0 => Type::Unit,
+                1 => field_types.remove(0),
+                _ => Type::Tuple(field_types),
+            };
+            self.add_constraint(Constraint::Equal(carried, fields_type));
+            return Ok(union_type);
+        }
+
+        // Declared cases check their fields and construct the union type
+        if let Some((declared, union_type)) = self.instantiate_case(variant) {
+            let expected = self.case_field_types(variant, declared, fields.len())?;
+            for (field, expected) in fields.iter().zip(expected) {
+                let field_type = self.infer(field, env)?;
+                self.add_constraint(Constraint::Equal(expected, field_type));
+            }
+            return Ok(union_type);
+        }
+
         // Infer types of all fields
         let mut field_types = Vec::new();
         for field in fields {
@@ -963,6 +2136,21 @@ impl TypeInference {
         Ok(Type::Variant(variant.to_string(), field_types))
     }
 
+    /// The result type of the multi-case active pattern `function`, with a
+    /// fresh type for the value each case carries, and the type carried by
+    /// `case`.
+    ///
+    /// `|Even|Odd|` returns `Variant("|Even|Odd|", [even; odd])`.
+    fn active_case_type(&mut self, function: &str, case: &str) -> Option<(Type, Type)> {
+        let (cases, false) = active_pattern_cases(function)? else {
+            return None;
+        };
+        let index = cases.iter().position(|c| *c == case)?;
+        let carried: Vec<Type> = cases.iter().map(|_| Type::Var(self.fresh_var())).collect();
+        let case_type = carried[index].clone();
+        Some((Type::Variant(function.to_string(), carried), case_type))
+    }
+
     /// Infer the type of a match expression.
     ///
     /// For `match scrutinee with | pat1 -> e1 | pat2 -> e2`:
@@ -998,6 +2186,26 @@ impl TypeInference {
         Ok(first_result_type)
     }
 
+    /// Infer the type of a try ... with expression.
+    ///
+    /// Exceptions are ordinary union values whose cases are typed one by one,
+    /// so each arm gets its own scrutinee type; every arm must produce the
+    /// body's type.
+    fn infer_try_with(
+        &mut self,
+        body: &Expr,
+        arms: &[MatchArm],
+        env: &TypeEnv,
+    ) -> Result<Type, TypeError> {
+        let body_type = self.infer(body, env)?;
+        let exn_type = Type::Variant(EXN_TYPE_NAME.to_string(), vec![]);
+        for arm in arms {
+            let (_, arm_type) = self.infer_match_arm(arm, &exn_type, env)?;
+            self.add_constraint(Constraint::Equal(body_type.clone(), arm_type));
+        }
+        Ok(body_type)
+    }
+
     /// Infer the type of a single match arm.
     ///
     /// Returns the extended environment from pattern bindings and the body type.
@@ -1010,6 +2218,12 @@ impl TypeInference {
         // Check pattern against scrutinee type and get bindings
         let pattern_env = self.infer_pattern(&arm.pattern, scrutinee_type, env)?;
 
+        // Guard sees the pattern bindings and must be a boolean
+        if let Some(guard) = &arm.guard {
+            let guard_type = self.infer(guard, &pattern_env)?;
+            self.add_constraint(Constraint::Equal(guard_type, Type::Bool));
+        }
+
         // Infer body type in extended environment
         let body_type = self.infer(&arm.body, &pattern_env)?;
 
@@ -1064,14 +2278,26 @@ impl TypeInference {
 
             // Variant pattern
             Pattern::Variant { variant, patterns } => {
-                // Create types for variant fields
-                let mut field_types = Vec::new();
-                for _ in patterns {
-                    field_types.push(Type::Var(self.fresh_var()));
-                }
+                let field_types = if let Some((declared, union_type)) =
+                    self.instantiate_case(variant)
+                {
+                    self.add_constraint(Constraint::Equal(scrutinee_ty.clone(), union_type));
+                    // `Rect _` matches all of the case's fields
+                    if matches!(patterns.as_slice(), [Pattern::Wildcard]) {
+                        return Ok(env.clone());
+                    }
+                    self.case_field_types(variant, declared, patterns.len())?
+                } else {
+                    // Create types for variant fields
+                    let mut field_types = Vec::new();
+                    for _ in patterns {
+                        field_types.push(Type::Var(self.fresh_var()));
+                    }
 
-                let expected_variant = Type::Variant(variant.clone(), field_types.clone());
-                self.add_constraint(Constraint::Equal(scrutinee_ty.clone(), expected_variant));
+                    let expected_variant = Type::Variant(variant.clone(), field_types.clone());
+                    self.add_constraint(Constraint::Equal(scrutinee_ty.clone(), expected_variant));
+                    field_types
+                };
 
                 // Process field patterns
                 let mut extended_env = env.clone();
@@ -1081,16 +2307,199 @@ impl TypeInference {
 
                 Ok(extended_env)
             }
+
+            // List pattern: every element has the list's element type
+            Pattern::List(patterns) => {
+                let elem_type = Type::Var(self.fresh_var());
+                self.add_constraint(Constraint::Equal(
+                    scrutinee_ty.clone(),
+                    Type::List(Box::new(elem_type.clone())),
+                ));
+
+                let mut extended_env = env.clone();
+                for pattern in patterns {
+                    extended_env = self.infer_pattern(pattern, &elem_type, &extended_env)?;
+                }
+
+                Ok(extended_env)
+            }
+
+            // Cons pattern: head is an element, tail is the same list type
+            Pattern::Cons { head, tail } => {
+                let elem_type = Type::Var(self.fresh_var());
+                self.add_constraint(Constraint::Equal(
+                    scrutinee_ty.clone(),
+                    Type::List(Box::new(elem_type.clone())),
+                ));
+
+                let extended_env = self.infer_pattern(head, &elem_type, env)?;
+                self.infer_pattern(tail, scrutinee_ty, &extended_env)
+            }
+
+            // Array pattern
+            Pattern::Array(patterns) => {
+                let elem_type = Type::Var(self.fresh_var());
+                self.add_constraint(Constraint::Equal(
+                    scrutinee_ty.clone(),
+                    Type::Array(Box::new(elem_type.clone())),
+                ));
+
+                let mut extended_env = env.clone();
+                for pattern in patterns {
+                    extended_env = self.infer_pattern(pattern, &elem_type, &extended_env)?;
+                }
+
+                Ok(extended_env)
+            }
+
+            // Record pattern: each field is typed like a record access
+            Pattern::Record(fields) => {
+                let mut extended_env = env.clone();
+                for (name, pattern) in fields {
+                    let field_type = self.infer_field(scrutinee_ty.clone(), name)?;
+                    extended_env = self.infer_pattern(pattern, &field_type, &extended_env)?;
+                }
+                Ok(extended_env)
+            }
+
+            // Or-pattern: each alternative matches the scrutinee and binds
+            // the same variables at the same types
+            Pattern::Or(alternatives) => {
+                let Some((first, rest)) = alternatives.split_first() else {
+                    return Ok(env.clone());
+                };
+
+                let extended_env = self.infer_pattern(first, scrutinee_ty, env)?;
+                for alternative in rest {
+                    let alt_env = self.infer_pattern(alternative, scrutinee_ty, env)?;
+                    for name in first.bound_vars() {
+                        if let (Some(expected), Some(actual)) =
+                            (extended_env.lookup(name), alt_env.lookup(name))
+                        {
+                            self.add_constraint(Constraint::Equal(
+                                expected.ty.clone(),
+                                actual.ty.clone(),
+                            ));
+                        }
+                    }
+                }
+
+                Ok(extended_env)
+            }
+
+            // As-pattern binds the whole value alongside the inner pattern
+            Pattern::As { pattern, name } => {
+                let extended_env = self.infer_pattern(pattern, scrutinee_ty, env)?;
+                let scheme = TypeScheme::mono(scrutinee_ty.clone());
+                Ok(extended_env.extend(name.clone(), scheme))
+            }
+
+            // Active pattern: the function maps the scrutinee to its result,
+            // and the case's patterns match the value the result carries
+            Pattern::Active {
+                function,
+                case,
+                patterns,
+            } => {
+                let function_type = self.infer_var(function, env)?;
+                let result_type = Type::Var(self.fresh_var());
+                self.add_constraint(Constraint::Equal(
+                    function_type,
+                    Type::Function(
+                        Box::new(scrutinee_ty.clone()),
+                        Box::new(result_type.clone()),
+                    ),
+                ));
+
+                let carried = match active_pattern_cases(function) {
+                    Some((_, true)) => {
+                        let carried = Type::Var(self.fresh_var());
+                        self.add_constraint(Constraint::Equal(
+                            result_type,
+                            Type::Variant("Option".to_string(), vec![carried.clone()]),
+                        ));
+                        carried
+                    }
+                    Some((cases, false)) if cases.len() == 1 => result_type,
+                    _ => {
+                        let (union_type, carried) =
+                            self.active_case_type(function, case).ok_or_else(|| {
+                                TypeError::new(TypeErrorKind::UnboundVariable {
+                                    name: case.clone(),
+                                })
+                            })?;
+                        self.add_constraint(Constraint::Equal(result_type, union_type));
+                        carried
+                    }
+                };
+                self.infer_pattern(&active_result_pattern(patterns), &carried, env)
+            }
+        }
+    }
+
+    /// Unify the types of the fields two record types have in common.
+    fn unify_shared_fields(
+        &mut self,
+        fields1: &HashMap<String, Type>,
+        fields2: &HashMap<String, Type>,
+    ) -> Result<Substitution, TypeError> {
+        let mut subst = Substitution::empty();
+        for (name, ty1) in fields1 {
+            if let Some(ty2) = fields2.get(name) {
+                let new_subst = self.unify(&ty1.apply(&subst), &ty2.apply(&subst))?;
+                subst = Substitution::compose(&new_subst, &subst);
+            }
         }
+        Ok(subst)
+    }
+
+    /// The fields of `fields` that `other` lacks, with `subst` applied.
+    fn fields_missing_from(
+        fields: &HashMap<String, Type>,
+        other: &HashMap<String, Type>,
+        subst: &Substitution,
+    ) -> HashMap<String, Type> {
+        fields
+            .iter()
+            .filter(|(name, _)| !other.contains_key(*name))
+            .map(|(name, ty)| (name.clone(), ty.apply(subst)))
+            .collect()
     }
 
     /// Solve all accumulated constraints using unification.
     ///
     /// Returns a substitution that satisfies all constraints.
+    ///
+    /// Arithmetic on operands that remain unknown defaults to int.
     pub fn solve_constraints(&mut self) -> Result<Substitution, TypeError> {
+        self.solve_all(true)
+    }
+
+    /// Solve the accumulated constraints, leaving arithmetic on unknown
+    /// operands in `deferred` rather than defaulting it, so that a binding
+    /// such as `let add a b = a + b` can still be generalized.
+    fn solve_deferring(&mut self) -> Result<Substitution, TypeError> {
+        self.solve_all(false)
+    }
+
+    fn solve_all(&mut self, default_operators: bool) -> Result<Substitution, TypeError> {
+        // Unifying rows may create type variables, so the constraints are
+        // set aside while they are solved
+        let constraints = std::mem::take(&mut self.constraints);
+        let result = self.solve(&constraints, default_operators);
+        self.constraints = constraints;
+        result
+    }
+
+    fn solve(
+        &mut self,
+        constraints: &[Constraint],
+        default_operators: bool,
+    ) -> Result<Substitution, TypeError> {
         let mut subst = Substitution::empty();
+        let mut waiting = Vec::new();
 
-        for constraint in &self.constraints {
+        for constraint in constraints {
             match constraint {
                 Constraint::Equal(t1, t2) => {
                     // Apply current substitution to both sides
@@ -1101,24 +2510,155 @@ impl TypeInference {
                     let new_subst = self.unify(&t1_subst, &t2_subst)?;
                     subst = Substitution::compose(&new_subst, &subst);
                 }
+                Constraint::Annotated(expected, actual, span) => {
+                    let new_subst = self
+                        .unify(&expected.apply(&subst), &actual.apply(&subst))
+                        .map_err(|mut err| {
+                            err.span = Some(*span);
+                            err
+                        })?;
+                    subst = Substitution::compose(&new_subst, &subst);
+                }
+                Constraint::Operator(..) => waiting.push(constraint.apply(&subst)),
+            }
+
+            // Operators wait until an operand's type is known
+            let mut progress = true;
+            while progress {
+                progress = false;
+                for waiting_constraint in std::mem::take(&mut waiting) {
+                    let current = waiting_constraint.apply(&subst);
+                    match self.resolve_operator(&current, false)? {
+                        Some(new_subst) => {
+                            subst = Substitution::compose(&new_subst, &subst);
+                            progress = true;
+                        }
+                        None => waiting.push(current),
+                    }
+                }
+            }
+        }
+
+        self.deferred.clear();
+        for waiting_constraint in waiting {
+            let current = waiting_constraint.apply(&subst);
+            if default_operators {
+                if let Some(new_subst) = self.resolve_operator(&current, true)? {
+                    subst = Substitution::compose(&new_subst, &subst);
+                }
+            } else {
+                self.deferred.push(current);
             }
         }
 
         Ok(subst)
     }
 
+    /// Resolve an operator constraint once an operand's type is known.
+    ///
+    /// An operand whose type declares the operator as a member gives the
+    /// member's signature; otherwise both operands and the result are float
+    /// when either operand is, and int when either is known. Returns `None`
+    /// while both operands are type variables, unless `default_to_int`.
+    ///
+    /// Units of measure must agree for `+`, `-` and `%`, while `*` and `/`
+    /// multiply and divide them. Those wait for both operands, so that
+    /// `let half x = x / 2.0` keeps the unit of its argument; by default an
+    /// unknown operand is dimensionless.
+    fn resolve_operator(
+        &mut self,
+        constraint: &Constraint,
+        default_to_int: bool,
+    ) -> Result<Option<Substitution>, TypeError> {
+        let Constraint::Operator(op, left, right, result) = constraint else {
+            return Ok(None);
+        };
+
+        let member = op.member_name().and_then(|name| {
+            [left, right]
+                .into_iter()
+                .find_map(|operand| self.type_members(operand)?.get(name).cloned())
+        });
+        if let Some(scheme) = member {
+            let member_type = TypeEnv::new().instantiate(&scheme, &mut || self.fresh_var());
+            let expected = Type::Function(
+                Box::new(left.clone()),
+                Box::new(Type::Function(
+                    Box::new(right.clone()),
+                    Box::new(result.clone()),
+                )),
+            );
+            return self.unify(&member_type, &expected).map(Some);
+        }
+
+        if matches!(op, BinOp::Mul | BinOp::Div) {
+            match (left.as_measured(), right.as_measured()) {
+                (Some((base, left_measure)), Some((right_base, right_measure))) => {
+                    let subst = self.unify(base, right_base)?;
+                    let measure = match op {
+                        BinOp::Mul => left_measure.product(&right_measure),
+                        _ => left_measure.quotient(&right_measure),
+                    };
+                    let product = Type::measured(base.clone(), measure);
+                    let new_subst = self.unify(&result.apply(&subst), &product)?;
+                    return Ok(Some(Substitution::compose(&new_subst, &subst)));
+                }
+                (Some((base, _)), None) | (None, Some((base, _)))
+                    if matches!(left, Type::Var(_)) || matches!(right, Type::Var(_)) =>
+                {
+                    if !default_to_int {
+                        return Ok(None);
+                    }
+                    let unknown = if matches!(left, Type::Var(_)) {
+                        left
+                    } else {
+                        right
+                    };
+                    let subst = self.unify(unknown, base)?;
+                    let resolved = self.resolve_operator(&constraint.apply(&subst), true)?;
+                    return Ok(resolved.map(|new_subst| Substitution::compose(&new_subst, &subst)));
+                }
+                _ => {}
+            }
+        }
+
+        let is_float = |ty: &Type| matches!(ty.as_measured(), Some((Type::Float, _)));
+        let number = match [left, right].into_iter().find(|ty| is_float(ty)) {
+            Some(float) => float.clone(),
+            None => match [left, right]
+                .into_iter()
+                .find(|ty| ty.as_measured().is_some())
+            {
+                Some(int) => int.clone(),
+                None if matches!((left, right), (Type::Var(_), Type::Var(_)))
+                    && !default_to_int =>
+                {
+                    return Ok(None)
+                }
+                None => Type::Int,
+            },
+        };
+        let mut subst = Substitution::empty();
+        for operand in [left, right, result] {
+            let new_subst = self.unify(&operand.apply(&subst), &number)?;
+            subst = Substitution::compose(&new_subst, &subst);
+        }
+        Ok(Some(subst))
+    }
+
     /// Unify two types using Robinson's unification algorithm.
     ///
     /// Returns a substitution that makes the types equal, or an error if unification fails.
-    #[allow(clippy::only_used_in_recursion)]
-    pub fn unify(&self, t1: &Type, t2: &Type) -> Result<Substitution, TypeError> {
+    pub fn unify(&mut self, t1: &Type, t2: &Type) -> Result<Substitution, TypeError> {
         match (t1, t2) {
             // Identical types unify trivially
             (Type::Int, Type::Int)
             | (Type::Bool, Type::Bool)
             | (Type::String, Type::String)
             | (Type::Unit, Type::Unit)
-            | (Type::Float, Type::Float) => Ok(Substitution::empty()),
+            | (Type::Float, Type::Float)
+            | (Type::Char, Type::Char)
+            | (Type::Bytes, Type::Bytes) => Ok(Substitution::empty()),
 
             // Same type variable
             (Type::Var(v1), Type::Var(v2)) if v1 == v2 => Ok(Substitution::empty()),
@@ -1198,6 +2738,63 @@ impl TypeInference {
                 Ok(subst)
             }
 
+            // Anonymous records unify field by field; fields only one side has
+            // extend the other side's row, which must then be open
+            (Type::AnonRecord(fields1, rest1), Type::AnonRecord(fields2, rest2)) => {
+                let mismatch = || {
+                    TypeError::new(TypeErrorKind::Mismatch {
+                        expected: t1.clone(),
+                        got: t2.clone(),
+                    })
+                };
+                let mut subst = self.unify_shared_fields(fields1, fields2)?;
+                let only1 = Self::fields_missing_from(fields1, fields2, &subst);
+                let only2 = Self::fields_missing_from(fields2, fields1, &subst);
+
+                let tail = match (rest1, rest2) {
+                    (Some(r1), Some(r2)) if r1 == r2 => {
+                        return if only1.is_empty() && only2.is_empty() {
+                            Ok(subst)
+                        } else {
+                            Err(mismatch())
+                        };
+                    }
+                    (Some(_), Some(_)) => Some(self.fresh_var()),
+                    _ => None,
+                };
+                for (rest, extra) in [(rest1, only2), (rest2, only1)] {
+                    match rest {
+                        Some(var) => {
+                            let row = Type::AnonRecord(extra, tail.clone());
+                            let new_subst =
+                                self.unify(&Type::Var(var.clone()).apply(&subst), &row)?;
+                            subst = Substitution::compose(&new_subst, &subst);
+                        }
+                        None if !extra.is_empty() => return Err(mismatch()),
+                        None => {}
+                    }
+                }
+                Ok(subst)
+            }
+
+            // A declared record satisfies an open row with a subset of its fields
+            (Type::Record(fields), Type::AnonRecord(row, Some(rest)))
+            | (Type::AnonRecord(row, Some(rest)), Type::Record(fields)) => {
+                if let Some(field) = row.keys().find(|f| !fields.contains_key(*f)) {
+                    return Err(TypeError::new(TypeErrorKind::FieldNotFound {
+                        record_type: Type::Record(fields.clone()),
+                        field: field.clone(),
+                    }));
+                }
+                let subst = self.unify_shared_fields(fields, row)?;
+                let remaining = Self::fields_missing_from(fields, row, &subst);
+                let new_subst = self.unify(
+                    &Type::Var(rest.clone()).apply(&subst),
+                    &Type::Record(remaining),
+                )?;
+                Ok(Substitution::compose(&new_subst, &subst))
+            }
+
             // Variant types unify if same variant name and field types unify
             (Type::Variant(name1, fields1), Type::Variant(name2, fields2)) => {
                 if name1 != name2 {
@@ -1224,6 +2821,34 @@ impl TypeInference {
                 Ok(subst)
             }
 
+            // Numbers agree on their base type and their unit of measure
+            (Type::Measured(..), _) | (_, Type::Measured(..))
+                if t1.as_measured().is_some() && t2.as_measured().is_some() =>
+            {
+                let (Some((base1, measure1)), Some((base2, measure2))) =
+                    (t1.as_measured(), t2.as_measured())
+                else {
+                    unreachable!("both types are numbers");
+                };
+                let subst = self.unify(base1, base2)?;
+                let new_subst = self.unify_measures(&measure1, &measure2).ok_or_else(|| {
+                    TypeError::new(TypeErrorKind::Mismatch {
+                        expected: t1.clone(),
+                        got: t2.clone(),
+                    })
+                })?;
+                Ok(Substitution::compose(&new_subst, &subst))
+            }
+
+            (Type::Measure(m1), Type::Measure(m2)) => {
+                self.unify_measures(m1, m2).ok_or_else(|| {
+                    TypeError::new(TypeErrorKind::Mismatch {
+                        expected: t1.clone(),
+                        got: t2.clone(),
+                    })
+                })
+            }
+
             // All other cases are type mismatches
             _ => Err(TypeError::new(TypeErrorKind::Mismatch {
                 expected: t1.clone(),
@@ -1232,9 +2857,58 @@ impl TypeInference {
         }
     }
 
+    /// Unify two units of measure, or `None` when they differ.
+    ///
+    /// Measures form an abelian group, so this solves `m1 / m2 = 1` for the
+    /// unit variables (Kennedy's algorithm): the variable with the smallest
+    /// exponent is bound to cancel the rest when its exponent divides every
+    /// other one, and otherwise to a fresh variable that lowers the other
+    /// exponents, until the quotient is `1` or has no variables left.
+    fn unify_measures(&mut self, m1: &Measure, m2: &Measure) -> Option<Substitution> {
+        let mut subst = Substitution::empty();
+        let mut rest = m1.quotient(m2);
+        while !rest.is_one() {
+            let (var, exp) = rest
+                .units()
+                .filter_map(|(unit, exp)| match unit {
+                    MeasureUnit::Var(var) => Some((var.clone(), exp)),
+                    MeasureUnit::Named(_) => None,
+                })
+                .min_by_key(|(_, exp)| exp.abs())?;
+            let others: Vec<(MeasureUnit, i32)> = rest
+                .units()
+                .filter(|(unit, _)| **unit != MeasureUnit::Var(var.clone()))
+                .map(|(unit, exp)| (unit.clone(), exp))
+                .collect();
+
+            let divides = others.iter().all(|(_, other)| other % exp == 0);
+            let other_vars = others
+                .iter()
+                .any(|(unit, _)| matches!(unit, MeasureUnit::Var(_)));
+            if !divides && !other_vars {
+                // No choice for the variable makes 'u^2 equal to m^3
+                return None;
+            }
+            let mut value = if divides {
+                Measure::one()
+            } else {
+                Measure::var(self.fresh_var())
+            };
+            for (unit, other) in others {
+                value = value.product(&Measure::factor(unit, -other.div_euclid(exp)));
+            }
+
+            let new_subst = Substitution::singleton(var, Type::Measure(value));
+            rest = rest.apply(&new_subst);
+            subst = Substitution::compose(&new_subst, &subst);
+        }
+        Some(subst)
+    }
+
     /// Convenience method: infer type and solve constraints in one step.
     ///
-    /// This is the main entry point for type checking.
+    /// This is the main entry point for type checking. Members of types
+    /// registered since the last call are inferred first.
     ///
     /// # Example
     ///
@@ -1252,6 +2926,8 @@ impl TypeInference {
     pub fn infer_and_solve(&mut self, expr: &Expr, env: &TypeEnv) -> Result<Type, TypeError> {
         // Clear any previous constraints
         self.constraints.clear();
+        self.infer_members(env)?;
+        self.annotation_vars.clear();
 
         // Infer the type (generating constraints)
         let ty = self.infer(expr, env)?;
@@ -1273,6 +2949,7 @@ impl Default for TypeInference {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ast::{ModuleItem, TypeDefinition};
 
     // Helper to create simple test expressions
     fn lit_int(n: i64) -> Expr {
@@ -1286,6 +2963,8 @@ mod tests {
     fn lambda(param: &str, body: Expr) -> Expr {
         Expr::Lambda {
             param: param.to_string(),
+            param_ty: None,
+            return_ty: None,
             body: Box::new(body),
         }
     }
@@ -1297,9 +2976,39 @@ mod tests {
         }
     }
 
+    // Infer the type of `source` after `setup` has prepared the inference,
+    // for example by registering the types that the source uses
+    fn infer_source(
+        source: &str,
+        setup: impl FnOnce(&mut TypeInference),
+    ) -> Result<Type, TypeError> {
+        let tokens = crate::lexer::Lexer::new(source).tokenize().unwrap();
+        let expr = crate::parser::Parser::new(tokens).parse().unwrap();
+        let mut inf = TypeInference::new();
+        setup(&mut inf);
+        inf.infer_and_solve(&expr, &TypeEnv::new())
+    }
+
+    // Register the types declared by `declarations`
+    fn register_types(inf: &mut TypeInference, declarations: &str) {
+        let tokens = crate::lexer::Lexer::new(declarations).tokenize().unwrap();
+        let program = crate::parser::Parser::new(tokens).parse_program().unwrap();
+        for item in &program.items {
+            match item {
+                ModuleItem::TypeDef(TypeDefinition::Du(du)) => inf.register_du(du),
+                ModuleItem::TypeDef(TypeDefinition::Record(record)) => inf.register_record(record),
+                ModuleItem::TypeDef(TypeDefinition::Measure(measure)) => {
+                    inf.register_measure(measure)
+                }
+                _ => {}
+            }
+        }
+    }
+
     fn let_expr(name: &str, value: Expr, body: Expr) -> Expr {
         Expr::Let {
             name: name.to_string(),
+            ty: None,
             value: Box::new(value),
             body: Box::new(body),
         }
@@ -1329,6 +3038,21 @@ mod tests {
         assert_eq!(ty, Type::Bool);
     }
 
+    #[test]
+    fn test_infer_literal_char_and_bytes() {
+        let mut inf = TypeInference::new();
+        let env = TypeEnv::new();
+
+        let ty = inf
+            .infer_and_solve(&Expr::Lit(Literal::Char('a')), &env)
+            .unwrap();
+        assert_eq!(ty, Type::Char);
+        let ty = inf
+            .infer_and_solve(&Expr::Lit(Literal::Bytes(b"GET".to_vec())), &env)
+            .unwrap();
+        assert_eq!(ty, Type::Bytes);
+    }
+
     #[test]
     fn test_infer_identity_function() {
         let mut inf = TypeInference::new();
@@ -1387,6 +3111,777 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_infer_try_with_arms_match_body_type() {
+        // try 1 with | Failure _ -> 0 | HostError(_, _, _) -> 2
+        let mut inf = TypeInference::new();
+        let env = TypeEnv::new();
+        let failure = Pattern::Variant {
+            variant: "Failure".to_string(),
+            patterns: vec![Pattern::Wildcard],
+        };
+        let host_error = Pattern::Variant {
+            variant: "HostError".to_string(),
+            patterns: vec![Pattern::Wildcard, Pattern::Wildcard, Pattern::Wildcard],
+        };
+        let expr = Expr::TryWith {
+            body: Box::new(lit_int(1)),
+            arms: vec![
+                MatchArm::new(failure.clone(), lit_int(0)),
+                MatchArm::new(host_error, lit_int(2)),
+            ],
+        };
+        assert_eq!(inf.infer_and_solve(&expr, &env).unwrap(), Type::Int);
+
+        // try 1 with | Failure _ -> true
+        let mut inf = TypeInference::new();
+        let expr = Expr::TryWith {
+            body: Box::new(lit_int(1)),
+            arms: vec![MatchArm::new(failure, Expr::Lit(Literal::Bool(true)))],
+        };
+        assert!(inf.infer_and_solve(&expr, &env).is_err());
+    }
+
+    #[test]
+    fn test_infer_try_with_handler_binds_exn() {
+        // try "ok" with e -> e
+        let mut inf = TypeInference::new();
+        let expr = Expr::TryWith {
+            body: Box::new(Expr::Lit(Literal::Str("ok".to_string()))),
+            arms: vec![MatchArm::new(
+                Pattern::Var("e".to_string()),
+                Expr::Var("e".to_string()),
+            )],
+        };
+        assert!(inf.infer_and_solve(&expr, &TypeEnv::new()).is_err());
+
+        // try 1 with | Some _ -> 0
+        let mut inf = TypeInference::new();
+        let expr = Expr::TryWith {
+            body: Box::new(lit_int(1)),
+            arms: vec![MatchArm::new(
+                Pattern::Variant {
+                    variant: "Some".to_string(),
+                    patterns: vec![Pattern::Wildcard],
+                },
+                lit_int(0),
+            )],
+        };
+        assert!(inf.infer_and_solve(&expr, &TypeEnv::new()).is_err());
+
+        // try "x" with | Failure msg -> msg
+        let mut inf = TypeInference::new();
+        let expr = Expr::TryWith {
+            body: Box::new(Expr::Lit(Literal::Str("x".to_string()))),
+            arms: vec![MatchArm::new(
+                Pattern::Variant {
+                    variant: "Failure".to_string(),
+                    patterns: vec![Pattern::Var("msg".to_string())],
+                },
+                Expr::Var("msg".to_string()),
+            )],
+        };
+        assert_eq!(
+            inf.infer_and_solve(&expr, &TypeEnv::new()).unwrap(),
+            Type::String
+        );
+    }
+
+    #[test]
+    fn test_infer_assignment_keeps_declared_type() {
+        // let mutable x = 0 in x <- 1
+        let mut inf = TypeInference::new();
+        let env = TypeEnv::new();
+        let assign = |value| Expr::LetMutable {
+            name: "x".to_string(),
+            value: Box::new(lit_int(0)),
+            body: Box::new(Expr::Assign {
+                name: "x".to_string(),
+                value: Box::new(value),
+            }),
+        };
+        assert_eq!(
+            inf.infer_and_solve(&assign(lit_int(1)), &env).unwrap(),
+            Type::Unit
+        );
+
+        // let mutable x = 0 in x <- true
+        let mut inf = TypeInference::new();
+        let expr = assign(Expr::Lit(Literal::Bool(true)));
+        assert!(inf.infer_and_solve(&expr, &env).is_err());
+    }
+
+    #[test]
+    fn test_infer_interpolated_string_checks_typed_holes() {
+        // Holes of different types are fine; %s accepts anything
+        assert_eq!(
+            infer_source(r#"$"{"apples"}: {3:%d} at {0.5:%.2f}""#, |_| {}).unwrap(),
+            Type::String
+        );
+        assert!(infer_source(r#"$"{"three":%d}""#, |_| {}).is_err());
+        assert!(infer_source(r#"$"{1:%f}""#, |_| {}).is_err());
+    }
+
+    #[test]
+    fn test_infer_sprintf_checks_arity_and_types() {
+        let sprintf = |format: &str, args: Vec<Expr>| Expr::App {
+            func: Box::new(Expr::App {
+                func: Box::new(var("sprintf")),
+                arg: Box::new(Expr::Lit(Literal::Str(format.to_string()))),
+            }),
+            arg: Box::new(Expr::List(args)),
+        };
+        let env = TypeEnv::new();
+
+        let expr = sprintf(
+            "%s is %d",
+            vec![Expr::Lit(Literal::Str("x".to_string())), lit_int(1)],
+        );
+        assert_eq!(
+            TypeInference::new().infer_and_solve(&expr, &env).unwrap(),
+            Type::String
+        );
+
+        let expr = sprintf("%s is %d", vec![lit_int(1)]);
+        let err = TypeInference::new()
+            .infer_and_solve(&expr, &env)
+            .unwrap_err();
+        assert!(matches!(
+            err.kind,
+            TypeErrorKind::ArityMismatch {
+                expected: 2,
+                got: 1
+            }
+        ));
+
+        let expr = sprintf("%d", vec![Expr::Lit(Literal::Bool(true))]);
+        assert!(TypeInference::new().infer_and_solve(&expr, &env).is_err());
+
+        let expr = sprintf("%y", vec![lit_int(1)]);
+        assert!(TypeInference::new().infer_and_solve(&expr, &env).is_err());
+    }
+
+    #[test]
+    fn test_infer_generic_du_instantiates_per_use() {
+        let tree = |inf: &mut TypeInference| {
+            register_types(
+                inf,
+                "type Tree<'a> = Leaf | Node of Tree<'a> * 'a * Tree<'a>",
+            )
+        };
+        let tree_of = |ty| Type::Variant("Tree".to_string(), vec![ty]);
+
+        assert_eq!(
+            infer_source("Node(Leaf, 1, Leaf)", tree).unwrap(),
+            tree_of(Type::Int)
+        );
+        // Leaf is instantiated separately at each use
+        assert_eq!(
+            infer_source(
+                r#"let a = Node(Leaf, 1, Leaf) in let b = Node(Leaf, "x", Leaf) in b"#,
+                tree
+            )
+            .unwrap(),
+            tree_of(Type::String)
+        );
+        // Fields must agree on 'a
+        assert!(infer_source(r#"Node(Node(Leaf, 1, Leaf), "x", Leaf)"#, tree).is_err());
+        // Matching recovers the element type
+        assert_eq!(
+            infer_source(
+                "match Node(Leaf, 2.5, Leaf) with | Leaf -> 0.0 | Node(_, v, _) -> v",
+                tree
+            )
+            .unwrap(),
+            Type::Float
+        );
+
+        let err = infer_source("Node(Leaf, 1)", tree).unwrap_err();
+        assert!(matches!(
+            err.kind,
+            TypeErrorKind::ArityMismatch {
+                expected: 3,
+                got: 2
+            }
+        ));
+    }
+
+    #[test]
+    fn test_infer_active_patterns() {
+        // Multi-case: each case carries its own type
+        assert_eq!(
+            infer_source(
+                "let (|Pos|Neg|) n = if n > 0 then Pos n else Neg true in \
+                 match 1 with | Pos p -> p | Neg b -> if b then 0 else 1",
+                |_| {}
+            )
+            .unwrap(),
+            Type::Int
+        );
+        // Partial: the payload of Some
+        assert_eq!(
+            infer_source(
+                "let (|Small|_|) n = if n < 10 then Some (n > 5) else None in \
+                 match 7 with | Small b -> b | _ -> false",
+                |_| {}
+            )
+            .unwrap(),
+            Type::Bool
+        );
+        // Single case: the result itself
+        assert_eq!(
+            infer_source(
+                "let (|Twice|) n = n * 2 in match 4 with | Twice t -> t",
+                |_| {}
+            )
+            .unwrap(),
+            Type::Int
+        );
+
+        // The scrutinee must fit the function's parameter
+        assert!(infer_source(
+            "let (|Even|Odd|) n = if n % 2 = 0 then Even else Odd in \
+             match \"s\" with | Even -> 1 | Odd -> 2",
+            |_| {}
+        )
+        .is_err());
+        // Case payloads keep their types
+        assert!(infer_source(
+            "let (|Pos|Neg|) n = if n > 0 then Pos n else Neg true in \
+             match 1 with | Pos p -> p | Neg b -> b",
+            |_| {}
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_infer_annotations_constrain_bindings() {
+        assert_eq!(
+            infer_source("let f (x: int) (y: string) : bool = true in f", |_| {}).unwrap(),
+            Type::Function(
+                Box::new(Type::Int),
+                Box::new(Type::Function(Box::new(Type::String), Box::new(Type::Bool)))
+            )
+        );
+        // Without the annotation, id would stay polymorphic
+        assert_eq!(
+            infer_source("let id (x: float) = x in id", |_| {}).unwrap(),
+            Type::Function(Box::new(Type::Float), Box::new(Type::Float))
+        );
+        // Annotation type variables are generalized like inferred ones
+        assert_eq!(
+            infer_source("let id (x: 'a) : 'a = x in id true", |_| {}).unwrap(),
+            Type::Bool
+        );
+
+        // byte[] and bytes name the same type
+        assert_eq!(
+            infer_source("let b: byte[] = \"ok\"B in let c: bytes = b in c", |_| {}).unwrap(),
+            Type::Bytes
+        );
+        assert!(infer_source("let c: char = \"a\" in c", |_| {}).is_err());
+
+        // Mismatches point at the annotation rather than the use site
+        let err = infer_source("let x: string = 42 in x", |_| {}).unwrap_err();
+        let span = err.span.expect("annotation mismatch should carry a span");
+        assert_eq!((span.start.column, span.end.column), (8, 14));
+
+        let err = infer_source("let f (n: int) : bool = n + 1 in f", |_| {}).unwrap_err();
+        assert!(matches!(err.kind, TypeErrorKind::Mismatch { .. }));
+        assert_eq!(err.span.unwrap().start.column, 18);
+    }
+
+    #[test]
+    fn test_infer_record_access_on_annotated_parameter() {
+        let config_type = |inf: &mut TypeInference| {
+            register_types(inf, "type Config = { Name: string; Port: int }")
+        };
+
+        let config = Type::Record(HashMap::from([
+            ("Name".to_string(), Type::String),
+            ("Port".to_string(), Type::Int),
+        ]));
+        assert_eq!(
+            infer_source(
+                "let describe (c: Config) = c.Port + 1 in describe",
+                config_type
+            )
+            .unwrap(),
+            Type::Function(Box::new(config), Box::new(Type::Int))
+        );
+    }
+
+    #[test]
+    fn test_infer_field_access_on_anonymous_records_generalizes() {
+        // name works on any record with a Name field, whatever its type
+        assert_eq!(
+            infer_source(
+                r#"let name r = r.Name in
+                   let a = name {| Name = "x"; Age = 3 |} in
+                   name {| Name = 1 |}"#,
+                |_| {}
+            )
+            .unwrap(),
+            Type::Int
+        );
+        assert_eq!(
+            infer_source(
+                "let area r = r.W * r.H in area {| H = 2; W = 3; Label = 0 |}",
+                |_| {}
+            )
+            .unwrap(),
+            Type::Int
+        );
+        assert!(infer_source("let name r = r.Name in name {| Age = 3 |}", |_| {}).is_err());
+        // Anonymous records are closed once built
+        assert!(matches!(
+            infer_source("{| X = 1 |}.Y", |_| {}).unwrap_err().kind,
+            TypeErrorKind::FieldNotFound { .. }
+        ));
+        assert_eq!(
+            infer_source(
+                "let p = {| X = 1; Y = true |} in { p with Y = false }.Y",
+                |_| {}
+            )
+            .unwrap(),
+            Type::Bool
+        );
+        assert!(infer_source("let p = {| X = 1 |} in { p with X = true }", |_| {}).is_err());
+    }
+
+    #[test]
+    fn test_infer_methods_and_properties() {
+        let declared = |inf: &mut TypeInference| {
+            register_types(
+                inf,
+                r#"
+type Shape =
+    | Circle of float
+    | Square of float
+with
+    member this.Area() = match this with | Circle r -> 3.0 * r * r | Square s -> s * s
+    member this.Scale(k: float) = match this with | Circle r -> Circle (r * k) | Square s -> Square (s * k)
+    member this.Big = this.Area() > 10.0
+    static member Unit = Circle 1.0
+type Config = { Host: string; Port: int }
+with
+    static member Default = { Host = "localhost"; Port = 80 }
+    member c.Secure = c.Port = 443
+"#,
+            )
+        };
+
+        assert_eq!(
+            infer_source("(Square 2.0).Scale(3.0)", declared).unwrap(),
+            Type::Variant("Shape".to_string(), vec![])
+        );
+        assert_eq!(
+            infer_source("fun (s: Shape) -> (s.Area(), s.Big)", declared).unwrap(),
+            Type::Function(
+                Box::new(Type::Variant("Shape".to_string(), vec![])),
+                Box::new(Type::Tuple(vec![Type::Float, Type::Bool]))
+            )
+        );
+        assert_eq!(
+            infer_source("(Config.Default.Port, Config.Default.Secure)", declared).unwrap(),
+            Type::Tuple(vec![Type::Int, Type::Bool])
+        );
+        assert_eq!(
+            infer_source("Shape.Unit.Area()", declared).unwrap(),
+            Type::Float
+        );
+
+        assert!(infer_source("(Circle 1.0).Scale(true)", declared).is_err());
+        assert!(infer_source("(Circle 1.0).Area(1)", declared).is_err());
+        assert!(infer_source("Config.Default.Secure(1)", declared).is_err());
+
+        // Declared types only have the members they declare
+        for source in [
+            "(Circle 1.0).Perimeter()",
+            "Shape.Unit.Radius",
+            "Config.Missing",
+            "Config.Default.Reset()",
+        ] {
+            assert!(
+                matches!(
+                    infer_source(source, declared).unwrap_err().kind,
+                    TypeErrorKind::MemberNotFound { .. }
+                ),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_infer_operators_resolve_to_members() {
+        let declared = |inf: &mut TypeInference| {
+            register_types(
+                inf,
+                r#"
+type Vector2 = { X: float; Y: float }
+with
+    static member (+) (a: Vector2, b: Vector2) = { X = a.X + b.X; Y = a.Y + b.Y }
+    static member ( * ) (v: Vector2) (k: float) = { X = v.X * k; Y = v.Y * k }
+type Money = Money of int
+with
+    static member (+) (a: Money, b: Money) =
+        match (a, b) with
+        | (Money x, Money y) -> Money (x + y)
+"#,
+            )
+        };
+        let vector = Type::Record(HashMap::from([
+            ("X".to_string(), Type::Float),
+            ("Y".to_string(), Type::Float),
+        ]));
+        let money = Type::Variant("Money".to_string(), vec![]);
+
+        assert_eq!(
+            infer_source("{ X = 1.0; Y = 2.0 } + { X = 3.0; Y = 4.0 }", declared).unwrap(),
+            vector
+        );
+        assert_eq!(
+            infer_source("fun (v: Vector2) -> v * 2.0", declared).unwrap(),
+            Type::Function(Box::new(vector.clone()), Box::new(vector))
+        );
+        assert_eq!(infer_source("1.5 * 2.0", declared).unwrap(), Type::Float);
+        assert_eq!(
+            infer_source("fun a b -> a + b", declared).unwrap(),
+            Type::Function(
+                Box::new(Type::Int),
+                Box::new(Type::Function(Box::new(Type::Int), Box::new(Type::Int)))
+            )
+        );
+
+        // A generic sum keeps its operator constraint and resolves it per use
+        assert_eq!(
+            infer_source(
+                "let rec sum zero xs = match xs with | [] -> zero | x :: rest -> x + sum zero rest in
+                 (sum 0 [1; 2], sum 0.5 [1.5], sum (Money 0) [Money 1])",
+                declared
+            )
+            .unwrap(),
+            Type::Tuple(vec![Type::Int, Type::Float, money])
+        );
+
+        assert!(infer_source("{ X = 1.0; Y = 2.0 } - { X = 1.0; Y = 1.0 }", declared).is_err());
+        assert!(infer_source("Money 1 + 2", declared).is_err());
+        assert!(infer_source("1 + 2.0", declared).is_err());
+        assert!(infer_source(
+            "let rec sum zero xs = match xs with | [] -> zero | x :: rest -> x + sum zero rest in
+             sum true [false]",
+            declared
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_infer_units_of_measure() {
+        let measures = |inf: &mut TypeInference| {
+            register_types(
+                inf,
+                "[<Measure>] type m\n[<Measure>] type s\n[<Measure>] type Hz = 1/s\n",
+            )
+        };
+
+        assert_eq!(
+            infer_source("1.0<m> + 2.0<m>", measures)
+                .unwrap()
+                .to_string(),
+            "float<m>"
+        );
+        assert_eq!(
+            infer_source("let d = 100.0<m> in let t = 9.58<s> in d / t", measures)
+                .unwrap()
+                .to_string(),
+            "float<m/s>"
+        );
+        assert_eq!(
+            infer_source(
+                "let speed (d: float<m>) (t: float<s>) : float<m/s> = d / t in \
+                 speed 1.0<m> 2.0<s> * 3.0<s>",
+                measures
+            )
+            .unwrap()
+            .to_string(),
+            "float<m>"
+        );
+        assert_eq!(
+            infer_source("5<Hz> * 2<s>", measures).unwrap().to_string(),
+            "int"
+        );
+        assert_eq!(
+            infer_source("1.0<m> < 2.0<m>", measures)
+                .unwrap()
+                .to_string(),
+            "bool"
+        );
+
+        // Generic code keeps the unit of its argument
+        assert_eq!(
+            infer_source("let half x = x / 2.0 in (half 3.0<m>, half 4.0)", measures)
+                .unwrap()
+                .to_string(),
+            "(float<m> * float)"
+        );
+        assert_eq!(
+            infer_source("let sq (x: float<'u>) = x * x in sq 3.0<m/s>", measures)
+                .unwrap()
+                .to_string(),
+            "float<m^2/s^2>"
+        );
+
+        assert!(infer_source("1.0<m> + 2.0<s>", measures).is_err());
+        assert!(infer_source("1.0<m> + 2.0", measures).is_err());
+        assert!(infer_source("1<m> = 1.0<m>", measures).is_err());
+        assert!(infer_source("fun (x: float<'u^2>) -> x + 1.0<m^3>", measures).is_err());
+        assert!(matches!(
+            TypeInference::new()
+                .infer_and_solve(
+                    &Expr::MeasuredLit {
+                        value: Literal::Float(1.0),
+                        measure: TypeExpr::Measure(vec![(TypeExpr::Named("kg".to_string()), 1)]),
+                    },
+                    &TypeEnv::new()
+                )
+                .unwrap_err()
+                .kind,
+            TypeErrorKind::UnknownType { .. }
+        ));
+    }
+
+    #[test]
+    fn test_infer_ranges_and_comprehensions() {
+        let list = |t: Type| Type::List(Box::new(t));
+
+        assert_eq!(infer_source("[1 .. 10]", |_| {}).unwrap(), list(Type::Int));
+        assert_eq!(
+            infer_source("[0.0 .. 0.5 .. 2.0]", |_| {}).unwrap(),
+            list(Type::Float)
+        );
+        assert!(infer_source("[1 .. true]", |_| {}).is_err());
+        assert_eq!(
+            infer_source("[ for i in 1 .. 3 -> i > 1 ]", |_| {}).unwrap(),
+            list(Type::Bool)
+        );
+        assert_eq!(
+            infer_source(
+                r#"[| for x in [1; 2] do if x > 1 then yield "big" |]"#,
+                |_| {}
+            )
+            .unwrap(),
+            Type::Array(Box::new(Type::String))
+        );
+        assert_eq!(
+            infer_source("[ yield 0; yield! [| 1; 2 |] ]", |_| {}).unwrap(),
+            list(Type::Int)
+        );
+        assert!(infer_source("[ yield 0; yield true ]", |_| {}).is_err());
+        assert!(infer_source("yield 1", |_| {}).is_err());
+    }
+
+    #[test]
+    fn test_infer_bitwise_and_composition_operators() {
+        assert_eq!(infer_source("7 % 3", |_| {}).unwrap(), Type::Int);
+        assert_eq!(
+            infer_source("(6 &&& 3) ||| 1 <<< 4", |_| {}).unwrap(),
+            Type::Int
+        );
+        assert!(infer_source("true ^^^ false", |_| {}).is_err());
+        assert_eq!(
+            infer_source("(fun x -> x + 1) >> (fun n -> n > 2)", |_| {}).unwrap(),
+            Type::Function(Box::new(Type::Int), Box::new(Type::Bool))
+        );
+        assert_eq!(
+            infer_source("((fun n -> n > 2) << (fun x -> x + 1)) <| 5", |_| {}).unwrap(),
+            Type::Bool
+        );
+        assert!(infer_source("(fun x -> x + 1) >> (fun b -> b && true)", |_| {}).is_err());
+        assert_eq!(infer_source(r#""a" ++ "b""#, |_| {}).unwrap(), Type::String);
+    }
+
+    #[test]
+    fn test_infer_field_access_resolves_declared_records() {
+        let declared = |inf: &mut TypeInference| {
+            register_types(
+                inf,
+                "type Person = { Name: string; Age: int }\n\
+                 type Pet = { Name: string; Species: string }",
+            )
+        };
+        let record = |fields: &[(&str, Type)]| {
+            Type::Record(
+                fields
+                    .iter()
+                    .map(|(name, ty)| (name.to_string(), ty.clone()))
+                    .collect(),
+            )
+        };
+        let person = record(&[("Name", Type::String), ("Age", Type::Int)]);
+        let pet = record(&[("Name", Type::String), ("Species", Type::String)]);
+
+        // The most recently declared record with the field wins
+        assert_eq!(
+            infer_source("fun r -> r.Name", declared).unwrap(),
+            Type::Function(Box::new(pet.clone()), Box::new(Type::String))
+        );
+        // An annotation or a qualified literal picks the other one
+        assert_eq!(
+            infer_source("fun (r: Person) -> r.Name", declared).unwrap(),
+            Type::Function(Box::new(person.clone()), Box::new(Type::String))
+        );
+        assert_eq!(
+            infer_source(r#"{ Person.Name = "Ann"; Age = 31 }"#, declared).unwrap(),
+            person
+        );
+        // An unqualified literal takes the record with exactly its fields
+        assert_eq!(
+            infer_source(r#"{ Name = "Ann"; Age = 31 }.Age"#, declared).unwrap(),
+            Type::Int
+        );
+
+        assert!(matches!(
+            infer_source(r#"{ Person.Name = "Ann" }"#, declared)
+                .unwrap_err()
+                .kind,
+            TypeErrorKind::MissingField { .. }
+        ));
+        assert!(matches!(
+            infer_source(
+                r#"{ Person.Name = "Ann"; Age = 31; Species = "cat" }"#,
+                declared
+            )
+            .unwrap_err()
+            .kind,
+            TypeErrorKind::ExtraField { .. }
+        ));
+        assert!(infer_source(r#"{ Person.Name = "Ann"; Age = "old" }"#, declared).is_err());
+        assert!(matches!(
+            infer_source(r#"{ Robot.Name = "R2" }"#, declared)
+                .unwrap_err()
+                .kind,
+            TypeErrorKind::UnknownType { .. }
+        ));
+    }
+
+    #[test]
+    fn test_infer_builtin_option_and_result() {
+        let env = TypeEnv::new();
+        let some = Expr::VariantConstruct {
+            type_name: String::new(),
+            variant: "Some".to_string(),
+            fields: vec![Box::new(lit_int(1))],
+        };
+        assert_eq!(
+            TypeInference::new().infer_and_solve(&some, &env).unwrap(),
+            Type::Variant("Option".to_string(), vec![Type::Int])
+        );
+
+        // if true then Some 1 else None
+        let expr = Expr::If {
+            cond: Box::new(Expr::Lit(Literal::Bool(true))),
+            then_branch: Box::new(some),
+            else_branch: Box::new(Expr::VariantConstruct {
+                type_name: String::new(),
+                variant: "None".to_string(),
+                fields: vec![],
+            }),
+        };
+        assert_eq!(
+            TypeInference::new().infer_and_solve(&expr, &env).unwrap(),
+            Type::Variant("Option".to_string(), vec![Type::Int])
+        );
+
+        let error = Expr::VariantConstruct {
+            type_name: String::new(),
+            variant: "Error".to_string(),
+            fields: vec![Box::new(Expr::Lit(Literal::Str("boom".to_string())))],
+        };
+        let ty = TypeInference::new().infer_and_solve(&error, &env).unwrap();
+        assert!(matches!(
+            ty,
+            Type::Variant(name, params) if name == "Result" && params[1] == Type::String
+        ));
+    }
+
+    #[test]
+    fn test_infer_match_guard_must_be_bool() {
+        let mut inf = TypeInference::new();
+        let env = TypeEnv::new();
+        // match 1 with | n when n > 0 -> n | _ -> 0
+        let positive = Expr::BinOp {
+            op: BinOp::Gt,
+            left: Box::new(var("n")),
+            right: Box::new(lit_int(0)),
+        };
+        let arms = vec![
+            MatchArm::with_guard(Pattern::Var("n".to_string()), positive, var("n")),
+            MatchArm::new(Pattern::Wildcard, lit_int(0)),
+        ];
+        let expr = Expr::Match {
+            scrutinee: Box::new(lit_int(1)),
+            arms,
+        };
+        assert_eq!(inf.infer_and_solve(&expr, &env).unwrap(), Type::Int);
+
+        // match 1 with | n when n -> n | _ -> 0
+        let mut inf = TypeInference::new();
+        let arms = vec![
+            MatchArm::with_guard(Pattern::Var("n".to_string()), var("n"), var("n")),
+            MatchArm::new(Pattern::Wildcard, lit_int(0)),
+        ];
+        let expr = Expr::Match {
+            scrutinee: Box::new(lit_int(1)),
+            arms,
+        };
+        assert!(inf.infer_and_solve(&expr, &env).is_err());
+    }
+
+    #[test]
+    fn test_infer_cons_and_list_patterns() {
+        let mut inf = TypeInference::new();
+        let env = TypeEnv::new();
+        // match [1] with | h :: t -> h | [] -> 0
+        let arms = vec![
+            MatchArm::new(
+                Pattern::Cons {
+                    head: Box::new(Pattern::Var("h".to_string())),
+                    tail: Box::new(Pattern::Var("t".to_string())),
+                },
+                var("h"),
+            ),
+            MatchArm::new(Pattern::List(vec![]), lit_int(0)),
+        ];
+        let expr = Expr::Match {
+            scrutinee: Box::new(Expr::List(vec![lit_int(1)])),
+            arms,
+        };
+        assert_eq!(inf.infer_and_solve(&expr, &env).unwrap(), Type::Int);
+    }
+
+    #[test]
+    fn test_infer_or_pattern_binding_types_must_agree() {
+        let mut inf = TypeInference::new();
+        let env = TypeEnv::new();
+        // match (1, true) with | (x, _) | (_, x) -> x
+        let arms = vec![MatchArm::new(
+            Pattern::Or(vec![
+                Pattern::Tuple(vec![Pattern::Var("x".to_string()), Pattern::Wildcard]),
+                Pattern::Tuple(vec![Pattern::Wildcard, Pattern::Var("x".to_string())]),
+            ]),
+            var("x"),
+        )];
+        let expr = Expr::Match {
+            scrutinee: Box::new(Expr::Tuple(vec![
+                lit_int(1),
+                Expr::Lit(Literal::Bool(true)),
+            ])),
+            arms,
+        };
+        assert!(inf.infer_and_solve(&expr, &env).is_err());
+    }
+
     // ========================================================================
     // Auto-Recursive Detection Tests (Issue #126)
     // ========================================================================
@@ -1499,10 +3994,12 @@ mod tests {
         // Define a Person record type
         let person_type = TypeDefinition::Record(RecordTypeDef {
             name: "Person".to_string(),
+            type_params: vec![],
             fields: vec![
                 ("name".to_string(), AstTypeExpr::Named("string".to_string())),
                 ("age".to_string(), AstTypeExpr::Named("int".to_string())),
             ],
+            members: vec![],
         });
 
         let mut types = HashMap::new();
@@ -1535,10 +4032,12 @@ mod tests {
         // Define a Person record type
         let person_type = TypeDefinition::Record(RecordTypeDef {
             name: "Person".to_string(),
+            type_params: vec![],
             fields: vec![
                 ("name".to_string(), AstTypeExpr::Named("string".to_string())),
                 ("age".to_string(), AstTypeExpr::Named("int".to_string())),
             ],
+            members: vec![],
         });
 
         let mut types = HashMap::new();
@@ -1579,10 +4078,12 @@ mod tests {
         // Define a Person record type
         let person_type = TypeDefinition::Record(RecordTypeDef {
             name: "Person".to_string(),
+            type_params: vec![],
             fields: vec![
                 ("name".to_string(), AstTypeExpr::Named("string".to_string())),
                 ("age".to_string(), AstTypeExpr::Named("int".to_string())),
             ],
+            members: vec![],
         });
 
         let mut types = HashMap::new();
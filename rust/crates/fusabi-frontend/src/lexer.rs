@@ -3,7 +3,8 @@
 //! This module implements the lexical analyzer that converts source text into
 //! a stream of tokens. The lexer supports:
 //!
-//! - Literals: integers, floats, booleans, strings
+//! - Literals: integers, floats, booleans, chars (`'a'`), strings, byte strings (`"..."B`),
+//!   interpolated strings (`$"..."`)
 //! - Keywords: let, rec, and, in, if, then, else, fun, true, false, async, return, yield
 //! - Computation expression keywords: let!, do!, return!, yield!
 //! - Identifiers: alphanumeric names starting with letter or underscore
@@ -40,16 +41,26 @@ pub enum Token {
     Bool(bool),
     /// String literal (e.g., "hello")
     String(String),
+    /// Character literal (e.g., 'a', '\n')
+    Char(char),
+    /// Byte string literal (e.g., "GET"B)
+    Bytes(Vec<u8>),
+    /// Interpolated string literal (e.g., $"Hello {name}")
+    InterpolatedString(Vec<InterpolationPart>),
 
     // Identifiers
     /// Identifier (variable or function name)
     Ident(String),
+    /// Type variable such as 'a, stored without the quote
+    TypeVar(String),
 
     // Keywords
     /// let keyword
     Let,
     /// rec keyword (for recursive bindings)
     Rec,
+    /// mutable keyword (for mutable let-bindings)
+    Mutable,
     /// and keyword (for mutual recursion)
     AndKeyword,
     /// in keyword
@@ -68,8 +79,24 @@ pub enum Token {
     Type,
     /// with keyword (for record updates)
     With,
+    /// member keyword (for members declared on a type)
+    Member,
+    /// static keyword (for static members)
+    Static,
+    /// private keyword (binding visible only inside its module)
+    Private,
+    /// internal keyword (binding visible only inside its file)
+    Internal,
     /// of keyword (for discriminated unions)
     Of,
+    /// when keyword (for match arm guards)
+    When,
+    /// as keyword (for as-patterns)
+    As,
+    /// try keyword (for exception handling)
+    Try,
+    /// finally keyword (for try ... finally)
+    Finally,
     /// open keyword (for importing modules)
     Open,
     /// module keyword (for module definitions)
@@ -78,6 +105,12 @@ pub enum Token {
     Do,
     /// while keyword (for while loops)
     While,
+    /// for keyword (for for-loops)
+    For,
+    /// to keyword (for ascending for-loops)
+    To,
+    /// downto keyword (for descending for-loops)
+    Downto,
     /// break keyword (for breaking out of loops)
     Break,
     /// continue keyword (for continuing to next iteration)
@@ -106,6 +139,8 @@ pub enum Token {
     Star,
     /// / operator
     Slash,
+    /// % operator (remainder)
+    Percent,
     /// = operator
     Eq,
     /// == operator (equality comparison)
@@ -126,10 +161,32 @@ pub enum Token {
     Or,
     /// :: operator (cons)
     ColonColon,
-    /// <- operator (array assignment)
+    /// <- operator (array and mutable variable assignment)
     LArrow,
+    /// := operator (reference cell assignment)
+    ColonEquals,
+    /// ! operator (reference cell dereference)
+    Bang,
     /// |> operator (pipeline)
     PipeRight,
+    /// <| operator (backward pipeline)
+    PipeLeft,
+    /// >> operator (forward composition)
+    GtGt,
+    /// << operator (backward composition)
+    LtLt,
+    /// &&& operator (bitwise and)
+    AmpAmpAmp,
+    /// ||| operator (bitwise or)
+    PipePipePipe,
+    /// ^^^ operator (bitwise exclusive or)
+    CaretCaretCaret,
+    /// ^ (exponent in a unit of measure, as in m/s^2)
+    Caret,
+    /// <<< operator (left shift)
+    LtLtLt,
+    /// >>> operator (arithmetic right shift)
+    GtGtGt,
     /// ++ operator (string concatenation)
     PlusPlus,
 
@@ -154,6 +211,8 @@ pub enum Token {
     Semicolon,
     /// . dot
     Dot,
+    /// .. range operator
+    DotDot,
     /// { left brace
     LBrace,
     /// } right brace
@@ -185,10 +244,21 @@ impl fmt::Display for Token {
             Token::Float(n) => write!(f, "Float({})", n),
             Token::Bool(b) => write!(f, "Bool({})", b),
             Token::String(s) => write!(f, "String(\"{}\")", s),
+            Token::Char(c) => write!(f, "Char({:?})", c),
+            Token::Bytes(b) => write!(f, "Bytes({:?})", b),
+            Token::InterpolatedString(parts) => {
+                write!(f, "$\"")?;
+                for part in parts {
+                    write!(f, "{}", part)?;
+                }
+                write!(f, "\"")
+            }
             Token::Ident(s) => write!(f, "Ident({})", s),
+            Token::TypeVar(s) => write!(f, "'{}", s),
             Token::Let => write!(f, "let"),
             Token::In => write!(f, "in"),
             Token::Rec => write!(f, "rec"),
+            Token::Mutable => write!(f, "mutable"),
             Token::AndKeyword => write!(f, "and"),
             Token::If => write!(f, "if"),
             Token::Then => write!(f, "then"),
@@ -197,10 +267,15 @@ impl fmt::Display for Token {
             Token::Match => write!(f, "match"),
             Token::Type => write!(f, "type"),
             Token::With => write!(f, "with"),
+            Token::Member => write!(f, "member"),
+            Token::Static => write!(f, "static"),
+            Token::Private => write!(f, "private"),
+            Token::Internal => write!(f, "internal"),
             Token::Plus => write!(f, "+"),
             Token::Minus => write!(f, "-"),
             Token::Star => write!(f, "*"),
             Token::Slash => write!(f, "/"),
+            Token::Percent => write!(f, "%"),
             Token::Eq => write!(f, "="),
             Token::EqEq => write!(f, "=="),
             Token::Neq => write!(f, "<>"),
@@ -212,7 +287,18 @@ impl fmt::Display for Token {
             Token::Or => write!(f, "||"),
             Token::ColonColon => write!(f, "::"),
             Token::LArrow => write!(f, "<-"),
+            Token::ColonEquals => write!(f, ":="),
+            Token::Bang => write!(f, "!"),
             Token::PipeRight => write!(f, "|>"),
+            Token::PipeLeft => write!(f, "<|"),
+            Token::GtGt => write!(f, ">>"),
+            Token::LtLt => write!(f, "<<"),
+            Token::AmpAmpAmp => write!(f, "&&&"),
+            Token::PipePipePipe => write!(f, "|||"),
+            Token::CaretCaretCaret => write!(f, "^^^"),
+            Token::Caret => write!(f, "^"),
+            Token::LtLtLt => write!(f, "<<<"),
+            Token::GtGtGt => write!(f, ">>>"),
             Token::PlusPlus => write!(f, "++"),
             Token::LParen => write!(f, "("),
             Token::RParen => write!(f, ")"),
@@ -224,6 +310,7 @@ impl fmt::Display for Token {
             Token::Comma => write!(f, ","),
             Token::Semicolon => write!(f, ";"),
             Token::Dot => write!(f, "."),
+            Token::DotDot => write!(f, ".."),
             Token::LBrace => write!(f, "{{"),
             Token::RBrace => write!(f, "}}"),
             Token::LBracePipe => write!(f, "{{|"),
@@ -232,10 +319,17 @@ impl fmt::Display for Token {
             Token::Pipe => write!(f, "|"),
             Token::Underscore => write!(f, "_"),
             Token::Of => write!(f, "of"),
+            Token::When => write!(f, "when"),
+            Token::As => write!(f, "as"),
+            Token::Try => write!(f, "try"),
+            Token::Finally => write!(f, "finally"),
             Token::Open => write!(f, "open"),
             Token::Module => write!(f, "module"),
             Token::Do => write!(f, "do"),
             Token::While => write!(f, "while"),
+            Token::For => write!(f, "for"),
+            Token::To => write!(f, "to"),
+            Token::Downto => write!(f, "downto"),
             Token::Break => write!(f, "break"),
             Token::Continue => write!(f, "continue"),
             Token::Async => write!(f, "async"),
@@ -251,6 +345,44 @@ impl fmt::Display for Token {
     }
 }
 
+/// A piece of an interpolated string literal.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterpolationPart {
+    /// Literal text, with escapes and `{{`/`}}` already resolved
+    Text(String),
+    /// A `{expr}` or `{expr:%spec}` hole
+    Hole {
+        /// Tokens of the expression, terminated by `Eof`
+        tokens: Vec<TokenWithPos>,
+        /// Format specifier after the colon, e.g. `%d`
+        format: Option<String>,
+    },
+}
+
+impl fmt::Display for InterpolationPart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InterpolationPart::Text(text) => {
+                write!(f, "{}", text.replace('{', "{{").replace('}', "}}"))
+            }
+            InterpolationPart::Hole { tokens, format } => {
+                write!(f, "{{")?;
+                let tokens = tokens.iter().filter(|t| t.token != Token::Eof);
+                for (i, tok) in tokens.enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", tok.token)?;
+                }
+                if let Some(format) = format {
+                    write!(f, ":{}", format)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
 /// A token with its position in the source code.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TokenWithPos {
@@ -296,6 +428,10 @@ pub enum LexError {
     UnterminatedComment(Position),
     /// Unknown directive
     UnknownDirective(String, Position),
+    /// Malformed hole in an interpolated string
+    InvalidInterpolation(String, Position),
+    /// Character above '\u{ff}' in a byte string literal
+    InvalidByteChar(char, Position),
 }
 
 impl fmt::Display for LexError {
@@ -316,6 +452,16 @@ impl fmt::Display for LexError {
             LexError::UnknownDirective(name, pos) => {
                 write!(f, "Unknown directive '{}' at {}", name, pos)
             }
+            LexError::InvalidInterpolation(message, pos) => {
+                write!(f, "Invalid interpolated string at {}: {}", pos, message)
+            }
+            LexError::InvalidByteChar(ch, pos) => {
+                write!(
+                    f,
+                    "Character '{}' does not fit in a byte string at {}",
+                    ch, pos
+                )
+            }
         }
     }
 }
@@ -409,6 +555,9 @@ impl Lexer {
                 }
             }
             '"' => self.lex_string(),
+            '$' if !self.is_at_end_or(1) && self.peek_char() == '"' => {
+                self.lex_interpolated_string()
+            }
             '+' => self.lex_plus_or_plusplus(),
             '-' => self.lex_minus_or_arrow(),
             '*' => {
@@ -419,15 +568,25 @@ impl Lexer {
                 self.advance();
                 Ok(Token::Slash)
             }
+            '%' => {
+                self.advance();
+                Ok(Token::Percent)
+            }
             '=' => self.lex_eq_or_eqeq(),
             '<' => self.lex_lt_or_lte_or_neq_or_larrow(),
             '>' => self.lex_gt_or_gte(),
             '&' => self.lex_and(),
+            '^' => self.lex_caret(),
             '|' => {
                 self.advance();
                 if !self.is_at_end() && self.current_char() == '|' {
                     self.advance();
-                    Ok(Token::Or)
+                    if !self.is_at_end() && self.current_char() == '|' {
+                        self.advance();
+                        Ok(Token::PipePipePipe)
+                    } else {
+                        Ok(Token::Or)
+                    }
                 } else if !self.is_at_end() && self.current_char() == '>' {
                     self.advance();
                     Ok(Token::PipeRight)
@@ -442,6 +601,10 @@ impl Lexer {
                 }
             }
             ':' => self.lex_colon_or_coloncolon(),
+            '!' => {
+                self.advance();
+                Ok(Token::Bang)
+            }
             '(' => {
                 self.advance();
                 Ok(Token::LParen)
@@ -470,9 +633,25 @@ impl Lexer {
             }
             '.' => {
                 self.advance();
-                Ok(Token::Dot)
+                if !self.is_at_end() && self.current_char() == '.' {
+                    self.advance();
+                    Ok(Token::DotDot)
+                } else {
+                    Ok(Token::Dot)
+                }
             }
             '#' => self.lex_directive(),
+            // 'a' and '\n' are chars; 'a on its own is a type variable
+            '\'' if self.peek_char() == '\\'
+                || (!self.is_at_end_or(2) && self.input[self.pos + 2] == '\'') =>
+            {
+                self.lex_char()
+            }
+            '\'' if !self.is_at_end_or(1)
+                && (self.peek_char().is_alphabetic() || self.peek_char() == '_') =>
+            {
+                self.lex_type_var()
+            }
             _ => Err(LexError::UnexpectedChar(ch, self.current_position())),
         }
     }
@@ -509,6 +688,20 @@ impl Lexer {
     }
 
     /// Lex an identifier or keyword.
+    /// Lex a type variable: 'a, 'key
+    fn lex_type_var(&mut self) -> Result<Token, LexError> {
+        self.advance(); // consume the quote
+        let start = self.pos;
+
+        while !self.is_at_end()
+            && (self.current_char().is_alphanumeric() || self.current_char() == '_')
+        {
+            self.advance();
+        }
+
+        Ok(Token::TypeVar(self.input[start..self.pos].iter().collect()))
+    }
+
     fn lex_identifier_or_keyword(&mut self) -> Result<Token, LexError> {
         let start = self.pos;
 
@@ -540,6 +733,7 @@ impl Lexer {
             "let" => Token::Let,
             "in" => Token::In,
             "rec" => Token::Rec,
+            "mutable" => Token::Mutable,
             "match" => Token::Match,
             "and" => Token::AndKeyword,
             "if" => Token::If,
@@ -548,11 +742,22 @@ impl Lexer {
             "fun" => Token::Fun,
             "type" => Token::Type,
             "with" => Token::With,
+            "member" => Token::Member,
+            "static" => Token::Static,
+            "private" => Token::Private,
+            "internal" => Token::Internal,
             "of" => Token::Of,
+            "when" => Token::When,
+            "as" => Token::As,
+            "try" => Token::Try,
+            "finally" => Token::Finally,
             "open" => Token::Open,
             "module" => Token::Module,
             "do" => Token::Do,
             "while" => Token::While,
+            "for" => Token::For,
+            "to" => Token::To,
+            "downto" => Token::Downto,
             "break" => Token::Break,
             "continue" => Token::Continue,
             "async" => Token::Async,
@@ -601,9 +806,175 @@ impl Lexer {
         }
 
         self.advance(); // consume closing "
+
+        // "..."B is a byte string
+        if self.current_char() == 'B'
+            && !(self.peek_char().is_alphanumeric() || self.peek_char() == '_')
+        {
+            self.advance();
+            return s
+                .chars()
+                .map(|c| u8::try_from(c).map_err(|_| LexError::InvalidByteChar(c, start_pos)))
+                .collect::<Result<Vec<u8>, LexError>>()
+                .map(Token::Bytes);
+        }
         Ok(Token::String(s))
     }
 
+    /// Lex a character literal: 'a', '\n', '\''
+    fn lex_char(&mut self) -> Result<Token, LexError> {
+        let start_pos = self.current_position();
+        self.advance(); // consume opening '
+
+        let ch = match self.current_char() {
+            '\\' => {
+                self.advance();
+                match self.current_char() {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    '0' => '\0',
+                    c => c, // \\, \' and \" stand for themselves
+                }
+            }
+            c => c,
+        };
+        self.advance();
+
+        if self.current_char() != '\'' {
+            return Err(LexError::UnexpectedChar(self.current_char(), start_pos));
+        }
+        self.advance(); // consume closing '
+        Ok(Token::Char(ch))
+    }
+
+    /// Lex an interpolated string literal: $"text {expr} {expr:%d}"
+    ///
+    /// Holes are tokenized in place, so their tokens keep their real source
+    /// positions; the parser turns each hole into an expression.
+    fn lex_interpolated_string(&mut self) -> Result<Token, LexError> {
+        let start_pos = self.current_position();
+        self.advance(); // consume $
+        self.advance(); // consume opening "
+
+        let mut parts = Vec::new();
+        let mut text = String::new();
+
+        loop {
+            if self.is_at_end() {
+                return Err(LexError::UnterminatedString(start_pos));
+            }
+            match self.current_char() {
+                '"' => break,
+                '\\' => {
+                    self.advance();
+                    if self.is_at_end() {
+                        return Err(LexError::UnterminatedString(start_pos));
+                    }
+                    text.push(match self.current_char() {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        c => c, // \\, \" and unknown escapes
+                    });
+                    self.advance();
+                }
+                '{' if !self.is_at_end_or(1) && self.peek_char() == '{' => {
+                    text.push('{');
+                    self.advance();
+                    self.advance();
+                }
+                '}' if !self.is_at_end_or(1) && self.peek_char() == '}' => {
+                    text.push('}');
+                    self.advance();
+                    self.advance();
+                }
+                '{' => {
+                    if !text.is_empty() {
+                        parts.push(InterpolationPart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(self.lex_interpolation_hole()?);
+                }
+                '}' => {
+                    return Err(LexError::InvalidInterpolation(
+                        "unmatched '}' (write '}}' for a literal brace)".to_string(),
+                        self.current_position(),
+                    ))
+                }
+                ch => {
+                    text.push(ch);
+                    self.advance();
+                }
+            }
+        }
+
+        self.advance(); // consume closing "
+        if !text.is_empty() || parts.is_empty() {
+            parts.push(InterpolationPart::Text(text));
+        }
+        Ok(Token::InterpolatedString(parts))
+    }
+
+    /// Lex one `{expr}` or `{expr:%spec}` hole, starting at its `{`.
+    fn lex_interpolation_hole(&mut self) -> Result<InterpolationPart, LexError> {
+        let hole_pos = self.current_position();
+        self.advance(); // consume {
+
+        let mut tokens = Vec::new();
+        let mut depth = 0usize;
+        let mut format = None;
+
+        loop {
+            self.skip_whitespace_and_comments()?;
+            if self.is_at_end() {
+                return Err(LexError::InvalidInterpolation(
+                    "unclosed '{'".to_string(),
+                    hole_pos,
+                ));
+            }
+            let ch = self.current_char();
+            if depth == 0 && ch == '}' {
+                self.advance();
+                break;
+            }
+            if depth == 0 && ch == ':' && !self.is_at_end_or(1) && self.peek_char() == '%' {
+                self.advance(); // consume :
+                let mut spec = String::new();
+                while !self.is_at_end() && !matches!(self.current_char(), '}' | '"') {
+                    spec.push(self.current_char());
+                    self.advance();
+                }
+                if self.is_at_end() || self.current_char() != '}' {
+                    return Err(LexError::InvalidInterpolation(
+                        "unclosed '{'".to_string(),
+                        hole_pos,
+                    ));
+                }
+                self.advance(); // consume }
+                format = Some(spec);
+                break;
+            }
+
+            let pos = self.current_position();
+            let token = self.next_token()?;
+            match token {
+                Token::LBrace => depth += 1,
+                Token::RBrace => depth -= 1,
+                _ => {}
+            }
+            tokens.push(TokenWithPos::new(token, pos));
+        }
+
+        if tokens.is_empty() {
+            return Err(LexError::InvalidInterpolation(
+                "empty expression in '{}'".to_string(),
+                hole_pos,
+            ));
+        }
+        tokens.push(TokenWithPos::new(Token::Eof, self.current_position()));
+        Ok(InterpolationPart::Hole { tokens, format })
+    }
+
     /// Lex + or ++.
     fn lex_plus_or_plusplus(&mut self) -> Result<Token, LexError> {
         self.advance();
@@ -646,6 +1017,19 @@ impl Lexer {
                     self.advance();
                     Ok(Token::Lte)
                 }
+                '<' => {
+                    self.advance();
+                    if !self.is_at_end() && self.current_char() == '<' {
+                        self.advance();
+                        Ok(Token::LtLtLt)
+                    } else {
+                        Ok(Token::LtLt)
+                    }
+                }
+                '|' => {
+                    self.advance();
+                    Ok(Token::PipeLeft)
+                }
                 '>' => {
                     self.advance();
                     Ok(Token::Neq)
@@ -661,36 +1045,71 @@ impl Lexer {
         }
     }
 
-    /// Lex > or >=.
+    /// Lex >, >=, >> or >>>.
+    ///
+    /// `>>` also closes two nested type argument lists (`Option<List<int>>`);
+    /// the parser splits it back up in that position.
     fn lex_gt_or_gte(&mut self) -> Result<Token, LexError> {
         self.advance();
         if !self.is_at_end() && self.current_char() == '=' {
             self.advance();
             Ok(Token::Gte)
+        } else if !self.is_at_end() && self.current_char() == '>' {
+            self.advance();
+            if !self.is_at_end() && self.current_char() == '>' {
+                self.advance();
+                Ok(Token::GtGtGt)
+            } else {
+                Ok(Token::GtGt)
+            }
         } else {
             Ok(Token::Gt)
         }
     }
 
-    /// Lex &&.
+    /// Lex && or &&&.
     fn lex_and(&mut self) -> Result<Token, LexError> {
         let pos = self.current_position();
         self.advance();
         if !self.is_at_end() && self.current_char() == '&' {
             self.advance();
-            Ok(Token::And)
+            if !self.is_at_end() && self.current_char() == '&' {
+                self.advance();
+                Ok(Token::AmpAmpAmp)
+            } else {
+                Ok(Token::And)
+            }
         } else {
             Err(LexError::UnexpectedChar('&', pos))
         }
     }
 
-    /// Lex : or ::.
+    /// Lex ^ or ^^^.
+    fn lex_caret(&mut self) -> Result<Token, LexError> {
+        let pos = self.current_position();
+        self.advance();
+        if self.current_char() != '^' {
+            return Ok(Token::Caret);
+        }
+        for _ in 0..2 {
+            if self.is_at_end() || self.current_char() != '^' {
+                return Err(LexError::UnexpectedChar('^', pos));
+            }
+            self.advance();
+        }
+        Ok(Token::CaretCaretCaret)
+    }
+
+    /// Lex :, :: or :=.
     fn lex_colon_or_coloncolon(&mut self) -> Result<Token, LexError> {
         let _pos = self.current_position();
         self.advance();
         if !self.is_at_end() && self.current_char() == ':' {
             self.advance();
             Ok(Token::ColonColon)
+        } else if !self.is_at_end() && self.current_char() == '=' {
+            self.advance();
+            Ok(Token::ColonEquals)
         } else {
             Ok(Token::Colon)
         }
@@ -919,6 +1338,35 @@ mod tests {
         assert_eq!(tokens[0].token, Token::PipeRBrace);
     }
 
+    #[test]
+    fn test_lex_bitwise_and_composition_operators() {
+        let mut lexer = Lexer::new("a % b &&& c ||| d ^^^ e <<< 1 >>> 2");
+        let tokens = lexer.tokenize().unwrap();
+        let tokens: Vec<&Token> = tokens.iter().map(|t| &t.token).collect();
+        assert_eq!(tokens[1], &Token::Percent);
+        assert_eq!(tokens[3], &Token::AmpAmpAmp);
+        assert_eq!(tokens[5], &Token::PipePipePipe);
+        assert_eq!(tokens[7], &Token::CaretCaretCaret);
+        assert_eq!(tokens[9], &Token::LtLtLt);
+        assert_eq!(tokens[11], &Token::GtGtGt);
+
+        let mut lexer = Lexer::new("m/s^2 ^^^");
+        let tokens = lexer.tokenize().unwrap();
+        let tokens: Vec<&Token> = tokens.iter().map(|t| &t.token).collect();
+        assert_eq!(tokens[3], &Token::Caret);
+        assert_eq!(tokens[5], &Token::CaretCaretCaret);
+        assert!(Lexer::new("a ^^ b").tokenize().is_err());
+
+        let mut lexer = Lexer::new("f >> g << h <| x || y && z");
+        let tokens = lexer.tokenize().unwrap();
+        let tokens: Vec<&Token> = tokens.iter().map(|t| &t.token).collect();
+        assert_eq!(tokens[1], &Token::GtGt);
+        assert_eq!(tokens[3], &Token::LtLt);
+        assert_eq!(tokens[5], &Token::PipeLeft);
+        assert_eq!(tokens[7], &Token::Or);
+        assert_eq!(tokens[9], &Token::And);
+    }
+
     // Multi-line comment tests
     #[test]
     fn test_simple_multiline_comment() {
@@ -1097,6 +1545,156 @@ mod tests {
         assert_eq!(tokens[0].token, Token::Async);
     }
 
+    #[test]
+    fn test_lex_when_keyword() {
+        let mut lexer = Lexer::new("| x when x > 0 ->");
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[2].token, Token::When);
+        assert_eq!(format!("{}", Token::When), "when");
+    }
+
+    #[test]
+    fn test_lex_try_keywords() {
+        let mut lexer = Lexer::new("try f x with | _ -> 0");
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[0].token, Token::Try);
+        assert_eq!(tokens[3].token, Token::With);
+
+        let mut lexer = Lexer::new("try a finally b");
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[2].token, Token::Finally);
+        assert_eq!(format!("{}", Token::Finally), "finally");
+    }
+
+    #[test]
+    fn test_lex_char_and_byte_string_literals() {
+        let mut lexer = Lexer::new(r#"'a' '\n' '\'' 'x "GET"B 'key"#);
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[0].token, Token::Char('a'));
+        assert_eq!(tokens[1].token, Token::Char('\n'));
+        assert_eq!(tokens[2].token, Token::Char('\''));
+        // A quote without a closing quote is still a type variable
+        assert_eq!(tokens[3].token, Token::TypeVar("x".to_string()));
+        assert_eq!(tokens[4].token, Token::Bytes(b"GET".to_vec()));
+        assert_eq!(tokens[5].token, Token::TypeVar("key".to_string()));
+
+        assert!(matches!(
+            Lexer::new(r#""λ"B"#).tokenize(),
+            Err(LexError::InvalidByteChar('λ', _))
+        ));
+    }
+
+    #[test]
+    fn test_lex_mutable_and_ref_cell_operators() {
+        let mut lexer = Lexer::new("let mutable x = 0 in x <- 1");
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[1].token, Token::Mutable);
+        assert_eq!(tokens[7].token, Token::LArrow);
+
+        let mut lexer = Lexer::new("r := !r");
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[1].token, Token::ColonEquals);
+        assert_eq!(tokens[2].token, Token::Bang);
+        assert_eq!(tokens[3].token, Token::Ident("r".to_string()));
+    }
+
+    #[test]
+    fn test_lex_interpolated_string() {
+        let mut lexer = Lexer::new(r#"$"Hi {name}, {n + 1:%d} {{ok}}\n""#);
+        let tokens = lexer.tokenize().unwrap();
+        let Token::InterpolatedString(parts) = &tokens[0].token else {
+            panic!("Expected interpolated string, got {:?}", tokens[0].token);
+        };
+        assert_eq!(parts.len(), 5);
+        assert_eq!(parts[0], InterpolationPart::Text("Hi ".to_string()));
+        assert_eq!(parts[4], InterpolationPart::Text(" {ok}\n".to_string()));
+        let InterpolationPart::Hole {
+            tokens: hole,
+            format,
+        } = &parts[3]
+        else {
+            panic!("Expected hole, got {:?}", parts[3]);
+        };
+        assert_eq!(hole[1].token, Token::Plus);
+        assert_eq!(hole[1].pos.column, 17);
+        assert_eq!(hole[3].token, Token::Eof);
+        assert_eq!(format.as_deref(), Some("%d"));
+        assert_eq!(tokens[1].token, Token::Eof);
+    }
+
+    #[test]
+    fn test_lex_interpolated_string_errors() {
+        for source in [r#"$"{}""#, r#"$"a } b""#, r#"$"{x""#] {
+            let err = Lexer::new(source).tokenize().unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    LexError::InvalidInterpolation(..) | LexError::UnterminatedString(_)
+                ),
+                "{}: {:?}",
+                source,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_lex_member_keywords() {
+        let mut lexer = Lexer::new("with static member (+)");
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[0].token, Token::With);
+        assert_eq!(tokens[1].token, Token::Static);
+        assert_eq!(tokens[2].token, Token::Member);
+        assert_eq!(tokens[4].token, Token::Plus);
+    }
+
+    #[test]
+    fn test_lex_access_modifiers() {
+        let mut lexer = Lexer::new("let private x let internal y");
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[1].token, Token::Private);
+        assert_eq!(tokens[4].token, Token::Internal);
+    }
+
+    #[test]
+    fn test_lex_for_loop_keywords() {
+        let mut lexer = Lexer::new("for i = 10 downto 1 to");
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[0].token, Token::For);
+        assert_eq!(tokens[4].token, Token::Downto);
+        assert_eq!(tokens[6].token, Token::To);
+
+        let mut lexer = Lexer::new("1..5 xs.Length");
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[0].token, Token::Int(1));
+        assert_eq!(tokens[1].token, Token::DotDot);
+        assert_eq!(tokens[2].token, Token::Int(5));
+        assert_eq!(tokens[4].token, Token::Dot);
+    }
+
+    #[test]
+    fn test_lex_type_variables() {
+        let mut lexer = Lexer::new("Tree<'a> = 'key list");
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[0].token, Token::Ident("Tree".to_string()));
+        assert_eq!(tokens[1].token, Token::Lt);
+        assert_eq!(tokens[2].token, Token::TypeVar("a".to_string()));
+        assert_eq!(tokens[3].token, Token::Gt);
+        assert_eq!(tokens[5].token, Token::TypeVar("key".to_string()));
+        assert_eq!(format!("{}", tokens[5].token), "'key");
+
+        let mut lexer = Lexer::new("' x");
+        assert!(lexer.tokenize().is_err());
+    }
+
+    #[test]
+    fn test_lex_as_keyword() {
+        let mut lexer = Lexer::new("| h :: _ as xs ->");
+        let tokens = lexer.tokenize().unwrap();
+        assert_eq!(tokens[4].token, Token::As);
+        assert_eq!(format!("{}", Token::As), "as");
+    }
+
     #[test]
     fn test_lex_return_keyword() {
         let mut lexer = Lexer::new("return");
@@ -10,6 +10,8 @@
 //! - `lexer`: Lexer/Tokenizer for Mini-F# source code
 //! - `parser`: Recursive-descent parser for Mini-F# expressions
 //! - `compiler`: Bytecode compiler (AST → Bytecode)
+//! - `exhaustiveness`: Match exhaustiveness and unreachable-arm analysis
+//! - `format`: printf-style format strings used by `sprintf` and interpolation
 //! - `types`: Type system infrastructure for Hindley-Milner type inference
 //! - `inference`: Type inference engine (Hindley-Milner algorithm)
 //! - `typed_ast`: Optional typed AST with type annotations
@@ -56,6 +58,8 @@
 pub mod ast;
 pub mod compiler;
 pub mod error;
+pub mod exhaustiveness;
+pub mod format;
 pub mod inference;
 pub mod lexer;
 pub mod loader;
@@ -68,7 +72,7 @@ pub mod types;
 
 // Re-export commonly used types for convenience
 pub use ast::{BinOp, Expr, Literal, LoadDirective, ModuleDef, ModuleItem, Pattern, Program};
-pub use compiler::{CompileError, CompileOptions, Compiler};
+pub use compiler::{CompileError, CompileOptions, CompileWarning, Compiler};
 pub use error::{TypeError, TypeErrorKind};
 pub use inference::TypeInference;
 pub use lexer::{LexError, Lexer, Position, Token, TokenWithPos};
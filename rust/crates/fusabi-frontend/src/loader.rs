@@ -151,6 +151,39 @@ impl FileLoader {
         Ok(self.cache.get(&resolved).unwrap())
     }
 
+    /// Load every file reachable from the `#load` directives of `program`
+    ///
+    /// Each file appears once, after the files it loads itself, so the
+    /// programs can be registered in the returned order.
+    pub fn load_directives(
+        &mut self,
+        program: &Program,
+        from_file: &Path,
+    ) -> Result<Vec<LoadedFile>, LoadError> {
+        let mut seen = HashSet::new();
+        let mut files = Vec::new();
+        self.collect_directives(program, from_file, &mut seen, &mut files)?;
+        Ok(files)
+    }
+
+    /// Append the files loaded by `program` to `files` in dependency order
+    fn collect_directives(
+        &mut self,
+        program: &Program,
+        from_file: &Path,
+        seen: &mut HashSet<PathBuf>,
+        files: &mut Vec<LoadedFile>,
+    ) -> Result<(), LoadError> {
+        for directive in &program.directives {
+            let loaded = self.load(&directive.path, from_file)?.clone();
+            if seen.insert(loaded.path.clone()) {
+                self.collect_directives(&loaded.program, &loaded.path, seen, files)?;
+                files.push(loaded);
+            }
+        }
+        Ok(())
+    }
+
     /// Resolve a path relative to a source file
     fn resolve_path(&self, path: &str, from_file: &Path) -> Result<PathBuf, LoadError> {
         let resolved = if path.starts_with('/') {
@@ -254,6 +287,34 @@ mod tests {
         assert_eq!(loader.cache.len(), 2); // both main and utils
     }
 
+    #[test]
+    fn test_load_directives_lists_dependencies_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let main_file = temp_dir.path().join("main.fsx");
+        fs::write(temp_dir.path().join("utils.fsx"), "let x = 1").unwrap();
+        fs::write(
+            temp_dir.path().join("math.fsx"),
+            "#load \"utils.fsx\"\nlet y = 2",
+        )
+        .unwrap();
+
+        let main = Parser::new(
+            Lexer::new("#load \"math.fsx\"\n#load \"utils.fsx\"\n0")
+                .tokenize()
+                .unwrap(),
+        )
+        .parse_program()
+        .unwrap();
+        let mut loader = FileLoader::new(temp_dir.path().to_path_buf());
+        let files = loader.load_directives(&main, &main_file).unwrap();
+
+        let names: Vec<_> = files
+            .iter()
+            .map(|f| f.path.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["utils.fsx", "math.fsx"]);
+    }
+
     #[test]
     fn test_file_not_found() {
         let temp_dir = TempDir::new().unwrap();
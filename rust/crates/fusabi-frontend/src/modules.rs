@@ -10,6 +10,7 @@
 //! - Qualified name resolution (e.g., Math.add)
 //! - Name conflict detection
 //! - Type environment tracking per module
+//! - `private` and `internal` bindings hidden from code outside the module
 //!
 //! # Example
 //!
@@ -24,7 +25,7 @@
 //! let result2 = Math.multiply 3 4  // Qualified access
 //! ```
 
-use crate::ast::{DuTypeDef, Expr, RecordTypeDef, TypeProviderDecl};
+use crate::ast::{Access, DuTypeDef, Expr, MeasureDef, RecordTypeDef, TypeProviderDecl};
 use crate::types::TypeEnv;
 use std::collections::HashMap;
 
@@ -49,6 +50,27 @@ pub struct Module {
     pub types: HashMap<String, TypeDefinition>,
     /// Type environment for this module
     pub type_env: TypeEnv,
+    /// Accessibility of bindings declared `private` or `internal`
+    pub access: HashMap<String, Access>,
+    /// Whether the module comes from a `#load`ed file, which hides its
+    /// `internal` bindings
+    pub loaded: bool,
+}
+
+impl Module {
+    /// Accessibility of a binding (public unless declared otherwise)
+    pub fn access_of(&self, name: &str) -> Access {
+        self.access.get(name).copied().unwrap_or_default()
+    }
+
+    /// Check whether code outside the module may use a binding
+    pub fn is_exported(&self, name: &str) -> bool {
+        match self.access_of(name) {
+            Access::Public => true,
+            Access::Internal => !self.loaded,
+            Access::Private => false,
+        }
+    }
 }
 
 /// Type definition exported by a module
@@ -60,6 +82,8 @@ pub enum TypeDefinition {
     Du(DuTypeDef),
     /// Type provider declaration (resolved at compile time)
     Provider(TypeProviderDecl),
+    /// Unit of measure declaration
+    Measure(MeasureDef),
 }
 
 impl ModuleRegistry {
@@ -114,6 +138,8 @@ impl ModuleRegistry {
             make_global_ref("String.startsWith"),
         );
         string_bindings.insert("endsWith".to_string(), make_global_ref("String.endsWith"));
+        string_bindings.insert("toChars".to_string(), make_global_ref("String.toChars"));
+        string_bindings.insert("ofChars".to_string(), make_global_ref("String.ofChars"));
         self.register_module("String".to_string(), string_bindings, HashMap::new());
 
         // Map module
@@ -198,28 +224,61 @@ impl ModuleRegistry {
         name: String,
         bindings: HashMap<String, Expr>,
         types: HashMap<String, TypeDefinition>,
+    ) {
+        self.register_module_with_access(name, bindings, types, HashMap::new());
+    }
+
+    /// Register a module whose bindings may be `private` or `internal`
+    ///
+    /// Bindings missing from `access` are public.
+    pub fn register_module_with_access(
+        &mut self,
+        name: String,
+        bindings: HashMap<String, Expr>,
+        types: HashMap<String, TypeDefinition>,
+        access: HashMap<String, Access>,
     ) {
         let module = Module {
             name: name.clone(),
             bindings,
             types,
             type_env: TypeEnv::new(),
+            access,
+            loaded: false,
         };
         self.modules.insert(name, module);
     }
 
+    /// Mark a module as coming from a `#load`ed file
+    ///
+    /// Its `internal` bindings are then hidden from the loading file.
+    pub fn mark_loaded(&mut self, name: &str) {
+        if let Some(module) = self.modules.get_mut(name) {
+            module.loaded = true;
+        }
+    }
+
     /// Resolve a qualified name (e.g., "Math.add")
     ///
     /// # Returns
     ///
-    /// The expression bound to the qualified name, or None if not found.
+    /// The expression bound to the qualified name, or None if not found or
+    /// not visible outside the module.
     pub fn resolve_qualified(&self, module_name: &str, binding_name: &str) -> Option<&Expr> {
         self.modules
             .get(module_name)
+            .filter(|m| m.is_exported(binding_name))
             .and_then(|m| m.bindings.get(binding_name))
     }
 
-    /// Get all bindings from a module (for "open" imports)
+    /// Resolve a binding from inside its own module, ignoring accessibility
+    pub fn resolve_in_module(&self, module_name: &str, binding_name: &str) -> Option<&Expr> {
+        self.modules
+            .get(module_name)
+            .and_then(|m| m.bindings.get(binding_name))
+    }
+
+    /// Get all bindings from a module, including hidden ones
     ///
     /// # Returns
     ///
@@ -228,6 +287,21 @@ impl ModuleRegistry {
         self.modules.get(module_name).map(|m| &m.bindings)
     }
 
+    /// Get the bindings an "open" import brings into scope
+    ///
+    /// # Returns
+    ///
+    /// The bindings visible outside the module, or None if module not found.
+    pub fn get_exported_bindings(&self, module_name: &str) -> Option<HashMap<&str, &Expr>> {
+        self.modules.get(module_name).map(|m| {
+            m.bindings
+                .iter()
+                .filter(|(name, _)| m.is_exported(name))
+                .map(|(name, expr)| (name.as_str(), expr))
+                .collect()
+        })
+    }
+
     /// Get all type definitions from a module
     ///
     /// # Returns
@@ -333,8 +407,12 @@ mod tests {
             "add".to_string(),
             Expr::Lambda {
                 param: "x".to_string(),
+                param_ty: None,
+                return_ty: None,
                 body: Box::new(Expr::Lambda {
                     param: "y".to_string(),
+                    param_ty: None,
+                    return_ty: None,
                     body: Box::new(Expr::BinOp {
                         op: crate::ast::BinOp::Add,
                         left: Box::new(Expr::Var("x".to_string())),
@@ -398,10 +476,12 @@ mod tests {
             "Person".to_string(),
             TypeDefinition::Record(RecordTypeDef {
                 name: "Person".to_string(),
+                type_params: vec![],
                 fields: vec![
                     ("name".to_string(), TypeExpr::Named("string".to_string())),
                     ("age".to_string(), TypeExpr::Named("int".to_string())),
                 ],
+                members: vec![],
             }),
         );
 
@@ -412,4 +492,34 @@ mod tests {
         assert_eq!(module_types.unwrap().len(), 1);
         assert!(module_types.unwrap().contains_key("Person"));
     }
+
+    #[test]
+    fn test_hidden_bindings() {
+        let mut registry = ModuleRegistry::new();
+
+        let mut bindings = HashMap::new();
+        bindings.insert("api".to_string(), Expr::Lit(Literal::Int(1)));
+        bindings.insert("helper".to_string(), Expr::Lit(Literal::Int(2)));
+        bindings.insert("shared".to_string(), Expr::Lit(Literal::Int(3)));
+        let mut access = HashMap::new();
+        access.insert("helper".to_string(), Access::Private);
+        access.insert("shared".to_string(), Access::Internal);
+
+        registry.register_module_with_access("Lib".to_string(), bindings, HashMap::new(), access);
+
+        // Private bindings are only reachable from inside the module
+        assert!(registry.resolve_qualified("Lib", "api").is_some());
+        assert!(registry.resolve_qualified("Lib", "helper").is_none());
+        assert!(registry.resolve_in_module("Lib", "helper").is_some());
+        assert!(registry.resolve_qualified("Lib", "shared").is_some());
+
+        let exported = registry.get_exported_bindings("Lib").unwrap();
+        assert_eq!(exported.len(), 2);
+        assert!(!exported.contains_key("helper"));
+
+        // Internal bindings disappear once the module comes from a loaded file
+        registry.mark_loaded("Lib");
+        assert!(registry.resolve_qualified("Lib", "shared").is_none());
+        assert_eq!(registry.get_exported_bindings("Lib").unwrap().len(), 1);
+    }
 }
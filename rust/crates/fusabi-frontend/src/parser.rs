@@ -4,6 +4,7 @@
 //! from the lexer into an Abstract Syntax Tree (AST). The parser supports:
 //!
 //! - Literals: integers, floats, booleans, strings, unit
+//! - Interpolated strings: `$"Hello {name}, {count:%d} items"` (desugared to `sprintf`)
 //! - Variables and identifiers
 //! - Let-bindings: `let x = expr in body`
 //! - Multi-parameter functions (curried): `let f x y = expr in body`
@@ -24,8 +25,9 @@
 //!
 //! module     ::= declaration*
 //! declaration::= type_def | let_binding
-//! type_def   ::= "type" IDENT "=" "{" (IDENT ":" type_expr (";" IDENT ":" type_expr)* ";"?)? "}"
-//!              | "type" IDENT "=" variant ("|" variant)*
+//! type_def   ::= "type" IDENT "=" "{" (IDENT ":" type_expr (";" IDENT ":" type_expr)* ";"?)? "}" members?
+//!              | "type" IDENT "=" variant ("|" variant)* members?
+//! members    ::= "with" (("static" "member" | "member" IDENT ".") (IDENT | "(" operator ")") params "=" expr)+
 //! variant    ::= IDENT ("of" type_expr ("*" type_expr)*)?
 //! type_expr  ::= simple_type ("->" type_expr)? | simple_type ("*" simple_type)*
 //! simple_type::= IDENT
@@ -75,10 +77,14 @@
 //! assert!(ast.is_let());
 //! ```
 use crate::ast::{
-    BinOp, CEStatement, DuTypeDef, Expr, Import, Literal, LoadDirective, MatchArm, ModuleDef,
-    ModuleItem, Pattern, Program, TypeDefinition, TypeExpr, TypeProviderDecl, VariantDef,
+    active_pattern_cases, Access, BinOp, CEStatement, DuTypeDef, Expr, Import, Literal,
+    LoadDirective, MatchArm, MeasureDef, MemberDef, ModuleDef, ModuleItem, Pattern, Program,
+    RecordTypeDef, TypeAnnotation, TypeDefinition, TypeExpr, TypeProviderDecl, VariantDef,
 };
-use crate::lexer::{Position, Token, TokenWithPos};
+use crate::format::parse_format;
+use crate::lexer::{InterpolationPart, Position, Token, TokenWithPos};
+use crate::span::Span;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 /// Parse errors with position information.
@@ -130,7 +136,7 @@ type Result<T> = std::result::Result<T, ParseError>;
 
 /// Result of parsing a `let` construct, which could be a ModuleItem or an Expr
 enum LetResult {
-    Item(ModuleItem),
+    Item(ModuleItem, Access),
     Expr(Expr),
 }
 
@@ -138,12 +144,27 @@ enum LetResult {
 pub struct Parser {
     tokens: Vec<TokenWithPos>,
     pos: usize,
+    /// Whether a `yield` was parsed since the enclosing `[` or `[|`, which
+    /// makes the brackets a comprehension
+    saw_yield: bool,
+    /// Cases of the active patterns defined so far, mapped to the name of
+    /// the active pattern function (`Even` -> `|Even|Odd|`)
+    active_patterns: HashMap<String, String>,
+    /// Types defined so far with static members, whose `Type.Member(args)`
+    /// is a method call rather than module access
+    types_with_statics: HashSet<String>,
 }
 
 impl Parser {
     /// Create a new parser from a token stream.
     pub fn new(tokens: Vec<TokenWithPos>) -> Self {
-        Parser { tokens, pos: 0 }
+        Parser {
+            tokens,
+            pos: 0,
+            saw_yield: false,
+            active_patterns: HashMap::new(),
+            types_with_statics: HashSet::new(),
+        }
     }
 
     /// Parse the token stream into an expression AST.
@@ -200,7 +221,7 @@ impl Parser {
                     // We parse the binding part first
                     let binding = self.parse_let_binding_or_expr()?;
                     match binding {
-                        LetResult::Item(item) => items.push(item),
+                        LetResult::Item(item, _) => items.push(item),
                         LetResult::Expr(expr) => {
                             main_expr = Some(expr);
                             // If we found a main expression (e.g., let ... in ...),
@@ -223,6 +244,10 @@ impl Parser {
                     let type_def = self.parse_type_def()?;
                     items.push(ModuleItem::TypeDef(type_def));
                 }
+                Token::LBracket if self.starts_attribute() => {
+                    let measure = self.parse_measure_def()?;
+                    items.push(ModuleItem::TypeDef(measure));
+                }
                 _ => {
                     // Assume main expression
                     let expr = self.parse_expr()?;
@@ -247,14 +272,20 @@ impl Parser {
         let name = self.expect_ident()?;
         self.expect_token(Token::Eq)?;
 
-        let items = self.parse_module_items()?;
+        let (items, access) = self.parse_module_items()?;
 
-        Ok(ModuleDef { name, items })
+        Ok(ModuleDef {
+            name,
+            items,
+            access,
+        })
     }
 
-    /// Parse module items (let bindings, types, nested modules)
-    fn parse_module_items(&mut self) -> Result<Vec<ModuleItem>> {
+    /// Parse module items (let bindings, types, nested modules), along with
+    /// the bindings declared `private` or `internal`
+    fn parse_module_items(&mut self) -> Result<(Vec<ModuleItem>, HashMap<String, Access>)> {
         let mut items = vec![];
+        let mut access = HashMap::new();
 
         // Continue until we hit EOF or another module keyword
         while !self.is_at_end()
@@ -273,7 +304,22 @@ impl Parser {
                     // Let's use parse_let_binding_or_expr and ensure it returns an Item.
                     let result = self.parse_let_binding_or_expr()?;
                     match result {
-                        LetResult::Item(item) => items.push(item),
+                        LetResult::Item(item, item_access) => {
+                            if item_access != Access::Public {
+                                let names: Vec<&String> = match &item {
+                                    ModuleItem::Let(name, _) => name.iter().collect(),
+                                    ModuleItem::LetRec(bindings) => {
+                                        bindings.iter().map(|(name, _)| name).collect()
+                                    }
+                                    ModuleItem::LetMutable(name, _) => vec![name],
+                                    _ => vec![],
+                                };
+                                for name in names {
+                                    access.insert(name.clone(), item_access);
+                                }
+                            }
+                            items.push(item);
+                        }
                         LetResult::Expr(_) => {
                             return Err(ParseError::UnexpectedToken {
                                 expected: "module item".to_string(),
@@ -293,65 +339,63 @@ impl Parser {
                     let type_def = self.parse_type_def()?;
                     items.push(ModuleItem::TypeDef(type_def));
                 }
+                Token::LBracket if self.starts_attribute() => {
+                    let measure = self.parse_measure_def()?;
+                    items.push(ModuleItem::TypeDef(measure));
+                }
                 _ => break,
             }
         }
 
-        Ok(items)
+        Ok((items, access))
     }
 
     /// Helper: parse a let construct that could be a binding or an expression
+    ///
+    /// A binding may carry an accessibility modifier, `let private x = ...` or
+    /// `let rec internal f x = ...`, which only module items take.
     fn parse_let_binding_or_expr(&mut self) -> Result<LetResult> {
         self.expect_token(Token::Let)?;
 
         // Check for "rec" keyword
         let is_rec = self.match_token(&Token::Rec);
 
+        let modifier = self.current_token().clone();
+        let access = match modifier.token {
+            Token::Private => Access::Private,
+            Token::Internal => Access::Internal,
+            _ => Access::Public,
+        };
+        if access != Access::Public {
+            self.advance();
+        }
+
+        match self.parse_let_rest(is_rec)? {
+            LetResult::Item(item, _) => Ok(LetResult::Item(item, access)),
+            LetResult::Expr(_) if access != Access::Public => Err(ParseError::InvalidExpr {
+                message: format!("'{}' is only allowed on module bindings", access),
+                pos: modifier.pos,
+            }),
+            expr => Ok(expr),
+        }
+    }
+
+    /// Parse the rest of a let construct after `let`, `rec` and any modifier
+    fn parse_let_rest(&mut self, is_rec: bool) -> Result<LetResult> {
         if is_rec {
             // Recursive binding(s)
-            let first_name = self.expect_ident()?;
-            let mut params = vec![];
-            while let Token::Ident(_) = &self.current_token().token {
-                params.push(self.expect_ident()?);
-            }
-
-            self.expect_token(Token::Eq)?;
-            let mut first_value = self.parse_expr()?;
-
-            // Desugar params
-            if !params.is_empty() {
-                first_value =
-                    params
-                        .into_iter()
-                        .rev()
-                        .fold(first_value, |acc, param| Expr::Lambda {
-                            param,
-                            body: Box::new(acc),
-                        });
-            }
+            let first_name = self.expect_binding_name()?;
+            let (ty, first_value) = self.parse_binding_rhs()?;
+            let first_value = Self::annotate_value(&first_name, ty, first_value);
 
             // Check for mutual recursion ('and')
             if self.match_token(&Token::AndKeyword) {
                 let mut bindings = vec![(first_name, first_value)];
 
                 loop {
-                    let name = self.expect_ident()?;
-                    let mut params = vec![];
-                    while let Token::Ident(_) = &self.current_token().token {
-                        params.push(self.expect_ident()?);
-                    }
-                    self.expect_token(Token::Eq)?;
-                    let mut value = self.parse_expr()?;
-
-                    if !params.is_empty() {
-                        value = params
-                            .into_iter()
-                            .rev()
-                            .fold(value, |acc, param| Expr::Lambda {
-                                param,
-                                body: Box::new(acc),
-                            });
-                    }
+                    let name = self.expect_binding_name()?;
+                    let (ty, value) = self.parse_binding_rhs()?;
+                    let value = Self::annotate_value(&name, ty, value);
                     bindings.push((name, value));
 
                     if !self.match_token(&Token::AndKeyword) {
@@ -370,7 +414,10 @@ impl Parser {
                     // It's a LetRec item.
                     // Convert (name, val) pairs.
                     // ModuleItem::LetRec takes a Vec.
-                    Ok(LetResult::Item(ModuleItem::LetRec(bindings)))
+                    Ok(LetResult::Item(
+                        ModuleItem::LetRec(bindings),
+                        Access::Public,
+                    ))
                 }
             } else {
                 // Single recursive binding
@@ -384,12 +431,30 @@ impl Parser {
                         body: Box::new(body),
                     }))
                 } else {
-                    Ok(LetResult::Item(ModuleItem::LetRec(vec![(
-                        first_name,
-                        first_value,
-                    )])))
+                    Ok(LetResult::Item(
+                        ModuleItem::LetRec(vec![(first_name, first_value)]),
+                        Access::Public,
+                    ))
                 }
             }
+        } else if self.match_token(&Token::Mutable) {
+            let name = self.expect_ident()?;
+            self.expect_token(Token::Eq)?;
+            let value = self.parse_expr()?;
+
+            if self.match_token(&Token::In) {
+                let body = self.parse_expr()?;
+                Ok(LetResult::Expr(Expr::LetMutable {
+                    name,
+                    value: Box::new(value),
+                    body: Box::new(body),
+                }))
+            } else {
+                Ok(LetResult::Item(
+                    ModuleItem::LetMutable(name, value),
+                    Access::Public,
+                ))
+            }
         } else {
             // Simple Let
             // Check for discard pattern '_'
@@ -402,26 +467,10 @@ impl Parser {
                     self.advance();
                     None
                 }
-                _ => Some(self.expect_ident()?),
+                _ => Some(self.expect_binding_name()?),
             };
 
-            let mut params = vec![];
-            while let Token::Ident(_) = &self.current_token().token {
-                params.push(self.expect_ident()?);
-            }
-
-            self.expect_token(Token::Eq)?;
-            let mut value = self.parse_expr()?;
-
-            if !params.is_empty() {
-                value = params
-                    .into_iter()
-                    .rev()
-                    .fold(value, |acc, param| Expr::Lambda {
-                        param,
-                        body: Box::new(acc),
-                    });
-            }
+            let (ty, value) = self.parse_binding_rhs()?;
 
             if self.match_token(&Token::In) {
                 let body = self.parse_expr()?;
@@ -435,11 +484,16 @@ impl Parser {
                 }
                 Ok(LetResult::Expr(Expr::Let {
                     name: name.unwrap(),
+                    ty,
                     value: Box::new(value),
                     body: Box::new(body),
                 }))
             } else {
-                Ok(LetResult::Item(ModuleItem::Let(name, value)))
+                let value = Self::annotate_value(name.as_deref().unwrap_or("_"), ty, value);
+                Ok(LetResult::Item(
+                    ModuleItem::Let(name, value),
+                    Access::Public,
+                ))
             }
         }
     }
@@ -483,12 +537,32 @@ impl Parser {
     /// Syntax:
     /// - DU: `type Option = Some of int | None`
     /// - Record: `type Person = { name: string; age: int }`
+    /// - Generic: `type Tree<'a> = Leaf | Node of Tree<'a> * 'a * Tree<'a>`
     /// - Provider: `type DbSchema = SqlProvider<"schema.sql">`
     fn parse_type_def(&mut self) -> Result<TypeDefinition> {
         self.expect_token(Token::Type)?;
+        let name_pos = self.current_token().pos;
         let type_name = self.expect_ident()?;
+        let type_params = self.parse_type_params()?;
         self.expect_token(Token::Eq)?;
 
+        if self.check(&Token::LBrace) {
+            let fields = self.parse_record_type_fields()?;
+            Self::check_type_vars(
+                &type_name,
+                &type_params,
+                fields.iter().map(|(_, ty)| ty),
+                name_pos,
+            )?;
+            let members = self.parse_type_members(&type_name, &type_params)?;
+            return Ok(TypeDefinition::Record(RecordTypeDef {
+                name: type_name,
+                type_params,
+                fields,
+                members,
+            }));
+        }
+
         // Check if this is a type provider: ProviderName<"source">
         // Look ahead to see if we have Ident followed by Lt
         if let Token::Ident(provider_name) = self.current_token().token.clone() {
@@ -572,26 +646,427 @@ impl Parser {
             }
         }
 
+        Self::check_type_vars(
+            &type_name,
+            &type_params,
+            variants.iter().flat_map(|v| &v.fields),
+            name_pos,
+        )?;
+        // The union's cases shadow active pattern cases of the same name
+        for variant in &variants {
+            self.active_patterns.remove(&variant.name);
+        }
+        let members = self.parse_type_members(&type_name, &type_params)?;
         Ok(TypeDefinition::Du(DuTypeDef {
             name: type_name,
+            type_params,
             variants,
+            members,
         }))
     }
 
+    /// Parse the optional `with` block of a type definition
+    ///
+    /// Syntax: `with static member (+) (a: Vector2, b: Vector2) = ...`, with
+    /// further member declarations following the first. Operators are named
+    /// by their symbol in parentheses. An instance member names its receiver,
+    /// `member this.Area() = ...`, which becomes its first parameter, typed as
+    /// the declaring type.
+    fn parse_type_members(
+        &mut self,
+        type_name: &str,
+        type_params: &[String],
+    ) -> Result<Vec<MemberDef>> {
+        let mut members = vec![];
+        if !self.check(&Token::With) {
+            return Ok(members);
+        }
+        self.advance();
+
+        loop {
+            let is_static = self.match_token(&Token::Static);
+            self.expect_token(Token::Member)?;
+            let receiver = if is_static {
+                None
+            } else {
+                Some(self.parse_member_receiver(type_name, type_params)?)
+            };
+            let name = self.parse_member_name()?;
+            let mut params = self.parse_member_params()?;
+            let ty = if self.match_token(&Token::Colon) {
+                Some(self.parse_type_annotation()?)
+            } else {
+                None
+            };
+            self.expect_token(Token::Eq)?;
+            let body = self.parse_expr()?;
+
+            let self_name = receiver.as_ref().map(|(name, _)| name.clone());
+            params.splice(0..0, receiver);
+            let value = if params.is_empty() {
+                Self::annotate_value(&name, ty, body)
+            } else {
+                Self::wrap_in_lambdas(params, ty, body)
+            };
+            if self_name.is_none() {
+                self.types_with_statics.insert(type_name.to_string());
+            }
+            members.push(MemberDef {
+                name,
+                self_name,
+                value,
+            });
+
+            if !self.check(&Token::Static) && !self.check(&Token::Member) {
+                return Ok(members);
+            }
+        }
+    }
+
+    /// Parse `this.` before the name of an instance member, giving the
+    /// receiver parameter annotated with the declaring type
+    fn parse_member_receiver(
+        &mut self,
+        type_name: &str,
+        type_params: &[String],
+    ) -> Result<(String, Option<TypeAnnotation>)> {
+        let pos = self.current_token().pos;
+        let self_name = if self.match_token(&Token::Underscore) {
+            "_".to_string()
+        } else {
+            self.expect_ident()?
+        };
+        self.expect_token(Token::Dot)?;
+
+        let ty = if type_params.is_empty() {
+            TypeExpr::Named(type_name.to_string())
+        } else {
+            TypeExpr::App(
+                type_name.to_string(),
+                type_params.iter().cloned().map(TypeExpr::Var).collect(),
+            )
+        };
+        Ok((self_name, Some(TypeAnnotation::new(ty, Span::point(pos)))))
+    }
+
+    /// Parse the name of a member: `Compare`, or an operator such as `(+)`
+    fn parse_member_name(&mut self) -> Result<String> {
+        if !self.match_token(&Token::LParen) {
+            return self.expect_ident();
+        }
+        let tok = self.current_token().clone();
+        let op = match tok.token {
+            Token::Plus => BinOp::Add,
+            Token::Minus => BinOp::Sub,
+            Token::Star => BinOp::Mul,
+            Token::Slash => BinOp::Div,
+            Token::Percent => BinOp::Mod,
+            found => {
+                return Err(ParseError::UnexpectedToken {
+                    expected: "overloadable operator (+, -, *, / or %)".to_string(),
+                    found,
+                    pos: tok.pos,
+                })
+            }
+        };
+        self.advance();
+        self.expect_token(Token::RParen)?;
+        Ok(op.member_name().unwrap_or_default().to_string())
+    }
+
+    /// Parse the parameters of a member
+    ///
+    /// Besides the curried parameters of a `let`, a member may take its
+    /// parameters as a tuple, `(a: Vector2, b: Vector2)`, which is still
+    /// applied one argument at a time, or `()` for a single unit parameter.
+    fn parse_member_params(&mut self) -> Result<Vec<(String, Option<TypeAnnotation>)>> {
+        let start = self.pos;
+        if self.match_token(&Token::LParen) {
+            if self.match_token(&Token::RParen) {
+                let unit = TypeExpr::Named("unit".to_string());
+                let pos = self.tokens[start].pos;
+                return Ok(vec![(
+                    "_".to_string(),
+                    Some(TypeAnnotation::new(unit, Span::point(pos))),
+                )]);
+            }
+            if let Ok(params) = self.parse_tupled_params() {
+                return Ok(params);
+            }
+            self.pos = start;
+        }
+        self.parse_params()
+    }
+
+    /// Parse `a: T, b: T)` after the opening parenthesis of tupled parameters;
+    /// a single parameter, `(r)`, must be the only one
+    fn parse_tupled_params(&mut self) -> Result<Vec<(String, Option<TypeAnnotation>)>> {
+        let mut params = vec![];
+        loop {
+            let name = self.expect_ident()?;
+            let ty = if self.match_token(&Token::Colon) {
+                Some(self.parse_type_annotation()?)
+            } else {
+                None
+            };
+            params.push((name, ty));
+            if !self.match_token(&Token::Comma) {
+                break;
+            }
+        }
+        self.expect_token(Token::RParen)?;
+        let last = matches!(self.current_token().token, Token::Eq | Token::Colon);
+        if params.len() < 2 && !last {
+            let tok = self.current_token();
+            return Err(ParseError::UnexpectedToken {
+                expected: ",".to_string(),
+                found: tok.token.clone(),
+                pos: tok.pos,
+            });
+        }
+        Ok(params)
+    }
+
+    /// Whether the current `[` opens an attribute such as `[<Measure>]`
+    fn starts_attribute(&self) -> bool {
+        self.check(&Token::LBracket)
+            && self.tokens.get(self.pos + 1).map(|t| &t.token) == Some(&Token::Lt)
+    }
+
+    /// Parse a unit of measure declaration: `[<Measure>] type m` declares a
+    /// base unit and `[<Measure>] type Hz = 1/s` an abbreviation
+    fn parse_measure_def(&mut self) -> Result<TypeDefinition> {
+        self.expect_token(Token::LBracket)?;
+        self.expect_token(Token::Lt)?;
+        let attribute_pos = self.current_token().pos;
+        let attribute = self.expect_ident()?;
+        if attribute != "Measure" {
+            return Err(ParseError::InvalidExpr {
+                message: format!("unsupported attribute '{}', expected Measure", attribute),
+                pos: attribute_pos,
+            });
+        }
+        self.expect_token(Token::Gt)?;
+        self.expect_token(Token::RBracket)?;
+        self.expect_token(Token::Type)?;
+        let name = self.expect_ident()?;
+        let definition = if self.match_token(&Token::Eq) {
+            Some(self.parse_measure()?)
+        } else {
+            None
+        };
+        Ok(TypeDefinition::Measure(MeasureDef { name, definition }))
+    }
+
+    /// Parse a unit of measure: `m`, `m/s^2`, `kg m / s^2`, `'u`, `1/s`
+    ///
+    /// Units written next to each other (or joined by `*`) multiply and bind
+    /// tighter than `/`; `^` raises a unit to an integer power. The result is
+    /// a `TypeExpr::Measure` with each unit listed once.
+    fn parse_measure(&mut self) -> Result<TypeExpr> {
+        let mut factors = self.parse_measure_product()?;
+        while self.match_token(&Token::Slash) {
+            for (unit, exp) in self.parse_measure_product()? {
+                Self::push_measure_factor(&mut factors, unit, -exp);
+            }
+        }
+        Ok(TypeExpr::Measure(factors))
+    }
+
+    /// Parse units multiplied by juxtaposition on one line or by `*`
+    fn parse_measure_product(&mut self) -> Result<Vec<(TypeExpr, i32)>> {
+        let mut factors = self.parse_measure_power()?;
+        loop {
+            let tok = self.current_token();
+            let previous_line = self.tokens[self.pos - 1].pos.line;
+            let juxtaposed = tok.pos.line == previous_line
+                && matches!(
+                    tok.token,
+                    Token::Ident(_) | Token::TypeVar(_) | Token::Int(1) | Token::LParen
+                );
+            if !juxtaposed && !self.match_token(&Token::Star) {
+                return Ok(factors);
+            }
+            for (unit, exp) in self.parse_measure_power()? {
+                Self::push_measure_factor(&mut factors, unit, exp);
+            }
+        }
+    }
+
+    /// Parse a unit with an optional power: `s`, `s^2`, `s^-1`, `(m/s)^2`
+    fn parse_measure_power(&mut self) -> Result<Vec<(TypeExpr, i32)>> {
+        let tok = self.current_token().clone();
+        let mut factors = match tok.token {
+            Token::Ident(name) => {
+                self.advance();
+                vec![(TypeExpr::Named(name), 1)]
+            }
+            Token::TypeVar(name) => {
+                self.advance();
+                vec![(TypeExpr::Var(name), 1)]
+            }
+            Token::Int(1) => {
+                self.advance();
+                vec![]
+            }
+            Token::LParen => {
+                self.advance();
+                let TypeExpr::Measure(factors) = self.parse_measure()? else {
+                    unreachable!("parse_measure builds a measure");
+                };
+                self.expect_token(Token::RParen)?;
+                factors
+            }
+            found => {
+                return Err(ParseError::UnexpectedToken {
+                    expected: "unit of measure".to_string(),
+                    found,
+                    pos: tok.pos,
+                });
+            }
+        };
+
+        if self.match_token(&Token::Caret) {
+            let negative = self.match_token(&Token::Minus);
+            let tok = self.current_token().clone();
+            let power = match tok.token {
+                Token::Int(n) => i32::try_from(n).ok(),
+                _ => None,
+            };
+            let Some(power) = power else {
+                return Err(ParseError::UnexpectedToken {
+                    expected: "integer exponent".to_string(),
+                    found: tok.token,
+                    pos: tok.pos,
+                });
+            };
+            self.advance();
+            let power = if negative { -power } else { power };
+            let mut raised = vec![];
+            for (unit, exp) in factors {
+                Self::push_measure_factor(&mut raised, unit, exp * power);
+            }
+            factors = raised;
+        }
+        Ok(factors)
+    }
+
+    /// Multiply a unit into a list of factors, dropping units that cancel
+    fn push_measure_factor(factors: &mut Vec<(TypeExpr, i32)>, unit: TypeExpr, exp: i32) {
+        match factors.iter().position(|(existing, _)| *existing == unit) {
+            Some(index) => {
+                factors[index].1 += exp;
+                if factors[index].1 == 0 {
+                    factors.remove(index);
+                }
+            }
+            None if exp != 0 => factors.push((unit, exp)),
+            None => {}
+        }
+    }
+
+    /// Parse the optional type parameters of a definition: `<'a, 'b>`
+    fn parse_type_params(&mut self) -> Result<Vec<String>> {
+        let mut params = vec![];
+        if !self.match_token(&Token::Lt) {
+            return Ok(params);
+        }
+
+        loop {
+            let tok = self.current_token().clone();
+            match tok.token {
+                Token::TypeVar(name) if params.contains(&name) => {
+                    return Err(ParseError::InvalidExpr {
+                        message: format!("duplicate type parameter '{}", name),
+                        pos: tok.pos,
+                    });
+                }
+                Token::TypeVar(name) => {
+                    self.advance();
+                    params.push(name);
+                }
+                found => {
+                    return Err(ParseError::UnexpectedToken {
+                        expected: "type parameter such as 'a".to_string(),
+                        found,
+                        pos: tok.pos,
+                    });
+                }
+            }
+            if !self.match_token(&Token::Comma) {
+                break;
+            }
+        }
+
+        self.expect_token(Token::Gt)?;
+        Ok(params)
+    }
+
+    /// Parse the fields of a record type: `{ name: string; age: int }`
+    ///
+    /// Fields are separated by `;` or by starting a new line.
+    fn parse_record_type_fields(&mut self) -> Result<Vec<(String, TypeExpr)>> {
+        self.expect_token(Token::LBrace)?;
+        let mut fields = vec![];
+
+        while !self.check(&Token::RBrace) {
+            let field_name = self.expect_ident()?;
+            self.expect_token(Token::Colon)?;
+            fields.push((field_name, self.parse_tuple_type_expr()?));
+
+            let previous_line = self.tokens[self.pos - 1].pos.line;
+            if !self.match_token(&Token::Semicolon)
+                && self.current_token().pos.line == previous_line
+            {
+                break;
+            }
+        }
+
+        self.expect_token(Token::RBrace)?;
+        Ok(fields)
+    }
+
+    /// Reject type variables that the definition does not declare
+    fn check_type_vars<'a>(
+        type_name: &str,
+        type_params: &[String],
+        types: impl Iterator<Item = &'a TypeExpr>,
+        pos: Position,
+    ) -> Result<()> {
+        for ty in types {
+            if let Some(var) = ty
+                .type_vars()
+                .into_iter()
+                .find(|v| !type_params.iter().any(|p| p == v))
+            {
+                return Err(ParseError::InvalidExpr {
+                    message: format!(
+                        "type variable '{} is not declared by type {}",
+                        var, type_name
+                    ),
+                    pos,
+                });
+            }
+        }
+        Ok(())
+    }
+
     // ========================================================================
     // Expression Parsing
     // ========================================================================
 
     /// Parse an expression
     fn parse_expr(&mut self) -> Result<Expr> {
-        // Try let, if, lambda, match, while first, then fall through to parse_pipeline_expr
+        // Try let, if, lambda, match, try, while first, then fall through to parse_pipeline_expr
         let tok = &self.current_token().token;
         match tok {
             Token::Let => self.parse_let(),
             Token::If => self.parse_if(),
             Token::Fun => self.parse_lambda(),
             Token::Match => self.parse_match(),
+            Token::Try => self.parse_try(),
             Token::While => self.parse_while(),
+            Token::For => self.parse_for(),
             Token::Break => {
                 self.advance();
                 Ok(Expr::Break)
@@ -600,8 +1075,53 @@ impl Parser {
                 self.advance();
                 Ok(Expr::Continue)
             }
-            _ => self.parse_pipeline_expr(), // Start parsing from pipeline operator level
+            Token::Yield => {
+                self.advance();
+                self.saw_yield = true;
+                Ok(Expr::Yield(Box::new(self.parse_expr()?)))
+            }
+            Token::YieldBang => {
+                self.advance();
+                self.saw_yield = true;
+                Ok(Expr::YieldBang(Box::new(self.parse_expr()?)))
+            }
+            _ => self.parse_assign_expr(),
+        }
+    }
+
+    /// Parse assignment: x <- expr (mutable variable) or r := expr (ref cell)
+    fn parse_assign_expr(&mut self) -> Result<Expr> {
+        let target = self.parse_pipeline_expr()?;
+
+        if self.check(&Token::LArrow) {
+            let pos = self.current_token().pos;
+            let Expr::Var(name) = target else {
+                return Err(ParseError::InvalidExpr {
+                    message: "only variables can be assigned with <-".to_string(),
+                    pos,
+                });
+            };
+            self.advance();
+            let value = self.parse_expr()?;
+            return Ok(Expr::Assign {
+                name,
+                value: Box::new(value),
+            });
+        }
+
+        if self.match_token(&Token::ColonEquals) {
+            // r := v  =>  op_ColonEquals r v
+            let value = self.parse_expr()?;
+            return Ok(Expr::App {
+                func: Box::new(Expr::App {
+                    func: Box::new(Expr::Var("op_ColonEquals".to_string())),
+                    arg: Box::new(target),
+                }),
+                arg: Box::new(value),
+            });
         }
+
+        Ok(target)
     }
 
     /// Parse let-binding: let x = expr in body
@@ -613,35 +1133,31 @@ impl Parser {
             return self.parse_let_rec();
         }
 
-        let name = self.expect_ident()?;
-
-        // Parse optional parameter list (for multi-parameter functions)
-        // Example: let f x y = ...
-        let mut params = vec![];
-        while let Token::Ident(_) = &self.current_token().token {
-            params.push(self.expect_ident()?);
+        if self.match_token(&Token::Mutable) {
+            let name = self.expect_ident()?;
+            self.expect_token(Token::Eq)?;
+            let value = self.parse_expr()?;
+            self.expect_token(Token::In)?;
+            let body = self.parse_expr()?;
+            return Ok(Expr::LetMutable {
+                name,
+                value: Box::new(value),
+                body: Box::new(body),
+            });
         }
 
-        self.expect_token(Token::Eq)?;
-        let mut value = self.parse_expr()?;
+        let name = self.expect_binding_name()?;
 
-        // If we have params, desugar into nested lambdas
-        // let f x y = body  =>  let f = fun x -> fun y -> body
-        if !params.is_empty() {
-            value = params
-                .into_iter()
-                .rev()
-                .fold(value, |acc, param| Expr::Lambda {
-                    param,
-                    body: Box::new(acc),
-                });
-        }
+        // Parse optional parameter list (for multi-parameter functions)
+        // Example: let f x y = ...  =>  let f = fun x -> fun y -> ...
+        let (ty, value) = self.parse_binding_rhs()?;
 
         self.expect_token(Token::In)?;
         let body = self.parse_expr()?;
 
         Ok(Expr::Let {
             name,
+            ty,
             value: Box::new(value),
             body: Box::new(body),
         })
@@ -650,50 +1166,20 @@ impl Parser {
     /// Parse recursive let-binding: let rec f x = ... in body
     fn parse_let_rec(&mut self) -> Result<Expr> {
         // Expect function name
-        let first_name = self.expect_ident()?;
+        let first_name = self.expect_binding_name()?;
 
-        // Parse parameters
-        let mut params = vec![];
-        while let Token::Ident(_) = &self.current_token().token {
-            params.push(self.expect_ident()?);
-        }
-
-        self.expect_token(Token::Eq)?;
-        let mut first_value = self.parse_expr()?;
-
-        // Desugar params into nested lambdas
-        if !params.is_empty() {
-            first_value = params
-                .into_iter()
-                .rev()
-                .fold(first_value, |acc, param| Expr::Lambda {
-                    param,
-                    body: Box::new(acc),
-                });
-        }
+        // Parse parameters, desugared into nested lambdas
+        let (ty, first_value) = self.parse_binding_rhs()?;
+        let first_value = Self::annotate_value(&first_name, ty, first_value);
 
         // Check for mutual recursion ('and')
         if self.match_token(&Token::AndKeyword) {
             let mut bindings = vec![(first_name, first_value)];
 
             loop {
-                let name = self.expect_ident()?;
-                let mut params = vec![];
-                while let Token::Ident(_) = &self.current_token().token {
-                    params.push(self.expect_ident()?);
-                }
-                self.expect_token(Token::Eq)?;
-                let mut value = self.parse_expr()?;
-
-                if !params.is_empty() {
-                    value = params
-                        .into_iter()
-                        .rev()
-                        .fold(value, |acc, param| Expr::Lambda {
-                            param,
-                            body: Box::new(acc),
-                        });
-                }
+                let name = self.expect_binding_name()?;
+                let (ty, value) = self.parse_binding_rhs()?;
+                let value = Self::annotate_value(&name, ty, value);
                 bindings.push((name, value));
 
                 if !self.match_token(&Token::AndKeyword) {
@@ -730,8 +1216,12 @@ impl Parser {
         self.expect_token(Token::Then)?;
         let then_branch = self.parse_expr()?;
 
-        self.expect_token(Token::Else)?;
-        let else_branch = self.parse_expr()?;
+        // Without else, the then branch runs for its effect: if c then yield x
+        let else_branch = if self.match_token(&Token::Else) {
+            self.parse_expr()?
+        } else {
+            Expr::Lit(Literal::Unit)
+        };
 
         Ok(Expr::If {
             cond: Box::new(cond),
@@ -740,86 +1230,522 @@ impl Parser {
         })
     }
 
-    /// Parse lambda function: fun x -> body or fun x y -> body
-    fn parse_lambda(&mut self) -> Result<Expr> {
-        self.expect_token(Token::Fun)?;
+    /// Parse lambda function: fun x -> body or fun x y -> body
+    fn parse_lambda(&mut self) -> Result<Expr> {
+        self.expect_token(Token::Fun)?;
+
+        // Parse parameter list
+        let params = self.parse_params()?;
+
+        if params.is_empty() {
+            let tok = self.current_token();
+            return Err(ParseError::UnexpectedToken {
+                expected: "parameter".to_string(),
+                found: tok.token.clone(),
+                pos: tok.pos,
+            });
+        }
+
+        self.expect_token(Token::Arrow)?;
+        let body = self.parse_expr()?;
+
+        // Desugar multi-param lambda into nested lambdas
+        // fun x y -> body  =>  fun x -> fun y -> body
+        Ok(Self::wrap_in_lambdas(params, None, body))
+    }
+
+    /// Parse function parameters, each `x` or an annotated `(x: int)`
+    fn parse_params(&mut self) -> Result<Vec<(String, Option<TypeAnnotation>)>> {
+        let mut params = vec![];
+        loop {
+            match &self.current_token().token {
+                Token::Ident(_) => params.push((self.expect_ident()?, None)),
+                Token::LParen
+                    if matches!(
+                        self.tokens.get(self.pos + 2).map(|t| &t.token),
+                        Some(Token::Colon)
+                    ) =>
+                {
+                    self.advance();
+                    let name = self.expect_ident()?;
+                    self.expect_token(Token::Colon)?;
+                    let ty = self.parse_type_annotation()?;
+                    self.expect_token(Token::RParen)?;
+                    params.push((name, Some(ty)));
+                }
+                _ => return Ok(params),
+            }
+        }
+    }
+
+    /// Parse the rest of a binding after its name: parameters, an optional
+    /// `: type` annotation, `=` and the value.
+    ///
+    /// Parameters desugar into nested lambdas, and an annotation then gives the
+    /// function's result type. Without parameters the annotation is the type of
+    /// the value and is returned for the caller to attach.
+    fn parse_binding_rhs(&mut self) -> Result<(Option<TypeAnnotation>, Expr)> {
+        let params = self.parse_params()?;
+        let ty = if self.match_token(&Token::Colon) {
+            Some(self.parse_type_annotation()?)
+        } else {
+            None
+        };
+
+        self.expect_token(Token::Eq)?;
+        let value = self.parse_expr()?;
+
+        if params.is_empty() {
+            Ok((ty, value))
+        } else {
+            Ok((None, Self::wrap_in_lambdas(params, ty, value)))
+        }
+    }
+
+    /// Desugar parameters into nested lambdas; the innermost one carries the result type
+    fn wrap_in_lambdas(
+        params: Vec<(String, Option<TypeAnnotation>)>,
+        mut return_ty: Option<TypeAnnotation>,
+        body: Expr,
+    ) -> Expr {
+        params
+            .into_iter()
+            .rev()
+            .fold(body, |acc, (param, param_ty)| Expr::Lambda {
+                param,
+                param_ty,
+                return_ty: return_ty.take(),
+                body: Box::new(acc),
+            })
+    }
+
+    /// Attach a value annotation where the binding form has no place for one
+    /// (top-level items and recursive bindings): `let x: int = v` becomes
+    /// `let x: int = v in x` as the bound value.
+    fn annotate_value(name: &str, ty: Option<TypeAnnotation>, value: Expr) -> Expr {
+        match ty {
+            Some(ty) => Expr::Let {
+                name: name.to_string(),
+                ty: Some(ty),
+                value: Box::new(value),
+                body: Box::new(Expr::Var(name.to_string())),
+            },
+            None => value,
+        }
+    }
+
+    /// Parse the type of an annotation, recording the span it covers
+    fn parse_type_annotation(&mut self) -> Result<TypeAnnotation> {
+        let start = self.current_token().pos;
+        let ty = self.parse_tuple_type_expr()?;
+
+        // A type ends with a name, a type variable, `)` or `>`
+        let last = &self.tokens[self.pos - 1];
+        let (columns, bytes) = match &last.token {
+            Token::Ident(name) => (name.chars().count(), name.len()),
+            Token::TypeVar(name) => (name.chars().count() + 1, name.len() + 1),
+            _ => (1, 1),
+        };
+        let end = Position::new(
+            last.pos.line,
+            last.pos.column + columns,
+            last.pos.offset + bytes,
+        );
+
+        Ok(TypeAnnotation::new(ty, Span::new(start, end)))
+    }
+
+    /// Parse match expression: match expr with | pattern [when guard] -> expr
+    fn parse_match(&mut self) -> Result<Expr> {
+        self.expect_token(Token::Match)?;
+
+        let scrutinee = Box::new(self.parse_expr()?);
+
+        self.expect_token(Token::With)?;
+
+        let arms = self.parse_match_arms()?;
+
+        Ok(Expr::Match { scrutinee, arms })
+    }
+
+    /// Parse the arms following `with` in a match or try expression
+    fn parse_match_arms(&mut self) -> Result<Vec<MatchArm>> {
+        let mut arms = vec![];
+
+        loop {
+            // Optional leading pipe
+            self.match_token(&Token::Pipe);
+
+            let pattern = self.parse_pattern()?;
+
+            // Optional guard: | pattern when cond -> body
+            let guard = if self.match_token(&Token::When) {
+                Some(Box::new(self.parse_expr()?))
+            } else {
+                None
+            };
+
+            self.expect_token(Token::Arrow)?;
+
+            let body = Box::new(self.parse_expr()?);
+
+            arms.push(MatchArm {
+                pattern,
+                body,
+                guard,
+            });
+
+            // Check if there's another arm (starts with |)
+            if !self.check(&Token::Pipe) {
+                break;
+            }
+        }
+
+        Ok(arms)
+    }
+
+    /// Parse exception handling: try body with | pat -> e ... or try body finally e
+    fn parse_try(&mut self) -> Result<Expr> {
+        self.expect_token(Token::Try)?;
+
+        let body = Box::new(self.parse_expr()?);
+
+        if self.match_token(&Token::Finally) {
+            let finalizer = Box::new(self.parse_expr()?);
+            return Ok(Expr::TryFinally { body, finalizer });
+        }
+
+        self.expect_token(Token::With)?;
+
+        let arms = self.parse_match_arms()?;
+
+        Ok(Expr::TryWith { body, arms })
+    }
+
+    /// Parse while loop: while cond do body
+    fn parse_while(&mut self) -> Result<Expr> {
+        self.expect_token(Token::While)?;
+
+        let cond = Box::new(self.parse_expr()?);
+
+        self.expect_token(Token::Do)?;
+
+        let body = Box::new(self.parse_expr()?);
+
+        Ok(Expr::While { cond, body })
+    }
+
+    /// Desugar an interpolated string into a `sprintf` call
+    ///
+    /// `$"{name} has {count:%d} items"` becomes
+    /// `sprintf "%s has %d items" [name; count]`; untyped holes use `%s`.
+    fn desugar_interpolated_string(parts: &[InterpolationPart], pos: Position) -> Result<Expr> {
+        let mut format = String::new();
+        let mut args = Vec::new();
+
+        for part in parts {
+            match part {
+                InterpolationPart::Text(text) => format.push_str(&text.replace('%', "%%")),
+                InterpolationPart::Hole {
+                    tokens,
+                    format: spec,
+                } => {
+                    let spec = spec.as_deref().unwrap_or("%s");
+                    // Exactly one specifier and nothing else, e.g. `%d` or `%.2f`
+                    let single = matches!(parse_format(spec).as_deref(), Ok([_]))
+                        && spec.rfind('%') == Some(0)
+                        && spec.ends_with(['s', 'd', 'f']);
+                    if !single {
+                        return Err(ParseError::InvalidExpr {
+                            message: format!(
+                                "invalid format specifier '{}' in interpolated string",
+                                spec
+                            ),
+                            pos,
+                        });
+                    }
+                    format.push_str(spec);
+                    args.push(Parser::new(tokens.clone()).parse()?);
+                }
+            }
+        }
+
+        if args.is_empty() {
+            return Ok(Expr::Lit(Literal::Str(format.replace("%%", "%"))));
+        }
+        Ok(Expr::App {
+            func: Box::new(Expr::App {
+                func: Box::new(Expr::Var("sprintf".to_string())),
+                arg: Box::new(Expr::Lit(Literal::Str(format))),
+            }),
+            arg: Box::new(Expr::List(args)),
+        })
+    }
+
+    /// Parse for loop:
+    /// `for i = a to b do body`, `for i = a downto b do body`,
+    /// `for pat in source do body` or `for i in a .. b do body`.
+    ///
+    /// In comprehensions `for pat in source -> e` is short for
+    /// `for pat in source do yield e`.
+    fn parse_for(&mut self) -> Result<Expr> {
+        self.expect_token(Token::For)?;
+
+        let pos = self.current_token().pos;
+        let pattern = self.parse_pattern()?;
+
+        if self.match_token(&Token::Eq) {
+            let Pattern::Var(var) = pattern else {
+                return Err(ParseError::InvalidExpr {
+                    message: "a counted for loop needs a variable before '='".to_string(),
+                    pos,
+                });
+            };
+            let start = Box::new(self.parse_expr()?);
+            let descending = if self.match_token(&Token::Downto) {
+                true
+            } else {
+                self.expect_token(Token::To)?;
+                false
+            };
+            let finish = Box::new(self.parse_expr()?);
+            self.expect_token(Token::Do)?;
+            let body = Box::new(self.parse_expr()?);
+            return Ok(Expr::For {
+                var,
+                start,
+                finish,
+                descending,
+                body,
+            });
+        }
+
+        self.expect_token(Token::In)?;
+        let source = self.parse_range_or_expr()?;
+
+        let body = if self.match_token(&Token::Arrow) {
+            self.saw_yield = true;
+            Box::new(Expr::Yield(Box::new(self.parse_expr()?)))
+        } else {
+            self.expect_token(Token::Do)?;
+            Box::new(self.parse_expr()?)
+        };
+
+        // for i in a .. b  is the counted loop  for i = a to b
+        if let Expr::Range {
+            start,
+            step: None,
+            finish,
+        } = source
+        {
+            let var = match pattern {
+                Pattern::Var(var) => var,
+                Pattern::Wildcard => "_".to_string(),
+                _ => {
+                    return Err(ParseError::InvalidExpr {
+                        message: "a range loop needs a variable before 'in'".to_string(),
+                        pos,
+                    })
+                }
+            };
+            return Ok(Expr::For {
+                var,
+                start,
+                finish,
+                descending: false,
+                body,
+            });
+        }
+
+        Ok(Expr::ForIn {
+            pattern,
+            source: Box::new(source),
+            body,
+        })
+    }
+
+    /// Parse an expression that may be a range: `a`, `a .. b` or `a .. step .. b`
+    fn parse_range_or_expr(&mut self) -> Result<Expr> {
+        let start = self.parse_expr()?;
+        if !self.match_token(&Token::DotDot) {
+            return Ok(start);
+        }
+        let second = self.parse_expr()?;
+        let (step, finish) = if self.match_token(&Token::DotDot) {
+            (Some(Box::new(second)), self.parse_expr()?)
+        } else {
+            (None, second)
+        };
+        Ok(Expr::Range {
+            start: Box::new(start),
+            step,
+            finish: Box::new(finish),
+        })
+    }
+
+    /// Parse the contents of `[ ... ]` or `[| ... |]` up to the closing token.
+    ///
+    /// The contents are a range (`[1 .. 10]`), a comprehension when they yield
+    /// values (`[ for x in xs -> x * 2 ]`), or else the elements of a literal.
+    fn parse_collection_body(&mut self, close: Token, array: bool) -> Result<Expr> {
+        let outer_saw_yield = std::mem::replace(&mut self.saw_yield, false);
+        let mut elements = vec![];
+
+        loop {
+            elements.push(self.parse_range_or_expr()?);
+
+            // Check for comma or semicolon separator
+            let separated = if array {
+                self.match_token(&Token::Semicolon)
+            } else {
+                self.match_token(&Token::Comma) || self.match_token(&Token::Semicolon)
+            };
+            // A trailing separator may precede the closing bracket
+            if !separated || self.current_token().token == close {
+                break;
+            }
+        }
+
+        self.expect_token(close)?;
+        let is_comprehension = std::mem::replace(&mut self.saw_yield, outer_saw_yield);
 
-        // Parse parameter list
-        let mut params = vec![];
-        while let Token::Ident(_) = &self.current_token().token {
-            params.push(self.expect_ident()?);
+        if is_comprehension {
+            // Statements run in order, as if each were bound to _
+            let body = elements
+                .into_iter()
+                .rev()
+                .reduce(|rest, statement| Expr::Let {
+                    name: "_".to_string(),
+                    ty: None,
+                    value: Box::new(statement),
+                    body: Box::new(rest),
+                })
+                .expect("collection body has at least one element");
+            return Ok(Expr::Comprehension {
+                body: Box::new(body),
+                array,
+            });
         }
 
-        if params.is_empty() {
-            let tok = self.current_token();
-            return Err(ParseError::UnexpectedToken {
-                expected: "parameter".to_string(),
-                found: tok.token.clone(),
-                pos: tok.pos,
+        if elements.len() == 1 && matches!(elements[0], Expr::Range { .. }) {
+            let range = elements.remove(0);
+            return Ok(if array {
+                Expr::Comprehension {
+                    body: Box::new(Expr::YieldBang(Box::new(range))),
+                    array: true,
+                }
+            } else {
+                range
             });
         }
 
-        self.expect_token(Token::Arrow)?;
-        let body = self.parse_expr()?;
-
-        // Desugar multi-param lambda into nested lambdas
-        // fun x y -> body  =>  fun x -> fun y -> body
-        Ok(params
-            .into_iter()
-            .rev()
-            .fold(body, |acc, param| Expr::Lambda {
-                param,
-                body: Box::new(acc),
-            }))
+        if array {
+            Ok(Expr::Array(elements))
+        } else {
+            Ok(Expr::List(elements))
+        }
     }
 
-    /// Parse match expression: match expr with | pattern -> expr
-    fn parse_match(&mut self) -> Result<Expr> {
-        self.expect_token(Token::Match)?;
+    /// Parse a pattern for match expressions
+    ///
+    /// Grammar (loosest binding first):
+    /// ```text
+    /// pattern   := or_pat ("as" IDENT)*
+    /// or_pat    := cons_pat ("|" cons_pat)*
+    /// cons_pat  := atomic ("::" cons_pat)?
+    /// ```
+    fn parse_pattern(&mut self) -> Result<Pattern> {
+        let mut pattern = self.parse_or_pattern()?;
 
-        let scrutinee = Box::new(self.parse_expr()?);
+        while self.match_token(&Token::As) {
+            let tok = self.current_token();
+            match &tok.token {
+                Token::Ident(name) => {
+                    let name = name.clone();
+                    self.advance();
+                    pattern = Pattern::As {
+                        pattern: Box::new(pattern),
+                        name,
+                    };
+                }
+                _ => {
+                    return Err(ParseError::UnexpectedToken {
+                        expected: "identifier after 'as'".to_string(),
+                        found: tok.token.clone(),
+                        pos: tok.pos,
+                    })
+                }
+            }
+        }
 
-        self.expect_token(Token::With)?;
+        Ok(pattern)
+    }
 
-        let mut arms = vec![];
+    /// Parse or-pattern: p1 | p2 | ...
+    fn parse_or_pattern(&mut self) -> Result<Pattern> {
+        let first = self.parse_cons_pattern()?;
+        if !self.check(&Token::Pipe) {
+            return Ok(first);
+        }
 
-        // Parse match arms
-        loop {
-            // Optional leading pipe
-            self.match_token(&Token::Pipe);
+        let mut alternatives = vec![first];
+        while self.match_token(&Token::Pipe) {
+            alternatives.push(self.parse_cons_pattern()?);
+        }
+        Ok(Pattern::Or(alternatives))
+    }
 
-            let pattern = self.parse_pattern()?;
+    /// Parse cons pattern: head :: tail (right-associative)
+    fn parse_cons_pattern(&mut self) -> Result<Pattern> {
+        let head = self.parse_atomic_pattern()?;
 
-            self.expect_token(Token::Arrow)?;
+        if self.match_token(&Token::ColonColon) {
+            let tail = self.parse_cons_pattern()?;
+            return Ok(Pattern::Cons {
+                head: Box::new(head),
+                tail: Box::new(tail),
+            });
+        }
 
-            let body = Box::new(self.parse_expr()?);
+        Ok(head)
+    }
 
-            arms.push(MatchArm { pattern, body });
+    /// Parse semicolon-separated patterns up to (and including) the closing token
+    fn parse_pattern_sequence(&mut self, close: Token) -> Result<Vec<Pattern>> {
+        let mut patterns = vec![];
 
-            // Check if there's another arm (starts with |)
-            if !self.check(&Token::Pipe) {
+        while !self.check(&close) {
+            patterns.push(self.parse_pattern()?);
+            if !self.match_token(&Token::Semicolon) {
                 break;
             }
         }
 
-        Ok(Expr::Match { scrutinee, arms })
+        self.expect_token(close)?;
+        Ok(patterns)
     }
 
-    /// Parse while loop: while cond do body
-    fn parse_while(&mut self) -> Result<Expr> {
-        self.expect_token(Token::While)?;
-
-        let cond = Box::new(self.parse_expr()?);
-
-        self.expect_token(Token::Do)?;
-
-        let body = Box::new(self.parse_expr()?);
-
-        Ok(Expr::While { cond, body })
+    /// Check whether the current token can begin an atomic pattern
+    fn starts_atomic_pattern(&self) -> bool {
+        matches!(
+            self.current_token().token,
+            Token::Underscore
+                | Token::Ident(_)
+                | Token::Int(_)
+                | Token::Float(_)
+                | Token::Bool(_)
+                | Token::String(_)
+                | Token::Char(_)
+                | Token::Bytes(_)
+                | Token::LParen
+                | Token::LBracket
+                | Token::LBracketPipe
+                | Token::LBrace
+        )
     }
 
-    /// Parse a pattern for match expressions
-    fn parse_pattern(&mut self) -> Result<Pattern> {
+    /// Parse a single pattern without `::`, `|` or `as`
+    fn parse_atomic_pattern(&mut self) -> Result<Pattern> {
         let tok = self.current_token();
 
         match &tok.token {
@@ -853,16 +1779,14 @@ impl Parser {
                         }
 
                         self.expect_token(Token::RParen)?;
-                        Ok(Pattern::Variant {
-                            variant: val,
-                            patterns,
-                        })
+                        Ok(self.case_pattern(val, patterns))
+                    } else if self.starts_atomic_pattern() {
+                        // Juxtaposed single field: Some x, Circle [a; b]
+                        let field = self.parse_atomic_pattern()?;
+                        Ok(self.case_pattern(val, vec![field]))
                     } else {
                         // Simple variant without patterns: None, Left
-                        Ok(Pattern::Variant {
-                            variant: val,
-                            patterns: vec![],
-                        })
+                        Ok(self.case_pattern(val, vec![]))
                     }
                 } else {
                     // Variable pattern
@@ -889,6 +1813,16 @@ impl Parser {
                 self.advance();
                 Ok(Pattern::Literal(Literal::Str(val)))
             }
+            Token::Char(c) => {
+                let val = *c;
+                self.advance();
+                Ok(Pattern::Literal(Literal::Char(val)))
+            }
+            Token::Bytes(b) => {
+                let val = b.clone();
+                self.advance();
+                Ok(Pattern::Literal(Literal::Bytes(val)))
+            }
             Token::LParen => {
                 self.advance(); // consume '('
 
@@ -929,6 +1863,47 @@ impl Parser {
                     Ok(first_pat)
                 }
             }
+            Token::LBracket => {
+                // List pattern: [], [p1; p2; ...]
+                self.advance();
+                Ok(Pattern::List(self.parse_pattern_sequence(Token::RBracket)?))
+            }
+            Token::LBracketPipe => {
+                // Array pattern: [||], [| p1; p2; ... |]
+                self.advance();
+                Ok(Pattern::Array(
+                    self.parse_pattern_sequence(Token::PipeRBracket)?,
+                ))
+            }
+            Token::LBrace => {
+                // Record pattern: { Field = p; ... }
+                self.advance();
+                let mut fields = vec![];
+
+                while !self.check(&Token::RBrace) {
+                    let field = match &self.current_token().token {
+                        Token::Ident(name) => name.clone(),
+                        _ => {
+                            let tok = self.current_token();
+                            return Err(ParseError::UnexpectedToken {
+                                expected: "field name".to_string(),
+                                found: tok.token.clone(),
+                                pos: tok.pos,
+                            });
+                        }
+                    };
+                    self.advance();
+                    self.expect_token(Token::Eq)?;
+                    fields.push((field, self.parse_pattern()?));
+
+                    if !self.match_token(&Token::Semicolon) {
+                        break;
+                    }
+                }
+
+                self.expect_token(Token::RBrace)?;
+                Ok(Pattern::Record(fields))
+            }
             _ => Err(ParseError::UnexpectedToken {
                 expected: "pattern".to_string(),
                 found: tok.token.clone(),
@@ -971,10 +1946,10 @@ impl Parser {
 
     /// Parse comparison expression
     fn parse_comp_expr(&mut self) -> Result<Expr> {
-        let left = self.parse_cons_expr()?;
+        let left = self.parse_bitwise_expr()?;
 
         if let Some(op) = self.match_comparison_op() {
-            let right = self.parse_cons_expr()?;
+            let right = self.parse_bitwise_expr()?;
             Ok(Expr::BinOp {
                 op,
                 left: Box::new(left),
@@ -985,6 +1960,38 @@ impl Parser {
         }
     }
 
+    /// Parse bitwise expression (&&&, ||| and ^^^)
+    fn parse_bitwise_expr(&mut self) -> Result<Expr> {
+        let mut left = self.parse_shift_expr()?;
+
+        while let Some(op) = self.match_bitwise_op() {
+            let right = self.parse_shift_expr()?;
+            left = Expr::BinOp {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+
+        Ok(left)
+    }
+
+    /// Parse shift expression (<<< and >>>)
+    fn parse_shift_expr(&mut self) -> Result<Expr> {
+        let mut left = self.parse_cons_expr()?;
+
+        while let Some(op) = self.match_shift_op() {
+            let right = self.parse_cons_expr()?;
+            left = Expr::BinOp {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+
+        Ok(left)
+    }
+
     /// Parse cons expression (list cons operator ::)
     fn parse_cons_expr(&mut self) -> Result<Expr> {
         let left = self.parse_add_expr()?;
@@ -1047,15 +2054,50 @@ impl Parser {
         }
     }
 
-    /// Parse pipeline expression: expr |> func
+    /// Parse pipeline expression: expr |> func, or func <| expr
+    ///
+    /// Both pipes associate to the left, as in F#: `f <| g <| x` is `(f g) x`.
     fn parse_pipeline_expr(&mut self) -> Result<Expr> {
+        let mut left = self.parse_compose_expr()?;
+
+        loop {
+            if self.match_token(&Token::PipeRight) {
+                let func_expr = self.parse_app_expr()?; // The function to pipe into
+                left = Expr::App {
+                    func: Box::new(func_expr),
+                    arg: Box::new(left),
+                };
+            } else if self.match_token(&Token::PipeLeft) {
+                let arg = self.parse_compose_expr()?;
+                left = Expr::App {
+                    func: Box::new(left),
+                    arg: Box::new(arg),
+                };
+            } else {
+                break;
+            }
+        }
+
+        Ok(left)
+    }
+
+    /// Parse function composition: f >> g, or f << g
+    fn parse_compose_expr(&mut self) -> Result<Expr> {
         let mut left = self.parse_or_expr()?;
 
-        while self.match_token(&Token::PipeRight) {
-            let func_expr = self.parse_app_expr()?; // The function to pipe into
-            left = Expr::App {
-                func: Box::new(func_expr),
-                arg: Box::new(left),
+        loop {
+            let op = if self.match_token(&Token::GtGt) {
+                BinOp::ComposeRight
+            } else if self.match_token(&Token::LtLt) {
+                BinOp::ComposeLeft
+            } else {
+                break;
+            };
+            let right = self.parse_or_expr()?;
+            left = Expr::BinOp {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
             };
         }
 
@@ -1186,7 +2228,17 @@ impl Parser {
     }
 
     /// Parse postfix expressions (array indexing, array update, record access)
+    /// and the prefix dereference operator `!`
     fn parse_postfix_expr(&mut self) -> Result<Expr> {
+        if self.match_token(&Token::Bang) {
+            // !r  =>  op_Dereference r
+            let cell = self.parse_postfix_expr()?;
+            return Ok(Expr::App {
+                func: Box::new(Expr::Var("op_Dereference".to_string())),
+                arg: Box::new(cell),
+            });
+        }
+
         let mut expr = self.parse_primary()?;
 
         loop {
@@ -1220,13 +2272,17 @@ impl Parser {
 
                         // Check if the base expression is a module (uppercase identifier)
                         // If so, use record access (for F#-style Module.function calls)
-                        // not method call syntax
-                        let is_module =
-                            matches!(&expr, Expr::Var(name) if Self::is_uppercase_ident(name));
+                        // not method call syntax. Static members of a script type,
+                        // `Config.Create()`, are method calls.
+                        let is_module = matches!(&expr, Expr::Var(name)
+                            if Self::is_uppercase_ident(name)
+                                && !self.types_with_statics.contains(name));
 
                         // Check if followed by '(' to distinguish method call from field access
                         // But don't use method call for module access (List.map, Array.ofList, etc.)
-                        if self.current_token().token == Token::LParen && !is_module {
+                        // A '(' at column 1 starts a new statement, as in function application.
+                        let tok = self.current_token();
+                        if tok.token == Token::LParen && tok.pos.column != 1 && !is_module {
                             // Method call: obj.method(args)
                             self.advance(); // consume '('
 
@@ -1327,11 +2383,17 @@ impl Parser {
         // Not a record update, restore position and parse as record literal
         self.pos = save_pos;
 
-        // Parse record literal fields
+        // Parse record literal fields; the first may name the record type,
+        // as in { Person.Name = "John"; Age = 30 }
+        let mut type_name = String::new();
         let mut fields = vec![];
 
         loop {
-            let field_name = self.expect_ident()?;
+            let mut field_name = self.expect_ident()?;
+            if fields.is_empty() && self.match_token(&Token::Dot) {
+                type_name = field_name;
+                field_name = self.expect_ident()?;
+            }
             self.expect_token(Token::Eq)?;
             let value = self.parse_expr()?;
             fields.push((field_name, Box::new(value)));
@@ -1349,10 +2411,7 @@ impl Parser {
 
         self.expect_token(Token::RBrace)?;
 
-        Ok(Expr::RecordLiteral {
-            type_name: String::new(),
-            fields,
-        })
+        Ok(Expr::RecordLiteral { type_name, fields })
     }
 
     /// Parse anonymous record literal: {| name = "John"; age = 30 |}
@@ -1363,10 +2422,7 @@ impl Parser {
 
         // Empty anonymous record: {||}
         if self.match_token(&Token::PipeRBrace) {
-            return Ok(Expr::RecordLiteral {
-                type_name: String::new(),
-                fields: vec![],
-            });
+            return Ok(Expr::AnonRecordLiteral { fields: vec![] });
         }
 
         // Anonymous records don't support the 'with' update syntax in the literal itself
@@ -1401,10 +2457,7 @@ impl Parser {
 
         self.expect_token(Token::PipeRBrace)?;
 
-        Ok(Expr::RecordLiteral {
-            type_name: String::new(), // Anonymous records have no type name
-            fields,
-        })
+        Ok(Expr::AnonRecordLiteral { fields })
     }
 
     /// Parse variant constructor: Some(42), Left, Rectangle(10.0, 20.0)
@@ -1432,7 +2485,7 @@ impl Parser {
 
             self.expect_token(Token::RParen)?;
             Ok(Expr::VariantConstruct {
-                type_name: String::new(), // Filled by typechecker
+                type_name: self.active_case_owner(&variant_name),
                 variant: variant_name,
                 fields,
             })
@@ -1440,19 +2493,68 @@ impl Parser {
             // Simple variant without fields: None, Left
             // This will be converted to VariantConstruct with fields if followed by arguments
             Ok(Expr::VariantConstruct {
-                type_name: String::new(),
+                type_name: self.active_case_owner(&variant_name),
                 variant: variant_name,
                 fields: vec![],
             })
         }
     }
 
+    /// The multi-case active pattern whose result `variant` constructs, as in
+    /// `Even` within `let (|Even|Odd|) n = ...`, or an empty string for the
+    /// typechecker to fill in
+    fn active_case_owner(&self, variant: &str) -> String {
+        match self.active_patterns.get(variant) {
+            Some(function) if matches!(active_pattern_cases(function), Some((cases, false)) if cases.len() > 1) => {
+                function.clone()
+            }
+            _ => String::new(),
+        }
+    }
+
+    /// A constructor pattern, which applies an active pattern if its name is
+    /// one of an active pattern's cases
+    fn case_pattern(&self, name: String, patterns: Vec<Pattern>) -> Pattern {
+        match self.active_patterns.get(&name) {
+            Some(function) => Pattern::Active {
+                function: function.clone(),
+                case: name,
+                patterns,
+            },
+            None => Pattern::Variant {
+                variant: name,
+                patterns,
+            },
+        }
+    }
+
     /// Check if an identifier starts with uppercase letter (heuristic for variant constructor)
     fn is_uppercase_ident(s: &str) -> bool {
         s.chars().next().is_some_and(|c| c.is_uppercase())
     }
 
     /// Parse primary expression (literals, variables, parenthesized expressions, tuples, lists)
+    /// Parse the unit of measure of a number literal, as in `9.81<m/s^2>`
+    ///
+    /// The `<` must follow on the same line; when no measure and `>` follow,
+    /// the `<` is left for a comparison such as `0 < n`.
+    fn parse_measure_suffix(&mut self, value: Literal) -> Expr {
+        let literal_line = self.tokens[self.pos - 1].pos.line;
+        let tok = self.current_token();
+        if tok.token != Token::Lt || tok.pos.line != literal_line {
+            return Expr::Lit(value);
+        }
+        let saved_pos = self.pos;
+        self.advance();
+        match self.parse_measure() {
+            Ok(measure) if self.match_token(&Token::Gt) => Expr::MeasuredLit { value, measure },
+            _ => {
+                self.pos = saved_pos;
+                Expr::Lit(value)
+            }
+        }
+    }
+
     fn parse_primary(&mut self) -> Result<Expr> {
         let tok = self.current_token();
 
@@ -1460,12 +2562,12 @@ impl Parser {
             Token::Int(n) => {
                 let val = *n;
                 self.advance();
-                Ok(Expr::Lit(Literal::Int(val)))
+                Ok(self.parse_measure_suffix(Literal::Int(val)))
             }
             Token::Float(f) => {
                 let val = *f;
                 self.advance();
-                Ok(Expr::Lit(Literal::Float(val)))
+                Ok(self.parse_measure_suffix(Literal::Float(val)))
             }
             Token::Bool(b) => {
                 let val = *b;
@@ -1477,10 +2579,31 @@ impl Parser {
                 self.advance();
                 Ok(Expr::Lit(Literal::Str(val)))
             }
+            Token::Char(c) => {
+                let val = *c;
+                self.advance();
+                Ok(Expr::Lit(Literal::Char(val)))
+            }
+            Token::Bytes(b) => {
+                let val = b.clone();
+                self.advance();
+                Ok(Expr::Lit(Literal::Bytes(val)))
+            }
+            Token::InterpolatedString(parts) => {
+                let parts = parts.clone();
+                let pos = tok.pos;
+                self.advance();
+                Self::desugar_interpolated_string(&parts, pos)
+            }
             Token::Ident(name) => {
                 let val = name.clone();
                 self.advance();
 
+                // builder { ... } is a computation expression, e.g. seq { ... }
+                if self.starts_computation_body(&val) {
+                    return self.parse_computation_body(val);
+                }
+
                 // Check for Array.length (special case for compatibility)
                 if val == "Array" && self.match_token(&Token::Dot) {
                     let method = self.expect_ident()?;
@@ -1500,9 +2623,7 @@ impl Parser {
                 // Check if this could be a variant constructor
                 // Use uppercase heuristic: if identifier starts with uppercase, it's likely a variant
                 // Exception: if followed by '.', it's likely a module access (e.g. String.length)
-                if (Self::is_uppercase_ident(&val) && !self.check(&Token::Dot))
-                    || matches!(self.current_token().token, Token::LParen)
-                {
+                if Self::is_uppercase_ident(&val) && !self.check(&Token::Dot) {
                     // This looks like a variant constructor
                     self.parse_variant_construct(val)
                 } else {
@@ -1510,6 +2631,9 @@ impl Parser {
                     Ok(Expr::Var(val))
                 }
             }
+            Token::LParen if self.starts_active_pattern_name() => {
+                Ok(Expr::Var(self.parse_active_pattern_name()?))
+            }
             Token::LParen => {
                 self.advance(); // consume '('
 
@@ -1568,7 +2692,8 @@ impl Parser {
         }
     }
 
-    /// Parse list: [1, 2, 3] or [1; 2; 3] or []
+    /// Parse list: [1, 2, 3] or [1; 2; 3] or [], a range [1 .. 10] or a
+    /// comprehension [ for x in xs -> x * 2 ]
     ///
     /// Supports both comma and semicolon separators for backward compatibility.
     /// Trailing separators are allowed: [1, 2, 3,] or [1; 2; 3;]
@@ -1580,30 +2705,11 @@ impl Parser {
             return Ok(Expr::List(vec![]));
         }
 
-        // Parse list elements
-        let mut elements = vec![];
-
-        loop {
-            elements.push(self.parse_expr()?);
-
-            // Check for comma or semicolon separator
-            if self.match_token(&Token::Comma) || self.match_token(&Token::Semicolon) {
-                // Check for trailing separator before ]
-                if matches!(self.current_token().token, Token::RBracket) {
-                    break;
-                }
-                // Otherwise continue parsing
-            } else {
-                break;
-            }
-        }
-
-        self.expect_token(Token::RBracket)?;
-
-        Ok(Expr::List(elements))
+        self.parse_collection_body(Token::RBracket, false)
     }
 
-    /// Parse array: [|1; 2; 3|] or [||]
+    /// Parse array: [|1; 2; 3|] or [||], a range [|1 .. 10|] or a
+    /// comprehension [| for x in xs -> x * 2 |]
     fn parse_array(&mut self) -> Result<Expr> {
         self.expect_token(Token::LBracketPipe)?;
 
@@ -1612,34 +2718,16 @@ impl Parser {
             return Ok(Expr::Array(vec![]));
         }
 
-        // Parse array elements
-        let mut elements = vec![];
-
-        loop {
-            elements.push(self.parse_expr()?);
-
-            // Check for semicolon separator
-            if self.match_token(&Token::Semicolon) {
-                // Check for trailing semicolon before |]
-                if matches!(self.current_token().token, Token::PipeRBracket) {
-                    break;
-                }
-                // Otherwise continue parsing
-            } else {
-                break;
-            }
-        }
-
-        self.expect_token(Token::PipeRBracket)?;
-
-        Ok(Expr::Array(elements))
+        self.parse_collection_body(Token::PipeRBracket, true)
     }
 
     /// Parse DU type definition: type Option = Some of int | None
     pub fn parse_du_type_def(&mut self) -> Result<DuTypeDef> {
         self.expect_token(Token::Type)?;
 
+        let name_pos = self.current_token().pos;
         let type_name = self.expect_ident()?;
+        let type_params = self.parse_type_params()?;
 
         self.expect_token(Token::Eq)?;
 
@@ -1693,9 +2781,18 @@ impl Parser {
             }
         }
 
+        Self::check_type_vars(
+            &type_name,
+            &type_params,
+            variants.iter().flat_map(|v| &v.fields),
+            name_pos,
+        )?;
+        let members = self.parse_type_members(&type_name, &type_params)?;
         Ok(DuTypeDef {
             name: type_name,
+            type_params,
             variants,
+            members,
         })
     }
 
@@ -1704,7 +2801,7 @@ impl Parser {
         // Parse a simple type or function type
         // Note: tuple types (int * int) are handled by the caller (parse_du_type_def)
         // because the * separator is used differently in DU definitions
-        let left = self.parse_simple_type()?;
+        let left = self.parse_postfix_type()?;
 
         // Check for function type: int -> string
         if self.match_token(&Token::Arrow) {
@@ -1715,10 +2812,87 @@ impl Parser {
         Ok(left)
     }
 
-    /// Parse simple type (identifier)
+    /// Parse a type expression where `*` builds a tuple: `int * string -> bool`
+    fn parse_tuple_type_expr(&mut self) -> Result<TypeExpr> {
+        let mut elements = vec![self.parse_postfix_type()?];
+        while self.match_token(&Token::Star) {
+            elements.push(self.parse_postfix_type()?);
+        }
+        let left = if elements.len() == 1 {
+            elements.pop().unwrap()
+        } else {
+            TypeExpr::Tuple(elements)
+        };
+
+        if self.match_token(&Token::Arrow) {
+            let right = self.parse_tuple_type_expr()?;
+            return Ok(TypeExpr::Function(Box::new(left), Box::new(right)));
+        }
+
+        Ok(left)
+    }
+
+    /// Parse postfix type application: `int list`, `'a option list`, `byte[]`
+    ///
+    /// The applied type name must be on the same line, so a definition ending
+    /// in a type is not glued to an expression on the next line.
+    fn parse_postfix_type(&mut self) -> Result<TypeExpr> {
+        let mut ty = self.parse_simple_type()?;
+        loop {
+            let tok = self.current_token();
+            let previous_line = self.tokens[self.pos - 1].pos.line;
+            match &tok.token {
+                Token::Ident(name) if tok.pos.line == previous_line => {
+                    ty = TypeExpr::App(name.clone(), vec![ty]);
+                    self.advance();
+                }
+                Token::LBracket
+                    if tok.pos.line == previous_line
+                        && self.tokens.get(self.pos + 1).map(|t| &t.token)
+                            == Some(&Token::RBracket) =>
+                {
+                    ty = TypeExpr::App("array".to_string(), vec![ty]);
+                    self.advance();
+                    self.advance();
+                }
+                _ => return Ok(ty),
+            }
+        }
+    }
+
+    /// Parse simple type: `int`, `'a`, `Tree<'a>` or a parenthesized type
     fn parse_simple_type(&mut self) -> Result<TypeExpr> {
-        let name = self.expect_ident()?;
-        Ok(TypeExpr::Named(name))
+        let tok = self.current_token().clone();
+        match tok.token {
+            Token::TypeVar(name) => {
+                self.advance();
+                Ok(TypeExpr::Var(name))
+            }
+            Token::LParen => {
+                self.advance();
+                let ty = self.parse_tuple_type_expr()?;
+                self.expect_token(Token::RParen)?;
+                Ok(ty)
+            }
+            _ => {
+                let name = self.expect_ident()?;
+                if !self.match_token(&Token::Lt) {
+                    return Ok(TypeExpr::Named(name));
+                }
+                if matches!(name.as_str(), "int" | "float") {
+                    // A number with a unit of measure: float<m/s>
+                    let measure = self.parse_measure()?;
+                    self.expect_closing_angle()?;
+                    return Ok(TypeExpr::App(name, vec![measure]));
+                }
+                let mut args = vec![self.parse_tuple_type_expr()?];
+                while self.match_token(&Token::Comma) {
+                    args.push(self.parse_tuple_type_expr()?);
+                }
+                self.expect_closing_angle()?;
+                Ok(TypeExpr::App(name, args))
+            }
+        }
     }
 
     // ========================================================================
@@ -1730,7 +2904,7 @@ impl Parser {
     /// A computation expression is of the form:
     ///   async { let! x = expr; do! expr; return expr }
     ///
-    /// Future: could support other builders like "seq", "option", "result"
+    /// Other builders are parsed from `builder { ... }` in `parse_primary`.
     fn parse_computation_expr(&mut self) -> Result<Expr> {
         // Get builder name from current token
         let builder = match &self.current_token().token {
@@ -1748,9 +2922,43 @@ impl Parser {
             }
         };
 
+        self.parse_computation_body(builder)
+    }
+
+    /// Whether `builder` followed by `{` starts a computation expression rather
+    /// than applying `builder` to a record
+    ///
+    /// The built-in `seq`, `option` and `result` builders always do; any other
+    /// builder does when the block opens with a statement such as `let!`.
+    fn starts_computation_body(&self, builder: &str) -> bool {
+        if !self.check(&Token::LBrace) {
+            return false;
+        }
+        if matches!(builder, "seq" | "option" | "result") {
+            return true;
+        }
+        matches!(
+            self.tokens.get(self.pos + 1).map(|t| &t.token),
+            Some(
+                Token::LetBang
+                    | Token::Let
+                    | Token::DoBang
+                    | Token::Return
+                    | Token::ReturnBang
+                    | Token::Yield
+                    | Token::YieldBang
+            )
+        )
+    }
+
+    /// Parse the `{ statements... }` of a computation expression for `builder`
+    fn parse_computation_body(&mut self, builder: String) -> Result<Expr> {
         // Expect '{'
         self.expect_token(Token::LBrace)?;
 
+        // A yield in here belongs to the builder, not to an enclosing list
+        let outer_saw_yield = self.saw_yield;
+
         // Parse CE statements
         let mut body = Vec::new();
         while !self.check(&Token::RBrace) && !self.is_at_end() {
@@ -1761,6 +2969,7 @@ impl Parser {
 
         // Expect '}'
         self.expect_token(Token::RBrace)?;
+        self.saw_yield = outer_saw_yield;
 
         Ok(Expr::ComputationExpr { builder, body })
     }
@@ -1775,6 +2984,7 @@ impl Parser {
     /// - return! expr     (return from another CE)
     /// - yield expr       (yield value for sequence)
     /// - yield! expr      (yield from another sequence)
+    /// - a .. b           (yield every value of a range)
     /// - expr             (plain expression)
     fn parse_ce_statement(&mut self) -> Result<CEStatement> {
         let tok = &self.current_token().token;
@@ -1828,8 +3038,11 @@ impl Parser {
                 Ok(CEStatement::YieldBang { value })
             }
             _ => {
-                // Plain expression
-                let value = Box::new(self.parse_expr()?);
+                // Plain expression, or a range standing for yield! of its values
+                let value = Box::new(self.parse_range_or_expr()?);
+                if value.is_range() {
+                    return Ok(CEStatement::YieldBang { value });
+                }
                 Ok(CEStatement::Expr { value })
             }
         }
@@ -1889,6 +3102,23 @@ impl Parser {
         }
     }
 
+    /// Expect the `>` closing a type argument list.
+    ///
+    /// The lexer reads `>>` and `>>>` as operators, so in `Option<List<int>>`
+    /// one token closes several lists: take its first `>` and leave the rest.
+    fn expect_closing_angle(&mut self) -> Result<()> {
+        let rest = match self.current_token().token {
+            Token::GtGt => Token::Gt,
+            Token::GtGtGt => Token::GtGt,
+            _ => return self.expect_token(Token::Gt),
+        };
+        let tok = &mut self.tokens[self.pos];
+        tok.token = rest;
+        tok.pos.column += 1;
+        tok.pos.offset += 1;
+        Ok(())
+    }
+
     /// Expect a specific token and consume it
     fn expect_token(&mut self, expected: Token) -> Result<()> {
         if self.check(&expected) {
@@ -1905,6 +3135,75 @@ impl Parser {
     }
 
     /// Expect an identifier and return it
+    /// Parse the name bound by a let: an identifier or an active pattern
+    fn expect_binding_name(&mut self) -> Result<String> {
+        if self.starts_active_pattern_name() {
+            self.parse_active_pattern_name()
+        } else {
+            self.expect_ident()
+        }
+    }
+
+    /// Check whether the current tokens begin an active pattern name: `(|`
+    fn starts_active_pattern_name(&self) -> bool {
+        self.check(&Token::LParen)
+            && self.tokens.get(self.pos + 1).map(|t| &t.token) == Some(&Token::Pipe)
+    }
+
+    /// Parse an active pattern name: `(|Even|Odd|)`, `(|Upper|)` or `(|Int|_|)`
+    ///
+    /// The cases become usable in patterns from here on. Returns the name of
+    /// the function, such as `|Even|Odd|`.
+    fn parse_active_pattern_name(&mut self) -> Result<String> {
+        let start = self.current_token().pos;
+        self.expect_token(Token::LParen)?;
+        self.expect_token(Token::Pipe)?;
+
+        let mut cases = vec![];
+        let mut partial = false;
+        loop {
+            let tok = self.current_token();
+            match &tok.token {
+                Token::Ident(name) if Self::is_uppercase_ident(name) && !partial => {
+                    cases.push(name.clone());
+                }
+                Token::Underscore if !partial && !cases.is_empty() => partial = true,
+                Token::Ident(name) if name == "_" && !partial && !cases.is_empty() => {
+                    partial = true
+                }
+                _ => {
+                    return Err(ParseError::UnexpectedToken {
+                        expected: "active pattern case name".to_string(),
+                        found: tok.token.clone(),
+                        pos: tok.pos,
+                    })
+                }
+            }
+            self.advance();
+            self.expect_token(Token::Pipe)?;
+            if self.match_token(&Token::RParen) {
+                break;
+            }
+        }
+
+        if partial && cases.len() > 1 {
+            return Err(ParseError::UnexpectedToken {
+                expected: "a single case in a partial active pattern".to_string(),
+                found: Token::Underscore,
+                pos: start,
+            });
+        }
+
+        let mut function = format!("|{}|", cases.join("|"));
+        if partial {
+            function.push_str("_|");
+        }
+        for case in cases {
+            self.active_patterns.insert(case, function.clone());
+        }
+        Ok(function)
+    }
+
     fn expect_ident(&mut self) -> Result<String> {
         let tok = self.current_token();
         match &tok.token {
@@ -1933,11 +3232,16 @@ impl Parser {
                 | Token::Float(_)
                 | Token::Bool(_)
                 | Token::String(_)
+                | Token::Char(_)
+                | Token::Bytes(_)
+                | Token::InterpolatedString(_)
                 | Token::Ident(_)
                 | Token::LParen
                 | Token::LBracket
                 | Token::LBracketPipe
                 | Token::LBrace
+                | Token::LBracePipe
+                | Token::Bang
         )
     }
 
@@ -1985,6 +3289,40 @@ impl Parser {
         let op = match tok {
             Token::Star => Some(BinOp::Mul),
             Token::Slash => Some(BinOp::Div),
+            Token::Percent => Some(BinOp::Mod),
+            _ => None,
+        };
+
+        if op.is_some() {
+            self.advance();
+        }
+
+        op
+    }
+
+    /// Try to match bitwise operator
+    fn match_bitwise_op(&mut self) -> Option<BinOp> {
+        let tok = &self.current_token().token;
+        let op = match tok {
+            Token::AmpAmpAmp => Some(BinOp::BitAnd),
+            Token::PipePipePipe => Some(BinOp::BitOr),
+            Token::CaretCaretCaret => Some(BinOp::BitXor),
+            _ => None,
+        };
+
+        if op.is_some() {
+            self.advance();
+        }
+
+        op
+    }
+
+    /// Try to match shift operator
+    fn match_shift_op(&mut self) -> Option<BinOp> {
+        let tok = &self.current_token().token;
+        let op = match tok {
+            Token::LtLtLt => Some(BinOp::Shl),
+            Token::GtGtGt => Some(BinOp::Shr),
             _ => None,
         };
 
@@ -2032,6 +3370,324 @@ mod tests {
         assert!(expr.is_lambda());
     }
 
+    #[test]
+    fn test_parse_annotated_function_binding() {
+        let expr = parse_str("let f (x: int) (y: string) : bool = true in f").unwrap();
+        assert_eq!(
+            expr.to_string(),
+            "(let f = (fun (x: int) -> (fun (y: string) : bool -> true)) in f)"
+        );
+    }
+
+    #[test]
+    fn test_parse_annotated_value_binding() {
+        let expr = parse_str("let xs: int list = [] in xs").unwrap();
+        match expr {
+            Expr::Let { ty: Some(ty), .. } => {
+                assert_eq!(ty.to_string(), "int list");
+                // The span covers the written type
+                assert_eq!((ty.span.start.column, ty.span.end.column), (9, 17));
+            }
+            _ => panic!("Expected annotated let, got {:?}", expr),
+        }
+    }
+
+    #[test]
+    fn test_parse_char_pattern_and_byte_array_annotation() {
+        let expr = parse_str("match c with | 'a' -> 1 | _ -> 0").unwrap();
+        let (_, arms) = expr.as_match().unwrap();
+        assert!(matches!(
+            arms[0].pattern,
+            Pattern::Literal(Literal::Char('a'))
+        ));
+
+        let expr = parse_str("let b: byte[] = \"hi\"B in b").unwrap();
+        match expr {
+            Expr::Let {
+                ty: Some(ty),
+                value,
+                ..
+            } => {
+                assert_eq!(ty.to_string(), "byte array");
+                assert_eq!(*value, Expr::Lit(Literal::Bytes(b"hi".to_vec())));
+            }
+            _ => panic!("Expected annotated let, got {:?}", expr),
+        }
+    }
+
+    #[test]
+    fn test_parse_annotated_lambda_parameter() {
+        let expr = parse_str("fun (r: Config) n -> r").unwrap();
+        assert_eq!(expr.to_string(), "(fun (r: Config) -> (fun n -> r))");
+        // A parenthesized parameter without a type is still an application argument
+        assert!(parse_str("f (x)").unwrap().is_app());
+    }
+
+    #[test]
+    fn test_parse_active_pattern_definition_and_use() {
+        let expr = parse_str(
+            "let (|Even|Odd|) n = if n % 2 = 0 then Even else Odd in \
+             match 3 with | Even -> 0 | Odd -> 1",
+        )
+        .unwrap();
+        let Expr::Let {
+            name, value, body, ..
+        } = expr
+        else {
+            panic!("Expected let");
+        };
+        assert_eq!(name, "|Even|Odd|");
+        // Cases built in the body belong to the active pattern's result
+        assert!(value.to_string().contains("Even"));
+        let (_, arms) = body.as_match().unwrap();
+        assert_eq!(
+            arms[0].pattern,
+            Pattern::Active {
+                function: "|Even|Odd|".to_string(),
+                case: "Even".to_string(),
+                patterns: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_partial_active_pattern() {
+        let expr =
+            parse_str("let (|Int|_|) s = None in match s with | Int n -> n | Some x -> x").unwrap();
+        let Expr::Let { name, body, .. } = expr else {
+            panic!("Expected let");
+        };
+        assert_eq!(name, "|Int|_|");
+        let (_, arms) = body.as_match().unwrap();
+        assert!(matches!(
+            &arms[0].pattern,
+            Pattern::Active { function, case, patterns }
+                if function == "|Int|_|" && case == "Int" && patterns.len() == 1
+        ));
+        // Other constructors are unaffected
+        assert!(arms[1].pattern.is_variant());
+
+        // Only a single case may be partial
+        assert!(parse_str("let (|A|B|_|) x = None in x").is_err());
+        // The function can be applied directly
+        assert!(parse_str("let (|Upper|) s = s in (|Upper|) 1")
+            .unwrap()
+            .is_let());
+    }
+
+    #[test]
+    fn test_parse_match_guard() {
+        let expr = parse_str("match n with | x when x > 0 -> 1 | _ -> 0").unwrap();
+        let (_, arms) = expr.as_match().unwrap();
+        assert_eq!(arms.len(), 2);
+        assert!(arms[0].has_guard());
+        assert!(matches!(
+            arms[0].guard.as_deref(),
+            Some(Expr::BinOp { op: BinOp::Gt, .. })
+        ));
+        assert!(!arms[1].has_guard());
+    }
+
+    #[test]
+    fn test_parse_match_guard_on_variant() {
+        let expr =
+            parse_str("match o with | Some(v) when v = 1 -> v | Some(v) -> 0 - v | None -> 0")
+                .unwrap();
+        let (_, arms) = expr.as_match().unwrap();
+        assert_eq!(arms.len(), 3);
+        assert_eq!(arms[0].to_string(), "Some(v) when (v = 1) -> v");
+    }
+
+    #[test]
+    fn test_parse_try_with() {
+        let expr =
+            parse_str("try failwith \"boom\" with | Failure msg -> msg | _ -> \"other\"").unwrap();
+        match expr {
+            Expr::TryWith { body, arms } => {
+                assert!(body.is_app());
+                assert_eq!(arms.len(), 2);
+                assert_eq!(arms[0].to_string(), "Failure(msg) -> msg");
+            }
+            _ => panic!("Expected TryWith, got {:?}", expr),
+        }
+    }
+
+    #[test]
+    fn test_parse_lowercase_call_with_parens_is_application() {
+        let expr = parse_str("raise (Failure \"x\")").unwrap();
+        assert!(expr.is_app());
+        assert_eq!(expr.to_string(), "(raise Failure(\"x\"))");
+    }
+
+    #[test]
+    fn test_parse_try_finally() {
+        let expr = parse_str("try 1 + 2 finally print \"done\"").unwrap();
+        assert_eq!(expr.to_string(), "(try (1 + 2) finally (print \"done\"))");
+    }
+
+    #[test]
+    fn test_parse_let_mutable_and_assign() {
+        let expr = parse_str("let mutable i = 0 in while i < 3 do i <- i + 1").unwrap();
+        assert_eq!(
+            expr.to_string(),
+            "(let mutable i = 0 in (while (i < 3) do (i <- (i + 1))))"
+        );
+    }
+
+    #[test]
+    fn test_parse_assign_requires_variable_target() {
+        let err = parse_str("f x <- 1").unwrap_err();
+        assert!(err.to_string().contains("only variables can be assigned"));
+    }
+
+    #[test]
+    fn test_parse_ref_cell_operators() {
+        let expr = parse_str("r := !r + 1").unwrap();
+        assert_eq!(
+            expr.to_string(),
+            "((op_ColonEquals r) ((op_Dereference r) + 1))"
+        );
+        let expr = parse_str("f !r").unwrap();
+        assert_eq!(expr.to_string(), "(f (op_Dereference r))");
+    }
+
+    #[test]
+    fn test_parse_top_level_let_mutable() {
+        let program = parse_program_str("let mutable count = 0\ncount").unwrap();
+        assert_eq!(
+            program.items[0],
+            ModuleItem::LetMutable("count".to_string(), Expr::Lit(Literal::Int(0)))
+        );
+        assert_eq!(program.main_expr, Some(Expr::Var("count".to_string())));
+    }
+
+    #[test]
+    fn test_parse_interpolated_string_desugars_to_sprintf() {
+        let expr = parse_str(r#"$"{name} has {count:%d} items (100%)""#).unwrap();
+        assert_eq!(
+            expr.to_string(),
+            r#"((sprintf "%s has %d items (100%%)") [name; count])"#
+        );
+        let expr = parse_str(r#"$"no holes, 5%""#).unwrap();
+        assert_eq!(expr, Expr::Lit(Literal::Str("no holes, 5%".to_string())));
+    }
+
+    #[test]
+    fn test_parse_interpolated_string_rejects_bad_specifier() {
+        let err = parse_str(r#"$"{x:%q}""#).unwrap_err();
+        assert!(err.to_string().contains("invalid format specifier '%q'"));
+        let err = parse_str(r#"$"{x:%d%d}""#).unwrap_err();
+        assert!(err.to_string().contains("invalid format specifier"));
+    }
+
+    #[test]
+    fn test_parse_counted_for_loops() {
+        let expr = parse_str("for i = 1 to n do print i").unwrap();
+        assert_eq!(expr.to_string(), "(for i = 1 to n do (print i))");
+        let expr = parse_str("for i = n - 1 downto 0 do print i").unwrap();
+        assert_eq!(expr.to_string(), "(for i = (n - 1) downto 0 do (print i))");
+        let expr = parse_str("for i in 1 .. 3 do print i").unwrap();
+        assert_eq!(expr.to_string(), "(for i = 1 to 3 do (print i))");
+    }
+
+    #[test]
+    fn test_parse_for_in_loop() {
+        let expr = parse_str("for (k, v) in pairs do print k").unwrap();
+        assert_eq!(expr.to_string(), "(for (k, v) in pairs do (print k))");
+    }
+
+    #[test]
+    fn test_parse_ranges() {
+        assert_eq!(parse_str("[1 .. 10]").unwrap().to_string(), "(1 .. 10)");
+        assert_eq!(
+            parse_str("[0 .. 2 .. n]").unwrap().to_string(),
+            "(0 .. 2 .. n)"
+        );
+        assert_eq!(
+            parse_str("[|1 .. 3|]").unwrap().to_string(),
+            "[| (yield! (1 .. 3)) |]"
+        );
+        // A stepped range loop walks the range instead of counting
+        assert!(matches!(
+            parse_str("for i in 0 .. 2 .. 10 do print i").unwrap(),
+            Expr::ForIn { source, .. } if matches!(*source, Expr::Range { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_comprehensions() {
+        let expr = parse_str("[ for x in xs -> x * 2 ]").unwrap();
+        assert_eq!(expr.to_string(), "[ (for x in xs do (yield (x * 2))) ]");
+        let expr = parse_str("[| for x in xs do if x > 0 then yield x |]").unwrap();
+        assert!(matches!(expr, Expr::Comprehension { array: true, .. }));
+        // Statements are sequenced; a nested list does not become a comprehension
+        let expr = parse_str("[ yield [1; 2]; yield! xs ]").unwrap();
+        let Expr::Comprehension { body, array } = expr else {
+            panic!("expected a comprehension");
+        };
+        assert!(!array);
+        assert!(matches!(*body, Expr::Let { ref name, .. } if name == "_"));
+        assert!(parse_str("[[1; 2]; [3]]").unwrap().is_list());
+    }
+
+    #[test]
+    fn test_parse_if_without_else() {
+        let expr = parse_str("if ok then print 1").unwrap();
+        assert_eq!(expr.to_string(), "(if ok then (print 1) else ())");
+    }
+
+    #[test]
+    fn test_parse_counted_for_requires_variable() {
+        let err = parse_str("for (a, b) = 1 to 3 do a").unwrap_err();
+        assert!(err.to_string().contains("needs a variable"));
+    }
+
+    #[test]
+    fn test_parse_list_and_cons_patterns() {
+        let expr =
+            parse_str("match xs with | [] -> 0 | [a; b] -> a | h :: t :: rest -> h").unwrap();
+        let (_, arms) = expr.as_match().unwrap();
+        assert_eq!(arms[0].pattern, Pattern::List(vec![]));
+        assert_eq!(arms[1].pattern.to_string(), "[a; b]");
+        assert_eq!(arms[2].pattern.to_string(), "h :: t :: rest");
+        assert!(matches!(
+            &arms[2].pattern,
+            Pattern::Cons { tail, .. } if matches!(tail.as_ref(), Pattern::Cons { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_array_and_record_patterns() {
+        let expr =
+            parse_str("match v with | [| x; _ |] -> x | { Name = n; Age = 30 } -> n").unwrap();
+        let (_, arms) = expr.as_match().unwrap();
+        assert_eq!(
+            arms[0].pattern,
+            Pattern::Array(vec![Pattern::Var("x".to_string()), Pattern::Wildcard])
+        );
+        assert_eq!(
+            arms[1].pattern,
+            Pattern::Record(vec![
+                ("Name".to_string(), Pattern::Var("n".to_string())),
+                ("Age".to_string(), Pattern::Literal(Literal::Int(30))),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_or_and_as_patterns() {
+        let expr =
+            parse_str("match c with | Red | Green as c -> c | (x :: _ as all) -> x").unwrap();
+        let (_, arms) = expr.as_match().unwrap();
+        assert_eq!(arms.len(), 2);
+        assert_eq!(arms[0].pattern.to_string(), "Red | Green as c");
+        assert!(matches!(
+            &arms[0].pattern,
+            Pattern::As { pattern, name } if name == "c" && matches!(pattern.as_ref(), Pattern::Or(alts) if alts.len() == 2)
+        ));
+        assert_eq!(arms[1].pattern.to_string(), "x :: _ as all");
+    }
+
     #[test]
     fn test_parse_string_concat() {
         let expr = parse_str(r#""hello" ++ "world""#).unwrap();
@@ -2396,6 +4052,91 @@ mod tests {
         assert_eq!(body.len(), 0);
     }
 
+    #[test]
+    fn test_parse_seq_block() {
+        let expr = parse_str("seq { yield 1; 2 .. 4; for x in xs do yield x }").unwrap();
+        let (builder, body) = expr.as_computation_expr().unwrap();
+        assert_eq!(builder, "seq");
+        assert!(matches!(body[0], CEStatement::Yield { .. }));
+        // A bare range yields each of its values
+        assert!(matches!(&body[1], CEStatement::YieldBang { value } if value.is_range()));
+        assert!(matches!(&body[2], CEStatement::Expr { value } if value.is_for()));
+        // A seq inside a list does not make the list a comprehension
+        assert!(parse_str("[seq { for x in xs do yield x }]")
+            .unwrap()
+            .is_list());
+    }
+
+    #[test]
+    fn test_parse_builder_blocks() {
+        let expr = parse_str("option { let! x = Some 1; return x }").unwrap();
+        let (builder, body) = expr.as_computation_expr().unwrap();
+        assert_eq!(builder, "option");
+        assert!(matches!(body[0], CEStatement::LetBang { .. }));
+
+        // A user-defined builder is recognised by its first statement
+        let expr = parse_str("maybe { return 1 }").unwrap();
+        assert_eq!(expr.as_computation_expr().unwrap().0, "maybe");
+
+        // ...otherwise the braces are a record argument
+        assert!(parse_str("f { X = 1 }").unwrap().is_app());
+    }
+
+    #[test]
+    fn test_parse_bitwise_and_modulo_precedence() {
+        // 1 + 2 <<< 3 &&& 7 = 0  =>  (((1 + 2) <<< 3) &&& 7) = 0
+        let expr = parse_str("1 + 2 <<< 3 &&& 7 = 0").unwrap();
+        let Expr::BinOp { op, left, .. } = expr else {
+            panic!("Expected comparison at the top, got {:?}", expr);
+        };
+        assert_eq!(op, BinOp::Eq);
+        let Expr::BinOp { op, left, .. } = *left else {
+            panic!("Expected &&& under the comparison");
+        };
+        assert_eq!(op, BinOp::BitAnd);
+        assert!(matches!(*left, Expr::BinOp { op: BinOp::Shl, .. }));
+
+        let expr = parse_str("a + b % c").unwrap();
+        let Expr::BinOp { op, right, .. } = expr else {
+            panic!("Expected + at the top");
+        };
+        assert_eq!(op, BinOp::Add);
+        assert!(matches!(*right, Expr::BinOp { op: BinOp::Mod, .. }));
+    }
+
+    #[test]
+    fn test_parse_composition_and_backward_pipe() {
+        let expr = parse_str("f >> g << h").unwrap();
+        let Expr::BinOp { op, left, .. } = expr else {
+            panic!("Expected composition to associate to the left");
+        };
+        assert_eq!(op, BinOp::ComposeLeft);
+        let Expr::BinOp { op, .. } = *left else {
+            panic!("Expected f >> g on the left");
+        };
+        assert_eq!(op, BinOp::ComposeRight);
+
+        // f <| x + 1  =>  f (x + 1)
+        let expr = parse_str("f <| x + 1").unwrap();
+        let Expr::App { func, arg } = expr else {
+            panic!("Expected application");
+        };
+        assert_eq!(*func, Expr::Var("f".to_string()));
+        assert!(matches!(*arg, Expr::BinOp { op: BinOp::Add, .. }));
+
+        // Composition binds tighter than the pipes
+        let expr = parse_str("x |> (f >> g) |> h <| y").unwrap();
+        assert!(expr.is_app());
+        assert!(parse_str("f >> g <| x").unwrap().is_app());
+    }
+
+    #[test]
+    fn test_parse_nested_type_arguments_close_with_shift_tokens() {
+        assert!(parse_str("let x: Option<List<int>> = None in x").is_ok());
+        assert!(parse_str("let x: Result<int, Option<List<int>>> = Ok 1 in x").is_ok());
+        assert!(parse_str("fun (m: Map<string, List<int>>) -> m").is_ok());
+    }
+
     // ========================================================================
     // Type Provider Tests
     // ========================================================================
@@ -2473,6 +4214,212 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_generic_record_type_def() {
+        let program = parse_program_str(
+            "type Page<'t> = { Items: 't list; Next: string option; Pos: int * int }\nlet p = 1",
+        )
+        .unwrap();
+        assert_eq!(program.items.len(), 2);
+
+        match &program.items[0] {
+            ModuleItem::TypeDef(TypeDefinition::Record(record)) => {
+                assert_eq!(record.name, "Page");
+                assert_eq!(record.type_params, vec!["t".to_string()]);
+                assert_eq!(
+                    record.fields[0].1,
+                    TypeExpr::App("list".to_string(), vec![TypeExpr::Var("t".to_string())])
+                );
+                assert_eq!(
+                    format!("{}", record),
+                    "type Page<'t> = { Items: 't list; Next: string option; Pos: int * int }"
+                );
+            }
+            _ => panic!("Expected record type definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_type_members() {
+        let program = parse_program_str(
+            "type V = { X: int }\nwith\n  static member (+) (a: V, b: V) = { X = a.X + b.X }\n  static member ( * ) (v: V) (k: int) = v\n  static member Compare (a, b) = a.X - b.X\nlet z = 1",
+        )
+        .unwrap();
+        assert_eq!(program.items.len(), 2);
+        let ModuleItem::TypeDef(TypeDefinition::Record(record)) = &program.items[0] else {
+            panic!("Expected record type definition");
+        };
+        let names: Vec<&str> = record.members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["op_Addition", "op_Multiply", "Compare"]);
+        // Tupled parameters are still curried
+        let Expr::Lambda { param, body, .. } = &record.members[0].value else {
+            panic!("Expected a lambda for (+)");
+        };
+        assert_eq!(param, "a");
+        assert!(matches!(&**body, Expr::Lambda { param, .. } if param == "b"));
+
+        let program =
+            parse_program_str("type M = M of int with static member (%) (a, b) = a").unwrap();
+        let ModuleItem::TypeDef(TypeDefinition::Du(du)) = &program.items[0] else {
+            panic!("Expected union type definition");
+        };
+        assert_eq!(du.members[0].name, "op_Modulus");
+
+        assert!(parse_program_str("type M = M of int with static member (&&) (a, b) = a").is_err());
+        assert!(parse_program_str("type M = M of int with member Value = 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_instance_members() {
+        let program = parse_program_str(
+            "type Box<'a> = Box of 'a\nwith\n  member this.Get() = match this with | Box x -> x\n  member _.Kind = \"box\"\n  static member Empty = 0\n  static member Make(x) = Box x\nBox.Make(1).Get()",
+        )
+        .unwrap();
+        let ModuleItem::TypeDef(TypeDefinition::Du(du)) = &program.items[0] else {
+            panic!("Expected union type definition");
+        };
+        let kinds: Vec<(&str, bool)> = du
+            .members
+            .iter()
+            .map(|m| (m.name.as_str(), m.is_static()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("Get", false),
+                ("Kind", false),
+                ("Empty", true),
+                ("Make", true)
+            ]
+        );
+        assert_eq!(du.members[1].self_name.as_deref(), Some("_"));
+
+        // The receiver comes first, typed as the declaring type, then `()` as unit
+        let Expr::Lambda {
+            param,
+            param_ty,
+            body,
+            ..
+        } = &du.members[0].value
+        else {
+            panic!("Expected a lambda for Get");
+        };
+        assert_eq!(param, "this");
+        assert_eq!(
+            param_ty.as_ref().map(|ty| &ty.ty),
+            Some(&TypeExpr::App(
+                "Box".to_string(),
+                vec![TypeExpr::Var("a".to_string())]
+            ))
+        );
+        assert!(matches!(&**body, Expr::Lambda { param_ty: Some(ty), .. }
+            if ty.ty == TypeExpr::Named("unit".to_string())));
+
+        // A static call on the type is a method call, not module access
+        let Some(Expr::MethodCall {
+            receiver,
+            method_name,
+            args,
+        }) = &program.main_expr
+        else {
+            panic!("Expected a method call");
+        };
+        assert_eq!(method_name, "Get");
+        assert!(args.is_empty());
+        assert!(
+            matches!(&**receiver, Expr::MethodCall { method_name, .. } if method_name == "Make")
+        );
+    }
+
+    #[test]
+    fn test_parse_units_of_measure() {
+        let program = parse_program_str(
+            "[<Measure>] type m\n[<Measure>] type N = kg m / s^2\nlet g : float<m/s^2> = 9.81<m s^-2>\nif 0 < 1 then g else 1.0<1/s>",
+        )
+        .unwrap();
+        let ModuleItem::TypeDef(TypeDefinition::Measure(base)) = &program.items[0] else {
+            panic!("Expected a unit of measure");
+        };
+        assert_eq!(base.to_string(), "[<Measure>] type m");
+        let ModuleItem::TypeDef(TypeDefinition::Measure(newton)) = &program.items[1] else {
+            panic!("Expected a unit of measure");
+        };
+        assert_eq!(newton.definition.as_ref().unwrap().to_string(), "kg m/s^2");
+
+        // The annotation and the literal write the same measure
+        let ModuleItem::Let(
+            Some(_),
+            Expr::Let {
+                ty: Some(ty),
+                value,
+                ..
+            },
+        ) = &program.items[2]
+        else {
+            panic!("Expected an annotated binding");
+        };
+        assert_eq!(ty.ty.to_string(), "float<m/s^2>");
+        assert_eq!(value.to_string(), "9.81<m/s^2>");
+
+        // `0 < 1` is still a comparison
+        let Some(Expr::If {
+            cond, else_branch, ..
+        }) = &program.main_expr
+        else {
+            panic!("Expected an if expression");
+        };
+        assert!(matches!(&**cond, Expr::BinOp { op: BinOp::Lt, .. }));
+        assert_eq!(else_branch.to_string(), "1<1/s>");
+    }
+
+    #[test]
+    fn test_parse_access_modifiers() {
+        let program = parse_program_str(
+            "module Lib =\n    let private helper x = x\n    let rec internal loop n = loop n\n    let api x = helper x\n\n0",
+        )
+        .unwrap();
+        let module = &program.modules[0];
+        assert_eq!(module.items.len(), 3);
+        assert_eq!(module.access_of("helper"), Access::Private);
+        assert_eq!(module.access_of("loop"), Access::Internal);
+        assert_eq!(module.access_of("api"), Access::Public);
+
+        // Modifiers only apply to module bindings
+        assert!(parse_program_str("let private x = 1 in x").is_err());
+    }
+
+    #[test]
+    fn test_parse_record_type_fields_on_separate_lines() {
+        let program = parse_program_str(
+            "type TabInfo =\n  { Title: string\n    Index: int\n    Tags: string list }\n",
+        )
+        .unwrap();
+        match &program.items[0] {
+            ModuleItem::TypeDef(TypeDefinition::Record(record)) => {
+                assert!(record.type_params.is_empty());
+                assert_eq!(record.fields.len(), 3);
+                assert_eq!(record.fields[2].0, "Tags");
+            }
+            _ => panic!("Expected record type definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_postfix_type_stops_at_line_end() {
+        // The type ends the line; the next line's identifier is the main expression
+        let program = parse_program_str("type Wrap = Wrap of int\nresult").unwrap();
+        match &program.items[0] {
+            ModuleItem::TypeDef(TypeDefinition::Du(du)) => {
+                assert_eq!(
+                    du.variants[0].fields,
+                    vec![TypeExpr::Named("int".to_string())]
+                );
+            }
+            _ => panic!("Expected DU type definition"),
+        }
+        assert_eq!(program.main_expr, Some(Expr::Var("result".to_string())));
+    }
+
     #[test]
     fn test_parse_mixed_types() {
         // Parse both DU and type provider in same program
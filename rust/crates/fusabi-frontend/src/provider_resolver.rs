@@ -16,7 +16,7 @@ use crate::ast::{
     DuTypeDef, RecordTypeDef, TypeDefinition as AstTypeDef, TypeExpr as AstTypeExpr,
     TypeProviderDecl, VariantDef as AstVariantDef,
 };
-use crate::types::{Type, TypeEnv, TypeScheme};
+use crate::types::{Type, TypeEnv, TypeScheme, TypeVar};
 use fusabi_type_providers::{
     GeneratedTypes, ProviderParams, ProviderRegistry, TypeDefinition as ProviderTypeDef,
     TypeExpr as ProviderTypeExpr, TypeProvider,
@@ -170,7 +170,9 @@ impl ProviderResolver {
 
                 Ok(AstTypeDef::Record(RecordTypeDef {
                     name: record.name.clone(),
+                    type_params: vec![],
                     fields,
+                    members: vec![],
                 }))
             }
             ProviderTypeDef::Du(du) => {
@@ -185,7 +187,9 @@ impl ProviderResolver {
 
                 Ok(AstTypeDef::Du(DuTypeDef {
                     name: du.name.clone(),
+                    type_params: vec![],
                     variants,
+                    members: vec![],
                 }))
             }
         }
@@ -202,10 +206,18 @@ impl ProviderResolver {
                 Box::new(self.convert_type_expr(param)),
                 Box::new(self.convert_type_expr(ret)),
             ),
+            ProviderTypeExpr::Var(name) => AstTypeExpr::Var(name.clone()),
+            ProviderTypeExpr::App(name, args) => AstTypeExpr::App(
+                name.clone(),
+                args.iter().map(|t| self.convert_type_expr(t)).collect(),
+            ),
         }
     }
 
     /// Generate TypeSchemes from AST type definitions for TypeEnv injection
+    ///
+    /// Generic definitions produce schemes quantified over their type
+    /// parameters, so each use of `Tree<'a>` or its cases gets fresh variables.
     fn generate_type_schemes(&self, types: &[AstTypeDef]) -> HashMap<String, TypeScheme> {
         let mut schemes = HashMap::new();
 
@@ -213,73 +225,98 @@ impl ProviderResolver {
             match type_def {
                 AstTypeDef::Record(record) => {
                     // Record type is represented as a HashMap of field types
+                    let mut vars = Self::type_param_vars(&record.type_params);
                     let fields: HashMap<String, Type> = record
                         .fields
                         .iter()
-                        .map(|(name, ty)| (name.clone(), self.ast_type_to_type(ty)))
+                        .map(|(name, ty)| (name.clone(), self.ast_type_to_type(ty, &mut vars)))
                         .collect();
-                    let scheme = TypeScheme::mono(Type::Record(fields));
+                    let scheme = Self::quantify(vars, Type::Record(fields));
                     schemes.insert(record.name.clone(), scheme);
                 }
                 AstTypeDef::Du(du) => {
-                    // DU type - register the type as a Variant with empty type params
-                    let scheme = TypeScheme::mono(Type::Variant(du.name.clone(), vec![]));
-                    schemes.insert(du.name.clone(), scheme);
+                    // DU type - a Variant applied to its own type parameters
+                    let vars = Self::type_param_vars(&du.type_params);
+                    let du_type = Type::Variant(
+                        du.name.clone(),
+                        du.type_params
+                            .iter()
+                            .map(|param| Type::Var(vars[param].clone()))
+                            .collect(),
+                    );
+                    schemes.insert(
+                        du.name.clone(),
+                        Self::quantify(vars.clone(), du_type.clone()),
+                    );
 
                     // Register variant constructors
                     for variant in &du.variants {
-                        let du_type = Type::Variant(du.name.clone(), vec![]);
+                        let mut variant_vars = vars.clone();
                         let variant_type = if variant.fields.is_empty() {
-                            du_type
+                            du_type.clone()
                         } else if variant.fields.len() == 1 {
                             Type::Function(
-                                Box::new(self.ast_type_to_type(&variant.fields[0])),
-                                Box::new(du_type),
+                                Box::new(
+                                    self.ast_type_to_type(&variant.fields[0], &mut variant_vars),
+                                ),
+                                Box::new(du_type.clone()),
                             )
                         } else {
                             // Multi-field variant: (t1 * t2 * ...) -> Type
                             let tuple_fields: Vec<Type> = variant
                                 .fields
                                 .iter()
-                                .map(|f| self.ast_type_to_type(f))
+                                .map(|f| self.ast_type_to_type(f, &mut variant_vars))
                                 .collect();
                             Type::Function(
                                 Box::new(Type::Tuple(tuple_fields)),
-                                Box::new(du_type),
+                                Box::new(du_type.clone()),
                             )
                         };
-                        schemes.insert(variant.name.clone(), TypeScheme::mono(variant_type));
+                        schemes.insert(
+                            variant.name.clone(),
+                            Self::quantify(variant_vars, variant_type),
+                        );
                     }
                 }
                 AstTypeDef::Provider(_) => {
                     // Provider declarations should already be resolved
                 }
+                AstTypeDef::Measure(_) => {
+                    // Units of measure only exist for type inference
+                }
             }
         }
 
         schemes
     }
 
-    /// Convert AST type expression to inference Type
-    fn ast_type_to_type(&self, ty: &AstTypeExpr) -> Type {
-        match ty {
-            AstTypeExpr::Named(name) => match name.as_str() {
-                "int" => Type::Int,
-                "float" => Type::Float,
-                "bool" => Type::Bool,
-                "string" => Type::String,
-                "unit" => Type::Unit,
-                // Custom types are represented as Variant with empty type params
-                _ => Type::Variant(name.clone(), vec![]),
-            },
-            AstTypeExpr::Tuple(types) => {
-                Type::Tuple(types.iter().map(|t| self.ast_type_to_type(t)).collect())
-            }
-            AstTypeExpr::Function(param, ret) => Type::Function(
-                Box::new(self.ast_type_to_type(param)),
-                Box::new(self.ast_type_to_type(ret)),
-            ),
+    /// Number the type parameters of a definition as the variables 0..n
+    fn type_param_vars(params: &[String]) -> HashMap<String, TypeVar> {
+        params
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (name.clone(), TypeVar::new(id, name.clone())))
+            .collect()
+    }
+
+    /// Build a scheme quantified over the given variables (monomorphic if there are none)
+    fn quantify(vars: HashMap<String, TypeVar>, ty: Type) -> TypeScheme {
+        if vars.is_empty() {
+            return TypeScheme::mono(ty);
         }
+        let mut vars: Vec<TypeVar> = vars.into_values().collect();
+        vars.sort_by_key(|var| var.id);
+        TypeScheme::poly(vars, ty)
+    }
+
+    /// Convert AST type expression to inference Type, resolving type variables through `vars`
+    fn ast_type_to_type(&self, ty: &AstTypeExpr, vars: &mut HashMap<String, TypeVar>) -> Type {
+        let mut next_id = vars.len();
+        Type::from_type_expr(ty, vars, &mut || {
+            next_id += 1;
+            TypeVar::fresh(next_id - 1)
+        })
     }
 
     /// Inject resolved types into a TypeEnv
@@ -335,15 +372,18 @@ mod tests {
         let resolver = ProviderResolver::new();
 
         assert!(matches!(
-            resolver.ast_type_to_type(&AstTypeExpr::Named("int".to_string())),
+            resolver.ast_type_to_type(&AstTypeExpr::Named("int".to_string()), &mut HashMap::new()),
             Type::Int
         ));
         assert!(matches!(
-            resolver.ast_type_to_type(&AstTypeExpr::Named("string".to_string())),
+            resolver.ast_type_to_type(
+                &AstTypeExpr::Named("string".to_string()),
+                &mut HashMap::new()
+            ),
             Type::String
         ));
         assert!(matches!(
-            resolver.ast_type_to_type(&AstTypeExpr::Named("bool".to_string())),
+            resolver.ast_type_to_type(&AstTypeExpr::Named("bool".to_string()), &mut HashMap::new()),
             Type::Bool
         ));
     }
@@ -353,7 +393,82 @@ mod tests {
         let resolver = ProviderResolver::new();
 
         // Custom types become Variant types
-        let ty = resolver.ast_type_to_type(&AstTypeExpr::Named("Person".to_string()));
+        let ty = resolver.ast_type_to_type(
+            &AstTypeExpr::Named("Person".to_string()),
+            &mut HashMap::new(),
+        );
         assert!(matches!(ty, Type::Variant(name, params) if name == "Person" && params.is_empty()));
     }
+
+    #[test]
+    fn test_type_conversion_generics() {
+        let resolver = ProviderResolver::new();
+
+        let list = ProviderTypeExpr::App(
+            "list".to_string(),
+            vec![ProviderTypeExpr::Var("t".to_string())],
+        );
+        let converted = resolver.convert_type_expr(&list);
+        assert_eq!(
+            converted,
+            AstTypeExpr::App("list".to_string(), vec![AstTypeExpr::Var("t".to_string())])
+        );
+        assert_eq!(list.to_string(), "'t list");
+    }
+
+    #[test]
+    fn test_generic_du_schemes_are_polymorphic() {
+        let resolver = ProviderResolver::new();
+        let a = || AstTypeExpr::Var("a".to_string());
+        let tree = || AstTypeExpr::App("Tree".to_string(), vec![a()]);
+        let du = AstTypeDef::Du(DuTypeDef {
+            name: "Tree".to_string(),
+            type_params: vec!["a".to_string()],
+            variants: vec![
+                AstVariantDef::new_simple("Leaf".to_string()),
+                AstVariantDef::new("Node".to_string(), vec![tree(), a(), tree()]),
+            ],
+            members: vec![],
+        });
+
+        let schemes = resolver.generate_type_schemes(&[du]);
+        let var_a = Type::Var(TypeVar::new(0, "a"));
+        let tree_a = Type::Variant("Tree".to_string(), vec![var_a.clone()]);
+
+        let leaf = &schemes["Leaf"];
+        assert_eq!(leaf.vars, vec![TypeVar::new(0, "a")]);
+        assert_eq!(leaf.ty, tree_a);
+
+        let node = &schemes["Node"];
+        assert_eq!(node.vars.len(), 1);
+        assert_eq!(
+            node.ty,
+            Type::Function(
+                Box::new(Type::Tuple(vec![tree_a.clone(), var_a, tree_a.clone()])),
+                Box::new(tree_a),
+            )
+        );
+    }
+
+    #[test]
+    fn test_generic_record_scheme() {
+        let resolver = ProviderResolver::new();
+        let record = AstTypeDef::Record(RecordTypeDef {
+            name: "Page".to_string(),
+            type_params: vec!["t".to_string()],
+            fields: vec![(
+                "Items".to_string(),
+                AstTypeExpr::App("list".to_string(), vec![AstTypeExpr::Var("t".to_string())]),
+            )],
+            members: vec![],
+        });
+
+        let schemes = resolver.generate_type_schemes(&[record]);
+        let page = &schemes["Page"];
+        assert!(!page.is_mono());
+        assert!(matches!(
+            &page.ty,
+            Type::Record(fields) if fields["Items"] == Type::List(Box::new(Type::Var(TypeVar::new(0, "t"))))
+        ));
+    }
 }
@@ -30,7 +30,8 @@
 //! assert_eq!(result, Type::Int);
 //! ```
 
-use std::collections::{HashMap, HashSet};
+use crate::ast::{fmt_measure, TypeExpr};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -39,7 +40,7 @@ use std::sync::Arc;
 ///
 /// Type variables represent unknown types during type inference.
 /// Examples: 'a, 'b, 't1, 't2
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct TypeVar {
     /// Unique identifier for this type variable
     pub id: usize,
@@ -71,6 +72,164 @@ impl fmt::Display for TypeVar {
     }
 }
 
+/// A unit a measure is built from: a declared unit such as `m`, or a unit
+/// variable standing for any measure.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MeasureUnit {
+    /// A unit declared with `[<Measure>] type`
+    Named(String),
+    /// A unit variable (e.g., 'u)
+    Var(TypeVar),
+}
+
+impl fmt::Display for MeasureUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeasureUnit::Named(name) => write!(f, "{}", name),
+            MeasureUnit::Var(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// A unit of measure: a product of units raised to non-zero integer powers.
+///
+/// Measures form an abelian group, so `m/s * s` is `m` and the empty product
+/// is the dimensionless measure `1`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Measure {
+    units: BTreeMap<MeasureUnit, i32>,
+}
+
+impl Measure {
+    /// The dimensionless measure `1`.
+    pub fn one() -> Self {
+        Measure::default()
+    }
+
+    /// A single declared unit.
+    pub fn unit(name: impl Into<String>) -> Self {
+        Measure::factor(MeasureUnit::Named(name.into()), 1)
+    }
+
+    /// A single unit variable.
+    pub fn var(var: TypeVar) -> Self {
+        Measure::factor(MeasureUnit::Var(var), 1)
+    }
+
+    /// A single unit raised to a power.
+    pub fn factor(unit: MeasureUnit, exp: i32) -> Self {
+        let mut measure = Measure::one();
+        measure.multiply_factor(unit, exp);
+        measure
+    }
+
+    fn multiply_factor(&mut self, unit: MeasureUnit, exp: i32) {
+        let total = self.units.get(&unit).copied().unwrap_or(0) + exp;
+        if total == 0 {
+            self.units.remove(&unit);
+        } else {
+            self.units.insert(unit, total);
+        }
+    }
+
+    /// Whether this is the dimensionless measure.
+    pub fn is_one(&self) -> bool {
+        self.units.is_empty()
+    }
+
+    /// The units of the measure with their exponents.
+    pub fn units(&self) -> impl Iterator<Item = (&MeasureUnit, i32)> {
+        self.units.iter().map(|(unit, exp)| (unit, *exp))
+    }
+
+    /// The product of two measures.
+    pub fn product(&self, other: &Measure) -> Measure {
+        let mut result = self.clone();
+        for (unit, exp) in other.units() {
+            result.multiply_factor(unit.clone(), exp);
+        }
+        result
+    }
+
+    /// The quotient of two measures.
+    pub fn quotient(&self, other: &Measure) -> Measure {
+        self.product(&other.pow(-1))
+    }
+
+    /// The measure raised to an integer power.
+    pub fn pow(&self, n: i32) -> Measure {
+        let mut result = Measure::one();
+        for (unit, exp) in self.units() {
+            result.multiply_factor(unit.clone(), exp * n);
+        }
+        result
+    }
+
+    /// The unit variables occurring in the measure.
+    pub fn free_vars(&self) -> HashSet<TypeVar> {
+        self.units
+            .keys()
+            .filter_map(|unit| match unit {
+                MeasureUnit::Var(v) => Some(v.clone()),
+                MeasureUnit::Named(_) => None,
+            })
+            .collect()
+    }
+
+    /// Apply a substitution to the unit variables of the measure.
+    ///
+    /// A unit variable is bound either to a measure or, when a scheme is
+    /// instantiated, to another variable.
+    pub fn apply(&self, subst: &Substitution) -> Measure {
+        let mut result = Measure::one();
+        for (unit, exp) in self.units() {
+            let replacement = match unit {
+                MeasureUnit::Var(v) => match subst.lookup(v) {
+                    Some(Type::Measure(m)) => m,
+                    Some(Type::Var(w)) => Measure::var(w),
+                    _ => Measure::var(v.clone()),
+                },
+                MeasureUnit::Named(_) => Measure::factor(unit.clone(), 1),
+            };
+            result = result.product(&replacement.pow(exp));
+        }
+        result
+    }
+
+    /// Convert a written measure to a measure.
+    ///
+    /// Unit variables are shared through `vars` as in [`Type::from_type_expr`].
+    pub fn from_type_expr(
+        expr: &TypeExpr,
+        vars: &mut HashMap<String, TypeVar>,
+        fresh_var: &mut impl FnMut() -> TypeVar,
+    ) -> Measure {
+        match expr {
+            TypeExpr::Named(name) => Measure::unit(name.clone()),
+            TypeExpr::Var(name) => {
+                let var = vars.entry(name.clone()).or_insert_with(|| {
+                    let fresh = fresh_var();
+                    TypeVar::new(fresh.id, name.clone())
+                });
+                Measure::var(var.clone())
+            }
+            TypeExpr::Measure(factors) => {
+                factors.iter().fold(Measure::one(), |acc, (factor, exp)| {
+                    acc.product(&Measure::from_type_expr(factor, vars, fresh_var).pow(*exp))
+                })
+            }
+            _ => Measure::one(),
+        }
+    }
+}
+
+impl fmt::Display for Measure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let factors: Vec<_> = self.units().collect();
+        fmt_measure(f, &factors)
+    }
+}
+
 /// Core type representation.
 ///
 /// Represents all types in the Fusabi type system, including:
@@ -93,6 +252,12 @@ pub enum Type {
     /// String type
     String,
 
+    /// Character type
+    Char,
+
+    /// Byte buffer type (byte[])
+    Bytes,
+
     /// Unit type ()
     Unit,
 
@@ -114,8 +279,18 @@ pub enum Type {
     /// Record type with named fields
     Record(HashMap<String, Type>),
 
+    /// Anonymous record type (e.g., {| Name: string |}), with a row variable
+    /// standing for further fields when the record is only known to have these
+    AnonRecord(HashMap<String, Type>, Option<TypeVar>),
+
     /// Discriminated union variant (type name, type parameters)
     Variant(String, Vec<Type>),
+
+    /// Int or float carrying a unit of measure other than `1` (e.g., float<m/s>)
+    Measured(Box<Type>, Measure),
+
+    /// A unit of measure, the type a unit variable is bound to
+    Measure(Measure),
 }
 
 impl Type {
@@ -130,7 +305,13 @@ impl Type {
                 set.insert(v.clone());
                 set
             }
-            Type::Int | Type::Bool | Type::String | Type::Unit | Type::Float => HashSet::new(),
+            Type::Int
+            | Type::Bool
+            | Type::String
+            | Type::Char
+            | Type::Bytes
+            | Type::Unit
+            | Type::Float => HashSet::new(),
             Type::Tuple(types) => types.iter().flat_map(|t| t.free_vars()).collect(),
             Type::List(t) | Type::Array(t) => t.free_vars(),
             Type::Function(arg, ret) => {
@@ -139,7 +320,18 @@ impl Type {
                 set
             }
             Type::Record(fields) => fields.values().flat_map(|t| t.free_vars()).collect(),
+            Type::AnonRecord(fields, rest) => fields
+                .values()
+                .flat_map(|t| t.free_vars())
+                .chain(rest.clone())
+                .collect(),
             Type::Variant(_, params) => params.iter().flat_map(|t| t.free_vars()).collect(),
+            Type::Measured(base, measure) => {
+                let mut set = base.free_vars();
+                set.extend(measure.free_vars());
+                set
+            }
+            Type::Measure(measure) => measure.free_vars(),
         }
     }
 
@@ -149,7 +341,13 @@ impl Type {
     pub fn apply(&self, subst: &Substitution) -> Type {
         match self {
             Type::Var(v) => subst.lookup(v).unwrap_or_else(|| self.clone()),
-            Type::Int | Type::Bool | Type::String | Type::Unit | Type::Float => self.clone(),
+            Type::Int
+            | Type::Bool
+            | Type::String
+            | Type::Char
+            | Type::Bytes
+            | Type::Unit
+            | Type::Float => self.clone(),
             Type::Tuple(types) => Type::Tuple(types.iter().map(|t| t.apply(subst)).collect()),
             Type::List(t) => Type::List(Box::new(t.apply(subst))),
             Type::Array(t) => Type::Array(Box::new(t.apply(subst))),
@@ -162,10 +360,34 @@ impl Type {
                     .map(|(name, ty)| (name.clone(), ty.apply(subst)))
                     .collect(),
             ),
+            Type::AnonRecord(fields, rest) => {
+                let mut fields: HashMap<String, Type> = fields
+                    .iter()
+                    .map(|(name, ty)| (name.clone(), ty.apply(subst)))
+                    .collect();
+                // A bound row variable contributes its fields and decides how
+                // the row ends: still open, closed, or a declared record
+                match rest.as_ref().and_then(|var| subst.lookup(var)) {
+                    Some(Type::AnonRecord(more, tail)) => {
+                        fields.extend(more);
+                        Type::AnonRecord(fields, tail)
+                    }
+                    Some(Type::Record(more)) => {
+                        fields.extend(more);
+                        Type::Record(fields)
+                    }
+                    Some(Type::Var(var)) => Type::AnonRecord(fields, Some(var)),
+                    _ => Type::AnonRecord(fields, rest.clone()),
+                }
+            }
             Type::Variant(name, params) => Type::Variant(
                 name.clone(),
                 params.iter().map(|t| t.apply(subst)).collect(),
             ),
+            Type::Measured(base, measure) => {
+                Type::measured(base.apply(subst), measure.apply(subst))
+            }
+            Type::Measure(measure) => Type::Measure(measure.apply(subst)),
         }
     }
 
@@ -176,12 +398,45 @@ impl Type {
     pub fn occurs_check(&self, var: &TypeVar) -> bool {
         match self {
             Type::Var(v) => v == var,
-            Type::Int | Type::Bool | Type::String | Type::Unit | Type::Float => false,
+            Type::Int
+            | Type::Bool
+            | Type::String
+            | Type::Char
+            | Type::Bytes
+            | Type::Unit
+            | Type::Float => false,
             Type::Tuple(types) => types.iter().any(|t| t.occurs_check(var)),
             Type::List(t) | Type::Array(t) => t.occurs_check(var),
             Type::Function(arg, ret) => arg.occurs_check(var) || ret.occurs_check(var),
             Type::Record(fields) => fields.values().any(|t| t.occurs_check(var)),
+            Type::AnonRecord(fields, rest) => {
+                rest.as_ref() == Some(var) || fields.values().any(|t| t.occurs_check(var))
+            }
             Type::Variant(_, params) => params.iter().any(|t| t.occurs_check(var)),
+            Type::Measured(base, measure) => {
+                base.occurs_check(var) || measure.free_vars().contains(var)
+            }
+            Type::Measure(measure) => measure.free_vars().contains(var),
+        }
+    }
+
+    /// An int or float type with a unit of measure, which is just the base
+    /// type when the measure is `1`.
+    pub fn measured(base: Type, measure: Measure) -> Type {
+        match base {
+            _ if measure.is_one() => base,
+            Type::Measured(base, inner) => Type::measured(*base, inner.product(&measure)),
+            base => Type::Measured(Box::new(base), measure),
+        }
+    }
+
+    /// The base type and unit of measure of a number type; unmeasured ints and
+    /// floats have measure `1`.
+    pub fn as_measured(&self) -> Option<(&Type, Measure)> {
+        match self {
+            Type::Int | Type::Float => Some((self, Measure::one())),
+            Type::Measured(base, measure) => Some((base, measure.clone())),
+            _ => None,
         }
     }
 
@@ -194,6 +449,75 @@ impl Type {
             Type::Function(Box::new(arg.clone()), Box::new(acc))
         })
     }
+
+    /// Convert a written type expression to a type.
+    ///
+    /// `vars` maps type variable names (without the quote) to the variables
+    /// they stand for, typically a definition's type parameters. A variable
+    /// that is not in the map gets a fresh one, which is added to the map so
+    /// repeated uses of the same name agree.
+    ///
+    /// `T list`, `T array` and `T option` map to the built-in list, array and
+    /// `Option` types, and `int<m>` and `float<m>` to numbers with a unit of
+    /// measure; other names are user-defined unions or records.
+    pub fn from_type_expr(
+        expr: &TypeExpr,
+        vars: &mut HashMap<String, TypeVar>,
+        fresh_var: &mut impl FnMut() -> TypeVar,
+    ) -> Type {
+        match expr {
+            TypeExpr::Named(name) => match name.as_str() {
+                "int" => Type::Int,
+                "float" => Type::Float,
+                "bool" => Type::Bool,
+                "string" => Type::String,
+                "char" => Type::Char,
+                "bytes" => Type::Bytes,
+                "unit" => Type::Unit,
+                _ => Type::Variant(name.clone(), vec![]),
+            },
+            TypeExpr::Var(name) => {
+                let var = vars.entry(name.clone()).or_insert_with(|| {
+                    let fresh = fresh_var();
+                    TypeVar::new(fresh.id, name.clone())
+                });
+                Type::Var(var.clone())
+            }
+            TypeExpr::Tuple(types) => Type::Tuple(
+                types
+                    .iter()
+                    .map(|t| Type::from_type_expr(t, vars, fresh_var))
+                    .collect(),
+            ),
+            TypeExpr::Function(param, ret) => Type::Function(
+                Box::new(Type::from_type_expr(param, vars, fresh_var)),
+                Box::new(Type::from_type_expr(ret, vars, fresh_var)),
+            ),
+            TypeExpr::App(name, args)
+                if matches!(name.as_str(), "int" | "float")
+                    && matches!(args.as_slice(), [TypeExpr::Measure(_)]) =>
+            {
+                let base = Type::from_type_expr(&TypeExpr::Named(name.clone()), vars, fresh_var);
+                Type::measured(base, Measure::from_type_expr(&args[0], vars, fresh_var))
+            }
+            TypeExpr::App(name, args) => {
+                let mut args: Vec<Type> = args
+                    .iter()
+                    .map(|t| Type::from_type_expr(t, vars, fresh_var))
+                    .collect();
+                match (name.as_str(), args.len()) {
+                    ("list", 1) => Type::List(Box::new(args.remove(0))),
+                    ("array", 1) if args[0] == Type::Variant("byte".to_string(), vec![]) => {
+                        Type::Bytes
+                    }
+                    ("array", 1) => Type::Array(Box::new(args.remove(0))),
+                    ("option", 1) => Type::Variant("Option".to_string(), args),
+                    _ => Type::Variant(name.clone(), args),
+                }
+            }
+            TypeExpr::Measure(_) => Type::Measure(Measure::from_type_expr(expr, vars, fresh_var)),
+        }
+    }
 }
 
 impl fmt::Display for Type {
@@ -203,6 +527,8 @@ impl fmt::Display for Type {
             Type::Int => write!(f, "int"),
             Type::Bool => write!(f, "bool"),
             Type::String => write!(f, "string"),
+            Type::Char => write!(f, "char"),
+            Type::Bytes => write!(f, "byte[]"),
             Type::Unit => write!(f, "unit"),
             Type::Float => write!(f, "float"),
             Type::Tuple(types) => {
@@ -236,6 +562,18 @@ impl fmt::Display for Type {
                 }
                 write!(f, "}}")
             }
+            Type::AnonRecord(fields, rest) => {
+                write!(f, "{{|")?;
+                let mut first = true;
+                for (name, ty) in fields {
+                    write!(f, "{} {}: {}", if first { "" } else { ";" }, name, ty)?;
+                    first = false;
+                }
+                if rest.is_some() {
+                    write!(f, "{} ..", if first { "" } else { ";" })?;
+                }
+                write!(f, " |}}")
+            }
             Type::Variant(name, params) if params.is_empty() => write!(f, "{}", name),
             Type::Variant(name, params) => {
                 write!(f, "{}<", name)?;
@@ -247,6 +585,8 @@ impl fmt::Display for Type {
                 }
                 write!(f, ">")
             }
+            Type::Measured(base, measure) => write!(f, "{}<{}>", base, measure),
+            Type::Measure(measure) => write!(f, "{}", measure),
         }
     }
 }
@@ -679,6 +1019,56 @@ mod tests {
         assert_eq!(format!("{}", ty), "(int -> bool) -> string");
     }
 
+    #[test]
+    fn test_type_from_type_expr() {
+        // Tree<'a> * 'a list -> string option
+        let expr = TypeExpr::Function(
+            Box::new(TypeExpr::Tuple(vec![
+                TypeExpr::App("Tree".to_string(), vec![TypeExpr::Var("a".to_string())]),
+                TypeExpr::App("list".to_string(), vec![TypeExpr::Var("a".to_string())]),
+            ])),
+            Box::new(TypeExpr::App(
+                "option".to_string(),
+                vec![TypeExpr::Named("string".to_string())],
+            )),
+        );
+        let mut vars = HashMap::new();
+        vars.insert("a".to_string(), TypeVar::new(0, "a"));
+        let mut next = 1;
+        let ty = Type::from_type_expr(&expr, &mut vars, &mut || {
+            next += 1;
+            TypeVar::fresh(next)
+        });
+
+        let a = Type::Var(TypeVar::new(0, "a"));
+        assert_eq!(
+            ty,
+            Type::Function(
+                Box::new(Type::Tuple(vec![
+                    Type::Variant("Tree".to_string(), vec![a.clone()]),
+                    Type::List(Box::new(a)),
+                ])),
+                Box::new(Type::Variant("Option".to_string(), vec![Type::String])),
+            )
+        );
+        // No fresh variables were needed
+        assert_eq!(next, 1);
+    }
+
+    #[test]
+    fn test_type_from_type_expr_unbound_var_is_fresh() {
+        let expr = TypeExpr::Tuple(vec![
+            TypeExpr::Var("b".to_string()),
+            TypeExpr::Var("b".to_string()),
+        ]);
+        let mut vars = HashMap::new();
+        let ty = Type::from_type_expr(&expr, &mut vars, &mut || TypeVar::fresh(7));
+
+        let b = Type::Var(TypeVar::new(7, "b"));
+        assert_eq!(ty, Type::Tuple(vec![b.clone(), b]));
+        assert_eq!(vars.len(), 1);
+    }
+
     #[test]
     fn test_type_tuple() {
         let ty = Type::Tuple(vec![Type::Int, Type::Bool, Type::String]);
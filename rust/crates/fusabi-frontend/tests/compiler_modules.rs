@@ -6,14 +6,16 @@
 //! NOTE: Phase 1 limitation - Lambda compilation is not yet fully implemented,
 //! so these tests focus on module structure and simple expressions.
 
-use fusabi_frontend::ast::{BinOp, Expr, Import, Literal, ModuleDef, ModuleItem, Program};
-use fusabi_frontend::compiler::Compiler;
+use fusabi_frontend::ast::{Access, BinOp, Expr, Import, Literal, ModuleDef, ModuleItem, Program};
+use fusabi_frontend::compiler::{CompileError, Compiler};
+use std::collections::HashMap;
 
 /// Helper to create a simple module with a single let binding
 fn make_simple_module(name: &str, binding_name: &str, expr: Expr) -> ModuleDef {
     ModuleDef {
         name: name.to_string(),
         items: vec![ModuleItem::Let(Some(binding_name.to_string()), expr)],
+        access: HashMap::new(),
     }
 }
 
@@ -190,6 +192,7 @@ fn test_compile_module_with_multiple_bindings() {
             ModuleItem::Let(Some("pi".to_string()), Expr::Lit(Literal::Int(3))),
             ModuleItem::Let(Some("e".to_string()), Expr::Lit(Literal::Int(2))),
         ],
+        access: HashMap::new(),
     };
 
     let program = Program {
@@ -216,6 +219,7 @@ fn test_compile_nested_modules() {
     let outer_module = ModuleDef {
         name: "Outer".to_string(),
         items: vec![ModuleItem::Module(Box::new(inner_module))],
+        access: HashMap::new(),
     };
 
     let program = Program {
@@ -243,6 +247,7 @@ fn test_compile_module_with_constant() {
             ModuleItem::Let(Some("pi".to_string()), Expr::Lit(Literal::Int(3))),
             ModuleItem::Let(Some("e".to_string()), Expr::Lit(Literal::Int(2))),
         ],
+        access: HashMap::new(),
     };
 
     let program = Program {
@@ -281,6 +286,50 @@ fn test_compile_program_using_imported_constant() {
     assert!(chunk.constants.contains(&fusabi_vm::value::Value::Int(99)));
 }
 
+#[test]
+fn test_compile_private_binding_used_by_sibling() {
+    // module Secret =
+    //     let private key = 7
+    //     let reveal = key
+    //
+    // open Secret
+    // reveal
+    let secret_module = ModuleDef {
+        name: "Secret".to_string(),
+        items: vec![
+            ModuleItem::Let(Some("key".to_string()), Expr::Lit(Literal::Int(7))),
+            ModuleItem::Let(Some("reveal".to_string()), Expr::Var("key".to_string())),
+        ],
+        access: HashMap::from([("key".to_string(), Access::Private)]),
+    };
+
+    let program = Program {
+        directives: vec![],
+        modules: vec![secret_module.clone()],
+        imports: vec![make_import("Secret")],
+        items: vec![],
+        main_expr: Some(Expr::Var("reveal".to_string())),
+    };
+
+    let chunk = Compiler::compile_program(&program).unwrap();
+    assert!(chunk.constants.contains(&fusabi_vm::value::Value::Int(7)));
+
+    // The private binding itself is not reachable by qualified name
+    let program = Program {
+        directives: vec![],
+        modules: vec![secret_module],
+        imports: vec![],
+        items: vec![],
+        main_expr: Some(Expr::Var("Secret.key".to_string())),
+    };
+
+    let result = Compiler::compile_program(&program);
+    assert_eq!(
+        result.unwrap_err(),
+        CompileError::InaccessibleBinding("Secret.key".to_string(), Access::Private)
+    );
+}
+
 #[test]
 fn test_compile_error_undefined_module() {
     // Reference a module that doesn't exist
@@ -30,17 +30,19 @@ fn test_parse_two_param_function() {
 
     // Verify it's a let expression
     match &expr {
-        Expr::Let { name, value, body } => {
+        Expr::Let {
+            name, value, body, ..
+        } => {
             assert_eq!(name, "add");
 
             // Value should be desugared to nested lambdas: fun x -> fun y -> x + y
             match value.as_ref() {
-                Expr::Lambda { param, body } => {
+                Expr::Lambda { param, body, .. } => {
                     assert_eq!(param, "x");
 
                     // Inner lambda: fun y -> x + y
                     match body.as_ref() {
-                        Expr::Lambda { param, body } => {
+                        Expr::Lambda { param, body, .. } => {
                             assert_eq!(param, "y");
 
                             // Body should be: x + y
@@ -71,11 +73,11 @@ fn test_parse_three_param_function() {
 
             // Should desugar to: fun x -> fun y -> fun z -> x + y + z
             match value.as_ref() {
-                Expr::Lambda { param, body } => {
+                Expr::Lambda { param, body, .. } => {
                     assert_eq!(param, "x");
 
                     match body.as_ref() {
-                        Expr::Lambda { param, body } => {
+                        Expr::Lambda { param, body, .. } => {
                             assert_eq!(param, "y");
 
                             match body.as_ref() {
@@ -107,7 +109,7 @@ fn test_parse_single_param_function_unchanged() {
 
             // Single param should still be a lambda
             match value.as_ref() {
-                Expr::Lambda { param, body } => {
+                Expr::Lambda { param, body, .. } => {
                     assert_eq!(param, "x");
                     assert!(body.is_binop());
                 }
@@ -147,11 +149,11 @@ fn test_parse_zero_param_function_is_value() {
 //
 //     // Should desugar to: fun x -> fun y -> x + y
 //     match &expr {
-//         Expr::Lambda { param, body } => {
+//         Expr::Lambda { param, body, .. } => {
 //             assert_eq!(param, "x");
 //
 //             match body.as_ref() {
-//                 Expr::Lambda { param, body } => {
+//                 Expr::Lambda { param, body, .. } => {
 //                     assert_eq!(param, "y");
 //                     assert!(body.is_binop());
 //                 }
@@ -174,7 +176,7 @@ fn test_parse_zero_param_function_is_value() {
 //
 //     for (i, expected_param) in expected_params.iter().enumerate() {
 //         match current {
-//             Expr::Lambda { param, body } => {
+//             Expr::Lambda { param, body, .. } => {
 //                 assert_eq!(param, expected_param);
 //
 //                 if i < expected_params.len() - 1 {
@@ -227,7 +229,9 @@ fn test_parse_partial_application_simple() {
 
     // Outer let: add
     match &expr {
-        Expr::Let { name, value, body } => {
+        Expr::Let {
+            name, value, body, ..
+        } => {
             assert_eq!(name, "add");
 
             // add should be nested lambdas
@@ -235,7 +239,9 @@ fn test_parse_partial_application_simple() {
 
             // Body is next let
             match body.as_ref() {
-                Expr::Let { name, value, body } => {
+                Expr::Let {
+                    name, value, body, ..
+                } => {
                     assert_eq!(name, "add10");
 
                     // add10 = add 10 (partial application)
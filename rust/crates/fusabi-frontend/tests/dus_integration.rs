@@ -97,6 +97,7 @@ fn test_compile_match_simple_variant() {
                     patterns: vec![],
                 },
                 body: Box::new(Expr::Lit(Literal::Int(0))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Variant {
@@ -104,6 +105,7 @@ fn test_compile_match_simple_variant() {
                     patterns: vec![Pattern::Var("y".to_string())],
                 },
                 body: Box::new(Expr::Var("y".to_string())),
+                guard: None,
             },
         ],
     };
@@ -111,6 +113,7 @@ fn test_compile_match_simple_variant() {
     // This test requires x to be defined
     let expr_with_let = Expr::Let {
         name: "x".to_string(),
+        ty: None,
         value: Box::new(Expr::VariantConstruct {
             type_name: "Option".to_string(),
             variant: "Some".to_string(),
@@ -148,6 +151,7 @@ fn test_compile_match_variant_with_field_binding() {
                     patterns: vec![Pattern::Var("x".to_string())],
                 },
                 body: Box::new(Expr::Var("x".to_string())),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Variant {
@@ -155,6 +159,7 @@ fn test_compile_match_variant_with_field_binding() {
                     patterns: vec![],
                 },
                 body: Box::new(Expr::Lit(Literal::Int(0))),
+                guard: None,
             },
         ],
     };
@@ -191,10 +196,12 @@ fn test_compile_match_variant_multiple_fields() {
                     left: Box::new(Expr::Var("w".to_string())),
                     right: Box::new(Expr::Var("h".to_string())),
                 }),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Int(0))),
+                guard: None,
             },
         ],
     };
@@ -263,10 +270,12 @@ fn test_compile_match_nested_variant_patterns() {
                     }],
                 },
                 body: Box::new(Expr::Var("x".to_string())),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Int(0))),
+                guard: None,
             },
         ],
     };
@@ -308,6 +317,7 @@ fn test_compile_direction_enum() {
                     patterns: vec![],
                 },
                 body: Box::new(Expr::Lit(Literal::Int(1))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Variant {
@@ -315,6 +325,7 @@ fn test_compile_direction_enum() {
                     patterns: vec![],
                 },
                 body: Box::new(Expr::Lit(Literal::Int(2))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Variant {
@@ -322,6 +333,7 @@ fn test_compile_direction_enum() {
                     patterns: vec![],
                 },
                 body: Box::new(Expr::Lit(Literal::Int(3))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Variant {
@@ -329,6 +341,7 @@ fn test_compile_direction_enum() {
                     patterns: vec![],
                 },
                 body: Box::new(Expr::Lit(Literal::Int(4))),
+                guard: None,
             },
         ],
     };
@@ -359,6 +372,7 @@ fn test_compile_variant_in_let() {
     // let opt = Some(42) in opt
     let expr = Expr::Let {
         name: "opt".to_string(),
+        ty: None,
         value: Box::new(Expr::VariantConstruct {
             type_name: "Option".to_string(),
             variant: "Some".to_string(),
@@ -505,6 +519,7 @@ fn test_compile_match_with_wildcard_and_variant() {
     // match x with | Some(42) -> 1 | _ -> 0
     let expr = Expr::Let {
         name: "x".to_string(),
+        ty: None,
         value: Box::new(Expr::VariantConstruct {
             type_name: "Option".to_string(),
             variant: "Some".to_string(),
@@ -519,10 +534,12 @@ fn test_compile_match_with_wildcard_and_variant() {
                         patterns: vec![Pattern::Literal(Literal::Int(42))],
                     },
                     body: Box::new(Expr::Lit(Literal::Int(1))),
+                    guard: None,
                 },
                 MatchArm {
                     pattern: Pattern::Wildcard,
                     body: Box::new(Expr::Lit(Literal::Int(0))),
+                    guard: None,
                 },
             ],
         }),
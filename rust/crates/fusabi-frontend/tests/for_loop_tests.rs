@@ -0,0 +1,104 @@
+//! Integration tests for counted and collection for loops
+//!
+//! Tests the complete flow for for loops: source code → lexer → parser → compiler → bytecode
+
+use fusabi_frontend::compiler::Compiler;
+use fusabi_frontend::lexer::Lexer;
+use fusabi_frontend::parser::Parser;
+use fusabi_vm::instruction::Instruction;
+use fusabi_vm::value::Value;
+
+/// Helper function to run the full pipeline
+fn compile_source(source: &str) -> Result<fusabi_vm::chunk::Chunk, String> {
+    let mut lexer = Lexer::new(source);
+    let tokens = lexer.tokenize().map_err(|e| format!("Lex error: {}", e))?;
+
+    let mut parser = Parser::new(tokens);
+    let ast = parser.parse().map_err(|e| format!("Parse error: {}", e))?;
+
+    let chunk = Compiler::compile(&ast).map_err(|e| format!("Compile error: {}", e))?;
+
+    Ok(chunk)
+}
+
+#[test]
+fn test_for_to_loop_counts_up() {
+    let chunk = compile_source("for i = 1 to 10 do i").unwrap();
+
+    // Bounds are compared with <= and the counter stepped with +1
+    assert!(chunk.instructions.contains(&Instruction::Lte));
+    assert!(chunk.instructions.contains(&Instruction::Add));
+    assert!(chunk.constants.contains(&Value::Unit));
+    assert!(chunk
+        .instructions
+        .iter()
+        .any(|i| matches!(i, Instruction::Jump(offset) if *offset < 0)));
+}
+
+#[test]
+fn test_for_downto_loop_counts_down() {
+    let chunk = compile_source("for i = 10 downto 1 do i").unwrap();
+
+    assert!(chunk.instructions.contains(&Instruction::Gte));
+    assert!(chunk.instructions.contains(&Instruction::Sub));
+    assert!(!chunk.instructions.contains(&Instruction::Add));
+}
+
+#[test]
+fn test_for_in_loop_walks_an_enumerator() {
+    let chunk = compile_source("for x in [1; 2; 3] do x").unwrap();
+
+    assert!(chunk.instructions.contains(&Instruction::GetEnumerator));
+    assert!(chunk
+        .instructions
+        .iter()
+        .any(|i| matches!(i, Instruction::MoveNext(_))));
+    assert!(!chunk.instructions.contains(&Instruction::ToList));
+}
+
+#[test]
+fn test_continue_in_for_loop_jumps_forward_to_step() {
+    let chunk = compile_source("for i = 1 to 3 do continue").unwrap();
+
+    // continue skips the rest of the body but still steps the counter
+    let continue_jump = chunk
+        .instructions
+        .iter()
+        .position(|i| matches!(i, Instruction::Jump(offset) if *offset >= 0))
+        .unwrap();
+    let Instruction::Jump(offset) = chunk.instructions[continue_jump] else {
+        unreachable!()
+    };
+    let target = continue_jump + 1 + offset as usize;
+    assert!(matches!(
+        chunk.instructions[target],
+        Instruction::LoadLocal(_)
+    ));
+    assert!(matches!(chunk.instructions[target + 2], Instruction::Add));
+}
+
+#[test]
+fn test_break_in_for_in_loop() {
+    let chunk = compile_source("for x in [1; 2] do break").unwrap();
+
+    // The break jump lands on the unit result after the loop
+    let break_jump = chunk
+        .instructions
+        .iter()
+        .rposition(|i| matches!(i, Instruction::Jump(offset) if *offset >= 0))
+        .unwrap();
+    let Instruction::Jump(offset) = chunk.instructions[break_jump] else {
+        unreachable!()
+    };
+    let target = break_jump + 1 + offset as usize;
+    assert!(matches!(
+        chunk.instructions[target],
+        Instruction::LoadConst(_)
+    ));
+}
+
+#[test]
+fn test_for_loop_variable_is_not_mutable() {
+    let err = compile_source("for i = 1 to 3 do i <- 5").unwrap_err();
+    assert!(err.contains("Cannot assign to 'i'"));
+}
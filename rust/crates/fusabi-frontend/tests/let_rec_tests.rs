@@ -10,6 +10,8 @@ use fusabi_frontend::ast::{Expr, Literal};
 use fusabi_frontend::compiler::Compiler;
 use fusabi_frontend::lexer::Lexer;
 use fusabi_frontend::parser::Parser;
+use fusabi_vm::instruction::Instruction;
+use fusabi_vm::value::Value;
 
 /// Helper function to parse a string into an AST
 fn parse(input: &str) -> Result<Expr, Box<dyn std::error::Error>> {
@@ -120,6 +122,8 @@ fn test_let_rec_ast_structure() {
         name: "fact".to_string(),
         value: Box::new(Expr::Lambda {
             param: "n".to_string(),
+            param_ty: None,
+            return_ty: None,
             body: Box::new(Expr::Var("n".to_string())),
         }),
         body: Box::new(Expr::Var("fact".to_string())),
@@ -144,6 +148,8 @@ fn test_let_rec_mutual_ast_structure() {
                 "even".to_string(),
                 Expr::Lambda {
                     param: "n".to_string(),
+                    param_ty: None,
+                    return_ty: None,
                     body: Box::new(Expr::Lit(Literal::Bool(true))),
                 },
             ),
@@ -151,6 +157,8 @@ fn test_let_rec_mutual_ast_structure() {
                 "odd".to_string(),
                 Expr::Lambda {
                     param: "n".to_string(),
+                    param_ty: None,
+                    return_ty: None,
                     body: Box::new(Expr::Lit(Literal::Bool(false))),
                 },
             ),
@@ -223,6 +231,46 @@ fn _test_compile_let_rec_mutual_even_odd() {
     assert!(compile(&expr).is_ok());
 }
 
+/// Instructions of the first function defined in a chunk
+fn first_function_body(chunk: &fusabi_vm::chunk::Chunk) -> Vec<Instruction> {
+    chunk
+        .constants
+        .iter()
+        .find_map(|constant| match constant {
+            Value::Closure(closure) => Some(closure.chunk.instructions.clone()),
+            _ => None,
+        })
+        .expect("Expected a function")
+}
+
+#[test]
+fn test_compile_tail_calls() {
+    // The recursive call is the result of both the `else` and the match arm
+    let code = r#"
+        let rec count n =
+            if n = 0 then 0
+            else match n with
+                 | _ -> count (n - 1)
+        in count 5
+    "#;
+    let chunk = Compiler::compile(&parse(code).unwrap()).unwrap();
+    let body = first_function_body(&chunk);
+    assert!(body.contains(&Instruction::TailCall(1)));
+    assert!(!body.contains(&Instruction::Call(1)));
+
+    // A call whose result is still used is not a tail call
+    let code = "let rec fact n = if n <= 1 then 1 else n * fact (n - 1) in fact 5";
+    let chunk = Compiler::compile(&parse(code).unwrap()).unwrap();
+    let body = first_function_body(&chunk);
+    assert!(body.contains(&Instruction::Call(1)));
+    assert!(!body.contains(&Instruction::TailCall(1)));
+
+    // Nor is one inside a try block
+    let code = "let rec retry n = try retry (n - 1) with _ -> n in retry 3";
+    let chunk = Compiler::compile(&parse(code).unwrap()).unwrap();
+    assert!(!first_function_body(&chunk).contains(&Instruction::TailCall(1)));
+}
+
 // ========================================================================
 // Display/Format Tests
 // ========================================================================
@@ -234,6 +282,8 @@ fn test_let_rec_display() {
         name: "f".to_string(),
         value: Box::new(Expr::Lambda {
             param: "x".to_string(),
+            param_ty: None,
+            return_ty: None,
             body: Box::new(Expr::Var("x".to_string())),
         }),
         body: Box::new(Expr::Var("f".to_string())),
@@ -252,6 +302,8 @@ fn test_let_rec_mutual_display() {
                 "a".to_string(),
                 Expr::Lambda {
                     param: "x".to_string(),
+                    param_ty: None,
+                    return_ty: None,
                     body: Box::new(Expr::Lit(Literal::Int(1))),
                 },
             ),
@@ -259,6 +311,8 @@ fn test_let_rec_mutual_display() {
                 "b".to_string(),
                 Expr::Lambda {
                     param: "y".to_string(),
+                    param_ty: None,
+                    return_ty: None,
                     body: Box::new(Expr::Lit(Literal::Int(2))),
                 },
             ),
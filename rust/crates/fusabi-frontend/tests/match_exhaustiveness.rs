@@ -0,0 +1,120 @@
+//! Integration tests for match exhaustiveness and unreachable-arm warnings
+//!
+//! These tests drive the full lexer → parser → compiler pipeline and check
+//! how findings are surfaced through `CompileOptions`.
+
+use fusabi_frontend::compiler::{CompileError, CompileOptions, CompileWarning, Compiler};
+use fusabi_frontend::lexer::Lexer;
+use fusabi_frontend::parser::Parser;
+
+fn compile(source: &str, options: CompileOptions) -> Result<Vec<CompileWarning>, CompileError> {
+    let tokens = Lexer::new(source).tokenize().unwrap();
+    let program = Parser::new(tokens).parse_program().unwrap();
+    Compiler::compile_program_with_warnings(&program, options).map(|(_, warnings)| warnings)
+}
+
+fn strict() -> CompileOptions {
+    CompileOptions {
+        strict_mode: true,
+        ..Default::default()
+    }
+}
+
+const SHAPES: &str = "type Shape = Circle of int | Square of int | Empty\n";
+
+#[test]
+fn test_missing_du_case_is_warning_by_default() {
+    let source = format!(
+        "{}let s = Square(2)\nmatch s with\n| Square(n) -> n\n| Empty -> 0\n",
+        SHAPES
+    );
+    let warnings = compile(&source, CompileOptions::default()).unwrap();
+    assert_eq!(
+        warnings,
+        vec![CompileWarning::IncompleteMatch {
+            missing: vec!["Circle(_)".to_string()]
+        }]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "Incomplete pattern match: Circle(_) not covered"
+    );
+}
+
+#[test]
+fn test_exhaustive_du_match_has_no_warnings() {
+    let source = format!(
+        "{}let s = Empty\nmatch s with\n| Circle(r) -> r\n| Square(n) -> n\n| Empty -> 0\n",
+        SHAPES
+    );
+    assert!(compile(&source, strict()).unwrap().is_empty());
+}
+
+#[test]
+fn test_strict_mode_rejects_incomplete_option_match() {
+    let source = "let x = Some(1)\nmatch x with\n| Some(n) -> n\n";
+    let err = compile(source, strict()).unwrap_err();
+    assert_eq!(
+        err,
+        CompileError::WarningAsError(CompileWarning::IncompleteMatch {
+            missing: vec!["None".to_string()]
+        })
+    );
+}
+
+#[test]
+fn test_disallowed_warnings_reject_unreachable_arm() {
+    let source = "match (1, true) with\n| (_, _) -> 1\n| (0, false) -> 2\n";
+    let options = CompileOptions {
+        allow_warnings: false,
+        ..Default::default()
+    };
+    let err = compile(source, options).unwrap_err();
+    assert!(matches!(
+        err,
+        CompileError::WarningAsError(CompileWarning::UnreachableArm { arm_index: 1, .. })
+    ));
+}
+
+#[test]
+fn test_match_inside_lambda_is_checked() {
+    let source = "let f = fun b -> match b with | true -> 1\nf true\n";
+    let warnings = compile(source, CompileOptions::default()).unwrap();
+    assert_eq!(
+        warnings,
+        vec![CompileWarning::IncompleteMatch {
+            missing: vec!["false".to_string()]
+        }]
+    );
+}
+
+const PARITY: &str = "let (|Even|Odd|) n = if n % 2 = 0 then Even else Odd\n";
+
+#[test]
+fn test_total_active_pattern_cases_are_complete() {
+    let source = format!("{}match 3 with\n| Even -> 0\n| Odd -> 1\n", PARITY);
+    assert!(compile(&source, strict()).unwrap().is_empty());
+
+    let source = format!("{}match 3 with\n| Even -> 0\n", PARITY);
+    assert_eq!(
+        compile(&source, CompileOptions::default()).unwrap(),
+        vec![CompileWarning::IncompleteMatch {
+            missing: vec!["Odd".to_string()]
+        }]
+    );
+}
+
+#[test]
+fn test_partial_active_pattern_needs_fallback() {
+    let source = "let (|Pos|_|) n = if n > 0 then Some n else None\nmatch 3 with\n| Pos p -> p\n";
+    assert_eq!(
+        compile(source, CompileOptions::default()).unwrap(),
+        vec![CompileWarning::IncompleteMatch {
+            missing: vec!["_".to_string()]
+        }]
+    );
+
+    let source =
+        "let (|Pos|_|) n = if n > 0 then Some n else None\nmatch 3 with\n| Pos p -> p\n| _ -> 0\n";
+    assert!(compile(source, strict()).unwrap().is_empty());
+}
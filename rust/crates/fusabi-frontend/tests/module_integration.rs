@@ -48,6 +48,9 @@ fn test_e2e_module_parsing_and_registration() {
                         bindings.insert(name.clone(), expr.clone());
                     }
                 }
+                ModuleItem::LetMutable(name, expr) => {
+                    bindings.insert(name.clone(), expr.clone());
+                }
                 ModuleItem::Module(_) => {
                     // Nested modules would be handled here
                 }
@@ -113,12 +113,14 @@ fn test_variant_def_display_with_multiple_fields() {
 fn test_du_typedef_simple_enum() {
     let du = DuTypeDef {
         name: "Direction".to_string(),
+        type_params: vec![],
         variants: vec![
             VariantDef::new_simple("Left".to_string()),
             VariantDef::new_simple("Right".to_string()),
             VariantDef::new_simple("Up".to_string()),
             VariantDef::new_simple("Down".to_string()),
         ],
+        members: vec![],
     };
 
     assert_eq!(du.name, "Direction");
@@ -133,10 +135,12 @@ fn test_du_typedef_simple_enum() {
 fn test_du_typedef_option() {
     let du = DuTypeDef {
         name: "Option".to_string(),
+        type_params: vec![],
         variants: vec![
             VariantDef::new("Some".to_string(), vec![TypeExpr::Named("int".to_string())]),
             VariantDef::new_simple("None".to_string()),
         ],
+        members: vec![],
     };
 
     assert_eq!(du.name, "Option");
@@ -151,6 +155,7 @@ fn test_du_typedef_option() {
 fn test_du_typedef_find_variant() {
     let du = DuTypeDef {
         name: "Shape".to_string(),
+        type_params: vec![],
         variants: vec![
             VariantDef::new(
                 "Circle".to_string(),
@@ -164,6 +169,7 @@ fn test_du_typedef_find_variant() {
                 ],
             ),
         ],
+        members: vec![],
     };
 
     let circle = du.find_variant("Circle");
@@ -178,10 +184,12 @@ fn test_du_typedef_find_variant() {
 fn test_du_typedef_display() {
     let du = DuTypeDef {
         name: "Option".to_string(),
+        type_params: vec![],
         variants: vec![
             VariantDef::new("Some".to_string(), vec![TypeExpr::Named("int".to_string())]),
             VariantDef::new_simple("None".to_string()),
         ],
+        members: vec![],
     };
 
     assert_eq!(format!("{}", du), "type Option = Some of int | None");
@@ -191,10 +199,12 @@ fn test_du_typedef_display() {
 fn test_du_typedef_display_simple_enum() {
     let du = DuTypeDef {
         name: "Direction".to_string(),
+        type_params: vec![],
         variants: vec![
             VariantDef::new_simple("Left".to_string()),
             VariantDef::new_simple("Right".to_string()),
         ],
+        members: vec![],
     };
 
     assert_eq!(format!("{}", du), "type Direction = Left | Right");
@@ -208,10 +218,12 @@ fn test_du_typedef_display_simple_enum() {
 fn test_type_definition_du() {
     let du = DuTypeDef {
         name: "Option".to_string(),
+        type_params: vec![],
         variants: vec![
             VariantDef::new("Some".to_string(), vec![TypeExpr::Named("int".to_string())]),
             VariantDef::new_simple("None".to_string()),
         ],
+        members: vec![],
     };
 
     let typedef = TypeDefinition::Du(du);
@@ -229,10 +241,12 @@ fn test_type_definition_du() {
 fn test_type_definition_display_du() {
     let du = DuTypeDef {
         name: "Bool".to_string(),
+        type_params: vec![],
         variants: vec![
             VariantDef::new_simple("True".to_string()),
             VariantDef::new_simple("False".to_string()),
         ],
+        members: vec![],
     };
 
     let typedef = TypeDefinition::Du(du);
@@ -474,6 +488,7 @@ fn test_token_of_display() {
 fn test_du_with_all_variant_types() {
     let du = DuTypeDef {
         name: "Mixed".to_string(),
+        type_params: vec![],
         variants: vec![
             VariantDef::new_simple("Simple".to_string()),
             VariantDef::new(
@@ -488,6 +503,7 @@ fn test_du_with_all_variant_types() {
                 ],
             ),
         ],
+        members: vec![],
     };
 
     assert_eq!(du.variant_count(), 3);
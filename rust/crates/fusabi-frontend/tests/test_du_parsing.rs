@@ -7,7 +7,7 @@
 //! - Integration with other language features
 //! - Error cases
 
-use fusabi_frontend::ast::{DuTypeDef, Expr};
+use fusabi_frontend::ast::{DuTypeDef, Expr, TypeExpr};
 use fusabi_frontend::lexer::Lexer;
 use fusabi_frontend::parser::Parser;
 
@@ -608,3 +608,48 @@ fn test_parse_variant_with_many_fields() {
     let many = du.find_variant("Many").unwrap();
     assert_eq!(many.field_count(), 4);
 }
+
+// ============================================================================
+// Generic DU Tests
+// ============================================================================
+
+#[test]
+fn test_parse_generic_du() {
+    let du = parse_du_typedef("type Tree<'a> = Leaf | Node of Tree<'a> * 'a * Tree<'a>").unwrap();
+    assert_eq!(du.type_params, vec!["a".to_string()]);
+
+    let node = du.find_variant("Node").unwrap();
+    let tree_a = TypeExpr::App("Tree".to_string(), vec![TypeExpr::Var("a".to_string())]);
+    assert_eq!(
+        node.fields,
+        vec![tree_a.clone(), TypeExpr::Var("a".to_string()), tree_a]
+    );
+    assert_eq!(
+        format!("{}", du),
+        "type Tree<'a> = Leaf | Node of Tree<'a> * 'a * Tree<'a>"
+    );
+}
+
+#[test]
+fn test_parse_du_with_postfix_and_multi_param_types() {
+    let du = parse_du_typedef(
+        "type Outcome<'t, 'e> = Done of 't list | Failed of Map<string, 'e> | Skipped of (int * 't) option",
+    )
+    .unwrap();
+    assert_eq!(du.type_params, vec!["t".to_string(), "e".to_string()]);
+    assert_eq!(
+        format!("{}", du),
+        "type Outcome<'t, 'e> = Done of 't list | Failed of Map<string, 'e> | Skipped of (int * 't) option"
+    );
+}
+
+#[test]
+fn test_parse_du_rejects_undeclared_type_variable() {
+    let err = parse_du_typedef("type Box<'a> = Box of 'b").unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("type variable 'b is not declared by type Box"));
+
+    let err = parse_du_typedef("type Pair<'a, 'a> = Pair of 'a * 'a").unwrap_err();
+    assert!(err.to_string().contains("duplicate type parameter 'a"));
+}
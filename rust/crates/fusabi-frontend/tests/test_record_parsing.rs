@@ -143,6 +143,24 @@ fn test_parse_record_literal_with_if_expression() {
     assert!(fields[0].1.is_if());
 }
 
+#[test]
+fn test_parse_record_literal_qualified_field() {
+    let expr = parse("{ Person.name = \"John\"; age = 30 }").unwrap();
+    let (type_name, fields) = expr.as_record_literal().unwrap();
+    assert_eq!(type_name, "Person");
+    assert_eq!(fields[0].0, "name");
+    assert_eq!(fields[1].0, "age");
+}
+
+#[test]
+fn test_parse_anonymous_record_literal() {
+    let expr = parse("{| name = \"John\"; age = 30 |}").unwrap();
+    assert!(!expr.is_record_literal());
+    assert_eq!(expr.to_string(), "{| name = \"John\"; age = 30 |}");
+    // An anonymous record can be a function argument
+    assert!(parse("describe {| name = \"John\" |}").unwrap().is_app());
+}
+
 // ========================================================================
 // Record Field Access Tests (Phase 3)
 // ========================================================================
@@ -52,6 +52,7 @@ fn test_tuple_in_let_binding() {
     // Test: let pair = (1, 2) in pair
     let expr = Expr::Let {
         name: "pair".to_string(),
+        ty: None,
         value: Box::new(Expr::Tuple(vec![
             Expr::Lit(Literal::Int(1)),
             Expr::Lit(Literal::Int(2)),
@@ -128,9 +129,11 @@ fn test_tuple_with_variables() {
     // Test: let x = 1 in let y = 2 in (x, y)
     let expr = Expr::Let {
         name: "x".to_string(),
+        ty: None,
         value: Box::new(Expr::Lit(Literal::Int(1))),
         body: Box::new(Expr::Let {
             name: "y".to_string(),
+            ty: None,
             value: Box::new(Expr::Lit(Literal::Int(2))),
             body: Box::new(Expr::Tuple(vec![
                 Expr::Var("x".to_string()),
@@ -224,9 +227,11 @@ fn test_tuple_complex_expression() {
     // Test: let x = 10 in let y = 20 in (x + y, x * y)
     let expr = Expr::Let {
         name: "x".to_string(),
+        ty: None,
         value: Box::new(Expr::Lit(Literal::Int(10))),
         body: Box::new(Expr::Let {
             name: "y".to_string(),
+            ty: None,
             value: Box::new(Expr::Lit(Literal::Int(20))),
             body: Box::new(Expr::Tuple(vec![
                 Expr::BinOp {
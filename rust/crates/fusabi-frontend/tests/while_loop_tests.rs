@@ -229,3 +229,38 @@ fn test_parsing_continue_keyword() {
 
     assert!(ast.is_continue());
 }
+
+#[test]
+fn test_while_loop_with_mutable_counter() {
+    let source = "let mutable i = 0 in while i < 10 do i <- i + 1";
+    let chunk = compile_source(source).unwrap();
+
+    // The counter is read and written through the same local slot
+    assert!(chunk
+        .instructions
+        .iter()
+        .any(|i| matches!(i, Instruction::LoadLocal(0))));
+    assert!(
+        chunk
+            .instructions
+            .iter()
+            .filter(|i| matches!(i, Instruction::StoreLocal(0)))
+            .count()
+            >= 2
+    );
+}
+
+#[test]
+fn test_break_closes_captured_loop_locals() {
+    let source = "while true do let x = 1 in let f = fun u -> x in break";
+    let chunk = compile_source(source).unwrap();
+
+    assert!(chunk
+        .instructions
+        .iter()
+        .any(|i| matches!(i, Instruction::MakeClosure(_, 1))));
+    assert!(chunk
+        .instructions
+        .iter()
+        .any(|i| matches!(i, Instruction::CloseUpvalue(0))));
+}
@@ -59,6 +59,16 @@ impl FusabiLanguageServer {
                         pos.column,
                         format!("Unknown directive: '{}'", name),
                     ),
+                    fusabi_frontend::LexError::InvalidInterpolation(message, pos) => (
+                        pos.line,
+                        pos.column,
+                        format!("Invalid interpolated string: {}", message),
+                    ),
+                    fusabi_frontend::LexError::InvalidByteChar(ch, pos) => (
+                        pos.line,
+                        pos.column,
+                        format!("Character '{}' does not fit in a byte string", ch),
+                    ),
                 };
                 diagnostics.push(Diagnostic {
                     range: Range {
@@ -158,6 +168,7 @@ impl FusabiLanguageServer {
             "let" => "**let** - Bind a value to a name\n\n```fusabi\nlet x = 42\nlet add a b = a + b\n```",
             "let!" => "**let!** - Async/computation expression binding\n\n```fusabi\nasync { let! result = fetchData() }\n```",
             "rec" => "**rec** - Recursive binding\n\n```fusabi\nlet rec factorial n = if n <= 1 then 1 else n * factorial (n - 1)\n```",
+            "mutable" => "**mutable** - Mutable binding, updated with `<-`\n\n```fusabi\nlet mutable count = 0\ncount <- count + 1\n```",
             "in" => "**in** - Body of let expression\n\n```fusabi\nlet x = 5 in x + 1\n```",
             "if" => "**if** - Conditional expression\n\n```fusabi\nif condition then trueValue else falseValue\n```",
             "then" => "**then** - True branch of if expression",
@@ -167,8 +178,12 @@ impl FusabiLanguageServer {
             "with" => "**with** - Match arms or record update\n\n```fusabi\n{ record with field = newValue }\n```",
             "type" => "**type** - Type definition\n\n```fusabi\ntype Option = Some of int | None\ntype Person = { name: string; age: int }\ntype Schema = SqlProvider<\"schema.sql\">  // Type provider\n```",
             "of" => "**of** - Discriminated union variant payload",
+            "member" => "**member** - Method or property declared on a type\n\n```fusabi\ntype Shape = Circle of float\nwith\n    member this.Area() = match this with | Circle r -> 3.14159 * r * r\n```",
+            "static" => "**static member** - Member declared on a type itself, such as an operator overload\n\n```fusabi\ntype Vector2 = { X: float; Y: float }\nwith\n    static member (+) (a: Vector2, b: Vector2) = { X = a.X + b.X; Y = a.Y + b.Y }\n```",
             "module" => "**module** - Module definition\n\n```fusabi\nmodule Math =\n  let pi = 3.14159\n```",
             "open" => "**open** - Import module\n\n```fusabi\nopen Math\n```",
+            "private" => "**private** - Module binding visible only inside its module\n\n```fusabi\nmodule Math =\n  let private square x = x * x\n  let norm x y = square x + square y\n```",
+            "internal" => "**internal** - Module binding hidden from files that `#load` its module\n\n```fusabi\nmodule Math =\n  let internal epsilon = 1e-9\n```",
             "async" => "**async** - Async computation expression\n\n```fusabi\nasync { let! data = fetch(); return data }\n```",
             "return" => "**return** - Return value from computation expression",
             "return!" => "**return!** - Return wrapped value from computation expression",
@@ -177,6 +192,8 @@ impl FusabiLanguageServer {
             "do" => "**do** - Execute expression for side effects",
             "do!" => "**do!** - Execute async expression for side effects",
             "while" => "**while** - While loop\n\n```fusabi\nwhile condition do\n  body\n```",
+            "for" => "**for** - For loop\n\n```fusabi\nfor i = 1 to 10 do\n  body\n\nfor x in items do\n  body\n```",
+            "to" | "downto" => "**to** / **downto** - Count up or down in a for loop",
             "break" => "**break** - Exit loop early",
             "continue" => "**continue** - Skip to next iteration",
             "true" => "**true** - Boolean true literal",
@@ -202,6 +219,11 @@ impl FusabiLanguageServer {
         let keywords = vec![
             ("let", "Bind a value", CompletionItemKind::KEYWORD),
             ("let rec", "Recursive binding", CompletionItemKind::KEYWORD),
+            (
+                "let mutable",
+                "Mutable binding",
+                CompletionItemKind::KEYWORD,
+            ),
             ("in", "Let body", CompletionItemKind::KEYWORD),
             ("if", "Conditional", CompletionItemKind::KEYWORD),
             ("then", "If true branch", CompletionItemKind::KEYWORD),
@@ -221,6 +243,7 @@ impl FusabiLanguageServer {
             ("yield", "Yield value", CompletionItemKind::KEYWORD),
             ("do", "Side effect", CompletionItemKind::KEYWORD),
             ("while", "While loop", CompletionItemKind::KEYWORD),
+            ("for", "For loop", CompletionItemKind::KEYWORD),
             ("break", "Exit loop", CompletionItemKind::KEYWORD),
             ("continue", "Next iteration", CompletionItemKind::KEYWORD),
             ("true", "Boolean true", CompletionItemKind::CONSTANT),
@@ -234,6 +257,7 @@ impl FusabiLanguageServer {
                 CompletionItemKind::FUNCTION,
             ),
             ("printf", "Print formatted", CompletionItemKind::FUNCTION),
+            ("ref", "Create reference cell", CompletionItemKind::FUNCTION),
             ("List.map", "Map over list", CompletionItemKind::FUNCTION),
             ("List.filter", "Filter list", CompletionItemKind::FUNCTION),
             ("List.fold", "Fold list", CompletionItemKind::FUNCTION),
@@ -24,7 +24,7 @@
 //! ```
 
 use anyhow::{anyhow, Context, Result};
-use fusabi::{Engine, Value};
+use fusabi::{Engine, Value, VmLimits};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
@@ -353,8 +353,9 @@ impl McpServer {
 
     /// Execute a Fusabi script
     ///
-    /// Note: Timeout protection is limited because Fusabi's Value type uses Rc which is not Send.
-    /// A full timeout implementation would require the Fusabi crate to use Arc instead.
+    /// The script runs with a deadline of `timeout_duration`, which the VM
+    /// checks while executing, so non-terminating scripts fail instead of
+    /// blocking the server.
     async fn eval_fusabi(&mut self, arguments: serde_json::Value) -> Result<serde_json::Value> {
         #[derive(Deserialize)]
         struct EvalArgs {
@@ -367,9 +368,8 @@ impl McpServer {
         eprintln!("Executing script: {}", args.script);
 
         // Create a fresh engine for each execution to ensure clean state
-        // Note: Since Value contains Rc, we cannot move it to another thread
-        // This means we cannot use tokio::spawn_blocking for true timeout protection
         let mut engine = Engine::new();
+        engine.set_limits(VmLimits::default().with_timeout(self.timeout_duration));
         match engine.eval(&args.script) {
             Ok(value) => {
                 eprintln!("Execution succeeded: {:?}", value);
@@ -527,6 +527,14 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_eval_fusabi_timeout() {
+        let mut server = McpServer::with_timeout(Duration::from_millis(50));
+        let args = json!({ "script": "while true do ()" });
+        let error = server.eval_fusabi(args).await.unwrap_err();
+        assert!(error.to_string().contains("deadline"));
+    }
+
     #[tokio::test]
     async fn test_get_context() {
         let server = McpServer::new();
@@ -15,6 +15,10 @@ pub enum TypeExpr {
     Tuple(Vec<TypeExpr>),
     /// Function type (e.g., int -> string)
     Function(Box<TypeExpr>, Box<TypeExpr>),
+    /// Type variable, without its quote (e.g., 'a is Var("a"))
+    Var(String),
+    /// Applied generic type (e.g., `int list` is App("list", [int]))
+    App(String, Vec<TypeExpr>),
 }
 
 impl fmt::Display for TypeExpr {
@@ -31,6 +35,22 @@ impl fmt::Display for TypeExpr {
                 Ok(())
             }
             TypeExpr::Function(arg, ret) => write!(f, "{} -> {}", arg, ret),
+            TypeExpr::Var(name) => write!(f, "'{}", name),
+            TypeExpr::App(name, args)
+                if args.len() == 1 && matches!(name.as_str(), "list" | "option" | "array") =>
+            {
+                write!(f, "{} {}", args[0], name)
+            }
+            TypeExpr::App(name, args) => {
+                write!(f, "{}<", name)?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ">")
+            }
         }
     }
 }
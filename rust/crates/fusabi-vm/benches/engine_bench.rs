@@ -0,0 +1,66 @@
+// Compares the two interpreter loops on compiled scripts
+// Run with: cargo bench -p fusabi-vm --bench engine_bench
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use fusabi_vm::{Chunk, FastVm, Vm};
+
+/// Scripts covering the instructions FastVm handles inline (arithmetic,
+/// locals, jumps) and the ones it hands to `Vm::dispatch` (calls, records,
+/// lists)
+const SCRIPTS: &[(&str, &str)] = &[
+    (
+        "counting_loop",
+        "let mutable total = 0 in\nlet d = (for i = 1 to 20000 do total <- total + i) in\ntotal",
+    ),
+    (
+        "fibonacci",
+        "let rec fib n = if n < 2 then n else fib (n - 1) + fib (n - 2)\nfib 20",
+    ),
+    (
+        "records_and_lists",
+        "let point x = { X = x; Y = x * 2 }\nList.length (List.map (fun x -> (point x).Y) [1 .. 20000])",
+    ),
+];
+
+/// Compile a benchmark script; stdlib registration stays outside the timed
+/// part of each iteration
+fn compile(source: &str) -> Chunk {
+    fusabi::compile_to_chunk(source).expect("benchmark script should compile")
+}
+
+fn bench_engines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("engine");
+
+    for (name, source) in SCRIPTS {
+        let chunk = compile(source);
+
+        group.bench_with_input(BenchmarkId::new("vm", name), &chunk, |b, chunk| {
+            b.iter_batched(
+                || {
+                    let mut vm = Vm::new();
+                    fusabi_vm::stdlib::register_stdlib(&mut vm);
+                    vm
+                },
+                |mut vm| black_box(vm.execute(chunk.clone()).unwrap()),
+                BatchSize::SmallInput,
+            );
+        });
+
+        group.bench_with_input(BenchmarkId::new("fast_vm", name), &chunk, |b, chunk| {
+            b.iter_batched(
+                || {
+                    let mut vm = FastVm::new();
+                    fusabi_vm::stdlib::register_stdlib(&mut vm);
+                    vm
+                },
+                |mut vm| black_box(vm.execute(chunk.clone()).unwrap()),
+                BatchSize::SmallInput,
+            );
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_engines);
+criterion_main!(benches);
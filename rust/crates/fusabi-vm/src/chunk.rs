@@ -49,6 +49,16 @@ impl fmt::Display for SourceSpan {
     }
 }
 
+/// Where a closure's upvalue comes from when `MakeClosure` runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UpvalueSource {
+    /// A local slot of the frame creating the closure
+    Local(u8),
+    /// An upvalue of the closure creating the closure
+    Upvalue(u8),
+}
+
 /// A chunk of bytecode representing a compiled function
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -65,6 +75,15 @@ pub struct Chunk {
     /// Source file name
     #[cfg_attr(feature = "serde", serde(default))]
     pub source_file: Option<String>,
+    /// Number of local variable slots used by this chunk (including parameters)
+    ///
+    /// The VM reserves these slots when a frame is entered so that locals never
+    /// overlap temporaries pushed by the surrounding expression.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub local_count: u8,
+    /// How each upvalue is captured when this chunk is turned into a closure
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub upvalue_sources: Vec<UpvalueSource>,
 }
 
 impl Chunk {
@@ -76,6 +95,8 @@ impl Chunk {
             spans: Vec::new(),
             source: None,
             source_file: None,
+            local_count: 0,
+            upvalue_sources: Vec::new(),
         }
     }
 
@@ -87,6 +108,8 @@ impl Chunk {
             spans: Vec::new(),
             source: None,
             source_file: None,
+            local_count: 0,
+            upvalue_sources: Vec::new(),
         }
     }
 
@@ -0,0 +1,91 @@
+// Fusabi VM - Per-VM Context
+// Stdlib state that outlives a single call, such as event handlers and config
+// values, owned by each Vm so that engines in one host stay isolated
+
+use crate::stdlib::commands::CommandRegistry;
+use crate::stdlib::config::ConfigStore;
+use crate::stdlib::events::EventBus;
+use crate::stdlib::terminal_control::TerminalControlProvider;
+use crate::stdlib::terminal_info::TerminalInfoProvider;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// A slot holding the provider a host registered, if any
+pub type ProviderSlot<P> = Arc<Mutex<Option<Box<P>>>>;
+
+/// Stdlib state owned by a [`Vm`](crate::Vm)
+///
+/// A new context has no event handlers, config entries, commands or
+/// providers. Cloning a context shares every part with the clone, so a host
+/// that wants two VMs to see the same state assigns
+/// `vm_b.context = vm_a.context.clone()`. Assigning a single field, such as
+/// `vm_b.context.events = vm_a.context.events.clone()`, shares only that
+/// part.
+#[derive(Clone, Default)]
+pub struct VmContext {
+    /// Handlers registered with `Events.on` and `Events.once`
+    pub events: Arc<Mutex<EventBus>>,
+    /// Schemas and values registered with `Config.define`
+    pub config: Arc<Mutex<ConfigStore>>,
+    /// Commands registered with `Commands.register`
+    pub commands: Arc<Mutex<CommandRegistry>>,
+    /// Backend for the `TerminalControl` module
+    pub terminal_control: ProviderSlot<dyn TerminalControlProvider>,
+    /// Backend for the `TerminalInfo` module
+    pub terminal_info: ProviderSlot<dyn TerminalInfoProvider>,
+}
+
+impl VmContext {
+    /// Create a context with empty registries and no providers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `other` shares every part of this context
+    pub fn is_shared_with(&self, other: &VmContext) -> bool {
+        Arc::ptr_eq(&self.events, &other.events)
+            && Arc::ptr_eq(&self.config, &other.config)
+            && Arc::ptr_eq(&self.commands, &other.commands)
+            && Arc::ptr_eq(&self.terminal_control, &other.terminal_control)
+            && Arc::ptr_eq(&self.terminal_info, &other.terminal_info)
+    }
+}
+
+impl fmt::Debug for VmContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VmContext")
+            .field("events", &self.events)
+            .field("config", &self.config)
+            .field("commands", &self.commands)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_contexts_are_isolated() {
+        let a = VmContext::new();
+        let b = VmContext::new();
+        assert!(!a.is_shared_with(&b));
+        assert!(!Arc::ptr_eq(&a.events, &b.events));
+    }
+
+    #[test]
+    fn test_clone_shares_every_part() {
+        let a = VmContext::new();
+        let b = a.clone();
+        assert!(a.is_shared_with(&b));
+    }
+
+    #[test]
+    fn test_single_part_can_be_shared() {
+        let a = VmContext::new();
+        let mut b = VmContext::new();
+        b.config = a.config.clone();
+        assert!(Arc::ptr_eq(&a.config, &b.config));
+        assert!(!a.is_shared_with(&b));
+    }
+}
@@ -42,6 +42,12 @@ impl From<&str> for Value {
     }
 }
 
+impl From<char> for Value {
+    fn from(c: char) -> Self {
+        Value::Char(c)
+    }
+}
+
 impl From<()> for Value {
     fn from(_: ()) -> Self {
         Value::Unit
@@ -113,6 +119,16 @@ impl TryFrom<Value> for String {
     }
 }
 
+impl TryFrom<Value> for char {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        value
+            .as_char()
+            .ok_or_else(|| format!("Expected Char, got {}", value.type_name()))
+    }
+}
+
 impl TryFrom<Value> for () {
     type Error = String;
 
@@ -202,6 +218,14 @@ mod tests {
         assert_eq!(v, Value::Str("hello".to_string()));
     }
 
+    #[test]
+    fn test_from_char() {
+        let v: Value = 'x'.into();
+        assert_eq!(v, Value::Char('x'));
+        assert_eq!(char::try_from(v), Ok('x'));
+        assert!(char::try_from(Value::Str("x".to_string())).is_err());
+    }
+
     #[test]
     fn test_from_unit() {
         let v: Value = ().into();
@@ -0,0 +1,101 @@
+// Fusabi Exceptions
+// Exceptions are ordinary variant values of the built-in `exn` type, so
+// `try ... with` arms can pattern-match on them like any other union.
+
+use crate::chunk::SourceSpan;
+use crate::value::Value;
+use crate::vm::VmError;
+
+/// Type name carried by exception values built by the runtime
+pub const EXN_TYPE_NAME: &str = "exn";
+
+/// `Failure message` - raised by `failwith`
+pub fn failure(message: impl Into<String>) -> Value {
+    Value::Variant {
+        type_name: EXN_TYPE_NAME.to_string(),
+        variant_name: "Failure".to_string(),
+        fields: vec![Value::Str(message.into())],
+    }
+}
+
+/// `HostError(message, line, column)` - a host function call failed
+///
+/// The line and column are 0 when the call site has no span information.
+pub fn host_error(message: impl Into<String>, span: Option<SourceSpan>) -> Value {
+    let (line, column) = span.map_or((0, 0), |s| (s.line as i64, s.column as i64));
+    Value::Variant {
+        type_name: EXN_TYPE_NAME.to_string(),
+        variant_name: "HostError".to_string(),
+        fields: vec![
+            Value::Str(message.into()),
+            Value::Int(line),
+            Value::Int(column),
+        ],
+    }
+}
+
+/// Convert an error returned by a host function into a catchable exception
+///
+/// Exceptions raised by scripts (possibly re-entered through a host function
+/// such as `List.map`) are passed through unchanged.
+pub fn from_error(error: &VmError, span: Option<SourceSpan>) -> Value {
+    match error {
+        VmError::Exception(exn) => exn.clone(),
+        VmError::Runtime(message) => host_error(message.clone(), span),
+        other => host_error(other.to_string(), span),
+    }
+}
+
+/// failwith : string -> 'a
+/// Raises `Failure message`
+pub fn failwith(message: &Value) -> Result<Value, VmError> {
+    match message {
+        Value::Str(s) => Err(VmError::Exception(failure(s.clone()))),
+        _ => Err(VmError::TypeMismatch {
+            expected: "string",
+            got: message.type_name(),
+        }),
+    }
+}
+
+/// raise : exn -> 'a
+/// Raises any value as an exception
+pub fn raise(exn: &Value) -> Result<Value, VmError> {
+    Err(VmError::Exception(exn.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_value() {
+        assert_eq!(failure("boom").to_string(), "Failure(boom)");
+    }
+
+    #[test]
+    fn test_failwith_raises_failure() {
+        let err = failwith(&Value::Str("boom".into())).unwrap_err();
+        assert_eq!(err, VmError::Exception(failure("boom")));
+        assert_eq!(err.to_string(), "Unhandled exception: Failure(boom)");
+    }
+
+    #[test]
+    fn test_host_error_carries_span() {
+        let exn = host_error("bad", Some(SourceSpan::new(3, 7, 20, 4)));
+        let (_, variant, fields) = exn.as_variant().unwrap();
+        assert_eq!(variant, "HostError");
+        assert_eq!(fields[1], Value::Int(3));
+        assert_eq!(fields[2], Value::Int(7));
+    }
+
+    #[test]
+    fn test_from_error_keeps_script_exceptions() {
+        let exn = failure("inner");
+        assert_eq!(from_error(&VmError::Exception(exn.clone()), None), exn);
+        assert_eq!(
+            from_error(&VmError::Runtime("oops".into()), None),
+            host_error("oops", None)
+        );
+    }
+}
@@ -3,7 +3,8 @@
 
 use crate::closure::{Closure, Upvalue};
 use crate::value::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 /// Trace trait for marking reachable objects during garbage collection
 pub trait Trace {
@@ -289,10 +290,14 @@ fn mark_value(value: &Value, tracer: &mut Tracer, objects: &HashMap<usize, GcObj
         | Value::Float(_)
         | Value::Bool(_)
         | Value::Str(_)
+        | Value::Char(_)
+        | Value::Bytes(_)
         | Value::Unit
         | Value::Nil => {}
         // HostData is managed by Rust's reference counting
         Value::HostData(_) => {}
+        // Sequences are immutable descriptions shared by reference counting
+        Value::Seq(_) => {}
         // Async values are managed by the AsyncRuntime
         #[cfg(feature = "async")]
         Value::Async(_) => {}
@@ -325,6 +330,8 @@ fn estimate_value_size(value: &Value) -> usize {
         Value::Float(_) => 8,
         Value::Bool(_) => 1,
         Value::Str(s) => std::mem::size_of::<String>() + s.len(),
+        Value::Char(_) => 4,
+        Value::Bytes(b) => std::mem::size_of::<Vec<u8>>() + b.len(),
         Value::Unit => 0,
         Value::Tuple(elements) => {
             std::mem::size_of::<Vec<Value>>()
@@ -369,11 +376,89 @@ fn estimate_value_size(value: &Value) -> usize {
             name.len() + args.iter().map(estimate_value_size).sum::<usize>()
         }
         Value::HostData(_) => 8, // Just the Rc pointer
+        Value::Seq(_) => 8,
         #[cfg(feature = "async")]
         Value::Async(_) => 16, // TaskId or boxed value
     }
 }
 
+/// Estimate the bytes held by every value reachable from `roots`
+///
+/// Shared arrays, records, maps and closures are counted once, so cyclic
+/// structures terminate. Long lists are walked iteratively.
+pub fn estimate_reachable_bytes<'a>(roots: impl IntoIterator<Item = &'a Value>) -> usize {
+    let mut seen = HashSet::new();
+    roots
+        .into_iter()
+        .map(|root| reachable_size(root, &mut seen))
+        .sum()
+}
+
+fn reachable_size(root: &Value, seen: &mut HashSet<usize>) -> usize {
+    let mut size = 0;
+    let mut pending = vec![root];
+    while let Some(value) = pending.pop() {
+        size += match value {
+            Value::Tuple(elements) => {
+                pending.extend(elements);
+                std::mem::size_of::<Vec<Value>>()
+            }
+            Value::Cons { head, tail } => {
+                pending.push(head);
+                pending.push(tail);
+                16
+            }
+            Value::Variant {
+                type_name,
+                variant_name,
+                fields,
+            } => {
+                pending.extend(fields);
+                type_name.len() + variant_name.len()
+            }
+            Value::NativeFn { name, args, .. } => {
+                pending.extend(args);
+                name.len()
+            }
+            Value::Array(arr) => {
+                if !seen.insert(Arc::as_ptr(arr) as *const () as usize) {
+                    continue;
+                }
+                let arr = arr.lock().unwrap();
+                std::mem::size_of::<Vec<Value>>()
+                    + arr.iter().map(|v| reachable_size(v, seen)).sum::<usize>()
+            }
+            Value::Record(fields) | Value::Map(fields) => {
+                if !seen.insert(Arc::as_ptr(fields) as *const () as usize) {
+                    continue;
+                }
+                let fields = fields.lock().unwrap();
+                std::mem::size_of::<HashMap<String, Value>>()
+                    + fields
+                        .iter()
+                        .map(|(k, v)| k.len() + reachable_size(v, seen))
+                        .sum::<usize>()
+            }
+            Value::Closure(c) => {
+                if !seen.insert(Arc::as_ptr(c) as *const () as usize) {
+                    continue;
+                }
+                let captured: usize = c
+                    .upvalues
+                    .iter()
+                    .map(|upvalue| match &*upvalue.lock().unwrap() {
+                        Upvalue::Closed(v) => reachable_size(v, seen),
+                        _ => 0,
+                    })
+                    .sum();
+                estimate_value_size(value) + captured
+            }
+            other => estimate_value_size(other),
+        };
+    }
+    size
+}
+
 // Implement Trace for standard containers
 impl<T: Trace> Trace for Vec<T> {
     fn trace(&self, tracer: &mut Tracer) {
@@ -644,4 +729,27 @@ mod tests {
         assert_eq!(heap.stats.collections, 1);
         assert!(heap.stats.objects_collected > 0);
     }
+
+    #[test]
+    fn test_estimate_reachable_bytes_counts_shared_values_once() {
+        let arr = Value::Array(Arc::new(Mutex::new(vec![Value::Str("x".repeat(1000))])));
+        let once = estimate_reachable_bytes([&arr]);
+        assert!(once > 1000);
+        assert_eq!(estimate_reachable_bytes([&arr, &arr.clone()]), once);
+
+        // A record that contains itself terminates
+        let record = Arc::new(Mutex::new(HashMap::new()));
+        record
+            .lock()
+            .unwrap()
+            .insert("me".to_string(), Value::Record(record.clone()));
+        assert!(estimate_reachable_bytes([&Value::Record(record.clone())]) > 0);
+        record.lock().unwrap().clear();
+    }
+
+    #[test]
+    fn test_estimate_reachable_bytes_long_list() {
+        let list = Value::vec_to_cons((0..2_000).map(Value::Int).collect());
+        assert!(estimate_reachable_bytes([&list]) >= 2_000 * 16);
+    }
 }
@@ -52,6 +52,9 @@ pub enum Instruction {
     /// Get tuple element by index (leaves tuple on stack)
     GetTupleElem(u8),
 
+    /// Pop the scrutinee and raise an error: no match arm accepted it
+    MatchFailure,
+
     // ===== Arithmetic Operations =====
     /// Pop two integers, push sum (a + b)
     Add,
@@ -65,6 +68,9 @@ pub enum Instruction {
     /// Pop two integers, push quotient (a / b)
     Div,
 
+    /// Pop two integers, push remainder (a % b)
+    Mod,
+
     /// Pop two strings, push concatenated string (a ++ b)
     Concat,
 
@@ -97,6 +103,22 @@ pub enum Instruction {
     /// Pop one boolean, push logical NOT (!a)
     Not,
 
+    // ===== Bitwise Operations =====
+    /// Pop two integers, push bitwise AND (a &&& b)
+    BitAnd,
+
+    /// Pop two integers, push bitwise OR (a ||| b)
+    BitOr,
+
+    /// Pop two integers, push bitwise exclusive OR (a ^^^ b)
+    BitXor,
+
+    /// Pop two integers, push a shifted left by b bits (a <<< b)
+    Shl,
+
+    /// Pop two integers, push a arithmetically shifted right by b bits (a >>> b)
+    Shr,
+
     // ===== Control Flow =====
     /// Unconditional jump by signed offset
     Jump(i16),
@@ -112,12 +134,26 @@ pub enum Instruction {
     /// The method name is passed as a constant index
     CallMethod(u16, u8),
 
-    /// Tail call optimization for recursive functions
+    /// Call function with N arguments in tail position
+    /// A closure callee reuses the current frame instead of pushing a new one;
+    /// other callees behave as `Call`
     TailCall(u8),
 
     /// Return from current function
     Return,
 
+    // ===== Exceptions =====
+    /// Install an exception handler whose code starts at the signed offset
+    /// When a value is raised, the stack is unwound to the height it had here
+    /// and the exception value is pushed before jumping to the handler
+    PushHandler(i16),
+
+    /// Remove the innermost exception handler (the protected code completed)
+    PopHandler,
+
+    /// Pop a value and raise it as an exception
+    Raise,
+
     // ===== Tuple Operations =====
     /// Create tuple from N stack values
     /// Pop N values from stack, create tuple, push tuple
@@ -144,11 +180,33 @@ pub enum Instruction {
     /// IsNil: Pop list, push bool (true if empty)
     IsNil,
 
+    /// ToList: Pop a list, array or map, push its elements as a list
+    /// (map entries become (key, value) tuples in key order)
+    ToList,
+
+    /// GetEnumerator: Pop a sequence, list, array or map, push a cursor over
+    /// its elements for `MoveNext`; sequences are enumerated lazily
+    GetEnumerator,
+
+    /// MoveNext: Advance the cursor in locals\[`idx`\], push the next element
+    /// and true, or only false once the elements are exhausted
+    MoveNext(u8),
+
+    /// ListReverse: Pop list, push it in reverse order
+    ListReverse,
+
+    /// MakeRange: Pop finish, pop step, pop start, push the list
+    /// [start .. step .. finish] (counting down for a negative step)
+    MakeRange,
+
     // ===== Array Operations =====
     /// Create array from N stack values [|e1; e2; e3|]
     /// Pop N values from stack (in reverse order), build array, push array
     MakeArray(u16),
 
+    /// ToArray: Pop a list or array, push its elements as a new array
+    ToArray,
+
     /// ArrayGet: Pop index, pop array, push element
     ArrayGet,
 
@@ -191,13 +249,24 @@ pub enum Instruction {
     /// Pop variant from stack, push field value at index
     GetVariantField(u8),
 
+    // ===== Type Members =====
+    /// Declare the members of a record or union type
+    /// Stack layout: [..., names (list of String), statics (Record), instance (Record)]
+    /// `names` are the record's field names, or the union's case names when
+    /// the flag is set. Pops all three and registers the members under the
+    /// type named by the constant; values of the type then dispatch
+    /// overloaded operators, method calls and properties to them. Static
+    /// members are also bound as the global record named by the type.
+    /// Args: (type_name_constant_index, is_union)
+    DefineType(u16, bool),
+
     // ===== Closure Operations =====
     /// Create closure from constant index (which points to a Chunk/Function)
-    /// Pops upvalues from stack based on the function's upvalue count
+    /// Captures upvalues as described by the function chunk's `upvalue_sources`
     /// Args: (constant_index, upvalue_count)
     MakeClosure(u16, u8),
 
-    /// Close upvalues on the stack up to a given stack slot
+    /// Close upvalues pointing at the given local slot and any slot above it
     CloseUpvalue(u8),
 }
 
@@ -220,12 +289,14 @@ impl fmt::Display for Instruction {
             Instruction::CheckString(s) => write!(f, "CHECK_STRING \"{}\"", s),
             Instruction::CheckTupleLen(n) => write!(f, "CHECK_TUPLE_LEN {}", n),
             Instruction::GetTupleElem(idx) => write!(f, "GET_TUPLE_ELEM {}", idx),
+            Instruction::MatchFailure => write!(f, "MATCH_FAILURE"),
 
             // Arithmetic
             Instruction::Add => write!(f, "ADD"),
             Instruction::Sub => write!(f, "SUB"),
             Instruction::Mul => write!(f, "MUL"),
             Instruction::Div => write!(f, "DIV"),
+            Instruction::Mod => write!(f, "MOD"),
             Instruction::Concat => write!(f, "CONCAT"),
 
             // Comparison
@@ -241,6 +312,13 @@ impl fmt::Display for Instruction {
             Instruction::Or => write!(f, "OR"),
             Instruction::Not => write!(f, "NOT"),
 
+            // Bitwise
+            Instruction::BitAnd => write!(f, "BIT_AND"),
+            Instruction::BitOr => write!(f, "BIT_OR"),
+            Instruction::BitXor => write!(f, "BIT_XOR"),
+            Instruction::Shl => write!(f, "SHL"),
+            Instruction::Shr => write!(f, "SHR"),
+
             // Control flow
             Instruction::Jump(offset) => write!(f, "JUMP {}", offset),
             Instruction::JumpIfFalse(offset) => write!(f, "JUMP_IF_FALSE {}", offset),
@@ -251,6 +329,11 @@ impl fmt::Display for Instruction {
             Instruction::TailCall(argc) => write!(f, "TAIL_CALL {}", argc),
             Instruction::Return => write!(f, "RETURN"),
 
+            // Exceptions
+            Instruction::PushHandler(offset) => write!(f, "PUSH_HANDLER {}", offset),
+            Instruction::PopHandler => write!(f, "POP_HANDLER"),
+            Instruction::Raise => write!(f, "RAISE"),
+
             // Tuple operations
             Instruction::MakeTuple(n) => write!(f, "MAKE_TUPLE {}", n),
             Instruction::GetTupleField(idx) => write!(f, "GET_TUPLE_FIELD {}", idx),
@@ -261,9 +344,15 @@ impl fmt::Display for Instruction {
             Instruction::ListHead => write!(f, "LIST_HEAD"),
             Instruction::ListTail => write!(f, "LIST_TAIL"),
             Instruction::IsNil => write!(f, "IS_NIL"),
+            Instruction::ToList => write!(f, "TO_LIST"),
+            Instruction::GetEnumerator => write!(f, "GET_ENUMERATOR"),
+            Instruction::MoveNext(idx) => write!(f, "MOVE_NEXT {}", idx),
+            Instruction::ListReverse => write!(f, "LIST_REVERSE"),
+            Instruction::MakeRange => write!(f, "MAKE_RANGE"),
 
             // Array operations
             Instruction::MakeArray(n) => write!(f, "MAKE_ARRAY {}", n),
+            Instruction::ToArray => write!(f, "TO_ARRAY"),
             Instruction::ArrayGet => write!(f, "ARRAY_GET"),
             Instruction::ArraySet => write!(f, "ARRAY_SET"),
             Instruction::ArrayLength => write!(f, "ARRAY_LENGTH"),
@@ -278,6 +367,9 @@ impl fmt::Display for Instruction {
             Instruction::MakeVariant(n) => write!(f, "MAKE_VARIANT {}", n),
             Instruction::CheckVariantTag(tag) => write!(f, "CHECK_VARIANT_TAG \"{}\"", tag),
             Instruction::GetVariantField(idx) => write!(f, "GET_VARIANT_FIELD {}", idx),
+            Instruction::DefineType(idx, is_union) => {
+                write!(f, "DEFINE_TYPE {} {}", idx, is_union)
+            }
 
             // Closure operations
             Instruction::MakeClosure(idx, count) => write!(f, "MAKE_CLOSURE {} {}", idx, count),
@@ -465,6 +557,16 @@ mod tests {
         assert_eq!(format!("{}", Instruction::Sub), "SUB");
         assert_eq!(format!("{}", Instruction::Mul), "MUL");
         assert_eq!(format!("{}", Instruction::Div), "DIV");
+        assert_eq!(format!("{}", Instruction::Mod), "MOD");
+    }
+
+    #[test]
+    fn test_display_bitwise() {
+        assert_eq!(format!("{}", Instruction::BitAnd), "BIT_AND");
+        assert_eq!(format!("{}", Instruction::BitOr), "BIT_OR");
+        assert_eq!(format!("{}", Instruction::BitXor), "BIT_XOR");
+        assert_eq!(format!("{}", Instruction::Shl), "SHL");
+        assert_eq!(format!("{}", Instruction::Shr), "SHR");
     }
 
     #[test]
@@ -502,6 +604,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_display_exception_handlers() {
+        assert_eq!(format!("{}", Instruction::PushHandler(4)), "PUSH_HANDLER 4");
+        assert_eq!(format!("{}", Instruction::PopHandler), "POP_HANDLER");
+        assert_eq!(format!("{}", Instruction::Raise), "RAISE");
+    }
+
     #[test]
     fn test_display_call() {
         assert_eq!(format!("{}", Instruction::Call(3)), "CALL 3");
@@ -554,12 +663,18 @@ mod tests {
     #[test]
     fn test_display_is_nil() {
         assert_eq!(format!("{}", Instruction::IsNil), "IS_NIL");
+        assert_eq!(format!("{}", Instruction::ToList), "TO_LIST");
+        assert_eq!(format!("{}", Instruction::GetEnumerator), "GET_ENUMERATOR");
+        assert_eq!(format!("{}", Instruction::MoveNext(2)), "MOVE_NEXT 2");
+        assert_eq!(format!("{}", Instruction::ListReverse), "LIST_REVERSE");
+        assert_eq!(format!("{}", Instruction::MakeRange), "MAKE_RANGE");
     }
 
     #[test]
     fn test_display_make_array() {
         assert_eq!(format!("{}", Instruction::MakeArray(3)), "MAKE_ARRAY 3");
         assert_eq!(format!("{}", Instruction::MakeArray(0)), "MAKE_ARRAY 0");
+        assert_eq!(format!("{}", Instruction::ToArray), "TO_ARRAY");
     }
 
     #[test]
@@ -604,6 +719,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_display_define_type() {
+        assert_eq!(
+            format!("{}", Instruction::DefineType(4, true)),
+            "DEFINE_TYPE 4 true"
+        );
+    }
+
     // ========== Clone Tests ==========
 
     #[test]
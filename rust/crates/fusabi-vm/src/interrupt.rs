@@ -0,0 +1,145 @@
+// Fusabi VM - Interruption
+// A thread-safe handle that lets a host cancel or pause a running script,
+// polled by the interpreter loop before every instruction
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+/// No request pending
+const NONE: u8 = 0;
+/// Stop at the next instruction with `VmError::Interrupted`
+const CANCEL: u8 = 1;
+/// Stop at the next instruction of a resumable run with `RunState::Paused`
+const PAUSE: u8 = 2;
+
+/// Request that a running [`Vm`](crate::Vm) stops or pauses
+///
+/// Handles are cheap to clone and can be sent to other threads, such as a UI
+/// thread that cancels a runaway script. A request is consumed by the VM
+/// once it acts on it.
+#[derive(Debug, Clone, Default)]
+pub struct InterruptHandle {
+    signal: Arc<AtomicU8>,
+}
+
+/// A request observed by the interpreter loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Interrupt {
+    Cancel,
+    Pause,
+}
+
+impl InterruptHandle {
+    /// Create a handle with no request pending
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop the script with `VmError::Interrupted`
+    ///
+    /// A cancel takes precedence over a pending pause. Requests made while
+    /// nothing runs are dropped when the next top-level execution starts.
+    pub fn interrupt(&self) {
+        self.signal.store(CANCEL, Ordering::Relaxed);
+    }
+
+    /// Pause the script so it can be resumed later
+    ///
+    /// Only runs started with `execute_resumable` can pause; others keep
+    /// the request pending until they finish. A pending cancel is not
+    /// replaced.
+    pub fn pause(&self) {
+        let _ = self
+            .signal
+            .compare_exchange(NONE, PAUSE, Ordering::Relaxed, Ordering::Relaxed);
+    }
+
+    /// Withdraw a request the VM has not acted on yet
+    pub fn clear(&self) {
+        self.signal.store(NONE, Ordering::Relaxed);
+    }
+
+    /// Whether a cancel or pause request is pending
+    pub fn is_pending(&self) -> bool {
+        self.signal.load(Ordering::Relaxed) != NONE
+    }
+
+    /// Take the pending request, if any
+    ///
+    /// The request is cleared in the same atomic step that reads it, so a
+    /// request made concurrently is never lost. A pause is left pending when
+    /// the run is not `pausable`.
+    #[inline(always)]
+    pub(crate) fn poll(&self, pausable: bool) -> Option<Interrupt> {
+        loop {
+            let current = self.signal.load(Ordering::Relaxed);
+            let request = match current {
+                NONE => return None,
+                CANCEL => Interrupt::Cancel,
+                _ if pausable => Interrupt::Pause,
+                _ => return None,
+            };
+            if self
+                .signal
+                .compare_exchange_weak(current, NONE, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+            {
+                return Some(request);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<InterruptHandle>();
+    }
+
+    #[test]
+    fn test_clones_share_requests() {
+        let handle = InterruptHandle::new();
+        let other = handle.clone();
+        assert_eq!(handle.poll(true), None);
+        other.pause();
+        assert!(handle.is_pending());
+        other.clear();
+        assert!(!handle.is_pending());
+    }
+
+    #[test]
+    fn test_poll_takes_the_request() {
+        let handle = InterruptHandle::new();
+        handle.pause();
+        assert_eq!(handle.poll(true), Some(Interrupt::Pause));
+        assert_eq!(handle.poll(true), None);
+        handle.interrupt();
+        assert_eq!(handle.poll(false), Some(Interrupt::Cancel));
+        assert!(!handle.is_pending());
+    }
+
+    #[test]
+    fn test_pause_does_not_replace_cancel() {
+        let handle = InterruptHandle::new();
+        handle.interrupt();
+        handle.pause();
+        assert_eq!(handle.poll(false), Some(Interrupt::Cancel));
+        assert!(!handle.is_pending());
+    }
+
+    #[test]
+    fn test_pause_stays_pending_for_non_pausable_runs() {
+        let handle = InterruptHandle::new();
+        handle.pause();
+        assert_eq!(handle.poll(false), None);
+        assert!(handle.is_pending());
+        // A cancel replaces the pending pause and is still delivered
+        handle.interrupt();
+        assert_eq!(handle.poll(false), Some(Interrupt::Cancel));
+        assert!(!handle.is_pending());
+    }
+}
@@ -2,11 +2,15 @@
 
 pub mod chunk;
 pub mod closure;
+pub mod context;
 pub mod conversions;
 pub mod error_reporter;
+pub mod exception;
 pub mod gc;
 pub mod host;
 pub mod instruction;
+pub mod interrupt;
+pub mod limits;
 pub mod optimized_vm;
 pub mod optimizer;
 pub mod stdlib;
@@ -19,15 +23,18 @@ pub mod async_types;
 #[cfg(feature = "async")]
 pub mod async_runtime;
 
-pub use chunk::{Chunk, ChunkBuilder, SourceSpan};
+pub use chunk::{Chunk, ChunkBuilder, SourceSpan, UpvalueSource};
 pub use closure::{Closure, Upvalue};
+pub use context::VmContext;
 pub use error_reporter::{format_error, RuntimeError};
 pub use gc::{GcHeap, GcStats, Trace, Tracer};
 pub use host::{HostFn, HostRegistry};
 pub use instruction::Instruction;
+pub use interrupt::InterruptHandle;
+pub use limits::{LimitKind, VmLimits, DEFAULT_MAX_NATIVE_STACK_BYTES, DEFAULT_MAX_STACK_DEPTH};
 pub use optimized_vm::FastVm;
-pub use value::{HostData, Value};
-pub use vm::{Frame, Vm, VmError};
+pub use value::{HostData, Seq, SeqSource, Value};
+pub use vm::{Frame, RunState, Vm, VmError};
 
 // Async re-exports (feature-gated)
 #[cfg(feature = "async")]
@@ -0,0 +1,133 @@
+// Fusabi VM - Execution Limits
+// Budgets that the interpreter loop checks cooperatively, so a host can stop
+// runaway scripts such as `while true do ()` without killing the thread
+
+use std::fmt;
+use std::time::Duration;
+
+/// Number of instructions between checks of the deadline and heap cap
+///
+/// Both checks are comparatively expensive, so they run periodically rather
+/// than on every instruction.
+pub(crate) const CHECK_INTERVAL: u64 = 1024;
+
+/// Call depth allowed by [`VmLimits::default`]
+///
+/// Deep enough for ordinary non-tail recursion, but stops a runaway
+/// recursion long before its frames exhaust the host's memory.
+pub const DEFAULT_MAX_STACK_DEPTH: usize = 100_000;
+
+/// Native stack that nested interpreter loops may use under
+/// [`VmLimits::default`]
+///
+/// Each host callback into a script closure, such as the function passed to
+/// `List.map`, runs a fresh interpreter loop on the native stack. Half of a
+/// 2 MiB thread stack leaves room for the host's own frames. How many levels
+/// of recursion through callbacks fit depends on the build, from about ten
+/// in debug builds to over a hundred in release builds.
+pub const DEFAULT_MAX_NATIVE_STACK_BYTES: usize = 1024 * 1024;
+
+/// Resource budgets enforced while a [`Vm`](crate::Vm) runs
+///
+/// By default only the call depth is capped, at [`DEFAULT_MAX_STACK_DEPTH`]
+/// frames and [`DEFAULT_MAX_NATIVE_STACK_BYTES`] of native stack for nested
+/// interpreter loops; the other limits are off. Exceeding one stops execution with
+/// [`VmError::LimitExceeded`](crate::VmError::LimitExceeded), which scripts
+/// cannot catch with `try ... with`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VmLimits {
+    /// Instructions a top-level execution may run
+    pub max_instructions: Option<u64>,
+    /// Call frames that may be active at once
+    pub max_stack_depth: Option<usize>,
+    /// Native stack bytes that interpreter loops nested inside host
+    /// callbacks may use
+    pub max_native_stack_bytes: Option<usize>,
+    /// Approximate bytes of values reachable from the stack and globals
+    pub max_heap_bytes: Option<usize>,
+    /// Wall-clock time a top-level execution may run
+    pub timeout: Option<Duration>,
+}
+
+impl Default for VmLimits {
+    fn default() -> Self {
+        VmLimits {
+            max_instructions: None,
+            max_stack_depth: Some(DEFAULT_MAX_STACK_DEPTH),
+            max_native_stack_bytes: Some(DEFAULT_MAX_NATIVE_STACK_BYTES),
+            max_heap_bytes: None,
+            timeout: None,
+        }
+    }
+}
+
+impl VmLimits {
+    /// Limits with every budget turned off, including the call depth
+    pub fn none() -> Self {
+        VmLimits {
+            max_instructions: None,
+            max_stack_depth: None,
+            max_native_stack_bytes: None,
+            max_heap_bytes: None,
+            timeout: None,
+        }
+    }
+
+    /// Set the instruction budget
+    pub fn with_max_instructions(mut self, max: u64) -> Self {
+        self.max_instructions = Some(max);
+        self
+    }
+
+    /// Set the maximum call depth
+    pub fn with_max_stack_depth(mut self, max: usize) -> Self {
+        self.max_stack_depth = Some(max);
+        self
+    }
+
+    /// Set the native stack nested interpreter loops may use, in bytes
+    pub fn with_max_native_stack_bytes(mut self, max: usize) -> Self {
+        self.max_native_stack_bytes = Some(max);
+        self
+    }
+
+    /// Set the heap cap in bytes
+    pub fn with_max_heap_bytes(mut self, max: usize) -> Self {
+        self.max_heap_bytes = Some(max);
+        self
+    }
+
+    /// Set the wall-clock time each top-level execution may run
+    ///
+    /// The deadline is computed when an execution starts, so the same limits
+    /// can be reused across runs.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+/// The budget that stopped execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitKind {
+    /// `max_instructions` was used up
+    Instructions,
+    /// A call would have exceeded `max_stack_depth` or
+    /// `max_native_stack_bytes`
+    StackDepth,
+    /// Reachable values grew past `max_heap_bytes`
+    HeapBytes,
+    /// The execution ran longer than its `timeout`
+    Deadline,
+}
+
+impl fmt::Display for LimitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitKind::Instructions => write!(f, "instruction budget"),
+            LimitKind::StackDepth => write!(f, "stack depth"),
+            LimitKind::HeapBytes => write!(f, "heap size"),
+            LimitKind::Deadline => write!(f, "deadline"),
+        }
+    }
+}
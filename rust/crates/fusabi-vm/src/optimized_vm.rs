@@ -1,18 +1,17 @@
 // Fusabi Fast VM - Optimized Bytecode Interpreter
 // Implements performance optimizations for the dispatch loop
 //
-// NOTE: This VM is optimized for pure bytecode execution without native function calls.
-// For workloads that require host/native functions, use the standard Vm.
+// FastVm wraps a standard Vm and shares its state (globals, host registry,
+// type members, upvalues, GC heap). Hot instructions run inline; everything
+// else is handed to `Vm::dispatch`, so scripts behave identically on both.
 
 use crate::chunk::Chunk;
-use crate::closure::{Closure, Upvalue};
-use crate::gc::GcHeap;
+use crate::closure::Closure;
 use crate::instruction::Instruction;
 use crate::value::Value;
-use crate::vm::{Frame, VmError};
-use std::collections::HashMap;
+use crate::vm::{RunState, Vm, VmError};
+use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
-use std::sync::Mutex;
 
 /// Default stack capacity for pre-allocation
 const DEFAULT_STACK_CAPACITY: usize = 256;
@@ -22,917 +21,177 @@ const DEFAULT_FRAME_CAPACITY: usize = 64;
 /// Fast VM - optimized bytecode interpreter
 ///
 /// Optimizations over the base Vm:
-/// - Pre-allocated stack with capacity
-/// - Inlined hot path functions
-/// - Unchecked access in inner loop with debug assertions
-/// - Reduced instruction cloning via references
+/// - Pre-allocated stack and frame capacity
+/// - Instructions matched by reference, without cloning, on the hot path
+/// - Int and float arithmetic and comparisons inlined
 ///
-/// Limitations:
-/// - Does not support native/host function calls (use standard Vm for that)
-/// - Optimized for pure bytecode workloads
+/// Derefs to [`Vm`], so host functions, the stdlib and everything else that
+/// takes a `&mut Vm` work unchanged.
 #[derive(Debug)]
 pub struct FastVm {
-    /// Value stack for operands and intermediate results (pre-allocated)
-    stack: Vec<Value>,
-    /// Call frame stack (pre-allocated)
-    frames: Vec<Frame>,
-    /// Global variables
-    pub globals: HashMap<String, Value>,
-    /// Garbage collector heap
-    pub gc_heap: GcHeap,
+    vm: Vm,
 }
 
 impl FastVm {
     /// Create a new FastVm with pre-allocated capacity
     pub fn new() -> Self {
-        FastVm {
-            stack: Vec::with_capacity(DEFAULT_STACK_CAPACITY),
-            frames: Vec::with_capacity(DEFAULT_FRAME_CAPACITY),
-            globals: HashMap::new(),
-            gc_heap: GcHeap::new(),
-        }
+        Self::with_capacity(DEFAULT_STACK_CAPACITY, DEFAULT_FRAME_CAPACITY)
     }
 
     /// Create a FastVm with custom stack capacity
     pub fn with_capacity(stack_capacity: usize, frame_capacity: usize) -> Self {
-        FastVm {
-            stack: Vec::with_capacity(stack_capacity),
-            frames: Vec::with_capacity(frame_capacity),
-            globals: HashMap::new(),
-            gc_heap: GcHeap::new(),
-        }
+        Self::from_vm(Vm::with_stack_capacity(stack_capacity), frame_capacity)
     }
 
     /// Create a FastVm with a custom GC threshold
     pub fn with_gc_threshold(threshold: usize) -> Self {
-        FastVm {
-            stack: Vec::with_capacity(DEFAULT_STACK_CAPACITY),
-            frames: Vec::with_capacity(DEFAULT_FRAME_CAPACITY),
-            globals: HashMap::new(),
-            gc_heap: GcHeap::with_threshold(threshold),
-        }
+        Self::from_vm(Vm::with_gc_threshold(threshold), DEFAULT_FRAME_CAPACITY)
     }
 
-    /// Collect garbage
-    pub fn collect_garbage(&mut self) {
-        let mut roots = Vec::new();
-        roots.extend(self.stack.iter().cloned());
-        roots.extend(self.globals.values().cloned());
-        for frame in &self.frames {
-            roots.push(Value::Closure(frame.closure.clone()));
-            for upvalue in &frame.closure.upvalues {
-                let upvalue = upvalue.lock().unwrap();
-                if let Upvalue::Closed(value) = &*upvalue {
-                    roots.push(value.clone());
-                }
-            }
-        }
-        self.gc_heap.collect(&roots);
+    fn from_vm(mut vm: Vm, frame_capacity: usize) -> Self {
+        vm.frames.reserve(frame_capacity);
+        FastVm { vm }
     }
 
-    /// Check if GC should run and trigger collection if needed
-    pub fn maybe_collect_garbage(&mut self) {
-        if self.gc_heap.should_collect() {
-            self.collect_garbage();
+    /// Execute a chunk of bytecode
+    pub fn execute(&mut self, chunk: Chunk) -> Result<Value, VmError> {
+        let closure = Arc::new(Closure::new(chunk));
+        self.vm.begin_execution();
+        let depth = self.vm.frames.len();
+        let base = self.vm.stack.len();
+        self.vm.push_frame(closure, base)?;
+
+        let result = self.run();
+        if result.is_err() {
+            self.vm.unwind_failed_call(depth, base);
         }
+        result
     }
 
-    /// Get GC statistics
-    pub fn gc_stats(&self) -> &crate::gc::GcStats {
-        &self.gc_heap.stats
+    /// Execute a chunk in a run that [`InterruptHandle::pause`] can pause
+    ///
+    /// [`InterruptHandle::pause`]: crate::InterruptHandle::pause
+    pub fn execute_resumable(&mut self, chunk: Chunk) -> Result<RunState, VmError> {
+        let run = self.vm.start_resumable(chunk)?;
+        let result = self.run_until(run.start_depth, true);
+        self.vm.finish_resumable(run, result)
     }
 
-    /// Execute a chunk of bytecode
-    pub fn execute(&mut self, chunk: Chunk) -> Result<Value, VmError> {
-        let closure = Arc::new(Closure::new(chunk));
-        let frame = Frame::new(closure, 0);
-        self.frames.push(frame);
-        self.run()
+    /// Continue a run that returned [`RunState::Paused`]
+    pub fn resume(&mut self) -> Result<RunState, VmError> {
+        let run = self.vm.take_paused()?;
+        let result = self.run_until(run.start_depth, true);
+        self.vm.finish_resumable(run, result)
     }
 
     /// Optimized interpreter loop
     pub fn run(&mut self) -> Result<Value, VmError> {
-        let start_depth = self.frames.len();
+        let start_depth = self.vm.frames.len();
+        match self.run_until(start_depth, false)? {
+            RunState::Finished(value) => Ok(value),
+            RunState::Paused => unreachable!("only resumable runs pause"),
+        }
+    }
 
-        loop {
-            // Get instruction pointer and instructions reference
-            let (ip, _instructions, closure) = {
-                let frame = self.frames.last().ok_or(VmError::NoActiveFrame)?;
-                (
-                    frame.ip,
-                    &frame.closure.chunk.instructions,
-                    frame.closure.clone(),
-                )
-            };
+    fn run_until(&mut self, start_depth: usize, pausable: bool) -> Result<RunState, VmError> {
+        self.vm.enter_run()?;
+        let state = self.run_loop(start_depth, pausable);
+        self.vm.exit_run();
+        state
+    }
 
-            // Bounds check with early return
-            if ip >= closure.chunk.instructions.len() {
-                return Err(VmError::InvalidInstructionPointer(ip));
+    fn run_loop(&mut self, start_depth: usize, pausable: bool) -> Result<RunState, VmError> {
+        // As in `Vm`, run at least one instruction before pausing
+        let mut can_pause = false;
+        loop {
+            if self.vm.tick(can_pause)? {
+                return Ok(RunState::Paused);
             }
-
-            // SAFETY: We just checked bounds above
-            let instruction = unsafe { closure.chunk.instructions.get_unchecked(ip) };
-
-            // Advance IP
-            self.frames.last_mut().unwrap().ip += 1;
-
-            // Dispatch on instruction reference (no clone for simple instructions)
-            match instruction {
+            can_pause = pausable;
+            let vm = &mut self.vm;
+            let frame = vm.frames.last_mut().ok_or(VmError::NoActiveFrame)?;
+            let instruction = frame
+                .closure
+                .chunk
+                .instructions
+                .get(frame.ip)
+                .ok_or(VmError::InvalidInstructionPointer(frame.ip))?;
+            frame.ip += 1;
+
+            // Instructions not handled inline fall through to `Vm::dispatch`
+            let fallback = match *instruction {
                 Instruction::LoadConst(idx) => {
-                    let constant = self.get_constant(*idx)?;
-                    self.push_fast(constant);
+                    let value = frame.get_constant(idx)?;
+                    vm.stack.push(value);
+                    None
                 }
-
                 Instruction::LoadLocal(idx) => {
-                    let value = self.get_local_fast(*idx)?;
-                    self.push_fast(value);
+                    let value = vm
+                        .stack
+                        .get(frame.base + idx as usize)
+                        .cloned()
+                        .ok_or(VmError::InvalidLocalIndex(idx))?;
+                    vm.stack.push(value);
+                    None
                 }
-
                 Instruction::StoreLocal(idx) => {
-                    let value = self.pop_fast()?;
-                    self.set_local_fast(*idx, value)?;
-                }
-
-                Instruction::LoadUpvalue(idx) => {
-                    let frame = self.frames.last().ok_or(VmError::NoActiveFrame)?;
-                    let upvalue = frame
-                        .closure
-                        .get_upvalue(*idx as usize)
-                        .ok_or(VmError::Runtime(format!("Invalid upvalue index: {}", idx)))?;
-                    let value = match &*upvalue.lock().unwrap() {
-                        Upvalue::Closed(v) => v.clone(),
-                        Upvalue::Open(stack_idx) => self.stack[*stack_idx].clone(),
-                    };
-                    self.push_fast(value);
-                }
-
-                Instruction::StoreUpvalue(idx) => {
-                    let value = self.pop_fast()?;
-                    let frame = self.frames.last().ok_or(VmError::NoActiveFrame)?;
-                    let upvalue = frame
-                        .closure
-                        .get_upvalue(*idx as usize)
-                        .ok_or(VmError::Runtime(format!("Invalid upvalue index: {}", idx)))?;
-                    match &mut *upvalue.lock().unwrap() {
-                        Upvalue::Closed(v) => *v = value,
-                        Upvalue::Open(stack_idx) => self.stack[*stack_idx] = value,
-                    };
-                }
-
-                Instruction::LoadGlobal(idx) => {
-                    let name_val = self.get_constant(*idx)?;
-                    let name = match name_val {
-                        Value::Str(s) => s,
-                        _ => {
-                            return Err(VmError::TypeMismatch {
-                                expected: "string (global name)",
-                                got: name_val.type_name(),
-                            })
-                        }
-                    };
-                    let value =
-                        self.globals.get(&name).cloned().ok_or_else(|| {
-                            VmError::Runtime(format!("Undefined global: {}", name))
-                        })?;
-                    self.push_fast(value);
-                }
-
-                Instruction::Pop => {
-                    self.pop_fast()?;
-                }
-
-                Instruction::Dup => {
-                    let value = self.peek_fast()?.clone();
-                    self.push_fast(value);
-                }
-
-                Instruction::CheckInt(expected) => {
-                    let value = self.peek_fast()?;
-                    let matches = matches!(value, Value::Int(n) if *n == *expected);
-                    self.push_fast(Value::Bool(matches));
-                }
-
-                Instruction::CheckBool(expected) => {
-                    let value = self.peek_fast()?;
-                    let matches = matches!(value, Value::Bool(b) if *b == *expected);
-                    self.push_fast(Value::Bool(matches));
-                }
-
-                Instruction::CheckString(expected) => {
-                    let value = self.peek_fast()?;
-                    let matches = matches!(value, Value::Str(s) if s == expected);
-                    self.push_fast(Value::Bool(matches));
-                }
-
-                Instruction::CheckTupleLen(expected) => {
-                    let value = self.peek_fast()?;
-                    let matches = if let Value::Tuple(elements) = value {
-                        elements.len() == *expected as usize
-                    } else {
-                        false
-                    };
-                    self.push_fast(Value::Bool(matches));
-                }
-
-                Instruction::GetTupleElem(index) => {
-                    let value = self.peek_fast()?;
-                    if let Value::Tuple(elements) = value {
-                        if (*index as usize) < elements.len() {
-                            self.push_fast(elements[*index as usize].clone());
-                        } else {
-                            return Err(VmError::Runtime("Tuple index out of bounds".into()));
-                        }
-                    } else {
-                        return Err(VmError::Runtime("Not a tuple".into()));
-                    }
-                }
-
-                Instruction::Add => {
-                    let b = self.pop_fast()?;
-                    let a = self.pop_fast()?;
-                    let result = self.binary_add(a, b)?;
-                    self.push_fast(result);
-                }
-
-                Instruction::Sub => {
-                    let b = self.pop_fast()?;
-                    let a = self.pop_fast()?;
-                    let result = self.binary_sub(a, b)?;
-                    self.push_fast(result);
-                }
-
-                Instruction::Mul => {
-                    let b = self.pop_fast()?;
-                    let a = self.pop_fast()?;
-                    let result = self.binary_mul(a, b)?;
-                    self.push_fast(result);
-                }
-
-                Instruction::Div => {
-                    let b = self.pop_fast()?;
-                    let a = self.pop_fast()?;
-                    let result = self.binary_div(a, b)?;
-                    self.push_fast(result);
-                }
-
-                Instruction::Concat => {
-                    let b = self.pop_fast()?;
-                    let a = self.pop_fast()?;
-                    match (a, b) {
-                        (Value::Str(a), Value::Str(b)) => {
-                            let mut result = a;
-                            result.push_str(&b);
-                            self.push_fast(Value::Str(result))
-                        }
-                        (a, b) => {
-                            return Err(VmError::Runtime(format!(
-                                "Type mismatch in concatenation: {} ++ {}",
-                                a.type_name(),
-                                b.type_name()
-                            )))
-                        }
+                    let value = vm.stack.pop().ok_or(VmError::StackUnderflow)?;
+                    let slot = frame.base + idx as usize;
+                    if vm.stack.len() <= slot {
+                        vm.stack.resize(slot + 1, Value::Unit);
                     }
+                    vm.stack[slot] = value;
+                    None
                 }
-
-                Instruction::Eq => {
-                    let b = self.pop_fast()?;
-                    let a = self.pop_fast()?;
-                    self.push_fast(Value::Bool(a == b));
-                }
-
-                Instruction::Neq => {
-                    let b = self.pop_fast()?;
-                    let a = self.pop_fast()?;
-                    self.push_fast(Value::Bool(a != b));
-                }
-
-                Instruction::Lt => {
-                    let b = self.pop_fast()?;
-                    let a = self.pop_fast()?;
-                    let result = self.binary_cmp_lt(a, b)?;
-                    self.push_fast(result);
-                }
-
-                Instruction::Lte => {
-                    let b = self.pop_fast()?;
-                    let a = self.pop_fast()?;
-                    let result = self.binary_cmp_lte(a, b)?;
-                    self.push_fast(result);
-                }
-
-                Instruction::Gt => {
-                    let b = self.pop_fast()?;
-                    let a = self.pop_fast()?;
-                    let result = self.binary_cmp_gt(a, b)?;
-                    self.push_fast(result);
-                }
-
-                Instruction::Gte => {
-                    let b = self.pop_fast()?;
-                    let a = self.pop_fast()?;
-                    let result = self.binary_cmp_gte(a, b)?;
-                    self.push_fast(result);
-                }
-
-                Instruction::And => {
-                    let b = self.pop_bool_fast()?;
-                    let a = self.pop_bool_fast()?;
-                    self.push_fast(Value::Bool(a && b));
-                }
-
-                Instruction::Or => {
-                    let b = self.pop_bool_fast()?;
-                    let a = self.pop_bool_fast()?;
-                    self.push_fast(Value::Bool(a || b));
-                }
-
-                Instruction::Not => {
-                    let a = self.pop_bool_fast()?;
-                    self.push_fast(Value::Bool(!a));
+                Instruction::Pop => {
+                    vm.stack.pop().ok_or(VmError::StackUnderflow)?;
+                    None
                 }
-
                 Instruction::Jump(offset) => {
-                    self.jump_fast(*offset)?;
+                    jump(
+                        &mut frame.ip,
+                        frame.closure.chunk.instructions.len(),
+                        offset,
+                    )?;
+                    None
                 }
-
                 Instruction::JumpIfFalse(offset) => {
-                    let condition = self.pop_fast()?;
+                    let condition = vm.stack.pop().ok_or(VmError::StackUnderflow)?;
                     if !condition.is_truthy() {
-                        self.jump_fast(*offset)?;
-                    }
-                }
-
-                Instruction::MakeTuple(n) => {
-                    let n = *n as usize;
-                    let mut elements = Vec::with_capacity(n);
-                    for _ in 0..n {
-                        elements.push(self.pop_fast()?);
-                    }
-                    elements.reverse();
-                    self.push_fast(Value::Tuple(elements));
-                }
-
-                Instruction::GetTupleField(idx) => {
-                    let value = self.pop_fast()?;
-                    match value.as_tuple() {
-                        Some(elements) => {
-                            let index = *idx as usize;
-                            if index >= elements.len() {
-                                return Err(VmError::InvalidTupleFieldIndex {
-                                    index: *idx,
-                                    tuple_size: elements.len(),
-                                });
-                            }
-                            self.push_fast(elements[index].clone());
-                        }
-                        None => {
-                            return Err(VmError::TypeMismatch {
-                                expected: "tuple",
-                                got: value.type_name(),
-                            });
-                        }
-                    }
-                }
-
-                Instruction::MakeClosure(idx, upvalue_count) => {
-                    let constant = self.get_constant(*idx)?;
-                    let prototype = match constant.as_closure() {
-                        Some(c) => c,
-                        None => {
-                            return Err(VmError::TypeMismatch {
-                                expected: "closure",
-                                got: constant.type_name(),
-                            })
-                        }
-                    };
-
-                    let mut closure = Closure::new(prototype.chunk.clone());
-                    closure.arity = prototype.arity;
-                    closure.name = prototype.name.clone();
-
-                    for _ in 0..*upvalue_count {
-                        self.pop_fast()?;
-                    }
-
-                    self.push_fast(Value::Closure(Arc::new(closure)));
-                }
-
-                Instruction::Call(argc) => {
-                    self.execute_call(*argc)?;
-                }
-
-                Instruction::CallMethod(method_name_idx, argc) => {
-                    self.execute_call_method(*method_name_idx, *argc)?;
-                }
-
-                Instruction::TailCall(_argc) => {
-                    // TODO: Implement tail call optimization
-                    unimplemented!("TailCall not yet implemented in FastVm")
-                }
-
-                Instruction::CloseUpvalue(_) => {
-                    // Placeholder
-                }
-
-                Instruction::Return => {
-                    self.frames.pop();
-                    if self.frames.len() < start_depth {
-                        return Ok(self.stack.pop().unwrap_or(Value::Unit));
-                    }
-                }
-
-                Instruction::MakeList(n) => {
-                    let n = *n as usize;
-                    let mut elements = Vec::with_capacity(n);
-                    for _ in 0..n {
-                        elements.push(self.pop_fast()?);
-                    }
-                    elements.reverse();
-                    let list = Value::vec_to_cons(elements);
-                    self.push_fast(list);
-                }
-
-                Instruction::Cons => {
-                    let tail = self.pop_fast()?;
-                    let head = self.pop_fast()?;
-                    self.push_fast(Value::Cons {
-                        head: Box::new(head),
-                        tail: Box::new(tail),
-                    });
-                }
-
-                Instruction::ListHead => {
-                    let value = self.pop_fast()?;
-                    match value {
-                        Value::Cons { head, .. } => self.push_fast(*head),
-                        Value::Nil => return Err(VmError::EmptyList),
-                        _ => {
-                            return Err(VmError::TypeMismatch {
-                                expected: "list",
-                                got: value.type_name(),
-                            })
-                        }
-                    }
-                }
-
-                Instruction::ListTail => {
-                    let value = self.pop_fast()?;
-                    match value {
-                        Value::Cons { tail, .. } => self.push_fast(*tail),
-                        Value::Nil => return Err(VmError::EmptyList),
-                        _ => {
-                            return Err(VmError::TypeMismatch {
-                                expected: "list",
-                                got: value.type_name(),
-                            })
-                        }
-                    }
-                }
-
-                Instruction::IsNil => {
-                    let value = self.pop_fast()?;
-                    self.push_fast(Value::Bool(value.is_nil()));
-                }
-
-                Instruction::MakeArray(n) => {
-                    let n = *n as usize;
-                    let mut elements = Vec::with_capacity(n);
-                    for _ in 0..n {
-                        elements.push(self.pop_fast()?);
-                    }
-                    elements.reverse();
-                    let array = Value::Array(Arc::new(Mutex::new(elements)));
-                    self.push_fast(array);
-                }
-
-                Instruction::ArrayGet => {
-                    let index = self.pop_fast()?;
-                    let array = self.pop_fast()?;
-                    let idx = self.extract_index(&index)?;
-                    let value = array.array_get(idx).map_err(VmError::Runtime)?;
-                    self.push_fast(value);
-                }
-
-                Instruction::ArraySet => {
-                    let value = self.pop_fast()?;
-                    let index = self.pop_fast()?;
-                    let array = self.pop_fast()?;
-                    let idx = self.extract_index(&index)?;
-                    array.array_set(idx, value).map_err(VmError::Runtime)?;
-                    self.push_fast(Value::Unit);
-                }
-
-                Instruction::ArrayLength => {
-                    let array = self.pop_fast()?;
-                    let len = array.array_length().map_err(VmError::Runtime)?;
-                    self.push_fast(Value::Int(len));
-                }
-
-                Instruction::ArrayUpdate => {
-                    let value = self.pop_fast()?;
-                    let index = self.pop_fast()?;
-                    let array = self.pop_fast()?;
-                    let idx = self.extract_index(&index)?;
-
-                    let new_arr = if let Value::Array(arr) = &array {
-                        let mut new_elements = arr.lock().unwrap().clone();
-                        if idx >= new_elements.len() {
-                            return Err(VmError::Runtime(format!("Index {} out of bounds", idx)));
-                        }
-                        new_elements[idx] = value;
-                        Value::Array(Arc::new(Mutex::new(new_elements)))
-                    } else {
-                        return Err(VmError::TypeMismatch {
-                            expected: "array",
-                            got: array.type_name(),
-                        });
-                    };
-                    self.push_fast(new_arr);
-                }
-
-                Instruction::MakeRecord(n) => {
-                    let n = *n as usize;
-                    let mut fields = HashMap::new();
-                    for _ in 0..n {
-                        let field_value = self.pop_fast()?;
-                        let field_name = self.pop_fast()?;
-                        let field_name_str =
-                            field_name.as_str().ok_or_else(|| VmError::TypeMismatch {
-                                expected: "string",
-                                got: field_name.type_name(),
-                            })?;
-                        fields.insert(field_name_str.to_string(), field_value);
+                        jump(
+                            &mut frame.ip,
+                            frame.closure.chunk.instructions.len(),
+                            offset,
+                        )?;
                     }
-                    let record = Value::Record(Arc::new(Mutex::new(fields)));
-                    self.push_fast(record);
-                }
-
-                Instruction::GetRecordField => {
-                    let field_name = self.pop_fast()?;
-                    let record = self.pop_fast()?;
-                    let field_name_str =
-                        field_name.as_str().ok_or_else(|| VmError::TypeMismatch {
-                            expected: "string",
-                            got: field_name.type_name(),
-                        })?;
-                    let field_value = record
-                        .record_get(field_name_str)
-                        .map_err(VmError::Runtime)?;
-                    self.push_fast(field_value);
-                }
-
-                Instruction::UpdateRecord(n) => {
-                    let n = *n as usize;
-                    let mut updates = HashMap::new();
-                    for _ in 0..n {
-                        let field_value = self.pop_fast()?;
-                        let field_name = self.pop_fast()?;
-                        let field_name_str =
-                            field_name.as_str().ok_or_else(|| VmError::TypeMismatch {
-                                expected: "string",
-                                got: field_name.type_name(),
-                            })?;
-                        updates.insert(field_name_str.to_string(), field_value);
-                    }
-                    let record = self.pop_fast()?;
-                    let new_record = record.record_update(updates).map_err(VmError::Runtime)?;
-                    self.push_fast(new_record);
-                }
-
-                Instruction::MakeVariant(n) => {
-                    let n = *n as usize;
-                    let mut fields = Vec::with_capacity(n);
-                    for _ in 0..n {
-                        fields.push(self.pop_fast()?);
-                    }
-                    fields.reverse();
-
-                    let variant_name = self.pop_fast()?;
-                    let type_name = self.pop_fast()?;
-
-                    let type_name_str =
-                        type_name.as_str().ok_or_else(|| VmError::TypeMismatch {
-                            expected: "string",
-                            got: type_name.type_name(),
-                        })?;
-                    let variant_name_str =
-                        variant_name.as_str().ok_or_else(|| VmError::TypeMismatch {
-                            expected: "string",
-                            got: variant_name.type_name(),
-                        })?;
-
-                    let variant = Value::Variant {
-                        type_name: type_name_str.to_string(),
-                        variant_name: variant_name_str.to_string(),
-                        fields,
-                    };
-                    self.push_fast(variant);
-                }
-
-                Instruction::CheckVariantTag(tag) => {
-                    let variant = self.pop_fast()?;
-                    let matches = variant.is_variant_named(tag);
-                    self.push_fast(Value::Bool(matches));
-                }
-
-                Instruction::GetVariantField(idx) => {
-                    let variant = self.pop_fast()?;
-                    let field_value = variant
-                        .variant_get_field(*idx as usize)
-                        .map_err(VmError::Runtime)?;
-                    self.push_fast(field_value);
-                }
-            }
-        }
-    }
-
-    // ========== Inlined Stack Operations ==========
-
-    #[inline(always)]
-    fn push_fast(&mut self, value: Value) {
-        self.stack.push(value);
-    }
-
-    #[inline(always)]
-    fn pop_fast(&mut self) -> Result<Value, VmError> {
-        self.stack.pop().ok_or(VmError::StackUnderflow)
-    }
-
-    #[inline(always)]
-    fn peek_fast(&self) -> Result<&Value, VmError> {
-        self.stack.last().ok_or(VmError::StackUnderflow)
-    }
-
-    #[inline(always)]
-    fn pop_bool_fast(&mut self) -> Result<bool, VmError> {
-        let value = self.pop_fast()?;
-        value.as_bool().ok_or(VmError::TypeMismatch {
-            expected: "bool",
-            got: value.type_name(),
-        })
-    }
-
-    // ========== Inlined Local Variable Access ==========
-
-    #[inline(always)]
-    fn get_local_fast(&self, idx: u8) -> Result<Value, VmError> {
-        let frame = self.frames.last().ok_or(VmError::NoActiveFrame)?;
-        let local_idx = frame.base + idx as usize;
-
-        debug_assert!(local_idx < self.stack.len(), "Local index out of bounds");
-
-        if local_idx < self.stack.len() {
-            // SAFETY: Bounds checked in debug, and we return error in release if invalid
-            Ok(unsafe { self.stack.get_unchecked(local_idx).clone() })
-        } else {
-            Err(VmError::InvalidLocalIndex(idx))
-        }
-    }
-
-    #[inline(always)]
-    fn set_local_fast(&mut self, idx: u8, value: Value) -> Result<(), VmError> {
-        let frame = self.frames.last().ok_or(VmError::NoActiveFrame)?;
-        let local_idx = frame.base + idx as usize;
-
-        while self.stack.len() <= local_idx {
-            self.stack.push(Value::Unit);
-        }
-
-        debug_assert!(local_idx < self.stack.len(), "Local index out of bounds");
-        self.stack[local_idx] = value;
-        Ok(())
-    }
-
-    // ========== Inlined Constant Access ==========
-
-    #[inline(always)]
-    fn get_constant(&self, idx: u16) -> Result<Value, VmError> {
-        let frame = self.frames.last().ok_or(VmError::NoActiveFrame)?;
-        frame
-            .closure
-            .chunk
-            .constant_at(idx)
-            .cloned()
-            .ok_or(VmError::InvalidConstantIndex(idx))
-    }
-
-    // ========== Inlined Jump ==========
-
-    #[inline(always)]
-    fn jump_fast(&mut self, offset: i16) -> Result<(), VmError> {
-        let frame = self.frames.last_mut().ok_or(VmError::NoActiveFrame)?;
-        let new_ip = if offset >= 0 {
-            frame.ip.wrapping_add(offset as usize)
-        } else {
-            frame.ip.wrapping_sub((-offset) as usize)
-        };
-
-        if new_ip > frame.closure.chunk.instructions.len() {
-            return Err(VmError::InvalidInstructionPointer(new_ip));
-        }
-
-        frame.ip = new_ip;
-        Ok(())
-    }
-
-    // ========== Inlined Arithmetic Operations ==========
-
-    #[inline(always)]
-    fn binary_add(&self, a: Value, b: Value) -> Result<Value, VmError> {
-        match (a, b) {
-            (Value::Int(a), Value::Int(b)) => Ok(Value::Int(a + b)),
-            (Value::Float(a), Value::Float(b)) => Ok(Value::Float(a + b)),
-            (a, b) => Err(VmError::Runtime(format!(
-                "Type mismatch in addition: {} + {}",
-                a.type_name(),
-                b.type_name()
-            ))),
-        }
-    }
-
-    #[inline(always)]
-    fn binary_sub(&self, a: Value, b: Value) -> Result<Value, VmError> {
-        match (a, b) {
-            (Value::Int(a), Value::Int(b)) => Ok(Value::Int(a - b)),
-            (Value::Float(a), Value::Float(b)) => Ok(Value::Float(a - b)),
-            (a, b) => Err(VmError::Runtime(format!(
-                "Type mismatch in subtraction: {} - {}",
-                a.type_name(),
-                b.type_name()
-            ))),
-        }
-    }
-
-    #[inline(always)]
-    fn binary_mul(&self, a: Value, b: Value) -> Result<Value, VmError> {
-        match (a, b) {
-            (Value::Int(a), Value::Int(b)) => Ok(Value::Int(a * b)),
-            (Value::Float(a), Value::Float(b)) => Ok(Value::Float(a * b)),
-            (a, b) => Err(VmError::Runtime(format!(
-                "Type mismatch in multiplication: {} * {}",
-                a.type_name(),
-                b.type_name()
-            ))),
-        }
-    }
-
-    #[inline(always)]
-    fn binary_div(&self, a: Value, b: Value) -> Result<Value, VmError> {
-        match (a, b) {
-            (Value::Int(a), Value::Int(b)) => {
-                if b == 0 {
-                    Err(VmError::DivisionByZero)
-                } else {
-                    Ok(Value::Int(a / b))
-                }
-            }
-            (Value::Float(a), Value::Float(b)) => {
-                if b == 0.0 {
-                    Err(VmError::DivisionByZero)
-                } else {
-                    Ok(Value::Float(a / b))
-                }
-            }
-            (a, b) => Err(VmError::Runtime(format!(
-                "Type mismatch in division: {} / {}",
-                a.type_name(),
-                b.type_name()
-            ))),
-        }
-    }
-
-    // ========== Inlined Comparison Operations ==========
-
-    #[inline(always)]
-    fn binary_cmp_lt(&self, a: Value, b: Value) -> Result<Value, VmError> {
-        match (a, b) {
-            (Value::Int(a), Value::Int(b)) => Ok(Value::Bool(a < b)),
-            (Value::Float(a), Value::Float(b)) => Ok(Value::Bool(a < b)),
-            (a, b) => Err(VmError::Runtime(format!(
-                "Type mismatch in comparison: {} < {}",
-                a.type_name(),
-                b.type_name()
-            ))),
-        }
-    }
-
-    #[inline(always)]
-    fn binary_cmp_lte(&self, a: Value, b: Value) -> Result<Value, VmError> {
-        match (a, b) {
-            (Value::Int(a), Value::Int(b)) => Ok(Value::Bool(a <= b)),
-            (Value::Float(a), Value::Float(b)) => Ok(Value::Bool(a <= b)),
-            (a, b) => Err(VmError::Runtime(format!(
-                "Type mismatch in comparison: {} <= {}",
-                a.type_name(),
-                b.type_name()
-            ))),
-        }
-    }
-
-    #[inline(always)]
-    fn binary_cmp_gt(&self, a: Value, b: Value) -> Result<Value, VmError> {
-        match (a, b) {
-            (Value::Int(a), Value::Int(b)) => Ok(Value::Bool(a > b)),
-            (Value::Float(a), Value::Float(b)) => Ok(Value::Bool(a > b)),
-            (a, b) => Err(VmError::Runtime(format!(
-                "Type mismatch in comparison: {} > {}",
-                a.type_name(),
-                b.type_name()
-            ))),
-        }
-    }
-
-    #[inline(always)]
-    fn binary_cmp_gte(&self, a: Value, b: Value) -> Result<Value, VmError> {
-        match (a, b) {
-            (Value::Int(a), Value::Int(b)) => Ok(Value::Bool(a >= b)),
-            (Value::Float(a), Value::Float(b)) => Ok(Value::Bool(a >= b)),
-            (a, b) => Err(VmError::Runtime(format!(
-                "Type mismatch in comparison: {} >= {}",
-                a.type_name(),
-                b.type_name()
-            ))),
-        }
-    }
-
-    // ========== Helper Methods ==========
+                    None
+                }
+                Instruction::Add
+                | Instruction::Sub
+                | Instruction::Mul
+                | Instruction::Lt
+                | Instruction::Lte
+                | Instruction::Gt
+                | Instruction::Gte
+                | Instruction::Eq
+                | Instruction::Neq
+                    if binary_fast(&mut vm.stack, instruction) =>
+                {
+                    None
+                }
+                _ => Some(instruction.clone()),
+            };
 
-    #[inline(always)]
-    fn extract_index(&self, value: &Value) -> Result<usize, VmError> {
-        match value {
-            Value::Int(i) => {
-                if *i < 0 {
-                    Err(VmError::TypeMismatch {
-                        expected: "non-negative int",
-                        got: "negative int",
-                    })
-                } else {
-                    Ok(*i as usize)
+            if let Some(instruction) = fallback {
+                match self.vm.dispatch(instruction, start_depth) {
+                    Ok(Some(result)) => return Ok(RunState::Finished(result)),
+                    Ok(None) => {}
+                    Err(error) => self.vm.throw_error(error, start_depth)?,
                 }
             }
-            _ => Err(VmError::TypeMismatch {
-                expected: "int",
-                got: value.type_name(),
-            }),
         }
     }
 
-    fn execute_call(&mut self, argc: u8) -> Result<(), VmError> {
-        let func_idx = self
-            .stack
-            .len()
-            .checked_sub(1 + argc as usize)
-            .ok_or(VmError::StackUnderflow)?;
-        let func = self.stack[func_idx].clone();
-
-        match func {
-            Value::Closure(closure) => {
-                if closure.arity != argc {
-                    return Err(VmError::Runtime(format!(
-                        "Arity mismatch: expected {}, got {}",
-                        closure.arity, argc
-                    )));
-                }
-                let base = func_idx + 1;
-                let frame = Frame::new(closure, base);
-                self.frames.push(frame);
-                Ok(())
-            }
-            Value::NativeFn { name, .. } => {
-                // FastVm does not support native function calls
-                // Use the standard Vm for workloads requiring host functions
-                Err(VmError::Runtime(format!(
-                    "FastVm does not support native function calls: '{}'. Use standard Vm instead.",
-                    name
-                )))
-            }
-            _ => Err(VmError::TypeMismatch {
-                expected: "function",
-                got: func.type_name(),
-            }),
-        }
-    }
-
-    fn execute_call_method(&mut self, method_name_idx: u16, _argc: u8) -> Result<(), VmError> {
-        let method_name_val = self.get_constant(method_name_idx)?;
-        let method_name = match method_name_val {
-            Value::Str(s) => s,
-            _ => {
-                return Err(VmError::TypeMismatch {
-                    expected: "string (method name)",
-                    got: method_name_val.type_name(),
-                })
-            }
-        };
-
-        // FastVm does not support method calls on host data
-        // Use the standard Vm for workloads requiring host functions
-        Err(VmError::Runtime(format!(
-            "FastVm does not support method calls: '{}'. Use standard Vm instead.",
-            method_name
-        )))
-    }
-
     /// Call a closure from Rust code (re-entrant)
     pub fn call_closure(
         &mut self,
@@ -947,39 +206,111 @@ impl FastVm {
             )));
         }
 
-        for arg in args {
-            self.push_fast(arg.clone());
+        self.vm.begin_execution();
+        let base = self.vm.stack.len();
+        self.vm.stack.extend_from_slice(args);
+        let depth = self.vm.frames.len();
+        if let Err(error) = self.vm.push_frame(closure, base) {
+            self.vm.stack.truncate(base);
+            return Err(error);
         }
 
-        let base = self.stack.len() - args.len();
-        let frame = Frame::new(closure, base);
-        self.frames.push(frame);
-        self.run()
+        let result = self.run();
+        if result.is_err() {
+            self.vm.unwind_failed_call(depth, base);
+        }
+        result
     }
 
-    /// Call any callable value from Rust code
+    /// Call any callable value (Closure or NativeFn) from Rust code
     pub fn call_value(&mut self, func: Value, args: &[Value]) -> Result<Value, VmError> {
         match func {
             Value::Closure(closure) => self.call_closure(closure, args),
-            Value::NativeFn { name, .. } => Err(VmError::Runtime(format!(
-                "FastVm does not support native function calls: '{}'. Use standard Vm instead.",
-                name
-            ))),
-            _ => Err(VmError::TypeMismatch {
-                expected: "function",
-                got: func.type_name(),
-            }),
+            other => self.vm.call_value(other, args),
         }
     }
 
-    /// Get the current stack size (for debugging)
-    pub fn stack_size(&self) -> usize {
-        self.stack.len()
+    /// Unwrap into the underlying standard Vm
+    pub fn into_inner(self) -> Vm {
+        self.vm
+    }
+}
+
+/// Move `ip` by a signed offset, validating it against the chunk length
+#[inline(always)]
+fn jump(ip: &mut usize, len: usize, offset: i16) -> Result<(), VmError> {
+    let new_ip = if offset >= 0 {
+        ip.wrapping_add(offset as usize)
+    } else {
+        ip.wrapping_sub((-offset) as usize)
+    };
+    if new_ip > len {
+        return Err(VmError::InvalidInstructionPointer(new_ip));
+    }
+    *ip = new_ip;
+    Ok(())
+}
+
+/// Apply a binary instruction in place when both operands are ints or both
+/// are floats
+///
+/// Returns false without touching the stack for any other operands, which
+/// leaves overloaded operators and type errors to `Vm::dispatch`.
+#[inline(always)]
+fn binary_fast(stack: &mut Vec<Value>, instruction: &Instruction) -> bool {
+    let len = stack.len();
+    if len < 2 {
+        return false;
+    }
+    let result = match (&stack[len - 2], &stack[len - 1]) {
+        (Value::Int(a), Value::Int(b)) => {
+            let (a, b) = (*a, *b);
+            match instruction {
+                Instruction::Add => Value::Int(a + b),
+                Instruction::Sub => Value::Int(a - b),
+                Instruction::Mul => Value::Int(a * b),
+                Instruction::Lt => Value::Bool(a < b),
+                Instruction::Lte => Value::Bool(a <= b),
+                Instruction::Gt => Value::Bool(a > b),
+                Instruction::Gte => Value::Bool(a >= b),
+                Instruction::Eq => Value::Bool(a == b),
+                Instruction::Neq => Value::Bool(a != b),
+                _ => return false,
+            }
+        }
+        (Value::Float(a), Value::Float(b)) => {
+            let (a, b) = (*a, *b);
+            match instruction {
+                Instruction::Add => Value::Float(a + b),
+                Instruction::Sub => Value::Float(a - b),
+                Instruction::Mul => Value::Float(a * b),
+                Instruction::Lt => Value::Bool(a < b),
+                Instruction::Lte => Value::Bool(a <= b),
+                Instruction::Gt => Value::Bool(a > b),
+                Instruction::Gte => Value::Bool(a >= b),
+                Instruction::Eq => Value::Bool(a == b),
+                Instruction::Neq => Value::Bool(a != b),
+                _ => return false,
+            }
+        }
+        _ => return false,
+    };
+    stack.truncate(len - 2);
+    stack.push(result);
+    true
+}
+
+impl Deref for FastVm {
+    type Target = Vm;
+
+    fn deref(&self) -> &Vm {
+        &self.vm
     }
+}
 
-    /// Get the current frame count (for debugging)
-    pub fn frame_count(&self) -> usize {
-        self.frames.len()
+impl DerefMut for FastVm {
+    fn deref_mut(&mut self) -> &mut Vm {
+        &mut self.vm
     }
 }
 
@@ -1053,6 +384,103 @@ mod tests {
         assert_eq!(result, Value::Tuple(vec![Value::Int(1), Value::Int(2)]));
     }
 
+    /// `sum n acc = if n = 0 then acc else sum (n - 1) (acc + n)`, with the
+    /// recursive call made by `call`
+    fn sum_to(call: Instruction) -> Value {
+        let mut chunk = ChunkBuilder::new()
+            .constant(Value::Int(0))
+            .constant(Value::Str("sum".to_string()))
+            .constant(Value::Int(1))
+            .instruction(Instruction::LoadLocal(0))
+            .instruction(Instruction::LoadConst(0))
+            .instruction(Instruction::Eq)
+            .instruction(Instruction::JumpIfFalse(2))
+            .instruction(Instruction::LoadLocal(1))
+            .instruction(Instruction::Return)
+            .instruction(Instruction::LoadGlobal(1))
+            .instruction(Instruction::LoadLocal(0))
+            .instruction(Instruction::LoadConst(2))
+            .instruction(Instruction::Sub)
+            .instruction(Instruction::LoadLocal(1))
+            .instruction(Instruction::LoadLocal(0))
+            .instruction(Instruction::Add)
+            .instruction(call)
+            .instruction(Instruction::Return)
+            .build();
+        chunk.local_count = 2;
+
+        let mut vm = FastVm::with_capacity(DEFAULT_STACK_CAPACITY, 2);
+        vm.globals.insert(
+            "sum".to_string(),
+            Value::Closure(Arc::new(Closure::with_arity(chunk, 2))),
+        );
+        let main = ChunkBuilder::new()
+            .constant(Value::Str("sum".to_string()))
+            .constant(Value::Int(1000))
+            .constant(Value::Int(0))
+            .instruction(Instruction::LoadGlobal(0))
+            .instruction(Instruction::LoadConst(1))
+            .instruction(Instruction::LoadConst(2))
+            .instruction(Instruction::Call(2))
+            .instruction(Instruction::Return)
+            .build();
+        let result = vm.execute(main).unwrap();
+        assert_eq!(vm.stack_size(), 0);
+        result
+    }
+
+    #[test]
+    fn test_fast_vm_tail_call() {
+        assert_eq!(sum_to(Instruction::Call(2)), Value::Int(500500));
+        assert_eq!(sum_to(Instruction::TailCall(2)), Value::Int(500500));
+    }
+
+    #[test]
+    fn test_fast_vm_native_partial_application() {
+        let mut vm = FastVm::new();
+        vm.host_registry
+            .lock()
+            .unwrap()
+            .register("add", |_, args| match (&args[0], &args[1]) {
+                (Value::Int(a), Value::Int(b)) => Ok(Value::Int(a + b)),
+                _ => Err(VmError::Runtime("add expects ints".into())),
+            });
+        vm.globals.insert(
+            "add".to_string(),
+            Value::NativeFn {
+                name: "add".to_string(),
+                arity: 2,
+                args: vec![],
+            },
+        );
+        let chunk = ChunkBuilder::new()
+            .constant(Value::Str("add".to_string()))
+            .constant(Value::Int(40))
+            .constant(Value::Int(2))
+            .instruction(Instruction::LoadGlobal(0))
+            .instruction(Instruction::LoadConst(1))
+            .instruction(Instruction::Call(1))
+            .instruction(Instruction::LoadConst(2))
+            .instruction(Instruction::Call(1))
+            .instruction(Instruction::Return)
+            .build();
+        assert_eq!(vm.execute(chunk).unwrap(), Value::Int(42));
+    }
+
+    #[test]
+    fn test_fast_vm_mixed_operands_fall_back() {
+        let mut vm = FastVm::new();
+        let chunk = ChunkBuilder::new()
+            .constant(Value::Int(1))
+            .constant(Value::Float(2.0))
+            .instruction(Instruction::LoadConst(0))
+            .instruction(Instruction::LoadConst(1))
+            .instruction(Instruction::Add)
+            .instruction(Instruction::Return)
+            .build();
+        assert!(matches!(vm.execute(chunk), Err(VmError::Runtime(_))));
+    }
+
     #[test]
     fn test_fast_vm_pre_allocated_capacity() {
         let vm = FastVm::with_capacity(512, 128);
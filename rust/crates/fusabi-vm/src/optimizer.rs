@@ -50,6 +50,30 @@ fn fold_arithmetic(chunk: &mut Chunk) -> bool {
                         .or_else(|| fold_binary_float(chunk, *a_idx, *b_idx, |a, b| a / b)),
                 }
             }
+            (Instruction::LoadConst(a_idx), Instruction::LoadConst(b_idx), Instruction::Mod) => {
+                match (chunk.constant_at(*a_idx), chunk.constant_at(*b_idx)) {
+                    (Some(Value::Int(_)), Some(Value::Int(0))) => None,
+                    (Some(Value::Float(_)), Some(Value::Float(b))) if *b == 0.0 => None,
+                    _ => fold_binary_int(chunk, *a_idx, *b_idx, |a, b| Some(a.wrapping_rem(b)))
+                        .or_else(|| fold_binary_float(chunk, *a_idx, *b_idx, |a, b| a % b)),
+                }
+            }
+            (Instruction::LoadConst(a_idx), Instruction::LoadConst(b_idx), Instruction::BitAnd) => {
+                fold_binary_int(chunk, *a_idx, *b_idx, |a, b| Some(a & b))
+            }
+            (Instruction::LoadConst(a_idx), Instruction::LoadConst(b_idx), Instruction::BitOr) => {
+                fold_binary_int(chunk, *a_idx, *b_idx, |a, b| Some(a | b))
+            }
+            (Instruction::LoadConst(a_idx), Instruction::LoadConst(b_idx), Instruction::BitXor) => {
+                fold_binary_int(chunk, *a_idx, *b_idx, |a, b| Some(a ^ b))
+            }
+            // Shift counts wrap modulo 64, matching the VM
+            (Instruction::LoadConst(a_idx), Instruction::LoadConst(b_idx), Instruction::Shl) => {
+                fold_binary_int(chunk, *a_idx, *b_idx, |a, b| Some(a.wrapping_shl(b as u32)))
+            }
+            (Instruction::LoadConst(a_idx), Instruction::LoadConst(b_idx), Instruction::Shr) => {
+                fold_binary_int(chunk, *a_idx, *b_idx, |a, b| Some(a.wrapping_shr(b as u32)))
+            }
             _ => None,
         };
 
@@ -177,6 +201,7 @@ fn fold_equality(chunk: &Chunk, a_idx: u16, b_idx: u16) -> Option<Value> {
         (Some(Value::Float(a)), Some(Value::Float(b))) => Some(Value::Bool(a == b)),
         (Some(Value::Bool(a)), Some(Value::Bool(b))) => Some(Value::Bool(a == b)),
         (Some(Value::Str(a)), Some(Value::Str(b))) => Some(Value::Bool(a == b)),
+        (Some(Value::Char(a)), Some(Value::Char(b))) => Some(Value::Bool(a == b)),
         (Some(Value::Unit), Some(Value::Unit)) => Some(Value::Bool(true)),
         _ => None,
     }
@@ -188,6 +213,7 @@ where
 {
     match (chunk.constant_at(a_idx), chunk.constant_at(b_idx)) {
         (Some(Value::Int(a)), Some(Value::Int(b))) => Some(Value::Bool(cmp(a.cmp(b)))),
+        (Some(Value::Char(a)), Some(Value::Char(b))) => Some(Value::Bool(cmp(a.cmp(b)))),
         (Some(Value::Float(a)), Some(Value::Float(b))) => {
             a.partial_cmp(b).map(|ord| Value::Bool(cmp(ord)))
         }
@@ -350,6 +376,58 @@ mod tests {
         assert_eq!(chunk.instructions.len(), 3);
     }
 
+    #[test]
+    fn test_fold_mod_integers() {
+        let mut chunk = Chunk::new();
+        let a = chunk.add_constant(Value::Int(17));
+        let b = chunk.add_constant(Value::Int(5));
+        chunk.emit(Instruction::LoadConst(a));
+        chunk.emit(Instruction::LoadConst(b));
+        chunk.emit(Instruction::Mod);
+
+        optimize_chunk(&mut chunk);
+
+        assert_eq!(chunk.instructions.len(), 1);
+        if let Instruction::LoadConst(idx) = chunk.instructions[0] {
+            assert_eq!(chunk.constant_at(idx), Some(&Value::Int(2)));
+        }
+    }
+
+    #[test]
+    fn test_no_fold_mod_by_zero() {
+        let mut chunk = Chunk::new();
+        let a = chunk.add_constant(Value::Int(17));
+        let b = chunk.add_constant(Value::Int(0));
+        chunk.emit(Instruction::LoadConst(a));
+        chunk.emit(Instruction::LoadConst(b));
+        chunk.emit(Instruction::Mod);
+
+        optimize_chunk(&mut chunk);
+
+        assert_eq!(chunk.instructions.len(), 3);
+    }
+
+    #[test]
+    fn test_fold_bitwise_chain() {
+        // (12 &&& 10) <<< 2  =>  32
+        let mut chunk = Chunk::new();
+        let a = chunk.add_constant(Value::Int(12));
+        let b = chunk.add_constant(Value::Int(10));
+        let c = chunk.add_constant(Value::Int(2));
+        chunk.emit(Instruction::LoadConst(a));
+        chunk.emit(Instruction::LoadConst(b));
+        chunk.emit(Instruction::BitAnd);
+        chunk.emit(Instruction::LoadConst(c));
+        chunk.emit(Instruction::Shl);
+
+        optimize_chunk(&mut chunk);
+
+        assert_eq!(chunk.instructions.len(), 1);
+        if let Instruction::LoadConst(idx) = chunk.instructions[0] {
+            assert_eq!(chunk.constant_at(idx), Some(&Value::Int(32)));
+        }
+    }
+
     #[test]
     fn test_fold_not_true() {
         let mut chunk = Chunk::new();
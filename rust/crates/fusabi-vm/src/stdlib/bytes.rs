@@ -0,0 +1,151 @@
+// Fusabi Bytes Standard Library
+// Provides operations on immutable byte strings (byte[])
+
+use crate::value::Value;
+use crate::vm::VmError;
+
+fn expect_bytes(value: &Value) -> Result<&[u8], VmError> {
+    value.as_bytes().ok_or_else(|| VmError::TypeMismatch {
+        expected: "byte[]",
+        got: value.type_name(),
+    })
+}
+
+/// Bytes.length : byte[] -> int
+/// Returns the number of bytes
+pub fn bytes_length(bytes: &Value) -> Result<Value, VmError> {
+    Ok(Value::Int(expect_bytes(bytes)?.len() as i64))
+}
+
+/// Bytes.get : int -> byte[] -> int
+/// Returns the byte at the given index as an int in 0..=255
+pub fn bytes_get(index: &Value, bytes: &Value) -> Result<Value, VmError> {
+    let data = expect_bytes(bytes)?;
+    let idx = index.as_int().ok_or_else(|| VmError::TypeMismatch {
+        expected: "int",
+        got: index.type_name(),
+    })?;
+    usize::try_from(idx)
+        .ok()
+        .and_then(|i| data.get(i))
+        .map(|b| Value::Int(*b as i64))
+        .ok_or_else(|| {
+            VmError::Runtime(format!(
+                "Bytes index out of bounds: index {} with length {}",
+                idx,
+                data.len()
+            ))
+        })
+}
+
+/// Bytes.ofString : string -> byte[]
+/// Encodes a string as UTF-8
+pub fn bytes_of_string(s: &Value) -> Result<Value, VmError> {
+    match s {
+        Value::Str(string) => Ok(Value::Bytes(string.as_bytes().to_vec())),
+        _ => Err(VmError::TypeMismatch {
+            expected: "string",
+            got: s.type_name(),
+        }),
+    }
+}
+
+/// Bytes.toString : byte[] -> string
+/// Decodes UTF-8 bytes into a string, failing on invalid UTF-8
+pub fn bytes_to_string(bytes: &Value) -> Result<Value, VmError> {
+    String::from_utf8(expect_bytes(bytes)?.to_vec())
+        .map(Value::Str)
+        .map_err(|e| VmError::Runtime(format!("Invalid UTF-8 in byte string: {}", e)))
+}
+
+/// Bytes.ofList : int list -> byte[]
+/// Builds a byte string from a list of ints in 0..=255
+pub fn bytes_of_list(list: &Value) -> Result<Value, VmError> {
+    let elements = list.list_to_vec().ok_or_else(|| VmError::TypeMismatch {
+        expected: "int list",
+        got: list.type_name(),
+    })?;
+
+    let mut result = Vec::with_capacity(elements.len());
+    for element in &elements {
+        match element {
+            Value::Int(n) => {
+                let byte = u8::try_from(*n)
+                    .map_err(|_| VmError::Runtime(format!("Value {} is not a byte", n)))?;
+                result.push(byte);
+            }
+            _ => {
+                return Err(VmError::TypeMismatch {
+                    expected: "int",
+                    got: element.type_name(),
+                })
+            }
+        }
+    }
+    Ok(Value::Bytes(result))
+}
+
+/// Bytes.toList : byte[] -> int list
+/// Converts a byte string to a list of ints
+pub fn bytes_to_list(bytes: &Value) -> Result<Value, VmError> {
+    Ok(Value::vec_to_cons(
+        expect_bytes(bytes)?
+            .iter()
+            .map(|b| Value::Int(*b as i64))
+            .collect(),
+    ))
+}
+
+/// Bytes.append : byte[] -> byte[] -> byte[]
+/// Concatenates two byte strings
+pub fn bytes_append(first: &Value, second: &Value) -> Result<Value, VmError> {
+    let mut result = expect_bytes(first)?.to_vec();
+    result.extend_from_slice(expect_bytes(second)?);
+    Ok(Value::Bytes(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_string_round_trip() {
+        let s = Value::Str("héllo".to_string());
+        let bytes = bytes_of_string(&s).unwrap();
+        assert_eq!(bytes_length(&bytes).unwrap(), Value::Int(6));
+        assert_eq!(bytes_to_string(&bytes).unwrap(), s);
+    }
+
+    #[test]
+    fn test_bytes_to_string_invalid_utf8() {
+        assert!(bytes_to_string(&Value::Bytes(vec![0xff, 0xfe])).is_err());
+    }
+
+    #[test]
+    fn test_bytes_get() {
+        let bytes = Value::Bytes(vec![1, 200]);
+        assert_eq!(bytes_get(&Value::Int(1), &bytes).unwrap(), Value::Int(200));
+        assert!(bytes_get(&Value::Int(2), &bytes).is_err());
+        assert!(bytes_get(&Value::Int(-1), &bytes).is_err());
+    }
+
+    #[test]
+    fn test_bytes_list_round_trip() {
+        let list = Value::vec_to_cons(vec![Value::Int(0), Value::Int(255)]);
+        let bytes = bytes_of_list(&list).unwrap();
+        assert_eq!(bytes, Value::Bytes(vec![0, 255]));
+        assert_eq!(bytes_to_list(&bytes).unwrap(), list);
+    }
+
+    #[test]
+    fn test_bytes_of_list_out_of_range() {
+        let list = Value::vec_to_cons(vec![Value::Int(256)]);
+        assert!(bytes_of_list(&list).is_err());
+    }
+
+    #[test]
+    fn test_bytes_append() {
+        let result = bytes_append(&Value::Bytes(vec![1]), &Value::Bytes(vec![2, 3])).unwrap();
+        assert_eq!(result, Value::Bytes(vec![1, 2, 3]));
+    }
+}
@@ -0,0 +1,148 @@
+// Fusabi Char Standard Library
+// Provides character classification and conversion
+
+use crate::value::Value;
+use crate::vm::VmError;
+
+fn expect_char(value: &Value) -> Result<char, VmError> {
+    value.as_char().ok_or_else(|| VmError::TypeMismatch {
+        expected: "char",
+        got: value.type_name(),
+    })
+}
+
+/// Char.isDigit : char -> bool
+/// Returns true for the ASCII digits '0' through '9'
+pub fn char_is_digit(c: &Value) -> Result<Value, VmError> {
+    Ok(Value::Bool(expect_char(c)?.is_ascii_digit()))
+}
+
+/// Char.isLetter : char -> bool
+/// Returns true for alphabetic characters
+pub fn char_is_letter(c: &Value) -> Result<Value, VmError> {
+    Ok(Value::Bool(expect_char(c)?.is_alphabetic()))
+}
+
+/// Char.isLetterOrDigit : char -> bool
+/// Returns true for alphabetic characters and digits
+pub fn char_is_letter_or_digit(c: &Value) -> Result<Value, VmError> {
+    Ok(Value::Bool(expect_char(c)?.is_alphanumeric()))
+}
+
+/// Char.isWhiteSpace : char -> bool
+/// Returns true for whitespace characters
+pub fn char_is_white_space(c: &Value) -> Result<Value, VmError> {
+    Ok(Value::Bool(expect_char(c)?.is_whitespace()))
+}
+
+/// Char.isUpper : char -> bool
+/// Returns true for uppercase characters
+pub fn char_is_upper(c: &Value) -> Result<Value, VmError> {
+    Ok(Value::Bool(expect_char(c)?.is_uppercase()))
+}
+
+/// Char.isLower : char -> bool
+/// Returns true for lowercase characters
+pub fn char_is_lower(c: &Value) -> Result<Value, VmError> {
+    Ok(Value::Bool(expect_char(c)?.is_lowercase()))
+}
+
+/// Char.toUpper : char -> char
+/// Converts a character to uppercase, leaving it unchanged when the
+/// uppercase form is more than one character
+pub fn char_to_upper(c: &Value) -> Result<Value, VmError> {
+    let ch = expect_char(c)?;
+    let mut upper = ch.to_uppercase();
+    Ok(Value::Char(match (upper.next(), upper.next()) {
+        (Some(u), None) => u,
+        _ => ch,
+    }))
+}
+
+/// Char.toLower : char -> char
+/// Converts a character to lowercase, leaving it unchanged when the
+/// lowercase form is more than one character
+pub fn char_to_lower(c: &Value) -> Result<Value, VmError> {
+    let ch = expect_char(c)?;
+    let mut lower = ch.to_lowercase();
+    Ok(Value::Char(match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => ch,
+    }))
+}
+
+/// Char.toInt : char -> int
+/// Returns the Unicode code point of a character
+pub fn char_to_int(c: &Value) -> Result<Value, VmError> {
+    Ok(Value::Int(expect_char(c)? as i64))
+}
+
+/// Char.ofInt : int -> char
+/// Converts a Unicode code point to a character
+pub fn char_of_int(n: &Value) -> Result<Value, VmError> {
+    match n {
+        Value::Int(code) => u32::try_from(*code)
+            .ok()
+            .and_then(char::from_u32)
+            .map(Value::Char)
+            .ok_or_else(|| VmError::Runtime(format!("Invalid character code: {}", code))),
+        _ => Err(VmError::TypeMismatch {
+            expected: "int",
+            got: n.type_name(),
+        }),
+    }
+}
+
+/// Char.toString : char -> string
+/// Converts a character to a one-character string
+pub fn char_to_string(c: &Value) -> Result<Value, VmError> {
+    Ok(Value::Str(expect_char(c)?.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_classification() {
+        assert_eq!(char_is_digit(&Value::Char('7')).unwrap(), Value::Bool(true));
+        assert_eq!(
+            char_is_digit(&Value::Char('x')).unwrap(),
+            Value::Bool(false)
+        );
+        assert_eq!(
+            char_is_letter(&Value::Char('é')).unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            char_is_white_space(&Value::Char('\t')).unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(char_is_upper(&Value::Char('Q')).unwrap(), Value::Bool(true));
+        assert_eq!(
+            char_is_lower(&Value::Char('Q')).unwrap(),
+            Value::Bool(false)
+        );
+    }
+
+    #[test]
+    fn test_char_case_conversion() {
+        assert_eq!(char_to_upper(&Value::Char('a')).unwrap(), Value::Char('A'));
+        assert_eq!(char_to_lower(&Value::Char('Z')).unwrap(), Value::Char('z'));
+        // 'ß' upper-cases to "SS", which is not a single char
+        assert_eq!(char_to_upper(&Value::Char('ß')).unwrap(), Value::Char('ß'));
+    }
+
+    #[test]
+    fn test_char_int_round_trip() {
+        assert_eq!(char_to_int(&Value::Char('A')).unwrap(), Value::Int(65));
+        assert_eq!(char_of_int(&Value::Int(955)).unwrap(), Value::Char('λ'));
+        assert!(char_of_int(&Value::Int(-1)).is_err());
+        assert!(char_of_int(&Value::Int(0xD800)).is_err());
+    }
+
+    #[test]
+    fn test_char_type_mismatch() {
+        assert!(char_is_digit(&Value::Str("1".to_string())).is_err());
+    }
+}
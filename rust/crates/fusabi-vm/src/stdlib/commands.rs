@@ -6,49 +6,18 @@ use crate::vm::{Vm, VmError};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
-// Use OnceLock for production, but a regular Mutex for tests (for isolation)
-#[cfg(not(test))]
-use std::sync::OnceLock;
-
-/// Global command registry (production)
-#[cfg(not(test))]
-static COMMAND_REGISTRY: OnceLock<Arc<Mutex<CommandRegistryInner>>> = OnceLock::new();
-
-/// Global command registry (testing - allows clearing)
-#[cfg(test)]
-static COMMAND_REGISTRY: Mutex<Option<Arc<Mutex<CommandRegistryInner>>>> = Mutex::new(None);
-
-/// Inner registry structure
-struct CommandRegistryInner {
+/// Per-VM command palette storage, held in [`VmContext`](crate::VmContext)
+#[derive(Debug, Default)]
+pub struct CommandRegistry {
     commands: HashMap<String, CommandEntry>,
     next_id: i64,
 }
 
-impl CommandRegistryInner {
-    fn new() -> Self {
-        Self {
-            commands: HashMap::new(),
-            next_id: 0,
-        }
-    }
-}
-
-/// Get or initialize the global command registry (production)
-#[cfg(not(test))]
-fn get_registry() -> Arc<Mutex<CommandRegistryInner>> {
-    COMMAND_REGISTRY
-        .get_or_init(|| Arc::new(Mutex::new(CommandRegistryInner::new())))
-        .clone()
-}
-
-/// Get or initialize the global command registry (testing)
-#[cfg(test)]
-fn get_registry() -> Arc<Mutex<CommandRegistryInner>> {
-    let mut reg = COMMAND_REGISTRY.lock().unwrap();
-    if reg.is_none() {
-        *reg = Some(Arc::new(Mutex::new(CommandRegistryInner::new())));
+impl CommandRegistry {
+    /// Create a registry with no commands
+    pub fn new() -> Self {
+        Self::default()
     }
-    reg.as_ref().unwrap().clone()
 }
 
 /// Internal representation of a registered command
@@ -158,7 +127,7 @@ impl CommandEntry {
 
 /// Commands.register : CommandInfo -> int
 /// Registers a command and returns its numeric ID
-pub fn commands_register(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+pub fn commands_register(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
     if args.len() != 1 {
         return Err(VmError::Runtime(format!(
             "Commands.register expects 1 argument, got {}",
@@ -166,7 +135,7 @@ pub fn commands_register(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError>
         )));
     }
 
-    let registry = get_registry();
+    let registry = vm.context.commands.clone();
     let mut reg = registry.lock().unwrap();
 
     let numeric_id = reg.next_id;
@@ -183,7 +152,7 @@ pub fn commands_register(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError>
 
 /// Commands.registerMany : CommandInfo list -> int list
 /// Registers multiple commands and returns their numeric IDs
-pub fn commands_register_many(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+pub fn commands_register_many(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
     if args.len() != 1 {
         return Err(VmError::Runtime(format!(
             "Commands.registerMany expects 1 argument, got {}",
@@ -211,7 +180,7 @@ pub fn commands_register_many(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmE
         }
     }
 
-    let registry = get_registry();
+    let registry = vm.context.commands.clone();
     let mut reg = registry.lock().unwrap();
 
     // Register all commands and collect their IDs
@@ -242,7 +211,7 @@ pub fn commands_register_many(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmE
 /// Commands.unregister : int -> bool
 /// Unregisters a command by its string ID (despite the type signature suggesting int)
 /// Returns true if a command was found and removed, false otherwise
-pub fn commands_unregister(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+pub fn commands_unregister(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
     if args.len() != 1 {
         return Err(VmError::Runtime(format!(
             "Commands.unregister expects 1 argument, got {}",
@@ -250,7 +219,7 @@ pub fn commands_unregister(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmErro
         )));
     }
 
-    let registry = get_registry();
+    let registry = vm.context.commands.clone();
     let mut reg = registry.lock().unwrap();
 
     match &args[0] {
@@ -283,7 +252,7 @@ pub fn commands_unregister(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmErro
 
 /// Commands.list : unit -> CommandInfo list
 /// Returns a list of all registered commands
-pub fn commands_list(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+pub fn commands_list(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
     if args.len() != 1 {
         return Err(VmError::Runtime(format!(
             "Commands.list expects 1 argument, got {}",
@@ -299,7 +268,7 @@ pub fn commands_list(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
         });
     }
 
-    let registry = get_registry();
+    let registry = vm.context.commands.clone();
     let reg = registry.lock().unwrap();
 
     // Convert all commands to a list
@@ -316,7 +285,7 @@ pub fn commands_list(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
 
 /// Commands.getById : string -> CommandInfo option
 /// Gets a command by its string ID
-pub fn commands_get_by_id(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+pub fn commands_get_by_id(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
     if args.len() != 1 {
         return Err(VmError::Runtime(format!(
             "Commands.getById expects 1 argument, got {}",
@@ -329,7 +298,7 @@ pub fn commands_get_by_id(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError
         got: args[0].type_name(),
     })?;
 
-    let registry = get_registry();
+    let registry = vm.context.commands.clone();
     let reg = registry.lock().unwrap();
 
     match reg.commands.get(id) {
@@ -363,7 +332,7 @@ pub fn commands_invoke(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
 
     // Get the handler (clone it so we don't hold the lock)
     let handler = {
-        let registry = get_registry();
+        let registry = vm.context.commands.clone();
         let reg = registry.lock().unwrap();
         reg.commands
             .get(id)
@@ -426,20 +395,8 @@ mod tests {
         Value::Record(Arc::new(Mutex::new(fields)))
     }
 
-    // Note: These tests share global state (COMMAND_REGISTRY).
-    // While we reset the registry in clear_registry(), parallel test execution
-    // can still cause interference. Tests pass when run serially:
-    //   cargo test --package fusabi-vm commands::tests -- --test-threads=1
-
-    fn clear_registry() {
-        // Reset the global registry for test isolation
-        let mut reg_opt = COMMAND_REGISTRY.lock().unwrap();
-        *reg_opt = Some(Arc::new(Mutex::new(CommandRegistryInner::new())));
-    }
-
     #[test]
     fn test_register_command() {
-        clear_registry();
         let mut vm = Vm::new();
 
         let cmd = create_test_command("test.command", "Test Command", "A test command", "Testing");
@@ -450,7 +407,7 @@ mod tests {
         assert_eq!(result, Value::Int(0));
 
         // Verify command is in registry
-        let registry = get_registry();
+        let registry = vm.context.commands.clone();
         let reg = registry.lock().unwrap();
         assert!(reg.commands.contains_key("test.command"));
         assert_eq!(reg.commands.len(), 1);
@@ -458,7 +415,6 @@ mod tests {
 
     #[test]
     fn test_register_multiple_commands() {
-        clear_registry();
         let mut vm = Vm::new();
 
         let cmd1 = create_test_command("cmd1", "Command 1", "First", "Test");
@@ -467,7 +423,7 @@ mod tests {
         commands_register(&mut vm, &[cmd1]).unwrap();
         commands_register(&mut vm, &[cmd2]).unwrap();
 
-        let registry = get_registry();
+        let registry = vm.context.commands.clone();
         let reg = registry.lock().unwrap();
         assert_eq!(reg.commands.len(), 2);
         assert!(reg.commands.contains_key("cmd1"));
@@ -476,7 +432,6 @@ mod tests {
 
     #[test]
     fn test_register_many_commands() {
-        clear_registry();
         let mut vm = Vm::new();
 
         let cmd1 = create_test_command("cmd1", "Command 1", "First", "Test");
@@ -508,14 +463,13 @@ mod tests {
             _ => panic!("Expected cons list"),
         }
 
-        let registry = get_registry();
+        let registry = vm.context.commands.clone();
         let reg = registry.lock().unwrap();
         assert_eq!(reg.commands.len(), 2);
     }
 
     #[test]
     fn test_list_commands() {
-        clear_registry();
         let mut vm = Vm::new();
 
         let cmd1 = create_test_command("cmd1", "Command 1", "First", "Test");
@@ -546,7 +500,6 @@ mod tests {
 
     #[test]
     fn test_get_by_id() {
-        clear_registry();
         let mut vm = Vm::new();
 
         let cmd = create_test_command("test.command", "Test Command", "A test command", "Testing");
@@ -586,7 +539,6 @@ mod tests {
 
     #[test]
     fn test_unregister_command() {
-        clear_registry();
         let mut vm = Vm::new();
 
         let cmd = create_test_command("test.command", "Test", "Description", "Category");
@@ -599,7 +551,7 @@ mod tests {
         assert_eq!(result, Value::Bool(true));
 
         // Verify it's gone
-        let registry = get_registry();
+        let registry = vm.context.commands.clone();
         let reg = registry.lock().unwrap();
         assert_eq!(reg.commands.len(), 0);
         drop(reg);
@@ -612,7 +564,6 @@ mod tests {
 
     #[test]
     fn test_unregister_by_numeric_id() {
-        clear_registry();
         let mut vm = Vm::new();
 
         let cmd = create_test_command("test.command", "Test", "Description", "Category");
@@ -624,14 +575,13 @@ mod tests {
         assert_eq!(result, Value::Bool(true));
 
         // Verify it's gone
-        let registry = get_registry();
+        let registry = vm.context.commands.clone();
         let reg = registry.lock().unwrap();
         assert_eq!(reg.commands.len(), 0);
     }
 
     #[test]
     fn test_missing_field_error() {
-        clear_registry();
         let mut vm = Vm::new();
 
         // Create command with missing 'name' field
@@ -661,7 +611,6 @@ mod tests {
 
     #[test]
     fn test_invalid_handler_type() {
-        clear_registry();
         let mut vm = Vm::new();
 
         // Create command with non-function handler
@@ -685,7 +634,6 @@ mod tests {
 
     #[test]
     fn test_type_mismatch_errors() {
-        clear_registry();
         let mut vm = Vm::new();
 
         // register expects record
@@ -704,4 +652,22 @@ mod tests {
         let result = commands_list(&mut vm, &[Value::Int(42)]);
         assert!(matches!(result, Err(VmError::TypeMismatch { .. })));
     }
+
+    #[test]
+    fn test_each_vm_has_its_own_commands() {
+        let mut vm_a = Vm::new();
+        let mut vm_b = Vm::new();
+
+        let cmd = create_test_command("test.command", "Test", "Description", "Category");
+        commands_register(&mut vm_a, &[cmd]).unwrap();
+
+        assert_eq!(
+            commands_list(&mut vm_b, &[Value::Unit]).unwrap(),
+            Value::Nil
+        );
+
+        vm_b.context.commands = vm_a.context.commands.clone();
+        let found = commands_get_by_id(&mut vm_b, &[Value::Str("test.command".to_string())]);
+        assert_eq!(found.unwrap().variant_name().unwrap(), "Some");
+    }
 }
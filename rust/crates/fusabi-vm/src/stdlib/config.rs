@@ -4,20 +4,25 @@
 use crate::value::Value;
 use crate::vm::{Vm, VmError};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
 
 type ConfigEntry = (ConfigSchema, Option<Value>);
-type ConfigRegistryType = Arc<Mutex<HashMap<String, ConfigEntry>>>;
 
-lazy_static::lazy_static! {
-    /// Global registry for configuration schemas and values
-    /// Key: config name, Value: (schema, current value)
-    static ref CONFIG_REGISTRY: ConfigRegistryType =
-        Arc::new(Mutex::new(HashMap::new()));
+/// Per-VM configuration storage, held in [`VmContext`](crate::VmContext)
+/// Key: config name, Value: (schema, current value)
+#[derive(Debug, Default)]
+pub struct ConfigStore {
+    entries: HashMap<String, ConfigEntry>,
+}
+
+impl ConfigStore {
+    /// Create a store with no configurations defined
+    pub fn new() -> Self {
+        Self::default()
+    }
 }
 
 /// Internal representation of a configuration schema
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 struct ConfigSchema {
     name: String,
     config_type: String,
@@ -202,22 +207,23 @@ pub fn config_define(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
     }
 
     // Register schema with default value
-    let mut registry = CONFIG_REGISTRY.lock().unwrap();
-    registry.insert(name.clone(), (schema, default_value));
+    let mut store = vm.context.config.lock().unwrap();
+    store.entries.insert(name.clone(), (schema, default_value));
 
     Ok(Value::Unit)
 }
 
 /// Config.get : string -> ConfigValue
 /// Get a configuration value (throws if not found)
-pub fn config_get(name: &Value) -> Result<Value, VmError> {
+pub fn config_get(vm: &Vm, name: &Value) -> Result<Value, VmError> {
     let name_str = name.as_str().ok_or_else(|| VmError::TypeMismatch {
         expected: "string",
         got: name.type_name(),
     })?;
 
-    let registry = CONFIG_REGISTRY.lock().unwrap();
-    let (_, value) = registry
+    let store = vm.context.config.lock().unwrap();
+    let (_, value) = store
+        .entries
         .get(name_str)
         .ok_or_else(|| VmError::Runtime(format!("Configuration '{}' not found", name_str)))?;
 
@@ -231,15 +237,15 @@ pub fn config_get(name: &Value) -> Result<Value, VmError> {
 
 /// Config.getOr : string -> ConfigValue -> ConfigValue
 /// Get a configuration value with a fallback default
-pub fn config_get_or(name: &Value, default: &Value) -> Result<Value, VmError> {
+pub fn config_get_or(vm: &Vm, name: &Value, default: &Value) -> Result<Value, VmError> {
     let name_str = name.as_str().ok_or_else(|| VmError::TypeMismatch {
         expected: "string",
         got: name.type_name(),
     })?;
 
-    let registry = CONFIG_REGISTRY.lock().unwrap();
+    let store = vm.context.config.lock().unwrap();
 
-    match registry.get(name_str) {
+    match store.entries.get(name_str) {
         Some((_, Some(value))) => Ok(value.clone()),
         Some((_, None)) => Ok(default.clone()),
         None => Ok(default.clone()),
@@ -263,8 +269,11 @@ pub fn config_set(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
 
     let value = &args[1];
 
-    let mut registry = CONFIG_REGISTRY.lock().unwrap();
-    let (schema, current_value) = registry
+    // Clone the handle so validators can run while the store is locked
+    let config = vm.context.config.clone();
+    let mut store = config.lock().unwrap();
+    let (schema, current_value) = store
+        .entries
         .get_mut(name_str)
         .ok_or_else(|| VmError::Runtime(format!("Configuration '{}' not defined", name_str)))?;
 
@@ -296,23 +305,23 @@ pub fn config_set(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
 
 /// Config.has : string -> bool
 /// Check if a configuration is defined
-pub fn config_has(name: &Value) -> Result<Value, VmError> {
+pub fn config_has(vm: &Vm, name: &Value) -> Result<Value, VmError> {
     let name_str = name.as_str().ok_or_else(|| VmError::TypeMismatch {
         expected: "string",
         got: name.type_name(),
     })?;
 
-    let registry = CONFIG_REGISTRY.lock().unwrap();
-    Ok(Value::Bool(registry.contains_key(name_str)))
+    let store = vm.context.config.lock().unwrap();
+    Ok(Value::Bool(store.entries.contains_key(name_str)))
 }
 
 /// Config.list : unit -> (string * ConfigValue) list
 /// List all defined configurations with their current values
-pub fn config_list(_unit: &Value) -> Result<Value, VmError> {
-    let registry = CONFIG_REGISTRY.lock().unwrap();
+pub fn config_list(vm: &Vm, _unit: &Value) -> Result<Value, VmError> {
+    let store = vm.context.config.lock().unwrap();
     let mut entries = Vec::new();
 
-    for (name, (_, value)) in registry.iter() {
+    for (name, (_, value)) in store.entries.iter() {
         if let Some(val) = value {
             let tuple = Value::Tuple(vec![Value::Str(name.clone()), val.clone()]);
             entries.push(tuple);
@@ -331,14 +340,15 @@ pub fn config_list(_unit: &Value) -> Result<Value, VmError> {
 
 /// Config.reset : string -> unit
 /// Reset a configuration to its default value
-pub fn config_reset(name: &Value) -> Result<Value, VmError> {
+pub fn config_reset(vm: &Vm, name: &Value) -> Result<Value, VmError> {
     let name_str = name.as_str().ok_or_else(|| VmError::TypeMismatch {
         expected: "string",
         got: name.type_name(),
     })?;
 
-    let mut registry = CONFIG_REGISTRY.lock().unwrap();
-    let (schema, current_value) = registry
+    let mut store = vm.context.config.lock().unwrap();
+    let (schema, current_value) = store
+        .entries
         .get_mut(name_str)
         .ok_or_else(|| VmError::Runtime(format!("Configuration '{}' not defined", name_str)))?;
 
@@ -350,6 +360,7 @@ pub fn config_reset(name: &Value) -> Result<Value, VmError> {
 mod tests {
     use super::*;
     use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
 
     fn create_config_schema(
         name: &str,
@@ -419,13 +430,8 @@ mod tests {
         }
     }
 
-    fn clear_registry() {
-        CONFIG_REGISTRY.lock().unwrap().clear();
-    }
-
     #[test]
     fn test_config_define_simple() {
-        clear_registry();
         let mut vm = Vm::new();
 
         let schema = create_config_schema(
@@ -440,13 +446,12 @@ mod tests {
         assert_eq!(result.unwrap(), Value::Unit);
 
         // Verify it was registered
-        let registry = CONFIG_REGISTRY.lock().unwrap();
-        assert!(registry.contains_key("app.name"));
+        let store = vm.context.config.lock().unwrap();
+        assert!(store.entries.contains_key("app.name"));
     }
 
     #[test]
     fn test_config_define_invalid_type() {
-        clear_registry();
         let mut vm = Vm::new();
 
         let schema = create_config_schema("test", "invalid_type", None, None);
@@ -457,13 +462,12 @@ mod tests {
 
     #[test]
     fn test_config_get_success() {
-        clear_registry();
         let mut vm = Vm::new();
 
         let schema = create_config_schema("port", "int", Some(create_config_value_int(8080)), None);
         config_define(&mut vm, &[schema]).unwrap();
 
-        let result = config_get(&Value::Str("port".to_string()));
+        let result = config_get(&vm, &Value::Str("port".to_string()));
         assert!(result.is_ok());
         let value = result.unwrap();
         assert_eq!(value.variant_name().unwrap(), "Int");
@@ -471,27 +475,25 @@ mod tests {
 
     #[test]
     fn test_config_get_not_found() {
-        clear_registry();
+        let vm = Vm::new();
 
-        let result = config_get(&Value::Str("nonexistent".to_string()));
+        let result = config_get(&vm, &Value::Str("nonexistent".to_string()));
         assert!(result.is_err());
     }
 
     #[test]
     fn test_config_get_no_value_no_default() {
-        clear_registry();
         let mut vm = Vm::new();
 
         let schema = create_config_schema("test", "string", None, None);
         config_define(&mut vm, &[schema]).unwrap();
 
-        let result = config_get(&Value::Str("test".to_string()));
+        let result = config_get(&vm, &Value::Str("test".to_string()));
         assert!(result.is_err());
     }
 
     #[test]
     fn test_config_get_or_with_value() {
-        clear_registry();
         let mut vm = Vm::new();
 
         let schema = create_config_schema(
@@ -503,7 +505,7 @@ mod tests {
         config_define(&mut vm, &[schema]).unwrap();
 
         let fallback = create_config_value_string("fallback");
-        let result = config_get_or(&Value::Str("name".to_string()), &fallback);
+        let result = config_get_or(&vm, &Value::Str("name".to_string()), &fallback);
         assert!(result.is_ok());
         let value = result.unwrap();
         assert_eq!(value.variant_name().unwrap(), "String");
@@ -511,45 +513,43 @@ mod tests {
 
     #[test]
     fn test_config_get_or_without_value() {
-        clear_registry();
+        let vm = Vm::new();
 
         let fallback = create_config_value_string("fallback");
-        let result = config_get_or(&Value::Str("nonexistent".to_string()), &fallback);
+        let result = config_get_or(&vm, &Value::Str("nonexistent".to_string()), &fallback);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), fallback);
     }
 
     #[test]
     fn test_config_has_true() {
-        clear_registry();
         let mut vm = Vm::new();
 
         let schema = create_config_schema("test", "bool", None, None);
         config_define(&mut vm, &[schema]).unwrap();
 
-        let result = config_has(&Value::Str("test".to_string()));
+        let result = config_has(&vm, &Value::Str("test".to_string()));
         assert_eq!(result.unwrap(), Value::Bool(true));
     }
 
     #[test]
     fn test_config_has_false() {
-        clear_registry();
+        let vm = Vm::new();
 
-        let result = config_has(&Value::Str("nonexistent".to_string()));
+        let result = config_has(&vm, &Value::Str("nonexistent".to_string()));
         assert_eq!(result.unwrap(), Value::Bool(false));
     }
 
     #[test]
     fn test_config_list_empty() {
-        clear_registry();
+        let vm = Vm::new();
 
-        let result = config_list(&Value::Unit);
+        let result = config_list(&vm, &Value::Unit);
         assert_eq!(result.unwrap(), Value::Nil);
     }
 
     #[test]
     fn test_config_list_with_values() {
-        clear_registry();
         let mut vm = Vm::new();
 
         let schema1 = create_config_schema("a", "int", Some(create_config_value_int(1)), None);
@@ -557,7 +557,7 @@ mod tests {
         config_define(&mut vm, &[schema1]).unwrap();
         config_define(&mut vm, &[schema2]).unwrap();
 
-        let result = config_list(&Value::Unit);
+        let result = config_list(&vm, &Value::Unit);
         assert!(result.is_ok());
         let list = result.unwrap();
         assert!(list.is_cons());
@@ -565,7 +565,6 @@ mod tests {
 
     #[test]
     fn test_config_reset() {
-        clear_registry();
         let mut vm = Vm::new();
         crate::stdlib::register_stdlib(&mut vm);
 
@@ -580,16 +579,16 @@ mod tests {
         .unwrap();
 
         // Verify new value
-        let value = config_get(&Value::Str("test".to_string())).unwrap();
+        let value = config_get(&vm, &Value::Str("test".to_string())).unwrap();
         if let Value::Variant { fields, .. } = value {
             assert_eq!(fields[0], Value::Int(200));
         }
 
         // Reset to default
-        config_reset(&Value::Str("test".to_string())).unwrap();
+        config_reset(&vm, &Value::Str("test".to_string())).unwrap();
 
         // Verify default value
-        let value = config_get(&Value::Str("test".to_string())).unwrap();
+        let value = config_get(&vm, &Value::Str("test".to_string())).unwrap();
         if let Value::Variant { fields, .. } = value {
             assert_eq!(fields[0], Value::Int(100));
         }
@@ -597,7 +596,6 @@ mod tests {
 
     #[test]
     fn test_config_type_validation_string() {
-        clear_registry();
         let mut vm = Vm::new();
         crate::stdlib::register_stdlib(&mut vm);
 
@@ -624,7 +622,6 @@ mod tests {
 
     #[test]
     fn test_config_type_validation_bool() {
-        clear_registry();
         let mut vm = Vm::new();
         crate::stdlib::register_stdlib(&mut vm);
 
@@ -651,4 +648,38 @@ mod tests {
         );
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_each_vm_has_its_own_config() {
+        let mut vm_a = Vm::new();
+        let vm_b = Vm::new();
+
+        let schema = create_config_schema("port", "int", Some(create_config_value_int(8080)), None);
+        config_define(&mut vm_a, &[schema]).unwrap();
+
+        let name = Value::Str("port".to_string());
+        assert_eq!(config_has(&vm_a, &name).unwrap(), Value::Bool(true));
+        assert_eq!(config_has(&vm_b, &name).unwrap(), Value::Bool(false));
+    }
+
+    #[test]
+    fn test_shared_config_store() {
+        let mut vm_a = Vm::new();
+        let mut vm_b = Vm::new();
+        vm_b.context.config = vm_a.context.config.clone();
+
+        let schema = create_config_schema("port", "int", Some(create_config_value_int(8080)), None);
+        config_define(&mut vm_a, &[schema]).unwrap();
+        config_set(
+            &mut vm_b,
+            &[
+                Value::Str("port".to_string()),
+                create_config_value_int(9090),
+            ],
+        )
+        .unwrap();
+
+        let value = config_get(&vm_a, &Value::Str("port".to_string())).unwrap();
+        assert_eq!(value, create_config_value_int(9090));
+    }
 }
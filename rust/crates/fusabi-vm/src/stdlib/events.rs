@@ -3,27 +3,44 @@
 
 use crate::value::Value;
 use crate::vm::{Vm, VmError};
-use lazy_static::lazy_static;
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
 
-/// Event handler storage
-/// Maps event names to lists of (handler_id, handler_value) pairs
-type HandlerRegistry = HashMap<String, Vec<(u64, Value)>>;
+/// Prefix of the keys under which one-time handlers are stored
+const ONCE_PREFIX: &str = "__once__";
 
-lazy_static! {
-    /// Global event handler registry
-    static ref EVENT_HANDLERS: Arc<Mutex<HandlerRegistry>> = Arc::new(Mutex::new(HashMap::new()));
-
-    /// Counter for generating unique handler IDs
-    static ref HANDLER_ID_COUNTER: Arc<Mutex<u64>> = Arc::new(Mutex::new(0));
+/// Per-VM event handler storage, held in [`VmContext`](crate::VmContext)
+#[derive(Debug, Default)]
+pub struct EventBus {
+    /// Maps event names to lists of (handler_id, handler_value) pairs
+    handlers: HashMap<String, Vec<(u64, Value)>>,
+    /// Last handler ID handed out
+    last_id: u64,
 }
 
-/// Generate a unique handler ID
-fn next_handler_id() -> u64 {
-    let mut counter = HANDLER_ID_COUNTER.lock().unwrap();
-    *counter += 1;
-    *counter
+impl EventBus {
+    /// Create an event bus with no handlers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a handler under `key` and return its ID
+    fn add(&mut self, key: String, handler: Value) -> u64 {
+        self.last_id += 1;
+        let handler_id = self.last_id;
+        self.handlers
+            .entry(key)
+            .or_default()
+            .push((handler_id, handler));
+        handler_id
+    }
+
+    /// Handlers registered for `key`, in registration order
+    fn handlers_for(&self, key: &str) -> Vec<Value> {
+        self.handlers
+            .get(key)
+            .map(|handlers| handlers.iter().map(|(_, h)| h.clone()).collect())
+            .unwrap_or_default()
+    }
 }
 
 /// Events.on : string -> ('a -> unit) -> int
@@ -32,7 +49,7 @@ fn next_handler_id() -> u64 {
 ///
 /// Example:
 ///   let handlerId = Events.on "WindowFocusChanged" (fun gained -> printfn (sprintf "Focus: %b" gained))
-pub fn events_on(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+pub fn events_on(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
     if args.len() != 2 {
         return Err(VmError::Runtime(format!(
             "Events.on expects 2 arguments (event_name, handler), got {}",
@@ -63,15 +80,7 @@ pub fn events_on(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
         }
     }
 
-    let handler_id = next_handler_id();
-
-    {
-        let mut registry = EVENT_HANDLERS.lock().unwrap();
-        registry
-            .entry(event_name)
-            .or_insert_with(Vec::new)
-            .push((handler_id, handler));
-    }
+    let handler_id = vm.context.events.lock().unwrap().add(event_name, handler);
 
     Ok(Value::Int(handler_id as i64))
 }
@@ -81,7 +90,7 @@ pub fn events_on(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
 ///
 /// Example:
 ///   Events.off handlerId
-pub fn events_off(handler_id: &Value) -> Result<Value, VmError> {
+pub fn events_off(vm: &Vm, handler_id: &Value) -> Result<Value, VmError> {
     let id = match handler_id {
         Value::Int(id) => *id as u64,
         _ => {
@@ -92,10 +101,10 @@ pub fn events_off(handler_id: &Value) -> Result<Value, VmError> {
         }
     };
 
-    let mut registry = EVENT_HANDLERS.lock().unwrap();
+    let mut bus = vm.context.events.lock().unwrap();
     let mut found = false;
 
-    for handlers in registry.values_mut() {
+    for handlers in bus.handlers.values_mut() {
         let initial_len = handlers.len();
         handlers.retain(|(hid, _)| *hid != id);
         if handlers.len() < initial_len {
@@ -134,13 +143,7 @@ pub fn events_emit(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
     let event_data = args[1].clone();
 
     // Get handlers (clone to avoid holding lock during calls)
-    let handlers: Vec<Value> = {
-        let registry = EVENT_HANDLERS.lock().unwrap();
-        registry
-            .get(&event_name)
-            .map(|handlers| handlers.iter().map(|(_, h)| h.clone()).collect())
-            .unwrap_or_default()
-    };
+    let handlers = vm.context.events.lock().unwrap().handlers_for(&event_name);
 
     // Call each handler with the event data
     for handler in handlers {
@@ -168,7 +171,7 @@ pub fn events_emit_async(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError>
 ///
 /// Example:
 ///   Events.once "Startup" (fun _ -> printfn "App started!")
-pub fn events_once(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+pub fn events_once(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
     if args.len() != 2 {
         return Err(VmError::Runtime(format!(
             "Events.once expects 2 arguments (event_name, handler), got {}",
@@ -199,17 +202,13 @@ pub fn events_once(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
         }
     }
 
-    let handler_id = next_handler_id();
-
     // Store with a special marker for one-time handlers
-    // We'll use negative IDs internally to mark one-time handlers
-    {
-        let mut registry = EVENT_HANDLERS.lock().unwrap();
-        registry
-            .entry(format!("__once__{}", event_name))
-            .or_insert_with(Vec::new)
-            .push((handler_id, handler));
-    }
+    let handler_id = vm
+        .context
+        .events
+        .lock()
+        .unwrap()
+        .add(format!("{}{}", ONCE_PREFIX, event_name), handler);
 
     Ok(Value::Int(handler_id as i64))
 }
@@ -219,7 +218,7 @@ pub fn events_once(_vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
 ///
 /// Example:
 ///   Events.clear "WindowResized"
-pub fn events_clear(event_name: &Value) -> Result<Value, VmError> {
+pub fn events_clear(vm: &Vm, event_name: &Value) -> Result<Value, VmError> {
     let name = match event_name {
         Value::Str(s) => s.clone(),
         _ => {
@@ -230,9 +229,9 @@ pub fn events_clear(event_name: &Value) -> Result<Value, VmError> {
         }
     };
 
-    let mut registry = EVENT_HANDLERS.lock().unwrap();
-    registry.remove(&name);
-    registry.remove(&format!("__once__{}", name));
+    let mut bus = vm.context.events.lock().unwrap();
+    bus.handlers.remove(&name);
+    bus.handlers.remove(&format!("{}{}", ONCE_PREFIX, name));
 
     Ok(Value::Unit)
 }
@@ -242,9 +241,8 @@ pub fn events_clear(event_name: &Value) -> Result<Value, VmError> {
 ///
 /// Example:
 ///   Events.clearAll ()
-pub fn events_clear_all(_unit: &Value) -> Result<Value, VmError> {
-    let mut registry = EVENT_HANDLERS.lock().unwrap();
-    registry.clear();
+pub fn events_clear_all(vm: &Vm, _unit: &Value) -> Result<Value, VmError> {
+    vm.context.events.lock().unwrap().handlers.clear();
     Ok(Value::Unit)
 }
 
@@ -253,7 +251,7 @@ pub fn events_clear_all(_unit: &Value) -> Result<Value, VmError> {
 ///
 /// Example:
 ///   let count = Events.handlers "WindowFocusChanged"
-pub fn events_handlers(event_name: &Value) -> Result<Value, VmError> {
+pub fn events_handlers(vm: &Vm, event_name: &Value) -> Result<Value, VmError> {
     let name = match event_name {
         Value::Str(s) => s.clone(),
         _ => {
@@ -264,10 +262,11 @@ pub fn events_handlers(event_name: &Value) -> Result<Value, VmError> {
         }
     };
 
-    let registry = EVENT_HANDLERS.lock().unwrap();
-    let count = registry.get(&name).map(|h| h.len()).unwrap_or(0)
-        + registry
-            .get(&format!("__once__{}", name))
+    let bus = vm.context.events.lock().unwrap();
+    let count = bus.handlers.get(&name).map(|h| h.len()).unwrap_or(0)
+        + bus
+            .handlers
+            .get(&format!("{}{}", ONCE_PREFIX, name))
             .map(|h| h.len())
             .unwrap_or(0);
 
@@ -279,20 +278,20 @@ pub fn events_handlers(event_name: &Value) -> Result<Value, VmError> {
 ///
 /// Example:
 ///   let events = Events.list ()
-pub fn events_list(_unit: &Value) -> Result<Value, VmError> {
-    let registry = EVENT_HANDLERS.lock().unwrap();
-    let mut event_names: Vec<String> = registry
+pub fn events_list(vm: &Vm, _unit: &Value) -> Result<Value, VmError> {
+    let bus = vm.context.events.lock().unwrap();
+    let mut event_names: Vec<String> = bus
+        .handlers
         .keys()
-        .filter(|k| !k.starts_with("__once__"))
+        .filter(|k| !k.starts_with(ONCE_PREFIX))
         .cloned()
         .collect();
 
     // Add unique once events
-    for key in registry.keys() {
-        if key.starts_with("__once__") {
-            let name = key.strip_prefix("__once__").unwrap().to_string();
-            if !event_names.contains(&name) {
-                event_names.push(name);
+    for key in bus.handlers.keys() {
+        if let Some(name) = key.strip_prefix(ONCE_PREFIX) {
+            if !event_names.iter().any(|n| n == name) {
+                event_names.push(name.to_string());
             }
         }
     }
@@ -305,23 +304,21 @@ pub fn events_list(_unit: &Value) -> Result<Value, VmError> {
 
 /// Internal function to emit events and handle once handlers
 pub fn emit_event_internal(vm: &mut Vm, event_name: &str, data: Value) -> Result<(), VmError> {
-    // Get regular handlers
-    let handlers: Vec<Value> = {
-        let registry = EVENT_HANDLERS.lock().unwrap();
-        registry
-            .get(event_name)
-            .map(|handlers| handlers.iter().map(|(_, h)| h.clone()).collect())
-            .unwrap_or_default()
-    };
+    let (handlers, once_handlers): (Vec<Value>, Vec<Value>) = {
+        let mut bus = vm.context.events.lock().unwrap();
+
+        // Get regular handlers
+        let handlers = bus.handlers_for(event_name);
 
-    // Get and remove one-time handlers
-    let once_handlers: Vec<Value> = {
-        let mut registry = EVENT_HANDLERS.lock().unwrap();
-        let once_key = format!("__once__{}", event_name);
-        registry
+        // Get and remove one-time handlers
+        let once_key = format!("{}{}", ONCE_PREFIX, event_name);
+        let once_handlers = bus
+            .handlers
             .remove(&once_key)
             .map(|handlers| handlers.into_iter().map(|(_, h)| h).collect())
-            .unwrap_or_default()
+            .unwrap_or_default();
+
+        (handlers, once_handlers)
     };
 
     // Call all handlers
@@ -332,20 +329,12 @@ pub fn emit_event_internal(vm: &mut Vm, event_name: &str, data: Value) -> Result
     Ok(())
 }
 
-/// Reset the event system (for testing)
-#[cfg(test)]
-pub fn reset_events() {
-    let mut registry = EVENT_HANDLERS.lock().unwrap();
-    registry.clear();
-    let mut counter = HANDLER_ID_COUNTER.lock().unwrap();
-    *counter = 0;
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::chunk::ChunkBuilder;
     use crate::closure::Closure;
+    use std::sync::Arc;
 
     fn create_test_vm() -> Vm {
         Vm::new()
@@ -359,7 +348,6 @@ mod tests {
 
     #[test]
     fn test_events_on_registers_handler() {
-        reset_events();
         let mut vm = create_test_vm();
         let handler = create_mock_handler();
 
@@ -376,7 +364,6 @@ mod tests {
 
     #[test]
     fn test_events_on_wrong_arg_count() {
-        reset_events();
         let mut vm = create_test_vm();
 
         let args = vec![Value::Str("TestEvent".to_string())];
@@ -387,7 +374,6 @@ mod tests {
 
     #[test]
     fn test_events_on_wrong_event_type() {
-        reset_events();
         let mut vm = create_test_vm();
         let handler = create_mock_handler();
 
@@ -402,7 +388,6 @@ mod tests {
 
     #[test]
     fn test_events_on_wrong_handler_type() {
-        reset_events();
         let mut vm = create_test_vm();
 
         let args = vec![Value::Str("TestEvent".to_string()), Value::Int(42)];
@@ -416,38 +401,36 @@ mod tests {
 
     #[test]
     fn test_events_off_removes_handler() {
-        reset_events();
         let mut vm = create_test_vm();
         let handler = create_mock_handler();
 
         let args = vec![Value::Str("TestEvent".to_string()), handler];
         let handler_id = events_on(&mut vm, &args).unwrap();
 
-        let result = events_off(&handler_id);
+        let result = events_off(&vm, &handler_id);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), Value::Bool(true));
     }
 
     #[test]
     fn test_events_off_not_found() {
-        reset_events();
+        let vm = create_test_vm();
 
-        let result = events_off(&Value::Int(9999));
+        let result = events_off(&vm, &Value::Int(9999));
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), Value::Bool(false));
     }
 
     #[test]
     fn test_events_off_wrong_type() {
-        reset_events();
+        let vm = create_test_vm();
 
-        let result = events_off(&Value::Str("not an id".to_string()));
+        let result = events_off(&vm, &Value::Str("not an id".to_string()));
         assert!(result.is_err());
     }
 
     #[test]
     fn test_events_clear_removes_all_handlers_for_event() {
-        reset_events();
         let mut vm = create_test_vm();
         let handler = create_mock_handler();
 
@@ -457,29 +440,28 @@ mod tests {
         events_on(&mut vm, &args).unwrap();
 
         // Verify handlers exist
-        let count = events_handlers(&Value::Str("TestEvent".to_string())).unwrap();
+        let count = events_handlers(&vm, &Value::Str("TestEvent".to_string())).unwrap();
         assert_eq!(count, Value::Int(2));
 
         // Clear handlers
-        let result = events_clear(&Value::Str("TestEvent".to_string()));
+        let result = events_clear(&vm, &Value::Str("TestEvent".to_string()));
         assert!(result.is_ok());
 
         // Verify handlers are gone
-        let count = events_handlers(&Value::Str("TestEvent".to_string())).unwrap();
+        let count = events_handlers(&vm, &Value::Str("TestEvent".to_string())).unwrap();
         assert_eq!(count, Value::Int(0));
     }
 
     #[test]
     fn test_events_clear_wrong_type() {
-        reset_events();
+        let vm = create_test_vm();
 
-        let result = events_clear(&Value::Int(42));
+        let result = events_clear(&vm, &Value::Int(42));
         assert!(result.is_err());
     }
 
     #[test]
     fn test_events_clear_all() {
-        reset_events();
         let mut vm = create_test_vm();
         let handler = create_mock_handler();
 
@@ -492,22 +474,21 @@ mod tests {
         events_on(&mut vm, &[Value::Str("Event2".to_string()), handler]).unwrap();
 
         // Clear all
-        let result = events_clear_all(&Value::Unit);
+        let result = events_clear_all(&vm, &Value::Unit);
         assert!(result.is_ok());
 
         // Verify all are gone
-        let list = events_list(&Value::Unit).unwrap();
+        let list = events_list(&vm, &Value::Unit).unwrap();
         assert_eq!(list, Value::Nil);
     }
 
     #[test]
     fn test_events_handlers_count() {
-        reset_events();
         let mut vm = create_test_vm();
         let handler = create_mock_handler();
 
         // No handlers initially
-        let count = events_handlers(&Value::Str("TestEvent".to_string())).unwrap();
+        let count = events_handlers(&vm, &Value::Str("TestEvent".to_string())).unwrap();
         assert_eq!(count, Value::Int(0));
 
         // Add handlers
@@ -516,29 +497,28 @@ mod tests {
         events_on(&mut vm, &args).unwrap();
         events_on(&mut vm, &args).unwrap();
 
-        let count = events_handlers(&Value::Str("TestEvent".to_string())).unwrap();
+        let count = events_handlers(&vm, &Value::Str("TestEvent".to_string())).unwrap();
         assert_eq!(count, Value::Int(3));
     }
 
     #[test]
     fn test_events_handlers_wrong_type() {
-        reset_events();
+        let vm = create_test_vm();
 
-        let result = events_handlers(&Value::Int(42));
+        let result = events_handlers(&vm, &Value::Int(42));
         assert!(result.is_err());
     }
 
     #[test]
     fn test_events_list_empty() {
-        reset_events();
+        let vm = create_test_vm();
 
-        let result = events_list(&Value::Unit).unwrap();
+        let result = events_list(&vm, &Value::Unit).unwrap();
         assert_eq!(result, Value::Nil);
     }
 
     #[test]
     fn test_events_list_with_events() {
-        reset_events();
         let mut vm = create_test_vm();
         let handler = create_mock_handler();
 
@@ -546,7 +526,7 @@ mod tests {
         events_on(&mut vm, &[Value::Str("Beta".to_string()), handler.clone()]).unwrap();
         events_on(&mut vm, &[Value::Str("Gamma".to_string()), handler]).unwrap();
 
-        let result = events_list(&Value::Unit).unwrap();
+        let result = events_list(&vm, &Value::Unit).unwrap();
 
         // Convert to vec for easier testing
         let vec = result.list_to_vec().unwrap();
@@ -558,7 +538,6 @@ mod tests {
 
     #[test]
     fn test_events_once_registers_handler() {
-        reset_events();
         let mut vm = create_test_vm();
         let handler = create_mock_handler();
 
@@ -575,7 +554,6 @@ mod tests {
 
     #[test]
     fn test_events_emit_wrong_arg_count() {
-        reset_events();
         let mut vm = create_test_vm();
 
         let args = vec![Value::Str("TestEvent".to_string())];
@@ -586,7 +564,6 @@ mod tests {
 
     #[test]
     fn test_events_emit_wrong_event_type() {
-        reset_events();
         let mut vm = create_test_vm();
 
         let args = vec![Value::Int(42), Value::Unit];
@@ -597,7 +574,6 @@ mod tests {
 
     #[test]
     fn test_events_emit_no_handlers() {
-        reset_events();
         let mut vm = create_test_vm();
 
         let args = vec![Value::Str("NoHandlers".to_string()), Value::Unit];
@@ -609,7 +585,6 @@ mod tests {
 
     #[test]
     fn test_unique_handler_ids() {
-        reset_events();
         let mut vm = create_test_vm();
         let handler = create_mock_handler();
 
@@ -624,4 +599,32 @@ mod tests {
         assert_ne!(id2, id3);
         assert_ne!(id1, id3);
     }
+
+    #[test]
+    fn test_each_vm_has_its_own_handlers() {
+        let mut vm_a = create_test_vm();
+        let vm_b = create_test_vm();
+        let handler = create_mock_handler();
+
+        events_on(&mut vm_a, &[Value::Str("TestEvent".to_string()), handler]).unwrap();
+
+        let name = Value::Str("TestEvent".to_string());
+        assert_eq!(events_handlers(&vm_a, &name).unwrap(), Value::Int(1));
+        assert_eq!(events_handlers(&vm_b, &name).unwrap(), Value::Int(0));
+    }
+
+    #[test]
+    fn test_shared_event_bus() {
+        let mut vm_a = create_test_vm();
+        let mut vm_b = create_test_vm();
+        vm_b.context.events = vm_a.context.events.clone();
+        let handler = create_mock_handler();
+
+        let id = events_once(&mut vm_a, &[Value::Str("Startup".to_string()), handler]).unwrap();
+
+        let name = Value::Str("Startup".to_string());
+        assert_eq!(events_handlers(&vm_b, &name).unwrap(), Value::Int(1));
+        assert_eq!(events_off(&vm_b, &id).unwrap(), Value::Bool(true));
+        assert_eq!(events_handlers(&vm_a, &name).unwrap(), Value::Int(0));
+    }
 }
@@ -1,7 +1,7 @@
 // Fusabi File Standard Library
 // Provides file I/O operations with line-based support
 
-use crate::value::Value;
+use crate::value::{Seq, SeqSource, Value};
 use crate::vm::VmError;
 use std::fs::{self, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
@@ -38,6 +38,18 @@ pub fn file_read_lines(path: &Value) -> Result<Value, VmError> {
     Ok(result)
 }
 
+/// File.lines : string -> seq<string>
+/// Reads the lines of a file lazily, as the sequence is enumerated
+pub fn file_lines(path: &Value) -> Result<Value, VmError> {
+    match path {
+        Value::Str(s) => Ok(Value::Seq(Seq::new(SeqSource::FileLines(s.clone())))),
+        _ => Err(VmError::TypeMismatch {
+            expected: "string",
+            got: path.type_name(),
+        }),
+    }
+}
+
 /// File.writeLines : string -> string list -> unit
 /// Writes a list of lines to a file (overwrites existing content)
 pub fn file_write_lines(path: &Value, lines: &Value) -> Result<Value, VmError> {
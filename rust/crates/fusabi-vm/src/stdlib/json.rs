@@ -84,6 +84,13 @@ fn fusabi_value_to_json(value: &Value) -> Result<serde_json::Value, VmError> {
             .map(serde_json::Value::Number)
             .ok_or_else(|| VmError::Runtime("Invalid float value for JSON".to_string())),
         Value::Str(s) => Ok(serde_json::Value::String(s.clone())),
+        Value::Char(c) => Ok(serde_json::Value::String(c.to_string())),
+        Value::Bytes(bytes) => Ok(serde_json::Value::Array(
+            bytes
+                .iter()
+                .map(|b| serde_json::Value::Number((*b).into()))
+                .collect(),
+        )),
         Value::Array(arr) => {
             let borrowed = arr.lock().unwrap();
             let mut json_arr = Vec::new();
@@ -232,6 +239,14 @@ mod tests {
         assert_eq!(result, Value::Str("true".to_string()));
     }
 
+    #[test]
+    fn test_stringify_char_and_bytes() {
+        let result = json_stringify(&Value::Char('x')).unwrap();
+        assert_eq!(result, Value::Str("\"x\"".to_string()));
+        let result = json_stringify(&Value::Bytes(vec![0, 127, 255])).unwrap();
+        assert_eq!(result, Value::Str("[0,127,255]".to_string()));
+    }
+
     #[test]
     fn test_stringify_int() {
         let result = json_stringify(&Value::Int(42)).unwrap();
@@ -3,6 +3,8 @@
 
 pub mod array;
 pub mod async_ops;
+pub mod bytes;
+pub mod char_ops;
 pub mod commands;
 pub mod config;
 pub mod console;
@@ -15,8 +17,10 @@ pub mod navigation;
 pub mod option;
 pub mod print;
 pub mod process;
+pub mod ref_cell;
 pub mod result;
 pub mod script;
+pub mod seq;
 pub mod string;
 pub mod terminal_control;
 pub mod terminal_info;
@@ -90,6 +94,63 @@ pub fn register_stdlib(vm: &mut Vm) {
             async_ops::async_combine_helper,
         );
 
+        // Seq functions
+        registry.register("Seq.singleton", |_vm, args| {
+            wrap_unary(args, seq::seq_singleton)
+        });
+        registry.register("Seq.ofList", |_vm, args| {
+            wrap_unary(args, seq::seq_of_collection)
+        });
+        registry.register("Seq.ofArray", |_vm, args| {
+            wrap_unary(args, seq::seq_of_collection)
+        });
+        registry.register("Seq.append", |_vm, args| wrap_binary(args, seq::seq_append));
+        registry.register("Seq.delay", |_vm, args| wrap_unary(args, seq::seq_delay));
+        registry.register("Seq.map", |_vm, args| wrap_binary(args, seq::seq_map));
+        registry.register("Seq.filter", |_vm, args| wrap_binary(args, seq::seq_filter));
+        registry.register("Seq.collect", |_vm, args| {
+            wrap_binary(args, seq::seq_collect)
+        });
+        registry.register("Seq.take", |_vm, args| wrap_binary(args, seq::seq_take));
+        registry.register("Seq.skip", |_vm, args| wrap_binary(args, seq::seq_skip));
+        registry.register("Seq.takeWhile", |_vm, args| {
+            wrap_binary(args, seq::seq_take_while)
+        });
+        registry.register("Seq.unfold", |_vm, args| wrap_binary(args, seq::seq_unfold));
+        registry.register("Seq.init", |_vm, args| wrap_binary(args, seq::seq_init));
+        registry.register("Seq.initInfinite", |_vm, args| {
+            wrap_unary(args, seq::seq_init_infinite)
+        });
+        registry.register("Seq.toList", seq::seq_to_list);
+        registry.register("Seq.toArray", seq::seq_to_array);
+        registry.register("Seq.iter", seq::seq_iter);
+        registry.register("Seq.fold", seq::seq_fold);
+        registry.register("Seq.length", seq::seq_length);
+        registry.register("Seq.head", seq::seq_head);
+        registry.register("Seq.isEmpty", seq::seq_is_empty);
+        registry.register("Seq.exists", seq::seq_exists);
+        registry.register("Seq.tryFind", seq::seq_try_find);
+        // Builder methods for seq { ... }
+        registry.register("seq.Yield", |_vm, args| {
+            wrap_unary(args, seq::seq_builder_yield)
+        });
+        registry.register("seq.YieldFrom", |_vm, args| {
+            wrap_unary(args, seq::seq_builder_yield_from)
+        });
+        registry.register("seq.Combine", |_vm, args| {
+            wrap_binary(args, seq::seq_builder_combine)
+        });
+        registry.register("seq.Delay", |_vm, args| {
+            wrap_unary(args, seq::seq_builder_delay)
+        });
+        registry.register("seq.Zero", seq::seq_builder_zero);
+        registry.register("seq.For", |_vm, args| {
+            wrap_binary(args, seq::seq_builder_for)
+        });
+        registry.register("seq.Range", |_vm, args| {
+            wrap_ternary(args, seq::seq_builder_range)
+        });
+
         // Array functions
         registry.register("Array.length", |_vm, args| {
             wrap_unary(args, array::array_length)
@@ -143,16 +204,95 @@ pub fn register_stdlib(vm: &mut Vm) {
         registry.register("String.format", |_vm, args| {
             wrap_binary(args, string::string_format)
         });
+        registry.register("String.toChars", |_vm, args| {
+            wrap_unary(args, string::string_to_chars)
+        });
+        registry.register("String.ofChars", |_vm, args| {
+            wrap_unary(args, string::string_of_chars)
+        });
         registry.register("sprintf", |_vm, args| {
             wrap_binary(args, string::string_format)
         });
 
+        // Char functions
+        registry.register("Char.isDigit", |_vm, args| {
+            wrap_unary(args, char_ops::char_is_digit)
+        });
+        registry.register("Char.isLetter", |_vm, args| {
+            wrap_unary(args, char_ops::char_is_letter)
+        });
+        registry.register("Char.isLetterOrDigit", |_vm, args| {
+            wrap_unary(args, char_ops::char_is_letter_or_digit)
+        });
+        registry.register("Char.isWhiteSpace", |_vm, args| {
+            wrap_unary(args, char_ops::char_is_white_space)
+        });
+        registry.register("Char.isUpper", |_vm, args| {
+            wrap_unary(args, char_ops::char_is_upper)
+        });
+        registry.register("Char.isLower", |_vm, args| {
+            wrap_unary(args, char_ops::char_is_lower)
+        });
+        registry.register("Char.toUpper", |_vm, args| {
+            wrap_unary(args, char_ops::char_to_upper)
+        });
+        registry.register("Char.toLower", |_vm, args| {
+            wrap_unary(args, char_ops::char_to_lower)
+        });
+        registry.register("Char.toInt", |_vm, args| {
+            wrap_unary(args, char_ops::char_to_int)
+        });
+        registry.register("Char.ofInt", |_vm, args| {
+            wrap_unary(args, char_ops::char_of_int)
+        });
+        registry.register("Char.toString", |_vm, args| {
+            wrap_unary(args, char_ops::char_to_string)
+        });
+
+        // Bytes functions
+        registry.register("Bytes.length", |_vm, args| {
+            wrap_unary(args, bytes::bytes_length)
+        });
+        registry.register("Bytes.get", |_vm, args| wrap_binary(args, bytes::bytes_get));
+        registry.register("Bytes.ofString", |_vm, args| {
+            wrap_unary(args, bytes::bytes_of_string)
+        });
+        registry.register("Bytes.toString", |_vm, args| {
+            wrap_unary(args, bytes::bytes_to_string)
+        });
+        registry.register("Bytes.ofList", |_vm, args| {
+            wrap_unary(args, bytes::bytes_of_list)
+        });
+        registry.register("Bytes.toList", |_vm, args| {
+            wrap_unary(args, bytes::bytes_to_list)
+        });
+        registry.register("Bytes.append", |_vm, args| {
+            wrap_binary(args, bytes::bytes_append)
+        });
+
         // Print functions (global functions, not in a module)
         registry.register("print", |_vm, args| wrap_unary(args, print::print_value));
         registry.register("printfn", |_vm, args| {
             wrap_unary(args, print::printfn_value)
         });
 
+        // Exceptions (global functions, not in a module)
+        registry.register("failwith", |_vm, args| {
+            wrap_unary(args, crate::exception::failwith)
+        });
+        registry.register("raise", |_vm, args| {
+            wrap_unary(args, crate::exception::raise)
+        });
+
+        // Reference cells (global functions, not in a module)
+        registry.register("ref", |_vm, args| wrap_unary(args, ref_cell::ref_create));
+        registry.register("op_Dereference", |_vm, args| {
+            wrap_unary(args, ref_cell::ref_get)
+        });
+        registry.register("op_ColonEquals", |_vm, args| {
+            wrap_binary(args, ref_cell::ref_set)
+        });
+
         // Math functions
         registry.register("Math.pi", |_vm, args| wrap_unary(args, math::math_pi));
         registry.register("Math.e", |_vm, args| wrap_unary(args, math::math_e));
@@ -218,6 +358,19 @@ pub fn register_stdlib(vm: &mut Vm) {
         registry.register("Option.orElse", |_vm, args| {
             wrap_binary(args, option::option_or_else)
         });
+        // Builder methods for option { ... }
+        registry.register("Option.Bind", option::option_builder_bind);
+        registry.register("Option.Return", |_vm, args| {
+            wrap_unary(args, option::option_builder_return)
+        });
+        registry.register("Option.ReturnFrom", |_vm, args| {
+            wrap_unary(args, option::option_builder_return_from)
+        });
+        registry.register("Option.Zero", option::option_builder_zero);
+        registry.register("Option.Delay", option::option_builder_delay);
+        registry.register("Option.Combine", |_vm, args| {
+            wrap_binary(args, option::option_builder_combine)
+        });
 
         // Option constructors - Some and None
         registry.register("Some", |_vm, args| {
@@ -262,6 +415,19 @@ pub fn register_stdlib(vm: &mut Vm) {
         registry.register("Result.mapError", result::result_map_error);
         registry.register("Result.bind", result::result_bind);
         registry.register("Result.iter", result::result_iter);
+        // Builder methods for result { ... }
+        registry.register("Result.Bind", result::result_builder_bind);
+        registry.register("Result.Return", |_vm, args| {
+            wrap_unary(args, result::result_builder_return)
+        });
+        registry.register("Result.ReturnFrom", |_vm, args| {
+            wrap_unary(args, result::result_builder_return_from)
+        });
+        registry.register("Result.Zero", result::result_builder_zero);
+        registry.register("Result.Delay", result::result_builder_delay);
+        registry.register("Result.Combine", |_vm, args| {
+            wrap_binary(args, result::result_builder_combine)
+        });
 
         // Result constructors - Ok and Error
         registry.register("Ok", |_vm, args| {
@@ -310,21 +476,21 @@ pub fn register_stdlib(vm: &mut Vm) {
 
         // Config functions
         registry.register("Config.define", config::config_define);
-        registry.register("Config.get", |_vm, args| {
-            wrap_unary(args, config::config_get)
+        registry.register("Config.get", |vm, args| {
+            wrap_unary(args, |a| config::config_get(vm, a))
         });
-        registry.register("Config.getOr", |_vm, args| {
-            wrap_binary(args, config::config_get_or)
+        registry.register("Config.getOr", |vm, args| {
+            wrap_binary(args, |a, b| config::config_get_or(vm, a, b))
         });
         registry.register("Config.set", config::config_set);
-        registry.register("Config.has", |_vm, args| {
-            wrap_unary(args, config::config_has)
+        registry.register("Config.has", |vm, args| {
+            wrap_unary(args, |a| config::config_has(vm, a))
         });
-        registry.register("Config.list", |_vm, args| {
-            wrap_unary(args, config::config_list)
+        registry.register("Config.list", |vm, args| {
+            wrap_unary(args, |a| config::config_list(vm, a))
         });
-        registry.register("Config.reset", |_vm, args| {
-            wrap_unary(args, config::config_reset)
+        registry.register("Config.reset", |vm, args| {
+            wrap_unary(args, |a| config::config_reset(vm, a))
         });
 
         // Time functions
@@ -395,78 +561,78 @@ pub fn register_stdlib(vm: &mut Vm) {
 
         // Events functions
         registry.register("Events.on", events::events_on);
-        registry.register("Events.off", |_vm, args| {
-            wrap_unary(args, events::events_off)
+        registry.register("Events.off", |vm, args| {
+            wrap_unary(args, |a| events::events_off(vm, a))
         });
         registry.register("Events.emit", events::events_emit);
         registry.register("Events.emitAsync", events::events_emit_async);
         registry.register("Events.once", events::events_once);
-        registry.register("Events.clear", |_vm, args| {
-            wrap_unary(args, events::events_clear)
+        registry.register("Events.clear", |vm, args| {
+            wrap_unary(args, |a| events::events_clear(vm, a))
         });
-        registry.register("Events.clearAll", |_vm, args| {
-            wrap_unary(args, events::events_clear_all)
+        registry.register("Events.clearAll", |vm, args| {
+            wrap_unary(args, |a| events::events_clear_all(vm, a))
         });
-        registry.register("Events.handlers", |_vm, args| {
-            wrap_unary(args, events::events_handlers)
+        registry.register("Events.handlers", |vm, args| {
+            wrap_unary(args, |a| events::events_handlers(vm, a))
         });
-        registry.register("Events.list", |_vm, args| {
-            wrap_unary(args, events::events_list)
+        registry.register("Events.list", |vm, args| {
+            wrap_unary(args, |a| events::events_list(vm, a))
         });
 
         // TerminalInfo functions
-        registry.register("TerminalInfo.getForegroundProcess", |_vm, args| {
-            wrap_unary(args, terminal_info::get_foreground_process)
+        registry.register("TerminalInfo.getForegroundProcess", |vm, args| {
+            wrap_unary(args, |a| terminal_info::get_foreground_process(vm, a))
         });
-        registry.register("TerminalInfo.getCurrentWorkingDir", |_vm, args| {
-            wrap_unary(args, terminal_info::get_current_working_dir)
+        registry.register("TerminalInfo.getCurrentWorkingDir", |vm, args| {
+            wrap_unary(args, |a| terminal_info::get_current_working_dir(vm, a))
         });
-        registry.register("TerminalInfo.getLine", |_vm, args| {
-            wrap_unary(args, terminal_info::get_line)
+        registry.register("TerminalInfo.getLine", |vm, args| {
+            wrap_unary(args, |a| terminal_info::get_line(vm, a))
         });
-        registry.register("TerminalInfo.getLines", |_vm, args| {
-            wrap_binary(args, terminal_info::get_lines)
+        registry.register("TerminalInfo.getLines", |vm, args| {
+            wrap_binary(args, |a, b| terminal_info::get_lines(vm, a, b))
         });
-        registry.register("TerminalInfo.getWindowTitle", |_vm, args| {
-            wrap_unary(args, terminal_info::get_window_title)
+        registry.register("TerminalInfo.getWindowTitle", |vm, args| {
+            wrap_unary(args, |a| terminal_info::get_window_title(vm, a))
         });
-        registry.register("TerminalInfo.getTabTitle", |_vm, args| {
-            wrap_unary(args, terminal_info::get_tab_title)
+        registry.register("TerminalInfo.getTabTitle", |vm, args| {
+            wrap_unary(args, |a| terminal_info::get_tab_title(vm, a))
         });
-        registry.register("TerminalInfo.getTerminalSize", |_vm, args| {
-            wrap_unary(args, terminal_info::get_terminal_size)
+        registry.register("TerminalInfo.getTerminalSize", |vm, args| {
+            wrap_unary(args, |a| terminal_info::get_terminal_size(vm, a))
         });
 
         // TerminalControl functions
-        registry.register("TerminalControl.sendText", |_vm, args| {
-            wrap_unary(args, terminal_control::send_text)
+        registry.register("TerminalControl.sendText", |vm, args| {
+            wrap_unary(args, |a| terminal_control::send_text(vm, a))
         });
-        registry.register("TerminalControl.sendKeys", |_vm, args| {
-            wrap_unary(args, terminal_control::send_keys)
+        registry.register("TerminalControl.sendKeys", |vm, args| {
+            wrap_unary(args, |a| terminal_control::send_keys(vm, a))
         });
-        registry.register("TerminalControl.splitHorizontal", |_vm, args| {
-            wrap_unary(args, terminal_control::split_horizontal)
+        registry.register("TerminalControl.splitHorizontal", |vm, args| {
+            wrap_unary(args, |a| terminal_control::split_horizontal(vm, a))
         });
-        registry.register("TerminalControl.splitVertical", |_vm, args| {
-            wrap_unary(args, terminal_control::split_vertical)
+        registry.register("TerminalControl.splitVertical", |vm, args| {
+            wrap_unary(args, |a| terminal_control::split_vertical(vm, a))
         });
-        registry.register("TerminalControl.closePane", |_vm, args| {
-            wrap_unary(args, terminal_control::close_pane)
+        registry.register("TerminalControl.closePane", |vm, args| {
+            wrap_unary(args, |a| terminal_control::close_pane(vm, a))
         });
-        registry.register("TerminalControl.focusPane", |_vm, args| {
-            wrap_unary(args, terminal_control::focus_pane)
+        registry.register("TerminalControl.focusPane", |vm, args| {
+            wrap_unary(args, |a| terminal_control::focus_pane(vm, a))
         });
-        registry.register("TerminalControl.createTab", |_vm, args| {
-            wrap_unary(args, terminal_control::create_tab)
+        registry.register("TerminalControl.createTab", |vm, args| {
+            wrap_unary(args, |a| terminal_control::create_tab(vm, a))
         });
-        registry.register("TerminalControl.closeTab", |_vm, args| {
-            wrap_unary(args, terminal_control::close_tab)
+        registry.register("TerminalControl.closeTab", |vm, args| {
+            wrap_unary(args, |a| terminal_control::close_tab(vm, a))
         });
-        registry.register("TerminalControl.setTabTitle", |_vm, args| {
-            wrap_binary(args, terminal_control::set_tab_title)
+        registry.register("TerminalControl.setTabTitle", |vm, args| {
+            wrap_binary(args, |a, b| terminal_control::set_tab_title(vm, a, b))
         });
-        registry.register("TerminalControl.showToast", |_vm, args| {
-            wrap_unary(args, terminal_control::show_toast)
+        registry.register("TerminalControl.showToast", |vm, args| {
+            wrap_unary(args, |a| terminal_control::show_toast(vm, a))
         });
 
         // UIFormatting functions
@@ -519,6 +685,7 @@ pub fn register_stdlib(vm: &mut Vm) {
         registry.register("File.readLines", |_vm, args| {
             wrap_unary(args, file::file_read_lines)
         });
+        registry.register("File.lines", |_vm, args| wrap_unary(args, file::file_lines));
         registry.register("File.writeLines", |_vm, args| {
             wrap_binary(args, file::file_write_lines)
         });
@@ -590,11 +757,48 @@ pub fn register_stdlib(vm: &mut Vm) {
     string_fields.insert("startsWith".to_string(), native("String.startsWith", 2));
     string_fields.insert("endsWith".to_string(), native("String.endsWith", 2));
     string_fields.insert("format".to_string(), native("String.format", 2));
+    string_fields.insert("toChars".to_string(), native("String.toChars", 1));
+    string_fields.insert("ofChars".to_string(), native("String.ofChars", 1));
     vm.globals.insert(
         "String".to_string(),
         Value::Record(Arc::new(Mutex::new(string_fields))),
     );
 
+    // Char Module
+    let mut char_fields = HashMap::new();
+    char_fields.insert("isDigit".to_string(), native("Char.isDigit", 1));
+    char_fields.insert("isLetter".to_string(), native("Char.isLetter", 1));
+    char_fields.insert(
+        "isLetterOrDigit".to_string(),
+        native("Char.isLetterOrDigit", 1),
+    );
+    char_fields.insert("isWhiteSpace".to_string(), native("Char.isWhiteSpace", 1));
+    char_fields.insert("isUpper".to_string(), native("Char.isUpper", 1));
+    char_fields.insert("isLower".to_string(), native("Char.isLower", 1));
+    char_fields.insert("toUpper".to_string(), native("Char.toUpper", 1));
+    char_fields.insert("toLower".to_string(), native("Char.toLower", 1));
+    char_fields.insert("toInt".to_string(), native("Char.toInt", 1));
+    char_fields.insert("ofInt".to_string(), native("Char.ofInt", 1));
+    char_fields.insert("toString".to_string(), native("Char.toString", 1));
+    vm.globals.insert(
+        "Char".to_string(),
+        Value::Record(Arc::new(Mutex::new(char_fields))),
+    );
+
+    // Bytes Module
+    let mut bytes_fields = HashMap::new();
+    bytes_fields.insert("length".to_string(), native("Bytes.length", 1));
+    bytes_fields.insert("get".to_string(), native("Bytes.get", 2));
+    bytes_fields.insert("ofString".to_string(), native("Bytes.ofString", 1));
+    bytes_fields.insert("toString".to_string(), native("Bytes.toString", 1));
+    bytes_fields.insert("ofList".to_string(), native("Bytes.ofList", 1));
+    bytes_fields.insert("toList".to_string(), native("Bytes.toList", 1));
+    bytes_fields.insert("append".to_string(), native("Bytes.append", 2));
+    vm.globals.insert(
+        "Bytes".to_string(),
+        Value::Record(Arc::new(Mutex::new(bytes_fields))),
+    );
+
     // Register sprintf as a global alias for String.format
     vm.globals
         .insert("sprintf".to_string(), native("sprintf", 2));
@@ -604,6 +808,13 @@ pub fn register_stdlib(vm: &mut Vm) {
     vm.globals
         .insert("printfn".to_string(), native("printfn", 1));
 
+    // Register reference cell operators as globals
+    vm.globals.insert("ref".to_string(), native("ref", 1));
+    vm.globals
+        .insert("op_Dereference".to_string(), native("op_Dereference", 1));
+    vm.globals
+        .insert("op_ColonEquals".to_string(), native("op_ColonEquals", 2));
+
     // Math Module
     let mut math_fields = HashMap::new();
     math_fields.insert("pi".to_string(), native("Math.pi", 1));
@@ -677,10 +888,17 @@ pub fn register_stdlib(vm: &mut Vm) {
     option_fields.insert("iter".to_string(), native("Option.iter", 2));
     option_fields.insert("map2".to_string(), native("Option.map2", 3));
     option_fields.insert("orElse".to_string(), native("Option.orElse", 2));
-    vm.globals.insert(
-        "Option".to_string(),
-        Value::Record(Arc::new(Mutex::new(option_fields))),
-    );
+    option_fields.insert("Bind".to_string(), native("Option.Bind", 2));
+    option_fields.insert("Return".to_string(), native("Option.Return", 1));
+    option_fields.insert("ReturnFrom".to_string(), native("Option.ReturnFrom", 1));
+    option_fields.insert("Zero".to_string(), native("Option.Zero", 0));
+    option_fields.insert("Delay".to_string(), native("Option.Delay", 1));
+    option_fields.insert("Combine".to_string(), native("Option.Combine", 2));
+    let option_module = Value::Record(Arc::new(Mutex::new(option_fields)));
+    // 'option { ... }' desugars to 'option.Bind', 'option.Return', etc.
+    vm.globals
+        .insert("option".to_string(), option_module.clone());
+    vm.globals.insert("Option".to_string(), option_module);
 
     // Register Option constructors as globals
     vm.globals.insert("Some".to_string(), native("Some", 1));
@@ -696,10 +914,17 @@ pub fn register_stdlib(vm: &mut Vm) {
     result_fields.insert("mapError".to_string(), native("Result.mapError", 2));
     result_fields.insert("bind".to_string(), native("Result.bind", 2));
     result_fields.insert("iter".to_string(), native("Result.iter", 2));
-    vm.globals.insert(
-        "Result".to_string(),
-        Value::Record(Arc::new(Mutex::new(result_fields))),
-    );
+    result_fields.insert("Bind".to_string(), native("Result.Bind", 2));
+    result_fields.insert("Return".to_string(), native("Result.Return", 1));
+    result_fields.insert("ReturnFrom".to_string(), native("Result.ReturnFrom", 1));
+    result_fields.insert("Zero".to_string(), native("Result.Zero", 0));
+    result_fields.insert("Delay".to_string(), native("Result.Delay", 1));
+    result_fields.insert("Combine".to_string(), native("Result.Combine", 2));
+    let result_module = Value::Record(Arc::new(Mutex::new(result_fields)));
+    // 'result { ... }' desugars to 'result.Bind', 'result.Return', etc.
+    vm.globals
+        .insert("result".to_string(), result_module.clone());
+    vm.globals.insert("Result".to_string(), result_module);
 
     // Register Result constructors as globals
     vm.globals.insert("Ok".to_string(), native("Ok", 1));
@@ -958,6 +1183,7 @@ pub fn register_stdlib(vm: &mut Vm) {
     // File Module
     let mut file_fields = HashMap::new();
     file_fields.insert("readLines".to_string(), native("File.readLines", 1));
+    file_fields.insert("lines".to_string(), native("File.lines", 1));
     file_fields.insert("writeLines".to_string(), native("File.writeLines", 2));
     file_fields.insert("appendLine".to_string(), native("File.appendLine", 2));
     vm.globals.insert(
@@ -997,6 +1223,51 @@ pub fn register_stdlib(vm: &mut Vm) {
         vm.globals.insert("async".to_string(), async_val.clone());
     }
 
+    // Seq Module
+    let mut seq_fields = HashMap::new();
+    seq_fields.insert("empty".to_string(), Value::Seq(crate::value::Seq::empty()));
+    seq_fields.insert("singleton".to_string(), native("Seq.singleton", 1));
+    seq_fields.insert("ofList".to_string(), native("Seq.ofList", 1));
+    seq_fields.insert("ofArray".to_string(), native("Seq.ofArray", 1));
+    seq_fields.insert("append".to_string(), native("Seq.append", 2));
+    seq_fields.insert("delay".to_string(), native("Seq.delay", 1));
+    seq_fields.insert("map".to_string(), native("Seq.map", 2));
+    seq_fields.insert("filter".to_string(), native("Seq.filter", 2));
+    seq_fields.insert("collect".to_string(), native("Seq.collect", 2));
+    seq_fields.insert("take".to_string(), native("Seq.take", 2));
+    seq_fields.insert("skip".to_string(), native("Seq.skip", 2));
+    seq_fields.insert("takeWhile".to_string(), native("Seq.takeWhile", 2));
+    seq_fields.insert("unfold".to_string(), native("Seq.unfold", 2));
+    seq_fields.insert("init".to_string(), native("Seq.init", 2));
+    seq_fields.insert("initInfinite".to_string(), native("Seq.initInfinite", 1));
+    seq_fields.insert("toList".to_string(), native("Seq.toList", 1));
+    seq_fields.insert("toArray".to_string(), native("Seq.toArray", 1));
+    seq_fields.insert("iter".to_string(), native("Seq.iter", 2));
+    seq_fields.insert("fold".to_string(), native("Seq.fold", 3));
+    seq_fields.insert("length".to_string(), native("Seq.length", 1));
+    seq_fields.insert("head".to_string(), native("Seq.head", 1));
+    seq_fields.insert("isEmpty".to_string(), native("Seq.isEmpty", 1));
+    seq_fields.insert("exists".to_string(), native("Seq.exists", 2));
+    seq_fields.insert("tryFind".to_string(), native("Seq.tryFind", 2));
+    vm.globals.insert(
+        "Seq".to_string(),
+        Value::Record(Arc::new(Mutex::new(seq_fields))),
+    );
+
+    // 'seq { ... }' desugars to 'seq.Yield', 'seq.Combine', etc.
+    let mut seq_builder_fields = HashMap::new();
+    seq_builder_fields.insert("Yield".to_string(), native("seq.Yield", 1));
+    seq_builder_fields.insert("YieldFrom".to_string(), native("seq.YieldFrom", 1));
+    seq_builder_fields.insert("Combine".to_string(), native("seq.Combine", 2));
+    seq_builder_fields.insert("Delay".to_string(), native("seq.Delay", 1));
+    seq_builder_fields.insert("Zero".to_string(), native("seq.Zero", 0));
+    seq_builder_fields.insert("For".to_string(), native("seq.For", 2));
+    seq_builder_fields.insert("Range".to_string(), native("seq.Range", 3));
+    vm.globals.insert(
+        "seq".to_string(),
+        Value::Record(Arc::new(Mutex::new(seq_builder_fields))),
+    );
+
     // Nav Module (Navigation/Keymap for Scarab integration)
     let mut nav_fields = HashMap::new();
     nav_fields.insert("getKeymap".to_string(), native("Nav.getKeymap", 0));
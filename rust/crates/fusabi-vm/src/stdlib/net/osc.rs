@@ -64,10 +64,12 @@ fn value_to_osc_type(v: &Value) -> Result<OscType, VmError> {
         Value::Int(i) => Ok(OscType::Int(*i as i32)),
         Value::Float(f) => Ok(OscType::Float(*f as f32)),
         Value::Str(s) => Ok(OscType::String(s.clone())),
+        Value::Char(c) => Ok(OscType::Char(*c)),
+        Value::Bytes(b) => Ok(OscType::Blob(b.clone())),
         Value::Bool(b) => Ok(OscType::Bool(*b)),
         Value::Unit => Ok(OscType::Nil),
         _ => Err(VmError::Runtime(format!(
-            "Cannot convert {} to OSC type. Supported types: int, float, string, bool, unit",
+            "Cannot convert {} to OSC type. Supported types: int, float, string, char, byte[], bool, unit",
             v.type_name()
         ))),
     }
@@ -254,6 +254,67 @@ pub fn option_or_else(opt1: &Value, opt2: &Value) -> Result<Value, VmError> {
     }
 }
 
+// ========== The option { ... } builder ==========
+
+fn some(value: Value) -> Value {
+    Value::Variant {
+        type_name: "Option".to_string(),
+        variant_name: "Some".to_string(),
+        fields: vec![value],
+    }
+}
+
+/// option.Bind : 'a option -> ('a -> 'b option) -> 'b option
+/// Continues with the value inside Some, stopping at the first None
+pub fn option_builder_bind(vm: &mut crate::vm::Vm, args: &[Value]) -> Result<Value, VmError> {
+    match args {
+        [opt, f] => option_bind(vm, &[f.clone(), opt.clone()]),
+        _ => Err(VmError::Runtime(format!(
+            "option.Bind expects 2 arguments, got {}",
+            args.len()
+        ))),
+    }
+}
+
+/// option.Return : 'a -> 'a option
+pub fn option_builder_return(value: &Value) -> Result<Value, VmError> {
+    Ok(some(value.clone()))
+}
+
+/// option.ReturnFrom : 'a option -> 'a option
+pub fn option_builder_return_from(opt: &Value) -> Result<Value, VmError> {
+    option_is_some(opt)?;
+    Ok(opt.clone())
+}
+
+/// option.Zero : unit -> unit option
+/// A branch with nothing to return carries on with Some ()
+pub fn option_builder_zero(_vm: &mut crate::vm::Vm, _args: &[Value]) -> Result<Value, VmError> {
+    Ok(some(Value::Unit))
+}
+
+/// option.Delay : (unit -> 'a option) -> 'a option
+/// Runs the rest of the block straight away
+pub fn option_builder_delay(vm: &mut crate::vm::Vm, args: &[Value]) -> Result<Value, VmError> {
+    match args {
+        [f] => vm.call_value(f.clone(), &[Value::Unit]),
+        _ => Err(VmError::Runtime(format!(
+            "option.Delay expects 1 argument, got {}",
+            args.len()
+        ))),
+    }
+}
+
+/// option.Combine : unit option -> 'a option -> 'a option
+/// Returns the second option unless the first is None
+pub fn option_builder_combine(first: &Value, second: &Value) -> Result<Value, VmError> {
+    if option_is_some(first)? == Value::Bool(true) {
+        Ok(second.clone())
+    } else {
+        Ok(first.clone())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -350,4 +411,20 @@ mod tests {
         let is_none = option_is_none(&other_variant).unwrap();
         assert_eq!(is_none, Value::Bool(false));
     }
+
+    #[test]
+    fn test_option_builder_combine_and_return() {
+        let none = make_none();
+        assert_eq!(
+            option_builder_return(&Value::Int(1)).unwrap(),
+            make_some(Value::Int(1))
+        );
+        assert_eq!(option_builder_return_from(&none).unwrap(), none);
+        assert!(option_builder_return_from(&Value::Int(1)).is_err());
+
+        let done = make_some(Value::Unit);
+        let rest = make_some(Value::Int(2));
+        assert_eq!(option_builder_combine(&done, &rest).unwrap(), rest);
+        assert_eq!(option_builder_combine(&none, &rest).unwrap(), none);
+    }
 }
@@ -0,0 +1,84 @@
+// Fusabi Reference Cells
+// A ref cell is a record with a single mutable `contents` field, matching
+// the F# representation, so `r.contents` works alongside `!r` and `r := v`.
+
+use crate::value::Value;
+use crate::vm::VmError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Field holding the value of a ref cell
+pub const CONTENTS_FIELD: &str = "contents";
+
+/// ref : 'a -> 'a ref
+/// Creates a new reference cell holding the value
+pub fn ref_create(value: &Value) -> Result<Value, VmError> {
+    let mut fields = HashMap::new();
+    fields.insert(CONTENTS_FIELD.to_string(), value.clone());
+    Ok(Value::Record(Arc::new(Mutex::new(fields))))
+}
+
+/// (!) : 'a ref -> 'a
+/// Returns the current contents of a reference cell
+pub fn ref_get(cell: &Value) -> Result<Value, VmError> {
+    match cell {
+        Value::Record(fields) => fields
+            .lock()
+            .unwrap()
+            .get(CONTENTS_FIELD)
+            .cloned()
+            .ok_or_else(|| VmError::Runtime("Record is not a reference cell".into())),
+        _ => Err(VmError::TypeMismatch {
+            expected: "ref cell",
+            got: cell.type_name(),
+        }),
+    }
+}
+
+/// (:=) : 'a ref -> 'a -> unit
+/// Replaces the contents of a reference cell
+pub fn ref_set(cell: &Value, value: &Value) -> Result<Value, VmError> {
+    match cell {
+        Value::Record(fields) => {
+            let mut fields = fields.lock().unwrap();
+            match fields.get_mut(CONTENTS_FIELD) {
+                Some(contents) => {
+                    *contents = value.clone();
+                    Ok(Value::Unit)
+                }
+                None => Err(VmError::Runtime("Record is not a reference cell".into())),
+            }
+        }
+        _ => Err(VmError::TypeMismatch {
+            expected: "ref cell",
+            got: cell.type_name(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ref_create_and_get() {
+        let cell = ref_create(&Value::Int(1)).unwrap();
+        assert_eq!(ref_get(&cell).unwrap(), Value::Int(1));
+    }
+
+    #[test]
+    fn test_ref_set_is_visible_through_clones() {
+        let cell = ref_create(&Value::Int(1)).unwrap();
+        let alias = cell.clone();
+        assert_eq!(ref_set(&cell, &Value::Int(2)).unwrap(), Value::Unit);
+        assert_eq!(ref_get(&alias).unwrap(), Value::Int(2));
+    }
+
+    #[test]
+    fn test_ref_get_rejects_non_cells() {
+        assert!(ref_get(&Value::Int(1)).is_err());
+        let record = Value::Record(Arc::new(Mutex::new(HashMap::new())));
+        assert!(ref_get(&record).is_err());
+        assert!(ref_set(&record, &Value::Int(1)).is_err());
+    }
+}
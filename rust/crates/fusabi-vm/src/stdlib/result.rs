@@ -222,6 +222,67 @@ pub fn result_iter(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
     }
 }
 
+// ========== The result { ... } builder ==========
+
+fn ok(value: Value) -> Value {
+    Value::Variant {
+        type_name: "Result".to_string(),
+        variant_name: "Ok".to_string(),
+        fields: vec![value],
+    }
+}
+
+/// result.Bind : Result<'a, 'e> -> ('a -> Result<'b, 'e>) -> Result<'b, 'e>
+/// Continues with the Ok value, stopping at the first Error
+pub fn result_builder_bind(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+    match args {
+        [result, f] => result_bind(vm, &[f.clone(), result.clone()]),
+        _ => Err(VmError::Runtime(format!(
+            "result.Bind expects 2 arguments, got {}",
+            args.len()
+        ))),
+    }
+}
+
+/// result.Return : 'a -> Result<'a, 'e>
+pub fn result_builder_return(value: &Value) -> Result<Value, VmError> {
+    Ok(ok(value.clone()))
+}
+
+/// result.ReturnFrom : Result<'a, 'e> -> Result<'a, 'e>
+pub fn result_builder_return_from(result: &Value) -> Result<Value, VmError> {
+    result_is_ok(result)?;
+    Ok(result.clone())
+}
+
+/// result.Zero : unit -> Result<unit, 'e>
+/// A branch with nothing to return carries on with Ok ()
+pub fn result_builder_zero(_vm: &mut Vm, _args: &[Value]) -> Result<Value, VmError> {
+    Ok(ok(Value::Unit))
+}
+
+/// result.Delay : (unit -> Result<'a, 'e>) -> Result<'a, 'e>
+/// Runs the rest of the block straight away
+pub fn result_builder_delay(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+    match args {
+        [f] => vm.call_value(f.clone(), &[Value::Unit]),
+        _ => Err(VmError::Runtime(format!(
+            "result.Delay expects 1 argument, got {}",
+            args.len()
+        ))),
+    }
+}
+
+/// result.Combine : Result<unit, 'e> -> Result<'a, 'e> -> Result<'a, 'e>
+/// Returns the second result unless the first is an Error
+pub fn result_builder_combine(first: &Value, second: &Value) -> Result<Value, VmError> {
+    if result_is_ok(first)? == Value::Bool(true) {
+        Ok(second.clone())
+    } else {
+        Ok(first.clone())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -318,4 +379,20 @@ mod tests {
         let is_error = result_is_error(&other_variant).unwrap();
         assert_eq!(is_error, Value::Bool(false));
     }
+
+    #[test]
+    fn test_result_builder_combine_and_return() {
+        let error = make_error(Value::Str("bad".to_string()));
+        assert_eq!(
+            result_builder_return(&Value::Int(1)).unwrap(),
+            make_ok(Value::Int(1))
+        );
+        assert_eq!(result_builder_return_from(&error).unwrap(), error);
+        assert!(result_builder_return_from(&Value::Int(1)).is_err());
+
+        let done = make_ok(Value::Unit);
+        let rest = make_ok(Value::Int(2));
+        assert_eq!(result_builder_combine(&done, &rest).unwrap(), rest);
+        assert_eq!(result_builder_combine(&error, &rest).unwrap(), error);
+    }
 }
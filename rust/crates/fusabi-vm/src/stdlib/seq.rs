@@ -0,0 +1,665 @@
+// Fusabi Seq Standard Library
+// Provides lazily evaluated sequences and the `seq { ... }` builder
+
+use crate::value::{Seq, SeqSource, Value};
+use crate::vm::{Vm, VmError};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::sync::{Arc, Mutex};
+
+/// View a list, array or sequence as a sequence
+pub fn to_seq(value: &Value) -> Result<Seq, VmError> {
+    match value {
+        Value::Seq(seq) => Ok(seq.clone()),
+        Value::Nil | Value::Cons { .. } | Value::Array(_) => {
+            Ok(Seq::new(SeqSource::Of(value.clone())))
+        }
+        _ => Err(VmError::TypeMismatch {
+            expected: "seq, list or array",
+            got: value.type_name(),
+        }),
+    }
+}
+
+/// Run a sequence to the end and collect its elements
+pub fn seq_to_vec(vm: &mut Vm, seq: &Seq) -> Result<Vec<Value>, VmError> {
+    let mut enumerator = SeqEnumerator::new(seq)?;
+    let mut elements = Vec::new();
+    while let Some(element) = enumerator.next(vm)? {
+        elements.push(element);
+    }
+    Ok(elements)
+}
+
+/// One pass over the elements of a sequence
+///
+/// Elements are produced on demand, so enumerating the first few elements of
+/// an infinite sequence terminates.
+pub struct SeqEnumerator {
+    cursor: Cursor,
+}
+
+impl SeqEnumerator {
+    /// Start enumerating `seq` from its first element
+    pub fn new(seq: &Seq) -> Result<Self, VmError> {
+        Ok(SeqEnumerator {
+            cursor: Cursor::start(seq)?,
+        })
+    }
+
+    /// Produce the next element, or `None` once the sequence is exhausted
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self, vm: &mut Vm) -> Result<Option<Value>, VmError> {
+        self.cursor.next(vm)
+    }
+}
+
+/// Enumeration state for each kind of [`SeqSource`]
+enum Cursor {
+    Done,
+    Single(Value),
+    List(Value),
+    Array(Arc<Mutex<Vec<Value>>>, usize),
+    Delay(Value),
+    Append(Box<Cursor>, Seq),
+    Map(Value, Box<Cursor>),
+    Filter(Value, Box<Cursor>),
+    Collect(Value, Box<Cursor>, Box<Cursor>),
+    Take(usize, Box<Cursor>),
+    Skip(usize, Box<Cursor>),
+    TakeWhile(Value, Box<Cursor>),
+    Unfold(Value, Value),
+    Init(Option<i64>, i64, Value),
+    IntRange(Option<i64>, i64, i64),
+    FloatRange(f64, f64, i64, i64),
+    Lines(Lines<BufReader<File>>, String),
+}
+
+impl Cursor {
+    fn start(seq: &Seq) -> Result<Cursor, VmError> {
+        let boxed = |seq: &Seq| Cursor::start(seq).map(Box::new);
+        Ok(match seq.source() {
+            SeqSource::Empty => Cursor::Done,
+            SeqSource::Singleton(value) => Cursor::Single(value.clone()),
+            SeqSource::Of(Value::Array(array)) => Cursor::Array(array.clone(), 0),
+            SeqSource::Of(list) => Cursor::List(list.clone()),
+            SeqSource::Delay(func) => Cursor::Delay(func.clone()),
+            SeqSource::Append(first, second) => Cursor::Append(boxed(first)?, second.clone()),
+            SeqSource::Map(func, source) => Cursor::Map(func.clone(), boxed(source)?),
+            SeqSource::Filter(pred, source) => Cursor::Filter(pred.clone(), boxed(source)?),
+            SeqSource::Collect(func, source) => {
+                Cursor::Collect(func.clone(), boxed(source)?, Box::new(Cursor::Done))
+            }
+            SeqSource::Take(count, source) => Cursor::Take(*count, boxed(source)?),
+            SeqSource::Skip(count, source) => Cursor::Skip(*count, boxed(source)?),
+            SeqSource::TakeWhile(pred, source) => Cursor::TakeWhile(pred.clone(), boxed(source)?),
+            SeqSource::Unfold(func, state) => Cursor::Unfold(func.clone(), state.clone()),
+            SeqSource::Init(count, func) => Cursor::Init(*count, 0, func.clone()),
+            SeqSource::Range(start, step, finish) => range_cursor(start, step, finish)?,
+            SeqSource::FileLines(path) => {
+                let file = File::open(path).map_err(|e| {
+                    VmError::Runtime(format!("Failed to open file '{}': {}", path, e))
+                })?;
+                Cursor::Lines(BufReader::new(file).lines(), path.clone())
+            }
+        })
+    }
+
+    fn next(&mut self, vm: &mut Vm) -> Result<Option<Value>, VmError> {
+        // Finished or delayed parts are replaced in place by what follows
+        // them, so long chains of `yield!` do not nest deeper and deeper
+        loop {
+            // Each step counts as an instruction, so that limits and
+            // interrupts stop a long enumeration such as `Seq.length` of a
+            // huge range, which runs no script code
+            vm.tick(false)?;
+            match self {
+                Cursor::Done => return Ok(None),
+                Cursor::Single(value) => {
+                    let value = std::mem::replace(value, Value::Unit);
+                    *self = Cursor::Done;
+                    return Ok(Some(value));
+                }
+                Cursor::List(list) => match std::mem::replace(list, Value::Nil) {
+                    Value::Cons { head, tail } => {
+                        *list = *tail;
+                        return Ok(Some(*head));
+                    }
+                    _ => *self = Cursor::Done,
+                },
+                Cursor::Array(array, index) => {
+                    let element = array.lock().unwrap().get(*index).cloned();
+                    match element {
+                        Some(element) => {
+                            *index += 1;
+                            return Ok(Some(element));
+                        }
+                        None => *self = Cursor::Done,
+                    }
+                }
+                Cursor::Delay(func) => {
+                    let result = vm.call_value(func.clone(), &[Value::Unit])?;
+                    *self = Cursor::start(&to_seq(&result)?)?;
+                }
+                Cursor::Append(first, second) => match first.next(vm)? {
+                    Some(element) => return Ok(Some(element)),
+                    None => {
+                        let second = second.clone();
+                        *self = Cursor::start(&second)?;
+                    }
+                },
+                Cursor::Map(func, source) => match source.next(vm)? {
+                    Some(element) => return vm.call_value(func.clone(), &[element]).map(Some),
+                    None => *self = Cursor::Done,
+                },
+                Cursor::Filter(pred, source) => match source.next(vm)? {
+                    Some(element) => {
+                        if holds(vm, pred, &element)? {
+                            return Ok(Some(element));
+                        }
+                    }
+                    None => *self = Cursor::Done,
+                },
+                Cursor::Collect(func, source, inner) => match inner.next(vm)? {
+                    Some(element) => return Ok(Some(element)),
+                    None => match source.next(vm)? {
+                        Some(element) => {
+                            let result = vm.call_value(func.clone(), &[element])?;
+                            **inner = Cursor::start(&to_seq(&result)?)?;
+                        }
+                        None => *self = Cursor::Done,
+                    },
+                },
+                Cursor::Take(remaining, source) => {
+                    if *remaining == 0 {
+                        *self = Cursor::Done;
+                        continue;
+                    }
+                    match source.next(vm)? {
+                        Some(element) => {
+                            *remaining -= 1;
+                            return Ok(Some(element));
+                        }
+                        None => *self = Cursor::Done,
+                    }
+                }
+                Cursor::Skip(remaining, source) => {
+                    while *remaining > 0 && source.next(vm)?.is_some() {
+                        *remaining -= 1;
+                    }
+                    *self = std::mem::replace(source.as_mut(), Cursor::Done);
+                }
+                Cursor::TakeWhile(pred, source) => match source.next(vm)? {
+                    Some(element) if holds(vm, pred, &element)? => return Ok(Some(element)),
+                    _ => *self = Cursor::Done,
+                },
+                Cursor::Unfold(func, state) => {
+                    let result = vm.call_value(func.clone(), std::slice::from_ref(state))?;
+                    match result {
+                        // Some (x, next) carries the pair either as one tuple
+                        // field or, built from a tuple literal, as two fields
+                        Value::Variant {
+                            variant_name,
+                            fields,
+                            ..
+                        } if variant_name == "Some" => {
+                            let mut pair = match <[Value; 1]>::try_from(fields) {
+                                Ok([Value::Tuple(pair)]) => pair,
+                                Ok(_) => Vec::new(),
+                                Err(fields) => fields,
+                            };
+                            if pair.len() != 2 {
+                                return Err(VmError::TypeMismatch {
+                                    expected: "Some (element, state)",
+                                    got: "Some with another value",
+                                });
+                            }
+                            *state = pair.pop().unwrap();
+                            return Ok(pair.pop());
+                        }
+                        Value::Variant { variant_name, .. } if variant_name == "None" => {
+                            *self = Cursor::Done
+                        }
+                        other => {
+                            return Err(VmError::TypeMismatch {
+                                expected: "option",
+                                got: other.type_name(),
+                            })
+                        }
+                    }
+                }
+                Cursor::Init(count, index, func) => {
+                    if count.is_some_and(|count| *index >= count) {
+                        *self = Cursor::Done;
+                        continue;
+                    }
+                    let element = vm.call_value(func.clone(), &[Value::Int(*index)])?;
+                    *index += 1;
+                    return Ok(Some(element));
+                }
+                Cursor::IntRange(next, step, finish) => match *next {
+                    Some(n) if (*step > 0 && n <= *finish) || (*step < 0 && n >= *finish) => {
+                        *next = n.checked_add(*step);
+                        return Ok(Some(Value::Int(n)));
+                    }
+                    _ => *self = Cursor::Done,
+                },
+                Cursor::FloatRange(start, step, index, last) => {
+                    if *index > *last {
+                        *self = Cursor::Done;
+                        continue;
+                    }
+                    let element = Value::Float(*start + *index as f64 * *step);
+                    *index += 1;
+                    return Ok(Some(element));
+                }
+                Cursor::Lines(lines, path) => match lines.next() {
+                    Some(line) => {
+                        let line = line.map_err(|e| {
+                            VmError::Runtime(format!("Failed to read file '{}': {}", path, e))
+                        })?;
+                        return Ok(Some(Value::Str(line)));
+                    }
+                    None => *self = Cursor::Done,
+                },
+            }
+        }
+    }
+}
+
+/// Start counting a range, with the same bounds and steps as a range list
+fn range_cursor(start: &Value, step: &Value, finish: &Value) -> Result<Cursor, VmError> {
+    let zero_step = || VmError::Runtime("range step cannot be zero".to_string());
+    match (start, step, finish) {
+        (Value::Int(start), Value::Int(step), Value::Int(finish)) => {
+            if *step == 0 {
+                return Err(zero_step());
+            }
+            Ok(Cursor::IntRange(Some(*start), *step, *finish))
+        }
+        (Value::Float(start), Value::Int(_) | Value::Float(_), Value::Float(finish)) => {
+            let step = match step {
+                Value::Int(n) => *n as f64,
+                Value::Float(f) => *f,
+                _ => unreachable!(),
+            };
+            if step == 0.0 {
+                return Err(zero_step());
+            }
+            let last = ((finish - start) / step + 1e-9).floor() as i64;
+            Ok(Cursor::FloatRange(*start, step, 0, last))
+        }
+        (start, step, finish) => Err(VmError::Runtime(format!(
+            "range bounds must be ints or floats, got {} .. {} .. {}",
+            start.type_name(),
+            step.type_name(),
+            finish.type_name()
+        ))),
+    }
+}
+
+/// Apply a predicate and require a bool result
+fn holds(vm: &mut Vm, pred: &Value, element: &Value) -> Result<bool, VmError> {
+    match vm.call_value(pred.clone(), std::slice::from_ref(element))? {
+        Value::Bool(b) => Ok(b),
+        other => Err(VmError::TypeMismatch {
+            expected: "bool",
+            got: other.type_name(),
+        }),
+    }
+}
+
+fn expect_count(value: &Value) -> Result<usize, VmError> {
+    match value {
+        Value::Int(n) if *n >= 0 => Ok(*n as usize),
+        Value::Int(n) => Err(VmError::Runtime(format!(
+            "count must not be negative, got {}",
+            n
+        ))),
+        _ => Err(VmError::TypeMismatch {
+            expected: "int",
+            got: value.type_name(),
+        }),
+    }
+}
+
+fn seq_value(source: SeqSource) -> Result<Value, VmError> {
+    Ok(Value::Seq(Seq::new(source)))
+}
+
+// ========== Building sequences ==========
+
+/// Seq.singleton : 'a -> seq<'a>
+pub fn seq_singleton(value: &Value) -> Result<Value, VmError> {
+    seq_value(SeqSource::Singleton(value.clone()))
+}
+
+/// Seq.ofList : 'a list -> seq<'a>
+/// Also used for Seq.ofArray; lists, arrays and sequences are all accepted
+pub fn seq_of_collection(collection: &Value) -> Result<Value, VmError> {
+    to_seq(collection).map(Value::Seq)
+}
+
+/// Seq.append : seq<'a> -> seq<'a> -> seq<'a>
+pub fn seq_append(first: &Value, second: &Value) -> Result<Value, VmError> {
+    seq_value(SeqSource::Append(to_seq(first)?, to_seq(second)?))
+}
+
+/// Seq.delay : (unit -> seq<'a>) -> seq<'a>
+/// The function is called each time the sequence is enumerated
+pub fn seq_delay(func: &Value) -> Result<Value, VmError> {
+    seq_value(SeqSource::Delay(func.clone()))
+}
+
+/// Seq.map : ('a -> 'b) -> seq<'a> -> seq<'b>
+pub fn seq_map(func: &Value, source: &Value) -> Result<Value, VmError> {
+    seq_value(SeqSource::Map(func.clone(), to_seq(source)?))
+}
+
+/// Seq.filter : ('a -> bool) -> seq<'a> -> seq<'a>
+pub fn seq_filter(pred: &Value, source: &Value) -> Result<Value, VmError> {
+    seq_value(SeqSource::Filter(pred.clone(), to_seq(source)?))
+}
+
+/// Seq.collect : ('a -> seq<'b>) -> seq<'a> -> seq<'b>
+pub fn seq_collect(func: &Value, source: &Value) -> Result<Value, VmError> {
+    seq_value(SeqSource::Collect(func.clone(), to_seq(source)?))
+}
+
+/// Seq.take : int -> seq<'a> -> seq<'a>
+/// Yields at most n elements; a shorter source simply ends early
+pub fn seq_take(count: &Value, source: &Value) -> Result<Value, VmError> {
+    seq_value(SeqSource::Take(expect_count(count)?, to_seq(source)?))
+}
+
+/// Seq.skip : int -> seq<'a> -> seq<'a>
+pub fn seq_skip(count: &Value, source: &Value) -> Result<Value, VmError> {
+    seq_value(SeqSource::Skip(expect_count(count)?, to_seq(source)?))
+}
+
+/// Seq.takeWhile : ('a -> bool) -> seq<'a> -> seq<'a>
+pub fn seq_take_while(pred: &Value, source: &Value) -> Result<Value, VmError> {
+    seq_value(SeqSource::TakeWhile(pred.clone(), to_seq(source)?))
+}
+
+/// Seq.unfold : ('s -> ('a * 's) option) -> 's -> seq<'a>
+pub fn seq_unfold(func: &Value, state: &Value) -> Result<Value, VmError> {
+    seq_value(SeqSource::Unfold(func.clone(), state.clone()))
+}
+
+/// Seq.init : int -> (int -> 'a) -> seq<'a>
+pub fn seq_init(count: &Value, func: &Value) -> Result<Value, VmError> {
+    let count = expect_count(count)? as i64;
+    seq_value(SeqSource::Init(Some(count), func.clone()))
+}
+
+/// Seq.initInfinite : (int -> 'a) -> seq<'a>
+pub fn seq_init_infinite(func: &Value) -> Result<Value, VmError> {
+    seq_value(SeqSource::Init(None, func.clone()))
+}
+
+// ========== Consuming sequences ==========
+
+/// Seq.toList : seq<'a> -> 'a list
+pub fn seq_to_list(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "Seq.toList expects 1 argument, got {}",
+            args.len()
+        )));
+    }
+    let seq = to_seq(&args[0])?;
+    Ok(Value::vec_to_cons(seq_to_vec(vm, &seq)?))
+}
+
+/// Seq.toArray : seq<'a> -> 'a[]
+pub fn seq_to_array(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "Seq.toArray expects 1 argument, got {}",
+            args.len()
+        )));
+    }
+    let seq = to_seq(&args[0])?;
+    let elements = seq_to_vec(vm, &seq)?;
+    Ok(Value::Array(Arc::new(Mutex::new(elements))))
+}
+
+/// Seq.iter : ('a -> unit) -> seq<'a> -> unit
+pub fn seq_iter(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 2 {
+        return Err(VmError::Runtime(format!(
+            "Seq.iter expects 2 arguments, got {}",
+            args.len()
+        )));
+    }
+    let mut enumerator = SeqEnumerator::new(&to_seq(&args[1])?)?;
+    while let Some(element) = enumerator.next(vm)? {
+        vm.call_value(args[0].clone(), &[element])?;
+    }
+    Ok(Value::Unit)
+}
+
+/// Seq.fold : ('s -> 'a -> 's) -> 's -> seq<'a> -> 's
+pub fn seq_fold(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 3 {
+        return Err(VmError::Runtime(format!(
+            "Seq.fold expects 3 arguments, got {}",
+            args.len()
+        )));
+    }
+    let mut acc = args[1].clone();
+    let mut enumerator = SeqEnumerator::new(&to_seq(&args[2])?)?;
+    while let Some(element) = enumerator.next(vm)? {
+        let partial = vm.call_value(args[0].clone(), &[acc])?;
+        acc = vm.call_value(partial, &[element])?;
+    }
+    Ok(acc)
+}
+
+/// Seq.length : seq<'a> -> int
+pub fn seq_length(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "Seq.length expects 1 argument, got {}",
+            args.len()
+        )));
+    }
+    let mut enumerator = SeqEnumerator::new(&to_seq(&args[0])?)?;
+    let mut count = 0;
+    while enumerator.next(vm)?.is_some() {
+        count += 1;
+    }
+    Ok(Value::Int(count))
+}
+
+/// Seq.head : seq<'a> -> 'a
+/// Throws error if the sequence is empty
+pub fn seq_head(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "Seq.head expects 1 argument, got {}",
+            args.len()
+        )));
+    }
+    let mut enumerator = SeqEnumerator::new(&to_seq(&args[0])?)?;
+    enumerator
+        .next(vm)?
+        .ok_or_else(|| VmError::Runtime("Seq.head: the sequence is empty".to_string()))
+}
+
+/// Seq.isEmpty : seq<'a> -> bool
+pub fn seq_is_empty(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 1 {
+        return Err(VmError::Runtime(format!(
+            "Seq.isEmpty expects 1 argument, got {}",
+            args.len()
+        )));
+    }
+    let mut enumerator = SeqEnumerator::new(&to_seq(&args[0])?)?;
+    Ok(Value::Bool(enumerator.next(vm)?.is_none()))
+}
+
+/// Seq.exists : ('a -> bool) -> seq<'a> -> bool
+/// Stops at the first element satisfying the predicate
+pub fn seq_exists(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 2 {
+        return Err(VmError::Runtime(format!(
+            "Seq.exists expects 2 arguments, got {}",
+            args.len()
+        )));
+    }
+    Ok(Value::Bool(find_first(vm, &args[0], &args[1])?.is_some()))
+}
+
+/// Seq.tryFind : ('a -> bool) -> seq<'a> -> 'a option
+/// Stops at the first element satisfying the predicate
+pub fn seq_try_find(vm: &mut Vm, args: &[Value]) -> Result<Value, VmError> {
+    if args.len() != 2 {
+        return Err(VmError::Runtime(format!(
+            "Seq.tryFind expects 2 arguments, got {}",
+            args.len()
+        )));
+    }
+    Ok(match find_first(vm, &args[0], &args[1])? {
+        Some(element) => Value::Variant {
+            type_name: "Option".to_string(),
+            variant_name: "Some".to_string(),
+            fields: vec![element],
+        },
+        None => Value::Variant {
+            type_name: "Option".to_string(),
+            variant_name: "None".to_string(),
+            fields: vec![],
+        },
+    })
+}
+
+fn find_first(vm: &mut Vm, pred: &Value, source: &Value) -> Result<Option<Value>, VmError> {
+    let mut enumerator = SeqEnumerator::new(&to_seq(source)?)?;
+    while let Some(element) = enumerator.next(vm)? {
+        if holds(vm, pred, &element)? {
+            return Ok(Some(element));
+        }
+    }
+    Ok(None)
+}
+
+// ========== The seq { ... } builder ==========
+
+/// seq.Yield : 'a -> seq<'a>
+pub fn seq_builder_yield(value: &Value) -> Result<Value, VmError> {
+    seq_singleton(value)
+}
+
+/// seq.YieldFrom : seq<'a> -> seq<'a>
+pub fn seq_builder_yield_from(source: &Value) -> Result<Value, VmError> {
+    seq_of_collection(source)
+}
+
+/// seq.Range : 'a -> 'a -> 'a -> seq<'a>
+/// A range in a sequence, `for i in 1 .. n` or `yield! 1 .. n`, is counted
+/// lazily instead of being built as a list first
+pub fn seq_builder_range(start: &Value, step: &Value, finish: &Value) -> Result<Value, VmError> {
+    range_cursor(start, step, finish)?;
+    seq_value(SeqSource::Range(
+        start.clone(),
+        step.clone(),
+        finish.clone(),
+    ))
+}
+
+/// seq.Combine : seq<'a> -> seq<'a> -> seq<'a>
+pub fn seq_builder_combine(first: &Value, second: &Value) -> Result<Value, VmError> {
+    seq_append(first, second)
+}
+
+/// seq.Delay : (unit -> seq<'a>) -> seq<'a>
+pub fn seq_builder_delay(func: &Value) -> Result<Value, VmError> {
+    seq_delay(func)
+}
+
+/// seq.Zero : unit -> seq<'a>
+pub fn seq_builder_zero(_vm: &mut Vm, _args: &[Value]) -> Result<Value, VmError> {
+    Ok(Value::Seq(Seq::empty()))
+}
+
+/// seq.For : seq<'a> -> ('a -> seq<'b>) -> seq<'b>
+pub fn seq_builder_for(source: &Value, body: &Value) -> Result<Value, VmError> {
+    seq_collect(body, source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stdlib::register_stdlib;
+
+    fn ints(values: &[i64]) -> Value {
+        Value::vec_to_cons(values.iter().map(|&n| Value::Int(n)).collect())
+    }
+
+    fn native(name: &str, arity: u8) -> Value {
+        Value::NativeFn {
+            name: name.to_string(),
+            arity,
+            args: vec![],
+        }
+    }
+
+    fn to_list(vm: &mut Vm, seq: &Value) -> Value {
+        seq_to_list(vm, std::slice::from_ref(seq)).unwrap()
+    }
+
+    #[test]
+    fn test_seq_of_list_round_trips() {
+        let mut vm = Vm::new();
+        let seq = seq_of_collection(&ints(&[1, 2, 3])).unwrap();
+        assert_eq!(to_list(&mut vm, &seq), ints(&[1, 2, 3]));
+        // A sequence can be enumerated more than once
+        assert_eq!(to_list(&mut vm, &seq), ints(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_seq_take_stops_infinite_sequence() {
+        let mut vm = Vm::new();
+        register_stdlib(&mut vm);
+        // Seq.initInfinite Math.abs yields 0, 1, 2, ...
+        let naturals = seq_init_infinite(&native("Math.abs", 1)).unwrap();
+        let first = seq_take(&Value::Int(4), &naturals).unwrap();
+        assert_eq!(to_list(&mut vm, &first), ints(&[0, 1, 2, 3]));
+        let rest = seq_take(
+            &Value::Int(2),
+            &seq_skip(&Value::Int(10), &naturals).unwrap(),
+        );
+        assert_eq!(to_list(&mut vm, &rest.unwrap()), ints(&[10, 11]));
+    }
+
+    #[test]
+    fn test_seq_append_and_empty() {
+        let mut vm = Vm::new();
+        let empty = seq_builder_zero(&mut vm, &[]).unwrap();
+        let joined = seq_append(&empty, &seq_singleton(&Value::Int(7)).unwrap()).unwrap();
+        assert_eq!(to_list(&mut vm, &joined), ints(&[7]));
+        assert_eq!(seq_is_empty(&mut vm, &[empty]).unwrap(), Value::Bool(true));
+    }
+
+    #[test]
+    fn test_seq_rejects_non_collections() {
+        assert!(seq_map(&native("Math.abs", 1), &Value::Int(1)).is_err());
+        assert!(seq_take(&Value::Int(-1), &Value::Nil).is_err());
+    }
+
+    #[test]
+    fn test_seq_range_matches_range_lists() {
+        let mut vm = Vm::new();
+        let range = |start, step, finish| seq_builder_range(&start, &step, &finish).unwrap();
+        let down = range(Value::Int(5), Value::Int(-2), Value::Int(0));
+        assert_eq!(to_list(&mut vm, &down), ints(&[5, 3, 1]));
+        let empty = range(Value::Int(3), Value::Int(1), Value::Int(1));
+        assert_eq!(to_list(&mut vm, &empty), Value::Nil);
+        let floats = range(Value::Float(0.0), Value::Float(0.1), Value::Float(0.3));
+        assert_eq!(seq_length(&mut vm, &[floats]).unwrap(), Value::Int(4));
+        assert!(seq_builder_range(&Value::Int(1), &Value::Int(0), &Value::Int(3)).is_err());
+        assert!(seq_builder_range(&Value::Int(1), &Value::Int(1), &Value::Nil).is_err());
+    }
+}
@@ -155,7 +155,7 @@ fn row_value_to_fusabi(row: &rusqlite::Row, idx: usize) -> Value {
         Ok(ValueRef::Integer(i)) => Value::Int(i),
         Ok(ValueRef::Real(f)) => Value::Float(f),
         Ok(ValueRef::Text(s)) => Value::Str(String::from_utf8_lossy(s).to_string()),
-        Ok(ValueRef::Blob(b)) => Value::Str(format!("<blob {} bytes>", b.len())),
+        Ok(ValueRef::Blob(b)) => Value::Bytes(b.to_vec()),
         Err(_) => Value::Unit,
     }
 }
@@ -254,6 +254,28 @@ mod tests {
         sqlite_close(&Value::Int(id)).unwrap();
     }
 
+    #[test]
+    fn test_query_blob_column() {
+        let id = match sqlite_open(&Value::Str(":memory:".to_string())).unwrap() {
+            Value::Int(i) => i,
+            _ => panic!("Expected Int"),
+        };
+
+        let rows = sqlite_query(
+            &Value::Int(id),
+            &Value::Str("SELECT X'00FF10' AS data".to_string()),
+        )
+        .unwrap();
+        let row = match rows {
+            Value::Cons { head, .. } => *head,
+            _ => panic!("Expected list"),
+        };
+        let data = row.record_get("data").unwrap();
+        assert_eq!(data, Value::Bytes(vec![0x00, 0xff, 0x10]));
+
+        sqlite_close(&Value::Int(id)).unwrap();
+    }
+
     #[test]
     fn test_invalid_connection() {
         let result = sqlite_execute(&Value::Int(99999), &Value::Str("SELECT 1".to_string()));
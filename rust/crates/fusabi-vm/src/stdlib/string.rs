@@ -161,6 +161,46 @@ pub fn string_ends_with(suffix: &Value, s: &Value) -> Result<Value, VmError> {
     }
 }
 
+/// String.toChars : string -> char list
+/// Splits a string into its characters
+pub fn string_to_chars(s: &Value) -> Result<Value, VmError> {
+    match s {
+        Value::Str(string) => Ok(Value::vec_to_cons(
+            string.chars().map(Value::Char).collect(),
+        )),
+        _ => Err(VmError::TypeMismatch {
+            expected: "string",
+            got: s.type_name(),
+        }),
+    }
+}
+
+/// String.ofChars : char list -> string
+/// Builds a string from a list or array of characters
+pub fn string_of_chars(chars: &Value) -> Result<Value, VmError> {
+    let elements = match chars {
+        Value::Array(arr) => arr.lock().unwrap().clone(),
+        _ => chars.list_to_vec().ok_or_else(|| VmError::TypeMismatch {
+            expected: "char list",
+            got: chars.type_name(),
+        })?,
+    };
+
+    let mut result = String::with_capacity(elements.len());
+    for element in &elements {
+        match element {
+            Value::Char(c) => result.push(*c),
+            _ => {
+                return Err(VmError::TypeMismatch {
+                    expected: "char",
+                    got: element.type_name(),
+                })
+            }
+        }
+    }
+    Ok(Value::Str(result))
+}
+
 /// String.format : string -> any list -> string
 /// Formats a string using printf-style formatting
 /// Supported specifiers: %s (string), %d (int), %f (float), %.Nf (float with precision), %% (literal %)
@@ -667,4 +707,27 @@ mod tests {
         let result = string_format(&fmt, &args);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_string_to_chars() {
+        let s = Value::Str("héy".to_string());
+        let result = string_to_chars(&s).unwrap();
+        assert_eq!(
+            result.list_to_vec().unwrap(),
+            vec![Value::Char('h'), Value::Char('é'), Value::Char('y')]
+        );
+    }
+
+    #[test]
+    fn test_string_of_chars_round_trip() {
+        let s = Value::Str("fusabi".to_string());
+        let chars = string_to_chars(&s).unwrap();
+        assert_eq!(string_of_chars(&chars).unwrap(), s);
+    }
+
+    #[test]
+    fn test_string_of_chars_rejects_non_char() {
+        let list = Value::vec_to_cons(vec![Value::Char('a'), Value::Int(1)]);
+        assert!(string_of_chars(&list).is_err());
+    }
 }
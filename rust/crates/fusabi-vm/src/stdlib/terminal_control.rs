@@ -2,8 +2,7 @@
 // Provides APIs for programmatic pane/window control in terminal emulators
 
 use crate::value::Value;
-use crate::vm::VmError;
-use std::sync::{Mutex, OnceLock};
+use crate::vm::{Vm, VmError};
 
 /// Trait for providing terminal control capabilities
 /// Host applications should implement this trait and register it via `register_provider`
@@ -39,35 +38,35 @@ pub trait TerminalControlProvider: Send + Sync {
     fn show_toast(&self, message: &str);
 }
 
-/// Global registry for the terminal control provider
-static PROVIDER: OnceLock<Mutex<Option<Box<dyn TerminalControlProvider>>>> = OnceLock::new();
-
-/// Register a terminal control provider
-/// This should be called by host applications to enable terminal control functionality
-pub fn register_provider(provider: Box<dyn TerminalControlProvider>) {
-    let mutex = PROVIDER.get_or_init(|| Mutex::new(None));
-    let mut guard = mutex.lock().unwrap();
+/// Register a terminal control provider for `vm`
+/// This should be called by host applications to enable terminal control functionality.
+/// VMs sharing `vm.context.terminal_control` see the same provider.
+pub fn register_provider(vm: &Vm, provider: Box<dyn TerminalControlProvider>) {
+    let mut guard = vm.context.terminal_control.lock().unwrap();
     *guard = Some(provider);
 }
 
-/// Unregister the terminal control provider (useful for testing)
-pub fn unregister_provider() {
-    if let Some(mutex) = PROVIDER.get() {
-        let mut guard = mutex.lock().unwrap();
-        *guard = None;
-    }
+/// Unregister the terminal control provider of `vm`
+pub fn unregister_provider(vm: &Vm) {
+    let mut guard = vm.context.terminal_control.lock().unwrap();
+    *guard = None;
+}
+
+// Helper function to get the provider
+fn with_provider<F, R>(vm: &Vm, f: F) -> Option<R>
+where
+    F: FnOnce(&dyn TerminalControlProvider) -> R,
+{
+    let guard = vm.context.terminal_control.lock().unwrap();
+    guard.as_ref().map(|provider| f(provider.as_ref()))
 }
 
 /// TerminalControl.sendText : string -> unit
 /// Send text to the active pane
-pub fn send_text(text: &Value) -> Result<Value, VmError> {
+pub fn send_text(vm: &Vm, text: &Value) -> Result<Value, VmError> {
     match text {
         Value::Str(s) => {
-            if let Some(mutex) = PROVIDER.get() {
-                if let Some(provider) = mutex.lock().unwrap().as_ref() {
-                    provider.send_text(s);
-                }
-            }
+            with_provider(vm, |provider| provider.send_text(s));
             Ok(Value::Unit)
         }
         _ => Err(VmError::TypeMismatch {
@@ -79,32 +78,20 @@ pub fn send_text(text: &Value) -> Result<Value, VmError> {
 
 /// TerminalControl.sendKeys : string list -> unit
 /// Send key sequences to the active pane
-pub fn send_keys(keys: &Value) -> Result<Value, VmError> {
+pub fn send_keys(vm: &Vm, keys: &Value) -> Result<Value, VmError> {
     let key_strings = list_to_string_vec(keys)?;
 
-    if let Some(mutex) = PROVIDER.get() {
-        if let Some(provider) = mutex.lock().unwrap().as_ref() {
-            provider.send_keys(&key_strings);
-        }
-    }
+    with_provider(vm, |provider| provider.send_keys(&key_strings));
 
     Ok(Value::Unit)
 }
 
 /// TerminalControl.splitHorizontal : unit -> int option
 /// Split the active pane horizontally, returning the new pane ID
-pub fn split_horizontal(unit: &Value) -> Result<Value, VmError> {
+pub fn split_horizontal(vm: &Vm, unit: &Value) -> Result<Value, VmError> {
     match unit {
         Value::Unit => {
-            let pane_id = if let Some(mutex) = PROVIDER.get() {
-                mutex
-                    .lock()
-                    .unwrap()
-                    .as_ref()
-                    .and_then(|p| p.split_horizontal())
-            } else {
-                None
-            };
+            let pane_id = with_provider(vm, |p| p.split_horizontal()).flatten();
 
             Ok(option_from_i64(pane_id))
         }
@@ -117,18 +104,10 @@ pub fn split_horizontal(unit: &Value) -> Result<Value, VmError> {
 
 /// TerminalControl.splitVertical : unit -> int option
 /// Split the active pane vertically, returning the new pane ID
-pub fn split_vertical(unit: &Value) -> Result<Value, VmError> {
+pub fn split_vertical(vm: &Vm, unit: &Value) -> Result<Value, VmError> {
     match unit {
         Value::Unit => {
-            let pane_id = if let Some(mutex) = PROVIDER.get() {
-                mutex
-                    .lock()
-                    .unwrap()
-                    .as_ref()
-                    .and_then(|p| p.split_vertical())
-            } else {
-                None
-            };
+            let pane_id = with_provider(vm, |p| p.split_vertical()).flatten();
 
             Ok(option_from_i64(pane_id))
         }
@@ -141,18 +120,10 @@ pub fn split_vertical(unit: &Value) -> Result<Value, VmError> {
 
 /// TerminalControl.closePane : int -> bool
 /// Close a pane by ID
-pub fn close_pane(pane_id: &Value) -> Result<Value, VmError> {
+pub fn close_pane(vm: &Vm, pane_id: &Value) -> Result<Value, VmError> {
     match pane_id {
         Value::Int(id) => {
-            let success = if let Some(mutex) = PROVIDER.get() {
-                mutex
-                    .lock()
-                    .unwrap()
-                    .as_ref()
-                    .map_or(false, |p| p.close_pane(*id))
-            } else {
-                false
-            };
+            let success = with_provider(vm, |p| p.close_pane(*id)).unwrap_or(false);
 
             Ok(Value::Bool(success))
         }
@@ -165,18 +136,10 @@ pub fn close_pane(pane_id: &Value) -> Result<Value, VmError> {
 
 /// TerminalControl.focusPane : int -> bool
 /// Focus a pane by ID
-pub fn focus_pane(pane_id: &Value) -> Result<Value, VmError> {
+pub fn focus_pane(vm: &Vm, pane_id: &Value) -> Result<Value, VmError> {
     match pane_id {
         Value::Int(id) => {
-            let success = if let Some(mutex) = PROVIDER.get() {
-                mutex
-                    .lock()
-                    .unwrap()
-                    .as_ref()
-                    .map_or(false, |p| p.focus_pane(*id))
-            } else {
-                false
-            };
+            let success = with_provider(vm, |p| p.focus_pane(*id)).unwrap_or(false);
 
             Ok(Value::Bool(success))
         }
@@ -189,14 +152,10 @@ pub fn focus_pane(pane_id: &Value) -> Result<Value, VmError> {
 
 /// TerminalControl.createTab : unit -> int option
 /// Create a new tab, returning the tab ID
-pub fn create_tab(unit: &Value) -> Result<Value, VmError> {
+pub fn create_tab(vm: &Vm, unit: &Value) -> Result<Value, VmError> {
     match unit {
         Value::Unit => {
-            let tab_id = if let Some(mutex) = PROVIDER.get() {
-                mutex.lock().unwrap().as_ref().and_then(|p| p.create_tab())
-            } else {
-                None
-            };
+            let tab_id = with_provider(vm, |p| p.create_tab()).flatten();
 
             Ok(option_from_i64(tab_id))
         }
@@ -209,18 +168,10 @@ pub fn create_tab(unit: &Value) -> Result<Value, VmError> {
 
 /// TerminalControl.closeTab : int -> bool
 /// Close a tab by ID
-pub fn close_tab(tab_id: &Value) -> Result<Value, VmError> {
+pub fn close_tab(vm: &Vm, tab_id: &Value) -> Result<Value, VmError> {
     match tab_id {
         Value::Int(id) => {
-            let success = if let Some(mutex) = PROVIDER.get() {
-                mutex
-                    .lock()
-                    .unwrap()
-                    .as_ref()
-                    .map_or(false, |p| p.close_tab(*id))
-            } else {
-                false
-            };
+            let success = with_provider(vm, |p| p.close_tab(*id)).unwrap_or(false);
 
             Ok(Value::Bool(success))
         }
@@ -233,18 +184,10 @@ pub fn close_tab(tab_id: &Value) -> Result<Value, VmError> {
 
 /// TerminalControl.setTabTitle : int -> string -> bool
 /// Set the title of a tab
-pub fn set_tab_title(tab_id: &Value, title: &Value) -> Result<Value, VmError> {
+pub fn set_tab_title(vm: &Vm, tab_id: &Value, title: &Value) -> Result<Value, VmError> {
     match (tab_id, title) {
         (Value::Int(id), Value::Str(t)) => {
-            let success = if let Some(mutex) = PROVIDER.get() {
-                mutex
-                    .lock()
-                    .unwrap()
-                    .as_ref()
-                    .map_or(false, |p| p.set_tab_title(*id, t))
-            } else {
-                false
-            };
+            let success = with_provider(vm, |p| p.set_tab_title(*id, t)).unwrap_or(false);
 
             Ok(Value::Bool(success))
         }
@@ -261,14 +204,10 @@ pub fn set_tab_title(tab_id: &Value, title: &Value) -> Result<Value, VmError> {
 
 /// TerminalControl.showToast : string -> unit
 /// Show a toast notification
-pub fn show_toast(message: &Value) -> Result<Value, VmError> {
+pub fn show_toast(vm: &Vm, message: &Value) -> Result<Value, VmError> {
     match message {
         Value::Str(s) => {
-            if let Some(mutex) = PROVIDER.get() {
-                if let Some(provider) = mutex.lock().unwrap().as_ref() {
-                    provider.show_toast(s);
-                }
-            }
+            with_provider(vm, |provider| provider.show_toast(s));
             Ok(Value::Unit)
         }
         _ => Err(VmError::TypeMismatch {
@@ -331,6 +270,7 @@ fn option_from_i64(opt: Option<i64>) -> Value {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Mutex;
 
     // Mock provider for testing
     struct MockProvider {
@@ -396,33 +336,31 @@ mod tests {
 
     #[test]
     fn test_send_text_no_provider() {
-        unregister_provider();
-        let result = send_text(&Value::Str("hello".to_string()));
+        let vm = Vm::new();
+        let result = send_text(&vm, &Value::Str("hello".to_string()));
         assert_eq!(result, Ok(Value::Unit));
     }
 
     #[test]
     fn test_send_text_with_provider() {
-        unregister_provider();
+        let vm = Vm::new();
         let provider = MockProvider::new();
-        register_provider(Box::new(provider));
+        register_provider(&vm, Box::new(provider));
 
-        let result = send_text(&Value::Str("test message".to_string()));
+        let result = send_text(&vm, &Value::Str("test message".to_string()));
         assert_eq!(result, Ok(Value::Unit));
-
-        unregister_provider();
     }
 
     #[test]
     fn test_send_text_type_error() {
-        unregister_provider();
-        let result = send_text(&Value::Int(42));
+        let vm = Vm::new();
+        let result = send_text(&vm, &Value::Int(42));
         assert!(matches!(result, Err(VmError::TypeMismatch { .. })));
     }
 
     #[test]
     fn test_send_keys_no_provider() {
-        unregister_provider();
+        let vm = Vm::new();
         let list = Value::Cons {
             head: Box::new(Value::Str("ctrl-c".to_string())),
             tail: Box::new(Value::Cons {
@@ -431,50 +369,48 @@ mod tests {
             }),
         };
 
-        let result = send_keys(&list);
+        let result = send_keys(&vm, &list);
         assert_eq!(result, Ok(Value::Unit));
     }
 
     #[test]
     fn test_send_keys_with_provider() {
-        unregister_provider();
+        let vm = Vm::new();
         let provider = MockProvider::new();
-        register_provider(Box::new(provider));
+        register_provider(&vm, Box::new(provider));
 
         let list = Value::Cons {
             head: Box::new(Value::Str("Enter".to_string())),
             tail: Box::new(Value::Nil),
         };
 
-        let result = send_keys(&list);
+        let result = send_keys(&vm, &list);
         assert_eq!(result, Ok(Value::Unit));
-
-        unregister_provider();
     }
 
     #[test]
     fn test_send_keys_type_error_non_list() {
-        unregister_provider();
-        let result = send_keys(&Value::Int(42));
+        let vm = Vm::new();
+        let result = send_keys(&vm, &Value::Int(42));
         assert!(matches!(result, Err(VmError::TypeMismatch { .. })));
     }
 
     #[test]
     fn test_send_keys_type_error_non_string_element() {
-        unregister_provider();
+        let vm = Vm::new();
         let list = Value::Cons {
             head: Box::new(Value::Int(42)),
             tail: Box::new(Value::Nil),
         };
 
-        let result = send_keys(&list);
+        let result = send_keys(&vm, &list);
         assert!(matches!(result, Err(VmError::TypeMismatch { .. })));
     }
 
     #[test]
     fn test_split_horizontal_no_provider() {
-        unregister_provider();
-        let result = split_horizontal(&Value::Unit);
+        let vm = Vm::new();
+        let result = split_horizontal(&vm, &Value::Unit);
         assert!(
             matches!(result, Ok(Value::Variant { variant_name, .. }) if variant_name == "None")
         );
@@ -482,11 +418,11 @@ mod tests {
 
     #[test]
     fn test_split_horizontal_with_provider() {
-        unregister_provider();
+        let vm = Vm::new();
         let provider = MockProvider::new();
-        register_provider(Box::new(provider));
+        register_provider(&vm, Box::new(provider));
 
-        let result = split_horizontal(&Value::Unit);
+        let result = split_horizontal(&vm, &Value::Unit);
         match result {
             Ok(Value::Variant {
                 variant_name,
@@ -498,24 +434,22 @@ mod tests {
             }
             _ => panic!("Expected Some(42)"),
         }
-
-        unregister_provider();
     }
 
     #[test]
     fn test_split_horizontal_type_error() {
-        unregister_provider();
-        let result = split_horizontal(&Value::Int(42));
+        let vm = Vm::new();
+        let result = split_horizontal(&vm, &Value::Int(42));
         assert!(matches!(result, Err(VmError::TypeMismatch { .. })));
     }
 
     #[test]
     fn test_split_vertical_with_provider() {
-        unregister_provider();
+        let vm = Vm::new();
         let provider = MockProvider::new();
-        register_provider(Box::new(provider));
+        register_provider(&vm, Box::new(provider));
 
-        let result = split_vertical(&Value::Unit);
+        let result = split_vertical(&vm, &Value::Unit);
         match result {
             Ok(Value::Variant {
                 variant_name,
@@ -527,55 +461,49 @@ mod tests {
             }
             _ => panic!("Expected Some(43)"),
         }
-
-        unregister_provider();
     }
 
     #[test]
     fn test_close_pane_no_provider() {
-        unregister_provider();
-        let result = close_pane(&Value::Int(1));
+        let vm = Vm::new();
+        let result = close_pane(&vm, &Value::Int(1));
         assert_eq!(result, Ok(Value::Bool(false)));
     }
 
     #[test]
     fn test_close_pane_with_provider() {
-        unregister_provider();
+        let vm = Vm::new();
         let provider = MockProvider::new();
-        register_provider(Box::new(provider));
+        register_provider(&vm, Box::new(provider));
 
-        let result = close_pane(&Value::Int(1));
+        let result = close_pane(&vm, &Value::Int(1));
         assert_eq!(result, Ok(Value::Bool(true)));
-
-        unregister_provider();
     }
 
     #[test]
     fn test_close_pane_type_error() {
-        unregister_provider();
-        let result = close_pane(&Value::Str("not an int".to_string()));
+        let vm = Vm::new();
+        let result = close_pane(&vm, &Value::Str("not an int".to_string()));
         assert!(matches!(result, Err(VmError::TypeMismatch { .. })));
     }
 
     #[test]
     fn test_focus_pane_with_provider() {
-        unregister_provider();
+        let vm = Vm::new();
         let provider = MockProvider::new();
-        register_provider(Box::new(provider));
+        register_provider(&vm, Box::new(provider));
 
-        let result = focus_pane(&Value::Int(2));
+        let result = focus_pane(&vm, &Value::Int(2));
         assert_eq!(result, Ok(Value::Bool(true)));
-
-        unregister_provider();
     }
 
     #[test]
     fn test_create_tab_with_provider() {
-        unregister_provider();
+        let vm = Vm::new();
         let provider = MockProvider::new();
-        register_provider(Box::new(provider));
+        register_provider(&vm, Box::new(provider));
 
-        let result = create_tab(&Value::Unit);
+        let result = create_tab(&vm, &Value::Unit);
         match result {
             Ok(Value::Variant {
                 variant_name,
@@ -587,38 +515,33 @@ mod tests {
             }
             _ => panic!("Expected Some(100)"),
         }
-
-        unregister_provider();
     }
 
     #[test]
     fn test_close_tab_with_provider() {
-        unregister_provider();
+        let vm = Vm::new();
         let provider = MockProvider::new();
-        register_provider(Box::new(provider));
+        register_provider(&vm, Box::new(provider));
 
-        let result = close_tab(&Value::Int(10));
+        let result = close_tab(&vm, &Value::Int(10));
         assert_eq!(result, Ok(Value::Bool(true)));
-
-        unregister_provider();
     }
 
     #[test]
     fn test_set_tab_title_with_provider() {
-        unregister_provider();
+        let vm = Vm::new();
         let provider = MockProvider::new();
-        register_provider(Box::new(provider));
+        register_provider(&vm, Box::new(provider));
 
-        let result = set_tab_title(&Value::Int(10), &Value::Str("New Tab".to_string()));
+        let result = set_tab_title(&vm, &Value::Int(10), &Value::Str("New Tab".to_string()));
         assert_eq!(result, Ok(Value::Bool(true)));
-
-        unregister_provider();
     }
 
     #[test]
     fn test_set_tab_title_type_error_invalid_id() {
-        unregister_provider();
+        let vm = Vm::new();
         let result = set_tab_title(
+            &vm,
             &Value::Str("not an int".to_string()),
             &Value::Str("Title".to_string()),
         );
@@ -627,34 +550,32 @@ mod tests {
 
     #[test]
     fn test_set_tab_title_type_error_invalid_title() {
-        unregister_provider();
-        let result = set_tab_title(&Value::Int(10), &Value::Int(42));
+        let vm = Vm::new();
+        let result = set_tab_title(&vm, &Value::Int(10), &Value::Int(42));
         assert!(matches!(result, Err(VmError::TypeMismatch { .. })));
     }
 
     #[test]
     fn test_show_toast_no_provider() {
-        unregister_provider();
-        let result = show_toast(&Value::Str("notification".to_string()));
+        let vm = Vm::new();
+        let result = show_toast(&vm, &Value::Str("notification".to_string()));
         assert_eq!(result, Ok(Value::Unit));
     }
 
     #[test]
     fn test_show_toast_with_provider() {
-        unregister_provider();
+        let vm = Vm::new();
         let provider = MockProvider::new();
-        register_provider(Box::new(provider));
+        register_provider(&vm, Box::new(provider));
 
-        let result = show_toast(&Value::Str("test toast".to_string()));
+        let result = show_toast(&vm, &Value::Str("test toast".to_string()));
         assert_eq!(result, Ok(Value::Unit));
-
-        unregister_provider();
     }
 
     #[test]
     fn test_show_toast_type_error() {
-        unregister_provider();
-        let result = show_toast(&Value::Bool(true));
+        let vm = Vm::new();
+        let result = show_toast(&vm, &Value::Bool(true));
         assert!(matches!(result, Err(VmError::TypeMismatch { .. })));
     }
 
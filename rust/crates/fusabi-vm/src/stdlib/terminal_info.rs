@@ -6,9 +6,9 @@
 // to provide terminal-specific functionality.
 
 use crate::value::Value;
-use crate::vm::VmError;
+use crate::vm::{Vm, VmError};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::{Arc, Mutex};
 
 /// ProcessInfo record structure
 /// Fields: name (string), pid (int), commandLine (string option)
@@ -69,45 +69,35 @@ pub trait TerminalInfoProvider: Send + Sync {
     fn get_terminal_size(&self) -> (i64, i64);
 }
 
-/// Global provider registry using OnceLock for thread-safe initialization
-static PROVIDER: OnceLock<Arc<Mutex<Option<Box<dyn TerminalInfoProvider>>>>> = OnceLock::new();
-
-/// Initialize the provider storage
-fn get_provider_storage() -> &'static Arc<Mutex<Option<Box<dyn TerminalInfoProvider>>>> {
-    PROVIDER.get_or_init(|| Arc::new(Mutex::new(None)))
-}
-
-/// Register a terminal info provider
-/// This should be called by host applications to provide terminal functionality
-pub fn register_provider(provider: Box<dyn TerminalInfoProvider>) {
-    let storage = get_provider_storage();
-    let mut guard = storage.lock().unwrap();
+/// Register a terminal info provider for `vm`
+/// This should be called by host applications to provide terminal functionality.
+/// VMs sharing `vm.context.terminal_info` see the same provider.
+pub fn register_provider(vm: &Vm, provider: Box<dyn TerminalInfoProvider>) {
+    let mut guard = vm.context.terminal_info.lock().unwrap();
     *guard = Some(provider);
 }
 
-/// Unregister the current provider (useful for testing)
-pub fn unregister_provider() {
-    let storage = get_provider_storage();
-    let mut guard = storage.lock().unwrap();
+/// Unregister the provider of `vm`
+pub fn unregister_provider(vm: &Vm) {
+    let mut guard = vm.context.terminal_info.lock().unwrap();
     *guard = None;
 }
 
 // Helper function to get the provider
-fn with_provider<F, R>(f: F) -> Option<R>
+fn with_provider<F, R>(vm: &Vm, f: F) -> Option<R>
 where
     F: FnOnce(&dyn TerminalInfoProvider) -> R,
 {
-    let storage = get_provider_storage();
-    let guard = storage.lock().unwrap();
+    let guard = vm.context.terminal_info.lock().unwrap();
     guard.as_ref().map(|provider| f(provider.as_ref()))
 }
 
 /// TerminalInfo.getForegroundProcess : unit -> ProcessInfo option
 /// Returns information about the foreground process if available
-pub fn get_foreground_process(unit: &Value) -> Result<Value, VmError> {
+pub fn get_foreground_process(vm: &Vm, unit: &Value) -> Result<Value, VmError> {
     match unit {
         Value::Unit => {
-            let result = with_provider(|provider| provider.get_foreground_process());
+            let result = with_provider(vm, |provider| provider.get_foreground_process());
 
             match result {
                 Some(Some(process_info)) => Ok(Value::Variant {
@@ -131,10 +121,10 @@ pub fn get_foreground_process(unit: &Value) -> Result<Value, VmError> {
 
 /// TerminalInfo.getCurrentWorkingDir : unit -> string option
 /// Returns the current working directory if available
-pub fn get_current_working_dir(unit: &Value) -> Result<Value, VmError> {
+pub fn get_current_working_dir(vm: &Vm, unit: &Value) -> Result<Value, VmError> {
     match unit {
         Value::Unit => {
-            let result = with_provider(|provider| provider.get_current_working_dir());
+            let result = with_provider(vm, |provider| provider.get_current_working_dir());
 
             match result {
                 Some(Some(cwd)) => Ok(Value::Variant {
@@ -158,10 +148,10 @@ pub fn get_current_working_dir(unit: &Value) -> Result<Value, VmError> {
 
 /// TerminalInfo.getLine : int -> string option
 /// Returns the content of a specific line from the scrollback buffer
-pub fn get_line(line_number: &Value) -> Result<Value, VmError> {
+pub fn get_line(vm: &Vm, line_number: &Value) -> Result<Value, VmError> {
     match line_number {
         Value::Int(n) => {
-            let result = with_provider(|provider| provider.get_line(*n));
+            let result = with_provider(vm, |provider| provider.get_line(*n));
 
             match result {
                 Some(Some(line)) => Ok(Value::Variant {
@@ -185,11 +175,11 @@ pub fn get_line(line_number: &Value) -> Result<Value, VmError> {
 
 /// TerminalInfo.getLines : int -> int -> string list
 /// Returns a list of lines from the scrollback buffer between start and end
-pub fn get_lines(start: &Value, end: &Value) -> Result<Value, VmError> {
+pub fn get_lines(vm: &Vm, start: &Value, end: &Value) -> Result<Value, VmError> {
     match (start, end) {
         (Value::Int(start_n), Value::Int(end_n)) => {
-            let lines =
-                with_provider(|provider| provider.get_lines(*start_n, *end_n)).unwrap_or_default();
+            let lines = with_provider(vm, |provider| provider.get_lines(*start_n, *end_n))
+                .unwrap_or_default();
 
             // Build list in reverse order
             let mut result = Value::Nil;
@@ -215,10 +205,10 @@ pub fn get_lines(start: &Value, end: &Value) -> Result<Value, VmError> {
 
 /// TerminalInfo.getWindowTitle : unit -> string
 /// Returns the window title, or empty string if no provider is registered
-pub fn get_window_title(unit: &Value) -> Result<Value, VmError> {
+pub fn get_window_title(vm: &Vm, unit: &Value) -> Result<Value, VmError> {
     match unit {
         Value::Unit => {
-            let title = with_provider(|provider| provider.get_window_title())
+            let title = with_provider(vm, |provider| provider.get_window_title())
                 .unwrap_or_else(|| String::new());
 
             Ok(Value::Str(title))
@@ -232,11 +222,11 @@ pub fn get_window_title(unit: &Value) -> Result<Value, VmError> {
 
 /// TerminalInfo.getTabTitle : unit -> string
 /// Returns the tab title, or empty string if no provider is registered
-pub fn get_tab_title(unit: &Value) -> Result<Value, VmError> {
+pub fn get_tab_title(vm: &Vm, unit: &Value) -> Result<Value, VmError> {
     match unit {
         Value::Unit => {
-            let title =
-                with_provider(|provider| provider.get_tab_title()).unwrap_or_else(|| String::new());
+            let title = with_provider(vm, |provider| provider.get_tab_title())
+                .unwrap_or_else(|| String::new());
 
             Ok(Value::Str(title))
         }
@@ -250,11 +240,11 @@ pub fn get_tab_title(unit: &Value) -> Result<Value, VmError> {
 /// TerminalInfo.getTerminalSize : unit -> (int * int)
 /// Returns the terminal size as a tuple (columns, rows)
 /// Returns (0, 0) if no provider is registered
-pub fn get_terminal_size(unit: &Value) -> Result<Value, VmError> {
+pub fn get_terminal_size(vm: &Vm, unit: &Value) -> Result<Value, VmError> {
     match unit {
         Value::Unit => {
             let (cols, rows) =
-                with_provider(|provider| provider.get_terminal_size()).unwrap_or((0, 0));
+                with_provider(vm, |provider| provider.get_terminal_size()).unwrap_or((0, 0));
 
             Ok(Value::Tuple(vec![Value::Int(cols), Value::Int(rows)]))
         }
@@ -268,10 +258,6 @@ pub fn get_terminal_size(unit: &Value) -> Result<Value, VmError> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Mutex;
-
-    // Test mutex to ensure tests run serially and don't interfere with each other
-    static TEST_MUTEX: Mutex<()> = Mutex::new(());
 
     // Mock provider for testing
     struct MockTerminalProvider {
@@ -326,10 +312,8 @@ mod tests {
 
     #[test]
     fn test_get_foreground_process_no_provider() {
-        let _lock = TEST_MUTEX.lock().unwrap();
-        unregister_provider();
-
-        let result = get_foreground_process(&Value::Unit).unwrap();
+        let vm = Vm::new();
+        let result = get_foreground_process(&vm, &Value::Unit).unwrap();
 
         match result {
             Value::Variant { variant_name, .. } => {
@@ -341,7 +325,7 @@ mod tests {
 
     #[test]
     fn test_get_foreground_process_with_provider() {
-        let _lock = TEST_MUTEX.lock().unwrap();
+        let vm = Vm::new();
         let provider = Box::new(MockTerminalProvider {
             foreground_process: Some(ProcessInfo {
                 name: "bash".to_string(),
@@ -355,9 +339,9 @@ mod tests {
             terminal_size: (80, 24),
         });
 
-        register_provider(provider);
+        register_provider(&vm, provider);
 
-        let result = get_foreground_process(&Value::Unit).unwrap();
+        let result = get_foreground_process(&vm, &Value::Unit).unwrap();
 
         match result {
             Value::Variant {
@@ -379,13 +363,12 @@ mod tests {
             }
             _ => panic!("Expected Option::Some variant"),
         }
-
-        unregister_provider();
     }
 
     #[test]
     fn test_get_foreground_process_type_error() {
-        let result = get_foreground_process(&Value::Int(42));
+        let vm = Vm::new();
+        let result = get_foreground_process(&vm, &Value::Int(42));
         assert!(matches!(
             result,
             Err(VmError::TypeMismatch {
@@ -397,10 +380,8 @@ mod tests {
 
     #[test]
     fn test_get_current_working_dir_no_provider() {
-        let _lock = TEST_MUTEX.lock().unwrap();
-        unregister_provider();
-
-        let result = get_current_working_dir(&Value::Unit).unwrap();
+        let vm = Vm::new();
+        let result = get_current_working_dir(&vm, &Value::Unit).unwrap();
 
         match result {
             Value::Variant { variant_name, .. } => {
@@ -412,7 +393,7 @@ mod tests {
 
     #[test]
     fn test_get_current_working_dir_with_provider() {
-        let _lock = TEST_MUTEX.lock().unwrap();
+        let vm = Vm::new();
         let provider = Box::new(MockTerminalProvider {
             foreground_process: None,
             cwd: Some("/home/user".to_string()),
@@ -422,9 +403,9 @@ mod tests {
             terminal_size: (80, 24),
         });
 
-        register_provider(provider);
+        register_provider(&vm, provider);
 
-        let result = get_current_working_dir(&Value::Unit).unwrap();
+        let result = get_current_working_dir(&vm, &Value::Unit).unwrap();
 
         match result {
             Value::Variant {
@@ -438,16 +419,12 @@ mod tests {
             }
             _ => panic!("Expected Option::Some variant"),
         }
-
-        unregister_provider();
     }
 
     #[test]
     fn test_get_line_no_provider() {
-        let _lock = TEST_MUTEX.lock().unwrap();
-        unregister_provider();
-
-        let result = get_line(&Value::Int(0)).unwrap();
+        let vm = Vm::new();
+        let result = get_line(&vm, &Value::Int(0)).unwrap();
 
         match result {
             Value::Variant { variant_name, .. } => {
@@ -459,7 +436,7 @@ mod tests {
 
     #[test]
     fn test_get_line_with_provider() {
-        let _lock = TEST_MUTEX.lock().unwrap();
+        let vm = Vm::new();
         let provider = Box::new(MockTerminalProvider {
             foreground_process: None,
             cwd: None,
@@ -473,9 +450,9 @@ mod tests {
             terminal_size: (80, 24),
         });
 
-        register_provider(provider);
+        register_provider(&vm, provider);
 
-        let result = get_line(&Value::Int(1)).unwrap();
+        let result = get_line(&vm, &Value::Int(1)).unwrap();
 
         match result {
             Value::Variant {
@@ -491,20 +468,19 @@ mod tests {
         }
 
         // Test out of bounds
-        let result = get_line(&Value::Int(10)).unwrap();
+        let result = get_line(&vm, &Value::Int(10)).unwrap();
         match result {
             Value::Variant { variant_name, .. } => {
                 assert_eq!(variant_name, "None");
             }
             _ => panic!("Expected Option::None variant"),
         }
-
-        unregister_provider();
     }
 
     #[test]
     fn test_get_line_type_error() {
-        let result = get_line(&Value::Str("not an int".to_string()));
+        let vm = Vm::new();
+        let result = get_line(&vm, &Value::Str("not an int".to_string()));
         assert!(matches!(
             result,
             Err(VmError::TypeMismatch {
@@ -516,10 +492,8 @@ mod tests {
 
     #[test]
     fn test_get_lines_no_provider() {
-        let _lock = TEST_MUTEX.lock().unwrap();
-        unregister_provider();
-
-        let result = get_lines(&Value::Int(0), &Value::Int(2)).unwrap();
+        let vm = Vm::new();
+        let result = get_lines(&vm, &Value::Int(0), &Value::Int(2)).unwrap();
 
         // Should return empty list
         assert!(matches!(result, Value::Nil));
@@ -527,7 +501,7 @@ mod tests {
 
     #[test]
     fn test_get_lines_with_provider() {
-        let _lock = TEST_MUTEX.lock().unwrap();
+        let vm = Vm::new();
         let provider = Box::new(MockTerminalProvider {
             foreground_process: None,
             cwd: None,
@@ -542,9 +516,9 @@ mod tests {
             terminal_size: (80, 24),
         });
 
-        register_provider(provider);
+        register_provider(&vm, provider);
 
-        let result = get_lines(&Value::Int(1), &Value::Int(3)).unwrap();
+        let result = get_lines(&vm, &Value::Int(1), &Value::Int(3)).unwrap();
 
         // Should return list containing "line 1" and "line 2"
         let mut count = 0;
@@ -562,13 +536,12 @@ mod tests {
             }
         }
         assert_eq!(count, 2);
-
-        unregister_provider();
     }
 
     #[test]
     fn test_get_lines_type_error() {
-        let result = get_lines(&Value::Str("not an int".to_string()), &Value::Int(2));
+        let vm = Vm::new();
+        let result = get_lines(&vm, &Value::Str("not an int".to_string()), &Value::Int(2));
         assert!(matches!(
             result,
             Err(VmError::TypeMismatch {
@@ -577,7 +550,7 @@ mod tests {
             })
         ));
 
-        let result = get_lines(&Value::Int(0), &Value::Str("not an int".to_string()));
+        let result = get_lines(&vm, &Value::Int(0), &Value::Str("not an int".to_string()));
         assert!(matches!(
             result,
             Err(VmError::TypeMismatch {
@@ -589,17 +562,15 @@ mod tests {
 
     #[test]
     fn test_get_window_title_no_provider() {
-        let _lock = TEST_MUTEX.lock().unwrap();
-        unregister_provider();
-
-        let result = get_window_title(&Value::Unit).unwrap();
+        let vm = Vm::new();
+        let result = get_window_title(&vm, &Value::Unit).unwrap();
 
         assert!(matches!(result, Value::Str(s) if s.is_empty()));
     }
 
     #[test]
     fn test_get_window_title_with_provider() {
-        let _lock = TEST_MUTEX.lock().unwrap();
+        let vm = Vm::new();
         let provider = Box::new(MockTerminalProvider {
             foreground_process: None,
             cwd: None,
@@ -609,28 +580,24 @@ mod tests {
             terminal_size: (80, 24),
         });
 
-        register_provider(provider);
+        register_provider(&vm, provider);
 
-        let result = get_window_title(&Value::Unit).unwrap();
+        let result = get_window_title(&vm, &Value::Unit).unwrap();
 
         assert!(matches!(result, Value::Str(s) if s == "Terminal Window"));
-
-        unregister_provider();
     }
 
     #[test]
     fn test_get_tab_title_no_provider() {
-        let _lock = TEST_MUTEX.lock().unwrap();
-        unregister_provider();
-
-        let result = get_tab_title(&Value::Unit).unwrap();
+        let vm = Vm::new();
+        let result = get_tab_title(&vm, &Value::Unit).unwrap();
 
         assert!(matches!(result, Value::Str(s) if s.is_empty()));
     }
 
     #[test]
     fn test_get_tab_title_with_provider() {
-        let _lock = TEST_MUTEX.lock().unwrap();
+        let vm = Vm::new();
         let provider = Box::new(MockTerminalProvider {
             foreground_process: None,
             cwd: None,
@@ -640,21 +607,17 @@ mod tests {
             terminal_size: (80, 24),
         });
 
-        register_provider(provider);
+        register_provider(&vm, provider);
 
-        let result = get_tab_title(&Value::Unit).unwrap();
+        let result = get_tab_title(&vm, &Value::Unit).unwrap();
 
         assert!(matches!(result, Value::Str(s) if s == "Tab 1"));
-
-        unregister_provider();
     }
 
     #[test]
     fn test_get_terminal_size_no_provider() {
-        let _lock = TEST_MUTEX.lock().unwrap();
-        unregister_provider();
-
-        let result = get_terminal_size(&Value::Unit).unwrap();
+        let vm = Vm::new();
+        let result = get_terminal_size(&vm, &Value::Unit).unwrap();
 
         match result {
             Value::Tuple(values) => {
@@ -668,7 +631,7 @@ mod tests {
 
     #[test]
     fn test_get_terminal_size_with_provider() {
-        let _lock = TEST_MUTEX.lock().unwrap();
+        let vm = Vm::new();
         let provider = Box::new(MockTerminalProvider {
             foreground_process: None,
             cwd: None,
@@ -678,9 +641,9 @@ mod tests {
             terminal_size: (120, 40),
         });
 
-        register_provider(provider);
+        register_provider(&vm, provider);
 
-        let result = get_terminal_size(&Value::Unit).unwrap();
+        let result = get_terminal_size(&vm, &Value::Unit).unwrap();
 
         match result {
             Value::Tuple(values) => {
@@ -690,13 +653,12 @@ mod tests {
             }
             _ => panic!("Expected tuple"),
         }
-
-        unregister_provider();
     }
 
     #[test]
     fn test_get_terminal_size_type_error() {
-        let result = get_terminal_size(&Value::Int(42));
+        let vm = Vm::new();
+        let result = get_terminal_size(&vm, &Value::Int(42));
         assert!(matches!(
             result,
             Err(VmError::TypeMismatch {
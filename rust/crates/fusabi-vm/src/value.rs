@@ -139,6 +139,81 @@ impl Default for HostData {
     }
 }
 
+/// Lazily evaluated sequence
+///
+/// A sequence only describes how to produce its elements. Nothing runs until
+/// it is enumerated (see `stdlib::seq`), and every enumeration starts over
+/// from the beginning, so a sequence value can be shared and reused freely.
+#[derive(Debug, Clone)]
+pub struct Seq(Arc<SeqSource>);
+
+/// How a [`Seq`] produces its elements
+#[derive(Debug)]
+pub enum SeqSource {
+    /// No elements
+    Empty,
+    /// A single element
+    Singleton(Value),
+    /// The elements of a list or array
+    Of(Value),
+    /// `f ()` is called on enumeration and its result enumerated
+    Delay(Value),
+    /// The elements of the first sequence, then those of the second
+    Append(Seq, Seq),
+    /// `f x` for each element `x`
+    Map(Value, Seq),
+    /// The elements for which the predicate holds
+    Filter(Value, Seq),
+    /// The elements of `f x` for each element `x`
+    Collect(Value, Seq),
+    /// At most the first n elements
+    Take(usize, Seq),
+    /// All but the first n elements
+    Skip(usize, Seq),
+    /// The leading elements for which the predicate holds
+    TakeWhile(Value, Seq),
+    /// `f state` returns `Some (x, next)` to produce `x`, or `None` to stop
+    Unfold(Value, Value),
+    /// `f 0`, `f 1`, ... up to the count, or forever without one
+    Init(Option<i64>, Value),
+    /// The numbers `start .. step .. finish`, counted as they are enumerated
+    Range(Value, Value, Value),
+    /// The lines of a text file, read as they are enumerated
+    FileLines(String),
+}
+
+impl Seq {
+    /// Create a sequence from its source
+    pub fn new(source: SeqSource) -> Self {
+        Seq(Arc::new(source))
+    }
+
+    /// The empty sequence
+    pub fn empty() -> Self {
+        Seq::new(SeqSource::Empty)
+    }
+
+    /// How this sequence produces its elements
+    pub fn source(&self) -> &SeqSource {
+        &self.0
+    }
+}
+
+impl PartialEq for Seq {
+    fn eq(&self, other: &Self) -> bool {
+        // Sequences are compared by identity; comparing elements would
+        // require running them
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Default for Seq {
+    fn default() -> Self {
+        // This is only used by serde when skipping
+        Seq::empty()
+    }
+}
+
 /// Runtime value representation for the Fusabi VM
 ///
 /// Note: HostData variant cannot be serialized/deserialized with serde.
@@ -185,11 +260,20 @@ pub enum Value {
         arity: u8,
         args: Vec<Value>,
     },
+    /// Unicode scalar value ('a')
+    /// Declared after the original variants so existing bytecode keeps its encoding.
+    Char(char),
+    /// Immutable byte buffer (`"GET"B`, `Bytes.*`, SQLite BLOBs, OSC blobs)
+    Bytes(Vec<u8>),
     /// Host-managed opaque data (for exposing Rust objects to scripts)
     /// WARNING: This variant is NOT serializable and should not appear in bytecode.
     /// It exists only for runtime host-guest interop.
     #[cfg_attr(feature = "serde", serde(skip))]
     HostData(HostData),
+    /// Lazily evaluated sequence (`seq { ... }`, `Seq.*`)
+    /// Like HostData, sequences hold live functions and are not serializable.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Seq(Seq),
     /// Async value (Tokio-backed async computation)
     /// Only available when the 'async' feature is enabled
     #[cfg(feature = "async")]
@@ -204,6 +288,8 @@ impl PartialEq for Value {
             (Value::Float(a), Value::Float(b)) => a == b,
             (Value::Bool(a), Value::Bool(b)) => a == b,
             (Value::Str(a), Value::Str(b)) => a == b,
+            (Value::Char(a), Value::Char(b)) => a == b,
+            (Value::Bytes(a), Value::Bytes(b)) => a == b,
             (Value::Unit, Value::Unit) => true,
             (Value::Tuple(a), Value::Tuple(b)) => a == b,
             (Value::Cons { head: h1, tail: t1 }, Value::Cons { head: h2, tail: t2 }) => {
@@ -248,6 +334,7 @@ impl PartialEq for Value {
                 },
             ) => n1 == n2 && a1 == a2 && args1 == args2,
             (Value::HostData(a), Value::HostData(b)) => a == b,
+            (Value::Seq(a), Value::Seq(b)) => a == b,
             #[cfg(feature = "async")]
             (Value::Async(a), Value::Async(b)) => a == b,
             _ => false,
@@ -264,6 +351,8 @@ impl Value {
             Value::Float(_) => "float",
             Value::Bool(_) => "bool",
             Value::Str(_) => "string",
+            Value::Char(_) => "char",
+            Value::Bytes(_) => "bytes",
             Value::Unit => "unit",
             Value::Tuple(_) => "tuple",
             Value::Cons { .. } => "list",
@@ -275,6 +364,7 @@ impl Value {
             Value::Closure(_) => "function",
             Value::NativeFn { .. } => "function",
             Value::HostData(_) => "host_data",
+            Value::Seq(_) => "seq",
             #[cfg(feature = "async")]
             Value::Async(_) => "async",
         }
@@ -325,6 +415,24 @@ impl Value {
         }
     }
 
+    /// Attempts to extract a char from the value
+    /// Returns Some(char) if the value is Char, None otherwise
+    pub fn as_char(&self) -> Option<char> {
+        match self {
+            Value::Char(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    /// Attempts to extract a byte slice from the value
+    /// Returns Some(&[u8]) if the value is Bytes, None otherwise
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(b) => Some(b),
+            _ => None,
+        }
+    }
+
     /// Attempts to extract a tuple reference from the value
     /// Returns Some(&`Vec<Value>`) if the value is Tuple, None otherwise
     pub fn as_tuple(&self) -> Option<&Vec<Value>> {
@@ -354,6 +462,8 @@ impl Value {
             Value::Int(n) => *n != 0,
             Value::Float(f) => *f != 0.0,
             Value::Str(s) => !s.is_empty(),
+            Value::Char(_) => true,
+            Value::Bytes(b) => !b.is_empty(),
             Value::Unit => false,
             Value::Tuple(elements) => !elements.is_empty(),
             Value::Cons { .. } => true,
@@ -365,6 +475,7 @@ impl Value {
             Value::Closure(_) => true,
             Value::NativeFn { .. } => true,
             Value::HostData(_) => true,
+            Value::Seq(_) => true,
             #[cfg(feature = "async")]
             Value::Async(_) => true,
         }
@@ -663,6 +774,18 @@ impl fmt::Display for Value {
             Value::Float(n) => write!(f, "{}", n),
             Value::Bool(b) => write!(f, "{}", b),
             Value::Str(s) => write!(f, "{}", s),
+            Value::Char(c) => write!(f, "{}", c),
+            Value::Bytes(bytes) => {
+                // Pretty-print as [|71uy; 69uy|], as F# does for byte[]
+                write!(f, "[|")?;
+                for (i, byte) in bytes.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}uy", byte)?;
+                }
+                write!(f, "|]")
+            }
             Value::Unit => write!(f, "()"),
             Value::Tuple(elements) => {
                 write!(f, "(")?;
@@ -761,6 +884,7 @@ impl fmt::Display for Value {
             Value::Closure(c) => write!(f, "{}", c),
             Value::NativeFn { name, .. } => write!(f, "<native fn {}>", name),
             Value::HostData(hd) => write!(f, "<host object: {}>", hd.type_name()),
+            Value::Seq(_) => write!(f, "seq [...]"),
             #[cfg(feature = "async")]
             Value::Async(av) => write!(f, "{}", av),
         }
@@ -925,6 +1049,21 @@ mod tests {
         assert!(tuple.unwrap()[1].is_tuple());
     }
 
+    #[test]
+    fn test_char_and_bytes_values() {
+        let c = Value::Char('λ');
+        assert_eq!(c.as_char(), Some('λ'));
+        assert_eq!(c.type_name(), "char");
+        assert_eq!(format!("{}", c), "λ");
+        assert_ne!(c, Value::Str("λ".to_string()));
+
+        let bytes = Value::Bytes(vec![71, 69, 84]);
+        assert_eq!(bytes.as_bytes(), Some(&b"GET"[..]));
+        assert_eq!(bytes.type_name(), "bytes");
+        assert_eq!(format!("{}", bytes), "[|71uy; 69uy; 84uy|]");
+        assert!(!Value::Bytes(vec![]).is_truthy());
+    }
+
     // ========== Truthiness Tests ==========
 
     #[test]
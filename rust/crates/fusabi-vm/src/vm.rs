@@ -1,16 +1,22 @@
 // Fusabi VM Interpreter
 // Implements the bytecode interpreter loop with stack-based execution
 
-use crate::chunk::Chunk;
+use crate::chunk::{Chunk, UpvalueSource};
 use crate::closure::{Closure, Upvalue};
+use crate::context::VmContext;
+use crate::exception;
 use crate::gc::GcHeap;
 use crate::host::HostRegistry;
 use crate::instruction::Instruction;
-use crate::value::Value;
+use crate::interrupt::{Interrupt, InterruptHandle};
+use crate::limits::{LimitKind, VmLimits, CHECK_INTERVAL};
+use crate::stdlib::seq::SeqEnumerator;
+use crate::value::{HostData, Value};
 use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Instant;
 
 /// Runtime error that can occur during VM execution
 #[derive(Debug, Clone, PartialEq)]
@@ -30,8 +36,6 @@ pub enum VmError {
     InvalidLocalIndex(u8),
     /// Invalid instruction pointer
     InvalidInstructionPointer(usize),
-    /// Call stack overflow
-    CallStackOverflow,
     /// No active frame
     NoActiveFrame,
     /// Invalid tuple field index
@@ -43,6 +47,14 @@ pub enum VmError {
     /// Runtime error with message
     Runtime(String),
     EmptyList,
+    /// Exception raised by a script that no `try ... with` handler caught
+    Exception(Value),
+    /// A budget configured through [`VmLimits`] ran out
+    LimitExceeded {
+        kind: LimitKind,
+    },
+    /// The host cancelled the run through an [`InterruptHandle`]
+    Interrupted,
 }
 
 impl fmt::Display for VmError {
@@ -58,7 +70,6 @@ impl fmt::Display for VmError {
             VmError::InvalidInstructionPointer(ip) => {
                 write!(f, "Invalid instruction pointer: {}", ip)
             }
-            VmError::CallStackOverflow => write!(f, "Call stack overflow"),
             VmError::NoActiveFrame => write!(f, "No active frame"),
             VmError::InvalidTupleFieldIndex { index, tuple_size } => {
                 write!(
@@ -69,12 +80,33 @@ impl fmt::Display for VmError {
             }
             VmError::EmptyList => write!(f, "Cannot access head/tail of empty list"),
             VmError::Runtime(msg) => write!(f, "Runtime error: {}", msg),
+            VmError::Exception(exn) => write!(f, "Unhandled exception: {}", exn),
+            VmError::LimitExceeded { kind } => write!(f, "Execution limit exceeded: {}", kind),
+            VmError::Interrupted => write!(f, "Execution interrupted"),
         }
     }
 }
 
 impl std::error::Error for VmError {}
 
+/// How a resumable run stopped
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunState {
+    /// The script returned this value
+    Finished(Value),
+    /// A pause was requested; continue with [`Vm::resume`]
+    Paused,
+}
+
+/// Bookkeeping for a resumable run, kept while it is paused
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PausedRun {
+    /// Frame count while the run's first frame is active
+    pub(crate) start_depth: usize,
+    /// Stack height when the run began
+    pub(crate) base: usize,
+}
+
 /// Call frame - represents an active function call
 #[derive(Debug, Clone)]
 pub struct Frame {
@@ -116,20 +148,93 @@ impl Frame {
     }
 }
 
+/// Exception handler installed by `PushHandler`
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Handler {
+    /// Number of active frames when the handler was installed
+    pub(crate) frame_depth: usize,
+    /// Stack height to unwind to before entering the handler
+    pub(crate) stack_height: usize,
+    /// Handler entry point in the installing frame's chunk
+    pub(crate) catch_ip: usize,
+}
+
+/// Members declared on a script type by `DefineType`
+#[derive(Debug, Clone)]
+struct TypeMembers {
+    /// Field names of a record type, or case names of a union
+    names: Vec<String>,
+    /// Whether the type is a union
+    is_union: bool,
+    /// Record of the static members by name
+    statics: Value,
+    /// Record of the instance members by name, each taking the receiver first
+    instance: Value,
+}
+
+impl TypeMembers {
+    /// Whether `value` is of this type
+    ///
+    /// A record has the type when it has exactly the type's fields, and a
+    /// union value when its case is one of the type's cases.
+    fn describes(&self, value: &Value) -> bool {
+        match value {
+            Value::Record(fields) if !self.is_union => {
+                let fields = fields.lock().unwrap();
+                fields.len() == self.names.len()
+                    && self.names.iter().all(|name| fields.contains_key(name))
+            }
+            Value::Variant { variant_name, .. } if self.is_union => {
+                self.names.contains(variant_name)
+            }
+            _ => false,
+        }
+    }
+}
+
 /// Maximum call stack depth
 /// The virtual machine - bytecode interpreter
 #[derive(Debug)]
 pub struct Vm {
     /// Value stack for operands and intermediate results
-    stack: Vec<Value>,
+    pub(crate) stack: Vec<Value>,
     /// Call frame stack
-    frames: Vec<Frame>,
+    pub(crate) frames: Vec<Frame>,
+    /// Active exception handlers, innermost last
+    handlers: Vec<Handler>,
+    /// Upvalues still pointing at live stack slots, shared by every closure
+    /// that captures the same slot
+    open_upvalues: Vec<Arc<Mutex<Upvalue>>>,
     /// Global variables
     pub globals: HashMap<String, Value>,
+    /// Script types that declare members, most recently declared last
+    types: Vec<TypeMembers>,
     /// Host function registry
     pub host_registry: Arc<Mutex<HostRegistry>>,
     /// Garbage collector heap
     pub gc_heap: GcHeap,
+    /// Execution budgets checked by the interpreter loop
+    limits: VmLimits,
+    /// Instructions run since the current top-level execution began
+    instruction_count: u64,
+    /// When the current top-level execution runs out of time
+    deadline: Option<Instant>,
+    /// Interpreter loops currently running, counting those entered from
+    /// host callbacks
+    nested_runs: usize,
+    /// Native stack address where the outermost running loop started
+    native_stack_base: usize,
+    /// Reachable bytes at the last heap walk plus the bytes allocated since
+    heap_estimate: usize,
+    /// Instruction count at which the heap is walked again, so allocations
+    /// made by host functions are noticed too
+    next_heap_walk: u64,
+    /// Cancel and pause requests from the host
+    interrupt: InterruptHandle,
+    /// The resumable run waiting for `resume`, if any
+    paused: Option<PausedRun>,
+    /// Stdlib state such as event handlers and config values
+    pub context: VmContext,
     /// Async runtime (Tokio-backed)
     #[cfg(feature = "async")]
     pub async_runtime: Option<Arc<crate::async_runtime::AsyncRuntime>>,
@@ -144,9 +249,22 @@ impl Vm {
         Vm {
             stack: Vec::with_capacity(Self::DEFAULT_STACK_CAPACITY),
             frames: Vec::new(),
+            handlers: Vec::new(),
+            open_upvalues: Vec::new(),
             globals: HashMap::new(),
+            types: Vec::new(),
             host_registry: Arc::new(Mutex::new(HostRegistry::new())),
             gc_heap: GcHeap::new(),
+            limits: VmLimits::default(),
+            instruction_count: 0,
+            deadline: None,
+            nested_runs: 0,
+            native_stack_base: 0,
+            heap_estimate: 0,
+            next_heap_walk: 0,
+            interrupt: InterruptHandle::new(),
+            paused: None,
+            context: VmContext::new(),
             #[cfg(feature = "async")]
             async_runtime: None,
         }
@@ -157,9 +275,22 @@ impl Vm {
         Vm {
             stack: Vec::with_capacity(capacity),
             frames: Vec::new(),
+            handlers: Vec::new(),
+            open_upvalues: Vec::new(),
             globals: HashMap::new(),
+            types: Vec::new(),
             host_registry: Arc::new(Mutex::new(HostRegistry::new())),
             gc_heap: GcHeap::new(),
+            limits: VmLimits::default(),
+            instruction_count: 0,
+            deadline: None,
+            nested_runs: 0,
+            native_stack_base: 0,
+            heap_estimate: 0,
+            next_heap_walk: 0,
+            interrupt: InterruptHandle::new(),
+            paused: None,
+            context: VmContext::new(),
             #[cfg(feature = "async")]
             async_runtime: None,
         }
@@ -170,9 +301,22 @@ impl Vm {
         Vm {
             stack: Vec::with_capacity(Self::DEFAULT_STACK_CAPACITY),
             frames: Vec::new(),
+            handlers: Vec::new(),
+            open_upvalues: Vec::new(),
             globals: HashMap::new(),
+            types: Vec::new(),
             host_registry: Arc::new(Mutex::new(HostRegistry::new())),
             gc_heap: GcHeap::with_threshold(threshold),
+            limits: VmLimits::default(),
+            instruction_count: 0,
+            deadline: None,
+            nested_runs: 0,
+            native_stack_base: 0,
+            heap_estimate: 0,
+            next_heap_walk: 0,
+            interrupt: InterruptHandle::new(),
+            paused: None,
+            context: VmContext::new(),
             #[cfg(feature = "async")]
             async_runtime: None,
         }
@@ -189,6 +333,13 @@ impl Vm {
         // Add globals as roots
         roots.extend(self.globals.values().cloned());
 
+        // Add the members of script types as roots
+        roots.extend(
+            self.types
+                .iter()
+                .flat_map(|ty| [ty.statics.clone(), ty.instance.clone()]),
+        );
+
         // Add closure constants and upvalues from frames as roots
         for frame in &self.frames {
             // Add the closure itself as a root
@@ -219,16 +370,112 @@ impl Vm {
         &self.gc_heap.stats
     }
 
+    /// Set the execution budgets checked while running
+    pub fn set_limits(&mut self, limits: VmLimits) {
+        self.limits = limits;
+    }
+
+    /// Get the execution budgets checked while running
+    pub fn limits(&self) -> VmLimits {
+        self.limits
+    }
+
+    /// Number of instructions run since the current top-level execution began
+    pub fn instruction_count(&self) -> u64 {
+        self.instruction_count
+    }
+
+    /// Get a handle that cancels or pauses this VM from any thread
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.interrupt.clone()
+    }
+
+    /// Approximate bytes held by values reachable from the stack and globals
+    pub fn heap_bytes(&self) -> usize {
+        crate::gc::estimate_reachable_bytes(self.stack.iter().chain(self.globals.values()))
+    }
+
     /// Execute a chunk of bytecode
     pub fn execute(&mut self, chunk: Chunk) -> Result<Value, VmError> {
         // Wrap the top-level chunk in a closure
         let closure = Arc::new(Closure::new(chunk));
 
-        // Push initial frame
-        let frame = Frame::new(closure, 0);
-        self.frames.push(frame);
+        // Push initial frame above anything already on the stack (re-entrant execute)
+        self.begin_execution();
+        let depth = self.frames.len();
+        let base = self.stack.len();
+        self.push_frame(closure, base)?;
+
+        let result = self.run();
+        if result.is_err() {
+            self.unwind_failed_call(depth, base);
+        }
+        result
+    }
+
+    /// Execute a chunk in a run that [`InterruptHandle::pause`] can pause
+    ///
+    /// Returns [`RunState::Paused`] with the script's frames left in place;
+    /// [`Vm::resume`] continues from the next instruction.
+    pub fn execute_resumable(&mut self, chunk: Chunk) -> Result<RunState, VmError> {
+        let run = self.start_resumable(chunk)?;
+        let result = self.run_until(run.start_depth, true);
+        self.finish_resumable(run, result)
+    }
+
+    /// Continue a run that returned [`RunState::Paused`]
+    pub fn resume(&mut self) -> Result<RunState, VmError> {
+        let run = self.take_paused()?;
+        let result = self.run_until(run.start_depth, true);
+        self.finish_resumable(run, result)
+    }
+
+    /// Whether a paused run is waiting for [`Vm::resume`]
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    /// Push the frame for a resumable run of `chunk`
+    pub(crate) fn start_resumable(&mut self, chunk: Chunk) -> Result<PausedRun, VmError> {
+        if self.paused.is_some() {
+            return Err(VmError::Runtime(
+                "A paused run must be resumed before starting another".into(),
+            ));
+        }
+        let closure = Arc::new(Closure::new(chunk));
+        self.begin_execution();
+        let base = self.stack.len();
+        self.push_frame(closure, base)?;
+        Ok(PausedRun {
+            start_depth: self.frames.len(),
+            base,
+        })
+    }
+
+    /// Take the paused run that `resume` should continue
+    pub(crate) fn take_paused(&mut self) -> Result<PausedRun, VmError> {
+        self.paused
+            .take()
+            .ok_or_else(|| VmError::Runtime("No paused run to resume".into()))
+    }
 
-        self.run()
+    /// Keep a paused run for `resume`, or clean up after a failed one
+    pub(crate) fn finish_resumable(
+        &mut self,
+        run: PausedRun,
+        result: Result<RunState, VmError>,
+    ) -> Result<RunState, VmError> {
+        match result {
+            Ok(RunState::Paused) => {
+                self.paused = Some(run);
+                Ok(RunState::Paused)
+            }
+            Err(error) => {
+                self.unwind_failed_call(run.start_depth - 1, run.base);
+                Err(error)
+            }
+            finished => finished,
+        }
     }
 
     /// Create a VM instance from pre-compiled bytecode.
@@ -273,8 +520,7 @@ impl Vm {
 
         // Wrap the chunk in a closure and push initial frame
         let closure = Arc::new(Closure::new(chunk));
-        let frame = Frame::new(closure, 0);
-        vm.frames.push(frame);
+        vm.push_frame(closure, 0)?;
 
         Ok(vm)
     }
@@ -320,855 +566,1137 @@ impl Vm {
     /// Run the interpreter loop
     pub fn run(&mut self) -> Result<Value, VmError> {
         let start_depth = self.frames.len();
+        match self.run_until(start_depth, false)? {
+            RunState::Finished(value) => Ok(value),
+            RunState::Paused => unreachable!("only resumable runs pause"),
+        }
+    }
+
+    /// Run until the frame at `start_depth` returns or, if `pausable`, a
+    /// pause is requested
+    ///
+    /// At least one instruction runs before pausing, so a host that pauses
+    /// on every resume still makes progress.
+    fn run_until(&mut self, start_depth: usize, pausable: bool) -> Result<RunState, VmError> {
+        self.enter_run()?;
+        let state = self.run_loop(start_depth, pausable);
+        self.exit_run();
+        state
+    }
+
+    /// The interpreter loop behind [`Vm::run_until`]
+    fn run_loop(&mut self, start_depth: usize, pausable: bool) -> Result<RunState, VmError> {
+        let mut can_pause = false;
 
         // Main interpreter loop
         loop {
+            if self.tick(can_pause)? {
+                return Ok(RunState::Paused);
+            }
+            can_pause = pausable;
+
             // Fetch next instruction in a separate scope to release mutable borrow on self
             let instruction = {
                 let frame = self.current_frame_mut()?;
                 frame.fetch_instruction()?
             };
 
-            // Execute instruction
-            match instruction {
-                Instruction::LoadConst(idx) => {
-                    let constant = self.current_frame()?.get_constant(idx)?;
-                    self.push(constant);
-                }
+            // Execute instruction, letting script handlers catch its errors
+            match self.dispatch(instruction, start_depth) {
+                Ok(Some(result)) => return Ok(RunState::Finished(result)),
+                Ok(None) => {}
+                Err(error) => self.throw_error(error, start_depth)?,
+            }
+        }
+    }
 
-                Instruction::LoadLocal(idx) => {
-                    let value = self.get_local(idx)?;
-                    self.push(value);
-                }
+    /// Execute one instruction of the current frame
+    ///
+    /// Returns the result once the frame that `run` started in returns.
+    /// `start_depth` is the frame count when that `run` call began.
+    pub(crate) fn dispatch(
+        &mut self,
+        instruction: Instruction,
+        start_depth: usize,
+    ) -> Result<Option<Value>, VmError> {
+        match instruction {
+            Instruction::LoadConst(idx) => {
+                let constant = self.current_frame()?.get_constant(idx)?;
+                self.push(constant);
+            }
 
-                Instruction::StoreLocal(idx) => {
-                    let value = self.pop()?;
-                    self.set_local(idx, value)?;
-                }
+            Instruction::LoadLocal(idx) => {
+                let value = self.get_local(idx)?;
+                self.push(value);
+            }
 
-                Instruction::LoadUpvalue(idx) => {
-                    let frame = self.current_frame()?;
-                    let upvalue = frame
-                        .closure
-                        .get_upvalue(idx as usize)
-                        .ok_or(VmError::Runtime(format!("Invalid upvalue index: {}", idx)))?;
-                    let value = match &*upvalue.lock().unwrap() {
-                        Upvalue::Closed(v) => v.clone(),
-                        Upvalue::Open(stack_idx) => self.stack[*stack_idx].clone(),
-                    };
-                    self.push(value);
-                }
+            Instruction::StoreLocal(idx) => {
+                let value = self.pop()?;
+                self.set_local(idx, value)?;
+            }
 
-                Instruction::StoreUpvalue(idx) => {
-                    let value = self.pop()?;
-                    let frame = self.current_frame()?;
-                    let upvalue = frame
-                        .closure
-                        .get_upvalue(idx as usize)
-                        .ok_or(VmError::Runtime(format!("Invalid upvalue index: {}", idx)))?;
-
-                    match &mut *upvalue.lock().unwrap() {
-                        Upvalue::Closed(v) => *v = value,
-                        Upvalue::Open(stack_idx) => self.stack[*stack_idx] = value,
-                    };
-                }
+            Instruction::LoadUpvalue(idx) => {
+                let frame = self.current_frame()?;
+                let upvalue = frame
+                    .closure
+                    .get_upvalue(idx as usize)
+                    .ok_or(VmError::Runtime(format!("Invalid upvalue index: {}", idx)))?;
+                let value = match &*upvalue.lock().unwrap() {
+                    Upvalue::Closed(v) => v.clone(),
+                    Upvalue::Open(stack_idx) => self.stack[*stack_idx].clone(),
+                };
+                self.push(value);
+            }
 
-                Instruction::LoadGlobal(idx) => {
-                    let name_val = self.current_frame()?.get_constant(idx)?;
-                    let name = match name_val {
-                        Value::Str(s) => s,
-                        _ => {
-                            return Err(VmError::TypeMismatch {
-                                expected: "string (global name)",
-                                got: name_val.type_name(),
-                            })
-                        }
-                    };
+            Instruction::StoreUpvalue(idx) => {
+                let value = self.pop()?;
+                let frame = self.current_frame()?;
+                let upvalue = frame
+                    .closure
+                    .get_upvalue(idx as usize)
+                    .ok_or(VmError::Runtime(format!("Invalid upvalue index: {}", idx)))?;
+
+                match &mut *upvalue.lock().unwrap() {
+                    Upvalue::Closed(v) => *v = value,
+                    Upvalue::Open(stack_idx) => self.stack[*stack_idx] = value,
+                };
+            }
 
-                    // First check vm.globals
-                    if let Some(value) = self.globals.get(&name).cloned() {
-                        self.push(value);
-                    } else {
-                        // Fall back to checking host_registry for registered functions
-                        let has_host_fn = self.host_registry.lock().unwrap().has_function(&name);
+            Instruction::LoadGlobal(idx) => {
+                let name_val = self.current_frame()?.get_constant(idx)?;
+                let name = match name_val {
+                    Value::Str(s) => s,
+                    _ => {
+                        return Err(VmError::TypeMismatch {
+                            expected: "string (global name)",
+                            got: name_val.type_name(),
+                        })
+                    }
+                };
 
-                        if has_host_fn {
-                            // Create a NativeFn value with arity 0 (dynamic arity)
-                            // The arity will be checked when the function is actually called
-                            self.push(Value::NativeFn {
-                                name: name.clone(),
-                                arity: 0,  // 0 means dynamic arity
-                                args: vec![],
-                            });
-                        } else {
-                            return Err(VmError::Runtime(format!("Undefined global: {}", name)));
-                        }
+                // First check vm.globals
+                if let Some(value) = self.globals.get(&name).cloned() {
+                    self.push(value);
+                } else {
+                    // Fall back to checking host_registry for registered functions
+                    let has_host_fn = self.host_registry.lock().unwrap().has_function(&name);
+
+                    if has_host_fn {
+                        // Create a NativeFn value with arity 0 (dynamic arity)
+                        // The arity will be checked when the function is actually called
+                        self.push(Value::NativeFn {
+                            name: name.clone(),
+                            arity: 0,  // 0 means dynamic arity
+                            args: vec![],
+                        });
+                    } else if let Some(member) = self.module_member(&name) {
+                        // e.g. 'seq.Yield' from a computation expression
+                        self.push(member);
+                    } else {
+                        return Err(VmError::Runtime(format!("Undefined global: {}", name)));
                     }
                 }
+            }
 
-                Instruction::Pop => {
-                    self.pop()?;
-                }
+            Instruction::Pop => {
+                self.pop()?;
+            }
 
-                Instruction::Dup => {
-                    let value = self.peek()?;
-                    self.push(value.clone());
-                }
+            Instruction::Dup => {
+                let value = self.peek()?;
+                self.push(value.clone());
+            }
 
-                Instruction::CheckInt(expected) => {
-                    let value = self.peek()?;
-                    let matches = matches!(value, Value::Int(n) if *n == expected);
-                    self.push(Value::Bool(matches));
-                }
+            Instruction::CheckInt(expected) => {
+                let value = self.peek()?;
+                let matches = matches!(value, Value::Int(n) if *n == expected);
+                self.push(Value::Bool(matches));
+            }
 
-                Instruction::CheckBool(expected) => {
-                    let value = self.peek()?;
-                    let matches = matches!(value, Value::Bool(b) if *b == expected);
-                    self.push(Value::Bool(matches));
-                }
+            Instruction::CheckBool(expected) => {
+                let value = self.peek()?;
+                let matches = matches!(value, Value::Bool(b) if *b == expected);
+                self.push(Value::Bool(matches));
+            }
 
-                Instruction::CheckString(expected) => {
-                    let value = self.peek()?;
-                    let matches = matches!(value, Value::Str(s) if *s == expected);
-                    self.push(Value::Bool(matches));
-                }
+            Instruction::CheckString(expected) => {
+                let value = self.peek()?;
+                let matches = matches!(value, Value::Str(s) if *s == expected);
+                self.push(Value::Bool(matches));
+            }
 
-                Instruction::CheckTupleLen(expected) => {
-                    let value = self.peek()?;
-                    let matches = if let Value::Tuple(elements) = value {
-                        elements.len() == expected as usize
-                    } else {
-                        false
-                    };
-                    self.push(Value::Bool(matches));
-                }
+            Instruction::CheckTupleLen(expected) => {
+                let value = self.peek()?;
+                let matches = if let Value::Tuple(elements) = value {
+                    elements.len() == expected as usize
+                } else {
+                    false
+                };
+                self.push(Value::Bool(matches));
+            }
 
-                Instruction::GetTupleElem(index) => {
-                    let value = self.peek()?;
-                    if let Value::Tuple(elements) = value {
-                        if (index as usize) < elements.len() {
-                            self.push(elements[index as usize].clone());
-                        } else {
-                            return Err(VmError::Runtime("Tuple index out of bounds".into()));
-                        }
+            Instruction::GetTupleElem(index) => {
+                let value = self.peek()?;
+                if let Value::Tuple(elements) = value {
+                    if (index as usize) < elements.len() {
+                        self.push(elements[index as usize].clone());
                     } else {
-                        return Err(VmError::Runtime("Not a tuple".into()));
+                        return Err(VmError::Runtime("Tuple index out of bounds".into()));
                     }
+                } else {
+                    return Err(VmError::Runtime("Not a tuple".into()));
                 }
+            }
+
+            Instruction::MatchFailure => {
+                let value = self.pop()?;
+                return Err(VmError::Runtime(format!(
+                    "Match failure: no pattern matched {}",
+                    value
+                )));
+            }
 
-                // Arithmetic operations - use unchecked pops since bytecode guarantees stack has values
-                Instruction::Add => {
-                    let b = self.pop_unchecked();
-                    let a = self.pop_unchecked();
-                    match (a, b) {
-                        (Value::Int(a), Value::Int(b)) => self.push(Value::Int(a + b)),
-                        (Value::Float(a), Value::Float(b)) => self.push(Value::Float(a + b)),
-                        (a, b) => {
+            // Arithmetic operations - use unchecked pops since bytecode guarantees stack has values
+            Instruction::Add => {
+                let b = self.pop_unchecked();
+                let a = self.pop_unchecked();
+                match (a, b) {
+                    (Value::Int(a), Value::Int(b)) => self.push(Value::Int(a + b)),
+                    (Value::Float(a), Value::Float(b)) => self.push(Value::Float(a + b)),
+                    (a, b) => match self.operator_member("op_Addition", &a, &b) {
+                        Some(member) => self.apply_member(member, a, b, start_depth)?,
+                        None => {
                             return Err(VmError::Runtime(format!(
                                 "Type mismatch in addition: {} + {}",
                                 a.type_name(),
                                 b.type_name()
                             )))
                         }
-                    }
+                    },
                 }
+            }
 
-                Instruction::Sub => {
-                    let b = self.pop_unchecked();
-                    let a = self.pop_unchecked();
-                    match (a, b) {
-                        (Value::Int(a), Value::Int(b)) => self.push(Value::Int(a - b)),
-                        (Value::Float(a), Value::Float(b)) => self.push(Value::Float(a - b)),
-                        (a, b) => {
+            Instruction::Sub => {
+                let b = self.pop_unchecked();
+                let a = self.pop_unchecked();
+                match (a, b) {
+                    (Value::Int(a), Value::Int(b)) => self.push(Value::Int(a - b)),
+                    (Value::Float(a), Value::Float(b)) => self.push(Value::Float(a - b)),
+                    (a, b) => match self.operator_member("op_Subtraction", &a, &b) {
+                        Some(member) => self.apply_member(member, a, b, start_depth)?,
+                        None => {
                             return Err(VmError::Runtime(format!(
                                 "Type mismatch in subtraction: {} - {}",
                                 a.type_name(),
                                 b.type_name()
                             )))
                         }
-                    }
+                    },
                 }
+            }
 
-                Instruction::Mul => {
-                    let b = self.pop_unchecked();
-                    let a = self.pop_unchecked();
-                    match (a, b) {
-                        (Value::Int(a), Value::Int(b)) => self.push(Value::Int(a * b)),
-                        (Value::Float(a), Value::Float(b)) => self.push(Value::Float(a * b)),
-                        (a, b) => {
+            Instruction::Mul => {
+                let b = self.pop_unchecked();
+                let a = self.pop_unchecked();
+                match (a, b) {
+                    (Value::Int(a), Value::Int(b)) => self.push(Value::Int(a * b)),
+                    (Value::Float(a), Value::Float(b)) => self.push(Value::Float(a * b)),
+                    (a, b) => match self.operator_member("op_Multiply", &a, &b) {
+                        Some(member) => self.apply_member(member, a, b, start_depth)?,
+                        None => {
                             return Err(VmError::Runtime(format!(
                                 "Type mismatch in multiplication: {} * {}",
                                 a.type_name(),
                                 b.type_name()
                             )))
                         }
-                    }
+                    },
                 }
+            }
 
-                Instruction::Div => {
-                    let b = self.pop_unchecked();
-                    let a = self.pop_unchecked();
-                    match (a, b) {
-                        (Value::Int(a), Value::Int(b)) => {
-                            if b == 0 {
-                                return Err(VmError::DivisionByZero);
-                            }
-                            self.push(Value::Int(a / b))
+            Instruction::Div => {
+                let b = self.pop_unchecked();
+                let a = self.pop_unchecked();
+                match (a, b) {
+                    (Value::Int(a), Value::Int(b)) => {
+                        if b == 0 {
+                            return Err(VmError::DivisionByZero);
                         }
-                        (Value::Float(a), Value::Float(b)) => {
-                            if b == 0.0 {
-                                return Err(VmError::DivisionByZero);
-                            }
-                            self.push(Value::Float(a / b))
+                        self.push(Value::Int(a / b))
+                    }
+                    (Value::Float(a), Value::Float(b)) => {
+                        if b == 0.0 {
+                            return Err(VmError::DivisionByZero);
                         }
-                        (a, b) => {
+                        self.push(Value::Float(a / b))
+                    }
+                    (a, b) => match self.operator_member("op_Division", &a, &b) {
+                        Some(member) => self.apply_member(member, a, b, start_depth)?,
+                        None => {
                             return Err(VmError::Runtime(format!(
                                 "Type mismatch in division: {} / {}",
                                 a.type_name(),
                                 b.type_name()
                             )))
                         }
-                    }
+                    },
                 }
+            }
 
-                Instruction::Concat => {
-                    let b = self.pop()?;
-                    let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Str(a), Value::Str(b)) => {
-                            let mut result = a.clone();
-                            result.push_str(&b);
-                            self.push(Value::Str(result))
+            Instruction::Mod => {
+                let b = self.pop_unchecked();
+                let a = self.pop_unchecked();
+                match (a, b) {
+                    (Value::Int(a), Value::Int(b)) => {
+                        if b == 0 {
+                            return Err(VmError::DivisionByZero);
                         }
-                        (a, b) => {
+                        self.push(Value::Int(a.wrapping_rem(b)))
+                    }
+                    (Value::Float(a), Value::Float(b)) => {
+                        if b == 0.0 {
+                            return Err(VmError::DivisionByZero);
+                        }
+                        self.push(Value::Float(a % b))
+                    }
+                    (a, b) => match self.operator_member("op_Modulus", &a, &b) {
+                        Some(member) => self.apply_member(member, a, b, start_depth)?,
+                        None => {
                             return Err(VmError::Runtime(format!(
-                                "Type mismatch in concatenation: {} ++ {}",
+                                "Type mismatch in remainder: {} % {}",
                                 a.type_name(),
                                 b.type_name()
                             )))
                         }
-                    }
+                    },
                 }
+            }
 
-                // Comparison operations
-                Instruction::Eq => {
-                    let b = self.pop()?;
-                    let a = self.pop()?;
-                    self.push(Value::Bool(a == b));
+            Instruction::Concat => {
+                let b = self.pop()?;
+                let a = self.pop()?;
+                match (a, b) {
+                    (Value::Str(a), Value::Str(b)) => {
+                        self.note_allocation(a.len() + b.len());
+                        let mut result = a.clone();
+                        result.push_str(&b);
+                        self.push(Value::Str(result))
+                    }
+                    (a, b) => {
+                        return Err(VmError::Runtime(format!(
+                            "Type mismatch in concatenation: {} ++ {}",
+                            a.type_name(),
+                            b.type_name()
+                        )))
+                    }
                 }
+            }
 
-                Instruction::Neq => {
-                    let b = self.pop()?;
-                    let a = self.pop()?;
-                    self.push(Value::Bool(a != b));
-                }
+            // Comparison operations
+            Instruction::Eq => {
+                let b = self.pop()?;
+                let a = self.pop()?;
+                self.push(Value::Bool(a == b));
+            }
 
-                Instruction::Lt => {
-                    let b = self.pop()?;
-                    let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Int(a), Value::Int(b)) => self.push(Value::Bool(a < b)),
-                        (Value::Float(a), Value::Float(b)) => self.push(Value::Bool(a < b)),
-                        (a, b) => {
+            Instruction::Neq => {
+                let b = self.pop()?;
+                let a = self.pop()?;
+                self.push(Value::Bool(a != b));
+            }
+
+            Instruction::Lt => {
+                let b = self.pop()?;
+                let a = self.pop()?;
+                match (a, b) {
+                    (Value::Int(a), Value::Int(b)) => self.push(Value::Bool(a < b)),
+                    (Value::Float(a), Value::Float(b)) => self.push(Value::Bool(a < b)),
+                    (Value::Char(a), Value::Char(b)) => self.push(Value::Bool(a < b)),
+                    (a, b) => match self.operator_member("Compare", &a, &b) {
+                        Some(member) => {
+                            if let Some(ordering) =
+                                self.compare_with_member(member, a, b, start_depth)?
+                            {
+                                self.push(Value::Bool(ordering.is_lt()));
+                            }
+                        }
+                        None => {
                             return Err(VmError::Runtime(format!(
                                 "Type mismatch in comparison: {} < {}",
                                 a.type_name(),
                                 b.type_name()
                             )))
                         }
-                    }
+                    },
                 }
+            }
 
-                Instruction::Lte => {
-                    let b = self.pop()?;
-                    let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Int(a), Value::Int(b)) => self.push(Value::Bool(a <= b)),
-                        (Value::Float(a), Value::Float(b)) => self.push(Value::Bool(a <= b)),
-                        (a, b) => {
+            Instruction::Lte => {
+                let b = self.pop()?;
+                let a = self.pop()?;
+                match (a, b) {
+                    (Value::Int(a), Value::Int(b)) => self.push(Value::Bool(a <= b)),
+                    (Value::Float(a), Value::Float(b)) => self.push(Value::Bool(a <= b)),
+                    (Value::Char(a), Value::Char(b)) => self.push(Value::Bool(a <= b)),
+                    (a, b) => match self.operator_member("Compare", &a, &b) {
+                        Some(member) => {
+                            if let Some(ordering) =
+                                self.compare_with_member(member, a, b, start_depth)?
+                            {
+                                self.push(Value::Bool(ordering.is_le()));
+                            }
+                        }
+                        None => {
                             return Err(VmError::Runtime(format!(
                                 "Type mismatch in comparison: {} <= {}",
                                 a.type_name(),
                                 b.type_name()
                             )))
                         }
-                    }
+                    },
                 }
+            }
 
-                Instruction::Gt => {
-                    let b = self.pop()?;
-                    let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Int(a), Value::Int(b)) => self.push(Value::Bool(a > b)),
-                        (Value::Float(a), Value::Float(b)) => self.push(Value::Bool(a > b)),
-                        (a, b) => {
+            Instruction::Gt => {
+                let b = self.pop()?;
+                let a = self.pop()?;
+                match (a, b) {
+                    (Value::Int(a), Value::Int(b)) => self.push(Value::Bool(a > b)),
+                    (Value::Float(a), Value::Float(b)) => self.push(Value::Bool(a > b)),
+                    (Value::Char(a), Value::Char(b)) => self.push(Value::Bool(a > b)),
+                    (a, b) => match self.operator_member("Compare", &a, &b) {
+                        Some(member) => {
+                            if let Some(ordering) =
+                                self.compare_with_member(member, a, b, start_depth)?
+                            {
+                                self.push(Value::Bool(ordering.is_gt()));
+                            }
+                        }
+                        None => {
                             return Err(VmError::Runtime(format!(
                                 "Type mismatch in comparison: {} > {}",
                                 a.type_name(),
                                 b.type_name()
                             )))
                         }
-                    }
+                    },
                 }
+            }
 
-                Instruction::Gte => {
-                    let b = self.pop()?;
-                    let a = self.pop()?;
-                    match (a, b) {
-                        (Value::Int(a), Value::Int(b)) => self.push(Value::Bool(a >= b)),
-                        (Value::Float(a), Value::Float(b)) => self.push(Value::Bool(a >= b)),
-                        (a, b) => {
+            Instruction::Gte => {
+                let b = self.pop()?;
+                let a = self.pop()?;
+                match (a, b) {
+                    (Value::Int(a), Value::Int(b)) => self.push(Value::Bool(a >= b)),
+                    (Value::Float(a), Value::Float(b)) => self.push(Value::Bool(a >= b)),
+                    (Value::Char(a), Value::Char(b)) => self.push(Value::Bool(a >= b)),
+                    (a, b) => match self.operator_member("Compare", &a, &b) {
+                        Some(member) => {
+                            if let Some(ordering) =
+                                self.compare_with_member(member, a, b, start_depth)?
+                            {
+                                self.push(Value::Bool(ordering.is_ge()));
+                            }
+                        }
+                        None => {
                             return Err(VmError::Runtime(format!(
                                 "Type mismatch in comparison: {} >= {}",
                                 a.type_name(),
                                 b.type_name()
                             )))
                         }
-                    }
+                    },
                 }
+            }
 
-                // Logical operations
-                Instruction::And => {
-                    let b = self.pop_bool()?;
-                    let a = self.pop_bool()?;
-                    self.push(Value::Bool(a && b));
-                }
+            // Logical operations
+            Instruction::And => {
+                let b = self.pop_bool()?;
+                let a = self.pop_bool()?;
+                self.push(Value::Bool(a && b));
+            }
 
-                Instruction::Or => {
-                    let b = self.pop_bool()?;
-                    let a = self.pop_bool()?;
-                    self.push(Value::Bool(a || b));
-                }
+            Instruction::Or => {
+                let b = self.pop_bool()?;
+                let a = self.pop_bool()?;
+                self.push(Value::Bool(a || b));
+            }
 
-                Instruction::Not => {
-                    let a = self.pop_bool()?;
-                    self.push(Value::Bool(!a));
-                }
+            Instruction::Not => {
+                let a = self.pop_bool()?;
+                self.push(Value::Bool(!a));
+            }
+
+            // Bitwise operations
+            Instruction::BitAnd
+            | Instruction::BitOr
+            | Instruction::BitXor
+            | Instruction::Shl
+            | Instruction::Shr => {
+                let b = self.pop()?;
+                let a = self.pop()?;
+                self.push(bitwise_op(&instruction, a, b)?);
+            }
+
+            // Control flow
+            Instruction::Jump(offset) => {
+                self.jump(offset)?;
+            }
 
-                // Control flow
-                Instruction::Jump(offset) => {
+            Instruction::JumpIfFalse(offset) => {
+                let condition = self.pop()?;
+                if !condition.is_truthy() {
                     self.jump(offset)?;
                 }
+            }
 
-                Instruction::JumpIfFalse(offset) => {
-                    let condition = self.pop()?;
-                    if !condition.is_truthy() {
-                        self.jump(offset)?;
-                    }
-                }
+            // Exceptions
+            Instruction::PushHandler(offset) => {
+                let ip = self.current_frame()?.ip;
+                self.handlers.push(Handler {
+                    frame_depth: self.frames.len(),
+                    stack_height: self.stack.len(),
+                    catch_ip: ip.wrapping_add_signed(offset as isize),
+                });
+            }
 
-                // Tuple operations
-                Instruction::MakeTuple(n) => {
-                    // Pop N values from stack in reverse order (last pushed is last in tuple)
-                    let mut elements = Vec::with_capacity(n as usize);
-                    for _ in 0..n {
-                        elements.push(self.pop()?);
-                    }
-                    // Reverse to maintain left-to-right order
-                    elements.reverse();
-                    self.push(Value::Tuple(elements));
+            Instruction::PopHandler => {
+                self.handlers.pop().ok_or_else(|| {
+                    VmError::Runtime("PopHandler without an active handler".into())
+                })?;
+            }
+
+            Instruction::Raise => {
+                let exn = self.pop()?;
+                self.throw(exn, start_depth)?;
+            }
+
+            // Tuple operations
+            Instruction::MakeTuple(n) => {
+                // Pop N values from stack in reverse order (last pushed is last in tuple)
+                let mut elements = Vec::with_capacity(n as usize);
+                for _ in 0..n {
+                    elements.push(self.pop()?);
                 }
+                // Reverse to maintain left-to-right order
+                elements.reverse();
+                self.note_allocation(estimate_shallow_bytes(n));
+                self.push(Value::Tuple(elements));
+            }
 
-                Instruction::GetTupleField(idx) => {
-                    let value = self.pop()?;
-                    match value.as_tuple() {
-                        Some(elements) => {
-                            let index = idx as usize;
-                            if index >= elements.len() {
-                                return Err(VmError::InvalidTupleFieldIndex {
-                                    index: idx,
-                                    tuple_size: elements.len(),
-                                });
-                            }
-                            self.push(elements[index].clone());
-                        }
-                        None => {
-                            return Err(VmError::TypeMismatch {
-                                expected: "tuple",
-                                got: value.type_name(),
+            Instruction::GetTupleField(idx) => {
+                let value = self.pop()?;
+                match value.as_tuple() {
+                    Some(elements) => {
+                        let index = idx as usize;
+                        if index >= elements.len() {
+                            return Err(VmError::InvalidTupleFieldIndex {
+                                index: idx,
+                                tuple_size: elements.len(),
                             });
                         }
+                        self.push(elements[index].clone());
+                    }
+                    None => {
+                        return Err(VmError::TypeMismatch {
+                            expected: "tuple",
+                            got: value.type_name(),
+                        });
                     }
                 }
+            }
 
-                Instruction::MakeClosure(idx, upvalue_count) => {
-                    let constant = self.current_frame()?.get_constant(idx)?;
-                    let prototype = match constant.as_closure() {
-                        Some(c) => c,
-                        None => {
-                            return Err(VmError::TypeMismatch {
-                                expected: "closure",
-                                got: constant.type_name(),
-                            })
+            Instruction::MakeClosure(idx, upvalue_count) => {
+                let constant = self.current_frame()?.get_constant(idx)?;
+                let prototype = match constant.as_closure() {
+                    Some(c) => c,
+                    None => {
+                        return Err(VmError::TypeMismatch {
+                            expected: "closure",
+                            got: constant.type_name(),
+                        })
+                    }
+                };
+
+                let sources = &prototype.chunk.upvalue_sources;
+                if sources.len() != upvalue_count as usize {
+                    return Err(VmError::Runtime(format!(
+                        "Upvalue count mismatch: expected {}, got {}",
+                        sources.len(),
+                        upvalue_count
+                    )));
+                }
+
+                let mut closure = Closure::new(prototype.chunk.clone());
+                closure.arity = prototype.arity;
+                closure.name = prototype.name.clone();
+
+                for source in sources {
+                    let upvalue = match *source {
+                        UpvalueSource::Local(slot) => {
+                            let base = self.current_frame()?.base;
+                            self.capture_upvalue(base + slot as usize)
                         }
+                        UpvalueSource::Upvalue(idx) => self
+                            .current_frame()?
+                            .closure
+                            .get_upvalue(idx as usize)
+                            .ok_or(VmError::Runtime(format!("Invalid upvalue index: {}", idx)))?,
                     };
+                    closure.add_upvalue(upvalue);
+                }
 
-                    let mut closure = Closure::new(prototype.chunk.clone());
-                    closure.arity = prototype.arity;
-                    closure.name = prototype.name.clone();
+                self.push(Value::Closure(Arc::new(closure)));
+            }
 
-                    // Pop upvalues (placeholder logic for now)
-                    for _ in 0..upvalue_count {
-                        self.pop()?;
+            Instruction::Call(argc) | Instruction::TailCall(argc) => {
+                let func_idx = self
+                    .stack
+                    .len()
+                    .checked_sub(1 + argc as usize)
+                    .ok_or(VmError::StackUnderflow)?;
+                let func = self.stack[func_idx].clone();
+
+                match func {
+                    Value::Closure(closure) => {
+                        if closure.arity != argc {
+                            return Err(VmError::Runtime(format!(
+                                "Arity mismatch: expected {}, got {}",
+                                closure.arity, argc
+                            )));
+                        }
+
+                        if matches!(instruction, Instruction::TailCall(_)) {
+                            // Reuse the caller's frame: its slots and temporaries
+                            // are replaced by the arguments
+                            let frame = self.frames.pop().ok_or(VmError::NoActiveFrame)?;
+                            self.close_upvalues(frame.base);
+                            self.stack.drain(frame.base..=func_idx);
+                            self.push_frame(closure, frame.base)?;
+                        } else {
+                            // Drop the callee so locals start at the first argument
+                            self.stack.remove(func_idx);
+                            self.push_frame(closure, func_idx)?;
+                        }
                     }
+                    Value::NativeFn {
+                        name,
+                        arity,
+                        args: applied_args,
+                    } => {
+                        // Pop new arguments from stack
+                        let mut new_args = Vec::with_capacity(argc as usize);
+                        for _ in 0..argc {
+                            new_args.push(self.pop()?);
+                        }
+                        new_args.reverse(); // Arguments are pushed left-to-right, so stack has last arg on top.
+                        self.pop()?; // The callee itself
 
-                    self.push(Value::Closure(Arc::new(closure)));
-                }
+                        // Combine with already applied arguments
+                        let mut all_args = applied_args.clone();
+                        all_args.extend(new_args);
 
-                Instruction::Call(argc) => {
-                    let func_idx = self
-                        .stack
-                        .len()
-                        .checked_sub(1 + argc as usize)
-                        .ok_or(VmError::StackUnderflow)?;
-                    let func = self.stack[func_idx].clone();
-
-                    match func {
-                        Value::Closure(closure) => {
-                            if closure.arity != argc {
-                                return Err(VmError::Runtime(format!(
-                                    "Arity mismatch: expected {}, got {}",
-                                    closure.arity, argc
-                                )));
-                            }
+                        let total_args = all_args.len();
+                        let arity_usize = arity as usize;
 
-                            // Locals start at first argument
-                            let base = func_idx + 1;
-                            let frame = Frame::new(closure.clone(), base);
-                            self.frames.push(frame);
-                        }
-                        Value::NativeFn {
-                            name,
-                            arity,
-                            args: applied_args,
-                        } => {
-                            // Pop new arguments from stack
-                            let mut new_args = Vec::with_capacity(argc as usize);
-                            for _ in 0..argc {
-                                new_args.push(self.pop()?);
-                            }
-                            new_args.reverse(); // Arguments are pushed left-to-right, so stack has last arg on top.
-
-                            // Combine with already applied arguments
-                            let mut all_args = applied_args.clone();
-                            all_args.extend(new_args);
-
-                            let total_args = all_args.len();
-                            let arity_usize = arity as usize;
-
-                            // Special case: arity 0 means dynamic arity (accepts any number of args)
-                            // This is used for host functions registered without specific arity info
-                            if arity == 0 || total_args == arity_usize {
-                                // Execute the function
-                                let host_fn = {
-                                    let registry = self.host_registry.lock().unwrap();
-                                    registry.get(&name)
-                                }; // Drop borrow
-
-                                if let Some(f) = host_fn {
-                                    let result = f(self, &all_args)?;
-                                    self.push(result);
-                                } else {
-                                    return Err(VmError::Runtime(format!(
-                                        "Undefined host function: {}",
-                                        name
-                                    )));
+                        // Special case: arity 0 means dynamic arity (accepts any number of args)
+                        // This is used for host functions registered without specific arity info
+                        if arity == 0 || total_args == arity_usize {
+                            // Execute the function
+                            let host_fn = {
+                                let registry = self.host_registry.lock().unwrap();
+                                registry.get(&name)
+                            }; // Drop borrow
+
+                            if let Some(f) = host_fn {
+                                match f(self, &all_args) {
+                                    Ok(result) => self.push(result),
+                                    Err(error) => self.throw_error(error, start_depth)?,
                                 }
-                            } else if total_args < arity_usize {
-                                // Partial application: return new NativeFn with accumulated args
-                                self.push(Value::NativeFn {
-                                    name: name.clone(),
-                                    arity,
-                                    args: all_args,
-                                });
                             } else {
-                                // Over-application: execute with first 'arity' args, then call result with rest
-                                // For Phase 1/2/3, let's just error or handle simple case
-                                // To handle this properly, we'd need to recurse or push result and call again.
-                                // Simpler: Error for now.
                                 return Err(VmError::Runtime(format!(
-                                    "Native function '{}' expects {} arguments, got {}",
-                                    name, arity, total_args
+                                    "Undefined host function: {}",
+                                    name
                                 )));
                             }
-                        }
-                        _ => {
-                            return Err(VmError::TypeMismatch {
-                                expected: "function",
-                                got: func.type_name(),
-                            })
+                        } else if total_args < arity_usize {
+                            // Partial application: return new NativeFn with accumulated args
+                            self.push(Value::NativeFn {
+                                name: name.clone(),
+                                arity,
+                                args: all_args,
+                            });
+                        } else {
+                            // Over-application: execute with first 'arity' args, then call result with rest
+                            // For Phase 1/2/3, let's just error or handle simple case
+                            // To handle this properly, we'd need to recurse or push result and call again.
+                            // Simpler: Error for now.
+                            return Err(VmError::Runtime(format!(
+                                "Native function '{}' expects {} arguments, got {}",
+                                name, arity, total_args
+                            )));
                         }
                     }
+                    _ => {
+                        return Err(VmError::TypeMismatch {
+                            expected: "function",
+                            got: func.type_name(),
+                        })
+                    }
                 }
+            }
 
-                Instruction::CallMethod(method_name_idx, argc) => {
-                    // Get method name from constants
-                    let method_name_val = self.current_frame()?.get_constant(method_name_idx)?;
-                    let method_name = match method_name_val {
-                        Value::Str(s) => s,
-                        _ => {
-                            return Err(VmError::TypeMismatch {
-                                expected: "string (method name)",
-                                got: method_name_val.type_name(),
-                            })
-                        }
-                    };
+            Instruction::CallMethod(method_name_idx, argc) => {
+                // Get method name from constants
+                let method_name_val = self.current_frame()?.get_constant(method_name_idx)?;
+                let method_name = match method_name_val {
+                    Value::Str(s) => s,
+                    _ => {
+                        return Err(VmError::TypeMismatch {
+                            expected: "string (method name)",
+                            got: method_name_val.type_name(),
+                        })
+                    }
+                };
+
+                // Get receiver - it's at position [stack.len() - argc - 1]
+                let receiver_idx = self
+                    .stack
+                    .len()
+                    .checked_sub(1 + argc as usize)
+                    .ok_or(VmError::StackUnderflow)?;
+                let receiver = &self.stack[receiver_idx];
+
+                // Methods declared by a script type: `shape.Area()`
+                if let Some(member) = self.instance_member(receiver, &method_name) {
+                    let mut args = self.stack.split_off(receiver_idx);
+                    let receiver = args.remove(0);
+                    if args.is_empty() {
+                        args.push(Value::Unit);
+                    }
+                    self.call_instance_member(member, receiver, args, start_depth)?;
+                    return Ok(None);
+                }
 
-                    // Get receiver - it's at position [stack.len() - argc - 1]
-                    let receiver_idx = self
-                        .stack
-                        .len()
-                        .checked_sub(1 + argc as usize)
-                        .ok_or(VmError::StackUnderflow)?;
-                    let receiver = &self.stack[receiver_idx];
-
-                    // Check receiver type and dispatch accordingly
-                    match receiver {
-                        Value::HostData(host_data) => {
-                            let type_id = host_data.type_id();
-
-                            // Collect all arguments including receiver
-                            let mut args = Vec::with_capacity(1 + argc as usize);
-                            args.push(receiver.clone()); // receiver is first arg
-
-                            // Pop method arguments from stack (in reverse order)
-                            for _ in 0..argc {
-                                args.push(self.pop()?);
-                            }
-                            // Reverse args (except receiver) to get correct order
-                            args[1..].reverse();
+                // Check receiver type and dispatch accordingly
+                match receiver {
+                    Value::HostData(host_data) => {
+                        let type_id = host_data.type_id();
 
-                            // Pop the receiver from stack
-                            self.pop()?;
+                        // Collect all arguments including receiver
+                        let mut args = Vec::with_capacity(1 + argc as usize);
+                        args.push(receiver.clone()); // receiver is first arg
 
-                            // Get the method function from registry
-                            let method_fn = {
-                                let registry = self.host_registry.lock().unwrap();
-                                registry.get_method(type_id, &method_name).ok_or_else(|| {
-                                    VmError::Runtime(format!("Method not found: {}", method_name))
-                                })?
-                            };
+                        // Pop method arguments from stack (in reverse order)
+                        for _ in 0..argc {
+                            args.push(self.pop()?);
+                        }
+                        // Reverse args (except receiver) to get correct order
+                        args[1..].reverse();
 
-                            // Now call the method with mutable VM reference
-                            let result = method_fn(self, &args)?;
+                        // Pop the receiver from stack
+                        self.pop()?;
 
-                            self.push(result);
+                        // Get the method function from registry
+                        let method_fn = {
+                            let registry = self.host_registry.lock().unwrap();
+                            registry.get_method(type_id, &method_name).ok_or_else(|| {
+                                VmError::Runtime(format!("Method not found: {}", method_name))
+                            })?
+                        };
+
+                        // Now call the method with mutable VM reference
+                        match method_fn(self, &args) {
+                            Ok(result) => self.push(result),
+                            Err(error) => self.throw_error(error, start_depth)?,
                         }
-                        Value::Record(_) => {
-                            // For records, get the field (which should be a closure/function)
-                            // and call it with the arguments
-                            let record = receiver.clone();
-
-                            // Get the method/field from the record
-                            let method_value = record.record_get(&method_name).map_err(|e| {
-                                VmError::Runtime(format!(
-                                    "Method not found on record: {} ({})",
-                                    method_name, e
-                                ))
-                            })?;
-
-                            // Pop arguments from stack (in reverse order)
-                            let mut args = Vec::with_capacity(argc as usize);
-                            for _ in 0..argc {
-                                args.push(self.pop()?);
-                            }
-                            args.reverse();
+                    }
+                    Value::Record(_) => {
+                        // For records, get the field (which should be a closure/function)
+                        // and call it with the arguments
+                        let record = receiver.clone();
+
+                        // Get the method/field from the record
+                        let method_value = record.record_get(&method_name).map_err(|e| {
+                            VmError::Runtime(format!(
+                                "Method not found on record: {} ({})",
+                                method_name, e
+                            ))
+                        })?;
 
-                            // Pop the receiver from stack
-                            self.pop()?;
+                        // Pop arguments from stack (in reverse order)
+                        let mut args = Vec::with_capacity(argc as usize);
+                        for _ in 0..argc {
+                            args.push(self.pop()?);
+                        }
+                        args.reverse();
 
-                            // Now call the method value based on its type
-                            match method_value {
-                                Value::Closure(closure) => {
-                                    // Push arguments onto stack for the call
-                                    for arg in args {
-                                        self.push(arg);
-                                    }
+                        // Pop the receiver from stack
+                        self.pop()?;
 
-                                    // Create new frame for closure
-                                    let base = self.stack.len() - closure.arity as usize;
-                                    let frame = Frame::new(closure, base);
+                        // Now call the method value based on its type
+                        match method_value {
+                            Value::Closure(closure) if closure.arity as usize == args.len() => {
+                                // Push arguments onto stack for the call
+                                for arg in args {
+                                    self.push(arg);
+                                }
 
-                                    if self.frames.len() >= 1000 {
-                                        return Err(VmError::CallStackOverflow);
-                                    }
-                                    self.frames.push(frame);
+                                // Create new frame for closure
+                                let base = self.stack.len() - closure.arity as usize;
+                                self.push_frame(closure, base)?;
+                            }
+                            Value::Closure(_) => {
+                                // A curried script function such as a builder's
+                                // `Bind = fun m f -> ...` takes one argument at a time
+                                match self.call_curried(method_value, &args) {
+                                    Ok(result) => self.push(result),
+                                    Err(error) => self.throw_error(error, start_depth)?,
                                 }
-                                Value::NativeFn {
-                                    name,
-                                    arity,
-                                    args: partial_args,
-                                } => {
-                                    // Combine partial args with new args
-                                    let mut all_args = partial_args.clone();
-                                    all_args.extend(args);
-
-                                    // Check if we have enough args
-                                    if all_args.len() < arity as usize {
-                                        // Partial application - return a new NativeFn with more args
-                                        self.push(Value::NativeFn {
-                                            name: name.clone(),
-                                            arity,
-                                            args: all_args,
-                                        });
-                                    } else {
-                                        // Full application - call the host function
-                                        let host_fn = {
-                                            let registry = self.host_registry.lock().unwrap();
-                                            registry.get(&name).ok_or_else(|| {
-                                                VmError::Runtime(format!(
-                                                    "Host function not found: {}",
-                                                    name
-                                                ))
-                                            })?
-                                        };
-                                        let result = host_fn(self, &all_args)?;
-                                        self.push(result);
+                            }
+                            Value::NativeFn {
+                                name,
+                                arity,
+                                args: partial_args,
+                            } => {
+                                // Combine partial args with new args
+                                let mut all_args = partial_args.clone();
+                                all_args.extend(args);
+
+                                // Check if we have enough args
+                                if all_args.len() < arity as usize {
+                                    // Partial application - return a new NativeFn with more args
+                                    self.push(Value::NativeFn {
+                                        name: name.clone(),
+                                        arity,
+                                        args: all_args,
+                                    });
+                                } else {
+                                    // Full application - call the host function
+                                    let host_fn = {
+                                        let registry = self.host_registry.lock().unwrap();
+                                        registry.get(&name).ok_or_else(|| {
+                                            VmError::Runtime(format!(
+                                                "Host function not found: {}",
+                                                name
+                                            ))
+                                        })?
+                                    };
+                                    match host_fn(self, &all_args) {
+                                        Ok(result) => self.push(result),
+                                        Err(error) => self.throw_error(error, start_depth)?,
                                     }
                                 }
-                                _ => {
-                                    return Err(VmError::Runtime(format!(
-                                        "Record field '{}' is not callable: {}",
-                                        method_name,
-                                        method_value.type_name()
-                                    )))
-                                }
+                            }
+                            _ => {
+                                return Err(VmError::Runtime(format!(
+                                    "Record field '{}' is not callable: {}",
+                                    method_name,
+                                    method_value.type_name()
+                                )))
                             }
                         }
-                        _ => {
-                            return Err(VmError::Runtime(format!(
-                                "Method dispatch not supported for type: {}",
-                                receiver.type_name()
-                            )))
-                        }
+                    }
+                    _ => {
+                        return Err(VmError::Runtime(format!(
+                            "Method dispatch not supported for type: {}",
+                            receiver.type_name()
+                        )))
                     }
                 }
+            }
 
-                Instruction::CloseUpvalue(_) => {
-                    // Placeholder
-                }
-
-                Instruction::Return => {
-                    let returned_value = self.pop().unwrap_or(Value::Unit);
+            Instruction::CloseUpvalue(slot) => {
+                let base = self.current_frame()?.base;
+                self.close_upvalues(base + slot as usize);
+            }
 
-                    // Pop the frame
-                    self.frames.pop();
+            Instruction::Return => {
+                let returned_value = self.pop().unwrap_or(Value::Unit);
 
-                    // If we've dropped below the starting depth, we're done with this run() call
-                    if self.frames.len() < start_depth {
-                        return Ok(returned_value);
-                    }
-                    self.push(returned_value); // Push back if not final return
+                // Pop the frame and discard its arguments, locals and temporaries
+                if let Some(frame) = self.frames.pop() {
+                    self.close_upvalues(frame.base);
+                    self.stack.truncate(frame.base);
                 }
 
-                // List operations
-                Instruction::MakeList(n) => {
-                    // Pop N values from stack in reverse order
-                    let mut elements = Vec::with_capacity(n as usize);
-                    for _ in 0..n {
-                        elements.push(self.pop()?);
-                    }
-                    // Reverse to maintain left-to-right order
-                    elements.reverse();
-                    // Build cons list from elements
-                    let list = Value::vec_to_cons(elements);
-                    self.push(list);
+                // If we've dropped below the starting depth, we're done with this run() call
+                if self.frames.len() < start_depth {
+                    return Ok(Some(returned_value));
                 }
+                self.push(returned_value); // Push back if not final return
+            }
 
-                Instruction::Cons => {
-                    let tail = self.pop()?;
-                    let head = self.pop()?;
-                    self.push(Value::Cons {
-                        head: Box::new(head),
-                        tail: Box::new(tail),
-                    });
+            // List operations
+            Instruction::MakeList(n) => {
+                // Pop N values from stack in reverse order
+                let mut elements = Vec::with_capacity(n as usize);
+                for _ in 0..n {
+                    elements.push(self.pop()?);
                 }
+                // Reverse to maintain left-to-right order
+                elements.reverse();
+                // Build cons list from elements
+                self.note_allocation(n as usize * LIST_CELL_BYTES);
+                let list = Value::vec_to_cons(elements);
+                self.push(list);
+            }
 
-                Instruction::ListHead => {
-                    let value = self.pop()?;
-                    match value {
-                        Value::Cons { head, .. } => self.push(*head),
-                        Value::Nil => return Err(VmError::EmptyList),
-                        _ => {
-                            return Err(VmError::TypeMismatch {
-                                expected: "list",
-                                got: value.type_name(),
-                            })
-                        }
+            Instruction::Cons => {
+                let tail = self.pop()?;
+                let head = self.pop()?;
+                self.note_allocation(LIST_CELL_BYTES);
+                self.push(Value::Cons {
+                    head: Box::new(head),
+                    tail: Box::new(tail),
+                });
+            }
+
+            Instruction::ListHead => {
+                let value = self.pop()?;
+                match value {
+                    Value::Cons { head, .. } => self.push(*head),
+                    Value::Nil => return Err(VmError::EmptyList),
+                    _ => {
+                        return Err(VmError::TypeMismatch {
+                            expected: "list",
+                            got: value.type_name(),
+                        })
                     }
                 }
+            }
 
-                Instruction::ListTail => {
-                    let value = self.pop()?;
-                    match value {
-                        Value::Cons { tail, .. } => self.push(*tail),
-                        Value::Nil => return Err(VmError::EmptyList),
-                        _ => {
-                            return Err(VmError::TypeMismatch {
-                                expected: "list",
-                                got: value.type_name(),
-                            })
-                        }
+            Instruction::ListTail => {
+                let value = self.pop()?;
+                match value {
+                    Value::Cons { tail, .. } => self.push(*tail),
+                    Value::Nil => return Err(VmError::EmptyList),
+                    _ => {
+                        return Err(VmError::TypeMismatch {
+                            expected: "list",
+                            got: value.type_name(),
+                        })
                     }
                 }
+            }
 
-                Instruction::IsNil => {
-                    let value = self.pop()?;
-                    self.push(Value::Bool(value.is_nil()));
-                }
+            Instruction::IsNil => {
+                let value = self.pop()?;
+                self.push(Value::Bool(value.is_nil()));
+            }
 
-                // Array operations
-                Instruction::MakeArray(n) => {
-                    // Pop N values from stack in reverse order
-                    let mut elements = Vec::with_capacity(n as usize);
-                    for _ in 0..n {
-                        elements.push(self.pop()?);
+            Instruction::ToList => {
+                let value = self.pop()?;
+                let list = match value {
+                    // Sequences run to the end here
+                    Value::Seq(seq) => {
+                        Value::vec_to_cons(crate::stdlib::seq::seq_to_vec(self, &seq)?)
+                    }
+                    _ => collection_to_list(value)?,
+                };
+                self.push(list);
+            }
+
+            Instruction::GetEnumerator => {
+                let value = self.pop()?;
+                let cursor = match value {
+                    // Sequences produce each element as the loop asks for it
+                    Value::Seq(seq) => {
+                        Value::HostData(HostData::new(SeqEnumerator::new(&seq)?, "SeqEnumerator"))
+                    }
+                    _ => collection_to_list(value)?,
+                };
+                self.push(cursor);
+            }
+
+            Instruction::MoveNext(idx) => {
+                let slot = self.current_frame()?.base + idx as usize;
+                let cursor = self
+                    .stack
+                    .get_mut(slot)
+                    .map(|cursor| std::mem::replace(cursor, Value::Nil))
+                    .ok_or(VmError::InvalidLocalIndex(idx))?;
+                let element = match cursor {
+                    // Lists are advanced in place rather than copied each step
+                    Value::Cons { head, tail } => {
+                        self.stack[slot] = *tail;
+                        Some(*head)
+                    }
+                    Value::HostData(data) => {
+                        self.stack[slot] = Value::HostData(data.clone());
+                        let mut enumerator = data.try_borrow_mut::<SeqEnumerator>().ok_or(
+                            VmError::TypeMismatch {
+                                expected: "enumerator",
+                                got: "host data",
+                            },
+                        )?;
+                        enumerator.next(self)?
+                    }
+                    _ => None,
+                };
+                match element {
+                    Some(element) => {
+                        self.push(element);
+                        self.push(Value::Bool(true));
                     }
-                    // Reverse to maintain left-to-right order
-                    elements.reverse();
-                    // Build array from elements
-                    use std::sync::Arc;
-                    use std::sync::Mutex;
-                    let array = Value::Array(Arc::new(Mutex::new(elements)));
-                    self.push(array);
+                    None => self.push(Value::Bool(false)),
                 }
+            }
 
-                Instruction::ArrayGet => {
-                    let index = self.pop()?;
-                    let array = self.pop()?;
-
-                    let idx = match index {
-                        Value::Int(i) => {
-                            if i < 0 {
-                                return Err(VmError::TypeMismatch {
-                                    expected: "non-negative int",
-                                    got: "negative int",
-                                });
-                            }
-                            i as usize
-                        }
-                        _ => {
-                            return Err(VmError::TypeMismatch {
-                                expected: "int",
-                                got: index.type_name(),
-                            })
-                        }
-                    };
+            Instruction::ListReverse => {
+                let list = self.pop()?;
+                self.push(reverse_list(list)?);
+            }
 
-                    let value = array.array_get(idx).map_err(VmError::Runtime)?;
+            Instruction::MakeRange => {
+                let finish = self.pop()?;
+                let step = self.pop()?;
+                let start = self.pop()?;
+                let len = range_len(&start, &step, &finish);
+                self.reserve_heap(len.saturating_mul(LIST_CELL_BYTES))?;
+                let range = make_range(start, step, finish, &mut || self.tick(false).map(drop))?;
+                self.push(range);
+            }
 
-                    self.push(value);
+            // Array operations
+            Instruction::MakeArray(n) => {
+                // Pop N values from stack in reverse order
+                let mut elements = Vec::with_capacity(n as usize);
+                for _ in 0..n {
+                    elements.push(self.pop()?);
                 }
+                // Reverse to maintain left-to-right order
+                elements.reverse();
+                // Build array from elements
+                self.note_allocation(estimate_shallow_bytes(n));
+                use std::sync::Arc;
+                use std::sync::Mutex;
+                let array = Value::Array(Arc::new(Mutex::new(elements)));
+                self.push(array);
+            }
 
-                Instruction::ArraySet => {
-                    let value = self.pop()?;
-                    let index = self.pop()?;
-                    let array = self.pop()?;
-
-                    let idx = match index {
-                        Value::Int(i) => {
-                            if i < 0 {
-                                return Err(VmError::TypeMismatch {
-                                    expected: "non-negative int",
-                                    got: "negative int",
-                                });
-                            }
-                            i as usize
-                        }
-                        _ => {
+            Instruction::ToArray => {
+                let value = self.pop()?;
+                self.push(collection_to_array(value)?);
+            }
+
+            Instruction::ArrayGet => {
+                let index = self.pop()?;
+                let array = self.pop()?;
+
+                let idx = match index {
+                    Value::Int(i) => {
+                        if i < 0 {
                             return Err(VmError::TypeMismatch {
-                                expected: "int",
-                                got: index.type_name(),
-                            })
+                                expected: "non-negative int",
+                                got: "negative int",
+                            });
                         }
-                    };
+                        i as usize
+                    }
+                    _ => {
+                        return Err(VmError::TypeMismatch {
+                            expected: "int",
+                            got: index.type_name(),
+                        })
+                    }
+                };
 
-                    array.array_set(idx, value).map_err(VmError::Runtime)?;
+                let value = array.array_get(idx).map_err(VmError::Runtime)?;
 
-                    // Push unit to indicate completion
-                    self.push(Value::Unit);
-                }
+                self.push(value);
+            }
 
-                Instruction::ArrayLength => {
-                    let array = self.pop()?;
-                    let len = array.array_length().map_err(VmError::Runtime)?;
-                    self.push(Value::Int(len));
-                }
+            Instruction::ArraySet => {
+                let value = self.pop()?;
+                let index = self.pop()?;
+                let array = self.pop()?;
 
-                Instruction::ArrayUpdate => {
-                    let value = self.pop()?;
-                    let index = self.pop()?;
-                    let array = self.pop()?;
-
-                    let idx = match index {
-                        Value::Int(i) => {
-                            if i < 0 {
-                                return Err(VmError::TypeMismatch {
-                                    expected: "non-negative int",
-                                    got: "negative int",
-                                });
-                            }
-                            i as usize
-                        }
-                        _ => {
+                let idx = match index {
+                    Value::Int(i) => {
+                        if i < 0 {
                             return Err(VmError::TypeMismatch {
-                                expected: "int",
-                                got: index.type_name(),
-                            })
-                        }
-                    };
-
-                    // Clone the array for immutable update
-                    use std::sync::Arc;
-                    use std::sync::Mutex;
-                    let new_arr = if let Value::Array(arr) = &array {
-                        let mut new_elements = arr.lock().unwrap().clone();
-                        if idx >= new_elements.len() {
-                            return Err(VmError::Runtime(format!("Index {} out of bounds", idx)));
+                                expected: "non-negative int",
+                                got: "negative int",
+                            });
                         }
-                        new_elements[idx] = value;
-                        Value::Array(Arc::new(Mutex::new(new_elements)))
-                    } else {
+                        i as usize
+                    }
+                    _ => {
                         return Err(VmError::TypeMismatch {
-                            expected: "array",
-                            got: array.type_name(),
-                        });
-                    };
+                            expected: "int",
+                            got: index.type_name(),
+                        })
+                    }
+                };
 
-                    self.push(new_arr);
-                }
+                array.array_set(idx, value).map_err(VmError::Runtime)?;
+
+                // Push unit to indicate completion
+                self.push(Value::Unit);
+            }
+
+            Instruction::ArrayLength => {
+                let array = self.pop()?;
+                let len = array.array_length().map_err(VmError::Runtime)?;
+                self.push(Value::Int(len));
+            }
 
-                // Record operations
-                Instruction::MakeRecord(n) => {
-                    // Pop N field name/value pairs from stack in reverse order
-                    use std::collections::HashMap;
-                    use std::sync::Arc;
-                    use std::sync::Mutex;
-
-                    let mut fields = HashMap::new();
-                    for _ in 0..n {
-                        // Stack: [..., value, field_name] (top)
-                        let field_value = self.pop()?;
-                        let field_name = self.pop()?;
-
-                        // Field name must be a string
-                        let field_name_str =
-                            field_name.as_str().ok_or_else(|| VmError::TypeMismatch {
-                                expected: "string",
-                                got: field_name.type_name(),
-                            })?;
-
-                        fields.insert(field_name_str.to_string(), field_value);
+            Instruction::ArrayUpdate => {
+                let value = self.pop()?;
+                let index = self.pop()?;
+                let array = self.pop()?;
+
+                let idx = match index {
+                    Value::Int(i) => {
+                        if i < 0 {
+                            return Err(VmError::TypeMismatch {
+                                expected: "non-negative int",
+                                got: "negative int",
+                            });
+                        }
+                        i as usize
                     }
+                    _ => {
+                        return Err(VmError::TypeMismatch {
+                            expected: "int",
+                            got: index.type_name(),
+                        })
+                    }
+                };
+
+                // Clone the array for immutable update
+                use std::sync::Arc;
+                use std::sync::Mutex;
+                let new_arr = if let Value::Array(arr) = &array {
+                    let mut new_elements = arr.lock().unwrap().clone();
+                    if idx >= new_elements.len() {
+                        return Err(VmError::Runtime(format!("Index {} out of bounds", idx)));
+                    }
+                    new_elements[idx] = value;
+                    Value::Array(Arc::new(Mutex::new(new_elements)))
+                } else {
+                    return Err(VmError::TypeMismatch {
+                        expected: "array",
+                        got: array.type_name(),
+                    });
+                };
 
-                    let record = Value::Record(Arc::new(Mutex::new(fields)));
-                    self.push(record);
-                }
+                self.push(new_arr);
+            }
 
-                Instruction::GetRecordField => {
-                    // Stack: [..., field_name, record] (top)
+            // Record operations
+            Instruction::MakeRecord(n) => {
+                // Pop N field name/value pairs from stack in reverse order
+                use std::collections::HashMap;
+                use std::sync::Arc;
+                use std::sync::Mutex;
+
+                let mut fields = HashMap::new();
+                for _ in 0..n {
+                    // Stack: [..., value, field_name] (top)
+                    let field_value = self.pop()?;
                     let field_name = self.pop()?;
-                    let record = self.pop()?;
 
                     // Field name must be a string
                     let field_name_str =
@@ -1177,6 +1705,28 @@ impl Vm {
                             got: field_name.type_name(),
                         })?;
 
+                    fields.insert(field_name_str.to_string(), field_value);
+                }
+
+                let record = Value::Record(Arc::new(Mutex::new(fields)));
+                self.push(record);
+            }
+
+            Instruction::GetRecordField => {
+                // Stack: [..., field_name, record] (top)
+                let field_name = self.pop()?;
+                let record = self.pop()?;
+
+                // Field name must be a string
+                let field_name_str = field_name.as_str().ok_or_else(|| VmError::TypeMismatch {
+                    expected: "string",
+                    got: field_name.type_name(),
+                })?;
+
+                // Properties declared by a script type: `shape.Area`
+                if let Some(member) = self.instance_member(&record, field_name_str) {
+                    self.call_instance_member(member, record, vec![], start_depth)?;
+                } else {
                     // Get the field value
                     let field_value = record
                         .record_get(field_name_str)
@@ -1184,101 +1734,277 @@ impl Vm {
 
                     self.push(field_value);
                 }
+            }
 
-                Instruction::UpdateRecord(n) => {
-                    // Pop N field name/value pairs, then the record
-                    use std::collections::HashMap;
+            Instruction::UpdateRecord(n) => {
+                // Pop N field name/value pairs, then the record
+                use std::collections::HashMap;
 
-                    let mut updates = HashMap::new();
-                    for _ in 0..n {
-                        // Stack: [..., value, field_name] (top)
-                        let field_value = self.pop()?;
-                        let field_name = self.pop()?;
+                let mut updates = HashMap::new();
+                for _ in 0..n {
+                    // Stack: [..., value, field_name] (top)
+                    let field_value = self.pop()?;
+                    let field_name = self.pop()?;
 
-                        // Field name must be a string
-                        let field_name_str =
-                            field_name.as_str().ok_or_else(|| VmError::TypeMismatch {
-                                expected: "string",
-                                got: field_name.type_name(),
-                            })?;
+                    // Field name must be a string
+                    let field_name_str =
+                        field_name.as_str().ok_or_else(|| VmError::TypeMismatch {
+                            expected: "string",
+                            got: field_name.type_name(),
+                        })?;
 
-                        updates.insert(field_name_str.to_string(), field_value);
-                    }
+                    updates.insert(field_name_str.to_string(), field_value);
+                }
 
-                    let record = self.pop()?;
+                let record = self.pop()?;
 
-                    // Create updated record (immutable)
-                    let new_record = record.record_update(updates).map_err(VmError::Runtime)?;
+                // Create updated record (immutable)
+                let new_record = record.record_update(updates).map_err(VmError::Runtime)?;
 
-                    self.push(new_record);
+                self.push(new_record);
+            }
+
+            // Discriminated union operations
+            Instruction::MakeVariant(n) => {
+                // Pop N fields, then variant_name, then type_name from stack
+                // Stack: [..., type_name, variant_name, field_0, ..., field_N-1] (top)
+
+                // Collect fields in reverse order (stack is LIFO)
+                let mut fields = Vec::with_capacity(n as usize);
+                for _ in 0..n {
+                    fields.push(self.pop()?);
                 }
+                fields.reverse(); // Restore correct field order
+
+                // Pop variant_name and type_name
+                let variant_name = self.pop()?;
+                let type_name = self.pop()?;
+
+                // Type name and variant name must be strings
+                let type_name_str = type_name.as_str().ok_or_else(|| VmError::TypeMismatch {
+                    expected: "string",
+                    got: type_name.type_name(),
+                })?;
+
+                let variant_name_str =
+                    variant_name.as_str().ok_or_else(|| VmError::TypeMismatch {
+                        expected: "string",
+                        got: variant_name.type_name(),
+                    })?;
+
+                // Create variant value
+                let variant = Value::Variant {
+                    type_name: type_name_str.to_string(),
+                    variant_name: variant_name_str.to_string(),
+                    fields,
+                };
+
+                self.push(variant);
+            }
 
-                // Discriminated union operations
-                Instruction::MakeVariant(n) => {
-                    // Pop N fields, then variant_name, then type_name from stack
-                    // Stack: [..., type_name, variant_name, field_0, ..., field_N-1] (top)
+            Instruction::CheckVariantTag(ref tag) => {
+                // Pop variant from stack, push bool indicating if tag matches
+                let variant = self.pop()?;
 
-                    // Collect fields in reverse order (stack is LIFO)
-                    let mut fields = Vec::with_capacity(n as usize);
-                    for _ in 0..n {
-                        fields.push(self.pop()?);
-                    }
-                    fields.reverse(); // Restore correct field order
+                // Check if value is a variant with the specified tag
+                let matches = variant.is_variant_named(tag);
 
-                    // Pop variant_name and type_name
-                    let variant_name = self.pop()?;
-                    let type_name = self.pop()?;
+                self.push(Value::Bool(matches));
+            }
 
-                    // Type name and variant name must be strings
-                    let type_name_str =
-                        type_name.as_str().ok_or_else(|| VmError::TypeMismatch {
-                            expected: "string",
-                            got: type_name.type_name(),
-                        })?;
+            Instruction::GetVariantField(idx) => {
+                // Pop variant from stack, push field at index
+                let variant = self.pop()?;
 
-                    let variant_name_str =
-                        variant_name.as_str().ok_or_else(|| VmError::TypeMismatch {
-                            expected: "string",
-                            got: variant_name.type_name(),
-                        })?;
+                // Get the field value
+                let field_value = variant
+                    .variant_get_field(idx as usize)
+                    .map_err(VmError::Runtime)?;
 
-                    // Create variant value
-                    let variant = Value::Variant {
-                        type_name: type_name_str.to_string(),
-                        variant_name: variant_name_str.to_string(),
-                        fields,
-                    };
+                self.push(field_value);
+            }
 
-                    self.push(variant);
+            Instruction::DefineType(name_idx, is_union) => {
+                let instance = self.pop()?;
+                let statics = self.pop()?;
+                let names = self.pop()?;
+                let type_name = self.current_frame()?.get_constant(name_idx)?;
+                let names = names
+                    .list_to_vec()
+                    .and_then(|names| {
+                        names
+                            .iter()
+                            .map(|name| name.as_str().map(str::to_string))
+                            .collect::<Option<Vec<_>>>()
+                    })
+                    .ok_or_else(|| {
+                        VmError::Runtime(format!(
+                            "Invalid field or case names for type {}",
+                            type_name
+                        ))
+                    })?;
+                // `Config.Default` reads the type's static members like a module's
+                let has_statics =
+                    matches!(&statics, Value::Record(r) if !r.lock().unwrap().is_empty());
+                if let (true, Value::Str(type_name)) = (has_statics, &type_name) {
+                    self.globals.insert(type_name.clone(), statics.clone());
                 }
+                self.types.push(TypeMembers {
+                    names,
+                    is_union,
+                    statics,
+                    instance,
+                });
+            }
+        }
+        Ok(None)
+    }
 
-                Instruction::CheckVariantTag(ref tag) => {
-                    // Pop variant from stack, push bool indicating if tag matches
-                    let variant = self.pop()?;
+    /// Push a call frame whose locals start at `base`
+    ///
+    /// Arguments are expected at `base..base + arity`; the remaining local
+    /// slots declared by the chunk are reserved so that temporaries are always
+    /// pushed above them.
+    pub(crate) fn push_frame(&mut self, closure: Arc<Closure>, base: usize) -> Result<(), VmError> {
+        if self
+            .limits
+            .max_stack_depth
+            .is_some_and(|max| self.frames.len() >= max)
+        {
+            return Err(VmError::LimitExceeded {
+                kind: LimitKind::StackDepth,
+            });
+        }
+        let slots_end = base + closure.chunk.local_count as usize;
+        if self.stack.len() < slots_end {
+            self.stack.resize(slots_end, Value::Unit);
+        }
+        self.frames.push(Frame::new(closure, base));
+        Ok(())
+    }
 
-                    // Check if value is a variant with the specified tag
-                    let matches = variant.is_variant_named(tag);
+    /// Count an interpreter loop about to start on the native stack
+    ///
+    /// Callbacks from host functions into script closures nest a loop per
+    /// call, so recursion through them is stopped by `max_native_stack_bytes`
+    /// before it overflows the native stack. Pair with [`Vm::exit_run`] once
+    /// the loop returns.
+    pub(crate) fn enter_run(&mut self) -> Result<(), VmError> {
+        let marker = 0u8;
+        let here = std::ptr::addr_of!(marker) as usize;
+        if self.nested_runs == 0 {
+            self.native_stack_base = here;
+        } else if self
+            .limits
+            .max_native_stack_bytes
+            .is_some_and(|max| self.native_stack_base.abs_diff(here) > max)
+        {
+            return Err(VmError::LimitExceeded {
+                kind: LimitKind::StackDepth,
+            });
+        }
+        self.nested_runs += 1;
+        Ok(())
+    }
 
-                    self.push(Value::Bool(matches));
-                }
+    /// Leave an interpreter loop counted by [`Vm::enter_run`]
+    pub(crate) fn exit_run(&mut self) {
+        self.nested_runs -= 1;
+    }
 
-                Instruction::GetVariantField(idx) => {
-                    // Pop variant from stack, push field at index
-                    let variant = self.pop()?;
+    /// Restart the instruction count and the timeout, and drop interrupt
+    /// requests left over from earlier runs, when no execution is in progress
+    pub(crate) fn begin_execution(&mut self) {
+        if self.frames.is_empty() {
+            self.interrupt.clear();
+            self.instruction_count = 0;
+            self.deadline = self.limits.timeout.map(|timeout| Instant::now() + timeout);
+            self.heap_estimate = 0;
+            self.next_heap_walk = 0;
+        }
+    }
 
-                    // Get the field value
-                    let field_value = variant
-                        .variant_get_field(idx as usize)
-                        .map_err(VmError::Runtime)?;
+    /// Act on pending interrupts, then count one instruction and check the
+    /// budgets in [`VmLimits`]
+    ///
+    /// Returns true when the run should pause before the instruction. Runs
+    /// that are not `pausable` leave a pause request pending.
+    #[inline(always)]
+    pub(crate) fn tick(&mut self, pausable: bool) -> Result<bool, VmError> {
+        match self.interrupt.poll(pausable) {
+            None => {}
+            Some(Interrupt::Cancel) => return Err(VmError::Interrupted),
+            Some(Interrupt::Pause) => return Ok(true),
+        }
+        self.instruction_count += 1;
+        if let Some(max) = self.limits.max_instructions {
+            if self.instruction_count > max {
+                return Err(VmError::LimitExceeded {
+                    kind: LimitKind::Instructions,
+                });
+            }
+        }
+        if self.instruction_count % CHECK_INTERVAL == 0 {
+            self.check_periodic_limits()?;
+        }
+        Ok(false)
+    }
 
-                    self.push(field_value);
+    /// Check the budgets that are too costly to test on every instruction
+    ///
+    /// The heap is only walked once the allocations counted since the last
+    /// walk could exceed the cap, or after a number of instructions
+    /// proportional to the heap size, which keeps heap-capped runs linear.
+    fn check_periodic_limits(&mut self) -> Result<(), VmError> {
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Err(VmError::LimitExceeded {
+                kind: LimitKind::Deadline,
+            });
+        }
+        if let Some(max) = self.limits.max_heap_bytes {
+            if self.heap_estimate > max || self.instruction_count >= self.next_heap_walk {
+                self.walk_heap();
+                if self.heap_estimate > max {
+                    return Err(VmError::LimitExceeded {
+                        kind: LimitKind::HeapBytes,
+                    });
                 }
+            }
+        }
+        Ok(())
+    }
+
+    /// Measure the reachable heap and schedule the next walk
+    fn walk_heap(&mut self) {
+        self.heap_estimate = self.heap_bytes();
+        self.next_heap_walk =
+            self.instruction_count + CHECK_INTERVAL.max(self.heap_estimate as u64 / 16);
+    }
+
+    /// Count `bytes` allocated by an instruction towards the heap cap
+    #[inline(always)]
+    fn note_allocation(&mut self, bytes: usize) {
+        self.heap_estimate = self.heap_estimate.saturating_add(bytes);
+    }
 
-                _ => {
-                    unimplemented!("Instruction not implemented in Phase 1: {:?}", instruction)
+    /// Fail before allocating `bytes` at once if that would exceed the heap
+    /// cap
+    fn reserve_heap(&mut self, bytes: usize) -> Result<(), VmError> {
+        if let Some(max) = self.limits.max_heap_bytes {
+            if self.heap_estimate.saturating_add(bytes) > max {
+                self.walk_heap();
+                if self.heap_estimate.saturating_add(bytes) > max {
+                    return Err(VmError::LimitExceeded {
+                        kind: LimitKind::HeapBytes,
+                    });
                 }
             }
         }
+        self.note_allocation(bytes);
+        Ok(())
     }
 
     /// Peek at the top of the stack without removing it
@@ -1405,6 +2131,106 @@ impl Vm {
         Ok(())
     }
 
+    /// Find the handler that should catch an exception raised in this `run()`
+    ///
+    /// Handlers installed by frames below `start_depth` belong to an outer
+    /// `run()`; the exception reaches them as an error from the host function
+    /// that re-entered the VM.
+    fn catching_handler(&mut self, start_depth: usize) -> Option<Handler> {
+        // Drop handlers whose frames were abandoned by an earlier uncaught error
+        while self
+            .handlers
+            .last()
+            .is_some_and(|h| h.frame_depth > self.frames.len())
+        {
+            self.handlers.pop();
+        }
+        self.handlers
+            .last()
+            .copied()
+            .filter(|h| h.frame_depth >= start_depth)
+    }
+
+    /// Raise `exn`, unwinding to the innermost handler of this `run()`
+    fn throw(&mut self, exn: Value, start_depth: usize) -> Result<(), VmError> {
+        let Some(handler) = self.catching_handler(start_depth) else {
+            return Err(VmError::Exception(exn));
+        };
+        self.handlers.pop();
+        self.frames.truncate(handler.frame_depth);
+        self.close_upvalues(handler.stack_height);
+        self.stack.truncate(handler.stack_height);
+        self.push(exn);
+        self.current_frame_mut()?.ip = handler.catch_ip;
+        Ok(())
+    }
+
+    /// Turn a runtime error or a failed host function call into a script
+    /// exception
+    ///
+    /// The exception records the message and the source span of the failing
+    /// instruction. When no script handler is active the original error is
+    /// returned unchanged.
+    pub(crate) fn throw_error(
+        &mut self,
+        error: VmError,
+        start_depth: usize,
+    ) -> Result<(), VmError> {
+        // Budgets and cancellation must stop the script, so they never become
+        // catchable exceptions
+        if matches!(error, VmError::LimitExceeded { .. } | VmError::Interrupted)
+            || self.catching_handler(start_depth).is_none()
+        {
+            return Err(error);
+        }
+        let span = self
+            .frames
+            .last()
+            .and_then(|frame| frame.closure.chunk.span_at(frame.ip.saturating_sub(1)));
+        self.throw(exception::from_error(&error, span), start_depth)
+    }
+
+    /// Discard frames, handlers and stack values left behind by a failed call
+    pub(crate) fn unwind_failed_call(&mut self, frame_depth: usize, stack_height: usize) {
+        self.frames.truncate(frame_depth);
+        self.handlers.retain(|h| h.frame_depth <= frame_depth);
+        self.close_upvalues(stack_height);
+        self.stack.truncate(stack_height);
+    }
+
+    /// Get the upvalue for a stack slot, sharing it with any closure that
+    /// already captured the same slot
+    fn capture_upvalue(&mut self, stack_idx: usize) -> Arc<Mutex<Upvalue>> {
+        if let Some(existing) = self
+            .open_upvalues
+            .iter()
+            .find(|u| u.lock().unwrap().stack_index() == Some(stack_idx))
+        {
+            return existing.clone();
+        }
+        let upvalue = Arc::new(Mutex::new(Upvalue::new_open(stack_idx)));
+        self.open_upvalues.push(upvalue.clone());
+        upvalue
+    }
+
+    /// Close every open upvalue at or above `from_stack_idx`
+    ///
+    /// The slot's current value moves into the upvalue so closures keep
+    /// seeing (and updating) it after the slot is reused or popped.
+    fn close_upvalues(&mut self, from_stack_idx: usize) {
+        let stack = &self.stack;
+        self.open_upvalues.retain(|upvalue| {
+            let mut upvalue = upvalue.lock().unwrap();
+            match upvalue.stack_index() {
+                Some(idx) if idx >= from_stack_idx => {
+                    upvalue.close(stack.get(idx).cloned().unwrap_or(Value::Unit));
+                    false
+                }
+                _ => true,
+            }
+        });
+    }
+
     /// Call a closure from Rust code (re-entrant)
     pub fn call_closure(
         &mut self,
@@ -1428,11 +2254,19 @@ impl Vm {
         let base = self.stack.len() - args.len();
 
         // Push frame
-        let frame = Frame::new(closure, base);
-        self.frames.push(frame);
+        self.begin_execution();
+        let depth = self.frames.len();
+        if let Err(error) = self.push_frame(closure, base) {
+            self.stack.truncate(base);
+            return Err(error);
+        }
 
         // Run the VM loop until this frame returns
-        self.run()
+        let result = self.run();
+        if result.is_err() {
+            self.unwind_failed_call(depth, base);
+        }
+        result
     }
 
     /// Call any callable value (Closure or NativeFn) from Rust code
@@ -1483,6 +2317,107 @@ impl Vm {
         }
     }
 
+    /// Apply a function to `args` one at a time, as `f a b` applies a curried function.
+    /// With no arguments the function takes unit, as for `builder.Zero()`.
+    fn call_curried(&mut self, func: Value, args: &[Value]) -> Result<Value, VmError> {
+        if args.is_empty() {
+            return self.call_value(func, &[Value::Unit]);
+        }
+        args.iter().try_fold(func, |func, arg| {
+            self.call_value(func, std::slice::from_ref(arg))
+        })
+    }
+
+    /// Find the static member `name` declared by the type of `a`, or else of `b`
+    fn operator_member(&self, name: &str, a: &Value, b: &Value) -> Option<Value> {
+        [a, b].into_iter().find_map(|value| {
+            let ty = self.types.iter().rev().find(|ty| ty.describes(value))?;
+            ty.statics.record_get(name).ok()
+        })
+    }
+
+    /// Find the instance member `name` declared by the type of `receiver`
+    ///
+    /// A record's own fields take precedence over its type's members.
+    fn instance_member(&self, receiver: &Value, name: &str) -> Option<Value> {
+        if let Value::Record(fields) = receiver {
+            if fields.lock().unwrap().contains_key(name) {
+                return None;
+            }
+        }
+        let ty = self.types.iter().rev().find(|ty| ty.describes(receiver))?;
+        ty.instance.record_get(name).ok()
+    }
+
+    /// Apply an instance member to its receiver and arguments and push the
+    /// result; a method called without arguments is passed unit
+    fn call_instance_member(
+        &mut self,
+        member: Value,
+        receiver: Value,
+        mut args: Vec<Value>,
+        start_depth: usize,
+    ) -> Result<(), VmError> {
+        args.insert(0, receiver);
+        match self.call_curried(member, &args) {
+            Ok(result) => {
+                self.push(result);
+                Ok(())
+            }
+            Err(error) => self.throw_error(error, start_depth),
+        }
+    }
+
+    /// Apply an operator member to both operands and push its result
+    fn apply_member(
+        &mut self,
+        member: Value,
+        a: Value,
+        b: Value,
+        start_depth: usize,
+    ) -> Result<(), VmError> {
+        match self.call_curried(member, &[a, b]) {
+            Ok(result) => {
+                self.push(result);
+                Ok(())
+            }
+            Err(error) => self.throw_error(error, start_depth),
+        }
+    }
+
+    /// Order two values with a type's `Compare` member, which returns a
+    /// negative, zero or positive int
+    ///
+    /// Returns `None` when the call raised an exception that a script
+    /// handler has taken over.
+    fn compare_with_member(
+        &mut self,
+        member: Value,
+        a: Value,
+        b: Value,
+        start_depth: usize,
+    ) -> Result<Option<std::cmp::Ordering>, VmError> {
+        let error = match self.call_curried(member, &[a, b]) {
+            Ok(Value::Int(n)) => return Ok(Some(n.cmp(&0))),
+            Ok(other) => VmError::Runtime(format!(
+                "Compare must return an int, got {}",
+                other.type_name()
+            )),
+            Err(error) => error,
+        };
+        self.throw_error(error, start_depth)?;
+        Ok(None)
+    }
+
+    /// Look up `Module.member` as a field of the global record `Module`
+    fn module_member(&self, name: &str) -> Option<Value> {
+        let (module, member) = name.split_once('.')?;
+        match self.globals.get(module)? {
+            Value::Record(fields) => fields.lock().unwrap().get(member).cloned(),
+            _ => None,
+        }
+    }
+
     /// Get the current stack size (for debugging)
     pub fn stack_size(&self) -> usize {
         self.stack.len()
@@ -1550,20 +2485,286 @@ impl Vm {
             .as_ref()
             .ok_or_else(|| VmError::Runtime("Async runtime not enabled".to_string()))?;
 
-        runtime.cancel(task_id)
+        runtime.cancel(task_id)
+    }
+}
+
+impl Default for Vm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Elements of a collection iterated by `for ... in`, as a list
+pub(crate) fn collection_to_list(value: Value) -> Result<Value, VmError> {
+    match value {
+        Value::Nil | Value::Cons { .. } => Ok(value),
+        Value::Array(_) => crate::stdlib::array::array_to_list(&value),
+        Value::Map(_) => crate::stdlib::map::map_to_list(&value),
+        _ => Err(VmError::TypeMismatch {
+            expected: "list, array or map",
+            got: value.type_name(),
+        }),
+    }
+}
+
+/// Reverse a list.
+pub(crate) fn reverse_list(list: Value) -> Result<Value, VmError> {
+    let mut elements = list.list_to_vec().ok_or(VmError::TypeMismatch {
+        expected: "list",
+        got: list.type_name(),
+    })?;
+    elements.reverse();
+    Ok(Value::vec_to_cons(elements))
+}
+
+/// Copy the elements of a list or array into a new array.
+pub(crate) fn collection_to_array(value: Value) -> Result<Value, VmError> {
+    let elements = match &value {
+        Value::Array(array) => array.lock().unwrap().clone(),
+        _ => value.list_to_vec().ok_or(VmError::TypeMismatch {
+            expected: "list or array",
+            got: value.type_name(),
+        })?,
+    };
+    Ok(Value::Array(Arc::new(std::sync::Mutex::new(elements))))
+}
+
+/// Apply a bitwise or shift instruction to two ints.
+///
+/// Shift counts are taken modulo 64, as in F#.
+pub(crate) fn bitwise_op(instr: &Instruction, a: Value, b: Value) -> Result<Value, VmError> {
+    let (a, b) = match (a, b) {
+        (Value::Int(a), Value::Int(b)) => (a, b),
+        (a, b) => {
+            return Err(VmError::Runtime(format!(
+                "Type mismatch in bitwise operation: {} {} {}",
+                a.type_name(),
+                instr,
+                b.type_name()
+            )))
+        }
+    };
+    let result = match instr {
+        Instruction::BitAnd => a & b,
+        Instruction::BitOr => a | b,
+        Instruction::BitXor => a ^ b,
+        Instruction::Shl => a.wrapping_shl(b as u32),
+        Instruction::Shr => a.wrapping_shr(b as u32),
+        _ => unreachable!("not a bitwise instruction: {}", instr),
+    };
+    Ok(Value::Int(result))
+}
+
+/// Build the list `[start .. step .. finish]` of ints or floats.
+///
+/// The finish is included when the steps land on it; a negative step counts
+/// down. Float ranges are computed from the step count so that rounding does
+/// not add or drop an element.
+/// Bytes counted towards the heap cap for each cell of a list
+const LIST_CELL_BYTES: usize = 24;
+
+/// Bytes counted towards the heap cap for a tuple or array of `n` elements,
+/// not counting what the elements themselves hold
+fn estimate_shallow_bytes(n: impl Into<usize>) -> usize {
+    std::mem::size_of::<Vec<Value>>() + n.into() * std::mem::size_of::<Value>()
+}
+
+/// Number of elements `make_range` would produce, saturating at `usize::MAX`
+///
+/// Malformed bounds count as empty; `make_range` reports them.
+fn range_len(start: &Value, step: &Value, finish: &Value) -> usize {
+    let steps = match (start, step, finish) {
+        (Value::Int(start), Value::Int(step), Value::Int(finish)) if *step != 0 => {
+            (*finish as i128 - *start as i128) / *step as i128
+        }
+        (Value::Float(start), Value::Int(_) | Value::Float(_), Value::Float(finish)) => {
+            let step = match step {
+                Value::Int(n) => *n as f64,
+                Value::Float(f) => *f,
+                _ => unreachable!(),
+            };
+            if step == 0.0 {
+                return 0;
+            }
+            ((finish - start) / step + 1e-9).floor() as i128
+        }
+        _ => return 0,
+    };
+    if steps < 0 {
+        0
+    } else {
+        usize::try_from(steps + 1).unwrap_or(usize::MAX)
+    }
+}
+
+/// Build the list `start .. step .. finish`
+///
+/// `check` runs before each element so that limits and interrupts can stop
+/// a long range part way through.
+pub(crate) fn make_range(
+    start: Value,
+    step: Value,
+    finish: Value,
+    check: &mut dyn FnMut() -> Result<(), VmError>,
+) -> Result<Value, VmError> {
+    let zero_step = || VmError::Runtime("range step cannot be zero".to_string());
+    match (start, step, finish) {
+        (Value::Int(start), Value::Int(step), Value::Int(finish)) => {
+            if step == 0 {
+                return Err(zero_step());
+            }
+            let mut elements = Vec::new();
+            let mut current = Some(start);
+            while let Some(n) = current {
+                if (step > 0 && n > finish) || (step < 0 && n < finish) {
+                    break;
+                }
+                check()?;
+                elements.push(Value::Int(n));
+                current = n.checked_add(step);
+            }
+            Ok(Value::vec_to_cons(elements))
+        }
+        (Value::Float(start), step @ (Value::Int(_) | Value::Float(_)), Value::Float(finish)) => {
+            let step = match step {
+                Value::Int(n) => n as f64,
+                Value::Float(f) => f,
+                _ => unreachable!(),
+            };
+            if step == 0.0 {
+                return Err(zero_step());
+            }
+            let steps = ((finish - start) / step + 1e-9).floor();
+            let elements = if steps < 0.0 {
+                Vec::new()
+            } else {
+                (0..=steps as i64)
+                    .map(|i| {
+                        check()?;
+                        Ok(Value::Float(start + i as f64 * step))
+                    })
+                    .collect::<Result<_, VmError>>()?
+            };
+            Ok(Value::vec_to_cons(elements))
+        }
+        (start, step, finish) => Err(VmError::Runtime(format!(
+            "range bounds must be ints or floats, got {} .. {} .. {}",
+            start.type_name(),
+            step.type_name(),
+            finish.type_name()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk::{ChunkBuilder, SourceSpan};
+
+    #[test]
+    fn test_vm_bitwise_op() {
+        let op = |instr, a, b| bitwise_op(&instr, Value::Int(a), Value::Int(b)).unwrap();
+        assert_eq!(op(Instruction::BitAnd, 0b1100, 0b1010), Value::Int(0b1000));
+        assert_eq!(op(Instruction::BitOr, 0b1100, 0b1010), Value::Int(0b1110));
+        assert_eq!(op(Instruction::BitXor, 0b1100, 0b1010), Value::Int(0b0110));
+        assert_eq!(op(Instruction::Shl, 1, 10), Value::Int(1024));
+        assert_eq!(op(Instruction::Shr, -16, 2), Value::Int(-4));
+        assert_eq!(op(Instruction::Shl, 1, 65), Value::Int(2));
+        assert!(bitwise_op(&Instruction::BitAnd, Value::Bool(true), Value::Int(1)).is_err());
+    }
+
+    #[test]
+    fn test_vm_make_range() {
+        let ints = |v: Vec<i64>| Value::vec_to_cons(v.into_iter().map(Value::Int).collect());
+        let build = |a, s, b| make_range(a, s, b, &mut || Ok(()));
+        let range = |a, s, b| build(Value::Int(a), Value::Int(s), Value::Int(b)).unwrap();
+        assert_eq!(range(1, 1, 5), ints(vec![1, 2, 3, 4, 5]));
+        assert_eq!(range(0, 3, 10), ints(vec![0, 3, 6, 9]));
+        assert_eq!(range(5, -2, 0), ints(vec![5, 3, 1]));
+        assert_eq!(range(5, 1, 1), Value::Nil);
+        assert!(build(Value::Int(1), Value::Int(0), Value::Int(3)).is_err());
+
+        let floats = build(Value::Float(0.0), Value::Float(0.1), Value::Float(0.3));
+        assert_eq!(floats.unwrap().list_to_vec().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_vm_make_range_runs_check_per_element() {
+        let mut calls = 0;
+        let mut check = || {
+            calls += 1;
+            if calls > 3 {
+                Err(VmError::Runtime("stop".into()))
+            } else {
+                Ok(())
+            }
+        };
+        let result = make_range(
+            Value::Int(1),
+            Value::Int(1),
+            Value::Int(1_000_000),
+            &mut check,
+        );
+        assert!(result.is_err());
+        assert_eq!(calls, 4);
+    }
+
+    #[test]
+    fn test_vm_range_len() {
+        assert_eq!(range_len(&Value::Int(1), &Value::Int(1), &Value::Int(5)), 5);
+        assert_eq!(
+            range_len(&Value::Int(5), &Value::Int(-2), &Value::Int(0)),
+            3
+        );
+        assert_eq!(range_len(&Value::Int(5), &Value::Int(1), &Value::Int(1)), 0);
+        assert_eq!(
+            range_len(&Value::Int(i64::MIN), &Value::Int(1), &Value::Int(i64::MAX)),
+            usize::MAX
+        );
     }
-}
 
-impl Default for Vm {
-    fn default() -> Self {
-        Self::new()
+    #[test]
+    fn test_vm_to_list_accepts_collections() {
+        let array = Value::Array(Arc::new(Mutex::new(vec![Value::Int(1), Value::Int(2)])));
+        assert_eq!(
+            collection_to_list(array).unwrap(),
+            Value::vec_to_cons(vec![Value::Int(1), Value::Int(2)])
+        );
+        assert_eq!(collection_to_list(Value::Nil).unwrap(), Value::Nil);
+        assert!(collection_to_list(Value::Int(3)).is_err());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::chunk::ChunkBuilder;
+    #[test]
+    fn test_vm_move_next_walks_list_and_seq() {
+        let list = Value::vec_to_cons(vec![Value::Int(1), Value::Int(2)]);
+        let seq = Value::Seq(crate::value::Seq::new(crate::value::SeqSource::Of(
+            list.clone(),
+        )));
+        for source in [list, seq] {
+            let mut vm = Vm::new();
+            // 1 + 2, then the cursor is exhausted
+            let mut chunk = ChunkBuilder::new()
+                .constant(source)
+                .instruction(Instruction::LoadConst(0))
+                .instruction(Instruction::GetEnumerator)
+                .instruction(Instruction::StoreLocal(0))
+                .instruction(Instruction::MoveNext(0))
+                .instruction(Instruction::Pop)
+                .instruction(Instruction::MoveNext(0))
+                .instruction(Instruction::Pop)
+                .instruction(Instruction::Add)
+                .instruction(Instruction::MoveNext(0))
+                .instruction(Instruction::MakeTuple(2))
+                .instruction(Instruction::Return)
+                .build();
+            chunk.local_count = 1;
+            assert_eq!(
+                vm.execute(chunk).unwrap(),
+                Value::Tuple(vec![Value::Int(3), Value::Bool(false)])
+            );
+        }
+    }
 
     #[test]
     fn test_vm_load_const() {
@@ -2688,6 +3889,443 @@ mod tests {
         assert_eq!(result, Value::Bool(false));
     }
 
+    #[test]
+    fn test_vm_match_failure() {
+        let mut vm = Vm::new();
+        let chunk = ChunkBuilder::new()
+            .constant(Value::Int(3))
+            .instruction(Instruction::LoadConst(0))
+            .instruction(Instruction::MatchFailure)
+            .build();
+        let err = vm.execute(chunk).unwrap_err();
+        assert_eq!(
+            err,
+            VmError::Runtime("Match failure: no pattern matched 3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_vm_locals_reserved_below_temporaries() {
+        let mut vm = Vm::new();
+        // 10 + (let x = 2 in x): the local slot must not overlap the pending 10
+        let mut chunk = ChunkBuilder::new()
+            .constant(Value::Int(10))
+            .constant(Value::Int(2))
+            .instruction(Instruction::LoadConst(0))
+            .instruction(Instruction::LoadConst(1))
+            .instruction(Instruction::StoreLocal(0))
+            .instruction(Instruction::LoadLocal(0))
+            .instruction(Instruction::Add)
+            .instruction(Instruction::Return)
+            .build();
+        chunk.local_count = 1;
+        assert_eq!(vm.execute(chunk).unwrap(), Value::Int(12));
+        assert!(vm.stack.is_empty());
+    }
+
+    #[test]
+    fn test_vm_raise_caught_by_handler() {
+        let mut vm = Vm::new();
+        // try (1 + raise "boom") with e -> e
+        let chunk = ChunkBuilder::new()
+            .constant(Value::Int(1))
+            .constant(Value::Str("boom".to_string()))
+            .instruction(Instruction::PushHandler(5))
+            .instruction(Instruction::LoadConst(0))
+            .instruction(Instruction::LoadConst(1))
+            .instruction(Instruction::Raise)
+            .instruction(Instruction::Add)
+            .instruction(Instruction::PopHandler)
+            .instruction(Instruction::Return)
+            .build();
+        let result = vm.execute(chunk).unwrap();
+        assert_eq!(result, Value::Str("boom".to_string()));
+        assert!(vm.handlers.is_empty());
+    }
+
+    #[test]
+    fn test_vm_runtime_error_caught_by_handler() {
+        let mut vm = Vm::new();
+        // try 1 / 0 with _ -> 42
+        let chunk = ChunkBuilder::new()
+            .constant(Value::Int(1))
+            .constant(Value::Int(0))
+            .constant(Value::Int(42))
+            .instruction(Instruction::PushHandler(5))
+            .instruction(Instruction::LoadConst(0))
+            .instruction(Instruction::LoadConst(1))
+            .instruction(Instruction::Div)
+            .instruction(Instruction::PopHandler)
+            .instruction(Instruction::Jump(2))
+            .instruction(Instruction::Pop)
+            .instruction(Instruction::LoadConst(2))
+            .instruction(Instruction::Return)
+            .build();
+        assert_eq!(vm.execute(chunk).unwrap(), Value::Int(42));
+        assert!(vm.handlers.is_empty());
+        assert!(vm.stack.is_empty());
+    }
+
+    #[test]
+    fn test_vm_runtime_error_without_handler_is_unchanged() {
+        let mut vm = Vm::new();
+        let chunk = ChunkBuilder::new()
+            .constant(Value::Int(1))
+            .constant(Value::Int(0))
+            .instruction(Instruction::LoadConst(0))
+            .instruction(Instruction::LoadConst(1))
+            .instruction(Instruction::Div)
+            .instruction(Instruction::Return)
+            .build();
+        assert_eq!(vm.execute(chunk).unwrap_err(), VmError::DivisionByZero);
+    }
+
+    #[test]
+    fn test_vm_raise_without_handler() {
+        let mut vm = Vm::new();
+        let chunk = ChunkBuilder::new()
+            .constant(Value::Int(7))
+            .instruction(Instruction::LoadConst(0))
+            .instruction(Instruction::Raise)
+            .build();
+        let err = vm.execute(chunk).unwrap_err();
+        assert_eq!(err, VmError::Exception(Value::Int(7)));
+    }
+
+    #[test]
+    fn test_vm_pop_handler_requires_handler() {
+        let mut vm = Vm::new();
+        let chunk = ChunkBuilder::new()
+            .instruction(Instruction::PopHandler)
+            .build();
+        assert!(vm.execute(chunk).is_err());
+    }
+
+    #[test]
+    fn test_vm_host_error_becomes_exception_with_span() {
+        let mut vm = Vm::new();
+        vm.host_registry
+            .lock()
+            .unwrap()
+            .register("fail", |_vm, _args| {
+                Err(VmError::Runtime("bad input".into()))
+            });
+
+        let mut chunk = Chunk::new();
+        let name = chunk.add_constant(Value::Str("fail".to_string()));
+        let arg = chunk.add_constant(Value::Unit);
+        chunk.emit(Instruction::PushHandler(4));
+        chunk.emit(Instruction::LoadGlobal(name));
+        chunk.emit(Instruction::LoadConst(arg));
+        chunk.emit_with_span(Instruction::Call(1), SourceSpan::new(2, 5, 12, 6));
+        chunk.emit(Instruction::PopHandler);
+        chunk.emit(Instruction::Return);
+
+        let result = vm.execute(chunk).unwrap();
+        assert_eq!(
+            result,
+            crate::exception::host_error("bad input", Some(SourceSpan::new(2, 5, 12, 6)))
+        );
+    }
+
+    #[test]
+    fn test_vm_uncaught_host_error_is_unchanged() {
+        let mut vm = Vm::new();
+        vm.host_registry
+            .lock()
+            .unwrap()
+            .register("fail", |_vm, _args| Err(VmError::DivisionByZero));
+
+        let chunk = ChunkBuilder::new()
+            .constant(Value::Str("fail".to_string()))
+            .constant(Value::Unit)
+            .instruction(Instruction::LoadGlobal(0))
+            .instruction(Instruction::LoadConst(1))
+            .instruction(Instruction::Call(1))
+            .instruction(Instruction::Return)
+            .build();
+        assert_eq!(vm.execute(chunk).unwrap_err(), VmError::DivisionByZero);
+    }
+
+    /// Closure prototype constant capturing the given upvalues
+    fn closure_proto(arity: u8, sources: Vec<UpvalueSource>, mut chunk: Chunk) -> Value {
+        chunk.upvalue_sources = sources;
+        Value::Closure(Arc::new(Closure::with_arity(chunk, arity)))
+    }
+
+    #[test]
+    fn test_vm_closure_writes_through_captured_local() {
+        let mut vm = Vm::new();
+        // let mutable x = 1 in (fun () -> x <- 10) (); x
+        let setter = ChunkBuilder::new()
+            .constant(Value::Int(10))
+            .constant(Value::Unit)
+            .instruction(Instruction::LoadConst(0))
+            .instruction(Instruction::StoreUpvalue(0))
+            .instruction(Instruction::LoadConst(1))
+            .instruction(Instruction::Return)
+            .build();
+        let setter = closure_proto(0, vec![UpvalueSource::Local(0)], setter);
+
+        let chunk = ChunkBuilder::new()
+            .constant(Value::Int(1))
+            .constant(setter)
+            .instruction(Instruction::LoadConst(0))
+            .instruction(Instruction::StoreLocal(0))
+            .instruction(Instruction::MakeClosure(1, 1))
+            .instruction(Instruction::Call(0))
+            .instruction(Instruction::Pop)
+            .instruction(Instruction::LoadLocal(0))
+            .instruction(Instruction::Return)
+            .build();
+        assert_eq!(vm.execute(chunk).unwrap(), Value::Int(10));
+        assert!(vm.open_upvalues.is_empty());
+    }
+
+    #[test]
+    fn test_vm_upvalue_closed_when_frame_returns() {
+        let mut vm = Vm::new();
+        // (fun n -> fun () -> n) 42 ()
+        let getter = ChunkBuilder::new()
+            .instruction(Instruction::LoadUpvalue(0))
+            .instruction(Instruction::Return)
+            .build();
+        let getter = closure_proto(0, vec![UpvalueSource::Local(0)], getter);
+        let mut maker = ChunkBuilder::new()
+            .constant(getter)
+            .instruction(Instruction::MakeClosure(0, 1))
+            .instruction(Instruction::Return)
+            .build();
+        maker.local_count = 1;
+        let maker = closure_proto(1, vec![], maker);
+
+        let chunk = ChunkBuilder::new()
+            .constant(maker)
+            .constant(Value::Int(42))
+            .instruction(Instruction::MakeClosure(0, 0))
+            .instruction(Instruction::LoadConst(1))
+            .instruction(Instruction::Call(1))
+            .instruction(Instruction::Call(0))
+            .instruction(Instruction::Return)
+            .build();
+        assert_eq!(vm.execute(chunk).unwrap(), Value::Int(42));
+    }
+
+    /// `count n = if n = 0 then depth () else count (n - 1)`, with the
+    /// recursive call made by `call`
+    fn countdown(call: Instruction) -> Value {
+        let mut chunk = ChunkBuilder::new()
+            .constant(Value::Int(0))
+            .constant(Value::Str("depth".to_string()))
+            .constant(Value::Unit)
+            .constant(Value::Str("count".to_string()))
+            .constant(Value::Int(1))
+            .instruction(Instruction::LoadLocal(0))
+            .instruction(Instruction::LoadConst(0))
+            .instruction(Instruction::Eq)
+            .instruction(Instruction::JumpIfFalse(4))
+            .instruction(Instruction::LoadGlobal(1))
+            .instruction(Instruction::LoadConst(2))
+            .instruction(Instruction::Call(1))
+            .instruction(Instruction::Return)
+            .instruction(Instruction::LoadGlobal(3))
+            .instruction(Instruction::LoadLocal(0))
+            .instruction(Instruction::LoadConst(4))
+            .instruction(Instruction::Sub)
+            .instruction(call)
+            .instruction(Instruction::Return)
+            .build();
+        chunk.local_count = 1;
+        closure_proto(1, vec![], chunk)
+    }
+
+    /// A VM whose globals hold `count` and the `depth` native it reports
+    fn countdown_vm(call: Instruction) -> Vm {
+        let mut vm = Vm::new();
+        vm.host_registry
+            .lock()
+            .unwrap()
+            .register("depth", |vm, _| Ok(Value::Int(vm.frame_count() as i64)));
+        vm.globals.insert(
+            "depth".to_string(),
+            Value::NativeFn {
+                name: "depth".to_string(),
+                arity: 1,
+                args: vec![],
+            },
+        );
+        vm.globals.insert("count".to_string(), countdown(call));
+        vm
+    }
+
+    /// Run `count 1000` and report the frame depth at the innermost call
+    fn countdown_depth(call: Instruction) -> Value {
+        let mut vm = countdown_vm(call);
+        let chunk = ChunkBuilder::new()
+            .constant(Value::Str("count".to_string()))
+            .constant(Value::Int(1000))
+            .instruction(Instruction::LoadGlobal(0))
+            .instruction(Instruction::LoadConst(1))
+            .instruction(Instruction::Call(1))
+            .instruction(Instruction::Return)
+            .build();
+        let result = vm.execute(chunk).unwrap();
+        assert_eq!(vm.stack_size(), 0);
+        result
+    }
+
+    #[test]
+    fn test_vm_tail_call_reuses_frame() {
+        assert_eq!(countdown_depth(Instruction::Call(1)), Value::Int(1002));
+        assert_eq!(countdown_depth(Instruction::TailCall(1)), Value::Int(2));
+    }
+
+    /// Run a chunk that jumps to itself forever under `limits`
+    fn run_forever(mut vm: Vm, limits: VmLimits) -> Result<Value, VmError> {
+        vm.set_limits(limits);
+        let chunk = ChunkBuilder::new()
+            .instruction(Instruction::Jump(-1))
+            .build();
+        let result = vm.execute(chunk);
+        assert_eq!(vm.frame_count(), 0);
+        result
+    }
+
+    #[test]
+    fn test_vm_instruction_limit() {
+        let result = run_forever(Vm::new(), VmLimits::default().with_max_instructions(10_000));
+        assert_eq!(
+            result,
+            Err(VmError::LimitExceeded {
+                kind: LimitKind::Instructions
+            })
+        );
+    }
+
+    #[test]
+    fn test_vm_deadline_limit() {
+        let limits = VmLimits::default().with_timeout(std::time::Duration::from_millis(10));
+        assert_eq!(
+            run_forever(Vm::new(), limits),
+            Err(VmError::LimitExceeded {
+                kind: LimitKind::Deadline
+            })
+        );
+    }
+
+    #[test]
+    fn test_vm_heap_limit() {
+        let mut vm = Vm::new();
+        vm.globals
+            .insert("big".to_string(), Value::Str("x".repeat(100_000)));
+        assert!(vm.heap_bytes() > 100_000);
+        assert_eq!(
+            run_forever(vm, VmLimits::default().with_max_heap_bytes(50_000)),
+            Err(VmError::LimitExceeded {
+                kind: LimitKind::HeapBytes
+            })
+        );
+    }
+
+    #[test]
+    fn test_vm_interrupt_from_another_thread() {
+        let vm = Vm::new();
+        let handle = vm.interrupt_handle();
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            handle.interrupt();
+            handle
+        });
+        assert_eq!(
+            run_forever(vm, VmLimits::default()),
+            Err(VmError::Interrupted)
+        );
+        assert!(!canceller.join().unwrap().is_pending());
+    }
+
+    /// A VM with a `pause` native that pauses the script calling it, and a
+    /// chunk that calls it twice before returning 42
+    fn pausing_script() -> (Vm, Chunk) {
+        let mut vm = Vm::new();
+        vm.host_registry.lock().unwrap().register("pause", |vm, _| {
+            vm.interrupt_handle().pause();
+            Ok(Value::Unit)
+        });
+        vm.globals.insert(
+            "pause".to_string(),
+            Value::NativeFn {
+                name: "pause".to_string(),
+                arity: 1,
+                args: vec![],
+            },
+        );
+        let mut builder = ChunkBuilder::new()
+            .constant(Value::Str("pause".to_string()))
+            .constant(Value::Unit)
+            .constant(Value::Int(42));
+        for _ in 0..2 {
+            builder = builder
+                .instruction(Instruction::LoadGlobal(0))
+                .instruction(Instruction::LoadConst(1))
+                .instruction(Instruction::Call(1))
+                .instruction(Instruction::Pop);
+        }
+        let chunk = builder
+            .instruction(Instruction::LoadConst(2))
+            .instruction(Instruction::Return)
+            .build();
+        (vm, chunk)
+    }
+
+    #[test]
+    fn test_vm_pause_and_resume() {
+        let (mut vm, chunk) = pausing_script();
+        assert_eq!(vm.execute_resumable(chunk), Ok(RunState::Paused));
+        assert!(vm.is_paused());
+        assert_eq!(vm.frame_count(), 1);
+        assert_eq!(vm.resume(), Ok(RunState::Paused));
+        assert_eq!(vm.resume(), Ok(RunState::Finished(Value::Int(42))));
+        assert!(!vm.is_paused());
+        assert_eq!(vm.stack_size(), 0);
+        assert!(vm.resume().is_err());
+    }
+
+    #[test]
+    fn test_vm_pause_ignored_outside_resumable_run() {
+        let (mut vm, chunk) = pausing_script();
+        assert_eq!(vm.execute(chunk), Ok(Value::Int(42)));
+        assert!(vm.interrupt_handle().is_pending());
+    }
+
+    #[test]
+    fn test_vm_stack_depth_limit() {
+        let run = |call| {
+            let mut vm = countdown_vm(call);
+            vm.set_limits(VmLimits::default().with_max_stack_depth(100));
+            let count = vm.globals["count"].clone();
+            vm.call_value(count, &[Value::Int(1000)])
+        };
+        assert_eq!(
+            run(Instruction::Call(1)),
+            Err(VmError::LimitExceeded {
+                kind: LimitKind::StackDepth
+            })
+        );
+        assert!(run(Instruction::TailCall(1)).is_ok());
+    }
+
+    #[test]
+    fn test_vm_make_closure_checks_upvalue_count() {
+        let mut vm = Vm::new();
+        let proto = closure_proto(0, vec![UpvalueSource::Local(0)], Chunk::new());
+        let chunk = ChunkBuilder::new()
+            .constant(proto)
+            .instruction(Instruction::MakeClosure(0, 0))
+            .instruction(Instruction::Return)
+            .build();
+        assert!(vm.execute(chunk).is_err());
+    }
+
     #[test]
     fn test_vm_check_variant_tag_not_variant() {
         let mut vm = Vm::new();
@@ -2880,6 +4518,28 @@ mod tests {
 
     // ========== CallMethod on Record Tests ==========
 
+    #[test]
+    fn test_vm_load_global_falls_back_to_module_member() {
+        let mut vm = Vm::new();
+        let mut fields = HashMap::new();
+        fields.insert("answer".to_string(), Value::Int(42));
+        vm.globals.insert(
+            "builder".to_string(),
+            Value::Record(Arc::new(Mutex::new(fields))),
+        );
+
+        let run = |vm: &mut Vm, name: &str| {
+            let chunk = ChunkBuilder::new()
+                .constant(Value::Str(name.to_string()))
+                .instruction(Instruction::LoadGlobal(0))
+                .instruction(Instruction::Return)
+                .build();
+            vm.execute(chunk)
+        };
+        assert_eq!(run(&mut vm, "builder.answer").unwrap(), Value::Int(42));
+        assert!(run(&mut vm, "builder.missing").is_err());
+    }
+
     #[test]
     fn test_vm_call_method_on_record_with_native_fn() {
         use crate::stdlib;
@@ -24,6 +24,26 @@ fn test_serialize_deserialize_chunk_simple() {
     assert_eq!(chunk, restored_chunk);
 }
 
+#[test]
+fn test_serialize_deserialize_chunk_with_char_and_bytes() {
+    let mut chunk = Chunk::new();
+    chunk.add_constant(Value::Char('λ'));
+    chunk.add_constant(Value::Bytes(vec![0x00, 0x7f, 0xff]));
+    chunk.add_constant(Value::Bytes(vec![]));
+    chunk.emit(Instruction::LoadConst(0));
+    chunk.emit(Instruction::Return);
+
+    let bytes = serialize_chunk(&chunk).unwrap();
+    let restored_chunk = deserialize_chunk(&bytes).unwrap();
+
+    assert_eq!(chunk, restored_chunk);
+    assert_eq!(restored_chunk.constants[0], Value::Char('λ'));
+    assert_eq!(
+        restored_chunk.constants[1],
+        Value::Bytes(vec![0x00, 0x7f, 0xff])
+    );
+}
+
 #[test]
 fn test_serialize_deserialize_chunk_with_closure_prototype() {
     let mut inner_chunk = Chunk::new();
@@ -2,8 +2,11 @@
 // Provides ergonomic embedding API for Rust applications
 
 use fusabi_frontend::compiler::CompileOptions;
-use fusabi_frontend::{Compiler, Lexer, Parser};
-use fusabi_vm::{HostData, HostRegistry, Value, Vm, VmError};
+use fusabi_frontend::{CompileWarning, Compiler, Lexer, Parser};
+use fusabi_vm::{
+    Chunk, FastVm, HostData, HostRegistry, InterruptHandle, RunState, Value, Vm, VmContext,
+    VmError, VmLimits,
+};
 use std::any::Any;
 use std::collections::HashMap;
 use std::convert::TryInto;
@@ -158,15 +161,17 @@ impl Module {
 /// assert_eq!(result.as_int(), Some(42));
 /// ```
 pub struct FusabiEngine {
-    vm: Vm,
+    vm: FastVm,
     host_registry: Arc<Mutex<HostRegistry>>,
     global_bindings: HashMap<String, Value>,
+    /// Warnings from the most recent compile
+    warnings: Vec<CompileWarning>,
 }
 
 impl FusabiEngine {
     /// Create a new Fusabi engine
     pub fn new() -> Self {
-        let mut vm = Vm::new();
+        let mut vm = FastVm::new();
 
         // Register standard library functions and modules
         fusabi_vm::stdlib::register_stdlib(&mut vm);
@@ -179,6 +184,7 @@ impl FusabiEngine {
             vm,
             host_registry,
             global_bindings: HashMap::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -225,6 +231,7 @@ impl FusabiEngine {
     ///     enable_type_checking: true,
     ///     verbose: false,
     ///     strict_mode: true,
+    ///     ..Default::default()
     /// };
     /// let result = engine.eval_with_options("let x = 42 in x * 2", options).unwrap();
     /// assert_eq!(result.as_int(), Some(84));
@@ -234,6 +241,66 @@ impl FusabiEngine {
         source: &str,
         options: crate::RunOptions,
     ) -> Result<Value, crate::FusabiError> {
+        let chunk = self.compile(source, &options)?;
+
+        // Stage 4: Execution
+        let engine_limits = self.vm.limits();
+        if let Some(limits) = options.limits {
+            self.vm.set_limits(limits);
+        }
+        let result = crate::run_chunk(&mut self.vm, chunk, options.engine);
+        self.vm.set_limits(engine_limits);
+
+        Ok(result?)
+    }
+
+    /// Evaluate a Fusabi script in a run that can be paused and resumed
+    ///
+    /// Pause the run from any thread with [`InterruptHandle::pause`]; the
+    /// script's state stays in the engine until [`FusabiEngine::resume`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use fusabi::{Engine, RunState};
+    ///
+    /// let mut engine = Engine::new();
+    /// let mut state = engine.eval_resumable("let x = 42 in x * 2").unwrap();
+    /// while state == RunState::Paused {
+    ///     state = engine.resume().unwrap();
+    /// }
+    /// ```
+    pub fn eval_resumable(&mut self, source: &str) -> Result<RunState, crate::FusabiError> {
+        let chunk = self.compile(source, &crate::RunOptions::default())?;
+        let state = match crate::EngineKind::from_env() {
+            crate::EngineKind::Fast => self.vm.execute_resumable(chunk),
+            crate::EngineKind::Standard => Vm::execute_resumable(&mut self.vm, chunk),
+        };
+        Ok(state?)
+    }
+
+    /// Continue a script that [`FusabiEngine::eval_resumable`] left paused
+    pub fn resume(&mut self) -> Result<RunState, crate::FusabiError> {
+        let state = match crate::EngineKind::from_env() {
+            crate::EngineKind::Fast => self.vm.resume(),
+            crate::EngineKind::Standard => Vm::resume(&mut self.vm),
+        };
+        Ok(state?)
+    }
+
+    /// Get a handle that cancels or pauses scripts run by this engine
+    ///
+    /// The handle is `Send + Sync`, so a UI thread can stop a runaway script
+    /// while another thread evaluates it.
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.vm.interrupt_handle()
+    }
+
+    /// Lex, parse and compile `source` with the engine's globals in scope
+    fn compile(
+        &mut self,
+        source: &str,
+        options: &crate::RunOptions,
+    ) -> Result<Chunk, crate::FusabiError> {
         // Sync global_bindings to vm.globals before execution
         for (name, value) in self.global_bindings.iter() {
             self.vm.globals.insert(name.clone(), value.clone());
@@ -254,12 +321,70 @@ impl FusabiEngine {
             allow_warnings: !options.strict_mode,
             provider_resolver: None, // Type providers can be configured separately
         };
-        let chunk = Compiler::compile_with_options(&ast, compile_options)?;
+        let (chunk, warnings) = Compiler::compile_with_warnings(&ast, compile_options)?;
+        if options.report_warnings {
+            crate::print_warnings(&warnings);
+        }
+        self.warnings = warnings;
+        Ok(chunk)
+    }
 
-        // Stage 4: Execution
-        let result = self.vm.execute(chunk)?;
+    /// Warnings from the most recently compiled script, such as incomplete
+    /// matches and unreachable match arms
+    ///
+    /// # Example
+    /// ```no_run
+    /// use fusabi::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.eval("match Some 1 with | Some y -> y").unwrap();
+    /// assert_eq!(engine.warnings().len(), 1);
+    /// ```
+    pub fn warnings(&self) -> &[CompileWarning] {
+        &self.warnings
+    }
 
-        Ok(result)
+    /// Set the execution budgets applied to every evaluation and call
+    ///
+    /// # Example
+    /// ```no_run
+    /// use fusabi::{Engine, VmLimits};
+    /// use std::time::Duration;
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.set_limits(VmLimits::default().with_timeout(Duration::from_secs(1)));
+    /// assert!(engine.eval("while true do ()").is_err());
+    /// ```
+    pub fn set_limits(&mut self, limits: VmLimits) {
+        self.vm.set_limits(limits);
+    }
+
+    /// Get the execution budgets applied to every evaluation and call
+    pub fn limits(&self) -> VmLimits {
+        self.vm.limits()
+    }
+
+    /// Get the stdlib state of this engine: event handlers, config values,
+    /// commands and terminal providers
+    pub fn context(&self) -> &VmContext {
+        &self.vm.context
+    }
+
+    /// Replace the stdlib state of this engine
+    ///
+    /// Each engine starts with its own state. Passing another engine's
+    /// context makes both see the same handlers, config and commands.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use fusabi::Engine;
+    ///
+    /// let plugin = Engine::new();
+    /// let mut host = Engine::new();
+    /// host.set_context(plugin.context().clone());
+    /// ```
+    pub fn set_context(&mut self, context: VmContext) {
+        self.vm.context = context;
     }
 
     /// Register a host function with dynamic arity
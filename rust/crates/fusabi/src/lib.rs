@@ -78,20 +78,24 @@
 //! assert_eq!(result.as_int(), Some(42));
 //! ```
 
-use fusabi_frontend::{Compiler, Lexer, Parser};
-use fusabi_vm::{deserialize_chunk, serialize_chunk, Chunk, Vm, VmError, FZB_MAGIC};
+use fusabi_frontend::{Compiler, FileLoader, Lexer, Parser};
+use fusabi_vm::{deserialize_chunk, serialize_chunk, Chunk, FastVm, Vm, VmError, FZB_MAGIC};
 use std::error::Error;
 use std::fmt;
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::string::FromUtf8Error;
 
 pub mod host_api;
 
 // Re-export the primary API at the crate root for easy access
-pub use fusabi_vm::{HostData, Value};
+pub use fusabi_vm::{
+    HostData, InterruptHandle, LimitKind, RunState, Value, VmContext, VmLimits,
+    DEFAULT_MAX_NATIVE_STACK_BYTES, DEFAULT_MAX_STACK_DEPTH,
+};
 pub use host_api::{FusabiEngine as Engine, Module};
 // Re-export CompileOptions for advanced compilation control
-pub use fusabi_frontend::CompileOptions;
+pub use fusabi_frontend::{CompileOptions, CompileWarning};
 
 // ============================================================================
 // Script.eval Implementation
@@ -173,6 +177,8 @@ pub enum FusabiError {
     Lex(fusabi_frontend::LexError),
     /// Parser error during parsing
     Parse(fusabi_frontend::ParseError),
+    /// Error loading a file named by a `#load` directive
+    Load(fusabi_frontend::LoadError),
     /// Compiler error during bytecode generation
     Compile(fusabi_frontend::CompileError),
     /// VM runtime error during execution
@@ -189,6 +195,7 @@ impl fmt::Display for FusabiError {
             FusabiError::Io(e) => write!(f, "IO Error: {}", e),
             FusabiError::Lex(e) => write!(f, "Lexer Error: {}", e),
             FusabiError::Parse(e) => write!(f, "Parser Error: {}", e),
+            FusabiError::Load(e) => write!(f, "Load Error: {}", e),
             FusabiError::Compile(e) => write!(f, "Compiler Error: {}", e),
             FusabiError::Runtime(e) => write!(f, "Runtime Error: {}", e),
             FusabiError::Serde(e) => write!(f, "Serialization Error: {}", e),
@@ -203,6 +210,7 @@ impl Error for FusabiError {
             FusabiError::Io(e) => Some(e),
             FusabiError::Lex(e) => Some(e),
             FusabiError::Parse(e) => Some(e),
+            FusabiError::Load(e) => Some(e),
             FusabiError::Compile(e) => Some(e),
             FusabiError::Runtime(e) => Some(e),
             FusabiError::Serde(e) => Some(e.as_ref()),
@@ -229,6 +237,12 @@ impl From<fusabi_frontend::ParseError> for FusabiError {
     }
 }
 
+impl From<fusabi_frontend::LoadError> for FusabiError {
+    fn from(err: fusabi_frontend::LoadError) -> Self {
+        FusabiError::Load(err)
+    }
+}
+
 impl From<fusabi_frontend::CompileError> for FusabiError {
     fn from(err: fusabi_frontend::CompileError) -> Self {
         FusabiError::Compile(err)
@@ -253,6 +267,53 @@ impl From<FromUtf8Error> for FusabiError {
     }
 }
 
+/// Interpreter loop used to execute compiled scripts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EngineKind {
+    /// The optimized [`FastVm`] dispatch loop
+    #[default]
+    Fast,
+    /// The standard [`Vm`] dispatch loop
+    Standard,
+}
+
+impl EngineKind {
+    /// Engine named by the `FUSABI_ENGINE` environment variable
+    ///
+    /// `vm` or `standard` selects [`EngineKind::Standard`]; anything else,
+    /// including an unset variable, selects [`EngineKind::Fast`].
+    pub fn from_env() -> Self {
+        match std::env::var("FUSABI_ENGINE").as_deref() {
+            Ok("vm") | Ok("standard") => EngineKind::Standard,
+            _ => EngineKind::Fast,
+        }
+    }
+}
+
+/// Run a compiled chunk on a fresh VM with the standard library registered
+fn execute_chunk(chunk: Chunk, options: &RunOptions) -> Result<Value, FusabiError> {
+    let mut vm = FastVm::new();
+    fusabi_vm::stdlib::register_stdlib(&mut vm);
+    // Override Script.eval with real implementation that has compiler access
+    register_script_eval_override(&mut vm);
+    if let Some(limits) = options.limits {
+        vm.set_limits(limits);
+    }
+    Ok(run_chunk(&mut vm, chunk, options.engine)?)
+}
+
+/// Run a chunk on `vm` with the selected dispatch loop
+pub(crate) fn run_chunk(
+    vm: &mut FastVm,
+    chunk: Chunk,
+    engine: Option<EngineKind>,
+) -> Result<Value, VmError> {
+    match engine.unwrap_or_else(EngineKind::from_env) {
+        EngineKind::Fast => vm.execute(chunk),
+        EngineKind::Standard => Vm::execute(vm, chunk),
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct RunOptions {
     /// Enable type checking before compilation
@@ -261,6 +322,13 @@ pub struct RunOptions {
     pub verbose: bool,
     /// Strict mode - treat warnings as errors
     pub strict_mode: bool,
+    /// Print compile warnings to stderr
+    pub report_warnings: bool,
+    /// Interpreter loop to run on; `None` uses [`EngineKind::from_env`]
+    pub engine: Option<EngineKind>,
+    /// Execution budgets; `None` runs with [`VmLimits::default`], or with the
+    /// engine's own limits when evaluating through [`Engine`]
+    pub limits: Option<VmLimits>,
 }
 
 /// Execute Mini-F# source code from a string (backward compatible)
@@ -278,7 +346,15 @@ pub fn run_source_checked(source: &str) -> Result<Value, FusabiError> {
 }
 
 /// Execute Mini-F# source code with custom run options
+///
+/// `#load` directives are resolved against the current directory.
 pub fn run_source_with_options(source: &str, options: RunOptions) -> Result<Value, FusabiError> {
+    run_source_from(source, Path::new(""), options)
+}
+
+/// Execute source read from `path`, resolving its `#load` directives
+/// relative to that file
+fn run_source_from(source: &str, path: &Path, options: RunOptions) -> Result<Value, FusabiError> {
     if options.verbose {
         println!("=== Fusabi Execution Pipeline ===");
         println!("Type checking: {}", options.enable_type_checking);
@@ -305,14 +381,30 @@ pub fn run_source_with_options(source: &str, options: RunOptions) -> Result<Valu
     if options.verbose {
         println!("  Parsed AST successfully");
     }
+    let loaded = FileLoader::new(PathBuf::from(".")).load_directives(&program, path)?;
+    if options.verbose && !loaded.is_empty() {
+        println!("  Loaded {} file(s)", loaded.len());
+    }
 
     // Stage 3: Compilation (with optional type checking)
     if options.verbose {
         println!("Stage 3: Compilation");
     }
-    // Note: compile_program doesn't support custom options yet, using default compilation
-    let chunk = Compiler::compile_program(&program)?;
+    let compile_options = CompileOptions {
+        enable_type_checking: options.enable_type_checking,
+        strict_mode: options.strict_mode,
+        allow_warnings: !options.strict_mode,
+        provider_resolver: None,
+    };
+    let (chunk, warnings) =
+        Compiler::compile_program_with_loads(&program, &loaded, compile_options)?;
+    if options.report_warnings {
+        print_warnings(&warnings);
+    }
     if options.verbose {
+        for warning in &warnings {
+            println!("  Warning: {}", warning);
+        }
         println!("  Generated {} instructions", chunk.instructions.len());
         println!("  Constant pool size: {}", chunk.constants.len());
     }
@@ -321,13 +413,7 @@ pub fn run_source_with_options(source: &str, options: RunOptions) -> Result<Valu
     if options.verbose {
         println!("Stage 4: Execution");
     }
-    let mut vm = Vm::new();
-    // Register standard library functions and globals
-    fusabi_vm::stdlib::register_stdlib(&mut vm);
-    // Override Script.eval with real implementation that has compiler access
-    register_script_eval_override(&mut vm);
-
-    let result = vm.execute(chunk)?;
+    let result = execute_chunk(chunk, &options)?;
     if options.verbose {
         println!("  Result: {:?}", result);
         println!();
@@ -336,37 +422,39 @@ pub fn run_source_with_options(source: &str, options: RunOptions) -> Result<Valu
     Ok(result)
 }
 
+/// Print compile warnings to stderr
+pub(crate) fn print_warnings(warnings: &[CompileWarning]) {
+    for warning in warnings {
+        eprintln!("Warning: {}", warning);
+    }
+}
+
 /// Execute a Mini-F# script from a file (backward compatible)
 pub fn run_file(path: &str) -> Result<Value, FusabiError> {
-    let bytes = fs::read(path)?;
-
-    if bytes.starts_with(FZB_MAGIC) {
-        // It's a pre-compiled bytecode file (.fzb)
-        let chunk = deserialize_chunk(&bytes)?;
-
-        let mut vm = Vm::new();
-        fusabi_vm::stdlib::register_stdlib(&mut vm);
-        // Override Script.eval with real implementation that has compiler access
-        register_script_eval_override(&mut vm);
-        let result = vm.execute(chunk)?;
-        Ok(result)
-    } else {
-        // It's a source file (.fsx), compile it
-        let source = String::from_utf8(bytes)?;
-        run_source(&source)
-    }
+    run_file_with_options(path, RunOptions::default())
 }
 
 /// Execute a Mini-F# script from a file with type checking enabled
 pub fn run_file_checked(path: &str) -> Result<Value, FusabiError> {
     let source = fs::read_to_string(path)?;
-    run_source_checked(&source)
+    let options = RunOptions {
+        enable_type_checking: true,
+        ..Default::default()
+    };
+    run_source_from(&source, Path::new(path), options)
 }
 
-/// Execute a Mini-F# script from a file with custom options
+/// Execute a Mini-F# script or `.fzb` bytecode file with custom options
 pub fn run_file_with_options(path: &str, options: RunOptions) -> Result<Value, FusabiError> {
-    let source = fs::read_to_string(path)?;
-    run_source_with_options(&source, options)
+    let bytes = fs::read(path)?;
+
+    if bytes.starts_with(FZB_MAGIC) {
+        let chunk = deserialize_chunk(&bytes)?;
+        execute_chunk(chunk, &options)
+    } else {
+        let source = String::from_utf8(bytes)?;
+        run_source_from(&source, Path::new(path), options)
+    }
 }
 
 /// Execute source with optional disassembly output (backward compatible)
@@ -380,7 +468,9 @@ pub fn run_source_with_disasm(source: &str, name: &str) -> Result<Value, FusabiE
     let program = parser.parse_program()?;
 
     // Stage 3: Compilation
-    let chunk = Compiler::compile_program(&program)?;
+    let (chunk, warnings) =
+        Compiler::compile_program_with_warnings(&program, CompileOptions::default())?;
+    print_warnings(&warnings);
 
     // Disassemble the chunk
     println!("\n=== Disassembly of '{}' ===", name);
@@ -388,15 +478,7 @@ pub fn run_source_with_disasm(source: &str, name: &str) -> Result<Value, FusabiE
     println!("=== End Disassembly ===\n");
 
     // Stage 4: Execution
-    let mut vm = Vm::new();
-    // Register standard library functions and globals
-    fusabi_vm::stdlib::register_stdlib(&mut vm);
-    // Override Script.eval with real implementation that has compiler access
-    register_script_eval_override(&mut vm);
-
-    let result = vm.execute(chunk)?;
-
-    Ok(result)
+    execute_chunk(chunk, &RunOptions::default())
 }
 
 /// Execute file with optional disassembly output
@@ -419,7 +501,10 @@ pub fn run_file_with_disasm(path: &str) -> Result<Value, FusabiError> {
         let program = parser.parse_program()?;
 
         // Stage 3: Compilation
-        Compiler::compile_program(&program)?
+        let (chunk, warnings) =
+            Compiler::compile_program_with_warnings(&program, CompileOptions::default())?;
+        print_warnings(&warnings);
+        chunk
     };
 
     // Disassemble the chunk
@@ -428,13 +513,7 @@ pub fn run_file_with_disasm(path: &str) -> Result<Value, FusabiError> {
     println!("=== End Disassembly ===\n");
 
     // Stage 4: Execution
-    let mut vm = Vm::new();
-    fusabi_vm::stdlib::register_stdlib(&mut vm);
-    // Override Script.eval with real implementation that has compiler access
-    register_script_eval_override(&mut vm);
-    let result = vm.execute(chunk)?;
-
-    Ok(result)
+    execute_chunk(chunk, &RunOptions::default())
 }
 
 // ============================================================================
@@ -668,13 +747,7 @@ pub fn compile_to_chunk(source: &str) -> Result<Chunk, FusabiError> {
 pub fn execute_bytecode(bytecode: &[u8]) -> Result<Value, FusabiError> {
     let chunk = deserialize_chunk(bytecode)?;
 
-    let mut vm = Vm::new();
-    fusabi_vm::stdlib::register_stdlib(&mut vm);
-    // Override Script.eval with real implementation that has compiler access
-    register_script_eval_override(&mut vm);
-    let result = vm.execute(chunk)?;
-
-    Ok(result)
+    execute_chunk(chunk, &RunOptions::default())
 }
 
 /// Compile and execute source in one step, returning both bytecode and result
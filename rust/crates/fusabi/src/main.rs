@@ -14,6 +14,9 @@
 //! # Run with disassembly output
 //! fus run --disasm examples/arithmetic.fsx
 //!
+//! # Treat compile warnings as errors
+//! fus run --strict examples/arithmetic.fsx
+//!
 //! # Evaluate an expression directly
 //! fus run -e "let x = 42 in x + 1"
 //!
@@ -28,7 +31,10 @@
 //! ```
 
 use colored::*;
-use fusabi::{run_file, run_file_with_disasm, run_source, run_source_with_disasm};
+use fusabi::{
+    run_file_with_disasm, run_file_with_options, run_source_with_disasm, run_source_with_options,
+    RunOptions,
+};
 use fusabi_frontend::{Compiler, Lexer, Parser};
 use std::env;
 use std::fs;
@@ -76,10 +82,14 @@ fn print_help() {
     println!("    -v, --version       Show version information");
     println!("    -e, --eval <EXPR>   Evaluate an expression directly (run mode only)");
     println!("    -d, --disasm        Show bytecode disassembly before execution");
+    println!("    -s, --strict        Treat compile warnings as errors (run mode only)");
     println!();
     println!("{}", "ARGUMENTS:".bold());
     println!("    FILE                Path to .fsx script file");
     println!();
+    println!("{}", "ENVIRONMENT:".bold());
+    println!("    FUSABI_ENGINE=vm    Run on the standard VM instead of the fast VM");
+    println!();
     println!("{}", "EXAMPLES:".bold());
     println!(
         "    {}",
@@ -126,6 +136,7 @@ fn print_help() {
 struct Config {
     mode: Mode,
     disasm: bool,
+    strict: bool,
 }
 
 enum Mode {
@@ -142,6 +153,7 @@ fn parse_args() -> Result<Config, String> {
 
     let mut mode = None;
     let mut disasm = false;
+    let mut strict = false;
     let mut i = 1;
 
     // Check for global flags first
@@ -170,6 +182,10 @@ fn parse_args() -> Result<Config, String> {
                             disasm = true;
                             i += 1;
                         }
+                        "-s" | "--strict" => {
+                            strict = true;
+                            i += 1;
+                        }
                         "-e" | "--eval" => {
                             if i + 1 >= args.len() {
                                 return Err("--eval requires an expression argument".to_string());
@@ -215,10 +231,19 @@ fn parse_args() -> Result<Config, String> {
 
     let mode = mode.unwrap_or_else(|| Mode::RunFile("examples/hello.fus".to_string()));
 
-    Ok(Config { mode, disasm })
+    Ok(Config {
+        mode,
+        disasm,
+        strict,
+    })
 }
 
 fn run(config: Config) -> Result<(), Box<dyn std::error::Error>> {
+    let options = RunOptions {
+        strict_mode: config.strict,
+        report_warnings: true,
+        ..Default::default()
+    };
     match config.mode {
         Mode::Help => {
             print_help();
@@ -236,7 +261,7 @@ fn run(config: Config) -> Result<(), Box<dyn std::error::Error>> {
             let result = if config.disasm {
                 run_source_with_disasm(&expr, "<eval>")?
             } else {
-                run_source(&expr)?
+                run_source_with_options(&expr, options)?
             };
             println!("{}", result);
             Ok(())
@@ -245,7 +270,7 @@ fn run(config: Config) -> Result<(), Box<dyn std::error::Error>> {
             let result = if config.disasm {
                 run_file_with_disasm(&path)?
             } else {
-                run_file(&path)?
+                run_file_with_options(&path, options)?
             };
             println!("{}", result);
             Ok(())
@@ -0,0 +1,165 @@
+// Integration tests for active patterns
+// These tests define and apply single-case, multi-case and partial active
+// patterns through the full pipeline
+
+use fusabi::{run_source, run_source_checked};
+use fusabi_vm::Value;
+
+fn strs(values: &[&str]) -> Value {
+    Value::vec_to_cons(values.iter().map(|s| Value::Str(s.to_string())).collect())
+}
+
+#[cfg(test)]
+mod multi_case_tests {
+    use super::*;
+
+    #[test]
+    fn test_even_odd() {
+        let source = r#"
+            let (|Even|Odd|) n = if n % 2 = 0 then Even else Odd in
+            let describe n =
+                match n with
+                | Even -> "even"
+                | Odd -> "odd"
+            in
+            [describe 1; describe 2]
+        "#;
+        let result = run_source(source).expect("Failed to run Even/Odd");
+        assert_eq!(result, strs(&["odd", "even"]));
+    }
+
+    #[test]
+    fn test_cases_carry_values() {
+        let source = r#"
+            let (|Pos|Neg|Zero|) n =
+                if n > 0 then Pos n else if n < 0 then Neg (0 - n) else Zero
+            in
+            let magnitude n =
+                match n with
+                | Pos p -> p
+                | Neg m -> m * 10
+                | Zero -> 0
+            in
+            (magnitude 4, magnitude (0 - 3), magnitude 0)
+        "#;
+        let result = run_source_checked(source).expect("Failed to run Pos/Neg/Zero");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![Value::Int(4), Value::Int(30), Value::Int(0)])
+        );
+    }
+
+    #[test]
+    fn test_nested_under_tuple_and_guard() {
+        let source = r#"
+            let (|Even|Odd|) n = if n % 2 = 0 then Even else Odd in
+            let classify pair =
+                match pair with
+                | (Even, Even) -> "both"
+                | (Odd, n) when n > 10 -> "odd and big"
+                | _ -> "other"
+            in
+            [classify (2, 4); classify (1, 11); classify (1, 2)]
+        "#;
+        let result = run_source(source).expect("Failed to run nested active patterns");
+        assert_eq!(result, strs(&["both", "odd and big", "other"]));
+    }
+
+    #[test]
+    fn test_applied_directly() {
+        let source = r#"
+            let (|Even|Odd|) n = if n % 2 = 0 then Even else Odd in
+            (|Even|Odd|) 7
+        "#;
+        let result = run_source(source).expect("Failed to apply active pattern");
+        assert!(matches!(result, Value::Variant { ref variant_name, .. } if variant_name == "Odd"));
+    }
+}
+
+#[cfg(test)]
+mod partial_tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_pattern_falls_through() {
+        let source = r#"
+            let (|Digit|_|) c = if Char.isDigit c then Some (Char.toInt c - 48) else None
+            in
+            let value c =
+                match c with
+                | Digit d -> d
+                | _ -> -1
+            in
+            (value '7', value 'x')
+        "#;
+        let result = run_source_checked(source).expect("Failed to run partial pattern");
+        assert_eq!(result, Value::Tuple(vec![Value::Int(7), Value::Int(-1)]));
+    }
+
+    #[test]
+    fn test_partial_patterns_for_command_parsing() {
+        let source = r#"
+            let (|Prefix|_|) (s: string) =
+                if String.startsWith "--" s then Some (String.trim s) else None
+            in
+            let (|Flag|_|) (s: string) =
+                if String.startsWith "-" s then Some s else None
+            in
+            let kind arg =
+                match arg with
+                | Prefix p -> "long " ++ p
+                | Flag f -> "short " ++ f
+                | other -> "value " ++ other
+            in
+            [kind "--verbose"; kind "-v"; kind "file.txt"]
+        "#;
+        let result = run_source(source).expect("Failed to run command parsing");
+        assert_eq!(
+            result,
+            strs(&["long --verbose", "short -v", "value file.txt"])
+        );
+    }
+
+    #[test]
+    fn test_partial_with_tuple_payload() {
+        let source = r#"
+            let (|Split|_|) (s: string) =
+                match String.split "=" s with
+                | [k; v] -> Some (k, v)
+                | _ -> None
+            in
+            match "key=value" with
+            | Split (k, v) -> v ++ ":" ++ k
+            | _ -> "none"
+        "#;
+        let result = run_source(source).expect("Failed to run tuple payload");
+        assert_eq!(result, Value::Str("value:key".to_string()));
+    }
+}
+
+#[cfg(test)]
+mod single_case_tests {
+    use super::*;
+
+    #[test]
+    fn test_single_case_transforms_value() {
+        let source = r#"
+            let (|Upper|) (s: string) = String.toUpper s in
+            match "abc" with
+            | Upper u -> u
+        "#;
+        let result = run_source_checked(source).expect("Failed to run single-case pattern");
+        assert_eq!(result, Value::Str("ABC".to_string()));
+    }
+
+    #[test]
+    fn test_single_case_in_nested_pattern() {
+        let source = r#"
+            let (|Twice|) n = n * 2 in
+            match (3, 4) with
+            | (Twice a, Twice b) -> a + b
+        "#;
+        let result = run_source(source).expect("Failed to run nested single-case pattern");
+        assert_eq!(result, Value::Int(14));
+    }
+}
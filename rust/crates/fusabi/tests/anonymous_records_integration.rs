@@ -484,3 +484,13 @@ fn test_anonymous_record_inline_expressions() {
         assert_eq!(*borrowed.get("flag").unwrap(), Value::Bool(true));
     }
 }
+
+#[test]
+fn test_anonymous_record_as_function_argument() {
+    let source = r#"
+        let area r = r.width * r.height in
+        area {| width = 6; height = 7 |}
+    "#;
+    let result = run_source(source).unwrap();
+    assert_eq!(result, Value::Int(42));
+}
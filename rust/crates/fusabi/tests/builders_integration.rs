@@ -0,0 +1,128 @@
+// Integration tests for computation expression builders
+// These tests run the built-in option/result builders and builders defined
+// in scripts as records of functions through the full pipeline
+
+use fusabi::{run_source, run_source_checked};
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod option_builder_tests {
+    use super::*;
+
+    #[test]
+    fn test_option_binds_until_none() {
+        let source = r#"
+            let parse (s: string) = if s = "1" then Some 1 else if s = "2" then Some 2 else None in
+            let add a b = option { let! x = parse a; let! y = parse b; return x + y } in
+            (add "1" "2", add "1" "x")
+        "#;
+        let result = run_source(source).expect("Failed to run option builder");
+        assert_eq!(result.to_string(), "(Some(3), None)");
+    }
+
+    #[test]
+    fn test_option_return_from_and_plain_let() {
+        let source = r#"
+            option {
+                let! x = Some 20
+                let y = x + 1
+                return! Some (y * 2)
+            }
+        "#;
+        let result = run_source(source).expect("Failed to run return!");
+        assert_eq!(result.to_string(), "Some(42)");
+    }
+
+    #[test]
+    fn test_option_builder_type_checks() {
+        let source = "option { let! x = Some 1; return x + 1 }";
+        let result = run_source_checked(source).expect("Failed to type check option builder");
+        assert_eq!(result.to_string(), "Some(2)");
+    }
+}
+
+#[cfg(test)]
+mod result_builder_tests {
+    use super::*;
+
+    #[test]
+    fn test_result_railway() {
+        let source = r#"
+            let check n = if n > 0 then Ok n else Error "not positive" in
+            let area w h = result { let! a = check w; let! b = check h; return a * b } in
+            (area 3 4, area 3 (0 - 1))
+        "#;
+        let result = run_source(source).expect("Failed to run result builder");
+        assert_eq!(result.to_string(), "(Ok(12), Error(not positive))");
+    }
+
+    #[test]
+    fn test_result_stops_before_later_steps() {
+        let source = r#"
+            let mutable steps = 0 in
+            let step n = (let d = (steps <- steps + 1) in if n > 1 then Error n else Ok n) in
+            let outcome = result { let! a = step 1; let! b = step 2; let! c = step 3; return a + b + c } in
+            (outcome, steps)
+        "#;
+        let result = run_source(source).expect("Failed to run short-circuiting result");
+        assert_eq!(result.to_string(), "(Error(2), 2)");
+    }
+}
+
+#[cfg(test)]
+mod custom_builder_tests {
+    use super::*;
+
+    #[test]
+    fn test_record_of_functions_as_builder() {
+        let source = r#"
+            let maybe = {
+                Bind = fun m f -> (match m with | Some x -> f x | None -> None);
+                Return = fun x -> Some x
+            } in
+            (maybe { let! x = Some 20; let! y = Some 22; return x + y },
+             maybe { let! x = None; return x })
+        "#;
+        let result = run_source(source).expect("Failed to run custom builder");
+        assert_eq!(result.to_string(), "(Some(42), None)");
+    }
+
+    #[test]
+    fn test_builder_with_yield_and_for() {
+        let source = r#"
+            let listOf = {
+                Yield = fun x -> [x];
+                YieldFrom = fun xs -> xs;
+                Combine = fun a b -> List.append a b;
+                Delay = fun f -> f ();
+                Zero = fun u -> [];
+                For = fun xs f -> List.fold (fun acc x -> List.append acc (f x)) [] xs
+            } in
+            listOf { yield 0; for x in [1; 2; 3; 4] do if x % 2 = 0 then yield x * 10; yield! [99] }
+        "#;
+        let result = run_source(source).expect("Failed to run yielding builder");
+        assert_eq!(
+            result,
+            Value::vec_to_cons(vec![
+                Value::Int(0),
+                Value::Int(20),
+                Value::Int(40),
+                Value::Int(99),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_builder_captured_by_closure() {
+        let source = r#"
+            let logged = { Bind = fun m f -> f m; Return = fun x -> (x, "done") } in
+            let run n = logged { let! a = n + 1; return a * 2 } in
+            run 4
+        "#;
+        let result = run_source(source).expect("Failed to run captured builder");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![Value::Int(10), Value::Str("done".to_string())])
+        );
+    }
+}
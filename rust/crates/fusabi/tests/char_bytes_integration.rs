@@ -0,0 +1,123 @@
+// Integration tests for char and byte[] values
+// These tests run char literals, byte strings and the Char/Bytes modules
+// through the full pipeline
+
+use fusabi::run_source;
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod char_tests {
+    use super::*;
+
+    #[test]
+    fn test_char_literals_and_escapes() {
+        let source = r"('a', '\n', '\'', '\\')";
+        let result = run_source(source).expect("Failed to run char literals");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![
+                Value::Char('a'),
+                Value::Char('\n'),
+                Value::Char('\''),
+                Value::Char('\\'),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_char_pattern_match() {
+        let source = r#"
+            let classify c =
+                match c with
+                | 'a' -> "a"
+                | ' ' -> "space"
+                | _ -> if Char.isDigit c then "digit" else "other"
+            in
+            (classify 'a', classify ' ', classify '4', classify 'z')
+        "#;
+        let result = run_source(source).expect("Failed to run char match");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![
+                Value::Str("a".to_string()),
+                Value::Str("space".to_string()),
+                Value::Str("digit".to_string()),
+                Value::Str("other".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_char_comparison() {
+        let source = "('a' < 'b', 'z' >= 'a', 'q' = 'q', 'q' <> 'r')";
+        let result = run_source(source).expect("Failed to run char comparison");
+        assert_eq!(result, Value::Tuple(vec![Value::Bool(true); 4]));
+    }
+
+    #[test]
+    fn test_string_to_chars_and_back() {
+        let source = r#"String.ofChars (List.map Char.toUpper (String.toChars "fusabi"))"#;
+        let result = run_source(source).expect("Failed to run String.toChars");
+        assert_eq!(result, Value::Str("FUSABI".to_string()));
+    }
+
+    #[test]
+    fn test_char_code_points() {
+        let source = "(Char.toInt 'A', Char.ofInt 97)";
+        let result = run_source(source).expect("Failed to run char conversions");
+        assert_eq!(result, Value::Tuple(vec![Value::Int(65), Value::Char('a')]));
+    }
+
+    #[test]
+    fn test_type_variables_still_lex() {
+        let source = "let id (x: 'a) : 'a = x in (id 'c', id 1)";
+        let result = run_source(source).expect("Failed to run generic annotation");
+        assert_eq!(result, Value::Tuple(vec![Value::Char('c'), Value::Int(1)]));
+    }
+}
+
+#[cfg(test)]
+mod bytes_tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_string_literal() {
+        let source = r#"let b : byte[] = "GET"B in b"#;
+        let result = run_source(source).expect("Failed to run byte string");
+        assert_eq!(result, Value::Bytes(b"GET".to_vec()));
+    }
+
+    #[test]
+    fn test_bytes_module() {
+        let source = r#"
+            let b = Bytes.append "ab"B (Bytes.ofList [0; 255]) in
+            (Bytes.length b, Bytes.get 3 b, Bytes.toList (Bytes.ofString "hi"))
+        "#;
+        let result = run_source(source).expect("Failed to run Bytes module");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![
+                Value::Int(4),
+                Value::Int(255),
+                Value::vec_to_cons(vec![Value::Int(104), Value::Int(105)]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_bytes_round_trip_utf8() {
+        let source = r#"Bytes.toString (Bytes.ofString "héllo")"#;
+        let result = run_source(source).expect("Failed to run UTF-8 round trip");
+        assert_eq!(result, Value::Str("héllo".to_string()));
+    }
+
+    #[test]
+    fn test_bytes_equality() {
+        let source = r#"("abc"B = Bytes.ofString "abc", "abc"B = "abd"B)"#;
+        let result = run_source(source).expect("Failed to run bytes equality");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![Value::Bool(true), Value::Bool(false)])
+        );
+    }
+}
@@ -0,0 +1,115 @@
+// Integration tests for ranges and list/array comprehensions
+// These tests run `[a .. b]`, `[ for .. -> .. ]` and `yield` through the full pipeline
+
+use fusabi::run_source;
+use fusabi_vm::Value;
+
+fn ints(values: &[i64]) -> Value {
+    Value::vec_to_cons(values.iter().map(|&n| Value::Int(n)).collect())
+}
+
+#[cfg(test)]
+mod range_tests {
+    use super::*;
+
+    #[test]
+    fn test_range_is_inclusive() {
+        let result = run_source("[1 .. 5]").expect("Failed to build range");
+        assert_eq!(result, ints(&[1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn test_stepped_ranges() {
+        let result = run_source("[0 .. 2 .. 10]").expect("Failed to build stepped range");
+        assert_eq!(result, ints(&[0, 2, 4, 6, 8, 10]));
+        let result = run_source("[10 .. -3 .. 1]").expect("Failed to build descending range");
+        assert_eq!(result, ints(&[10, 7, 4, 1]));
+    }
+
+    #[test]
+    fn test_empty_range() {
+        let result = run_source("[5 .. 1]").expect("Failed to build empty range");
+        assert_eq!(result, Value::Nil);
+    }
+
+    #[test]
+    fn test_float_range() {
+        let result = run_source("[0.0 .. 0.5 .. 1.0]").expect("Failed to build float range");
+        assert_eq!(
+            result,
+            Value::vec_to_cons(vec![
+                Value::Float(0.0),
+                Value::Float(0.5),
+                Value::Float(1.0)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_zero_step_is_an_error() {
+        assert!(run_source("[1 .. 0 .. 3]").is_err());
+    }
+
+    #[test]
+    fn test_array_range() {
+        let result = run_source("Array.length [| 1 .. 4 |]").expect("Failed to build array range");
+        assert_eq!(result, Value::Int(4));
+    }
+
+    #[test]
+    fn test_for_over_stepped_range() {
+        let source = "let mutable s = 0 in let d = (for x in 0 .. 3 .. 12 do s <- s + x) in s";
+        let result = run_source(source).expect("Failed to loop over stepped range");
+        assert_eq!(result, Value::Int(30));
+    }
+}
+
+#[cfg(test)]
+mod comprehension_tests {
+    use super::*;
+
+    #[test]
+    fn test_arrow_comprehension() {
+        let source = "let squares = fun n -> [ for i in 1 .. n -> i * i ] in squares 4";
+        let result = run_source(source).expect("Failed to run comprehension");
+        assert_eq!(result, ints(&[1, 4, 9, 16]));
+    }
+
+    #[test]
+    fn test_filtered_array_comprehension() {
+        let source = r#"
+            let xs = [1; 2; 3; 4; 5] in
+            let arr = [| for x in xs do if x > 2 then yield x * 10 |] in
+            (Array.length arr, arr.[0])
+        "#;
+        let result = run_source(source).expect("Failed to run array comprehension");
+        assert_eq!(result, Value::Tuple(vec![Value::Int(3), Value::Int(30)]));
+    }
+
+    #[test]
+    fn test_nested_loops_yield_in_order() {
+        let source = "[ for x in [1; 2] do for y in [10; 20] do yield x + y ]";
+        let result = run_source(source).expect("Failed to run nested comprehension");
+        assert_eq!(result, ints(&[11, 21, 12, 22]));
+    }
+
+    #[test]
+    fn test_yield_bang_splices_collections() {
+        let source = "let xs = [1; 2] in [ yield 0; yield! xs; yield! [| 3; 4 |]; yield 5 ]";
+        let result = run_source(source).expect("Failed to splice with yield!");
+        assert_eq!(result, ints(&[0, 1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn test_nested_comprehensions_keep_separate_results() {
+        let source = "[ for n in 1 .. 3 -> List.length [ for i in 1 .. n -> i ] ]";
+        let result = run_source(source).expect("Failed to run nested comprehensions");
+        assert_eq!(result, ints(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_yield_outside_comprehension_is_an_error() {
+        let err = run_source("let f = fun x -> yield x in f 1").unwrap_err();
+        assert!(err.to_string().contains("comprehension"));
+    }
+}
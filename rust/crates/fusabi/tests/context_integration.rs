@@ -0,0 +1,84 @@
+// Integration tests for per-engine stdlib state
+// Each engine keeps its own event handlers, config and providers unless the
+// host shares them explicitly with `set_context`
+
+use fusabi::Engine;
+use fusabi_vm::stdlib::terminal_control::TerminalControlProvider;
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod context_tests {
+    use super::*;
+
+    /// A provider whose splits always create pane 7
+    struct FixedPaneProvider;
+
+    impl TerminalControlProvider for FixedPaneProvider {
+        fn send_text(&self, _text: &str) {}
+        fn send_keys(&self, _keys: &[String]) {}
+        fn split_horizontal(&self) -> Option<i64> {
+            Some(7)
+        }
+        fn split_vertical(&self) -> Option<i64> {
+            Some(7)
+        }
+        fn close_pane(&self, _pane_id: i64) -> bool {
+            false
+        }
+        fn focus_pane(&self, _pane_id: i64) -> bool {
+            false
+        }
+        fn create_tab(&self) -> Option<i64> {
+            None
+        }
+        fn close_tab(&self, _tab_id: i64) -> bool {
+            false
+        }
+        fn set_tab_title(&self, _tab_id: i64, _title: &str) -> bool {
+            false
+        }
+        fn show_toast(&self, _message: &str) {}
+    }
+
+    fn handler_count(engine: &mut Engine) -> Value {
+        engine.eval("Events.handlers \"Ping\"").unwrap()
+    }
+
+    #[test]
+    fn test_engines_have_separate_event_handlers() {
+        let mut first = Engine::new();
+        let mut second = Engine::new();
+
+        first.eval("Events.on \"Ping\" (fun x -> ())").unwrap();
+
+        assert_eq!(handler_count(&mut first), Value::Int(1));
+        assert_eq!(handler_count(&mut second), Value::Int(0));
+        assert!(!first.context().is_shared_with(second.context()));
+    }
+
+    #[test]
+    fn test_shared_context_sees_the_same_handlers() {
+        let mut first = Engine::new();
+        let mut second = Engine::new();
+        second.set_context(first.context().clone());
+
+        first.eval("Events.on \"Ping\" (fun x -> ())").unwrap();
+
+        assert_eq!(handler_count(&mut second), Value::Int(1));
+        assert_eq!(second.eval("Events.emit \"Ping\" 1").unwrap(), Value::Unit);
+        second.eval("Events.clear \"Ping\"").unwrap();
+        assert_eq!(handler_count(&mut first), Value::Int(0));
+    }
+
+    #[test]
+    fn test_terminal_provider_is_per_engine() {
+        let mut first = Engine::new();
+        let mut second = Engine::new();
+        *first.context().terminal_control.lock().unwrap() = Some(Box::new(FixedPaneProvider));
+
+        let split = first.eval("TerminalControl.splitVertical ()").unwrap();
+        assert_eq!(split.variant_name(), Ok("Some"));
+        let split = second.eval("TerminalControl.splitVertical ()").unwrap();
+        assert_eq!(split.variant_name(), Ok("None"));
+    }
+}
@@ -0,0 +1,161 @@
+// Integration tests for failwith, raise, try ... with and try ... finally
+// These tests run exception handling through the full pipeline
+
+use fusabi::run_source;
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod try_with_tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_failwith() {
+        let source = r#"try failwith "boom" with | Failure msg -> msg"#;
+        let result = run_source(source).expect("Failed to catch failwith");
+        assert_eq!(result, Value::Str("boom".to_string()));
+    }
+
+    #[test]
+    fn test_body_value_when_nothing_raised() {
+        let source = r#"try 1 + 2 with | Failure _ -> 0"#;
+        let result = run_source(source).expect("Failed to run try body");
+        assert_eq!(result, Value::Int(3));
+    }
+
+    #[test]
+    fn test_catch_raised_value() {
+        let source = r#"try raise (Failure "custom") with | Failure msg -> msg"#;
+        let result = run_source(source).expect("Failed to catch raise");
+        assert_eq!(result, Value::Str("custom".to_string()));
+    }
+
+    #[test]
+    fn test_catch_host_error() {
+        let source = r#"
+            try List.head [] with
+            | HostError(msg, _, _) -> msg
+        "#;
+        let result = run_source(source).expect("Failed to catch host error");
+        assert_eq!(
+            result,
+            Value::Str("Cannot access head/tail of empty list".to_string())
+        );
+    }
+
+    #[test]
+    fn test_catch_division_by_zero() {
+        let result = run_source("try 1 / 0 with e -> 42").expect("Failed to catch 1 / 0");
+        assert_eq!(result, Value::Int(42));
+    }
+
+    #[test]
+    fn test_catch_runtime_error_as_host_error() {
+        let source = r#"
+            try 10 % 0 with
+            | HostError(msg, _, _) -> msg
+        "#;
+        let result = run_source(source).expect("Failed to catch 10 % 0");
+        assert_eq!(result, Value::Str("Division by zero".to_string()));
+    }
+
+    #[test]
+    fn test_uncaught_runtime_error_is_unchanged() {
+        let err = run_source("1 / 0").unwrap_err();
+        assert!(err.to_string().contains("Division by zero"));
+    }
+
+    #[test]
+    fn test_catch_inside_function() {
+        let source = r#"
+            let safeDiv = fun b ->
+                try (if b = 0 then failwith "div" else 10 / b) with
+                | Failure _ -> 0
+            in safeDiv 0 + safeDiv 2
+        "#;
+        let result = run_source(source).expect("Failed to catch inside function");
+        assert_eq!(result, Value::Int(5));
+    }
+
+    #[test]
+    fn test_catch_through_host_callback() {
+        let source = r#"
+            let check = fun x -> if x > 2 then failwith "too big" else x in
+            try List.map check [1; 2; 3] with
+            | Failure msg -> [0]
+        "#;
+        let result = run_source(source).expect("Failed to catch through List.map");
+        assert_eq!(result, Value::vec_to_cons(vec![Value::Int(0)]));
+    }
+
+    #[test]
+    fn test_unmatched_exception_is_raised_again() {
+        let source = r#"
+            try (try failwith "inner" with | HostError(m, _, _) -> m) with
+            | Failure msg -> msg
+        "#;
+        let result = run_source(source).expect("Failed to re-raise");
+        assert_eq!(result, Value::Str("inner".to_string()));
+    }
+
+    #[test]
+    fn test_uncaught_failwith_is_an_error() {
+        let err = run_source(r#"failwith "top level""#).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unhandled exception: Failure(top level)"));
+    }
+
+    #[test]
+    fn test_uncaught_host_error_keeps_original_message() {
+        let err = run_source("List.head []").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Cannot access head/tail of empty list"));
+    }
+}
+
+#[cfg(test)]
+mod try_finally_tests {
+    use super::*;
+
+    #[test]
+    fn test_finally_keeps_body_value() {
+        let source = r#"try 1 + 2 finally print """#;
+        let result = run_source(source).expect("Failed to run try/finally");
+        assert_eq!(result, Value::Int(3));
+    }
+
+    #[test]
+    fn test_finally_runs_after_normal_exit() {
+        let source = r#"try (try 1 finally failwith "cleanup") with | Failure msg -> msg"#;
+        let result = run_source(source).expect("Failed to run finalizer");
+        assert_eq!(result, Value::Str("cleanup".to_string()));
+    }
+
+    #[test]
+    fn test_finally_runs_before_exception_propagates() {
+        let source = r#"
+            try (try failwith "body" finally failwith "cleanup") with
+            | Failure msg -> msg
+        "#;
+        let result = run_source(source).expect("Failed to run finalizer on raise");
+        assert_eq!(result, Value::Str("cleanup".to_string()));
+    }
+
+    #[test]
+    fn test_finally_reraises_exception() {
+        let source = r#"try (try failwith "body" finally 0) with | Failure msg -> msg"#;
+        let result = run_source(source).expect("Failed to propagate through finally");
+        assert_eq!(result, Value::Str("body".to_string()));
+    }
+
+    #[test]
+    fn test_break_out_of_try_removes_handler() {
+        let source = r#"
+            let done = (while true do try break with | _ -> ()) in
+            failwith "after loop"
+        "#;
+        let err = run_source(source).unwrap_err();
+        assert!(err.to_string().contains("Failure(after loop)"));
+    }
+}
@@ -0,0 +1,156 @@
+// Integration tests for `for i = a to b`, `for i = a downto b` and `for pat in source`
+// These tests run loops through the full pipeline
+
+use fusabi::run_source;
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod counted_loop_tests {
+    use super::*;
+
+    #[test]
+    fn test_for_to_sums_range() {
+        let source = "let mutable s = 0 in let d = (for i = 1 to 10 do s <- s + i) in s";
+        let result = run_source(source).expect("Failed to run for/to loop");
+        assert_eq!(result, Value::Int(55));
+    }
+
+    #[test]
+    fn test_for_downto_visits_in_reverse() {
+        let source = "let mutable xs = [] in let d = (for i = 3 downto 1 do xs <- i :: xs) in xs";
+        let result = run_source(source).expect("Failed to run for/downto loop");
+        assert_eq!(
+            result,
+            Value::vec_to_cons(vec![Value::Int(1), Value::Int(2), Value::Int(3)])
+        );
+    }
+
+    #[test]
+    fn test_empty_range_skips_body() {
+        let source = "let mutable n = 0 in let d = (for i = 5 to 1 do n <- n + 1) in n";
+        let result = run_source(source).expect("Failed to run empty range");
+        assert_eq!(result, Value::Int(0));
+    }
+
+    #[test]
+    fn test_bounds_are_evaluated_once() {
+        let source = r#"
+            let mutable calls = 0 in
+            let limit = fun u -> let c = (calls <- calls + 1) in 3 in
+            let d = (for i = 1 to limit () do ()) in
+            calls
+        "#;
+        let result = run_source(source).expect("Failed to evaluate bounds");
+        assert_eq!(result, Value::Int(1));
+    }
+
+    #[test]
+    fn test_range_form_with_break_and_continue() {
+        let source = r#"
+            let mutable s = 0 in
+            let d = (for i in 1 .. 100 do
+                if i = 2 then continue
+                else if i > 4 then break
+                else s <- s + i) in
+            s
+        "#;
+        let result = run_source(source).expect("Failed to run range loop");
+        assert_eq!(result, Value::Int(8));
+    }
+
+    #[test]
+    fn test_closures_capture_each_counter_value() {
+        let source = r#"
+            let mutable fs = [] in
+            let d = (for i = 1 to 3 do fs <- (fun u -> i) :: fs) in
+            List.map (fun f -> f ()) fs
+        "#;
+        let result = run_source(source).expect("Failed to capture loop variable");
+        assert_eq!(
+            result,
+            Value::vec_to_cons(vec![Value::Int(3), Value::Int(2), Value::Int(1)])
+        );
+    }
+
+    #[test]
+    fn test_nested_loops_break_inner_only() {
+        let source = r#"
+            let mutable n = 0 in
+            let d = (for i = 1 to 3 do
+                for j = 1 to 3 do
+                    if j = 2 then break else n <- n + 1) in
+            n
+        "#;
+        let result = run_source(source).expect("Failed to run nested loops");
+        assert_eq!(result, Value::Int(3));
+    }
+}
+
+#[cfg(test)]
+mod collection_loop_tests {
+    use super::*;
+
+    #[test]
+    fn test_for_in_list() {
+        let source = "let mutable s = 0 in let d = (for x in [1; 2; 3] do s <- s + x) in s";
+        let result = run_source(source).expect("Failed to iterate list");
+        assert_eq!(result, Value::Int(6));
+    }
+
+    #[test]
+    fn test_for_in_array() {
+        let source = "let mutable s = 0 in let d = (for x in [|4; 5|] do s <- s * 10 + x) in s";
+        let result = run_source(source).expect("Failed to iterate array");
+        assert_eq!(result, Value::Int(45));
+    }
+
+    #[test]
+    fn test_for_in_map_yields_sorted_pairs() {
+        let source = r#"
+            let m = Map.ofList [("b", 2); ("a", 1)] in
+            let mutable keys = "" in
+            let mutable total = 0 in
+            let d = (for (k, v) in m do
+                let t = (total <- total + v) in
+                keys <- keys ++ k) in
+            (keys, total)
+        "#;
+        let result = run_source(source).expect("Failed to iterate map");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![Value::Str("ab".to_string()), Value::Int(3)])
+        );
+    }
+
+    #[test]
+    fn test_for_in_skips_elements_the_pattern_rejects() {
+        let source = r#"
+            let mutable s = 0 in
+            let d = (for Some x in [Some 1; None; Some 5] do s <- s + x) in
+            s
+        "#;
+        let result = run_source(source).expect("Failed to filter by pattern");
+        assert_eq!(result, Value::Int(6));
+    }
+
+    #[test]
+    fn test_continue_captures_each_element() {
+        let source = r#"
+            let mutable fs = [] in
+            let d = (for x in [1; 2; 3] do
+                if x = 2 then continue else fs <- (fun u -> x) :: fs) in
+            List.map (fun f -> f ()) fs
+        "#;
+        let result = run_source(source).expect("Failed to capture elements");
+        assert_eq!(
+            result,
+            Value::vec_to_cons(vec![Value::Int(3), Value::Int(1)])
+        );
+    }
+
+    #[test]
+    fn test_for_in_non_collection_is_an_error() {
+        let err = run_source("for x in 5 do x").unwrap_err();
+        assert!(err.to_string().contains("expected list, array or map"));
+    }
+}
@@ -0,0 +1,59 @@
+// Integration tests for generic records and discriminated unions
+// These tests declare types with 'a parameters and run them through the full pipeline
+
+use fusabi::run_source;
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod generic_types_tests {
+    use super::*;
+
+    #[test]
+    fn test_generic_tree() {
+        let source = r#"
+type Tree<'a> = Leaf | Node of Tree<'a> * 'a * Tree<'a>
+let rec sum t =
+    match t with
+    | Leaf -> 0
+    | Node(l, v, r) -> sum l + v + sum r
+sum (Node(Node(Leaf, 1, Leaf), 2, Node(Leaf, 3, Leaf)))
+"#;
+        let result = run_source(source).expect("Failed to run generic tree");
+        assert_eq!(result, Value::Int(6));
+    }
+
+    #[test]
+    fn test_generic_record() {
+        let source = r#"
+type Page<'t> = { Items: 't list; Next: string option }
+let page = { Items = ["a"; "b"]; Next = Some "cursor" }
+match page.Next with
+| Some next -> next
+| None -> "done"
+"#;
+        let result = run_source(source).expect("Failed to run generic record");
+        assert_eq!(result, Value::Str("cursor".to_string()));
+    }
+
+    #[test]
+    fn test_multi_parameter_union() {
+        let source = r#"
+type Either<'l, 'r> = Left of 'l | Right of 'r
+let describe e =
+    match e with
+    | Left n -> n * 2
+    | Right s -> 0
+describe (Left 21)
+"#;
+        let result = run_source(source).expect("Failed to run two-parameter union");
+        assert_eq!(result, Value::Int(42));
+    }
+
+    #[test]
+    fn test_undeclared_type_variable_is_rejected() {
+        let err = run_source("type Box<'a> = Box of 'b\n0").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("type variable 'b is not declared by type Box"));
+    }
+}
@@ -0,0 +1,69 @@
+// Integration tests for interpolated strings ($"...") and format strings
+// These tests run interpolation through the full pipeline
+
+use fusabi::{run_source, run_source_checked};
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod interpolation_tests {
+    use super::*;
+
+    #[test]
+    fn test_untyped_holes() {
+        let source = r#"let name = "Ann" in let xs = [1; 2] in $"{name} has {xs}""#;
+        let result = run_source(source).expect("Failed to interpolate");
+        assert_eq!(result, Value::Str("Ann has [1; 2]".to_string()));
+    }
+
+    #[test]
+    fn test_typed_holes() {
+        let source = r#"let count = 3 in $"{count:%d} items at {2.5:%.2f} each""#;
+        let result = run_source(source).expect("Failed to interpolate typed holes");
+        assert_eq!(result, Value::Str("3 items at 2.50 each".to_string()));
+    }
+
+    #[test]
+    fn test_escaped_braces_and_percent() {
+        let source = r#"let n = 50 in $"{{{n}%}}""#;
+        let result = run_source(source).expect("Failed to interpolate escapes");
+        assert_eq!(result, Value::Str("{50%}".to_string()));
+    }
+
+    #[test]
+    fn test_holes_hold_full_expressions() {
+        let source = r#"let f x = x * 2 in $"{f 21} {if f 1 > 1 then "big" else "small"}""#;
+        let result = run_source(source).expect("Failed to interpolate expressions");
+        assert_eq!(result, Value::Str("42 big".to_string()));
+    }
+
+    #[test]
+    fn test_typed_hole_with_wrong_value_fails_at_runtime() {
+        let err = run_source(r#"$"{"three":%d}""#).unwrap_err();
+        assert!(err.to_string().contains("Expected int for %d"));
+    }
+
+    #[test]
+    fn test_invalid_specifier_is_a_parse_error() {
+        let err = run_source(r#"let x = 1 in $"{x:%z}""#).unwrap_err();
+        assert!(err.to_string().contains("invalid format specifier '%z'"));
+    }
+
+    #[test]
+    fn test_checked_run_accepts_mixed_holes() {
+        let source = r#"let name = "Ann" in $"{name}: {3:%d}""#;
+        let result = run_source_checked(source).expect("Failed to run checked");
+        assert_eq!(result, Value::Str("Ann: 3".to_string()));
+    }
+
+    #[test]
+    fn test_checked_run_rejects_typed_hole_with_wrong_value() {
+        let err = run_source_checked(r#"let count = "three" in $"{count:%d} items""#).unwrap_err();
+        assert!(err.to_string().contains("Type error"), "got: {}", err);
+    }
+
+    #[test]
+    fn test_checked_run_rejects_sprintf_argument_of_wrong_type() {
+        let err = run_source_checked(r#"sprintf "%d and %s" ["x"; "y"]"#).unwrap_err();
+        assert!(err.to_string().contains("Type error"), "got: {}", err);
+    }
+}
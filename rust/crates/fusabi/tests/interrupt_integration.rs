@@ -0,0 +1,104 @@
+// Integration tests for interrupt handles
+// Hosts cancel running scripts from another thread, and pause scripts to
+// time-slice several engines on one thread
+
+use fusabi::{Engine, FusabiError, RunState};
+use fusabi_vm::{Value, VmError};
+use std::time::Duration;
+
+#[cfg(test)]
+mod interrupt_tests {
+    use super::*;
+
+    /// An engine whose `pauseHere ()` pauses the script calling it
+    fn slicing_engine() -> Engine {
+        let mut engine = Engine::new();
+        engine.register_raw("pauseHere", |vm, _| {
+            vm.interrupt_handle().pause();
+            Ok(Value::Unit)
+        });
+        engine
+    }
+
+    #[test]
+    fn test_cancel_from_another_thread() {
+        let mut engine = Engine::new();
+        let handle = engine.interrupt_handle();
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            handle.interrupt();
+        });
+        let result = engine.eval("while true do ()");
+        canceller.join().unwrap();
+        assert!(matches!(
+            result,
+            Err(FusabiError::Runtime(VmError::Interrupted))
+        ));
+        assert_eq!(engine.eval("1 + 2").unwrap(), Value::Int(3));
+    }
+
+    #[test]
+    fn test_cancel_is_not_catchable() {
+        let mut engine = Engine::new();
+        engine.register_raw("cancel", |vm, _| {
+            vm.interrupt_handle().interrupt();
+            Ok(Value::Unit)
+        });
+        let result = engine.eval("try List.map (fun x -> cancel ()) [1; 2] with | _ -> []");
+        assert!(matches!(
+            result,
+            Err(FusabiError::Runtime(VmError::Interrupted))
+        ));
+    }
+
+    #[test]
+    fn test_pause_after_cancel_still_cancels() {
+        let mut engine = Engine::new();
+        engine.register_raw("cancelThenPause", |vm, _| {
+            let handle = vm.interrupt_handle();
+            handle.interrupt();
+            handle.pause();
+            Ok(Value::Unit)
+        });
+        let result = engine.eval("let u = cancelThenPause () in while true do ()");
+        assert!(matches!(
+            result,
+            Err(FusabiError::Runtime(VmError::Interrupted))
+        ));
+    }
+
+    #[test]
+    fn test_cancel_while_idle_does_not_fail_next_eval() {
+        let mut engine = Engine::new();
+        engine.interrupt_handle().interrupt();
+        assert_eq!(engine.eval("1 + 1").unwrap(), Value::Int(2));
+        assert!(!engine.interrupt_handle().is_pending());
+    }
+
+    #[test]
+    fn test_time_slice_two_engines() {
+        let source = |n: i64| {
+            format!(
+                "let rec sum n acc = if n = 0 then acc else (let p = pauseHere () in sum (n - 1) (acc + n)) in sum {} 0",
+                n
+            )
+        };
+        let mut engines = [slicing_engine(), slicing_engine()];
+        let mut states = vec![
+            engines[0].eval_resumable(&source(10)).unwrap(),
+            engines[1].eval_resumable(&source(20)).unwrap(),
+        ];
+        let mut slices = 0;
+        while states.contains(&RunState::Paused) {
+            for (engine, state) in engines.iter_mut().zip(states.iter_mut()) {
+                if *state == RunState::Paused {
+                    *state = engine.resume().unwrap();
+                    slices += 1;
+                }
+            }
+        }
+        assert_eq!(states[0], RunState::Finished(Value::Int(55)));
+        assert_eq!(states[1], RunState::Finished(Value::Int(210)));
+        assert_eq!(slices, 30);
+    }
+}
@@ -0,0 +1,165 @@
+// Integration tests for execution limits
+// Budgets set through RunOptions or the engine stop runaway scripts, and
+// scripts cannot swallow the resulting error with try ... with
+
+use fusabi::{run_source_with_options, Engine, FusabiError, LimitKind, RunOptions, VmLimits};
+use fusabi_vm::{Value, VmError};
+use std::time::Duration;
+
+#[cfg(test)]
+mod limits_tests {
+    use super::*;
+
+    fn limit_kind(result: Result<Value, FusabiError>) -> Option<LimitKind> {
+        match result {
+            Err(FusabiError::Runtime(VmError::LimitExceeded { kind })) => Some(kind),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_instruction_budget_stops_infinite_loop() {
+        let options = RunOptions {
+            limits: Some(VmLimits::default().with_max_instructions(100_000)),
+            ..Default::default()
+        };
+        let result = run_source_with_options("while true do ()", options);
+        assert_eq!(limit_kind(result), Some(LimitKind::Instructions));
+    }
+
+    #[test]
+    fn test_engine_deadline_stops_infinite_loop() {
+        let mut engine = Engine::new();
+        engine.set_limits(VmLimits::default().with_timeout(Duration::from_millis(50)));
+        assert_eq!(
+            limit_kind(engine.eval("while true do ()")),
+            Some(LimitKind::Deadline)
+        );
+    }
+
+    #[test]
+    fn test_engine_timeout_restarts_for_each_eval() {
+        let mut engine = Engine::new();
+        engine.set_limits(VmLimits::default().with_timeout(Duration::from_millis(50)));
+        std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(engine.eval("1 + 2").unwrap(), Value::Int(3));
+        assert_eq!(
+            limit_kind(engine.eval("while true do ()")),
+            Some(LimitKind::Deadline)
+        );
+    }
+
+    #[test]
+    fn test_default_limits_stop_unbounded_recursion() {
+        let result = run_source_with_options("let rec f x = 1 + f x in f 0", RunOptions::default());
+        assert_eq!(limit_kind(result), Some(LimitKind::StackDepth));
+        let mut engine = Engine::new();
+        assert_eq!(
+            limit_kind(engine.eval("let rec f x = 1 + f x in f 0")),
+            Some(LimitKind::StackDepth)
+        );
+    }
+
+    /// Recursion where every level goes through a `List.map` callback
+    fn callback_recursion(depth: u32) -> String {
+        format!(
+            "let rec f n = if n = 0 then 0 else List.head (List.map (fun x -> f (n - 1) + x) [1]) in f {}",
+            depth
+        )
+    }
+
+    #[test]
+    fn test_default_limits_stop_recursion_through_callbacks() {
+        let result = run_source_with_options(&callback_recursion(100_000), RunOptions::default());
+        assert_eq!(limit_kind(result), Some(LimitKind::StackDepth));
+        let result = run_source_with_options(&callback_recursion(3), RunOptions::default());
+        assert_eq!(result.unwrap(), Value::Int(3));
+    }
+
+    #[test]
+    fn test_large_range_stops_at_deadline() {
+        let options = RunOptions {
+            limits: Some(VmLimits::default().with_timeout(Duration::from_millis(300))),
+            ..Default::default()
+        };
+        let result = run_source_with_options("List.length [1 .. 300000000]", options);
+        assert_eq!(limit_kind(result), Some(LimitKind::Deadline));
+    }
+
+    #[test]
+    fn test_seq_consumers_stop_at_deadline() {
+        for source in [
+            "Seq.length (seq { 1 .. 1000000000 })",
+            "Seq.toList (Seq.filter (fun x -> x < 0) (seq { 1 .. 1000000000 }))",
+        ] {
+            let options = RunOptions {
+                limits: Some(VmLimits::default().with_timeout(Duration::from_millis(300))),
+                ..Default::default()
+            };
+            let result = run_source_with_options(source, options);
+            assert_eq!(limit_kind(result), Some(LimitKind::Deadline), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_seq_consumers_count_toward_instruction_budget() {
+        let options = RunOptions {
+            limits: Some(VmLimits::default().with_max_instructions(100_000)),
+            ..Default::default()
+        };
+        let result = run_source_with_options("Seq.length (seq { 1 .. 1000000000 })", options);
+        assert_eq!(limit_kind(result), Some(LimitKind::Instructions));
+    }
+
+    #[test]
+    fn test_large_range_checked_against_heap_cap() {
+        let options = RunOptions {
+            limits: Some(VmLimits::default().with_max_heap_bytes(10_000_000)),
+            ..Default::default()
+        };
+        let result = run_source_with_options("List.length [1 .. 300000000]", options);
+        assert_eq!(limit_kind(result), Some(LimitKind::HeapBytes));
+        let options = RunOptions {
+            limits: Some(VmLimits::default().with_max_heap_bytes(10_000_000)),
+            ..Default::default()
+        };
+        let result = run_source_with_options("List.length [1 .. 1000]", options);
+        assert_eq!(result.unwrap(), Value::Int(1000));
+    }
+
+    #[test]
+    fn test_stack_depth_limit() {
+        let source = "let rec f n = if n = 0 then 0 else 1 + f (n - 1)\nf 500";
+        let options = RunOptions {
+            limits: Some(VmLimits::default().with_max_stack_depth(100)),
+            ..Default::default()
+        };
+        let result = run_source_with_options(source, options);
+        assert_eq!(limit_kind(result), Some(LimitKind::StackDepth));
+    }
+
+    #[test]
+    fn test_limit_is_not_catchable() {
+        let source = "try List.map (fun x -> while true do ()) [1] with | _ -> []";
+        let options = RunOptions {
+            limits: Some(VmLimits::default().with_max_instructions(100_000)),
+            ..Default::default()
+        };
+        let result = run_source_with_options(source, options);
+        assert_eq!(limit_kind(result), Some(LimitKind::Instructions));
+    }
+
+    #[test]
+    fn test_engine_usable_after_limit() {
+        let mut engine = Engine::new();
+        let options = RunOptions {
+            limits: Some(VmLimits::default().with_max_instructions(10_000)),
+            ..Default::default()
+        };
+        assert!(engine
+            .eval_with_options("while true do ()", options)
+            .is_err());
+        assert_eq!(engine.limits(), VmLimits::default());
+        assert_eq!(engine.eval("1 + 2").unwrap(), Value::Int(3));
+    }
+}
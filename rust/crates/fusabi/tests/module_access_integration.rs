@@ -0,0 +1,84 @@
+// Integration tests for private and internal module bindings
+// Hidden bindings stay usable inside their module but cannot be reached
+// through `open` or a qualified name; internal ones are also hidden from
+// files that `#load` their module
+
+use fusabi::{run_file, run_source};
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod module_access_tests {
+    use super::*;
+
+    const GEOMETRY: &str = r#"
+module Geometry =
+    let private square x = x * x
+    let internal norm2 x y = square x + square y
+    let rec private countDown n = if n <= 0 then 0 else 1 + countDown (n - 1)
+    let distance2 x y = norm2 x y + countDown 0
+"#;
+
+    #[test]
+    fn test_private_helpers_used_inside_module() {
+        let source = format!("open Geometry\n{}\n(distance2 3 4, norm2 1 2)", GEOMETRY);
+        let result = run_source(&source).expect("Failed to use module bindings");
+        assert_eq!(result, Value::Tuple(vec![Value::Int(25), Value::Int(5)]));
+    }
+
+    #[test]
+    fn test_open_does_not_import_private_bindings() {
+        let source = format!("open Geometry\n{}\ncountDown 3", GEOMETRY);
+        assert!(run_source(&source).is_err());
+    }
+
+    #[test]
+    fn test_qualified_private_binding_is_an_error() {
+        let source = format!("{}\nGeometry.square 3", GEOMETRY);
+        let err = run_source(&source).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'Geometry.square' is private and cannot be used outside its module"),
+            "{}",
+            err
+        );
+    }
+
+    /// Write `lib.fsx` and a main script that loads it, then run the script
+    fn run_with_loaded_lib(test: &str, main: &str) -> Result<Value, fusabi::FusabiError> {
+        let dir = std::env::temp_dir().join(format!("fusabi_module_access_{}", test));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("lib.fsx"),
+            "module Lib =\n    let internal secret = 41\n    let api = secret + 1\n",
+        )
+        .unwrap();
+        let main_path = dir.join("main.fsx");
+        std::fs::write(&main_path, format!("#load \"lib.fsx\"\n{}", main)).unwrap();
+        let result = run_file(main_path.to_str().unwrap());
+        std::fs::remove_dir_all(&dir).ok();
+        result
+    }
+
+    #[test]
+    fn test_loaded_module_public_binding_uses_internal_helper() {
+        let result = run_with_loaded_lib("public", "Lib.api").expect("Failed to load lib.fsx");
+        assert_eq!(result, Value::Int(42));
+    }
+
+    #[test]
+    fn test_loaded_module_internal_binding_is_an_error() {
+        let err = run_with_loaded_lib("internal", "Lib.secret").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'Lib.secret' is internal and cannot be used outside its module"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_internal_binding_visible_in_same_file() {
+        let source = "module Lib =\n    let internal secret = 41\n\nLib.secret";
+        assert_eq!(run_source(source).unwrap(), Value::Int(41));
+    }
+}
@@ -0,0 +1,159 @@
+// Integration tests for let mutable, <- assignment, closure capture and ref cells
+// These tests run mutation through the full pipeline
+
+use fusabi::run_source;
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod mutable_local_tests {
+    use super::*;
+
+    #[test]
+    fn test_while_loop_counter() {
+        let source = r#"
+            let mutable i = 0 in
+            let mutable sum = 0 in
+            let done = (while i < 5 do
+                let step = (i <- i + 1) in
+                sum <- sum + i) in
+            sum
+        "#;
+        let result = run_source(source).expect("Failed to run counter loop");
+        assert_eq!(result, Value::Int(15));
+    }
+
+    #[test]
+    fn test_assignment_returns_unit() {
+        let source = "let mutable x = 1 in x <- 2";
+        let result = run_source(source).expect("Failed to assign");
+        assert_eq!(result, Value::Unit);
+    }
+
+    #[test]
+    fn test_top_level_mutable() {
+        let source = r#"
+let mutable total = 0
+do total <- total + 5
+do total <- total * 2
+total
+"#;
+        let result = run_source(source).expect("Failed to update top-level mutable");
+        assert_eq!(result, Value::Int(10));
+    }
+
+    #[test]
+    fn test_assign_to_immutable_is_an_error() {
+        let err = run_source("let x = 1 in x <- 2").unwrap_err();
+        assert!(err.to_string().contains("Cannot assign to 'x'"));
+    }
+
+    #[test]
+    fn test_assign_to_parameter_is_an_error() {
+        let err = run_source("let f = fun x -> x <- 1 in f 0").unwrap_err();
+        assert!(err.to_string().contains("value is not mutable"));
+    }
+}
+
+#[cfg(test)]
+mod closure_capture_tests {
+    use super::*;
+
+    #[test]
+    fn test_curried_function_captures_argument() {
+        let source = "let add x y = x + y in let add2 = add 2 in add2 40";
+        let result = run_source(source).expect("Failed to capture argument");
+        assert_eq!(result, Value::Int(42));
+    }
+
+    #[test]
+    fn test_recursive_function_captures_itself() {
+        let source = "let rec fact n = if n <= 1 then 1 else n * fact (n - 1) in fact 5";
+        let result = run_source(source).expect("Failed to run recursive closure");
+        assert_eq!(result, Value::Int(120));
+    }
+
+    #[test]
+    fn test_top_level_function_captures_binding() {
+        let source = r#"
+let offset = 10
+let shift n = n + offset
+shift 5
+"#;
+        let result = run_source(source).expect("Failed to capture top-level binding");
+        assert_eq!(result, Value::Int(15));
+    }
+
+    #[test]
+    fn test_closure_mutates_captured_variable() {
+        let source = r#"
+            let mutable count = 0 in
+            let incr = fun u -> count <- count + 1 in
+            let a = incr () in
+            let b = incr () in
+            count
+        "#;
+        let result = run_source(source).expect("Failed to mutate captured variable");
+        assert_eq!(result, Value::Int(2));
+    }
+
+    #[test]
+    fn test_captured_mutable_outlives_its_scope() {
+        let source = r#"
+            let counter = (let mutable n = 0 in fun u -> let step = (n <- n + 1) in n) in
+            let a = counter () in
+            let b = counter () in
+            a * 10 + b
+        "#;
+        let result = run_source(source).expect("Failed to keep captured mutable");
+        assert_eq!(result, Value::Int(12));
+    }
+
+    #[test]
+    fn test_each_loop_iteration_captures_fresh_binding() {
+        let source = r#"
+            let mutable fs = [] in
+            let mutable i = 0 in
+            let done = (while i < 3 do
+                let j = i in
+                let saved = (fs <- (fun u -> j) :: fs) in
+                i <- i + 1) in
+            List.map (fun f -> f ()) fs
+        "#;
+        let result = run_source(source).expect("Failed to capture loop variable");
+        assert_eq!(
+            result,
+            Value::vec_to_cons(vec![Value::Int(2), Value::Int(1), Value::Int(0)])
+        );
+    }
+}
+
+#[cfg(test)]
+mod ref_cell_tests {
+    use super::*;
+
+    #[test]
+    fn test_ref_cell_update() {
+        let source = "let r = ref 1 in let u = (r := !r + 10) in !r";
+        let result = run_source(source).expect("Failed to update ref cell");
+        assert_eq!(result, Value::Int(11));
+    }
+
+    #[test]
+    fn test_ref_cell_contents_field() {
+        let source = "let r = ref \"a\" in r.contents";
+        let result = run_source(source).expect("Failed to read ref cell contents");
+        assert_eq!(result, Value::Str("a".to_string()));
+    }
+
+    #[test]
+    fn test_ref_cell_shared_with_closure() {
+        let source = r#"
+            let hits = ref 0 in
+            let record = fun x -> hits := !hits + x in
+            let d = List.map record [1; 2; 3] in
+            !hits
+        "#;
+        let result = run_source(source).expect("Failed to share ref cell");
+        assert_eq!(result, Value::Int(6));
+    }
+}
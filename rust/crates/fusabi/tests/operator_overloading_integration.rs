@@ -0,0 +1,108 @@
+// Integration tests for operator overloading
+// These tests declare operator and Compare members on record and union
+// types and use them through the arithmetic and ordering operators
+
+use fusabi::{run_source, run_source_checked};
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod record_operator_tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_addition_and_scaling() {
+        let source = r#"
+type Vector2 = { X: float; Y: float }
+with
+    static member (+) (a: Vector2, b: Vector2) = { X = a.X + b.X; Y = a.Y + b.Y }
+    static member ( * ) (v: Vector2) (k: float) = { X = v.X * k; Y = v.Y * k }
+
+let v = { X = 1.0; Y = 2.0 } + { X = 3.0; Y = 4.0 } in
+let w = v * 0.5 in
+(w.X, w.Y)
+"#;
+        let result = run_source_checked(source).expect("Failed to add vectors");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![Value::Float(2.0), Value::Float(3.0)])
+        );
+    }
+
+    #[test]
+    fn test_missing_operator_is_a_runtime_error() {
+        let source = r#"
+type Vector2 = { X: float; Y: float }
+with
+    static member (+) (a: Vector2, b: Vector2) = { X = a.X + b.X; Y = a.Y + b.Y }
+
+{ X = 1.0; Y = 2.0 } - { X = 3.0; Y = 4.0 }
+"#;
+        let err = run_source(source).unwrap_err();
+        assert!(err.to_string().contains("subtraction"), "{}", err);
+    }
+
+    #[test]
+    fn test_operator_member_raising_is_catchable() {
+        let source = r#"
+type Money = { Cents: int; Currency: string }
+with
+    static member (+) (a: Money, b: Money) =
+        if a.Currency = b.Currency then { Cents = a.Cents + b.Cents; Currency = a.Currency }
+        else failwith "currency mismatch"
+
+try
+    let total = { Cents = 100; Currency = "EUR" } + { Cents = 5; Currency = "USD" } in
+    "added"
+with
+| Failure msg -> msg
+"#;
+        let result = run_source(source).expect("Failed to catch operator failure");
+        assert_eq!(result, Value::Str("currency mismatch".to_string()));
+    }
+}
+
+#[cfg(test)]
+mod union_operator_tests {
+    use super::*;
+
+    #[test]
+    fn test_generic_sum_over_custom_type() {
+        let source = r#"
+type Money = Money of int
+with
+    static member (+) (a: Money, b: Money) =
+        match (a, b) with
+        | (Money x, Money y) -> Money (x + y)
+
+let sum zero xs = List.fold (fun acc x -> acc + x) zero xs in
+(sum 0 [1; 2; 3], sum 0.5 [1.0; 2.0], sum (Money 0) [Money 5; Money 7])
+"#;
+        let result = run_source(source).expect("Failed to sum");
+        assert_eq!(result.to_string(), "(6, 3.5, Money(12))");
+    }
+
+    #[test]
+    fn test_compare_member_drives_ordering() {
+        let source = r#"
+type Version = Version of int * int
+with
+    static member Compare (a, b) =
+        match (a, b) with
+        | (Version (ma, mi), Version (mb, ni)) -> if ma = mb then mi - ni else ma - mb
+
+let v1 = Version (1, 4) in
+let v2 = Version (2, 0) in
+(v1 < v2, v2 <= v1, v2 > v1, v1 >= Version (1, 4))
+"#;
+        let result = run_source(source).expect("Failed to compare versions");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![
+                Value::Bool(true),
+                Value::Bool(false),
+                Value::Bool(true),
+                Value::Bool(true),
+            ])
+        );
+    }
+}
@@ -0,0 +1,113 @@
+// Integration tests for the modulo, bitwise, composition and backward pipe operators
+// These tests run the operators through the full pipeline, including constant folding
+
+use fusabi::run_source;
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod arithmetic_operator_tests {
+    use super::*;
+
+    #[test]
+    fn test_modulo() {
+        let result = run_source("(17 % 5, -7 % 3, 7.5 % 2.0)").expect("Failed to run modulo");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![Value::Int(2), Value::Int(-1), Value::Float(1.5)])
+        );
+    }
+
+    #[test]
+    fn test_modulo_by_zero_is_an_error() {
+        assert!(run_source("let n = 0 in 5 % n").is_err());
+        assert!(run_source("5 % 0").is_err());
+    }
+
+    #[test]
+    fn test_bitwise_operators() {
+        let source = "(12 &&& 10, 12 ||| 10, 12 ^^^ 10, 1 <<< 10, -16 >>> 2)";
+        let result = run_source(source).expect("Failed to run bitwise operators");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![
+                Value::Int(8),
+                Value::Int(14),
+                Value::Int(6),
+                Value::Int(1024),
+                Value::Int(-4)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_bitwise_operators_on_variables() {
+        let source = r#"
+            let flags = 0 in
+            let withRead = flags ||| 1 <<< 2 in
+            let hasRead = withRead &&& 4 <> 0 in
+            (withRead, hasRead)
+        "#;
+        let result = run_source(source).expect("Failed to run bitwise flags");
+        assert_eq!(result, Value::Tuple(vec![Value::Int(4), Value::Bool(true)]));
+    }
+
+    #[test]
+    fn test_bitwise_operators_reject_non_ints() {
+        assert!(run_source("true &&& false").is_err());
+    }
+}
+
+#[cfg(test)]
+mod function_operator_tests {
+    use super::*;
+
+    #[test]
+    fn test_forward_and_backward_composition() {
+        let source = r#"
+            let inc = fun x -> x + 1 in
+            let double = fun x -> x * 2 in
+            ((inc >> double) 3, (inc << double) 3)
+        "#;
+        let result = run_source(source).expect("Failed to run composition");
+        assert_eq!(result, Value::Tuple(vec![Value::Int(8), Value::Int(7)]));
+    }
+
+    #[test]
+    fn test_composition_in_pipelines() {
+        let source = r#"
+            let parse = fun s -> String.length s in
+            let isLong = fun n -> n > 3 in
+            ["a"; "abcd"; "abcdef"] |> List.map (parse >> isLong)
+        "#;
+        let result = run_source(source).expect("Failed to compose in a pipeline");
+        assert_eq!(
+            result,
+            Value::vec_to_cons(vec![
+                Value::Bool(false),
+                Value::Bool(true),
+                Value::Bool(true)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_composition_chains() {
+        let source = r#"
+            let add = fun n -> fun x -> x + n in
+            let pipeline = add 1 >> add 10 >> add 100 in
+            pipeline 0
+        "#;
+        let result = run_source(source).expect("Failed to run composition chain");
+        assert_eq!(result, Value::Int(111));
+    }
+
+    #[test]
+    fn test_backward_pipe() {
+        let source = r#"
+            let double = fun x -> x * 2 in
+            double <| 3 + 4
+        "#;
+        let result = run_source(source).expect("Failed to run backward pipe");
+        assert_eq!(result, Value::Int(14));
+    }
+}
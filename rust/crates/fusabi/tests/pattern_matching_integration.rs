@@ -26,10 +26,12 @@ fn test_match_literal_int_zero() {
             MatchArm {
                 pattern: Pattern::Literal(Literal::Int(0)),
                 body: Box::new(Expr::Lit(Literal::Str("zero".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Str("other".into()))),
+                guard: None,
             },
         ],
     };
@@ -47,10 +49,12 @@ fn test_match_literal_int_nonzero() {
             MatchArm {
                 pattern: Pattern::Literal(Literal::Int(0)),
                 body: Box::new(Expr::Lit(Literal::Str("zero".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Str("other".into()))),
+                guard: None,
             },
         ],
     };
@@ -68,10 +72,12 @@ fn test_match_literal_bool_true() {
             MatchArm {
                 pattern: Pattern::Literal(Literal::Bool(true)),
                 body: Box::new(Expr::Lit(Literal::Int(1))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Literal(Literal::Bool(false)),
                 body: Box::new(Expr::Lit(Literal::Int(0))),
+                guard: None,
             },
         ],
     };
@@ -89,10 +95,12 @@ fn test_match_literal_bool_false() {
             MatchArm {
                 pattern: Pattern::Literal(Literal::Bool(true)),
                 body: Box::new(Expr::Lit(Literal::Int(1))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Literal(Literal::Bool(false)),
                 body: Box::new(Expr::Lit(Literal::Int(0))),
+                guard: None,
             },
         ],
     };
@@ -110,10 +118,12 @@ fn test_match_literal_string_match() {
             MatchArm {
                 pattern: Pattern::Literal(Literal::Str("hello".into())),
                 body: Box::new(Expr::Lit(Literal::Int(1))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Int(0))),
+                guard: None,
             },
         ],
     };
@@ -131,10 +141,12 @@ fn test_match_literal_string_nomatch() {
             MatchArm {
                 pattern: Pattern::Literal(Literal::Str("hello".into())),
                 body: Box::new(Expr::Lit(Literal::Int(1))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Int(0))),
+                guard: None,
             },
         ],
     };
@@ -152,18 +164,22 @@ fn test_match_multiple_literal_arms() {
             MatchArm {
                 pattern: Pattern::Literal(Literal::Int(0)),
                 body: Box::new(Expr::Lit(Literal::Str("zero".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Literal(Literal::Int(1)),
                 body: Box::new(Expr::Lit(Literal::Str("one".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Literal(Literal::Int(2)),
                 body: Box::new(Expr::Lit(Literal::Str("two".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Str("many".into()))),
+                guard: None,
             },
         ],
     };
@@ -181,14 +197,17 @@ fn test_match_literal_first_match_wins() {
             MatchArm {
                 pattern: Pattern::Literal(Literal::Int(0)),
                 body: Box::new(Expr::Lit(Literal::Str("first".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Literal(Literal::Int(0)),
                 body: Box::new(Expr::Lit(Literal::Str("second".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Str("other".into()))),
+                guard: None,
             },
         ],
     };
@@ -210,10 +229,12 @@ fn test_match_literal_with_expression_body() {
                     left: Box::new(Expr::Lit(Literal::Int(10))),
                     right: Box::new(Expr::Lit(Literal::Int(5))),
                 }),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Int(0))),
+                guard: None,
             },
         ],
     };
@@ -231,10 +252,12 @@ fn test_match_literal_negative_int() {
             MatchArm {
                 pattern: Pattern::Literal(Literal::Int(-5)),
                 body: Box::new(Expr::Lit(Literal::Str("negative five".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Str("other".into()))),
+                guard: None,
             },
         ],
     };
@@ -255,6 +278,7 @@ fn test_match_variable_binding_simple() {
         arms: vec![MatchArm {
             pattern: Pattern::Var("x".into()),
             body: Box::new(Expr::Var("x".into())),
+            guard: None,
         }],
     };
 
@@ -274,6 +298,7 @@ fn test_match_variable_binding_with_operation() {
                 left: Box::new(Expr::Var("x".into())),
                 right: Box::new(Expr::Lit(Literal::Int(2))),
             }),
+            guard: None,
         }],
     };
 
@@ -290,6 +315,7 @@ fn test_match_variable_binding_second_arm() {
             MatchArm {
                 pattern: Pattern::Literal(Literal::Int(0)),
                 body: Box::new(Expr::Lit(Literal::Int(0))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Var("n".into()),
@@ -298,6 +324,7 @@ fn test_match_variable_binding_second_arm() {
                     left: Box::new(Expr::Var("n".into())),
                     right: Box::new(Expr::Lit(Literal::Int(1))),
                 }),
+                guard: None,
             },
         ],
     };
@@ -314,6 +341,7 @@ fn test_match_variable_binding_string() {
         arms: vec![MatchArm {
             pattern: Pattern::Var("s".into()),
             body: Box::new(Expr::Var("s".into())),
+            guard: None,
         }],
     };
 
@@ -329,6 +357,7 @@ fn test_match_variable_binding_bool() {
         arms: vec![MatchArm {
             pattern: Pattern::Var("b".into()),
             body: Box::new(Expr::Var("b".into())),
+            guard: None,
         }],
     };
 
@@ -348,6 +377,7 @@ fn test_match_wildcard_simple() {
         arms: vec![MatchArm {
             pattern: Pattern::Wildcard,
             body: Box::new(Expr::Lit(Literal::Str("anything".into()))),
+            guard: None,
         }],
     };
 
@@ -364,14 +394,17 @@ fn test_match_wildcard_as_default() {
             MatchArm {
                 pattern: Pattern::Literal(Literal::Int(0)),
                 body: Box::new(Expr::Lit(Literal::Str("zero".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Literal(Literal::Int(1)),
                 body: Box::new(Expr::Lit(Literal::Str("one".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Str("other".into()))),
+                guard: None,
             },
         ],
     };
@@ -388,6 +421,7 @@ fn test_match_wildcard_catches_all() {
         arms: vec![MatchArm {
             pattern: Pattern::Wildcard,
             body: Box::new(Expr::Lit(Literal::Int(42))),
+            guard: None,
         }],
     };
 
@@ -414,10 +448,12 @@ fn test_match_tuple_literal_pair() {
                     Pattern::Literal(Literal::Int(0)),
                 ]),
                 body: Box::new(Expr::Lit(Literal::Str("origin".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Str("other".into()))),
+                guard: None,
             },
         ],
     };
@@ -443,10 +479,12 @@ fn test_match_tuple_literal_nomatch() {
                     Pattern::Literal(Literal::Int(0)),
                 ]),
                 body: Box::new(Expr::Lit(Literal::Str("origin".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Str("other".into()))),
+                guard: None,
             },
         ],
     };
@@ -467,10 +505,12 @@ fn test_match_tuple_with_wildcard() {
             MatchArm {
                 pattern: Pattern::Tuple(vec![Pattern::Literal(Literal::Int(0)), Pattern::Wildcard]),
                 body: Box::new(Expr::Lit(Literal::Str("y-axis".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Str("other".into()))),
+                guard: None,
             },
         ],
     };
@@ -494,6 +534,7 @@ fn test_match_tuple_with_variable_binding() {
                 left: Box::new(Expr::Var("x".into())),
                 right: Box::new(Expr::Var("y".into())),
             }),
+            guard: None,
         }],
     };
 
@@ -516,6 +557,7 @@ fn test_match_tuple_mixed_patterns() {
                     Pattern::Var("y".into()),
                 ]),
                 body: Box::new(Expr::Var("y".into())),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Tuple(vec![
@@ -523,6 +565,7 @@ fn test_match_tuple_mixed_patterns() {
                     Pattern::Literal(Literal::Int(0)),
                 ]),
                 body: Box::new(Expr::Var("x".into())),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Tuple(vec![Pattern::Var("x".into()), Pattern::Var("y".into())]),
@@ -531,6 +574,7 @@ fn test_match_tuple_mixed_patterns() {
                     left: Box::new(Expr::Var("x".into())),
                     right: Box::new(Expr::Var("y".into())),
                 }),
+                guard: None,
             },
         ],
     };
@@ -563,6 +607,7 @@ fn test_match_tuple_triple() {
                 }),
                 right: Box::new(Expr::Var("c".into())),
             }),
+            guard: None,
         }],
     };
 
@@ -578,6 +623,7 @@ fn test_match_tuple_single_element() {
         arms: vec![MatchArm {
             pattern: Pattern::Tuple(vec![Pattern::Var("x".into())]),
             body: Box::new(Expr::Var("x".into())),
+            guard: None,
         }],
     };
 
@@ -593,6 +639,7 @@ fn test_match_tuple_empty() {
         arms: vec![MatchArm {
             pattern: Pattern::Tuple(vec![]),
             body: Box::new(Expr::Lit(Literal::Int(42))),
+            guard: None,
         }],
     };
 
@@ -611,6 +658,7 @@ fn test_match_tuple_wildcard_entire() {
         arms: vec![MatchArm {
             pattern: Pattern::Wildcard,
             body: Box::new(Expr::Lit(Literal::Int(99))),
+            guard: None,
         }],
     };
 
@@ -629,6 +677,7 @@ fn test_match_tuple_variable_entire() {
         arms: vec![MatchArm {
             pattern: Pattern::Var("t".into()),
             body: Box::new(Expr::Var("t".into())),
+            guard: None,
         }],
     };
 
@@ -645,16 +694,19 @@ fn test_match_in_let_value() {
     // let x = (match 1 with | 1 -> 10 | _ -> 0) in x + 5
     let expr = Expr::Let {
         name: "x".into(),
+        ty: None,
         value: Box::new(Expr::Match {
             scrutinee: Box::new(Expr::Lit(Literal::Int(1))),
             arms: vec![
                 MatchArm {
                     pattern: Pattern::Literal(Literal::Int(1)),
                     body: Box::new(Expr::Lit(Literal::Int(10))),
+                    guard: None,
                 },
                 MatchArm {
                     pattern: Pattern::Wildcard,
                     body: Box::new(Expr::Lit(Literal::Int(0))),
+                    guard: None,
                 },
             ],
         }),
@@ -674,6 +726,7 @@ fn test_match_in_let_body() {
     // let n = 42 in (match n with | 42 -> "yes" | _ -> "no")
     let expr = Expr::Let {
         name: "n".into(),
+        ty: None,
         value: Box::new(Expr::Lit(Literal::Int(42))),
         body: Box::new(Expr::Match {
             scrutinee: Box::new(Expr::Var("n".into())),
@@ -681,10 +734,12 @@ fn test_match_in_let_body() {
                 MatchArm {
                     pattern: Pattern::Literal(Literal::Int(42)),
                     body: Box::new(Expr::Lit(Literal::Str("yes".into()))),
+                    guard: None,
                 },
                 MatchArm {
                     pattern: Pattern::Wildcard,
                     body: Box::new(Expr::Lit(Literal::Str("no".into()))),
+                    guard: None,
                 },
             ],
         }),
@@ -699,6 +754,7 @@ fn test_match_nested_let() {
     // let x = 10 in (match x with | n -> let y = n * 2 in y + 1)
     let expr = Expr::Let {
         name: "x".into(),
+        ty: None,
         value: Box::new(Expr::Lit(Literal::Int(10))),
         body: Box::new(Expr::Match {
             scrutinee: Box::new(Expr::Var("x".into())),
@@ -706,6 +762,7 @@ fn test_match_nested_let() {
                 pattern: Pattern::Var("n".into()),
                 body: Box::new(Expr::Let {
                     name: "y".into(),
+                    ty: None,
                     value: Box::new(Expr::BinOp {
                         op: BinOp::Mul,
                         left: Box::new(Expr::Var("n".into())),
@@ -717,6 +774,7 @@ fn test_match_nested_let() {
                         right: Box::new(Expr::Lit(Literal::Int(1))),
                     }),
                 }),
+                guard: None,
             }],
         }),
     };
@@ -741,14 +799,17 @@ fn test_match_classify_number() {
             MatchArm {
                 pattern: Pattern::Literal(Literal::Int(0)),
                 body: Box::new(Expr::Lit(Literal::Str("zero".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Literal(Literal::Int(1)),
                 body: Box::new(Expr::Lit(Literal::Str("one".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Str("many".into()))),
+                guard: None,
             },
         ],
     };
@@ -778,6 +839,7 @@ fn test_match_abs_value() {
                 }),
                 else_branch: Box::new(Expr::Var("n".into())),
             }),
+            guard: None,
         }],
     };
 
@@ -796,6 +858,7 @@ fn test_match_tuple_destructure_function() {
         arms: vec![MatchArm {
             pattern: Pattern::Tuple(vec![Pattern::Var("x".into()), Pattern::Wildcard]),
             body: Box::new(Expr::Var("x".into())),
+            guard: None,
         }],
     };
 
@@ -817,6 +880,7 @@ fn test_match_swap_function() {
                 Expr::Var("y".into()),
                 Expr::Var("x".into()),
             ])),
+            guard: None,
         }],
     };
 
@@ -824,6 +888,96 @@ fn test_match_swap_function() {
     assert_eq!(result, Value::Tuple(vec![Value::Int(2), Value::Int(1)]));
 }
 
+// ============================================================================
+// SECTION 6b: Guards
+// ============================================================================
+
+#[test]
+fn test_match_guard_selects_arm() {
+    // match (1, 2) with | (a, b) when a > b -> a | (a, b) -> b
+    let pair = || Pattern::Tuple(vec![Pattern::Var("a".into()), Pattern::Var("b".into())]);
+    let expr = Expr::Match {
+        scrutinee: Box::new(Expr::Tuple(vec![
+            Expr::Lit(Literal::Int(1)),
+            Expr::Lit(Literal::Int(2)),
+        ])),
+        arms: vec![
+            MatchArm::with_guard(
+                pair(),
+                Expr::BinOp {
+                    op: BinOp::Gt,
+                    left: Box::new(Expr::Var("a".into())),
+                    right: Box::new(Expr::Var("b".into())),
+                },
+                Expr::Var("a".into()),
+            ),
+            MatchArm::new(pair(), Expr::Var("b".into())),
+        ],
+    };
+
+    let result = run_expr(&expr).unwrap();
+    assert_eq!(result, Value::Int(2));
+}
+
+#[test]
+fn test_match_guard_fall_through_keeps_stack() {
+    // 100 + (match 7 with | n when n < 0 -> 0 | n when n > 5 -> n * 2 | _ -> 1)
+    let guarded = |op, limit, body| {
+        MatchArm::with_guard(
+            Pattern::Var("n".into()),
+            Expr::BinOp {
+                op,
+                left: Box::new(Expr::Var("n".into())),
+                right: Box::new(Expr::Lit(Literal::Int(limit))),
+            },
+            body,
+        )
+    };
+    let expr = Expr::BinOp {
+        op: BinOp::Add,
+        left: Box::new(Expr::Lit(Literal::Int(100))),
+        right: Box::new(Expr::Match {
+            scrutinee: Box::new(Expr::Lit(Literal::Int(7))),
+            arms: vec![
+                guarded(BinOp::Lt, 0, Expr::Lit(Literal::Int(0))),
+                guarded(
+                    BinOp::Gt,
+                    5,
+                    Expr::BinOp {
+                        op: BinOp::Mul,
+                        left: Box::new(Expr::Var("n".into())),
+                        right: Box::new(Expr::Lit(Literal::Int(2))),
+                    },
+                ),
+                MatchArm::new(Pattern::Wildcard, Expr::Lit(Literal::Int(1))),
+            ],
+        }),
+    };
+
+    let result = run_expr(&expr).unwrap();
+    assert_eq!(result, Value::Int(114));
+}
+
+#[test]
+fn test_match_all_guards_fail_is_runtime_error() {
+    // match 3 with | n when n > 5 -> n
+    let expr = Expr::Match {
+        scrutinee: Box::new(Expr::Lit(Literal::Int(3))),
+        arms: vec![MatchArm::with_guard(
+            Pattern::Var("n".into()),
+            Expr::BinOp {
+                op: BinOp::Gt,
+                left: Box::new(Expr::Var("n".into())),
+                right: Box::new(Expr::Lit(Literal::Int(5))),
+            },
+            Expr::Var("n".into()),
+        )],
+    };
+
+    let err = run_expr(&expr).unwrap_err();
+    assert!(err.to_string().contains("Match failure"));
+}
+
 // ============================================================================
 // SECTION 7: Complex Patterns (5+ tests)
 // ============================================================================
@@ -847,6 +1001,7 @@ fn test_match_point_classification() {
                     Pattern::Literal(Literal::Int(0)),
                 ]),
                 body: Box::new(Expr::Lit(Literal::Str("origin".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Tuple(vec![
@@ -854,6 +1009,7 @@ fn test_match_point_classification() {
                     Pattern::Var("y".into()),
                 ]),
                 body: Box::new(Expr::Lit(Literal::Str("y-axis".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Tuple(vec![
@@ -861,10 +1017,12 @@ fn test_match_point_classification() {
                     Pattern::Literal(Literal::Int(0)),
                 ]),
                 body: Box::new(Expr::Lit(Literal::Str("x-axis".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Tuple(vec![Pattern::Var("x".into()), Pattern::Var("y".into())]),
                 body: Box::new(Expr::Lit(Literal::Str("quadrant".into()))),
+                guard: None,
             },
         ],
     };
@@ -889,6 +1047,7 @@ fn test_match_point_y_axis() {
                     Pattern::Literal(Literal::Int(0)),
                 ]),
                 body: Box::new(Expr::Lit(Literal::Str("origin".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Tuple(vec![
@@ -896,6 +1055,7 @@ fn test_match_point_y_axis() {
                     Pattern::Var("y".into()),
                 ]),
                 body: Box::new(Expr::Lit(Literal::Str("y-axis".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Tuple(vec![
@@ -903,10 +1063,12 @@ fn test_match_point_y_axis() {
                     Pattern::Literal(Literal::Int(0)),
                 ]),
                 body: Box::new(Expr::Lit(Literal::Str("x-axis".into()))),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Tuple(vec![Pattern::Var("x".into()), Pattern::Var("y".into())]),
                 body: Box::new(Expr::Lit(Literal::Str("quadrant".into()))),
+                guard: None,
             },
         ],
     };
@@ -938,6 +1100,7 @@ fn test_match_complex_calculation() {
                     right: Box::new(Expr::Var("b".into())),
                 }),
             }),
+            guard: None,
         }],
     };
 
@@ -961,6 +1124,7 @@ fn test_match_mixed_types_in_tuple() {
                 Pattern::Var("b".into()),
             ]),
             body: Box::new(Expr::Var("n".into())),
+            guard: None,
         }],
     };
 
@@ -981,6 +1145,7 @@ fn test_match_boolean_with_computation() {
                     left: Box::new(Expr::Lit(Literal::Int(1))),
                     right: Box::new(Expr::Lit(Literal::Int(1))),
                 }),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Literal(Literal::Bool(false)),
@@ -989,6 +1154,7 @@ fn test_match_boolean_with_computation() {
                     left: Box::new(Expr::Lit(Literal::Int(2))),
                     right: Box::new(Expr::Lit(Literal::Int(2))),
                 }),
+                guard: None,
             },
         ],
     };
@@ -1013,17 +1179,21 @@ fn test_match_string_with_nested_match() {
                         MatchArm {
                             pattern: Pattern::Literal(Literal::Int(1)),
                             body: Box::new(Expr::Lit(Literal::Str("ok".into()))),
+                            guard: None,
                         },
                         MatchArm {
                             pattern: Pattern::Wildcard,
                             body: Box::new(Expr::Lit(Literal::Str("fail".into()))),
+                            guard: None,
                         },
                     ],
                 }),
+                guard: None,
             },
             MatchArm {
                 pattern: Pattern::Wildcard,
                 body: Box::new(Expr::Lit(Literal::Str("no".into()))),
+                guard: None,
             },
         ],
     };
@@ -1,7 +1,7 @@
 // Integration tests for Records Layer 4: Compiler Integration
 // Tests compilation and execution of record operations
 
-use fusabi::run_source;
+use fusabi::{run_source, run_source_checked};
 use fusabi_vm::Value;
 
 // ============================================================================
@@ -438,3 +438,25 @@ fn test_record_field_shadowing() {
     let result = run_source(source).unwrap();
     assert_eq!(result, Value::Str("Wendy".to_string()));
 }
+
+#[test]
+fn test_record_literal_with_qualified_field() {
+    let source = r#"
+type Person = { name: string; age: int }
+let person = { Person.name = "Xavier"; age = 52 }
+person.age
+"#;
+    let result = run_source(source).unwrap();
+    assert_eq!(result, Value::Int(52));
+}
+
+#[test]
+fn test_record_literal_with_qualified_field_type_checked() {
+    let source = r#"
+type Person = { Name: string; Age: int }
+let person = { Person.Name = "Ann"; Age = 3 }
+person.Age
+"#;
+    let result = run_source_checked(source).unwrap();
+    assert_eq!(result, Value::Int(3));
+}
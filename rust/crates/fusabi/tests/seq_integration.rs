@@ -0,0 +1,215 @@
+// Integration tests for lazy sequences: `seq { ... }` and the Seq module
+// These tests run sequences through the full pipeline
+
+use fusabi::run_source;
+use fusabi_vm::Value;
+
+fn ints(values: &[i64]) -> Value {
+    Value::vec_to_cons(values.iter().map(|&n| Value::Int(n)).collect())
+}
+
+#[cfg(test)]
+mod seq_builder_tests {
+    use super::*;
+
+    #[test]
+    fn test_seq_block_yields_in_order() {
+        let source = "Seq.toList (seq { yield 1; yield! [2; 3]; 4 .. 6 })";
+        let result = run_source(source).expect("Failed to run seq block");
+        assert_eq!(result, ints(&[1, 2, 3, 4, 5, 6]));
+    }
+
+    #[test]
+    fn test_seq_loops_and_conditionals() {
+        let source = r#"
+            let evens = seq { for x in [1; 2; 3; 4; 5; 6] do if x > 3 then yield x * 2 } in
+            let countdown = seq { for i = 3 downto 1 do yield i } in
+            (Seq.toList evens, Seq.toList countdown)
+        "#;
+        let result = run_source(source).expect("Failed to run seq loops");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![ints(&[8, 10, 12]), ints(&[3, 2, 1])])
+        );
+    }
+
+    #[test]
+    fn test_seq_ranges_are_counted_lazily() {
+        let source = r#"
+            let counted = seq { for i in 1 .. 1000000000 do yield i * 10 } in
+            let stepped = seq { 0 .. 5 .. 1000000000 } in
+            let down = seq { for i = 1000000000 downto 1 do yield i } in
+            (Seq.toList (Seq.take 2 counted), Seq.toList (Seq.take 3 stepped), Seq.head down)
+        "#;
+        let result = run_source(source).expect("Failed to take from large ranges");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![
+                ints(&[10, 20]),
+                ints(&[0, 5, 10]),
+                Value::Int(1000000000)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_seq_for_with_tuple_pattern() {
+        let source = r#"Seq.toList (seq { for (k, v) in [(1, "a"); (2, "b")] do yield k })"#;
+        let result = run_source(source).expect("Failed to run seq with pattern");
+        assert_eq!(result, ints(&[1, 2]));
+    }
+
+    #[test]
+    fn test_recursive_seq_is_lazy() {
+        let source = r#"
+            let rec nats n = seq { yield n; yield! nats (n + 1) } in
+            Seq.toList (Seq.take 5 (nats 10))
+        "#;
+        let result = run_source(source).expect("Failed to run infinite seq");
+        assert_eq!(result, ints(&[10, 11, 12, 13, 14]));
+    }
+
+    #[test]
+    fn test_long_recursive_seq_does_not_overflow() {
+        let source = r#"
+            let rec nats n = seq { yield n; yield! nats (n + 1) } in
+            Seq.fold (fun acc x -> acc + x) 0 (Seq.take 10000 (nats 1))
+        "#;
+        let result = run_source(source).expect("Failed to fold long seq");
+        assert_eq!(result, Value::Int(50005000));
+    }
+
+    #[test]
+    fn test_seq_body_runs_on_enumeration() {
+        let source = r#"
+            let mutable runs = 0 in
+            let s = seq { let u = (runs <- runs + 1); yield runs } in
+            let before = runs in
+            let first = Seq.toList s in
+            let second = Seq.toList s in
+            (before, first, second)
+        "#;
+        let result = run_source(source).expect("Failed to run delayed seq");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![Value::Int(0), ints(&[1]), ints(&[2])])
+        );
+    }
+}
+
+#[cfg(test)]
+mod seq_module_tests {
+    use super::*;
+
+    #[test]
+    fn test_map_filter_take_over_infinite_source() {
+        let source = r#"
+            Seq.initInfinite (fun i -> i)
+            |> Seq.map (fun x -> x * x)
+            |> Seq.filter (fun x -> x > 10)
+            |> Seq.take 3
+            |> Seq.toList
+        "#;
+        let result = run_source(source).expect("Failed to run Seq pipeline");
+        assert_eq!(result, ints(&[16, 25, 36]));
+    }
+
+    #[test]
+    fn test_builder_methods_are_not_seq_members() {
+        assert!(run_source("Seq.Yield 1").is_err());
+        let result = run_source("Seq.toList (seq.Yield 1)").expect("Failed to call builder");
+        assert_eq!(result, ints(&[1]));
+    }
+
+    #[test]
+    fn test_unfold() {
+        let source = r#"
+            let fibs = Seq.unfold (fun s -> match s with (a, b) -> Some (a, (b, a + b))) (0, 1) in
+            Seq.toList (Seq.take 8 fibs)
+        "#;
+        let result = run_source(source).expect("Failed to run Seq.unfold");
+        assert_eq!(result, ints(&[0, 1, 1, 2, 3, 5, 8, 13]));
+        let source = "Seq.toList (Seq.unfold (fun n -> if n > 3 then None else Some (n, n + 1)) 1)";
+        let result = run_source(source).expect("Failed to run finite Seq.unfold");
+        assert_eq!(result, ints(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_consumers() {
+        let source = r#"
+            let s = Seq.ofList [3; 1; 4; 1; 5] in
+            (Seq.length s, Seq.head s, Seq.exists (fun x -> x = 4) s, Seq.isEmpty Seq.empty)
+        "#;
+        let result = run_source(source).expect("Failed to consume seq");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![
+                Value::Int(5),
+                Value::Int(3),
+                Value::Bool(true),
+                Value::Bool(true)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_for_loops_and_comprehensions_accept_seqs() {
+        let source = r#"
+            let mutable total = 0 in
+            let d = (for x in Seq.init 4 (fun i -> i) do total <- total + x) in
+            (total, [ for x in Seq.skip 2 (Seq.ofArray [| 1; 2; 3 |]) -> x * 10 ])
+        "#;
+        let result = run_source(source).expect("Failed to iterate seq");
+        assert_eq!(result, Value::Tuple(vec![Value::Int(6), ints(&[30])]));
+    }
+
+    #[test]
+    fn test_for_loop_over_infinite_seq_stops_at_break() {
+        let source = r#"
+            let mutable total = 0 in
+            let d = (for x in Seq.initInfinite (fun i -> i) do
+                if x > 3 then break else total <- total + x) in
+            total
+        "#;
+        let result = run_source(source).expect("Failed to break out of infinite seq");
+        assert_eq!(result, Value::Int(6));
+    }
+
+    #[test]
+    fn test_for_loop_over_file_lines_stops_at_break() {
+        let path = std::env::temp_dir().join("fusabi_seq_for_file_lines_test.log");
+        std::fs::write(&path, "INFO start\nERROR disk full\nINFO retry\n").unwrap();
+        let source = format!(
+            r#"
+            let mutable before = 0 in
+            let d = (for line in File.lines "{}" do
+                if String.startsWith "ERROR" line then break else before <- before + 1) in
+            before
+            "#,
+            path.display()
+        );
+        let result = run_source(&source).expect("Failed to loop over file lines");
+        std::fs::remove_file(&path).ok();
+        assert_eq!(result, Value::Int(1));
+    }
+
+    #[test]
+    fn test_file_lines_reads_lazily() {
+        let path = std::env::temp_dir().join("fusabi_seq_file_lines_test.log");
+        std::fs::write(
+            &path,
+            "INFO start\nERROR disk full\nINFO retry\nERROR again\n",
+        )
+        .unwrap();
+        let source = format!(
+            r#"File.lines "{}" |> Seq.filter (fun l -> String.startsWith "ERROR" l) |> Seq.take 1 |> Seq.toList"#,
+            path.display()
+        );
+        let result = run_source(&source).expect("Failed to read file lines");
+        std::fs::remove_file(&path).ok();
+        assert_eq!(
+            result,
+            Value::vec_to_cons(vec![Value::Str("ERROR disk full".to_string())])
+        );
+    }
+}
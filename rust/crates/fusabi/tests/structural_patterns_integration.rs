@@ -0,0 +1,142 @@
+// Integration tests for list, array, record, cons, or- and as-patterns
+// These tests run match expressions through the full pipeline
+
+use fusabi::run_source;
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod list_pattern_tests {
+    use super::*;
+
+    #[test]
+    fn test_cons_pattern_head() {
+        let source = "match [1; 2; 3] with | [] -> 0 | h :: _ -> h";
+        let result = run_source(source).expect("Failed to match cons pattern");
+        assert_eq!(result, Value::Int(1));
+    }
+
+    #[test]
+    fn test_cons_pattern_binds_tail() {
+        let source = "match [1; 2; 3] with | _ :: t -> t | [] -> []";
+        let result = run_source(source).expect("Failed to bind list tail");
+        assert_eq!(
+            result,
+            Value::vec_to_cons(vec![Value::Int(2), Value::Int(3)])
+        );
+    }
+
+    #[test]
+    fn test_empty_list_pattern() {
+        let source = "match [] with | [] -> \"empty\" | _ :: _ -> \"non-empty\"";
+        let result = run_source(source).expect("Failed to match empty list");
+        assert_eq!(result, Value::Str("empty".to_string()));
+    }
+
+    #[test]
+    fn test_fixed_length_list_pattern() {
+        let source = "match [1; 2] with | [a; b] -> a + b | _ -> 0";
+        let result = run_source(source).expect("Failed to match list pattern");
+        assert_eq!(result, Value::Int(3));
+    }
+
+    #[test]
+    fn test_fixed_length_list_pattern_rejects_longer_list() {
+        let source = "match [1; 2; 3] with | [a; b] -> a + b | _ -> 99";
+        let result = run_source(source).expect("Failed to fall through list pattern");
+        assert_eq!(result, Value::Int(99));
+    }
+
+    #[test]
+    fn test_nested_cons_pattern() {
+        let source = "match [1; 2; 3] with | a :: b :: _ -> a * 10 + b | _ -> 0";
+        let result = run_source(source).expect("Failed to match nested cons");
+        assert_eq!(result, Value::Int(12));
+    }
+}
+
+#[cfg(test)]
+mod array_and_record_pattern_tests {
+    use super::*;
+
+    #[test]
+    fn test_array_pattern() {
+        let source = "match [|1; 2|] with | [| x; y |] -> x * 10 + y | _ -> 0";
+        let result = run_source(source).expect("Failed to match array pattern");
+        assert_eq!(result, Value::Int(12));
+    }
+
+    #[test]
+    fn test_array_pattern_length_mismatch() {
+        let source = "match [|1; 2; 3|] with | [| _; _ |] -> 2 | [||] -> 0 | _ -> 3";
+        let result = run_source(source).expect("Failed to fall through array pattern");
+        assert_eq!(result, Value::Int(3));
+    }
+
+    #[test]
+    fn test_record_pattern_binds_field() {
+        let source = r#"match { Name = "Ada"; Age = 36 } with | { Name = n } -> n"#;
+        let result = run_source(source).expect("Failed to match record pattern");
+        assert_eq!(result, Value::Str("Ada".to_string()));
+    }
+
+    #[test]
+    fn test_record_pattern_with_literal_field() {
+        let source = r#"
+            match { Name = "Ada"; Age = 36 } with
+            | { Age = 0 } -> "newborn"
+            | { Name = n; Age = a } -> n
+        "#;
+        let result = run_source(source).expect("Failed to test record field");
+        assert_eq!(result, Value::Str("Ada".to_string()));
+    }
+}
+
+#[cfg(test)]
+mod or_and_as_pattern_tests {
+    use super::*;
+
+    #[test]
+    fn test_or_pattern_literals() {
+        let source = "match 2 with | 1 | 2 -> \"small\" | _ -> \"big\"";
+        let result = run_source(source).expect("Failed to match or-pattern");
+        assert_eq!(result, Value::Str("small".to_string()));
+    }
+
+    #[test]
+    fn test_or_pattern_binds_from_either_alternative() {
+        let source = "match (0, Some 5) with | (n, None) | (_, Some n) -> n";
+        let result = run_source(source).expect("Failed to bind through or-pattern");
+        assert_eq!(result, Value::Int(5));
+    }
+
+    #[test]
+    fn test_or_pattern_must_bind_same_variables() {
+        let source = "match (1, 2) with | (a, 1) | (1, b) -> 0 | _ -> 1";
+        let err = run_source(source).unwrap_err();
+        assert!(err.to_string().contains("must bind the same variables"));
+    }
+
+    #[test]
+    fn test_as_pattern_binds_whole_value() {
+        let source = "match [5; 6] with | (h :: _ as all) -> all | [] -> []";
+        let result = run_source(source).expect("Failed to match as-pattern");
+        assert_eq!(
+            result,
+            Value::vec_to_cons(vec![Value::Int(5), Value::Int(6)])
+        );
+    }
+
+    #[test]
+    fn test_nested_tuple_literal_is_tested() {
+        let source = "match (3, 4) with | (0, y) -> y | (x, y) -> x * y";
+        let result = run_source(source).expect("Failed to test nested literal");
+        assert_eq!(result, Value::Int(12));
+    }
+
+    #[test]
+    fn test_refutable_last_arm_raises_match_failure() {
+        let source = "match [1] with | [] -> 0 | [a; b] -> a + b";
+        let err = run_source(source).unwrap_err();
+        assert!(err.to_string().contains("Match failure"));
+    }
+}
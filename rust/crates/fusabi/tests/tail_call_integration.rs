@@ -0,0 +1,47 @@
+// Integration tests for tail calls
+// Calls in tail position reuse the caller's frame, so these loops run in
+// constant stack space however many times they recurse
+
+use fusabi::run_source;
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod tail_call_tests {
+    use super::*;
+
+    #[test]
+    fn test_accumulator_loop() {
+        let source =
+            "let rec loop acc n = if n = 0 then acc else loop (acc + n) (n - 1)\nloop 0 100000";
+        let result = run_source(source).expect("Failed to run loop");
+        assert_eq!(result, Value::Int(5000050000));
+    }
+
+    #[test]
+    fn test_tail_call_from_match_arm() {
+        let source = r#"
+let rec last xs =
+    match xs with
+    | [] -> 0
+    | [x] -> x
+    | _ :: rest -> last rest
+last [1 .. 1000]
+"#;
+        let result = run_source(source).expect("Failed to walk list");
+        assert_eq!(result, Value::Int(1000));
+    }
+
+    #[test]
+    fn test_mutual_recursion() {
+        let source = r#"
+let rec isEven n = if n = 0 then true else isOdd (n - 1)
+and isOdd n = if n = 0 then false else isEven (n - 1)
+(isEven 100000, isOdd 7)
+"#;
+        let result = run_source(source).expect("Failed to run mutual recursion");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![Value::Bool(true), Value::Bool(true)])
+        );
+    }
+}
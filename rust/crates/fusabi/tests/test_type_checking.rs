@@ -3,7 +3,7 @@
 //! These tests validate that the compiler integration with type checking works correctly
 //! and maintains backward compatibility with existing code.
 
-use fusabi::{run_source, run_source_checked, run_source_with_options, RunOptions};
+use fusabi::{run_source, run_source_checked, run_source_with_options, Engine, RunOptions};
 use fusabi_vm::Value;
 
 // ============================================================================
@@ -145,6 +145,9 @@ fn test_run_with_options_type_checking_disabled() {
         enable_type_checking: false,
         verbose: false,
         strict_mode: false,
+        engine: None,
+        report_warnings: false,
+        limits: None,
     };
     let result = run_source_with_options("5 + 10", options);
     assert!(result.is_ok());
@@ -157,6 +160,9 @@ fn test_run_with_options_type_checking_enabled() {
         enable_type_checking: true,
         verbose: false,
         strict_mode: false,
+        engine: None,
+        report_warnings: false,
+        limits: None,
     };
     let result = run_source_with_options("5 + 10", options);
     assert!(result.is_ok());
@@ -169,12 +175,44 @@ fn test_run_with_options_verbose() {
         enable_type_checking: true,
         verbose: true, // This will print to stdout during test
         strict_mode: false,
+        engine: None,
+        report_warnings: false,
+        limits: None,
     };
     let result = run_source_with_options("42", options);
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), Value::Int(42));
 }
 
+#[test]
+fn test_run_with_options_strict_mode_rejects_incomplete_match() {
+    let source = "match true with | true -> 1";
+    assert_eq!(run_source(source).unwrap(), Value::Int(1));
+
+    let options = RunOptions {
+        strict_mode: true,
+        ..Default::default()
+    };
+    let err = run_source_with_options(source, options).unwrap_err();
+    assert!(err.to_string().contains("false not covered"));
+}
+
+#[test]
+fn test_engine_exposes_match_warnings() {
+    let mut engine = Engine::new();
+    let result = engine.eval("match true with | true -> 1").unwrap();
+    assert_eq!(result, Value::Int(1));
+    assert_eq!(engine.warnings().len(), 1);
+    assert!(engine.warnings()[0]
+        .to_string()
+        .contains("false not covered"));
+
+    engine
+        .eval("match true with | true -> 1 | false -> 0")
+        .unwrap();
+    assert!(engine.warnings().is_empty());
+}
+
 // ============================================================================
 // Error Handling Tests
 // ============================================================================
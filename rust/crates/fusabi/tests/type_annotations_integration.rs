@@ -0,0 +1,79 @@
+// Integration tests for type annotations on bindings, parameters and results
+// Annotated programs must run exactly like their unannotated counterparts, and
+// type checking rejects arguments that contradict an annotation
+
+use fusabi::{run_source, run_source_checked};
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod type_annotation_tests {
+    use super::*;
+
+    #[test]
+    fn test_annotated_function_parameters_and_result() {
+        let source = r#"
+let add (x: int) (y: int) : int = x + y
+let greeting: string = "hi"
+add 40 2
+"#;
+        let result = run_source(source).expect("Failed to run annotated function");
+        assert_eq!(result, Value::Int(42));
+    }
+
+    #[test]
+    fn test_annotated_recursive_function() {
+        let source = r#"
+let rec fact (n: int) : int = if n <= 1 then 1 else n * fact (n - 1)
+fact 5
+"#;
+        let result = run_source(source).expect("Failed to run annotated recursion");
+        assert_eq!(result, Value::Int(120));
+    }
+
+    #[test]
+    fn test_annotated_lambda_over_record() {
+        let source = r#"
+type Config = { Name: string; Port: int }
+let port = fun (c: Config) -> c.Port
+let local: int = 1
+port { Name = "svc"; Port = 8080 } + local
+"#;
+        let result = run_source(source).expect("Failed to run annotated lambda");
+        assert_eq!(result, Value::Int(8081));
+    }
+
+    #[test]
+    fn test_checked_rejects_argument_of_wrong_type() {
+        let err = run_source_checked("let f (x: int) : int = x in\nf \"a\"").unwrap_err();
+        assert!(err.to_string().contains("Type error"), "got: {}", err);
+    }
+
+    #[test]
+    fn test_checked_rejects_result_of_wrong_type() {
+        let err = run_source_checked("let f (x: int) : string = x in\nf 1").unwrap_err();
+        assert!(err.to_string().contains("Type error"), "got: {}", err);
+    }
+
+    #[test]
+    fn test_checked_rejects_top_level_binding_of_wrong_type() {
+        let source = r#"
+let add (x: int) (y: int) = x + y
+let label: string = add 1 2
+label
+"#;
+        let err = run_source_checked(source).unwrap_err();
+        assert!(err.to_string().contains("Type error"), "got: {}", err);
+    }
+
+    #[test]
+    fn test_checked_runs_well_typed_program() {
+        let source = r#"
+type Config = { Name: string; Port: int }
+let port (c: Config) : int = c.Port
+let names = List.map (fun (c: Config) -> c.Name) [{ Name = "svc"; Port = 1 }]
+port { Name = "svc"; Port = 8080 } + List.length names
+"#;
+        let result = run_source_checked(source).expect("Failed to run checked program");
+        assert_eq!(result, Value::Int(8081));
+    }
+}
@@ -0,0 +1,147 @@
+// Integration tests for members on record and union types
+// These tests declare instance and static members in `with` blocks and use
+// them through method calls, properties and `Type.Member` access
+
+use fusabi::{run_source, run_source_checked};
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod union_member_tests {
+    use super::*;
+
+    const SHAPE: &str = r#"
+type Shape =
+    | Circle of float
+    | Rect of float * float
+with
+    member this.Area() =
+        match this with
+        | Circle r -> 3.0 * r * r
+        | Rect (w, h) -> w * h
+    member this.Name = match this with | Circle _ -> "circle" | Rect _ -> "rect"
+    member s.Scale(k: float) =
+        match s with
+        | Circle r -> Circle (r * k)
+        | Rect (w, h) -> Rect (w * k, h * k)
+    static member Unit = Circle 1.0
+    static member Square(side) = Rect (side, side)
+"#;
+
+    #[test]
+    fn test_methods_and_properties() {
+        let source = format!(
+            "{}\nlet s = Rect (2.0, 3.0) in\n(s.Area(), s.Name, s.Scale(2.0).Area())",
+            SHAPE
+        );
+        let result = run_source_checked(&source).expect("Failed to call members");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![
+                Value::Float(6.0),
+                Value::Str("rect".to_string()),
+                Value::Float(24.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_static_members() {
+        let source = format!(
+            "{}\nlet unit = Shape.Unit in\n(unit.Area(), Shape.Square(2.0).Name)",
+            SHAPE
+        );
+        let result = run_source(&source).expect("Failed to use static members");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![Value::Float(3.0), Value::Str("rect".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_members_over_a_list() {
+        let source = format!(
+            "{}\nList.fold (fun acc (s: Shape) -> acc + s.Area()) 0.0 [Circle 1.0; Rect (1.0, 2.0)]",
+            SHAPE
+        );
+        let result = run_source(&source).expect("Failed to fold over shapes");
+        assert_eq!(result, Value::Float(5.0));
+    }
+}
+
+#[cfg(test)]
+mod record_member_tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+type Config = { Host: string; Port: int }
+with
+    static member Default = { Host = "localhost"; Port = 80 }
+    static member Create() = { Host = "example.org"; Port = 443 }
+    static member WithPort(host, port) = { Host = host; Port = port }
+    member this.Secure = this.Port = 443
+    member c.Describe(prefix) = prefix ++ c.Host
+"#;
+
+    #[test]
+    fn test_static_property_and_methods() {
+        let source = format!(
+            "{}\nlet d = Config.Default in\n(d.Port, Config.Create().Secure, Config.WithPort(\"h\", 8080).Port)",
+            CONFIG
+        );
+        let result = run_source(&source).expect("Failed to use static members");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![Value::Int(80), Value::Bool(true), Value::Int(8080)])
+        );
+    }
+
+    #[test]
+    fn test_instance_method_on_record() {
+        let source = format!(
+            "{}\nlet c = {{ Host = \"fusabi.dev\"; Port = 443 }} in\nc.Describe(\"at \")",
+            CONFIG
+        );
+        let result = run_source(&source).expect("Failed to call record method");
+        assert_eq!(result, Value::Str("at fusabi.dev".to_string()));
+    }
+
+    #[test]
+    fn test_member_body_ends_at_next_statement() {
+        let source = r#"
+type Money = { Cents: int }
+with
+    member this.Get() = this.Cents
+
+(1, 2)
+"#;
+        let result = run_source(source).expect("Failed to run after members");
+        assert_eq!(result, Value::Tuple(vec![Value::Int(1), Value::Int(2)]));
+    }
+
+    #[test]
+    fn test_member_bodies_are_type_checked() {
+        let source = r#"
+type Point = { X: int }
+with
+    member this.Bad = this.X + "oops"
+
+let p = { X = 1 } in p.X
+"#;
+        let err = run_source_checked(source).unwrap_err();
+        assert!(err.to_string().contains("Type mismatch"), "{}", err);
+    }
+
+    #[test]
+    fn test_missing_member_is_a_type_error() {
+        let source = format!("{}\nlet d = Config.Default in d.Missing()", CONFIG);
+        let err = run_source_checked(&source).unwrap_err();
+        assert!(err.to_string().contains("no member 'Missing'"), "{}", err);
+    }
+
+    #[test]
+    fn test_missing_member_is_a_runtime_error() {
+        let source = format!("{}\nlet d = Config.Default in d.Missing", CONFIG);
+        let err = run_source(&source).unwrap_err();
+        assert!(err.to_string().contains("Missing"), "{}", err);
+    }
+}
@@ -0,0 +1,67 @@
+// Integration tests for units of measure
+// Units are checked by type inference and erased by the compiler, so measured
+// numbers run as the plain ints and floats they carry
+
+use fusabi::{run_source, run_source_checked};
+use fusabi_vm::Value;
+
+#[cfg(test)]
+mod units_of_measure_tests {
+    use super::*;
+
+    const UNITS: &str = r#"
+[<Measure>] type m
+[<Measure>] type s
+[<Measure>] type Hz = 1/s
+"#;
+
+    #[test]
+    fn test_measured_arithmetic_is_erased() {
+        let source = format!(
+            "{}\nlet speed (d: float<m>) (t: float<s>) : float<m/s> = d / t\nlet v = speed 100.0<m> 4.0<s> in\n(v, v * 2.0<s>, 3<Hz> * 2<s>)",
+            UNITS
+        );
+        let result = run_source_checked(&source).expect("Failed to compute with units");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![Value::Float(25.0), Value::Float(50.0), Value::Int(6)])
+        );
+    }
+
+    #[test]
+    fn test_generic_function_over_units() {
+        let source = format!(
+            "{}\nlet half x = x / 2.0\nlet total = List.fold (fun acc x -> acc + x) 0.0<m> [1.0<m>; 2.0<m>] in\n(half total, half 5.0)",
+            UNITS
+        );
+        let result = run_source(&source).expect("Failed to use generic functions");
+        assert_eq!(
+            result,
+            Value::Tuple(vec![Value::Float(1.5), Value::Float(2.5)])
+        );
+    }
+
+    #[test]
+    fn test_comparison_is_not_a_unit() {
+        let source = format!("{}\nlet n = 3 in if 0 < n then 1.0<m> else 0.0<m>", UNITS);
+        let result = run_source(&source).expect("Failed to compare");
+        assert_eq!(result, Value::Float(1.0));
+    }
+
+    #[test]
+    fn test_adding_different_units_is_a_type_error() {
+        let source = format!("{}\n10.0<m> + 2.0<s>", UNITS);
+        let err = run_source_checked(&source).unwrap_err();
+        assert!(err.to_string().contains("Type error"), "got: {}", err);
+    }
+
+    #[test]
+    fn test_annotation_with_wrong_unit_is_a_type_error() {
+        let source = format!(
+            "{}\nlet speed (d: float<m>) (t: float<s>) : float<m/s> = d * t\nspeed 1.0<m> 1.0<s>",
+            UNITS
+        );
+        let err = run_source_checked(&source).unwrap_err();
+        assert!(err.to_string().contains("Type error"), "got: {}", err);
+    }
+}